@@ -1062,6 +1062,7 @@ async fn apply_config_to_file_with_context(
             provider_id
         ));
     }
+    crate::secret_store::ensure_resolved(&provider.settings_config)?;
 
     // Parse provider settings_config
     let provider_config: serde_json::Value = serde_json::from_str(&provider.settings_config)
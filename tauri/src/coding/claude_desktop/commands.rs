@@ -451,6 +451,7 @@ fn apply_provider_to_sqlite_provider(db: &SqliteDbState, provider_id: &str) -> R
             provider.name
         ));
     }
+    crate::secret_store::ensure_resolved(&provider.settings_config)?;
 
     let paths = config_writer::current_platform_paths()?;
 
@@ -3193,6 +3193,7 @@ async fn apply_config_to_file_with_previous_managed_config(
             provider_id
         ));
     }
+    crate::secret_store::ensure_resolved(&provider.settings_config)?;
 
     // Parse provider settings_config
     let provider_config = parse_codex_settings_config(&provider.settings_config)?;
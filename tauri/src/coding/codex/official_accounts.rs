@@ -1998,6 +1998,7 @@ pub async fn apply_codex_official_account(
             .auth_snapshot
             .as_deref()
             .ok_or_else(|| "Official account snapshot is missing".to_string())?;
+        crate::secret_store::ensure_resolved(snapshot)?;
         let snapshot_auth = auth_json_from_snapshot(snapshot)?;
         let refreshed_snapshot = ensure_fresh_official_runtime_auth(&db, &snapshot_auth).await?;
         if refreshed_snapshot != snapshot_auth {
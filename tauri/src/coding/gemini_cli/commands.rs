@@ -1458,6 +1458,7 @@ async fn apply_config_to_file(
             provider_id
         ));
     }
+    crate::secret_store::ensure_resolved(&provider.settings_config)?;
     let mut provider_settings = parse_provider_settings_config(&provider.settings_config)?;
     normalize_provider_settings_for_category(&mut provider_settings, &provider.category);
     let provider_env = extract_env_object(&provider_settings);
@@ -1315,6 +1315,7 @@ pub async fn apply_gemini_cli_official_account(
             .auth_snapshot
             .as_deref()
             .ok_or_else(|| "Gemini official account snapshot is missing".to_string())?;
+        crate::secret_store::ensure_resolved(snapshot)?;
         let snapshot_auth = auth_json_from_snapshot(snapshot)?;
         let refreshed_auth = ensure_fresh_auth_snapshot(state.inner(), &snapshot_auth).await?;
         if refreshed_auth != snapshot_auth {
@@ -608,6 +608,7 @@ async fn apply_grok_provider_to_file_with_previous_settings(
 ) -> Result<(), String> {
     let provider = get_provider(db, provider_id)?
         .ok_or_else(|| format!("Grok provider '{provider_id}' not found"))?;
+    crate::secret_store::ensure_resolved(&provider.settings_config)?;
     let settings: Value = serde_json::from_str(&provider.settings_config)
         .map_err(|error| format!("Invalid Grok provider settings JSON: {error}"))?;
     let config_path = get_grok_config_path_async(db).await?;
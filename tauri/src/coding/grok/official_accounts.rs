@@ -285,6 +285,7 @@ pub async fn apply_grok_official_account(
 ) -> Result<(), String> {
     let account = get_account(state.db(), &account_id)?
         .ok_or_else(|| format!("Grok official account '{account_id}' not found"))?;
+    crate::secret_store::ensure_resolved(account.auth_snapshot.as_deref().unwrap_or_default())?;
     // Refresh near-expiry tokens before writing live auth.json.
     let account =
         ensure_fresh_grok_account_auth(state.db(), Some(&app), account, false).await?;
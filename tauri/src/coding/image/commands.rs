@@ -3423,6 +3423,7 @@ pub async fn image_create_job(
             channel_dto.name
         ));
     }
+    crate::secret_store::ensure_resolved(&channel_dto.api_key)?;
 
     let model = find_channel_model(&channel_dto, input.model_id.trim()).ok_or_else(|| {
         format!(
@@ -4,7 +4,9 @@
 
 use serde_json::Value;
 
-use super::types::{FavoriteMcp, McpPreferences, McpServer, McpSyncDetail, McpSyncDetailDto};
+use super::types::{
+    FavoriteMcp, McpPreferences, McpProbeResult, McpServer, McpSyncDetail, McpSyncDetailDto,
+};
 use crate::coding::db_extract_id;
 
 /// Convert database record to McpServer struct
@@ -30,6 +32,7 @@ pub fn from_db_mcp_server(value: Value) -> McpServer {
         .unwrap_or_default();
 
     let sync_details = value.get("sync_details").cloned().filter(|v| !v.is_null());
+    let probe_result = value.get("probe_result").cloned().filter(|v| !v.is_null());
 
     McpServer {
         id: db_extract_id(&value),
@@ -49,6 +52,7 @@ pub fn from_db_mcp_server(value: Value) -> McpServer {
             .unwrap_or(serde_json::json!({})),
         enabled_tools,
         sync_details,
+        probe_result,
         description: value
             .get("description")
             .and_then(|v| v.as_str())
@@ -86,6 +90,7 @@ pub fn to_clean_mcp_server_payload(server: &McpServer) -> Value {
         "server_config": server.server_config,
         "enabled_tools": server.enabled_tools,
         "sync_details": server.sync_details,
+        "probe_result": server.probe_result,
         "description": server.description,
         "user_group": server.user_group,
         "user_note": server.user_note,
@@ -136,6 +141,14 @@ pub fn parse_sync_details_dto(server: &McpServer) -> Vec<McpSyncDetailDto> {
         .collect()
 }
 
+/// Parse the stored probe result, ignoring records written by an incompatible shape
+pub fn parse_probe_result(server: &McpServer) -> Option<McpProbeResult> {
+    server
+        .probe_result
+        .clone()
+        .and_then(|value| serde_json::from_value(value).ok())
+}
+
 /// Set a sync detail in sync_details JSON
 pub fn set_sync_detail(existing: &Option<Value>, tool: &str, detail: &McpSyncDetail) -> Value {
     let mut obj = existing
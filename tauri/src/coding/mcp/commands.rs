@@ -2,9 +2,10 @@
 //!
 //! Provides the public API for the MCP feature.
 
+use futures_util::{stream, StreamExt};
 use tauri::{AppHandle, Emitter, Runtime, State};
 
-use super::adapter::parse_sync_details_dto;
+use super::adapter::{parse_probe_result, parse_sync_details_dto};
 use super::config_sync::{
     import_servers_from_path, import_servers_from_plugin_mcp_json, import_servers_from_tool_async,
     remove_server_from_tool_async, sync_server_to_tool_async,
@@ -12,11 +13,12 @@ use super::config_sync::{
 };
 use super::mcp_store;
 use super::package_version;
+use super::probe;
 use super::types::{
     now_ms, CreateMcpServerInput, FavoriteMcp, FavoriteMcpDto, FavoriteMcpInput,
     McpDiscoveredServerDto, McpImportResultDto, McpPackageVersionResolveRequest,
-    McpPackageVersionResolveResult, McpScanResultDto, McpServer, McpServerDto, McpSyncDetail,
-    McpSyncResultDto, UpdateMcpServerInput,
+    McpPackageVersionResolveResult, McpProbeResult, McpScanResultDto, McpServer, McpServerDto,
+    McpServerProbeDto, McpSyncDetail, McpSyncResultDto, UpdateMcpServerInput,
 };
 use crate::coding::tools::{
     custom_store, get_mcp_runtime_tools, is_tool_installed_with_db_async,
@@ -25,6 +27,9 @@ use crate::coding::tools::{
 };
 use crate::SqliteDbState;
 
+/// Stdio probes spawn real processes; keep batch probing from starting them all at once.
+const MCP_PROBE_CONCURRENCY: usize = 4;
+
 fn normalize_optional_text(value: Option<String>) -> Option<String> {
     value.and_then(|text| {
         let trimmed = text.trim();
@@ -54,6 +59,7 @@ pub async fn mcp_list_servers(
             server_config: s.server_config.clone(),
             enabled_tools: s.enabled_tools.clone(),
             sync_details: parse_sync_details_dto(&s),
+            probe_result: parse_probe_result(&s),
             description: s.description.clone(),
             user_group: s.user_group.clone(),
             user_note: s.user_note.clone(),
@@ -75,6 +81,60 @@ pub async fn mcp_resolve_package_versions(
     Ok(package_version::resolve_package_versions(&state, requests).await)
 }
 
+/// Probe a single MCP server over its real transport and store the result.
+/// Probing never touches tool config files, so no change events are emitted.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_probe_server(
+    state: State<'_, SqliteDbState>,
+    serverId: String,
+) -> Result<McpProbeResult, String> {
+    let server = mcp_store::get_mcp_server_by_id(&state, &serverId)
+        .await?
+        .ok_or_else(|| format!("MCP server not found: {}", serverId))?;
+    let result = probe::probe_server(&state, &server).await;
+    mcp_store::update_probe_result(&state, &serverId, &result).await?;
+    Ok(result)
+}
+
+/// Probe several MCP servers (all servers when `serverIds` is omitted).
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_probe_servers(
+    state: State<'_, SqliteDbState>,
+    serverIds: Option<Vec<String>>,
+) -> Result<Vec<McpServerProbeDto>, String> {
+    let servers = mcp_store::get_mcp_servers(&state).await?;
+    let servers: Vec<McpServer> = match serverIds {
+        Some(ids) => servers
+            .into_iter()
+            .filter(|server| ids.contains(&server.id))
+            .collect(),
+        None => servers,
+    };
+
+    let db_state = state.inner();
+    let results: Vec<(McpServer, McpProbeResult)> = stream::iter(servers)
+        .map(|server| async move {
+            let result = probe::probe_server(db_state, &server).await;
+            (server, result)
+        })
+        .buffer_unordered(MCP_PROBE_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut probes = Vec::with_capacity(results.len());
+    for (server, result) in results {
+        mcp_store::update_probe_result(&state, &server.id, &result).await?;
+        probes.push(McpServerProbeDto {
+            server_id: server.id,
+            server_name: server.name,
+            result,
+        });
+    }
+    Ok(probes)
+}
+
 /// Create a new MCP server
 /// After creation, automatically sync to all enabled tools
 #[tauri::command]
@@ -91,6 +151,7 @@ pub async fn mcp_create_server<R: Runtime>(
         server_config: input.server_config.clone(),
         enabled_tools: input.enabled_tools.clone(),
         sync_details: None,
+        probe_result: None,
         description: input.description,
         user_group: None,
         user_note: None,
@@ -142,6 +203,7 @@ pub async fn mcp_create_server<R: Runtime>(
     let _ = app.emit("mcp-changed", "window");
 
     let sync_details = parse_sync_details_dto(&created);
+    let probe_result = parse_probe_result(&created);
     Ok(McpServerDto {
         id: created.id,
         name: created.name,
@@ -149,6 +211,7 @@ pub async fn mcp_create_server<R: Runtime>(
         server_config: created.server_config,
         enabled_tools: created.enabled_tools,
         sync_details,
+        probe_result,
         description: created.description,
         user_group: created.user_group,
         user_note: created.user_note,
@@ -185,9 +248,16 @@ pub async fn mcp_update_server<R: Runtime>(
         server.name = name;
     }
     if let Some(server_type) = input.server_type {
+        if server_type != server.server_type {
+            server.probe_result = None;
+        }
         server.server_type = server_type;
     }
     if let Some(server_config) = input.server_config {
+        // A probe of the previous command/url says nothing about the new one.
+        if server_config != server.server_config {
+            server.probe_result = None;
+        }
         server.server_config = server_config;
     }
     if let Some(enabled_tools) = input.enabled_tools {
@@ -281,6 +351,7 @@ pub async fn mcp_update_server<R: Runtime>(
     let _ = app.emit("mcp-changed", "window");
 
     let sync_details = parse_sync_details_dto(&updated);
+    let probe_result = parse_probe_result(&updated);
     Ok(McpServerDto {
         id: updated.id,
         name: updated.name,
@@ -288,6 +359,7 @@ pub async fn mcp_update_server<R: Runtime>(
         server_config: updated.server_config,
         enabled_tools: updated.enabled_tools,
         sync_details,
+        probe_result,
         description: updated.description,
         user_group: updated.user_group,
         user_note: updated.user_note,
@@ -601,6 +673,7 @@ pub async fn mcp_import_from_tool<R: Runtime>(
                     server_config: c.server_config,
                     enabled_tools: vec![],
                     sync_details: None,
+                    probe_result: None,
                     description: c.description,
                     user_group: None,
                     user_note: None,
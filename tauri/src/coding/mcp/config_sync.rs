@@ -84,6 +84,80 @@ pub async fn remove_server_from_tool_async(
     remove_server_from_path(tool, &config_path, server_name)
 }
 
+/// Sync an MCP server into an explicit config file (e.g. a project-level
+/// `.mcp.json`) using the tool's format and field
+pub fn sync_server_to_config_path(
+    tool: &RuntimeTool,
+    config_path: &Path,
+    server: &McpServer,
+    enabled: bool,
+) -> Result<McpSyncDetail, String> {
+    sync_server_to_path(tool, &config_path.to_path_buf(), server, enabled)
+}
+
+/// Remove an MCP server from an explicit config file
+pub fn remove_server_from_config_path(
+    tool: &RuntimeTool,
+    config_path: &Path,
+    server_name: &str,
+) -> Result<(), String> {
+    remove_server_from_path(tool, &config_path.to_path_buf(), server_name)
+}
+
+/// Read the raw entry for `server_name` from a JSON/JSONC or TOML config file.
+/// Returns `None` when the file or the entry does not exist.
+pub fn read_server_entry_from_config_path(
+    tool: &RuntimeTool,
+    config_path: &Path,
+    server_name: &str,
+) -> Result<Option<Value>, String> {
+    if !config_path.exists() {
+        return Ok(None);
+    }
+    let format = tool.mcp_config_format.as_deref().unwrap_or("json");
+    let field = tool.mcp_field.as_deref().unwrap_or("mcpServers");
+    let content = std::fs::read_to_string(config_path)
+        .map_err(|e| format!("Failed to read config file: {}", e))?;
+    let content = content.trim();
+    if content.is_empty() {
+        return Ok(None);
+    }
+    let config: Value = match format {
+        "json" | "jsonc" => {
+            json5::from_str(content).map_err(|e| format!("Failed to parse config file: {}", e))?
+        }
+        "toml" => {
+            let value: toml::Value = toml::from_str(content)
+                .map_err(|e| format!("Failed to parse TOML config: {}", e))?;
+            serde_json::to_value(value)
+                .map_err(|e| format!("Failed to convert TOML config: {}", e))?
+        }
+        _ => return Err(format!("Unsupported config format: {}", format)),
+    };
+    Ok(get_json_value_by_path(&config, field)
+        .and_then(|servers| servers.get(server_name))
+        .cloned())
+}
+
+/// The entry `sync_server_to_config_path` would write for this server, built
+/// in a scratch file so drift checks compare against the real writer output.
+pub fn expected_server_entry(
+    tool: &RuntimeTool,
+    config_path: &Path,
+    server: &McpServer,
+    enabled: bool,
+) -> Result<Option<Value>, String> {
+    let scratch_dir =
+        tempfile::tempdir().map_err(|e| format!("Failed to create scratch dir: {}", e))?;
+    let file_name = config_path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_else(|| "mcp.json".into());
+    let scratch_path = scratch_dir.path().join(file_name);
+    sync_server_to_config_path(tool, &scratch_path, server, enabled)?;
+    read_server_entry_from_config_path(tool, &scratch_path, &server.name)
+}
+
 /// Clone the server and apply `cmd /c` wrapping to its `server_config` when
 /// `should_wrap` is true and the server is stdio. Non-stdio servers are returned
 /// unchanged. Used by the yaml/cordis arms which don't thread `should_wrap_cmd`
@@ -1184,6 +1258,7 @@ fn parse_server_with_format_config(
         server_config: unified_config,
         enabled_tools: vec![],
         sync_details: None,
+        probe_result: None,
         description: None,
         user_group: None,
         user_note: None,
@@ -1226,6 +1301,7 @@ fn parse_standard_server_config(name: &str, server_config: &Value, now: i64) ->
         server_config: normalized_config,
         enabled_tools: vec![],
         sync_details: None,
+        probe_result: None,
         description: None,
         user_group: None,
         user_note: None,
@@ -1423,6 +1499,7 @@ fn import_servers_from_toml(
             server_config: normalized_config,
             enabled_tools: vec![],
             sync_details: None,
+            probe_result: None,
             description: None,
             user_group: None,
             user_note: None,
@@ -1525,6 +1602,7 @@ mod tests {
             }),
             enabled_tools: vec![],
             sync_details: None,
+            probe_result: None,
             description: None,
             user_group: None,
             user_note: None,
@@ -1549,6 +1627,7 @@ mod tests {
             }),
             enabled_tools: vec![],
             sync_details: None,
+            probe_result: None,
             description: None,
             user_group: None,
             user_note: None,
@@ -1571,6 +1650,7 @@ mod tests {
             }),
             enabled_tools: vec![],
             sync_details: None,
+            probe_result: None,
             description: None,
             user_group: None,
             user_note: None,
@@ -2084,6 +2164,7 @@ X-Test = "yes"
             }),
             enabled_tools: vec![],
             sync_details: None,
+            probe_result: None,
             description: None,
             user_group: None,
             user_note: None,
@@ -294,6 +294,7 @@ fn build_mcp_server_from_cordis(
                 server_config,
                 enabled_tools: vec![],
                 sync_details: None,
+                probe_result: None,
                 description: None,
                 user_group: None,
                 user_note: None,
@@ -329,6 +330,7 @@ fn build_mcp_server_from_cordis(
                 server_config: Value::Object(unified),
                 enabled_tools: vec![],
                 sync_details: None,
+                probe_result: None,
                 description: None,
                 user_group: None,
                 user_note: None,
@@ -507,6 +509,7 @@ mod tests {
             }),
             enabled_tools: vec![],
             sync_details: None,
+            probe_result: None,
             description: None,
             user_group: None,
             user_note: None,
@@ -529,6 +532,7 @@ mod tests {
             }),
             enabled_tools: vec![],
             sync_details: None,
+            probe_result: None,
             description: None,
             user_group: None,
             user_note: None,
@@ -143,6 +143,7 @@ pub(crate) fn convert_from_hermes_format(name: &str, spec: &Value) -> Option<Mcp
             server_config,
             enabled_tools: vec![],
             sync_details: None,
+            probe_result: None,
             description: None,
             user_group: None,
             user_note: None,
@@ -175,6 +176,7 @@ pub(crate) fn convert_from_hermes_format(name: &str, spec: &Value) -> Option<Mcp
             server_config: Value::Object(unified),
             enabled_tools: vec![],
             sync_details: None,
+            probe_result: None,
             description: None,
             user_group: None,
             user_note: None,
@@ -352,6 +354,7 @@ mod tests {
             server_config,
             enabled_tools: vec![],
             sync_details: None,
+            probe_result: None,
             description: None,
             user_group: None,
             user_note: None,
@@ -382,6 +385,7 @@ mod tests {
             server_config,
             enabled_tools: vec![],
             sync_details: None,
+            probe_result: None,
             description: None,
             user_group: None,
             user_note: None,
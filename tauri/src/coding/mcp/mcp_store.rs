@@ -9,7 +9,9 @@ use super::adapter::{
     set_sync_detail, to_clean_mcp_server_payload, to_mcp_preferences_payload,
 };
 use super::command_normalize;
-use super::types::{now_ms, FavoriteMcp, McpPreferences, McpServer, McpSyncDetail};
+use super::types::{
+    now_ms, FavoriteMcp, McpPreferences, McpProbeResult, McpServer, McpSyncDetail,
+};
 use crate::coding::db_id::db_new_id;
 use crate::db::helpers::{db_delete, db_get, db_list, db_max_i64, db_put, db_query_by_field};
 use crate::db::schema::{DbTable, JsonFieldPath, OrderDirection, OrderField, OrderSpec};
@@ -163,6 +165,23 @@ pub async fn delete_sync_detail(
     Ok(())
 }
 
+// ==================== Probe Result Operations ====================
+
+/// Store the latest live probe result. Probing does not change the server
+/// config, so `updated_at` is left untouched.
+pub async fn update_probe_result(
+    state: &SqliteDbState,
+    server_id: &str,
+    result: &McpProbeResult,
+) -> Result<(), String> {
+    let mut server = get_mcp_server_by_id(state, server_id)
+        .await?
+        .ok_or_else(|| format!("MCP server not found: {}", server_id))?;
+    server.probe_result = Some(serde_json::to_value(result).map_err(|e| e.to_string())?);
+    upsert_mcp_server(state, &server).await?;
+    Ok(())
+}
+
 /// Toggle a tool's enabled state for an MCP server
 pub async fn toggle_tool_enabled(
     state: &SqliteDbState,
@@ -283,6 +302,7 @@ mod tests {
             server_config: json!({"command": "cmd", "args": ["/c", "node"]}),
             enabled_tools: vec!["claude".to_string()],
             sync_details: None,
+            probe_result: None,
             description: None,
             sort_index: 0,
             created_at: 1,
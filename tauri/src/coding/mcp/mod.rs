@@ -13,6 +13,7 @@ pub mod hermes_mcp;
 pub mod mcp_store;
 pub mod opencode_path;
 pub mod package_version;
+pub mod probe;
 pub mod tray_support;
 pub mod types;
 pub mod yaml_sync;
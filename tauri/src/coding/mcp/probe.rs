@@ -0,0 +1,997 @@
+//! Live MCP server probing
+//!
+//! Speaks just enough of the MCP JSON-RPC protocol to tell whether a stored
+//! server actually works before it is synced to every tool: `initialize`,
+//! `notifications/initialized`, then `tools/list`, `prompts/list` and
+//! `resources/list` for the capabilities the server advertises.
+//!
+//! Transports:
+//! - stdio: newline-delimited JSON over the child process stdin/stdout
+//! - http: Streamable HTTP (POST, response is JSON or an SSE stream)
+//! - sse: legacy HTTP+SSE (GET opens the stream, `endpoint` event names the POST URL)
+
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures_util::StreamExt;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE};
+use reqwest::Client;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+use super::types::{
+    now_ms, HttpConfig, McpProbePrompt, McpProbeResource, McpProbeResult, McpProbeTool,
+    McpServer, StdioConfig,
+};
+use crate::coding::cli_resolver::{build_local_tokio_command, resolve_local_cli_by_name};
+use crate::coding::path_expand::expand_local_path;
+use crate::{http_client, SqliteDbState};
+
+const PROBE_PROTOCOL_VERSION: &str = "2025-06-18";
+const DEFAULT_PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+/// Upper bound of `nextCursor` pages followed per list call.
+const MAX_LIST_PAGES: usize = 20;
+const STDERR_TAIL_LIMIT: usize = 2048;
+const HTTP_ERROR_BODY_LIMIT: usize = 512;
+
+/// Probe a stored MCP server and return the result. Never fails: transport and
+/// protocol errors are reported through `status = "error"` and `error_message`.
+pub async fn probe_server(db_state: &SqliteDbState, server: &McpServer) -> McpProbeResult {
+    let probe_timeout = probe_timeout_for(server);
+    let started = Instant::now();
+    let mut result = McpProbeResult {
+        status: "error".to_string(),
+        probed_at: now_ms(),
+        ..Default::default()
+    };
+
+    let outcome = tokio::time::timeout(
+        probe_timeout,
+        run_probe(db_state, server, started, &mut result),
+    )
+    .await;
+
+    match outcome {
+        Ok(Ok(())) => result.status = "ok".to_string(),
+        Ok(Err(error)) => result.error_message = Some(error),
+        Err(_) => {
+            result.error_message = Some(format!(
+                "MCP probe timed out after {}s",
+                probe_timeout.as_secs()
+            ))
+        }
+    }
+    result
+}
+
+/// Use the server's configured timeout (milliseconds) when it is longer than the default.
+fn probe_timeout_for(server: &McpServer) -> Duration {
+    server
+        .timeout
+        .filter(|timeout| *timeout > 0)
+        .map(|timeout| Duration::from_millis(timeout as u64))
+        .map(|timeout| timeout.max(DEFAULT_PROBE_TIMEOUT))
+        .unwrap_or(DEFAULT_PROBE_TIMEOUT)
+}
+
+async fn run_probe(
+    db_state: &SqliteDbState,
+    server: &McpServer,
+    started: Instant,
+    result: &mut McpProbeResult,
+) -> Result<(), String> {
+    let transport = match server.server_type.as_str() {
+        "stdio" => {
+            let config: StdioConfig = serde_json::from_value(server.server_config.clone())
+                .map_err(|e| format!("Invalid stdio config: {}", e))?;
+            ProbeTransport::Stdio(StdioTransport::spawn(&config)?)
+        }
+        "http" | "sse" => {
+            let config: HttpConfig = serde_json::from_value(server.server_config.clone())
+                .map_err(|e| format!("Invalid {} config: {}", server.server_type, e))?;
+            let client = http_client::client_streaming_no_compression(db_state).await?;
+            let headers = build_header_map(config.headers.as_ref())?;
+            if server.server_type == "sse" {
+                ProbeTransport::Sse(SseTransport::connect(client, &config.url, headers).await?)
+            } else {
+                ProbeTransport::StreamableHttp(HttpTransport::new(client, &config.url, headers))
+            }
+        }
+        other => return Err(format!("Unsupported MCP server type: {}", other)),
+    };
+
+    let mut client = JsonRpcClient {
+        transport,
+        next_id: 1,
+    };
+    let outcome = probe_session(&mut client, started, result).await;
+    client.transport.shutdown().await;
+    outcome
+}
+
+async fn probe_session(
+    client: &mut JsonRpcClient,
+    started: Instant,
+    result: &mut McpProbeResult,
+) -> Result<(), String> {
+    let init = client
+        .call(
+            "initialize",
+            json!({
+                "protocolVersion": PROBE_PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": {
+                    "name": "ai-toolbox",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }),
+        )
+        .await
+        .map_err(|e| format!("initialize failed: {}", e))?;
+    result.latency_ms = Some(started.elapsed().as_millis() as i64);
+    apply_initialize_result(&init, result);
+    client.transport.set_protocol_version(result.protocol_version.clone());
+
+    client
+        .notify("notifications/initialized")
+        .await
+        .map_err(|e| format!("notifications/initialized failed: {}", e))?;
+
+    let capabilities = init.get("capabilities").cloned().unwrap_or(Value::Null);
+    let mut errors = Vec::new();
+
+    if capabilities.get("tools").is_some() {
+        match client.list_all("tools/list", "tools").await {
+            Ok(items) => result.tools = items.iter().filter_map(parse_probe_tool).collect(),
+            Err(e) => errors.push(format!("tools/list failed: {}", e)),
+        }
+    }
+    if capabilities.get("prompts").is_some() {
+        match client.list_all("prompts/list", "prompts").await {
+            Ok(items) => result.prompts = items.iter().filter_map(parse_probe_prompt).collect(),
+            Err(e) => errors.push(format!("prompts/list failed: {}", e)),
+        }
+    }
+    if capabilities.get("resources").is_some() {
+        match client.list_all("resources/list", "resources").await {
+            Ok(items) => {
+                result.resources = items.iter().filter_map(parse_probe_resource).collect()
+            }
+            Err(e) => errors.push(format!("resources/list failed: {}", e)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+fn apply_initialize_result(init: &Value, result: &mut McpProbeResult) {
+    result.protocol_version = init
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    result.server_name = init
+        .pointer("/serverInfo/name")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    result.server_version = init
+        .pointer("/serverInfo/version")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    result.instructions = init
+        .get("instructions")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    result.capabilities = init
+        .get("capabilities")
+        .and_then(Value::as_object)
+        .map(|object| object.keys().cloned().collect())
+        .unwrap_or_default();
+    result.capabilities.sort();
+}
+
+fn parse_probe_tool(item: &Value) -> Option<McpProbeTool> {
+    Some(McpProbeTool {
+        name: item.get("name")?.as_str()?.to_string(),
+        description: item
+            .get("description")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        input_schema: item.get("inputSchema").cloned(),
+    })
+}
+
+fn parse_probe_prompt(item: &Value) -> Option<McpProbePrompt> {
+    Some(McpProbePrompt {
+        name: item.get("name")?.as_str()?.to_string(),
+        description: item
+            .get("description")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        arguments: item
+            .get("arguments")
+            .and_then(Value::as_array)
+            .map(|args| {
+                args.iter()
+                    .filter_map(|arg| arg.get("name").and_then(Value::as_str))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+fn parse_probe_resource(item: &Value) -> Option<McpProbeResource> {
+    Some(McpProbeResource {
+        uri: item.get("uri")?.as_str()?.to_string(),
+        name: item.get("name").and_then(Value::as_str).map(str::to_string),
+        mime_type: item
+            .get("mimeType")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    })
+}
+
+// ==================== JSON-RPC ====================
+
+struct JsonRpcClient {
+    transport: ProbeTransport,
+    next_id: i64,
+}
+
+impl JsonRpcClient {
+    async fn call(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let message = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+        self.transport.request(id, &message).await
+    }
+
+    async fn notify(&mut self, method: &str) -> Result<(), String> {
+        let message = json!({
+            "jsonrpc": "2.0",
+            "method": method,
+        });
+        self.transport.notify(&message).await
+    }
+
+    /// Call a paginated `*/list` method and collect every page's `field` items.
+    async fn list_all(&mut self, method: &str, field: &str) -> Result<Vec<Value>, String> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_LIST_PAGES {
+            let params = match &cursor {
+                Some(cursor) => json!({ "cursor": cursor }),
+                None => json!({}),
+            };
+            let page = self.call(method, params).await?;
+            if let Some(page_items) = page.get(field).and_then(Value::as_array) {
+                items.extend(page_items.iter().cloned());
+            }
+            cursor = page
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .filter(|next| !next.is_empty())
+                .map(str::to_string);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(items)
+    }
+}
+
+/// Match a JSON-RPC message against a pending request id.
+///
+/// Returns `None` for notifications, server-initiated requests and responses to other ids.
+fn match_response(message: &Value, id: i64) -> Option<Result<Value, String>> {
+    if message.get("method").is_some() || message.get("id").and_then(Value::as_i64) != Some(id) {
+        return None;
+    }
+    if let Some(error) = message.get("error") {
+        let code = error.get("code").and_then(Value::as_i64).unwrap_or(0);
+        let text = error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return Some(Err(format!("JSON-RPC error {}: {}", code, text)));
+    }
+    Some(Ok(message.get("result").cloned().unwrap_or(Value::Null)))
+}
+
+/// Find the response for `id` in a message that may also be a JSON-RPC batch.
+fn match_response_in(message: &Value, id: i64) -> Option<Result<Value, String>> {
+    match message {
+        Value::Array(items) => items.iter().find_map(|item| match_response(item, id)),
+        _ => match_response(message, id),
+    }
+}
+
+/// Reply to a server-initiated request. Only `ping` is answered; everything
+/// else (sampling, roots, elicitation) is declined because the probe never
+/// advertises those client capabilities.
+fn server_request_reply(message: &Value) -> Option<Value> {
+    let method = message.get("method").and_then(Value::as_str)?;
+    let id = message.get("id")?.clone();
+    if method == "ping" {
+        return Some(json!({ "jsonrpc": "2.0", "id": id, "result": {} }));
+    }
+    Some(json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": -32601, "message": format!("Method not found: {}", method) },
+    }))
+}
+
+// ==================== Transports ====================
+
+enum ProbeTransport {
+    Stdio(StdioTransport),
+    StreamableHttp(HttpTransport),
+    Sse(SseTransport),
+}
+
+impl ProbeTransport {
+    async fn request(&mut self, id: i64, message: &Value) -> Result<Value, String> {
+        match self {
+            ProbeTransport::Stdio(transport) => transport.request(id, message).await,
+            ProbeTransport::StreamableHttp(transport) => transport.request(id, message).await,
+            ProbeTransport::Sse(transport) => transport.request(id, message).await,
+        }
+    }
+
+    async fn notify(&mut self, message: &Value) -> Result<(), String> {
+        match self {
+            ProbeTransport::Stdio(transport) => transport.send(message).await,
+            ProbeTransport::StreamableHttp(transport) => transport.notify(message).await,
+            ProbeTransport::Sse(transport) => transport.post(message).await,
+        }
+    }
+
+    fn set_protocol_version(&mut self, version: Option<String>) {
+        if let ProbeTransport::StreamableHttp(transport) = self {
+            transport.protocol_version = version;
+        }
+    }
+
+    async fn shutdown(&mut self) {
+        match self {
+            ProbeTransport::Stdio(transport) => transport.shutdown().await,
+            ProbeTransport::StreamableHttp(transport) => transport.shutdown().await,
+            ProbeTransport::Sse(transport) => transport.reader.abort(),
+        }
+    }
+}
+
+// ---------- stdio ----------
+
+struct StdioTransport {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+    stderr_tail: Arc<Mutex<String>>,
+}
+
+impl StdioTransport {
+    fn spawn(config: &StdioConfig) -> Result<Self, String> {
+        let command = expand_local_path(config.command.trim())
+            .unwrap_or_else(|_| config.command.trim().to_string());
+        if command.is_empty() {
+            return Err("Stdio MCP server has an empty command".to_string());
+        }
+        let program = resolve_stdio_program(&command);
+
+        let mut cmd = build_local_tokio_command(&program);
+        for arg in &config.args {
+            cmd.arg(expand_local_path(arg).unwrap_or_else(|_| arg.clone()));
+        }
+        if let Some(env) = config.env.as_ref().and_then(Value::as_object) {
+            for (key, value) in env {
+                let value = match value {
+                    Value::String(text) => text.clone(),
+                    Value::Null => continue,
+                    other => other.to_string(),
+                };
+                cmd.env(key, value);
+            }
+        }
+        cmd.stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| format!("Failed to start MCP server command '{}': {}", command, e))?;
+        let stdin = child.stdin.take().ok_or("Failed to open MCP server stdin")?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or("Failed to open MCP server stdout")?;
+
+        let stderr_tail = Arc::new(Mutex::new(String::new()));
+        if let Some(mut stderr) = child.stderr.take() {
+            let tail = Arc::clone(&stderr_tail);
+            tokio::spawn(async move {
+                let mut buffer = [0u8; 1024];
+                while let Ok(read) = stderr.read(&mut buffer).await {
+                    if read == 0 {
+                        break;
+                    }
+                    let mut tail = tail.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                    tail.push_str(&String::from_utf8_lossy(&buffer[..read]));
+                    truncate_to_tail(&mut tail, STDERR_TAIL_LIMIT);
+                }
+            });
+        }
+
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+            stderr_tail,
+        })
+    }
+
+    async fn send(&mut self, message: &Value) -> Result<(), String> {
+        let mut line = serde_json::to_string(message).map_err(|e| e.to_string())?;
+        line.push('\n');
+        self.stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| self.with_stderr(format!("Failed to write to MCP server: {}", e)))?;
+        self.stdin
+            .flush()
+            .await
+            .map_err(|e| self.with_stderr(format!("Failed to write to MCP server: {}", e)))
+    }
+
+    async fn request(&mut self, id: i64, message: &Value) -> Result<Value, String> {
+        self.send(message).await?;
+        loop {
+            let line = match self.stdout.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => {
+                    return Err(self.with_stderr("MCP server closed stdout".to_string()));
+                }
+                Err(e) => {
+                    return Err(self.with_stderr(format!("Failed to read MCP server output: {}", e)))
+                }
+            };
+            // Servers that log to stdout break the protocol for real clients too,
+            // but the probe keeps going so the actual handshake result is visible.
+            let Ok(incoming) = serde_json::from_str::<Value>(line.trim()) else {
+                continue;
+            };
+            if let Some(reply) = server_request_reply(&incoming) {
+                self.send(&reply).await?;
+                continue;
+            }
+            if let Some(outcome) = match_response(&incoming, id) {
+                return outcome;
+            }
+        }
+    }
+
+    fn with_stderr(&self, message: String) -> String {
+        let tail = self
+            .stderr_tail
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .trim()
+            .to_string();
+        if tail.is_empty() {
+            message
+        } else {
+            format!("{} (stderr: {})", message, tail)
+        }
+    }
+
+    async fn shutdown(&mut self) {
+        let _ = self.stdin.shutdown().await;
+        let _ = self.child.kill().await;
+    }
+}
+
+/// Resolve a bare command name (`npx`, `uvx`, `node`) through the same PATH and
+/// version-manager lookup used for the CLIs, since GUI launches often lack the
+/// shell PATH. Paths are used as-is.
+fn resolve_stdio_program(command: &str) -> PathBuf {
+    if command.contains('/') || command.contains('\\') {
+        return PathBuf::from(command);
+    }
+    resolve_local_cli_by_name(command)
+        .map(|program| program.path)
+        .unwrap_or_else(|| PathBuf::from(command))
+}
+
+fn truncate_to_tail(text: &mut String, limit: usize) {
+    if text.len() <= limit {
+        return;
+    }
+    let mut start = text.len() - limit;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    text.replace_range(..start, "");
+}
+
+// ---------- Streamable HTTP ----------
+
+struct HttpTransport {
+    client: Client,
+    url: String,
+    headers: HeaderMap,
+    session_id: Option<String>,
+    protocol_version: Option<String>,
+}
+
+impl HttpTransport {
+    fn new(client: Client, url: &str, headers: HeaderMap) -> Self {
+        Self {
+            client,
+            url: url.to_string(),
+            headers,
+            session_id: None,
+            protocol_version: None,
+        }
+    }
+
+    async fn post(&mut self, message: &Value) -> Result<reqwest::Response, String> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .headers(self.headers.clone())
+            .header(ACCEPT, "application/json, text/event-stream")
+            .header(CONTENT_TYPE, "application/json")
+            .json(message);
+        if let Some(session_id) = &self.session_id {
+            request = request.header("mcp-session-id", session_id);
+        }
+        if let Some(version) = &self.protocol_version {
+            request = request.header("mcp-protocol-version", version);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+        if let Some(session_id) = response
+            .headers()
+            .get("mcp-session-id")
+            .and_then(|value| value.to_str().ok())
+        {
+            self.session_id = Some(session_id.to_string());
+        }
+        ensure_success(response).await
+    }
+
+    async fn request(&mut self, id: i64, message: &Value) -> Result<Value, String> {
+        let response = self.post(message).await?;
+        let is_event_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+
+        if !is_event_stream {
+            let body: Value = response
+                .json()
+                .await
+                .map_err(|e| format!("Invalid JSON-RPC response: {}", e))?;
+            return match_response_in(&body, id)
+                .unwrap_or_else(|| Err("Response did not contain the request id".to_string()));
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut parser = SseParser::default();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| format!("Failed to read SSE response: {}", e))?;
+            for event in parser.push(&chunk) {
+                let Ok(incoming) = serde_json::from_str::<Value>(&event.data) else {
+                    continue;
+                };
+                if let Some(reply) = server_request_reply(&incoming) {
+                    // Replies to server requests are plain POSTs without a response body.
+                    let _ = self.post(&reply).await;
+                    continue;
+                }
+                if let Some(outcome) = match_response_in(&incoming, id) {
+                    return outcome;
+                }
+            }
+        }
+        Err("SSE response ended before the JSON-RPC response arrived".to_string())
+    }
+
+    async fn notify(&mut self, message: &Value) -> Result<(), String> {
+        self.post(message).await.map(|_| ())
+    }
+
+    /// Terminate the session so stateful servers can release it right away.
+    async fn shutdown(&mut self) {
+        if let Some(session_id) = self.session_id.take() {
+            let _ = self
+                .client
+                .delete(&self.url)
+                .headers(self.headers.clone())
+                .header("mcp-session-id", session_id)
+                .send()
+                .await;
+        }
+    }
+}
+
+// ---------- legacy HTTP+SSE ----------
+
+struct SseTransport {
+    client: Client,
+    endpoint: String,
+    headers: HeaderMap,
+    events: mpsc::UnboundedReceiver<Value>,
+    reader: JoinHandle<()>,
+}
+
+impl SseTransport {
+    async fn connect(client: Client, url: &str, headers: HeaderMap) -> Result<Self, String> {
+        let base = url::Url::parse(url).map_err(|e| format!("Invalid SSE URL: {}", e))?;
+        let response = client
+            .get(url)
+            .headers(headers.clone())
+            .header(ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .map_err(|e| format!("SSE connection failed: {}", e))?;
+        let response = ensure_success(response).await?;
+
+        let (endpoint_tx, endpoint_rx) = oneshot::channel::<String>();
+        let (events_tx, events) = mpsc::unbounded_channel::<Value>();
+        let reader = tokio::spawn(async move {
+            let mut endpoint_tx = Some(endpoint_tx);
+            let mut stream = response.bytes_stream();
+            let mut parser = SseParser::default();
+            while let Some(Ok(chunk)) = stream.next().await {
+                for event in parser.push(&chunk) {
+                    if event.event.as_deref() == Some("endpoint") {
+                        if let Some(sender) = endpoint_tx.take() {
+                            let _ = sender.send(event.data.trim().to_string());
+                        }
+                        continue;
+                    }
+                    if let Ok(message) = serde_json::from_str::<Value>(&event.data) {
+                        if events_tx.send(message).is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        let endpoint = match endpoint_rx.await {
+            Ok(endpoint) => endpoint,
+            Err(_) => {
+                reader.abort();
+                return Err("SSE stream closed before the endpoint event".to_string());
+            }
+        };
+        let endpoint = base
+            .join(&endpoint)
+            .map_err(|e| format!("Invalid SSE endpoint '{}': {}", endpoint, e))?;
+
+        Ok(Self {
+            client,
+            endpoint: endpoint.to_string(),
+            headers,
+            events,
+            reader,
+        })
+    }
+
+    async fn post(&mut self, message: &Value) -> Result<(), String> {
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .headers(self.headers.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .json(message)
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+        ensure_success(response).await.map(|_| ())
+    }
+
+    async fn request(&mut self, id: i64, message: &Value) -> Result<Value, String> {
+        self.post(message).await?;
+        while let Some(incoming) = self.events.recv().await {
+            if let Some(reply) = server_request_reply(&incoming) {
+                self.post(&reply).await?;
+                continue;
+            }
+            if let Some(outcome) = match_response_in(&incoming, id) {
+                return outcome;
+            }
+        }
+        Err("SSE stream closed before the JSON-RPC response arrived".to_string())
+    }
+}
+
+// ---------- HTTP helpers ----------
+
+fn build_header_map(headers: Option<&Value>) -> Result<HeaderMap, String> {
+    let mut map = HeaderMap::new();
+    let Some(headers) = headers.and_then(Value::as_object) else {
+        return Ok(map);
+    };
+    for (key, value) in headers {
+        let Some(value) = value.as_str() else {
+            continue;
+        };
+        let name = HeaderName::from_bytes(key.as_bytes())
+            .map_err(|e| format!("Invalid header name '{}': {}", key, e))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|e| format!("Invalid value for header '{}': {}", key, e))?;
+        map.insert(name, value);
+    }
+    Ok(map)
+}
+
+async fn ensure_success(response: reqwest::Response) -> Result<reqwest::Response, String> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    let mut body = body.trim().to_string();
+    if body.len() > HTTP_ERROR_BODY_LIMIT {
+        let mut end = HTTP_ERROR_BODY_LIMIT;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body.truncate(end);
+        body.push('…');
+    }
+    if body.is_empty() {
+        Err(format!("HTTP {}", status))
+    } else {
+        Err(format!("HTTP {}: {}", status, body))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct SseEvent {
+    event: Option<String>,
+    data: String,
+}
+
+/// Incremental SSE parser tolerant of LF / CRLF framing and split UTF-8 chunks.
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some((index, delimiter_len)) = find_event_delimiter(&self.buffer) {
+            let block: Vec<u8> = self.buffer.drain(..index + delimiter_len).collect();
+            let block = String::from_utf8_lossy(&block[..index]);
+            let mut event = None;
+            let mut data = Vec::new();
+            for line in block.lines() {
+                if let Some(value) = line.strip_prefix("event:") {
+                    event = Some(value.trim().to_string());
+                } else if let Some(value) = line.strip_prefix("data:") {
+                    data.push(value.strip_prefix(' ').unwrap_or(value).to_string());
+                }
+            }
+            if event.is_some() || !data.is_empty() {
+                events.push(SseEvent {
+                    event,
+                    data: data.join("\n"),
+                });
+            }
+        }
+        events
+    }
+}
+
+fn find_event_delimiter(buffer: &[u8]) -> Option<(usize, usize)> {
+    let crlf = buffer
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .map(|index| (index, 4));
+    let lf = buffer
+        .windows(2)
+        .position(|window| window == b"\n\n")
+        .map(|index| (index, 2));
+    match (crlf, lf) {
+        (Some(crlf), Some(lf)) => Some(if crlf.0 <= lf.0 { crlf } else { lf }),
+        (Some(delimiter), None) | (None, Some(delimiter)) => Some(delimiter),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_response_ignores_notifications_and_other_ids() {
+        let notification = json!({"jsonrpc": "2.0", "method": "notifications/message"});
+        assert!(match_response(&notification, 1).is_none());
+
+        let other = json!({"jsonrpc": "2.0", "id": 2, "result": {}});
+        assert!(match_response(&other, 1).is_none());
+
+        let ok = json!({"jsonrpc": "2.0", "id": 1, "result": {"tools": []}});
+        assert_eq!(match_response(&ok, 1), Some(Ok(json!({"tools": []}))));
+
+        let error = json!({"jsonrpc": "2.0", "id": 1, "error": {"code": -32601, "message": "nope"}});
+        assert_eq!(
+            match_response(&error, 1),
+            Some(Err("JSON-RPC error -32601: nope".to_string()))
+        );
+    }
+
+    #[test]
+    fn match_response_in_searches_batches() {
+        let batch = json!([
+            {"jsonrpc": "2.0", "method": "notifications/progress"},
+            {"jsonrpc": "2.0", "id": 3, "result": {"ok": true}}
+        ]);
+        assert_eq!(match_response_in(&batch, 3), Some(Ok(json!({"ok": true}))));
+    }
+
+    #[test]
+    fn server_request_reply_answers_ping_and_declines_others() {
+        let ping = json!({"jsonrpc": "2.0", "id": "p1", "method": "ping"});
+        assert_eq!(
+            server_request_reply(&ping),
+            Some(json!({"jsonrpc": "2.0", "id": "p1", "result": {}}))
+        );
+
+        let roots = json!({"jsonrpc": "2.0", "id": 7, "method": "roots/list"});
+        let reply = server_request_reply(&roots).expect("reply");
+        assert_eq!(reply["error"]["code"], json!(-32601));
+
+        let notification = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        assert!(server_request_reply(&notification).is_none());
+    }
+
+    #[test]
+    fn apply_initialize_result_reads_server_info_and_capabilities() {
+        let init = json!({
+            "protocolVersion": "2025-03-26",
+            "serverInfo": {"name": "demo", "version": "1.2.3"},
+            "capabilities": {"tools": {"listChanged": true}, "logging": {}},
+            "instructions": "use carefully"
+        });
+        let mut result = McpProbeResult::default();
+        apply_initialize_result(&init, &mut result);
+
+        assert_eq!(result.protocol_version.as_deref(), Some("2025-03-26"));
+        assert_eq!(result.server_name.as_deref(), Some("demo"));
+        assert_eq!(result.server_version.as_deref(), Some("1.2.3"));
+        assert_eq!(result.instructions.as_deref(), Some("use carefully"));
+        assert_eq!(result.capabilities, vec!["logging", "tools"]);
+    }
+
+    #[test]
+    fn list_item_parsers_keep_schema_and_skip_nameless_entries() {
+        let tool = json!({"name": "search", "description": "d", "inputSchema": {"type": "object"}});
+        assert_eq!(
+            parse_probe_tool(&tool),
+            Some(McpProbeTool {
+                name: "search".to_string(),
+                description: Some("d".to_string()),
+                input_schema: Some(json!({"type": "object"})),
+            })
+        );
+        assert!(parse_probe_tool(&json!({"description": "missing name"})).is_none());
+
+        let prompt = json!({"name": "review", "arguments": [{"name": "file"}, {"name": "focus"}]});
+        assert_eq!(
+            parse_probe_prompt(&prompt).map(|prompt| prompt.arguments),
+            Some(vec!["file".to_string(), "focus".to_string()])
+        );
+
+        let resource = json!({"uri": "file:///a.txt", "mimeType": "text/plain"});
+        let resource = parse_probe_resource(&resource).expect("resource");
+        assert_eq!(resource.mime_type.as_deref(), Some("text/plain"));
+    }
+
+    #[test]
+    fn sse_parser_handles_split_chunks_and_crlf() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b"event: endpoint\r\ndata: /messages?s").is_empty());
+        let events = parser.push(b"ession=1\r\n\r\ndata: {\"id\":1}\n\n");
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    event: Some("endpoint".to_string()),
+                    data: "/messages?session=1".to_string(),
+                },
+                SseEvent {
+                    event: None,
+                    data: "{\"id\":1}".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn build_header_map_rejects_invalid_names() {
+        let headers = json!({"Authorization": "Bearer x", "X-Count": 3});
+        let map = build_header_map(Some(&headers)).expect("headers");
+        assert_eq!(map.len(), 1);
+        assert!(build_header_map(Some(&json!({"bad header": "x"}))).is_err());
+    }
+
+    #[test]
+    fn truncate_to_tail_keeps_char_boundaries() {
+        let mut text = "错误信息abc".to_string();
+        truncate_to_tail(&mut text, 5);
+        assert_eq!(text, "abc");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stdio_probe_runs_handshake_against_scripted_server() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = dir.path().join("server.sh");
+        std::fs::write(
+            &script,
+            r#"#!/bin/sh
+read init
+echo 'not json log line'
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-06-18","serverInfo":{"name":"fake","version":"0.1.0"},"capabilities":{"tools":{}}}}'
+read initialized
+read list
+echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"echo","inputSchema":{"type":"object"}}]}}'
+sleep 5
+"#,
+        )
+        .expect("write script");
+
+        let server = McpServer {
+            id: "s1".to_string(),
+            name: "fake".to_string(),
+            server_type: "stdio".to_string(),
+            server_config: json!({"command": "/bin/sh", "args": [script.to_string_lossy()]}),
+            enabled_tools: Vec::new(),
+            sync_details: None,
+            probe_result: None,
+            description: None,
+            user_group: None,
+            user_note: None,
+            tags: Vec::new(),
+            timeout: None,
+            sort_index: 0,
+            created_at: 0,
+            updated_at: 0,
+        };
+        let db_state = SqliteDbState::in_memory_for_test().expect("sqlite");
+        let result = probe_server(&db_state, &server).await;
+
+        assert_eq!(result.status, "ok", "{:?}", result.error_message);
+        assert_eq!(result.server_name.as_deref(), Some("fake"));
+        assert_eq!(result.capabilities, vec!["tools"]);
+        assert_eq!(result.tools.len(), 1);
+        assert_eq!(result.tools[0].name, "echo");
+        assert!(result.latency_ms.is_some());
+    }
+}
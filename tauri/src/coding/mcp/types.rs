@@ -66,6 +66,9 @@ pub struct McpServer {
     pub enabled_tools: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sync_details: Option<Value>,
+    /// Last live protocol probe result (see `probe.rs`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probe_result: Option<Value>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -100,6 +103,7 @@ pub struct McpServerDto {
     pub server_config: Value,
     pub enabled_tools: Vec<String>,
     pub sync_details: Vec<McpSyncDetailDto>,
+    pub probe_result: Option<McpProbeResult>,
     pub description: Option<String>,
     pub user_group: Option<String>,
     pub user_note: Option<String>,
@@ -110,6 +114,71 @@ pub struct McpServerDto {
     pub updated_at: i64,
 }
 
+/// Tool advertised by a probed MCP server
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct McpProbeTool {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub input_schema: Option<Value>,
+}
+
+/// Prompt advertised by a probed MCP server
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct McpProbePrompt {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<String>,
+}
+
+/// Resource advertised by a probed MCP server
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct McpProbeResource {
+    pub uri: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+}
+
+/// Result of a live MCP handshake + list calls, stored next to sync_details
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct McpProbeResult {
+    pub status: String, // "ok" | "error"
+    pub probed_at: i64,
+    /// Round trip of the `initialize` handshake, including process startup for stdio
+    pub latency_ms: Option<i64>,
+    #[serde(default)]
+    pub protocol_version: Option<String>,
+    #[serde(default)]
+    pub server_name: Option<String>,
+    #[serde(default)]
+    pub server_version: Option<String>,
+    #[serde(default)]
+    pub instructions: Option<String>,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    #[serde(default)]
+    pub tools: Vec<McpProbeTool>,
+    #[serde(default)]
+    pub prompts: Vec<McpProbePrompt>,
+    #[serde(default)]
+    pub resources: Vec<McpProbeResource>,
+    #[serde(default)]
+    pub error_message: Option<String>,
+}
+
+/// Probe result for a single server (batch probe response)
+#[derive(Debug, Serialize)]
+pub struct McpServerProbeDto {
+    pub server_id: String,
+    pub server_name: String,
+    pub result: McpProbeResult,
+}
+
 /// DTO for MCP sync detail (frontend display)
 #[derive(Debug, Serialize)]
 pub struct McpSyncDetailDto {
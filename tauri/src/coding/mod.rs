@@ -21,6 +21,7 @@ pub mod open_claw;
 pub mod open_code;
 pub mod pi;
 pub mod preset_models;
+pub mod project_workspace;
 pub mod proxy_gateway;
 pub mod reapply_applied_runtime;
 pub mod runtime_location;
@@ -286,6 +286,8 @@ async fn write_opencode_config_file(
 
     let json_content = serde_json::to_string_pretty(&sanitized_config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    // Favorite providers carry sealed API keys; never write an unresolved reference.
+    crate::secret_store::ensure_resolved(&json_content)?;
 
     fs::write(config_path, json_content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
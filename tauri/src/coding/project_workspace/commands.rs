@@ -0,0 +1,194 @@
+//! Tauri commands for project workspaces
+
+use std::collections::HashSet;
+
+use tauri::{AppHandle, Runtime, State};
+
+use super::store;
+use super::sync::{
+    check_project_workspace_drift, remove_project_workspace_files, sync_project_workspace,
+    ProjectSkillSource,
+};
+use super::targets::{project_tool_target, resolve_project_root, PROJECT_TOOL_TARGETS};
+use super::types::{
+    ProjectSyncDetail, ProjectToolTargetDto, ProjectWorkspace, ProjectWorkspaceInput,
+};
+use crate::coding::db_new_id;
+use crate::coding::mcp::mcp_store;
+use crate::coding::mcp::types::{now_ms, McpServer};
+use crate::coding::skills::commands::resolve_skill_source_path;
+use crate::coding::skills::skill_store;
+use crate::SqliteDbState;
+
+fn dedupe(values: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    values
+        .into_iter()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty() && seen.insert(value.clone()))
+        .collect()
+}
+
+async fn load_workspace(
+    state: &SqliteDbState,
+    workspace_id: &str,
+) -> Result<ProjectWorkspace, String> {
+    store::get_project_workspace_by_id(state, workspace_id)
+        .await?
+        .ok_or_else(|| format!("Project workspace not found: {}", workspace_id))
+}
+
+/// Servers and skills currently assigned to the workspace. Entries that were
+/// deleted or disabled elsewhere are skipped, so the next sync cleans them up.
+async fn load_workspace_items<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    workspace: &ProjectWorkspace,
+) -> Result<(Vec<McpServer>, Vec<ProjectSkillSource>), String> {
+    let mut servers = Vec::new();
+    for server_id in &workspace.mcp_server_ids {
+        if let Some(server) = mcp_store::get_mcp_server_by_id(state, server_id).await? {
+            servers.push(server);
+        }
+    }
+
+    let mut skills = Vec::new();
+    for skill_id in &workspace.skill_ids {
+        let Some(skill) = skill_store::get_skill_by_id(state, skill_id).await? else {
+            continue;
+        };
+        if !skill.management_enabled {
+            continue;
+        }
+        let source_path = resolve_skill_source_path(app, state, &skill).await?;
+        skills.push(ProjectSkillSource {
+            id: skill.id,
+            name: skill.name,
+            source_path,
+        });
+    }
+    Ok((servers, skills))
+}
+
+#[tauri::command]
+pub async fn project_workspace_list(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<ProjectWorkspace>, String> {
+    store::get_project_workspaces(&state).await
+}
+
+/// Tools that can be managed per project, with their project-level paths
+#[tauri::command]
+pub fn project_workspace_get_tool_targets() -> Vec<ProjectToolTargetDto> {
+    PROJECT_TOOL_TARGETS
+        .iter()
+        .map(|target| target.to_dto())
+        .collect()
+}
+
+#[tauri::command]
+pub async fn project_workspace_save(
+    state: State<'_, SqliteDbState>,
+    input: ProjectWorkspaceInput,
+) -> Result<ProjectWorkspace, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Project workspace name cannot be empty".to_string());
+    }
+    resolve_project_root(&input.root_path)?;
+    let enabled_tools = dedupe(input.enabled_tools);
+    if let Some(unknown) = enabled_tools
+        .iter()
+        .find(|key| project_tool_target(key).is_none())
+    {
+        return Err(format!(
+            "Tool {} does not support project-level configuration",
+            unknown
+        ));
+    }
+
+    let now = now_ms();
+    let existing = match input.id.as_deref().filter(|id| !id.is_empty()) {
+        Some(id) => Some(load_workspace(&state, id).await?),
+        None => None,
+    };
+    let workspace = ProjectWorkspace {
+        id: existing
+            .as_ref()
+            .map(|workspace| workspace.id.clone())
+            .unwrap_or_else(db_new_id),
+        name,
+        root_path: input.root_path.trim().to_string(),
+        enabled_tools,
+        mcp_server_ids: dedupe(input.mcp_server_ids),
+        skill_ids: dedupe(input.skill_ids),
+        sync_details: existing
+            .as_ref()
+            .map(|workspace| workspace.sync_details.clone())
+            .unwrap_or_default(),
+        last_synced_at: existing
+            .as_ref()
+            .and_then(|workspace| workspace.last_synced_at),
+        created_at: existing
+            .as_ref()
+            .map(|workspace| workspace.created_at)
+            .unwrap_or(now),
+        updated_at: now,
+    };
+    store::upsert_project_workspace(&state, &workspace).await?;
+    Ok(workspace)
+}
+
+/// Delete a workspace, optionally removing the entries and skill copies the
+/// last sync wrote into the repository.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn project_workspace_delete(
+    state: State<'_, SqliteDbState>,
+    workspaceId: String,
+    removeFiles: Option<bool>,
+) -> Result<(), String> {
+    let workspace = load_workspace(&state, &workspaceId).await?;
+    if removeFiles.unwrap_or(false) {
+        let root = resolve_project_root(&workspace.root_path)?;
+        let errors = remove_project_workspace_files(&root, &workspace);
+        if !errors.is_empty() {
+            return Err(format!(
+                "Failed to remove project files: {}",
+                errors.join("; ")
+            ));
+        }
+    }
+    store::delete_project_workspace(&state, &workspaceId).await
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn project_workspace_sync<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    workspaceId: String,
+) -> Result<ProjectWorkspace, String> {
+    let mut workspace = load_workspace(&state, &workspaceId).await?;
+    let root = resolve_project_root(&workspace.root_path)?;
+    let (servers, skills) = load_workspace_items(&app, &state, &workspace).await?;
+    workspace.sync_details = sync_project_workspace(&root, &workspace, &servers, &skills);
+    workspace.last_synced_at = Some(now_ms());
+    store::upsert_project_workspace(&state, &workspace).await?;
+    Ok(workspace)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn project_workspace_check_drift<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    workspaceId: String,
+) -> Result<Vec<ProjectSyncDetail>, String> {
+    let workspace = load_workspace(&state, &workspaceId).await?;
+    let root = resolve_project_root(&workspace.root_path)?;
+    let (servers, skills) = load_workspace_items(&app, &state, &workspace).await?;
+    Ok(check_project_workspace_drift(
+        &root, &workspace, &servers, &skills,
+    ))
+}
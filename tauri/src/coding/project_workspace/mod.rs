@@ -0,0 +1,14 @@
+//! Project Workspace Module
+//!
+//! Registered repositories with their own enabled MCP servers and skills,
+//! written to the project-level files each tool reads (`.mcp.json`,
+//! `.codex/config.toml`, `.claude/skills`, `opencode.json`, ...) instead of
+//! the user-level locations managed by the MCP and Skills modules.
+
+pub mod commands;
+pub mod store;
+pub mod sync;
+pub mod targets;
+pub mod types;
+
+pub use commands::*;
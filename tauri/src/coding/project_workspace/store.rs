@@ -0,0 +1,50 @@
+//! Project workspace database operations
+
+use super::types::ProjectWorkspace;
+use crate::db::helpers::{db_delete, db_get, db_list, db_put};
+use crate::db::schema::{DbTable, OrderDirection, OrderField, OrderSpec};
+use crate::SqliteDbState;
+
+fn from_db_project_workspace(record: serde_json::Value) -> Result<ProjectWorkspace, String> {
+    serde_json::from_value(record)
+        .map_err(|error| format!("Failed to parse project workspace: {error}"))
+}
+
+pub async fn get_project_workspaces(
+    state: &SqliteDbState,
+) -> Result<Vec<ProjectWorkspace>, String> {
+    state.with_conn(|conn| {
+        let order = OrderSpec::single(OrderField::created_at(OrderDirection::Asc));
+        db_list(conn, DbTable::ProjectWorkspace, Some(&order))?
+            .into_iter()
+            .map(from_db_project_workspace)
+            .collect()
+    })
+}
+
+pub async fn get_project_workspace_by_id(
+    state: &SqliteDbState,
+    workspace_id: &str,
+) -> Result<Option<ProjectWorkspace>, String> {
+    state.with_conn(|conn| {
+        db_get(conn, DbTable::ProjectWorkspace, workspace_id)?
+            .map(from_db_project_workspace)
+            .transpose()
+    })
+}
+
+pub async fn upsert_project_workspace(
+    state: &SqliteDbState,
+    workspace: &ProjectWorkspace,
+) -> Result<(), String> {
+    let data = serde_json::to_value(workspace)
+        .map_err(|error| format!("Failed to serialize project workspace: {error}"))?;
+    state.with_conn(|conn| db_put(conn, DbTable::ProjectWorkspace, &workspace.id, &data))
+}
+
+pub async fn delete_project_workspace(
+    state: &SqliteDbState,
+    workspace_id: &str,
+) -> Result<(), String> {
+    state.with_conn(|conn| db_delete(conn, DbTable::ProjectWorkspace, workspace_id).map(|_| ()))
+}
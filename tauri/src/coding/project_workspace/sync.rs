@@ -0,0 +1,593 @@
+//! Project workspace synchronization and drift detection
+//!
+//! MCP servers go through the same writers as user-level sync, pointed at the
+//! project-level config file. Skills are always copied (never linked) so the
+//! files can be committed with the repository.
+
+use std::path::{Path, PathBuf};
+
+use super::targets::{is_within_root, project_tool_target, ProjectToolTarget};
+use super::types::{ProjectSyncDetail, ProjectWorkspace, ITEM_TYPE_MCP, ITEM_TYPE_SKILL};
+use crate::coding::mcp::config_sync::{
+    expected_server_entry, read_server_entry_from_config_path, remove_server_from_config_path,
+    sync_server_to_config_path,
+};
+use crate::coding::mcp::types::{now_ms, McpServer};
+use crate::coding::skills::content_hash::hash_dir;
+use crate::coding::skills::path_executor::sync_copy_target_path;
+use crate::coding::skills::sync_engine::remove_path;
+
+/// A workspace skill with its resolved central repo directory
+#[derive(Clone, Debug)]
+pub struct ProjectSkillSource {
+    pub id: String,
+    pub name: String,
+    pub source_path: PathBuf,
+}
+
+fn detail(
+    tool: &str,
+    item_type: &str,
+    item_id: &str,
+    item_name: &str,
+    target_path: &Path,
+) -> ProjectSyncDetail {
+    ProjectSyncDetail {
+        tool: tool.to_string(),
+        item_type: item_type.to_string(),
+        item_id: item_id.to_string(),
+        item_name: item_name.to_string(),
+        target_path: target_path.to_string_lossy().to_string(),
+        status: "ok".to_string(),
+        synced_at: None,
+        error_message: None,
+    }
+}
+
+fn with_result(mut detail: ProjectSyncDetail, result: Result<(), String>) -> ProjectSyncDetail {
+    match result {
+        Ok(()) => {
+            detail.status = "ok".to_string();
+            detail.synced_at = Some(now_ms());
+        }
+        Err(error) => {
+            detail.status = "error".to_string();
+            detail.error_message = Some(error);
+        }
+    }
+    detail
+}
+
+fn enabled_targets(workspace: &ProjectWorkspace) -> Vec<&'static ProjectToolTarget> {
+    workspace
+        .enabled_tools
+        .iter()
+        .filter_map(|key| project_tool_target(key))
+        .collect()
+}
+
+/// Write every enabled server and skill to each enabled tool, then remove the
+/// items a previous sync wrote that are no longer part of the workspace.
+pub fn sync_project_workspace(
+    root: &Path,
+    workspace: &ProjectWorkspace,
+    servers: &[McpServer],
+    skills: &[ProjectSkillSource],
+) -> Vec<ProjectSyncDetail> {
+    let mut details = Vec::new();
+    for target in enabled_targets(workspace) {
+        if let (Some(tool), Some(config_path)) =
+            (target.runtime_tool(root), target.mcp_config_file(root))
+        {
+            for server in servers {
+                let result =
+                    sync_server_to_config_path(&tool, &config_path, server, true).map(|_| ());
+                details.push(with_result(
+                    detail(
+                        target.key,
+                        ITEM_TYPE_MCP,
+                        &server.id,
+                        &server.name,
+                        &config_path,
+                    ),
+                    result,
+                ));
+            }
+        }
+        if let Some(skills_root) = target.skills_root(root) {
+            for skill in skills {
+                let (skill_path, result) = match target.skill_path(root, &skill.name) {
+                    Some(Ok(skill_path)) => {
+                        let result = sync_copy_target_path(
+                            &skill.source_path,
+                            &skill_path.to_string_lossy(),
+                        )
+                        .map(|_| ())
+                        .map_err(|error| error.to_string());
+                        (skill_path, result)
+                    }
+                    Some(Err(error)) => (skills_root.clone(), Err(error)),
+                    None => continue,
+                };
+                details.push(with_result(
+                    detail(
+                        target.key,
+                        ITEM_TYPE_SKILL,
+                        &skill.id,
+                        &skill.name,
+                        &skill_path,
+                    ),
+                    result,
+                ));
+            }
+        }
+    }
+
+    for previous in &workspace.sync_details {
+        if details.iter().any(|current| current.same_item(previous)) {
+            continue;
+        }
+        if let Err(error) = remove_synced_item(root, previous) {
+            log::warn!(
+                "Failed to remove {} '{}' from project workspace '{}' for {}: {}",
+                previous.item_type,
+                previous.item_name,
+                workspace.name,
+                previous.tool,
+                error
+            );
+        }
+    }
+    details
+}
+
+/// Compare the project files with what a sync would write. Items written by
+/// the last sync but no longer in the workspace are reported as `stale` while
+/// they are still on disk.
+pub fn check_project_workspace_drift(
+    root: &Path,
+    workspace: &ProjectWorkspace,
+    servers: &[McpServer],
+    skills: &[ProjectSkillSource],
+) -> Vec<ProjectSyncDetail> {
+    let mut details = Vec::new();
+    for target in enabled_targets(workspace) {
+        if let (Some(tool), Some(config_path)) =
+            (target.runtime_tool(root), target.mcp_config_file(root))
+        {
+            for server in servers {
+                let mut item = detail(
+                    target.key,
+                    ITEM_TYPE_MCP,
+                    &server.id,
+                    &server.name,
+                    &config_path,
+                );
+                let state =
+                    expected_server_entry(&tool, &config_path, server, true).and_then(|expected| {
+                        read_server_entry_from_config_path(&tool, &config_path, &server.name)
+                            .map(|actual| (expected, actual))
+                    });
+                match state {
+                    Ok((_, None)) => item.status = "missing".to_string(),
+                    Ok((expected, Some(actual))) if expected.as_ref() != Some(&actual) => {
+                        item.status = "drift".to_string()
+                    }
+                    Ok(_) => {}
+                    Err(error) => {
+                        item.status = "error".to_string();
+                        item.error_message = Some(error);
+                    }
+                }
+                details.push(item);
+            }
+        }
+        if let Some(skills_root) = target.skills_root(root) {
+            for skill in skills {
+                let skill_path = match target.skill_path(root, &skill.name) {
+                    Some(Ok(skill_path)) => skill_path,
+                    Some(Err(error)) => {
+                        let mut item = detail(
+                            target.key,
+                            ITEM_TYPE_SKILL,
+                            &skill.id,
+                            &skill.name,
+                            &skills_root,
+                        );
+                        item.status = "error".to_string();
+                        item.error_message = Some(error);
+                        details.push(item);
+                        continue;
+                    }
+                    None => continue,
+                };
+                let mut item = detail(
+                    target.key,
+                    ITEM_TYPE_SKILL,
+                    &skill.id,
+                    &skill.name,
+                    &skill_path,
+                );
+                if !skill_path.exists() {
+                    item.status = "missing".to_string();
+                } else {
+                    match (hash_dir(&skill.source_path), hash_dir(&skill_path)) {
+                        (Ok(expected), Ok(actual)) if expected != actual => {
+                            item.status = "drift".to_string()
+                        }
+                        (Ok(_), Ok(_)) => {}
+                        (Err(error), _) | (_, Err(error)) => {
+                            item.status = "error".to_string();
+                            item.error_message = Some(error.to_string());
+                        }
+                    }
+                }
+                details.push(item);
+            }
+        }
+    }
+
+    for item in details.iter_mut() {
+        item.synced_at = workspace
+            .sync_details
+            .iter()
+            .find(|previous| previous.same_item(item))
+            .and_then(|previous| previous.synced_at);
+    }
+
+    for previous in &workspace.sync_details {
+        if details.iter().any(|current| current.same_item(previous)) {
+            continue;
+        }
+        if synced_item_exists(root, previous) {
+            let mut item = previous.clone();
+            item.status = "stale".to_string();
+            item.error_message = None;
+            details.push(item);
+        }
+    }
+    details
+}
+
+/// Remove everything the last sync wrote, e.g. before deleting a workspace.
+pub fn remove_project_workspace_files(root: &Path, workspace: &ProjectWorkspace) -> Vec<String> {
+    workspace
+        .sync_details
+        .iter()
+        .filter_map(|item| {
+            remove_synced_item(root, item).err().map(|error| {
+                format!(
+                    "{} '{}' for {}: {}",
+                    item.item_type, item.item_name, item.tool, error
+                )
+            })
+        })
+        .collect()
+}
+
+/// Whether the recorded target is exactly where a sync of this item writes,
+/// so an edited or failed record cannot point a removal at other files.
+fn is_owned_target(root: &Path, item: &ProjectSyncDetail) -> bool {
+    let Some(target) = project_tool_target(&item.tool) else {
+        return false;
+    };
+    let expected = match item.item_type.as_str() {
+        ITEM_TYPE_MCP => target.mcp_config_file(root),
+        ITEM_TYPE_SKILL => target
+            .skill_path(root, &item.item_name)
+            .and_then(Result::ok),
+        _ => None,
+    };
+    expected.is_some_and(|expected| expected == Path::new(&item.target_path))
+}
+
+fn synced_item_exists(root: &Path, item: &ProjectSyncDetail) -> bool {
+    if !is_owned_target(root, item) {
+        return false;
+    }
+    let target_path = Path::new(&item.target_path);
+    match item.item_type.as_str() {
+        ITEM_TYPE_MCP => project_tool_target(&item.tool)
+            .and_then(|target| target.runtime_tool(root))
+            .and_then(|tool| {
+                read_server_entry_from_config_path(&tool, target_path, &item.item_name).ok()
+            })
+            .flatten()
+            .is_some(),
+        ITEM_TYPE_SKILL => target_path.exists(),
+        _ => false,
+    }
+}
+
+fn remove_synced_item(root: &Path, item: &ProjectSyncDetail) -> Result<(), String> {
+    let target_path = Path::new(&item.target_path);
+    // Only touch files this workspace owns, even if the record was edited.
+    if !is_within_root(root, target_path) || !is_owned_target(root, item) {
+        return Err(format!(
+            "Refusing to remove {} outside the project root",
+            target_path.display()
+        ));
+    }
+    match item.item_type.as_str() {
+        ITEM_TYPE_MCP => {
+            let Some(tool) = project_tool_target(&item.tool).and_then(|t| t.runtime_tool(root))
+            else {
+                return Ok(());
+            };
+            remove_server_from_config_path(&tool, target_path, &item.item_name)
+        }
+        ITEM_TYPE_SKILL => remove_path(&item.target_path),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn build_server(command: &str) -> McpServer {
+        McpServer {
+            id: "server-1".to_string(),
+            name: "docs".to_string(),
+            server_type: "stdio".to_string(),
+            server_config: json!({
+                "command": command,
+                "args": ["serve"],
+            }),
+            enabled_tools: vec![],
+            sync_details: None,
+            probe_result: None,
+            description: None,
+            user_group: None,
+            user_note: None,
+            tags: vec![],
+            timeout: None,
+            sort_index: 0,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    fn build_workspace(root: &Path) -> ProjectWorkspace {
+        ProjectWorkspace {
+            id: "workspace-1".to_string(),
+            name: "demo".to_string(),
+            root_path: root.to_string_lossy().to_string(),
+            enabled_tools: vec!["claude_code".to_string(), "codex".to_string()],
+            mcp_server_ids: vec!["server-1".to_string()],
+            skill_ids: vec!["skill-1".to_string()],
+            sync_details: vec![],
+            last_synced_at: None,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    fn status_of<'a>(details: &'a [ProjectSyncDetail], tool: &str, item_type: &str) -> &'a str {
+        details
+            .iter()
+            .find(|item| item.tool == tool && item.item_type == item_type)
+            .map(|item| item.status.as_str())
+            .expect("detail present")
+    }
+
+    #[test]
+    fn sync_writes_project_files_and_drift_check_tracks_edits() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let root = temp.path().join("repo");
+        let source = temp.path().join("central").join("reviewer");
+        std::fs::create_dir_all(&root).expect("create root");
+        std::fs::create_dir_all(&source).expect("create skill source");
+        std::fs::write(source.join("SKILL.md"), "---\nname: reviewer\n---\n").expect("write skill");
+
+        let mut workspace = build_workspace(&root);
+        let servers = vec![build_server("node")];
+        let skills = vec![ProjectSkillSource {
+            id: "skill-1".to_string(),
+            name: "reviewer".to_string(),
+            source_path: source.clone(),
+        }];
+
+        let before = check_project_workspace_drift(&root, &workspace, &servers, &skills);
+        assert!(before.iter().all(|item| item.status == "missing"));
+
+        workspace.sync_details = sync_project_workspace(&root, &workspace, &servers, &skills);
+        assert_eq!(workspace.sync_details.len(), 4);
+        assert!(workspace
+            .sync_details
+            .iter()
+            .all(|item| item.status == "ok"));
+        let mcp_json: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(root.join(".mcp.json")).expect("read .mcp.json"),
+        )
+        .expect("parse .mcp.json");
+        assert_eq!(mcp_json["mcpServers"]["docs"]["command"], "node");
+        let codex_toml =
+            std::fs::read_to_string(root.join(".codex/config.toml")).expect("read codex config");
+        assert!(codex_toml.contains("[mcp_servers.docs]"));
+        assert!(root.join(".claude/skills/reviewer/SKILL.md").is_file());
+        assert!(
+            !std::fs::symlink_metadata(root.join(".claude/skills/reviewer"))
+                .expect("skill target")
+                .file_type()
+                .is_symlink()
+        );
+
+        let clean = check_project_workspace_drift(&root, &workspace, &servers, &skills);
+        assert!(clean.iter().all(|item| item.status == "ok"));
+
+        let edited = vec![build_server("bun")];
+        let drift = check_project_workspace_drift(&root, &workspace, &edited, &skills);
+        assert_eq!(status_of(&drift, "claude_code", ITEM_TYPE_MCP), "drift");
+        assert_eq!(status_of(&drift, "codex", ITEM_TYPE_MCP), "drift");
+
+        std::fs::write(
+            source.join("SKILL.md"),
+            "---\nname: reviewer\n---\nupdated\n",
+        )
+        .expect("update skill");
+        std::fs::remove_dir_all(root.join(".codex/skills/reviewer")).expect("remove copy");
+        let drift = check_project_workspace_drift(&root, &workspace, &servers, &skills);
+        assert_eq!(status_of(&drift, "claude_code", ITEM_TYPE_SKILL), "drift");
+        assert_eq!(status_of(&drift, "codex", ITEM_TYPE_SKILL), "missing");
+    }
+
+    #[test]
+    fn removed_items_are_stale_until_next_sync_cleans_them() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let root = temp.path().join("repo");
+        let source = temp.path().join("central").join("reviewer");
+        std::fs::create_dir_all(&root).expect("create root");
+        std::fs::create_dir_all(&source).expect("create skill source");
+        std::fs::write(source.join("SKILL.md"), "---\nname: reviewer\n---\n").expect("write skill");
+        std::fs::write(
+            root.join(".mcp.json"),
+            r#"{"mcpServers":{"keep":{"command":"keep"}}}"#,
+        )
+        .expect("write existing config");
+
+        let mut workspace = build_workspace(&root);
+        workspace.enabled_tools = vec!["claude_code".to_string()];
+        let skills = vec![ProjectSkillSource {
+            id: "skill-1".to_string(),
+            name: "reviewer".to_string(),
+            source_path: source,
+        }];
+        workspace.sync_details =
+            sync_project_workspace(&root, &workspace, &[build_server("node")], &skills);
+
+        let stale = check_project_workspace_drift(&root, &workspace, &[], &[]);
+        assert_eq!(stale.len(), 2);
+        assert!(stale.iter().all(|item| item.status == "stale"));
+
+        workspace.sync_details = sync_project_workspace(&root, &workspace, &[], &[]);
+        assert!(workspace.sync_details.is_empty());
+        let mcp_json: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(root.join(".mcp.json")).expect("read .mcp.json"),
+        )
+        .expect("parse .mcp.json");
+        assert!(mcp_json["mcpServers"].get("docs").is_none());
+        assert_eq!(mcp_json["mcpServers"]["keep"]["command"], "keep");
+        assert!(!root.join(".claude/skills/reviewer").exists());
+    }
+
+    #[test]
+    fn removal_outside_project_root_is_refused() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let root = temp.path().join("repo");
+        let outside = temp.path().join("elsewhere");
+        std::fs::create_dir_all(&root).expect("create root");
+        std::fs::create_dir_all(&outside).expect("create outside dir");
+
+        let item = detail(
+            "claude_code",
+            ITEM_TYPE_SKILL,
+            "skill-1",
+            "reviewer",
+            &outside,
+        );
+        assert!(remove_synced_item(&root, &item).is_err());
+        assert!(outside.exists());
+    }
+
+    #[test]
+    fn renamed_items_are_stale_and_removed_by_the_next_sync() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let root = temp.path().join("repo");
+        let source = temp.path().join("central").join("reviewer");
+        std::fs::create_dir_all(&root).expect("create root");
+        std::fs::create_dir_all(&source).expect("create skill source");
+        std::fs::write(source.join("SKILL.md"), "---\nname: reviewer\n---\n").expect("write skill");
+
+        let mut workspace = build_workspace(&root);
+        workspace.enabled_tools = vec!["claude_code".to_string()];
+        let mut skill = ProjectSkillSource {
+            id: "skill-1".to_string(),
+            name: "reviewer".to_string(),
+            source_path: source,
+        };
+        let mut server = build_server("node");
+        workspace.sync_details =
+            sync_project_workspace(&root, &workspace, &[server.clone()], &[skill.clone()]);
+
+        skill.name = "code-reviewer".to_string();
+        server.name = "docs-v2".to_string();
+        let drift =
+            check_project_workspace_drift(&root, &workspace, &[server.clone()], &[skill.clone()]);
+        let stale: Vec<&str> = drift
+            .iter()
+            .filter(|item| item.status == "stale")
+            .map(|item| item.item_name.as_str())
+            .collect();
+        assert_eq!(stale, vec!["docs", "reviewer"]);
+
+        workspace.sync_details = sync_project_workspace(&root, &workspace, &[server], &[skill]);
+        assert!(!root.join(".claude/skills/reviewer").exists());
+        assert!(root.join(".claude/skills/code-reviewer/SKILL.md").is_file());
+        let mcp_json: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(root.join(".mcp.json")).expect("read .mcp.json"),
+        )
+        .expect("parse .mcp.json");
+        assert!(mcp_json["mcpServers"].get("docs").is_none());
+        assert!(mcp_json["mcpServers"].get("docs-v2").is_some());
+    }
+
+    #[test]
+    fn skill_names_cannot_escape_the_skills_directory() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let root = temp.path().join("repo");
+        let source = temp.path().join("central").join("escape");
+        std::fs::create_dir_all(&root).expect("create root");
+        std::fs::create_dir_all(&source).expect("create skill source");
+        std::fs::write(source.join("SKILL.md"), "---\nname: escape\n---\n").expect("write skill");
+
+        let kept_skill = root.join(".claude/skills/keep");
+        std::fs::create_dir_all(&kept_skill).expect("create existing skill");
+
+        let mut workspace = build_workspace(&root);
+        workspace.enabled_tools = vec!["claude_code".to_string()];
+        let skills = vec![ProjectSkillSource {
+            id: "skill-1".to_string(),
+            name: "../../escape".to_string(),
+            source_path: source,
+        }];
+        workspace.sync_details = sync_project_workspace(&root, &workspace, &[], &skills);
+        assert_eq!(
+            status_of(&workspace.sync_details, "claude_code", ITEM_TYPE_SKILL),
+            "error"
+        );
+        assert!(!temp.path().join("escape").exists());
+        assert!(!root.join(".claude/escape").exists());
+
+        // The failed record points at the skills directory, which is never removed.
+        let stale = check_project_workspace_drift(&root, &workspace, &[], &[]);
+        assert!(stale.is_empty());
+        workspace.sync_details = sync_project_workspace(&root, &workspace, &[], &[]);
+        assert!(kept_skill.exists());
+    }
+
+    #[test]
+    fn paths_with_parent_segments_are_not_within_the_root() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let root = temp.path().join("repo");
+        std::fs::create_dir_all(root.join(".claude/skills")).expect("create root");
+
+        assert!(is_within_root(&root, &root.join(".claude/skills/reviewer")));
+        assert!(!is_within_root(&root, &root));
+        assert!(!is_within_root(&root, &root.join("../elsewhere")));
+        assert!(!is_within_root(
+            &root,
+            &root.join(".claude/../../elsewhere")
+        ));
+        assert!(!is_within_root(&root, &temp.path().join("elsewhere")));
+
+        #[cfg(unix)]
+        {
+            let outside = temp.path().join("outside");
+            std::fs::create_dir_all(&outside).expect("create outside dir");
+            std::os::unix::fs::symlink(&outside, root.join("linked")).expect("create symlink");
+            assert!(!is_within_root(&root, &root.join("linked/reviewer")));
+        }
+    }
+}
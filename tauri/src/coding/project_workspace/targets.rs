@@ -0,0 +1,161 @@
+//! Project-level file locations
+//!
+//! Paths are relative to the project root. Only tools that read MCP servers or
+//! skills from the repository itself are listed; the MCP format and field
+//! follow the tool's user-level config so the same writers can be reused.
+
+use std::path::{Component, Path, PathBuf};
+
+use super::types::ProjectToolTargetDto;
+use crate::coding::expand_local_path;
+use crate::coding::skills::central_repo::skill_storage_dir_name;
+use crate::coding::tools::{builtin_tool_by_key, RuntimeTool};
+
+#[derive(Clone, Copy, Debug)]
+pub struct ProjectToolTarget {
+    pub key: &'static str,
+    pub mcp_config_path: Option<&'static str>,
+    pub mcp_config_format: Option<&'static str>,
+    pub mcp_field: Option<&'static str>,
+    pub skills_dir: Option<&'static str>,
+}
+
+pub const PROJECT_TOOL_TARGETS: &[ProjectToolTarget] = &[
+    ProjectToolTarget {
+        key: "claude_code",
+        mcp_config_path: Some(".mcp.json"),
+        mcp_config_format: Some("json"),
+        mcp_field: Some("mcpServers"),
+        skills_dir: Some(".claude/skills"),
+    },
+    ProjectToolTarget {
+        key: "codex",
+        mcp_config_path: Some(".codex/config.toml"),
+        mcp_config_format: Some("toml"),
+        mcp_field: Some("mcp_servers"),
+        skills_dir: Some(".codex/skills"),
+    },
+    ProjectToolTarget {
+        key: "gemini_cli",
+        mcp_config_path: Some(".gemini/settings.json"),
+        mcp_config_format: Some("json"),
+        mcp_field: Some("mcpServers"),
+        skills_dir: Some(".gemini/skills"),
+    },
+    ProjectToolTarget {
+        key: "qwen_code",
+        mcp_config_path: Some(".qwen/settings.json"),
+        mcp_config_format: Some("json"),
+        mcp_field: Some("mcpServers"),
+        skills_dir: Some(".qwen/skills"),
+    },
+    ProjectToolTarget {
+        key: "cursor",
+        mcp_config_path: Some(".cursor/mcp.json"),
+        mcp_config_format: Some("json"),
+        mcp_field: Some("mcpServers"),
+        skills_dir: Some(".cursor/skills"),
+    },
+    ProjectToolTarget {
+        key: "opencode",
+        mcp_config_path: Some("opencode.json"),
+        mcp_config_format: Some("json"),
+        mcp_field: Some("mcp"),
+        skills_dir: Some(".opencode/skills"),
+    },
+    ProjectToolTarget {
+        key: "shared_agents",
+        mcp_config_path: None,
+        mcp_config_format: None,
+        mcp_field: None,
+        skills_dir: Some(".agents/skills"),
+    },
+];
+
+pub fn project_tool_target(key: &str) -> Option<&'static ProjectToolTarget> {
+    PROJECT_TOOL_TARGETS.iter().find(|target| target.key == key)
+}
+
+impl ProjectToolTarget {
+    /// Runtime tool whose MCP config points at the project-level file, so the
+    /// tool-specific format handling in `config_sync` applies unchanged.
+    pub fn runtime_tool(&self, root: &Path) -> Option<RuntimeTool> {
+        let config_path = self.mcp_config_path?;
+        let mut tool = builtin_tool_by_key(self.key).map(RuntimeTool::from)?;
+        tool.mcp_config_path = Some(root.join(config_path).to_string_lossy().to_string());
+        tool.mcp_config_format = self.mcp_config_format.map(str::to_string);
+        tool.mcp_field = self.mcp_field.map(str::to_string);
+        Some(tool)
+    }
+
+    pub fn mcp_config_file(&self, root: &Path) -> Option<PathBuf> {
+        self.mcp_config_path.map(|path| root.join(path))
+    }
+
+    pub fn skills_root(&self, root: &Path) -> Option<PathBuf> {
+        self.skills_dir.map(|dir| root.join(dir))
+    }
+
+    /// Directory a skill is copied to. The skill name must be a single path
+    /// segment so the copy cannot land outside the tool's skills directory.
+    pub fn skill_path(&self, root: &Path, skill_name: &str) -> Option<Result<PathBuf, String>> {
+        let skills_root = self.skills_root(root)?;
+        let dir_name = skill_storage_dir_name(skill_name);
+        let mut components = Path::new(&dir_name).components();
+        Some(match (components.next(), components.next()) {
+            (Some(Component::Normal(segment)), None) => Ok(skills_root.join(segment)),
+            _ => Err(format!(
+                "Invalid skill name for a project directory: {skill_name}"
+            )),
+        })
+    }
+
+    pub fn to_dto(&self) -> ProjectToolTargetDto {
+        ProjectToolTargetDto {
+            key: self.key.to_string(),
+            display_name: builtin_tool_by_key(self.key)
+                .map(|tool| tool.display_name.to_string())
+                .unwrap_or_else(|| self.key.to_string()),
+            mcp_config_path: self.mcp_config_path.map(str::to_string),
+            skills_dir: self.skills_dir.map(str::to_string),
+        }
+    }
+}
+
+/// Expand and validate a workspace root; it must be an existing directory.
+pub fn resolve_project_root(root_path: &str) -> Result<PathBuf, String> {
+    let trimmed = root_path.trim();
+    if trimmed.is_empty() {
+        return Err("Project root path cannot be empty".to_string());
+    }
+    let root = PathBuf::from(expand_local_path(trimmed)?);
+    if !root.is_dir() {
+        return Err(format!(
+            "Project root is not a directory: {}",
+            root.display()
+        ));
+    }
+    Ok(root)
+}
+
+/// Whether `path` is strictly inside `root`, used before deleting anything we
+/// wrote. `.` and `..` segments are rejected, and the parent directory is
+/// resolved so a symlinked directory cannot redirect the removal elsewhere.
+pub fn is_within_root(root: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return false;
+    };
+    let mut components = relative.components().peekable();
+    if components.peek().is_none()
+        || !components.all(|component| matches!(component, Component::Normal(_)))
+    {
+        return false;
+    }
+    match path.parent().map(Path::canonicalize) {
+        Some(Ok(parent)) => root
+            .canonicalize()
+            .is_ok_and(|root| parent.starts_with(root)),
+        // A missing parent leaves nothing on disk to remove.
+        _ => true,
+    }
+}
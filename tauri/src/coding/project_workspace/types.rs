@@ -0,0 +1,86 @@
+//! Type definitions for project workspaces
+//!
+//! A project workspace is a registered repository with its own set of MCP
+//! servers and skills, synced to the project-level files each tool reads.
+
+use serde::{Deserialize, Serialize};
+
+pub const ITEM_TYPE_MCP: &str = "mcp";
+pub const ITEM_TYPE_SKILL: &str = "skill";
+
+/// Project workspace record stored in SQLite JSONB
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProjectWorkspace {
+    pub id: String,
+    pub name: String,
+    /// Repository root, stored as entered (may start with `~/`)
+    pub root_path: String,
+    /// Tool keys whose project-level files are managed for this workspace
+    #[serde(default)]
+    pub enabled_tools: Vec<String>,
+    #[serde(default)]
+    pub mcp_server_ids: Vec<String>,
+    #[serde(default)]
+    pub skill_ids: Vec<String>,
+    /// Result of the last sync, one entry per tool and item
+    #[serde(default)]
+    pub sync_details: Vec<ProjectSyncDetail>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_synced_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Sync state of one MCP server or skill for one tool in a project.
+///
+/// Mirrors `McpSyncDetail` with the item it refers to. Besides `ok` and
+/// `error`, drift checks report `missing` (never written or deleted),
+/// `drift` (edited by hand since the last sync) and `stale` (still on disk
+/// after being removed from the workspace).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ProjectSyncDetail {
+    pub tool: String,
+    pub item_type: String, // "mcp" | "skill"
+    pub item_id: String,
+    pub item_name: String,
+    pub target_path: String,
+    pub status: String, // "ok" | "error" | "missing" | "drift" | "stale"
+    pub synced_at: Option<i64>,
+    pub error_message: Option<String>,
+}
+
+impl ProjectSyncDetail {
+    /// Same item written to the same place. A renamed server or skill leaves
+    /// its old entry behind, so the name and target are part of the identity.
+    pub fn same_item(&self, other: &ProjectSyncDetail) -> bool {
+        self.tool == other.tool
+            && self.item_type == other.item_type
+            && self.item_id == other.item_id
+            && self.item_name == other.item_name
+            && self.target_path == other.target_path
+    }
+}
+
+/// Input for creating or updating a project workspace
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProjectWorkspaceInput {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    pub root_path: String,
+    #[serde(default)]
+    pub enabled_tools: Vec<String>,
+    #[serde(default)]
+    pub mcp_server_ids: Vec<String>,
+    #[serde(default)]
+    pub skill_ids: Vec<String>,
+}
+
+/// Project-level file locations of a supported tool (frontend display)
+#[derive(Debug, Serialize)]
+pub struct ProjectToolTargetDto {
+    pub key: String,
+    pub display_name: String,
+    pub mcp_config_path: Option<String>,
+    pub skills_dir: Option<String>,
+}
@@ -0,0 +1,399 @@
+//! Gateway-issued client tokens.
+//!
+//! Tokens let clients on other machines (a team VM, a devcontainer) use the
+//! gateway once it listens on a LAN interface. Local clients keep working
+//! without one so CLI takeover configs do not need to change; a local client
+//! that does present a token is still checked and labelled.
+
+use super::types::{
+    GatewayAccessToken, GatewayAccessTokenCreated, GatewayAccessTokenInput, GatewayCliKey,
+};
+use crate::db::helpers::{db_count, db_delete, db_get, db_list, db_put};
+use crate::db::schema::{DbTable, OrderDirection, OrderField, OrderSpec};
+use crate::db::SqliteDbState;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Every issued token starts with this prefix. Credentials without it are
+/// provider placeholders written by CLI takeover and are never treated as
+/// gateway tokens.
+pub const ACCESS_TOKEN_PREFIX: &str = "atb_";
+/// Dedicated header for clients that already use `Authorization` for
+/// something else. The gateway never forwards it upstream.
+pub const ACCESS_TOKEN_HEADER: &str = "x-ai-toolbox-token";
+
+const TOKEN_SECRET_BYTES: usize = 32;
+const TOKEN_HINT_CHARS: usize = 4;
+const MAX_LABEL_CHARS: usize = 64;
+
+/// Persisted form of a token; only the SHA-256 digest of the secret is kept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredAccessToken {
+    #[serde(default)]
+    pub id: String,
+    pub label: String,
+    pub token_hash: String,
+    pub token_hint: String,
+    pub cli_keys: Vec<GatewayCliKey>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredAccessToken {
+    fn to_public(&self) -> GatewayAccessToken {
+        GatewayAccessToken {
+            id: self.id.clone(),
+            label: self.label.clone(),
+            token_hint: self.token_hint.clone(),
+            cli_keys: self.cli_keys.clone(),
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// Outcome of checking an inbound request against the issued tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GatewayAccessDecision {
+    Allowed { client_label: Option<String> },
+    Denied(GatewayAccessDenial),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GatewayAccessDenial {
+    /// Missing, unknown or revoked token.
+    Unauthorized(String),
+    /// Valid token that is not scoped to the requested CLI route.
+    Forbidden(String),
+}
+
+pub fn load_access_tokens(conn: &Connection) -> Result<Vec<StoredAccessToken>, String> {
+    let order = OrderSpec::single(OrderField::created_at(OrderDirection::Asc));
+    db_list(conn, DbTable::ProxyGatewayAccessToken, Some(&order))?
+        .into_iter()
+        .map(|record| {
+            serde_json::from_value(record)
+                .map_err(|error| format!("Failed to parse gateway access token: {error}"))
+        })
+        .collect()
+}
+
+pub fn has_access_tokens(conn: &Connection) -> Result<bool, String> {
+    Ok(db_count(conn, DbTable::ProxyGatewayAccessToken)? > 0)
+}
+
+/// Whether the gateway may bind a LAN interface. Read failures keep it local.
+pub fn lan_listen_allowed(db: &SqliteDbState) -> bool {
+    db.with_conn(has_access_tokens).unwrap_or(false)
+}
+
+pub fn list_access_tokens(db: &SqliteDbState) -> Result<Vec<GatewayAccessToken>, String> {
+    let tokens = db.with_conn(load_access_tokens)?;
+    Ok(tokens.iter().map(StoredAccessToken::to_public).collect())
+}
+
+pub fn create_access_token(
+    db: &SqliteDbState,
+    input: GatewayAccessTokenInput,
+) -> Result<GatewayAccessTokenCreated, String> {
+    let label = normalize_label(&input.label)?;
+    let cli_keys = normalize_cli_keys(input.cli_keys)?;
+    let secret = generate_token_secret();
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    let stored = StoredAccessToken {
+        id: id.clone(),
+        label,
+        token_hash: hash_token(&secret),
+        token_hint: token_hint(&secret),
+        cli_keys,
+        created_at: Utc::now(),
+    };
+
+    db.with_conn(|conn| {
+        let existing = load_access_tokens(conn)?;
+        if existing
+            .iter()
+            .any(|token| token.label.eq_ignore_ascii_case(&stored.label))
+        {
+            return Err(format!(
+                "A gateway access token labelled '{}' already exists",
+                stored.label
+            ));
+        }
+        let data = serde_json::to_value(&stored)
+            .map_err(|error| format!("Failed to serialize gateway access token: {error}"))?;
+        db_put(conn, DbTable::ProxyGatewayAccessToken, &id, &data)
+    })?;
+
+    Ok(GatewayAccessTokenCreated {
+        token: stored.to_public(),
+        secret,
+    })
+}
+
+/// Delete a token. The last token cannot be removed while the gateway is set to
+/// listen on a LAN interface, otherwise remote clients would lose access
+/// silently on the next start.
+pub fn delete_access_token(
+    db: &SqliteDbState,
+    id: &str,
+    listen_host_is_loopback: bool,
+) -> Result<(), String> {
+    db.with_conn(|conn| {
+        if db_get(conn, DbTable::ProxyGatewayAccessToken, id)?.is_none() {
+            return Err(format!("Gateway access token '{id}' not found"));
+        }
+        if !listen_host_is_loopback && db_count(conn, DbTable::ProxyGatewayAccessToken)? <= 1 {
+            return Err(
+                "Switch the gateway listen host back to 127.0.0.1 before deleting the last access token"
+                    .to_string(),
+            );
+        }
+        db_delete(conn, DbTable::ProxyGatewayAccessToken, id).map(|_| ())
+    })
+}
+
+/// Check an inbound request. `cli_key` is the route the request targets, if
+/// any; `peer_is_local` is true for connections from this machine.
+pub fn authorize_request(
+    tokens: &[StoredAccessToken],
+    headers: &[(String, String)],
+    cli_key: Option<GatewayCliKey>,
+    peer_is_local: bool,
+) -> GatewayAccessDecision {
+    let Some(presented) = presented_token(headers) else {
+        return if peer_is_local {
+            GatewayAccessDecision::Allowed { client_label: None }
+        } else {
+            GatewayAccessDecision::Denied(GatewayAccessDenial::Unauthorized(
+                "Requests from other machines must present a gateway access token".to_string(),
+            ))
+        };
+    };
+
+    let presented_hash = hash_token(presented);
+    let Some(token) = tokens
+        .iter()
+        .find(|token| token.token_hash == presented_hash)
+    else {
+        return GatewayAccessDecision::Denied(GatewayAccessDenial::Unauthorized(
+            "Unknown or revoked gateway access token".to_string(),
+        ));
+    };
+
+    if let Some(cli_key) = cli_key {
+        if !token.cli_keys.contains(&cli_key) {
+            return GatewayAccessDecision::Denied(GatewayAccessDenial::Forbidden(format!(
+                "Gateway access token '{}' is not allowed to use the {} route",
+                token.label,
+                cli_key.as_str()
+            )));
+        }
+    }
+
+    GatewayAccessDecision::Allowed {
+        client_label: Some(token.label.clone()),
+    }
+}
+
+/// Find a gateway token among the credentials a client may send. CLIs only let
+/// users set an API key, so the token is accepted wherever that key travels.
+fn presented_token(headers: &[(String, String)]) -> Option<&str> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case(ACCESS_TOKEN_HEADER)
+                || name.eq_ignore_ascii_case("x-api-key")
+                || name.eq_ignore_ascii_case("x-goog-api-key")
+            {
+                Some(value)
+            } else if name.eq_ignore_ascii_case("authorization") {
+                value
+                    .get(..7)
+                    .filter(|scheme| scheme.eq_ignore_ascii_case("bearer "))
+                    .map(|_| value[7..].trim())
+            } else {
+                None
+            }
+        })
+        .find(|value| value.starts_with(ACCESS_TOKEN_PREFIX))
+}
+
+fn normalize_label(label: &str) -> Result<String, String> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("Gateway access token label cannot be empty".to_string());
+    }
+    if label.chars().count() > MAX_LABEL_CHARS {
+        return Err(format!(
+            "Gateway access token label must be at most {MAX_LABEL_CHARS} characters"
+        ));
+    }
+    Ok(label.to_string())
+}
+
+fn normalize_cli_keys(cli_keys: Vec<GatewayCliKey>) -> Result<Vec<GatewayCliKey>, String> {
+    let supported = GatewayCliKey::supported_mvp();
+    let mut normalized = Vec::new();
+    for cli_key in cli_keys {
+        if !supported.contains(&cli_key) {
+            return Err(format!(
+                "Gateway access tokens cannot be scoped to {}",
+                cli_key.as_str()
+            ));
+        }
+        if !normalized.contains(&cli_key) {
+            normalized.push(cli_key);
+        }
+    }
+    if normalized.is_empty() {
+        return Err("Gateway access token must allow at least one CLI".to_string());
+    }
+    Ok(normalized)
+}
+
+fn generate_token_secret() -> String {
+    let mut bytes = [0_u8; TOKEN_SECRET_BYTES];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    format!("{ACCESS_TOKEN_PREFIX}{}", URL_SAFE_NO_PAD.encode(bytes))
+}
+
+fn hash_token(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+fn token_hint(secret: &str) -> String {
+    let chars = secret.chars().collect::<Vec<_>>();
+    chars[chars.len().saturating_sub(TOKEN_HINT_CHARS)..]
+        .iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(label: &str, secret: &str, cli_keys: Vec<GatewayCliKey>) -> StoredAccessToken {
+        StoredAccessToken {
+            id: label.to_string(),
+            label: label.to_string(),
+            token_hash: hash_token(secret),
+            token_hint: token_hint(secret),
+            cli_keys,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn header(name: &str, value: &str) -> Vec<(String, String)> {
+        vec![(name.to_string(), value.to_string())]
+    }
+
+    #[test]
+    fn local_requests_without_token_are_allowed_unlabelled() {
+        let decision = authorize_request(&[], &[], Some(GatewayCliKey::Claude), true);
+        assert_eq!(
+            decision,
+            GatewayAccessDecision::Allowed { client_label: None }
+        );
+    }
+
+    #[test]
+    fn remote_requests_without_token_are_rejected() {
+        let tokens = vec![stored("vm", "atb_secret", vec![GatewayCliKey::Claude])];
+        let headers = header("x-api-key", "gateway-placeholder");
+        assert!(matches!(
+            authorize_request(&tokens, &headers, Some(GatewayCliKey::Claude), false),
+            GatewayAccessDecision::Denied(GatewayAccessDenial::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn token_is_accepted_from_any_cli_credential_header() {
+        let tokens = vec![stored(
+            "devcontainer",
+            "atb_secret",
+            vec![GatewayCliKey::Claude, GatewayCliKey::Codex],
+        )];
+        for headers in [
+            header("Authorization", "Bearer atb_secret"),
+            header("X-Api-Key", "atb_secret"),
+            header(ACCESS_TOKEN_HEADER, "atb_secret"),
+        ] {
+            assert_eq!(
+                authorize_request(&tokens, &headers, Some(GatewayCliKey::Codex), false),
+                GatewayAccessDecision::Allowed {
+                    client_label: Some("devcontainer".to_string())
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn token_scoped_to_other_cli_is_forbidden() {
+        let tokens = vec![stored("vm", "atb_secret", vec![GatewayCliKey::Claude])];
+        let headers = header("x-goog-api-key", "atb_secret");
+        assert!(matches!(
+            authorize_request(&tokens, &headers, Some(GatewayCliKey::Gemini), false),
+            GatewayAccessDecision::Denied(GatewayAccessDenial::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_token_is_rejected_even_from_local_peer() {
+        let tokens = vec![stored("vm", "atb_secret", vec![GatewayCliKey::Claude])];
+        let headers = header("Authorization", "Bearer atb_revoked");
+        assert!(matches!(
+            authorize_request(&tokens, &headers, Some(GatewayCliKey::Claude), true),
+            GatewayAccessDecision::Denied(GatewayAccessDenial::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn create_list_and_delete_round_trip() {
+        let db = SqliteDbState::in_memory_for_test().expect("open test db");
+        let created = create_access_token(
+            &db,
+            GatewayAccessTokenInput {
+                label: " team-vm ".to_string(),
+                cli_keys: vec![GatewayCliKey::Claude, GatewayCliKey::Claude],
+            },
+        )
+        .expect("create token");
+        assert!(created.secret.starts_with(ACCESS_TOKEN_PREFIX));
+        assert_eq!(created.token.label, "team-vm");
+        assert_eq!(created.token.cli_keys, vec![GatewayCliKey::Claude]);
+        assert!(created.secret.ends_with(&created.token.token_hint));
+
+        let duplicate = create_access_token(
+            &db,
+            GatewayAccessTokenInput {
+                label: "TEAM-VM".to_string(),
+                cli_keys: vec![GatewayCliKey::Codex],
+            },
+        );
+        assert!(duplicate.is_err());
+
+        let stored = db.with_conn(load_access_tokens).expect("load tokens");
+        assert_eq!(stored.len(), 1);
+        assert_ne!(stored[0].token_hash, created.secret);
+        assert_eq!(
+            authorize_request(
+                &stored,
+                &header("x-api-key", &created.secret),
+                Some(GatewayCliKey::Claude),
+                false,
+            ),
+            GatewayAccessDecision::Allowed {
+                client_label: Some("team-vm".to_string())
+            }
+        );
+
+        assert!(delete_access_token(&db, &created.token.id, false).is_err());
+        delete_access_token(&db, &created.token.id, true).expect("delete token");
+        assert!(list_access_tokens(&db).expect("list tokens").is_empty());
+    }
+}
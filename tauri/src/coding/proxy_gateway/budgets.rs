@@ -0,0 +1,698 @@
+//! Spending budgets for the gateway.
+//!
+//! Rules cap USD cost or tokens per day, week or month for a CLI, provider or
+//! model. Spend comes from `usage_stats::budget_usage`, which reads the live
+//! request log plus `usage_daily_rollups`, and is cached briefly so the hot
+//! path does not hit SQLite on every request.
+
+use super::transformer::AiProtocol;
+use super::types::{
+    GatewayBudgetAction, GatewayBudgetExceededEvent, GatewayBudgetPeriod, GatewayBudgetRule,
+    GatewayBudgetRuleInput, GatewayBudgetStatus, GatewayCliKey,
+};
+use super::usage_stats;
+use crate::db::helpers::{db_delete, db_get, db_list, db_put};
+use crate::db::schema::{DbTable, OrderDirection, OrderField, OrderSpec};
+use crate::db::SqliteDbState;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDate, TimeZone, Utc};
+use rusqlite::Connection;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// How long a rule's spend is reused before re-querying the request log.
+pub const BUDGET_USAGE_CACHE_TTL: Duration = Duration::from_secs(30);
+/// Response header listing the exceeded warn / fallback rules.
+pub const BUDGET_WARNING_HEADER: &str = "x-ai-toolbox-budget-warning";
+/// Minimum gap between two desktop events for the same rule.
+const EVENT_INTERVAL: Duration = Duration::from_secs(600);
+const MAX_NAME_CHARS: usize = 64;
+
+pub fn load_budget_rules(conn: &Connection) -> Result<Vec<GatewayBudgetRule>, String> {
+    let order = OrderSpec::single(OrderField::created_at(OrderDirection::Asc));
+    db_list(conn, DbTable::ProxyGatewayBudgetRule, Some(&order))?
+        .into_iter()
+        .map(|record| {
+            serde_json::from_value(record)
+                .map_err(|error| format!("Failed to parse gateway budget rule: {error}"))
+        })
+        .collect()
+}
+
+pub fn list_budget_rules(db: &SqliteDbState) -> Result<Vec<GatewayBudgetRule>, String> {
+    db.with_conn(load_budget_rules)
+}
+
+/// Create a rule, or update it in place when `input.id` is set.
+pub fn save_budget_rule(
+    db: &SqliteDbState,
+    input: GatewayBudgetRuleInput,
+) -> Result<GatewayBudgetRule, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Budget name is required".to_string());
+    }
+    if name.chars().count() > MAX_NAME_CHARS {
+        return Err(format!(
+            "Budget name must be at most {MAX_NAME_CHARS} characters"
+        ));
+    }
+    if let Some(cli_key) = input.cli_key {
+        if !GatewayCliKey::supported_mvp().contains(&cli_key) {
+            return Err(format!("Gateway does not serve {} yet", cli_key.as_str()));
+        }
+    }
+    let limit_usd = match input.limit_usd.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(value) => {
+            let amount =
+                Decimal::from_str(value).map_err(|_| format!("Invalid budget amount: {value}"))?;
+            if amount <= Decimal::ZERO {
+                return Err("Budget amount must be greater than 0".to_string());
+            }
+            Some(amount.normalize().to_string())
+        }
+    };
+    let limit_tokens = input.limit_tokens.filter(|tokens| *tokens > 0);
+    if limit_usd.is_none() && limit_tokens.is_none() {
+        return Err("Set a USD or token limit for the budget".to_string());
+    }
+
+    db.with_conn(|conn| {
+        let (id, created_at) = match input.id.as_deref() {
+            Some(id) => {
+                let Some(existing) = db_get(conn, DbTable::ProxyGatewayBudgetRule, id)? else {
+                    return Err(format!("Gateway budget '{id}' not found"));
+                };
+                let existing: GatewayBudgetRule = serde_json::from_value(existing)
+                    .map_err(|error| format!("Failed to parse gateway budget rule: {error}"))?;
+                (existing.id, existing.created_at)
+            }
+            None => (uuid::Uuid::new_v4().simple().to_string(), Utc::now()),
+        };
+        let rule = GatewayBudgetRule {
+            id,
+            name,
+            enabled: input.enabled,
+            cli_key: input.cli_key,
+            provider_id: non_empty(input.provider_id),
+            model: non_empty(input.model),
+            period: input.period,
+            limit_usd,
+            limit_tokens,
+            action: input.action,
+            created_at,
+        };
+        let data = serde_json::to_value(&rule)
+            .map_err(|error| format!("Failed to serialize gateway budget rule: {error}"))?;
+        db_put(conn, DbTable::ProxyGatewayBudgetRule, &rule.id, &data)?;
+        Ok(rule)
+    })
+}
+
+pub fn delete_budget_rule(db: &SqliteDbState, id: &str) -> Result<(), String> {
+    db.with_conn(|conn| {
+        if !db_delete(conn, DbTable::ProxyGatewayBudgetRule, id)? {
+            return Err(format!("Gateway budget '{id}' not found"));
+        }
+        Ok(())
+    })
+}
+
+/// Current spend for every rule, for the settings page.
+pub fn budget_statuses(db: &SqliteDbState) -> Result<Vec<GatewayBudgetStatus>, String> {
+    let now = Local::now();
+    db.with_conn(|conn| {
+        load_budget_rules(conn)?
+            .into_iter()
+            .map(|rule| {
+                let period_start = period_start(rule.period, now);
+                let (spent, tokens) = rule_usage(conn, &rule, period_start)?;
+                Ok(GatewayBudgetStatus {
+                    exceeded: is_exceeded(&rule, spent, tokens),
+                    period_start,
+                    spent_usd: spent.round_dp(6).normalize().to_string(),
+                    spent_tokens: tokens,
+                    rule,
+                })
+            })
+            .collect()
+    })
+}
+
+pub fn rule_usage(
+    conn: &Connection,
+    rule: &GatewayBudgetRule,
+    since: i64,
+) -> Result<(Decimal, u64), String> {
+    usage_stats::budget_usage(
+        conn,
+        since,
+        rule.cli_key,
+        rule.provider_id.as_deref(),
+        rule.model.as_deref(),
+    )
+}
+
+/// Unix timestamp of local midnight at the start of the current period.
+pub fn period_start<Tz: TimeZone>(period: GatewayBudgetPeriod, now: DateTime<Tz>) -> i64 {
+    let today = now.date_naive();
+    let first_day = match period {
+        GatewayBudgetPeriod::Daily => today,
+        GatewayBudgetPeriod::Weekly => {
+            today - ChronoDuration::days(i64::from(today.weekday().num_days_from_monday()))
+        }
+        GatewayBudgetPeriod::Monthly => {
+            NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap_or(today)
+        }
+    };
+    let midnight = first_day.and_hms_opt(0, 0, 0).unwrap_or_default();
+    now.timezone()
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|value| value.timestamp())
+        .unwrap_or_else(|| now.timestamp())
+}
+
+pub fn is_exceeded(rule: &GatewayBudgetRule, spent_usd: Decimal, spent_tokens: u64) -> bool {
+    let over_usd = rule
+        .limit_usd
+        .as_deref()
+        .and_then(|limit| Decimal::from_str(limit).ok())
+        .is_some_and(|limit| spent_usd >= limit);
+    let over_tokens = rule.limit_tokens.is_some_and(|limit| spent_tokens >= limit);
+    over_usd || over_tokens
+}
+
+/// Whether a rule covers requests for `cli_key` / `model`, ignoring the
+/// provider scope.
+fn rule_applies(rule: &GatewayBudgetRule, cli_key: GatewayCliKey, model: &str) -> bool {
+    rule.enabled
+        && rule.cli_key.is_none_or(|key| key == cli_key)
+        && rule
+            .model
+            .as_deref()
+            .is_none_or(|rule_model| rule_model.eq_ignore_ascii_case(model))
+}
+
+fn rule_covers_provider(rule: &GatewayBudgetRule, provider_id: &str) -> bool {
+    rule.provider_id
+        .as_deref()
+        .is_none_or(|rule_provider| rule_provider == provider_id)
+}
+
+/// A rule that is over its limit for the current period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExceededBudget {
+    pub rule: GatewayBudgetRule,
+    pub spent_usd: Decimal,
+    pub spent_tokens: u64,
+}
+
+impl ExceededBudget {
+    pub fn to_event(&self, cli_key: GatewayCliKey) -> GatewayBudgetExceededEvent {
+        GatewayBudgetExceededEvent {
+            cli_key,
+            rule_id: self.rule.id.clone(),
+            rule_name: self.rule.name.clone(),
+            action: self.rule.action,
+            period: self.rule.period,
+            spent_usd: self.spent_usd.round_dp(6).normalize().to_string(),
+            limit_usd: self.rule.limit_usd.clone(),
+            spent_tokens: self.spent_tokens,
+            limit_tokens: self.rule.limit_tokens,
+        }
+    }
+
+    /// ASCII-only header value; rule names may not be, so only the id is used.
+    pub fn warning_header_value(&self) -> String {
+        let period = match self.rule.period {
+            GatewayBudgetPeriod::Daily => "daily",
+            GatewayBudgetPeriod::Weekly => "weekly",
+            GatewayBudgetPeriod::Monthly => "monthly",
+        };
+        let mut value = format!(
+            "rule={}; period={period}; spent_usd={}",
+            self.rule.id,
+            self.spent_usd.round_dp(4).normalize()
+        );
+        if let Some(limit) = self.rule.limit_usd.as_deref() {
+            value.push_str(&format!("; limit_usd={limit}"));
+        }
+        if let Some(limit) = self.rule.limit_tokens {
+            value.push_str(&format!(
+                "; spent_tokens={}; limit_tokens={limit}",
+                self.spent_tokens
+            ));
+        }
+        value
+    }
+
+    pub fn reject_message(&self) -> String {
+        format!(
+            "Gateway budget '{}' is exhausted for the current period",
+            self.rule.name
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UsageCacheEntry {
+    loaded_at: Instant,
+    period_start: i64,
+    spent_usd: Decimal,
+    spent_tokens: u64,
+}
+
+/// Per-runtime budget state: the rule list, a short-lived spend cache and the
+/// desktop event throttle.
+#[derive(Debug, Default)]
+pub struct BudgetTracker {
+    rules: Vec<GatewayBudgetRule>,
+    usage_cache: HashMap<String, UsageCacheEntry>,
+    last_event_at: HashMap<String, Instant>,
+}
+
+impl BudgetTracker {
+    pub fn new(rules: Vec<GatewayBudgetRule>) -> Self {
+        Self {
+            rules,
+            ..Self::default()
+        }
+    }
+
+    pub fn replace_rules(&mut self, rules: Vec<GatewayBudgetRule>) {
+        self.rules = rules;
+        self.usage_cache.clear();
+    }
+
+    pub fn has_active_rules(&self) -> bool {
+        self.rules.iter().any(|rule| rule.enabled)
+    }
+
+    /// Rules covering this request on at least one of `provider_ids` whose
+    /// cached spend is stale or from an older period, with the period start
+    /// to load it from. Callers load spend outside the tracker lock and hand
+    /// it to [`BudgetTracker::record_usage`].
+    pub fn rules_to_load(
+        &self,
+        cli_key: GatewayCliKey,
+        model: &str,
+        provider_ids: &[&str],
+        now: Instant,
+        period_start_for: impl Fn(GatewayBudgetPeriod) -> i64,
+    ) -> Vec<(GatewayBudgetRule, i64)> {
+        self.covering_rules(cli_key, model, provider_ids)
+            .filter_map(|rule| {
+                let period_start = period_start_for(rule.period);
+                self.fresh_usage(rule, period_start, now)
+                    .is_none()
+                    .then(|| (rule.clone(), period_start))
+            })
+            .collect()
+    }
+
+    /// Cache the spend loaded for a rule. A failed load is logged and leaves
+    /// the rule unenforced rather than blocking traffic.
+    pub fn record_usage(
+        &mut self,
+        rule_id: &str,
+        period_start: i64,
+        now: Instant,
+        loaded: Result<(Decimal, u64), String>,
+    ) {
+        match loaded {
+            Ok((spent_usd, spent_tokens)) => {
+                self.usage_cache.insert(
+                    rule_id.to_string(),
+                    UsageCacheEntry {
+                        loaded_at: now,
+                        period_start,
+                        spent_usd,
+                        spent_tokens,
+                    },
+                );
+            }
+            Err(error) => {
+                log::warn!("Failed to load spend for gateway budget {rule_id}: {error}");
+            }
+        }
+    }
+
+    /// Rules over their limit that cover this request on at least one of
+    /// `provider_ids`, judged on the cached spend. Rules without a fresh
+    /// cache entry (their load failed) are skipped.
+    pub fn exceeded_rules(
+        &self,
+        cli_key: GatewayCliKey,
+        model: &str,
+        provider_ids: &[&str],
+        now: Instant,
+        period_start_for: impl Fn(GatewayBudgetPeriod) -> i64,
+    ) -> Vec<ExceededBudget> {
+        self.covering_rules(cli_key, model, provider_ids)
+            .filter_map(|rule| {
+                let usage = self.fresh_usage(rule, period_start_for(rule.period), now)?;
+                is_exceeded(rule, usage.spent_usd, usage.spent_tokens).then(|| ExceededBudget {
+                    rule: rule.clone(),
+                    spent_usd: usage.spent_usd,
+                    spent_tokens: usage.spent_tokens,
+                })
+            })
+            .collect()
+    }
+
+    fn covering_rules<'a>(
+        &'a self,
+        cli_key: GatewayCliKey,
+        model: &'a str,
+        provider_ids: &'a [&str],
+    ) -> impl Iterator<Item = &'a GatewayBudgetRule> + 'a {
+        self.rules.iter().filter(move |rule| {
+            rule_applies(rule, cli_key, model)
+                && provider_ids
+                    .iter()
+                    .any(|provider_id| rule_covers_provider(rule, provider_id))
+        })
+    }
+
+    fn fresh_usage(
+        &self,
+        rule: &GatewayBudgetRule,
+        period_start: i64,
+        now: Instant,
+    ) -> Option<&UsageCacheEntry> {
+        self.usage_cache.get(&rule.id).filter(|entry| {
+            entry.period_start == period_start
+                && now.duration_since(entry.loaded_at) < BUDGET_USAGE_CACHE_TTL
+        })
+    }
+
+    /// True at most once per `EVENT_INTERVAL` for each rule.
+    pub fn should_emit_event(&mut self, rule_id: &str, now: Instant) -> bool {
+        if self
+            .last_event_at
+            .get(rule_id)
+            .is_some_and(|last| now.duration_since(*last) < EVENT_INTERVAL)
+        {
+            return false;
+        }
+        self.last_event_at.insert(rule_id.to_string(), now);
+        true
+    }
+}
+
+/// One provider in failover order as seen by budget planning.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetCandidate {
+    pub provider_id: String,
+    pub cost_multiplier: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetPlan {
+    /// Indexes into the candidate list, in the order to try them.
+    pub order: Vec<usize>,
+    /// Set when every candidate is blocked by a reject rule.
+    pub rejected_by: Option<usize>,
+    /// Indexes into the exceeded list that should be surfaced as warnings.
+    pub warnings: Vec<usize>,
+}
+
+/// Apply exceeded rules to the failover chain. Reject rules drop the
+/// providers they cover; fallback rules move covered providers behind the
+/// unaffected ones and order them by ascending cost multiplier, so a
+/// CLI-wide fallback budget simply prefers the cheapest channel.
+pub fn plan_providers(exceeded: &[ExceededBudget], candidates: &[BudgetCandidate]) -> BudgetPlan {
+    let mut order = Vec::new();
+    let mut demoted = Vec::new();
+    let mut rejected_by = None;
+    for (index, candidate) in candidates.iter().enumerate() {
+        let covering = exceeded
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| rule_covers_provider(&item.rule, &candidate.provider_id));
+        let mut blocked = None;
+        let mut fallback = false;
+        for (exceeded_index, item) in covering {
+            match item.rule.action {
+                GatewayBudgetAction::Reject => {
+                    blocked.get_or_insert(exceeded_index);
+                }
+                GatewayBudgetAction::Fallback => fallback = true,
+                GatewayBudgetAction::Warn => {}
+            }
+        }
+        if let Some(exceeded_index) = blocked {
+            rejected_by.get_or_insert(exceeded_index);
+        } else if fallback {
+            demoted.push(index);
+        } else {
+            order.push(index);
+        }
+    }
+    demoted.sort_by(|left, right| {
+        candidates[*left]
+            .cost_multiplier
+            .cmp(&candidates[*right].cost_multiplier)
+    });
+    order.extend(demoted);
+
+    let warnings = exceeded
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.rule.action != GatewayBudgetAction::Reject)
+        .map(|(index, _)| index)
+        .collect();
+    BudgetPlan {
+        rejected_by: if order.is_empty() { rejected_by } else { None },
+        order,
+        warnings,
+    }
+}
+
+/// Quota error body in the dialect the client speaks, sent with HTTP 429.
+pub fn quota_error_body(protocol: Option<AiProtocol>, message: &str) -> Value {
+    match protocol {
+        Some(AiProtocol::AnthropicMessages) => json!({
+            "type": "error",
+            "error": {
+                "type": "rate_limit_error",
+                "message": message,
+            },
+        }),
+        Some(AiProtocol::OpenAiChat | AiProtocol::OpenAiResponses) => json!({
+            "error": {
+                "message": message,
+                "type": "insufficient_quota",
+                "param": null,
+                "code": "insufficient_quota",
+            },
+        }),
+        Some(AiProtocol::GeminiNative) => json!({
+            "error": {
+                "code": 429,
+                "message": message,
+                "status": "RESOURCE_EXHAUSTED",
+            },
+        }),
+        None => json!({
+            "error": "budget_exceeded",
+            "message": message,
+        }),
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn rule(id: &str, action: GatewayBudgetAction) -> GatewayBudgetRule {
+        GatewayBudgetRule {
+            id: id.to_string(),
+            name: id.to_string(),
+            enabled: true,
+            cli_key: Some(GatewayCliKey::Claude),
+            provider_id: None,
+            model: None,
+            period: GatewayBudgetPeriod::Daily,
+            limit_usd: Some("10".to_string()),
+            limit_tokens: None,
+            action,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn exceeded(rule: GatewayBudgetRule) -> ExceededBudget {
+        ExceededBudget {
+            rule,
+            spent_usd: Decimal::from(12),
+            spent_tokens: 0,
+        }
+    }
+
+    fn candidate(provider_id: &str, cost_multiplier: &str) -> BudgetCandidate {
+        BudgetCandidate {
+            provider_id: provider_id.to_string(),
+            cost_multiplier: Decimal::from_str(cost_multiplier).unwrap(),
+        }
+    }
+
+    #[test]
+    fn period_start_aligns_to_local_midnight() {
+        let tz = FixedOffset::east_opt(8 * 3600).unwrap();
+        // Wednesday 2026-03-18 15:30 +08:00
+        let now = tz.with_ymd_and_hms(2026, 3, 18, 15, 30, 0).unwrap();
+        let day = tz.with_ymd_and_hms(2026, 3, 18, 0, 0, 0).unwrap();
+        let monday = tz.with_ymd_and_hms(2026, 3, 16, 0, 0, 0).unwrap();
+        let first = tz.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            period_start(GatewayBudgetPeriod::Daily, now),
+            day.timestamp()
+        );
+        assert_eq!(
+            period_start(GatewayBudgetPeriod::Weekly, now),
+            monday.timestamp()
+        );
+        assert_eq!(
+            period_start(GatewayBudgetPeriod::Monthly, now),
+            first.timestamp()
+        );
+    }
+
+    #[test]
+    fn usd_or_token_limit_trips_the_rule() {
+        let mut tokens_rule = rule("tokens", GatewayBudgetAction::Warn);
+        tokens_rule.limit_usd = None;
+        tokens_rule.limit_tokens = Some(1000);
+        assert!(!is_exceeded(&tokens_rule, Decimal::from(50), 999));
+        assert!(is_exceeded(&tokens_rule, Decimal::ZERO, 1000));
+
+        let usd_rule = rule("usd", GatewayBudgetAction::Warn);
+        assert!(!is_exceeded(
+            &usd_rule,
+            Decimal::from_str("9.99").unwrap(),
+            0
+        ));
+        assert!(is_exceeded(&usd_rule, Decimal::from(10), 0));
+    }
+
+    #[test]
+    fn tracker_caches_spend_and_skips_rules_outside_scope() {
+        let mut model_rule = rule("model", GatewayBudgetAction::Warn);
+        model_rule.model = Some("claude-opus-4".to_string());
+        let mut codex_rule = rule("codex", GatewayBudgetAction::Warn);
+        codex_rule.cli_key = Some(GatewayCliKey::Codex);
+        let mut tracker = BudgetTracker::new(vec![model_rule, codex_rule]);
+        let now = Instant::now();
+        let mut loads = 0;
+        let mut check = |tracker: &mut BudgetTracker, model: &str, at: Instant| {
+            let pending = tracker.rules_to_load(GatewayCliKey::Claude, model, &["p1"], at, |_| 0);
+            for (rule, since) in pending {
+                loads += 1;
+                tracker.record_usage(&rule.id, since, at, Ok((Decimal::from(20), 0)));
+            }
+            tracker.exceeded_rules(GatewayCliKey::Claude, model, &["p1"], at, |_| 0)
+        };
+
+        assert!(check(&mut tracker, "claude-sonnet-4", now).is_empty());
+        assert_eq!(check(&mut tracker, "CLAUDE-OPUS-4", now).len(), 1);
+        assert_eq!(check(&mut tracker, "claude-opus-4", now).len(), 1);
+        let later = now + BUDGET_USAGE_CACHE_TTL;
+        assert_eq!(check(&mut tracker, "claude-opus-4", later).len(), 1);
+        assert_eq!(loads, 2);
+    }
+
+    #[test]
+    fn reject_drops_covered_providers_and_fallback_prefers_cheaper() {
+        let mut reject_primary = rule("reject", GatewayBudgetAction::Reject);
+        reject_primary.provider_id = Some("primary".to_string());
+        let fallback_all = rule("fallback", GatewayBudgetAction::Fallback);
+        let candidates = vec![
+            candidate("primary", "1"),
+            candidate("pricey", "2"),
+            candidate("cheap", "0.5"),
+        ];
+
+        let plan = plan_providers(
+            &[exceeded(reject_primary), exceeded(fallback_all)],
+            &candidates,
+        );
+        assert_eq!(plan.order, vec![2, 1]);
+        assert_eq!(plan.rejected_by, None);
+        assert_eq!(plan.warnings, vec![1]);
+    }
+
+    #[test]
+    fn cli_wide_reject_blocks_the_request() {
+        let plan = plan_providers(
+            &[exceeded(rule("cap", GatewayBudgetAction::Reject))],
+            &[candidate("a", "1"), candidate("b", "1")],
+        );
+        assert!(plan.order.is_empty());
+        assert_eq!(plan.rejected_by, Some(0));
+        assert!(plan.warnings.is_empty());
+    }
+
+    #[test]
+    fn quota_error_body_matches_client_dialect() {
+        let anthropic = quota_error_body(Some(AiProtocol::AnthropicMessages), "over");
+        assert_eq!(anthropic["error"]["type"], "rate_limit_error");
+        let openai = quota_error_body(Some(AiProtocol::OpenAiResponses), "over");
+        assert_eq!(openai["error"]["code"], "insufficient_quota");
+        let gemini = quota_error_body(Some(AiProtocol::GeminiNative), "over");
+        assert_eq!(gemini["error"]["status"], "RESOURCE_EXHAUSTED");
+        assert_eq!(quota_error_body(None, "over")["error"], "budget_exceeded");
+    }
+
+    #[test]
+    fn save_validates_and_updates_rules() {
+        let db = SqliteDbState::in_memory_for_test().expect("open test db");
+        let input = GatewayBudgetRuleInput {
+            id: None,
+            name: " Daily Claude ".to_string(),
+            enabled: true,
+            cli_key: Some(GatewayCliKey::Claude),
+            provider_id: Some("  ".to_string()),
+            model: None,
+            period: GatewayBudgetPeriod::Daily,
+            limit_usd: Some("5.50".to_string()),
+            limit_tokens: None,
+            action: GatewayBudgetAction::Reject,
+        };
+        let created = save_budget_rule(&db, input.clone()).unwrap();
+        assert_eq!(created.name, "Daily Claude");
+        assert_eq!(created.provider_id, None);
+        assert_eq!(created.limit_usd.as_deref(), Some("5.5"));
+
+        let updated = save_budget_rule(
+            &db,
+            GatewayBudgetRuleInput {
+                id: Some(created.id.clone()),
+                limit_usd: None,
+                limit_tokens: Some(1000),
+                ..input.clone()
+            },
+        )
+        .unwrap();
+        assert_eq!(updated.created_at, created.created_at);
+        assert_eq!(list_budget_rules(&db).unwrap(), vec![updated]);
+
+        let missing_limit = GatewayBudgetRuleInput {
+            limit_usd: Some("0".to_string()),
+            ..input
+        };
+        assert!(save_budget_rule(&db, missing_limit).is_err());
+        delete_budget_rule(&db, &created.id).unwrap();
+        assert!(list_budget_rules(&db).unwrap().is_empty());
+    }
+}
@@ -504,6 +504,47 @@ pub async fn engage_failover_cli(
     cli_key: GatewayCliKey,
     gateway_status: &ProxyGatewayStatus,
 ) -> Result<GatewayCliTakeoverStatus, String> {
+    engage_multi_provider_cli(
+        db,
+        paths,
+        cli_key,
+        gateway_status,
+        GatewayProxyMode::Failover,
+    )
+    .await
+}
+
+pub async fn engage_load_balance_cli(
+    db: &SqliteDbState,
+    paths: &ProxyGatewayPaths,
+    cli_key: GatewayCliKey,
+    gateway_status: &ProxyGatewayStatus,
+) -> Result<GatewayCliTakeoverStatus, String> {
+    engage_multi_provider_cli(
+        db,
+        paths,
+        cli_key,
+        gateway_status,
+        GatewayProxyMode::LoadBalance,
+    )
+    .await
+}
+
+/// Switch an engaged CLI into failover or load-balance mode. Both modes share
+/// the same CLI-side config, so moving between them only rewrites the manifest.
+pub async fn engage_multi_provider_cli(
+    db: &SqliteDbState,
+    paths: &ProxyGatewayPaths,
+    cli_key: GatewayCliKey,
+    gateway_status: &ProxyGatewayStatus,
+    mode: GatewayProxyMode,
+) -> Result<GatewayCliTakeoverStatus, String> {
+    if !mode.is_multi_provider() {
+        return Err(format!(
+            "Gateway proxy mode '{}' does not route across providers",
+            mode.as_str()
+        ));
+    }
     if !is_supported_cli(cli_key) {
         return Err("This CLI is not supported by the gateway MVP".to_string());
     }
@@ -530,7 +571,7 @@ pub async fn engage_failover_cli(
             &mut targets,
             &manifest.base_origin,
             Some(&primary_provider),
-            GatewayProxyMode::Failover,
+            mode,
             None,
             codex_auth_backup_content.as_deref(),
             codex_auth_preservation_enabled_for_cli(db, cli_key)?,
@@ -550,7 +591,11 @@ pub async fn engage_failover_cli(
             return Err(error);
         }
         sync_manifest_managed_fields(&mut manifest, &targets);
-        manifest.mode = GatewayProxyMode::Failover;
+        manifest.mode = mode;
+        manifest.updated_at = chrono::Utc::now().to_rfc3339();
+        write_manifest(paths, cli_key, &manifest)?;
+    } else if manifest.mode != mode {
+        manifest.mode = mode;
         manifest.updated_at = chrono::Utc::now().to_rfc3339();
         write_manifest(paths, cli_key, &manifest)?;
     }
@@ -571,7 +616,7 @@ pub async fn disengage_failover_cli(
     else {
         return Ok(cli_takeover_status(db, paths, cli_key, gateway_status).await);
     };
-    if manifest.enabled && manifest.mode == GatewayProxyMode::Failover {
+    if manifest.enabled && manifest.mode.is_multi_provider() {
         let primary_provider =
             load_proxyable_provider(db, cli_key, &manifest.primary_provider_id).await?;
         let mut targets = resolve_targets(db, cli_key).await?;
@@ -1044,7 +1089,7 @@ fn priority_entries_for_manifest_providers(
 
     let first_index = match manifest.mode {
         GatewayProxyMode::Single => 0,
-        GatewayProxyMode::Failover => 1,
+        GatewayProxyMode::Failover | GatewayProxyMode::LoadBalance => 1,
     };
     providers
         .iter()
@@ -1443,7 +1488,7 @@ fn apply_gateway_config(
                 required_target_path(targets, CLAUDE_SETTINGS_KIND)?,
                 &cli_gateway_endpoint(cli_key, base_origin),
                 primary_provider,
-                mode == GatewayProxyMode::Failover,
+                mode.is_multi_provider(),
                 claude_backup_content,
             )
         }
@@ -2777,6 +2822,8 @@ mod tests {
                 opus_model: opus_model.map(str::to_string),
                 fable_model: None,
                 reasoning_model: None,
+                azure_deployments: Default::default(),
+                azure_api_version: None,
             },
         }
     }
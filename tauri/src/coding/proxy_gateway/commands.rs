@@ -1,19 +1,29 @@
+use super::access_tokens;
+use super::budgets;
 use super::cli_proxy;
-use super::listen::check_port_available;
+use super::fixture_capture;
+use super::listen::{check_port_available, is_loopback_listen_host};
+use super::mock_upstream::{
+    MockUpstreamScript, MockUpstreamServer, MockUpstreamState, MockUpstreamStatus,
+};
 use super::model_health;
 use super::paths::ProxyGatewayPaths;
 use super::pricing;
 use super::provider_switch;
 use super::request_log;
+use super::response_cache;
 use super::runtime::ProxyGatewayState;
 use super::session_import;
 use super::settings;
 use super::types::{
-    DataSourceBreakdownInput, DataSourceBreakdownItem, GatewayCliKey, GatewayCliTakeoverStatus,
-    GatewayConnectivityTestRequest, GatewayConnectivityTestResponse, GatewayModelHealthItem,
+    DataSourceBreakdownInput, DataSourceBreakdownItem, GatewayAccessToken,
+    GatewayAccessTokenCreated, GatewayAccessTokenInput, GatewayBudgetRule, GatewayBudgetRuleInput,
+    GatewayBudgetStatus, GatewayCliKey, GatewayCliTakeoverStatus, GatewayClientStats,
+    GatewayConnectivityTestRequest, GatewayConnectivityTestResponse, GatewayFixtureCaptureSummary,
+    GatewayFixtureReplayReport, GatewayModelHealthItem,
     GatewayModelStats, GatewayPaginatedRequestLogs, GatewayProviderStats, GatewayRequestLogDetail,
-    GatewayRequestLogFilters, GatewaySessionUsageImportInput, GatewaySessionUsageImportResult,
-    GatewayUsageRecordedEvent, GatewayUsageSummary, GatewayUsageSummaryByCli,
+    GatewayRequestLogFilters, GatewayResponseCacheStats, GatewaySessionUsageImportInput,
+    GatewaySessionUsageImportResult, GatewayUsageRecordedEvent, GatewayUsageSummary, GatewayUsageSummaryByCli,
     GatewayUsageTrendPoint, ModelPricing, ProxyGatewayHealthCheckResult,
     ProxyGatewayPortCheckInput, ProxyGatewayPortCheckResult, ProxyGatewayRequestLogListInput,
     ProxyGatewaySettings, ProxyGatewayStatus, ProxyGatewayStopPreflight,
@@ -242,9 +252,71 @@ pub async fn proxy_gateway_health_check(
 
 #[tauri::command]
 pub fn proxy_gateway_check_port_available(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
     input: ProxyGatewayPortCheckInput,
 ) -> Result<ProxyGatewayPortCheckResult, String> {
-    check_port_available(input)
+    check_port_available(input, access_tokens::lan_listen_allowed(&sqlite_state))
+}
+
+#[tauri::command]
+pub fn proxy_gateway_list_access_tokens(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+) -> Result<Vec<GatewayAccessToken>, String> {
+    access_tokens::list_access_tokens(&sqlite_state)
+}
+
+#[tauri::command]
+pub fn proxy_gateway_create_access_token(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    gateway_state: tauri::State<'_, ProxyGatewayState>,
+    input: GatewayAccessTokenInput,
+) -> Result<GatewayAccessTokenCreated, String> {
+    let created = access_tokens::create_access_token(&sqlite_state, input)?;
+    gateway_state.reload_access_tokens()?;
+    Ok(created)
+}
+
+#[tauri::command]
+pub fn proxy_gateway_delete_access_token(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    gateway_state: tauri::State<'_, ProxyGatewayState>,
+    id: String,
+) -> Result<(), String> {
+    let settings = settings::load_settings_from_sqlite_state(&sqlite_state)?;
+    access_tokens::delete_access_token(
+        &sqlite_state,
+        &id,
+        is_loopback_listen_host(&settings.listen_host),
+    )?;
+    gateway_state.reload_access_tokens()
+}
+
+#[tauri::command]
+pub fn proxy_gateway_budget_statuses(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+) -> Result<Vec<GatewayBudgetStatus>, String> {
+    budgets::budget_statuses(&sqlite_state)
+}
+
+#[tauri::command]
+pub fn proxy_gateway_save_budget_rule(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    gateway_state: tauri::State<'_, ProxyGatewayState>,
+    input: GatewayBudgetRuleInput,
+) -> Result<GatewayBudgetRule, String> {
+    let rule = budgets::save_budget_rule(&sqlite_state, input)?;
+    gateway_state.reload_budget_rules()?;
+    Ok(rule)
+}
+
+#[tauri::command]
+pub fn proxy_gateway_delete_budget_rule(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    gateway_state: tauri::State<'_, ProxyGatewayState>,
+    id: String,
+) -> Result<(), String> {
+    budgets::delete_budget_rule(&sqlite_state, &id)?;
+    gateway_state.reload_budget_rules()
 }
 
 #[tauri::command]
@@ -327,6 +399,28 @@ pub async fn proxy_gateway_engage_failover(
     Ok(next_status)
 }
 
+#[tauri::command]
+pub async fn proxy_gateway_engage_load_balance(
+    gateway_state: tauri::State<'_, ProxyGatewayState>,
+    db_state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    cli_key: GatewayCliKey,
+) -> Result<GatewayCliTakeoverStatus, String> {
+    let status = {
+        let manager = gateway_state
+            .manager
+            .lock()
+            .map_err(|_| "Proxy gateway manager lock poisoned".to_string())?;
+        manager.status()
+    };
+    let paths = proxy_gateway_paths(&app)?;
+    let next_status =
+        cli_proxy::engage_load_balance_cli(db_state.db(), &paths, cli_key, &status).await?;
+    gateway_state.clear_provider_cache()?;
+    emit_gateway_cli_wsl_sync_request(&app, cli_key);
+    Ok(next_status)
+}
+
 #[tauri::command]
 pub async fn proxy_gateway_disengage_failover(
     gateway_state: tauri::State<'_, ProxyGatewayState>,
@@ -438,6 +532,89 @@ pub fn proxy_gateway_export_request_log_detail(
     write_export_json(Path::new(&export_path), &export_json)
 }
 
+#[tauri::command]
+pub fn proxy_gateway_list_fixture_captures(
+    app: tauri::AppHandle,
+) -> Result<Vec<GatewayFixtureCaptureSummary>, String> {
+    fixture_capture::list_fixture_captures(&proxy_gateway_paths(&app)?)
+}
+
+#[tauri::command]
+pub fn proxy_gateway_replay_fixture_capture(
+    manifest_path: String,
+) -> Result<GatewayFixtureReplayReport, String> {
+    fixture_capture::replay_fixture_capture(Path::new(&manifest_path))
+}
+
+#[tauri::command]
+pub fn proxy_gateway_response_cache_stats(
+    app: tauri::AppHandle,
+) -> Result<GatewayResponseCacheStats, String> {
+    response_cache::response_cache_stats(&proxy_gateway_paths(&app)?)
+}
+
+/// Removes every cached response and returns what was cleared.
+#[tauri::command]
+pub fn proxy_gateway_clear_response_cache(
+    app: tauri::AppHandle,
+) -> Result<GatewayResponseCacheStats, String> {
+    response_cache::clear_response_cache(&proxy_gateway_paths(&app)?)
+}
+
+#[tauri::command]
+pub fn proxy_gateway_mock_upstream_status(
+    mock_state: tauri::State<'_, MockUpstreamState>,
+) -> Result<MockUpstreamStatus, String> {
+    let server = mock_state
+        .server
+        .lock()
+        .map_err(|_| "Mock upstream lock poisoned".to_string())?;
+    Ok(mock_upstream_status(server.as_ref()))
+}
+
+/// Starts the mock upstream, replacing a running one so a new port or script
+/// always takes effect.
+#[tauri::command]
+pub fn proxy_gateway_mock_upstream_start(
+    mock_state: tauri::State<'_, MockUpstreamState>,
+    script: Option<MockUpstreamScript>,
+    port: Option<u16>,
+) -> Result<MockUpstreamStatus, String> {
+    let mut server = mock_state
+        .server
+        .lock()
+        .map_err(|_| "Mock upstream lock poisoned".to_string())?;
+    server.take();
+    let started = MockUpstreamServer::start_on_port(port.unwrap_or(0), script.unwrap_or_default())?;
+    let status = started.status();
+    *server = Some(started);
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn proxy_gateway_mock_upstream_stop(
+    mock_state: tauri::State<'_, MockUpstreamState>,
+) -> Result<MockUpstreamStatus, String> {
+    let mut server = mock_state
+        .server
+        .lock()
+        .map_err(|_| "Mock upstream lock poisoned".to_string())?;
+    server.take();
+    Ok(mock_upstream_status(None))
+}
+
+fn mock_upstream_status(server: Option<&MockUpstreamServer>) -> MockUpstreamStatus {
+    server
+        .map(MockUpstreamServer::status)
+        .unwrap_or(MockUpstreamStatus {
+            running: false,
+            base_url: None,
+            request_count: 0,
+            script: None,
+            recent_requests: Vec::new(),
+        })
+}
+
 fn load_request_log_detail(
     app: &tauri::AppHandle,
     db_state: &SqliteDbState,
@@ -571,7 +748,7 @@ fn redact_json_value(value: &mut Value) {
     match value {
         Value::Object(map) => {
             for (key, nested_value) in map.iter_mut() {
-                if is_sensitive_key(key) {
+                if request_log::is_sensitive_body_key(key) {
                     *nested_value = Value::String("xxx".to_string());
                 } else {
                     redact_json_value(nested_value);
@@ -607,16 +784,7 @@ fn redact_auth_like_text(text: &str) -> String {
                 redact_next = true;
                 return token.to_string();
             }
-            if lower.starts_with("sk-")
-                || lower.starts_with("sk_")
-                || lower.starts_with("xai-")
-                || lower.starts_with("ghp_")
-                || lower.starts_with("gho_")
-                || lower.starts_with("ghu_")
-                || lower.starts_with("ghs_")
-                || lower.starts_with("ghr_")
-                || lower.starts_with("github_pat_")
-            {
+            if request_log::is_secret_like_token(&lower) {
                 "xxx".to_string()
             } else {
                 redact_sensitive_text_assignments(token)
@@ -717,36 +885,6 @@ fn sensitive_assignment_value_end(token: &str, value_start: usize) -> usize {
         .unwrap_or(token.len())
 }
 
-fn is_sensitive_key(key: &str) -> bool {
-    let normalized = key.to_ascii_lowercase();
-    let separator_normalized = normalized
-        .chars()
-        .map(|character| match character {
-            '-' | ' ' => '_',
-            _ => character,
-        })
-        .collect::<String>();
-    request_log::is_sensitive_header(&normalized)
-        || request_log::is_sensitive_header(&separator_normalized)
-        || normalized.contains("secret")
-        || separator_normalized.contains("secret")
-        || normalized.contains("password")
-        || separator_normalized.contains("password")
-        || normalized.contains("credential")
-        || separator_normalized.contains("credential")
-        || normalized == "token"
-        || separator_normalized == "token"
-        || normalized.contains("access_token")
-        || separator_normalized.contains("access_token")
-        || normalized.contains("refresh_token")
-        || separator_normalized.contains("refresh_token")
-        || normalized == "key"
-        || separator_normalized == "key"
-        || normalized == "apikey"
-        || separator_normalized == "api_key"
-        || separator_normalized == "apikey"
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -837,6 +975,7 @@ mod tests {
                 output_tokens: Some(0),
                 cache_read_tokens: Some(0),
                 cache_creation_tokens: Some(0),
+                reasoning_tokens: None,
                 total_tokens: Some(0),
                 request_body_bytes: 0,
                 response_body_bytes: 0,
@@ -844,6 +983,10 @@ mod tests {
                 first_token_ms: None,
                 detail_file: None,
                 detail_offset: None,
+                client_label: None,
+                cache_hit: false,
+                image_count: 0,
+                batch_priced: false,
             },
             request_headers: None,
             request_body: None,
@@ -924,6 +1067,16 @@ pub fn proxy_gateway_model_stats(
     usage_stats::model_stats(&db_state, start_date, end_date, cli_key)
 }
 
+#[tauri::command]
+pub fn proxy_gateway_client_stats(
+    db_state: tauri::State<'_, SqliteDbState>,
+    start_date: Option<i64>,
+    end_date: Option<i64>,
+    cli_key: Option<GatewayCliKey>,
+) -> Result<Vec<GatewayClientStats>, String> {
+    usage_stats::client_stats(&db_state, start_date, end_date, cli_key)
+}
+
 #[tauri::command]
 pub fn proxy_gateway_data_source_breakdown(
     db_state: tauri::State<'_, SqliteDbState>,
@@ -0,0 +1,536 @@
+use super::paths::ProxyGatewayPaths;
+use super::request_log;
+use super::transformer::{
+    diff_json, fixture_events_to_jsonl, fixture_protocol_dir, parse_fixture_jsonl,
+    replay_conversion, sse_to_fixture_events, stream_events_value, ConversionReplayOutput,
+    ConversionRoute, FixtureResponseBody, FixtureStreamEvent,
+};
+use super::types::{
+    GatewayCliKey, GatewayFixtureCapture, GatewayFixtureCaptureSummary, GatewayFixtureReplayReport,
+    ProxyGatewaySettings,
+};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Upstream response snapshot limit while capture is on. Truncated bodies are
+/// never written, so this bounds the largest exchange that can be captured.
+pub const FIXTURE_CAPTURE_MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+const FIXTURE_CAPTURE_SCHEMA_VERSION: u32 = 1;
+const CAPTURE_MANIFEST_SUFFIX: &str = ".capture.json";
+
+pub struct FixtureCaptureInput<'a> {
+    pub trace_id: &'a str,
+    pub captured_at: DateTime<Utc>,
+    pub cli_key: Option<GatewayCliKey>,
+    pub method: &'a str,
+    pub path: &'a str,
+    pub requested_model: Option<&'a str>,
+    pub upstream_model_id: Option<&'a str>,
+    pub provider_type: Option<&'a str>,
+    pub status_code: u16,
+    pub route: ConversionRoute,
+    pub request_headers: &'a [(String, String)],
+    pub request_body: &'a [u8],
+    pub response_headers: &'a [(String, String)],
+    pub upstream_request_body: Option<&'a [u8]>,
+    pub upstream_response_body: &'a [u8],
+    pub is_streaming: bool,
+}
+
+pub fn should_capture(
+    settings: &ProxyGatewaySettings,
+    requested_model: Option<&str>,
+    path: &str,
+) -> bool {
+    if !settings.fixture_capture_enabled {
+        return false;
+    }
+    let terms = settings
+        .fixture_capture_filter
+        .split(',')
+        .map(|term| term.trim().to_ascii_lowercase())
+        .filter(|term| !term.is_empty())
+        .collect::<Vec<_>>();
+    if terms.is_empty() {
+        return true;
+    }
+    let model = requested_model.unwrap_or_default().to_ascii_lowercase();
+    let path = path.to_ascii_lowercase();
+    terms
+        .iter()
+        .any(|term| model.contains(term.as_str()) || path.contains(term.as_str()))
+}
+
+/// Writes one exchange into the fixture layout and returns the manifest path.
+/// Credentials in the bodies are redacted before anything touches disk, and
+/// the expected output is recorded from the redacted bodies so replays match.
+/// Returns `Ok(None)` when the bodies cannot be represented as fixtures.
+pub fn write_fixture_capture(
+    paths: &ProxyGatewayPaths,
+    input: &FixtureCaptureInput<'_>,
+) -> Result<Option<PathBuf>, String> {
+    let Ok(mut request) = serde_json::from_slice::<Value>(input.request_body) else {
+        return Ok(None);
+    };
+    redact_fixture_value(&mut request);
+    let (response, response_text, response_extension) = if input.is_streaming {
+        let mut events = sse_to_fixture_events(input.upstream_response_body);
+        if events.is_empty() {
+            return Ok(None);
+        }
+        events.iter_mut().for_each(redact_stream_event);
+        let text = fixture_events_to_jsonl(&events)?;
+        (FixtureResponseBody::Stream(events), text, "stream.jsonl")
+    } else {
+        let Ok(mut body) = serde_json::from_slice::<Value>(input.upstream_response_body) else {
+            return Ok(None);
+        };
+        redact_fixture_value(&mut body);
+        let text = pretty_json(&body)?;
+        (FixtureResponseBody::Json(body), text, "response.json")
+    };
+
+    let name = fixture_capture_name(input.requested_model, input.trace_id);
+    let request_fixture = format!(
+        "live_provider/{}/{name}.request.json",
+        fixture_protocol_dir(input.route.source)
+    );
+    let response_fixture = format!(
+        "live_provider/{}/{name}.{response_extension}",
+        fixture_protocol_dir(input.route.target)
+    );
+    let request_text = pretty_json(&request)?;
+    let (expected, expected_error) =
+        match replay_conversion(input.route, request_text.as_bytes(), &response) {
+            Ok(output) => (Some(output), None),
+            Err(error) => (None, Some(error)),
+        };
+    let capture = GatewayFixtureCapture {
+        schema_version: FIXTURE_CAPTURE_SCHEMA_VERSION,
+        name: name.clone(),
+        captured_at: input.captured_at,
+        trace_id: input.trace_id.to_string(),
+        cli_key: input.cli_key,
+        method: input.method.to_string(),
+        path: request_log::redact_request_path(input.path),
+        requested_model: input.requested_model.map(str::to_string),
+        upstream_model_id: input.upstream_model_id.map(str::to_string),
+        provider_type: input.provider_type.map(str::to_string),
+        status_code: input.status_code,
+        route: input.route,
+        request_headers: request_log::redact_headers(input.request_headers),
+        response_headers: request_log::redact_headers(input.response_headers),
+        request_fixture: request_fixture.clone(),
+        response_fixture: response_fixture.clone(),
+        observed_upstream_request: input
+            .upstream_request_body
+            .and_then(|body| serde_json::from_slice(body).ok())
+            .map(|mut body| {
+                redact_fixture_value(&mut body);
+                body
+            }),
+        expected,
+        expected_error,
+    };
+
+    let root = paths.fixture_capture_root();
+    write_text(&root.join(&request_fixture), &request_text)?;
+    write_text(&root.join(&response_fixture), &response_text)?;
+    let manifest_path = captures_dir(paths).join(format!("{name}{CAPTURE_MANIFEST_SUFFIX}"));
+    write_text(&manifest_path, &pretty_json(&capture)?)?;
+    Ok(Some(manifest_path))
+}
+
+pub fn list_fixture_captures(
+    paths: &ProxyGatewayPaths,
+) -> Result<Vec<GatewayFixtureCaptureSummary>, String> {
+    let dir = captures_dir(paths);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let entries = fs::read_dir(&dir).map_err(|error| {
+        format!(
+            "Failed to read fixture capture directory {}: {error}",
+            dir.display()
+        )
+    })?;
+    let mut summaries = Vec::new();
+    for entry in entries.flatten() {
+        let manifest_path = entry.path();
+        let is_manifest = manifest_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with(CAPTURE_MANIFEST_SUFFIX));
+        if !is_manifest {
+            continue;
+        }
+        match read_capture_manifest(&manifest_path) {
+            Ok(capture) => summaries.push(GatewayFixtureCaptureSummary {
+                name: capture.name,
+                manifest_path: manifest_path.to_string_lossy().to_string(),
+                captured_at: capture.captured_at,
+                cli_key: capture.cli_key,
+                path: capture.path,
+                requested_model: capture.requested_model,
+                route: capture.route,
+                status_code: capture.status_code,
+            }),
+            Err(error) => log::warn!("Skipping unreadable fixture capture: {error}"),
+        }
+    }
+    summaries.sort_by_key(|summary| std::cmp::Reverse(summary.captured_at));
+    Ok(summaries)
+}
+
+/// Replays a capture manifest. Fixture files are resolved against the
+/// directory above the manifest, so a copied capture tree replays as-is.
+pub fn replay_fixture_capture(manifest_path: &Path) -> Result<GatewayFixtureReplayReport, String> {
+    let capture = read_capture_manifest(manifest_path)?;
+    let root = manifest_path
+        .parent()
+        .and_then(Path::parent)
+        .ok_or_else(|| format!("Invalid fixture capture path {}", manifest_path.display()))?;
+    let request_body = read_fixture_file(root, &capture.request_fixture)?;
+    let response_text = String::from_utf8(read_fixture_file(root, &capture.response_fixture)?)
+        .map_err(|error| format!("Response fixture is not UTF-8: {error}"))?;
+    let response = if capture.response_fixture.ends_with(".stream.jsonl") {
+        FixtureResponseBody::Stream(parse_fixture_jsonl(&response_text)?)
+    } else {
+        FixtureResponseBody::Json(
+            serde_json::from_str(&response_text)
+                .map_err(|error| format!("Invalid response fixture: {error}"))?,
+        )
+    };
+
+    let (output, error) = match replay_conversion(capture.route, &request_body, &response) {
+        Ok(output) => (Some(output), None),
+        Err(error) => (None, Some(error)),
+    };
+    let differences = diff_json(
+        &replay_value(capture.expected.as_ref(), capture.expected_error.as_deref()),
+        &replay_value(output.as_ref(), error.as_deref()),
+    );
+    Ok(GatewayFixtureReplayReport {
+        name: capture.name,
+        route: capture.route,
+        passed: differences.is_empty(),
+        differences,
+        output,
+        error,
+    })
+}
+
+fn replay_value(output: Option<&ConversionReplayOutput>, error: Option<&str>) -> Value {
+    json!({
+        "upstream_request": output.map(|output| &output.upstream_request),
+        "client_response": output.and_then(|output| output.client_response.as_ref()),
+        "client_stream": output
+            .and_then(|output| output.client_stream.as_deref())
+            .map(stream_events_value),
+        "error": error,
+    })
+}
+
+/// Replaces credential fields and key-shaped words inside a captured body.
+/// Only string values are rewritten, so fields like `max_tokens` keep their
+/// type and the fixture still converts. Returns whether anything changed.
+fn redact_fixture_value(value: &mut Value) -> bool {
+    match value {
+        Value::Object(map) => map.iter_mut().fold(false, |changed, (key, nested)| {
+            let redacted = match nested {
+                Value::String(text) if request_log::is_sensitive_body_key(key) => {
+                    *text = "xxx".to_string();
+                    true
+                }
+                _ => redact_fixture_value(nested),
+            };
+            changed | redacted
+        }),
+        Value::Array(items) => items
+            .iter_mut()
+            .fold(false, |changed, item| redact_fixture_value(item) | changed),
+        Value::String(text) => match redact_secret_words(text) {
+            Some(redacted) => {
+                *text = redacted;
+                true
+            }
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+fn redact_stream_event(event: &mut FixtureStreamEvent) {
+    let Ok(mut data) = serde_json::from_str::<Value>(&event.data) else {
+        if let Some(redacted) = redact_secret_words(&event.data) {
+            event.data = redacted;
+        }
+        return;
+    };
+    if redact_fixture_value(&mut data) {
+        if let Ok(text) = serde_json::to_string(&data) {
+            event.data = text;
+        }
+    }
+}
+
+/// Masks API-key-shaped words and the credential following `Bearer`. The
+/// surrounding whitespace is kept so captured prompts are otherwise intact.
+fn redact_secret_words(text: &str) -> Option<String> {
+    let mut redacted = String::with_capacity(text.len());
+    let mut changed = false;
+    let mut redact_next = false;
+    for piece in text.split_inclusive(char::is_whitespace) {
+        let word = piece.trim_end_matches(char::is_whitespace);
+        if word.is_empty() {
+            redacted.push_str(piece);
+            continue;
+        }
+        let lower = word.to_ascii_lowercase();
+        if redact_next || request_log::is_secret_like_token(&lower) {
+            redact_next = false;
+            changed = true;
+            redacted.push_str("xxx");
+        } else {
+            redact_next = lower == "bearer";
+            redacted.push_str(word);
+        }
+        redacted.push_str(&piece[word.len()..]);
+    }
+    changed.then_some(redacted)
+}
+
+fn captures_dir(paths: &ProxyGatewayPaths) -> PathBuf {
+    paths.fixture_capture_root().join("captures")
+}
+
+fn fixture_capture_name(requested_model: Option<&str>, trace_id: &str) -> String {
+    let raw = match requested_model
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+    {
+        Some(model) => format!("{model}-{trace_id}"),
+        None => format!("capture-{trace_id}"),
+    };
+    raw.chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                ch
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn read_capture_manifest(path: &Path) -> Result<GatewayFixtureCapture, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read fixture capture {}: {error}", path.display()))?;
+    serde_json::from_str(&text).map_err(|error| {
+        format!(
+            "Failed to parse fixture capture {}: {error}",
+            path.display()
+        )
+    })
+}
+
+fn read_fixture_file(root: &Path, relative_path: &str) -> Result<Vec<u8>, String> {
+    let relative = Path::new(relative_path);
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Err(format!("Invalid fixture path {relative_path}"));
+    }
+    let path = root.join(relative);
+    fs::read(&path).map_err(|error| format!("Failed to read fixture {}: {error}", path.display()))
+}
+
+fn write_text(path: &Path, text: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| {
+            format!(
+                "Failed to create fixture directory {}: {error}",
+                parent.display()
+            )
+        })?;
+    }
+    fs::write(path, text)
+        .map_err(|error| format!("Failed to write fixture {}: {error}", path.display()))
+}
+
+fn pretty_json<T: serde::Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value)
+        .map(|text| text + "\n")
+        .map_err(|error| format!("Failed to serialize fixture: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::proxy_gateway::transformer::AiProtocol;
+
+    fn capture_input<'a>(
+        request_body: &'a [u8],
+        upstream_response_body: &'a [u8],
+        is_streaming: bool,
+    ) -> FixtureCaptureInput<'a> {
+        FixtureCaptureInput {
+            trace_id: "gw-1-2-3",
+            captured_at: Utc::now(),
+            cli_key: Some(GatewayCliKey::Claude),
+            method: "POST",
+            path: "/anthropic/v1/messages?key=secret",
+            requested_model: Some("deepseek/v4"),
+            upstream_model_id: None,
+            provider_type: None,
+            status_code: 200,
+            route: ConversionRoute::new(AiProtocol::AnthropicMessages, AiProtocol::OpenAiChat),
+            request_headers: &[],
+            request_body,
+            response_headers: &[],
+            upstream_request_body: None,
+            upstream_response_body,
+            is_streaming,
+        }
+    }
+
+    #[test]
+    fn capture_filter_matches_model_or_path() {
+        let mut settings = ProxyGatewaySettings {
+            fixture_capture_enabled: true,
+            ..ProxyGatewaySettings::default()
+        };
+        assert!(should_capture(
+            &settings,
+            Some("gpt-5"),
+            "/openai/v1/responses"
+        ));
+
+        settings.fixture_capture_filter = " DeepSeek , /gemini ".to_string();
+        assert!(should_capture(
+            &settings,
+            Some("deepseek-v4"),
+            "/anthropic/v1/messages"
+        ));
+        assert!(should_capture(&settings, None, "/gemini/v1beta/models"));
+        assert!(!should_capture(
+            &settings,
+            Some("gpt-5"),
+            "/openai/v1/responses"
+        ));
+
+        settings.fixture_capture_enabled = false;
+        assert!(!should_capture(&settings, Some("deepseek-v4"), "/"));
+    }
+
+    #[test]
+    fn captured_stream_writes_fixture_layout_and_replays_clean() {
+        let temp = tempfile::tempdir().unwrap();
+        let paths = ProxyGatewayPaths::new(temp.path());
+        let request = br#"{"model":"deepseek/v4","max_tokens":16,"stream":true,"messages":[{"role":"user","content":"hi"}]}"#;
+        let upstream = concat!(
+            "data: {\"id\":\"c1\",\"model\":\"m\",\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"hi\"}}]}\n\n",
+            "data: {\"id\":\"c1\",\"model\":\"m\",\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"stop\"}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+
+        let manifest_path =
+            write_fixture_capture(&paths, &capture_input(request, upstream.as_bytes(), true))
+                .unwrap()
+                .unwrap();
+
+        let root = paths.fixture_capture_root();
+        assert!(root
+            .join("live_provider/anthropic/deepseek-v4-gw-1-2-3.request.json")
+            .is_file());
+        assert!(root
+            .join("live_provider/openai_chat/deepseek-v4-gw-1-2-3.stream.jsonl")
+            .is_file());
+        let capture = read_capture_manifest(&manifest_path).unwrap();
+        assert_eq!(capture.path, "/anthropic/v1/messages?key=xxx");
+        assert!(capture.expected.is_some());
+
+        let report = replay_fixture_capture(&manifest_path).unwrap();
+        assert!(report.passed, "{:?}", report.differences);
+        assert_eq!(list_fixture_captures(&paths).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn replay_reports_drift_from_recorded_output() {
+        let temp = tempfile::tempdir().unwrap();
+        let paths = ProxyGatewayPaths::new(temp.path());
+        let request =
+            br#"{"model":"m","max_tokens":16,"messages":[{"role":"user","content":"hi"}]}"#;
+        let response = br#"{"id":"c1","object":"chat.completion","model":"m","choices":[{"index":0,"message":{"role":"assistant","content":"hello"},"finish_reason":"stop"}]}"#;
+        let manifest_path = write_fixture_capture(&paths, &capture_input(request, response, false))
+            .unwrap()
+            .unwrap();
+
+        let mut capture = read_capture_manifest(&manifest_path).unwrap();
+        if let Some(expected) = capture.expected.as_mut() {
+            expected.upstream_request["max_tokens"] = json!(99);
+        }
+        write_text(&manifest_path, &pretty_json(&capture).unwrap()).unwrap();
+
+        let report = replay_fixture_capture(&manifest_path).unwrap();
+        assert!(!report.passed);
+        assert!(report
+            .differences
+            .iter()
+            .any(|difference| difference.path.ends_with("/max_tokens")));
+    }
+
+    #[test]
+    fn captured_bodies_are_redacted_before_writing() {
+        let temp = tempfile::tempdir().unwrap();
+        let paths = ProxyGatewayPaths::new(temp.path());
+        let request = br#"{"model":"m","max_tokens":16,"metadata":{"api_key":"plain-key"},"messages":[{"role":"user","content":"use sk-live-123 and\n  Bearer abc.def please"}]}"#;
+        let upstream = concat!(
+            "data: {\"id\":\"c1\",\"model\":\"m\",\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"key ghp_secret\"}}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+
+        let manifest_path =
+            write_fixture_capture(&paths, &capture_input(request, upstream.as_bytes(), true))
+                .unwrap()
+                .unwrap();
+
+        let root = paths.fixture_capture_root();
+        let request_text = fs::read_to_string(
+            root.join("live_provider/anthropic/deepseek-v4-gw-1-2-3.request.json"),
+        )
+        .unwrap();
+        let stream_text = fs::read_to_string(
+            root.join("live_provider/openai_chat/deepseek-v4-gw-1-2-3.stream.jsonl"),
+        )
+        .unwrap();
+        for secret in ["plain-key", "sk-live-123", "abc.def", "ghp_secret"] {
+            assert!(!request_text.contains(secret), "{request_text}");
+            assert!(!stream_text.contains(secret), "{stream_text}");
+        }
+        let written: Value = serde_json::from_str(&request_text).unwrap();
+        assert_eq!(written["max_tokens"], json!(16));
+        assert_eq!(
+            written["messages"][0]["content"],
+            json!("use xxx and\n  Bearer xxx please")
+        );
+
+        let report = replay_fixture_capture(&manifest_path).unwrap();
+        assert!(report.passed, "{:?}", report.differences);
+    }
+
+    #[test]
+    fn skips_bodies_that_are_not_fixtures() {
+        let temp = tempfile::tempdir().unwrap();
+        let paths = ProxyGatewayPaths::new(temp.path());
+
+        let written =
+            write_fixture_capture(&paths, &capture_input(b"not json", b"{}", false)).unwrap();
+
+        assert!(written.is_none());
+        assert!(!paths.fixture_capture_root().exists());
+    }
+}
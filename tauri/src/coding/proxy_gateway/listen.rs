@@ -1,5 +1,5 @@
 use super::types::{ProxyGatewayPortCheckInput, ProxyGatewayPortCheckResult, ProxyGatewaySettings};
-use std::net::TcpListener;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, TcpListener};
 
 const MIN_USER_PORT: u16 = 1024;
 const AUTO_SELECT_SCAN_LIMIT: u16 = 100;
@@ -12,7 +12,10 @@ pub struct BoundGatewayListener {
     pub base_url: String,
 }
 
-pub fn validate_listen_host(input: &str) -> Result<String, String> {
+/// Validate and normalize the listen host. Loopback is always accepted; any
+/// other interface address needs `allow_lan`, which callers grant only once at
+/// least one gateway access token exists.
+pub fn validate_listen_host(input: &str, allow_lan: bool) -> Result<String, String> {
     let host = input.trim();
     if host.is_empty() {
         return Err("Gateway listen host cannot be empty".to_string());
@@ -25,9 +28,49 @@ pub fn validate_listen_host(input: &str) -> Result<String, String> {
         return Err("Gateway listen host must be a host only, not a URL or path".to_string());
     }
 
-    match host {
-        "127.0.0.1" | "localhost" => Ok("127.0.0.1".to_string()),
-        _ => Err("Gateway MVP only supports local loopback host 127.0.0.1".to_string()),
+    if matches!(host, "127.0.0.1" | "localhost") {
+        return Ok("127.0.0.1".to_string());
+    }
+    let address = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .map_err(|_| {
+            "Gateway listen host must be 127.0.0.1 or an IP address of this machine".to_string()
+        })?;
+    if address.is_loopback() {
+        return Ok(address.to_string());
+    }
+    if !allow_lan {
+        return Err(
+            "Gateway can only listen on a LAN interface after at least one access token is created"
+                .to_string(),
+        );
+    }
+    Ok(address.to_string())
+}
+
+pub fn is_loopback_listen_host(host: &str) -> bool {
+    host == "localhost"
+        || host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(|address| address.is_loopback())
+}
+
+/// Base URL local CLIs use to reach the gateway. A wildcard bind is reached
+/// through loopback so takeover configs stay valid on every platform.
+fn gateway_base_url(host: &str, port: u16) -> String {
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(address)) if address.is_unspecified() => {
+            format!("http://{}:{port}", Ipv4Addr::LOCALHOST)
+        }
+        Ok(IpAddr::V6(address)) if address.is_unspecified() => {
+            format!("http://[{}]:{port}", Ipv6Addr::LOCALHOST)
+        }
+        Ok(IpAddr::V6(address)) => format!("http://[{address}]:{port}"),
+        _ => format!("http://{host}:{port}"),
     }
 }
 
@@ -38,8 +81,11 @@ pub fn validate_listen_port(port: u16) -> Result<u16, String> {
     Ok(port)
 }
 
+/// Validate persisted settings. The listen host is only checked for shape here;
+/// whether a LAN interface is allowed depends on the access tokens and is
+/// enforced when saving and binding.
 pub fn validate_settings(settings: &ProxyGatewaySettings) -> Result<(String, u16), String> {
-    let host = validate_listen_host(&settings.listen_host)?;
+    let host = validate_listen_host(&settings.listen_host, true)?;
     let port = validate_listen_port(settings.listen_port)?;
     if settings.per_provider_retry_count > settings.max_retry_count {
         return Err("Gateway per-provider retry count cannot exceed max retry count".to_string());
@@ -63,6 +109,12 @@ pub fn validate_settings(settings: &ProxyGatewaySettings) -> Result<(String, u16
             return Err(format!("Gateway {label} must be at least 1 second, got 0"));
         }
     }
+    if settings.response_cache_ttl_secs == 0 {
+        return Err("Gateway response cache TTL must be at least 1 second, got 0".to_string());
+    }
+    if settings.response_cache_max_size_mb == 0 {
+        return Err("Gateway response cache size limit must be at least 1 MB, got 0".to_string());
+    }
     // Validate expression early so bad UI input fails at save time.
     super::retryable_status::parse_retryable_status_codes(&settings.retryable_status_codes)?;
     for (cli_key, app_config) in &settings.app_configs {
@@ -104,8 +156,10 @@ pub fn validate_settings(settings: &ProxyGatewaySettings) -> Result<(String, u16
 
 pub fn bind_gateway_listener(
     settings: &ProxyGatewaySettings,
+    allow_lan: bool,
 ) -> Result<BoundGatewayListener, String> {
-    let (host, start_port) = validate_settings(settings)?;
+    let (_, start_port) = validate_settings(settings)?;
+    let host = validate_listen_host(&settings.listen_host, allow_lan)?;
     let max_port = if settings.port_auto_select {
         start_port.saturating_add(AUTO_SELECT_SCAN_LIMIT)
     } else {
@@ -127,7 +181,7 @@ pub fn bind_gateway_listener(
                     listener,
                     listen_host: host.clone(),
                     listen_port: local_port,
-                    base_url: format!("http://{}:{}", host, local_port),
+                    base_url: gateway_base_url(&host, local_port),
                 });
             }
             Err(error) => {
@@ -151,8 +205,9 @@ pub fn bind_gateway_listener(
 
 pub fn check_port_available(
     input: ProxyGatewayPortCheckInput,
+    allow_lan: bool,
 ) -> Result<ProxyGatewayPortCheckResult, String> {
-    let host = validate_listen_host(&input.listen_host)?;
+    let host = validate_listen_host(&input.listen_host, allow_lan)?;
     let port = validate_listen_port(input.listen_port)?;
     let available = TcpListener::bind((host.as_str(), port)).is_ok();
     Ok(ProxyGatewayPortCheckResult {
@@ -172,27 +227,54 @@ mod tests {
 
     #[test]
     fn localhost_is_normalized_to_loopback() {
-        assert_eq!(validate_listen_host("localhost").unwrap(), "127.0.0.1");
+        assert_eq!(
+            validate_listen_host("localhost", false).unwrap(),
+            "127.0.0.1"
+        );
     }
 
     #[test]
     fn loopback_host_is_accepted() {
-        assert_eq!(validate_listen_host("127.0.0.1").unwrap(), "127.0.0.1");
+        assert_eq!(
+            validate_listen_host("127.0.0.1", false).unwrap(),
+            "127.0.0.1"
+        );
     }
 
     #[test]
     fn listen_host_rejects_url() {
-        assert!(validate_listen_host("http://127.0.0.1").is_err());
+        assert!(validate_listen_host("http://127.0.0.1", true).is_err());
     }
 
     #[test]
     fn listen_host_rejects_path() {
-        assert!(validate_listen_host("127.0.0.1/gateway").is_err());
+        assert!(validate_listen_host("127.0.0.1/gateway", true).is_err());
+    }
+
+    #[test]
+    fn listen_host_rejects_lan_without_access_tokens() {
+        assert!(validate_listen_host("0.0.0.0", false).is_err());
+        assert!(validate_listen_host("192.168.1.20", false).is_err());
+    }
+
+    #[test]
+    fn listen_host_accepts_lan_ip_with_access_tokens() {
+        assert_eq!(validate_listen_host("0.0.0.0", true).unwrap(), "0.0.0.0");
+        assert_eq!(
+            validate_listen_host("192.168.1.20", true).unwrap(),
+            "192.168.1.20"
+        );
+        assert!(validate_listen_host("gateway.lan", true).is_err());
     }
 
     #[test]
-    fn listen_host_rejects_lan_for_mvp() {
-        assert!(validate_listen_host("0.0.0.0").is_err());
+    fn wildcard_bind_advertises_loopback_base_url() {
+        assert_eq!(gateway_base_url("0.0.0.0", 8080), "http://127.0.0.1:8080");
+        assert_eq!(gateway_base_url("::", 8080), "http://[::1]:8080");
+        assert_eq!(
+            gateway_base_url("192.168.1.20", 8080),
+            "http://192.168.1.20:8080"
+        );
     }
 
     #[test]
@@ -204,10 +286,13 @@ mod tests {
     fn check_port_available_reports_occupied_port() {
         let listener = reserve_port();
         let port = listener.local_addr().unwrap().port();
-        let result = check_port_available(ProxyGatewayPortCheckInput {
-            listen_host: "127.0.0.1".to_string(),
-            listen_port: port,
-        })
+        let result = check_port_available(
+            ProxyGatewayPortCheckInput {
+                listen_host: "127.0.0.1".to_string(),
+                listen_port: port,
+            },
+            false,
+        )
         .unwrap();
         assert!(!result.available);
     }
@@ -222,7 +307,7 @@ mod tests {
             ..ProxyGatewaySettings::default()
         };
 
-        let bound = bind_gateway_listener(&settings).unwrap();
+        let bound = bind_gateway_listener(&settings, false).unwrap();
 
         assert_ne!(bound.listen_port, occupied_port);
         assert_eq!(bound.listen_host, "127.0.0.1");
@@ -0,0 +1,520 @@
+use super::types::{GatewayCliKey, GatewayLoadBalanceStrategy, GatewayProviderStats};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long request stats from `usage_stats` are reused before re-querying.
+pub const STATS_CACHE_TTL: Duration = Duration::from_secs(60);
+/// Window of request logs the latency / error-rate strategies look at.
+pub const STATS_WINDOW_SECS: i64 = 3600;
+const MAX_STICKY_SESSIONS: usize = 4096;
+/// Error-rate penalty per model-health failure point, so a channel that just
+/// failed is avoided before the request log catches up.
+const HEALTH_PENALTY_PER_POINT: f64 = 0.05;
+
+/// One provider as seen by the balancer, in failover order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceCandidate {
+    pub provider_id: String,
+    /// Relative share of traffic. `0` keeps the provider as a standby that is
+    /// only tried after every weighted provider failed.
+    pub weight: u32,
+    /// Not cooling down in `ModelHealthRegistry`.
+    pub available: bool,
+    /// Current model + provider failure score from `ModelHealthRegistry`.
+    pub failure_score: i32,
+    pub avg_latency_ms: Option<u64>,
+    /// Success ratio in `0.0..=1.0`.
+    pub success_ratio: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+struct StickyEntry {
+    provider_id: String,
+    last_used: Instant,
+}
+
+#[derive(Debug, Clone)]
+struct StatsCacheEntry {
+    loaded_at: Instant,
+    stats: HashMap<String, GatewayProviderStats>,
+}
+
+/// Per-runtime load balancing state: session stickiness and a short-lived
+/// cache of request stats.
+#[derive(Debug, Default)]
+pub struct LoadBalancer {
+    sticky_sessions: HashMap<(GatewayCliKey, String), StickyEntry>,
+    stats_cache: HashMap<GatewayCliKey, StatsCacheEntry>,
+}
+
+impl LoadBalancer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached request stats for `cli_key`, or None once the cache is older
+    /// than `STATS_CACHE_TTL`. Callers reload outside the balancer lock and
+    /// hand the result to [`LoadBalancer::store_provider_stats`].
+    pub fn fresh_provider_stats(
+        &self,
+        cli_key: GatewayCliKey,
+        now: Instant,
+    ) -> Option<HashMap<String, GatewayProviderStats>> {
+        self.stats_cache
+            .get(&cli_key)
+            .filter(|entry| now.duration_since(entry.loaded_at) < STATS_CACHE_TTL)
+            .map(|entry| entry.stats.clone())
+    }
+
+    /// Cache a stats reload for `cli_key`. A failed reload keeps the previous
+    /// snapshot.
+    pub fn store_provider_stats(
+        &mut self,
+        cli_key: GatewayCliKey,
+        now: Instant,
+        loaded: Result<Vec<GatewayProviderStats>, String>,
+    ) -> HashMap<String, GatewayProviderStats> {
+        match loaded {
+            Ok(items) => {
+                let stats = items
+                    .into_iter()
+                    .map(|item| (item.provider_id.clone(), item))
+                    .collect::<HashMap<_, _>>();
+                self.stats_cache.insert(
+                    cli_key,
+                    StatsCacheEntry {
+                        loaded_at: now,
+                        stats: stats.clone(),
+                    },
+                );
+                stats
+            }
+            Err(error) => {
+                log::warn!("Failed to load gateway provider stats for load balancing: {error}");
+                self.stats_cache
+                    .get(&cli_key)
+                    .map(|entry| entry.stats.clone())
+                    .unwrap_or_default()
+            }
+        }
+    }
+
+    /// Return candidate indexes in the order they should be tried.
+    ///
+    /// The first entry is the balancer's pick (the session's sticky provider
+    /// when it is still usable); the rest of the weighted pool follows in
+    /// failover order, then standby providers. `roll` is a uniform sample in
+    /// `[0, 1)` used by the weighted strategy.
+    #[allow(clippy::too_many_arguments)]
+    pub fn order(
+        &mut self,
+        cli_key: GatewayCliKey,
+        strategy: GatewayLoadBalanceStrategy,
+        candidates: &[BalanceCandidate],
+        session_key: Option<&str>,
+        sticky_ttl: Duration,
+        now: Instant,
+        roll: f64,
+    ) -> Vec<usize> {
+        let pool: Vec<usize> = (0..candidates.len())
+            .filter(|&index| candidates[index].weight > 0)
+            .collect();
+        let standby = (0..candidates.len()).filter(|&index| candidates[index].weight == 0);
+        if pool.is_empty() {
+            return standby.collect();
+        }
+
+        let available: Vec<usize> = pool
+            .iter()
+            .copied()
+            .filter(|&index| candidates[index].available)
+            .collect();
+        let eligible = if available.is_empty() {
+            pool.clone()
+        } else {
+            available
+        };
+
+        let sticky_key = session_key
+            .filter(|key| !key.is_empty() && !sticky_ttl.is_zero())
+            .map(|key| (cli_key, key.to_string()));
+        let sticky_choice = sticky_key.as_ref().and_then(|key| {
+            let entry = self.sticky_sessions.get(key)?;
+            if now.duration_since(entry.last_used) > sticky_ttl {
+                return None;
+            }
+            eligible
+                .iter()
+                .copied()
+                .find(|&index| candidates[index].provider_id == entry.provider_id)
+        });
+        let chosen = sticky_choice
+            .unwrap_or_else(|| pick_by_strategy(strategy, candidates, &eligible, roll));
+
+        if let Some(key) = sticky_key {
+            self.remember_session(key, &candidates[chosen].provider_id, sticky_ttl, now);
+        }
+
+        let mut ordered = Vec::with_capacity(candidates.len());
+        ordered.push(chosen);
+        ordered.extend(pool.into_iter().filter(|&index| index != chosen));
+        ordered.extend(standby);
+        ordered
+    }
+
+    fn remember_session(
+        &mut self,
+        key: (GatewayCliKey, String),
+        provider_id: &str,
+        sticky_ttl: Duration,
+        now: Instant,
+    ) {
+        if self.sticky_sessions.len() >= MAX_STICKY_SESSIONS
+            && !self.sticky_sessions.contains_key(&key)
+        {
+            self.sticky_sessions
+                .retain(|_, entry| now.duration_since(entry.last_used) <= sticky_ttl);
+            if self.sticky_sessions.len() >= MAX_STICKY_SESSIONS {
+                if let Some(oldest) = self
+                    .sticky_sessions
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(key, _)| key.clone())
+                {
+                    self.sticky_sessions.remove(&oldest);
+                }
+            }
+        }
+        self.sticky_sessions.insert(
+            key,
+            StickyEntry {
+                provider_id: provider_id.to_string(),
+                last_used: now,
+            },
+        );
+    }
+}
+
+fn pick_by_strategy(
+    strategy: GatewayLoadBalanceStrategy,
+    candidates: &[BalanceCandidate],
+    eligible: &[usize],
+    roll: f64,
+) -> usize {
+    let scored = match strategy {
+        GatewayLoadBalanceStrategy::Weighted => None,
+        GatewayLoadBalanceStrategy::LeastLatency => {
+            lowest_score(candidates, eligible, |candidate| {
+                candidate.avg_latency_ms.map(|latency| latency as f64)
+            })
+        }
+        GatewayLoadBalanceStrategy::LeastErrorRate => {
+            lowest_score(candidates, eligible, error_rate_score)
+        }
+    };
+    scored.unwrap_or_else(|| pick_weighted(candidates, eligible, roll))
+}
+
+fn error_rate_score(candidate: &BalanceCandidate) -> Option<f64> {
+    let penalty = candidate.failure_score.max(0) as f64 * HEALTH_PENALTY_PER_POINT;
+    match candidate.success_ratio {
+        Some(ratio) => Some((1.0 - ratio).max(0.0) + penalty),
+        None if candidate.failure_score > 0 => Some(penalty),
+        None => None,
+    }
+}
+
+/// Pick the candidate with the lowest score. Candidates without data are
+/// scored at the mean of the known ones so new channels still get traffic;
+/// ties go to the higher weight, then to failover order. Returns `None` when
+/// no candidate has data, so the caller falls back to weighted picking.
+fn lowest_score<F>(candidates: &[BalanceCandidate], eligible: &[usize], score: F) -> Option<usize>
+where
+    F: Fn(&BalanceCandidate) -> Option<f64>,
+{
+    let scores: Vec<Option<f64>> = eligible
+        .iter()
+        .map(|&index| score(&candidates[index]))
+        .collect();
+    let known: Vec<f64> = scores.iter().flatten().copied().collect();
+    if known.is_empty() {
+        return None;
+    }
+    let mean = known.iter().sum::<f64>() / known.len() as f64;
+
+    eligible
+        .iter()
+        .zip(scores)
+        .map(|(&index, score)| (index, score.unwrap_or(mean)))
+        .min_by(|(left_index, left), (right_index, right)| {
+            left.total_cmp(right)
+                .then_with(|| {
+                    candidates[*right_index]
+                        .weight
+                        .cmp(&candidates[*left_index].weight)
+                })
+                .then_with(|| left_index.cmp(right_index))
+        })
+        .map(|(index, _)| index)
+}
+
+fn pick_weighted(candidates: &[BalanceCandidate], eligible: &[usize], roll: f64) -> usize {
+    let total: u64 = eligible
+        .iter()
+        .map(|&index| u64::from(candidates[index].weight))
+        .sum();
+    let mut target = (roll.clamp(0.0, 1.0) * total as f64) as u64;
+    for &index in eligible {
+        let weight = u64::from(candidates[index].weight);
+        if target < weight {
+            return index;
+        }
+        target -= weight;
+    }
+    eligible[eligible.len() - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(id: &str, weight: u32) -> BalanceCandidate {
+        BalanceCandidate {
+            provider_id: id.to_string(),
+            weight,
+            available: true,
+            failure_score: 0,
+            avg_latency_ms: None,
+            success_ratio: None,
+        }
+    }
+
+    fn ids(candidates: &[BalanceCandidate], order: &[usize]) -> Vec<String> {
+        order
+            .iter()
+            .map(|&index| candidates[index].provider_id.clone())
+            .collect()
+    }
+
+    fn order(
+        balancer: &mut LoadBalancer,
+        strategy: GatewayLoadBalanceStrategy,
+        candidates: &[BalanceCandidate],
+        roll: f64,
+    ) -> Vec<String> {
+        let order = balancer.order(
+            GatewayCliKey::Claude,
+            strategy,
+            candidates,
+            None,
+            Duration::from_secs(60),
+            Instant::now(),
+            roll,
+        );
+        ids(candidates, &order)
+    }
+
+    #[test]
+    fn weighted_pick_follows_roll_and_keeps_failover_order_behind_it() {
+        let candidates = vec![
+            candidate("a", 1),
+            candidate("b", 3),
+            candidate("standby", 0),
+            candidate("c", 1),
+        ];
+        let mut balancer = LoadBalancer::new();
+        let weighted = GatewayLoadBalanceStrategy::Weighted;
+
+        assert_eq!(
+            order(&mut balancer, weighted, &candidates, 0.0),
+            vec!["a", "b", "c", "standby"]
+        );
+        assert_eq!(
+            order(&mut balancer, weighted, &candidates, 0.5),
+            vec!["b", "a", "c", "standby"]
+        );
+        assert_eq!(
+            order(&mut balancer, weighted, &candidates, 0.99),
+            vec!["c", "a", "b", "standby"]
+        );
+    }
+
+    #[test]
+    fn unavailable_providers_are_not_picked_while_others_are_healthy() {
+        let mut candidates = vec![candidate("a", 5), candidate("b", 1)];
+        candidates[0].available = false;
+        let mut balancer = LoadBalancer::new();
+
+        assert_eq!(
+            order(
+                &mut balancer,
+                GatewayLoadBalanceStrategy::Weighted,
+                &candidates,
+                0.0
+            ),
+            vec!["b", "a"]
+        );
+    }
+
+    #[test]
+    fn least_latency_and_error_rate_use_stats_and_health() {
+        let mut candidates = vec![candidate("a", 1), candidate("b", 1), candidate("c", 1)];
+        candidates[0].avg_latency_ms = Some(900);
+        candidates[1].avg_latency_ms = Some(300);
+        candidates[0].success_ratio = Some(0.99);
+        candidates[1].success_ratio = Some(0.80);
+        let mut balancer = LoadBalancer::new();
+
+        assert_eq!(
+            order(
+                &mut balancer,
+                GatewayLoadBalanceStrategy::LeastLatency,
+                &candidates,
+                0.0
+            )[0],
+            "b"
+        );
+        assert_eq!(
+            order(
+                &mut balancer,
+                GatewayLoadBalanceStrategy::LeastErrorRate,
+                &candidates,
+                0.9
+            )[0],
+            "a"
+        );
+
+        // Recent health failures outweigh an older good success rate.
+        candidates[0].failure_score = 4;
+        assert_eq!(
+            order(
+                &mut balancer,
+                GatewayLoadBalanceStrategy::LeastErrorRate,
+                &candidates,
+                0.9
+            )[0],
+            "b"
+        );
+    }
+
+    #[test]
+    fn strategies_without_data_fall_back_to_weights() {
+        let candidates = vec![candidate("a", 1), candidate("b", 9)];
+        let mut balancer = LoadBalancer::new();
+
+        assert_eq!(
+            order(
+                &mut balancer,
+                GatewayLoadBalanceStrategy::LeastLatency,
+                &candidates,
+                0.5
+            )[0],
+            "b"
+        );
+    }
+
+    #[test]
+    fn sessions_stick_to_their_provider_until_it_is_unavailable_or_expired() {
+        let mut candidates = vec![candidate("a", 1), candidate("b", 1)];
+        let mut balancer = LoadBalancer::new();
+        let ttl = Duration::from_secs(60);
+        let start = Instant::now();
+        let strategy = GatewayLoadBalanceStrategy::Weighted;
+
+        let first = balancer.order(
+            GatewayCliKey::Codex,
+            strategy,
+            &candidates,
+            Some("session-1"),
+            ttl,
+            start,
+            0.9,
+        );
+        assert_eq!(ids(&candidates, &first)[0], "b");
+
+        let sticky = balancer.order(
+            GatewayCliKey::Codex,
+            strategy,
+            &candidates,
+            Some("session-1"),
+            ttl,
+            start + Duration::from_secs(30),
+            0.0,
+        );
+        assert_eq!(ids(&candidates, &sticky)[0], "b");
+
+        let other_session = balancer.order(
+            GatewayCliKey::Codex,
+            strategy,
+            &candidates,
+            Some("session-2"),
+            ttl,
+            start + Duration::from_secs(30),
+            0.0,
+        );
+        assert_eq!(ids(&candidates, &other_session)[0], "a");
+
+        candidates[1].available = false;
+        let moved = balancer.order(
+            GatewayCliKey::Codex,
+            strategy,
+            &candidates,
+            Some("session-1"),
+            ttl,
+            start + Duration::from_secs(40),
+            0.9,
+        );
+        assert_eq!(ids(&candidates, &moved)[0], "a");
+
+        candidates[1].available = true;
+        let expired = balancer.order(
+            GatewayCliKey::Codex,
+            strategy,
+            &candidates,
+            Some("session-1"),
+            ttl,
+            start + Duration::from_secs(200),
+            0.9,
+        );
+        assert_eq!(ids(&candidates, &expired)[0], "b");
+    }
+
+    #[test]
+    fn stats_cache_reuses_snapshot_within_ttl() {
+        let mut balancer = LoadBalancer::new();
+        let start = Instant::now();
+        let stats = |latency| {
+            Ok(vec![GatewayProviderStats {
+                cli_key: GatewayCliKey::Claude,
+                provider_id: "a".to_string(),
+                provider_name: None,
+                request_count: 10,
+                total_tokens: 0,
+                total_cost_usd: "0".to_string(),
+                success_rate: 100.0,
+                avg_latency_ms: latency,
+            }])
+        };
+
+        assert!(balancer
+            .fresh_provider_stats(GatewayCliKey::Claude, start)
+            .is_none());
+        let first = balancer.store_provider_stats(GatewayCliKey::Claude, start, stats(100));
+        let cached = balancer
+            .fresh_provider_stats(GatewayCliKey::Claude, start + Duration::from_secs(5))
+            .expect("fresh snapshot");
+        assert_eq!(first["a"].avg_latency_ms, 100);
+        assert_eq!(cached["a"].avg_latency_ms, 100);
+
+        let expired = start + STATS_CACHE_TTL;
+        assert!(balancer
+            .fresh_provider_stats(GatewayCliKey::Claude, expired)
+            .is_none());
+        let failed = balancer.store_provider_stats(
+            GatewayCliKey::Claude,
+            expired,
+            Err("db locked".to_string()),
+        );
+        assert_eq!(failed["a"].avg_latency_ms, 100);
+    }
+}
@@ -0,0 +1,829 @@
+//! Scripted mock upstream provider for offline gateway testing.
+//!
+//! The server speaks Anthropic Messages, OpenAI Chat, OpenAI Responses and
+//! Gemini native on one listener, picking the dialect from the request path.
+//! Each request consumes the next scripted behavior, so a script such as
+//! `[status 429, success]` drives failover and model-health cooldowns without
+//! touching the network. It runs on the Tauri async runtime and can be started
+//! from the app (see the mock upstream commands) or directly from tests.
+
+use super::transformer::AiProtocol;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const MAX_REQUEST_BYTES: usize = 32 * 1024 * 1024;
+const MAX_RECORDED_REQUESTS: usize = 200;
+const DEFAULT_RESPONSE_TEXT: &str = "Hello from the mock upstream.";
+const INVALID_THINKING_SIGNATURE_MESSAGE: &str =
+    "messages.1.content.0: Invalid 'signature' in 'thinking' block";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MockUpstreamBehavior {
+    /// Regular response in the request's protocol.
+    #[default]
+    Success,
+    /// Regular response after holding the request before any byte is sent.
+    Delay { delay_ms: u64 },
+    /// Streams send headers and then stall; other requests stall before the
+    /// headers. The connection closes after `hold_ms` without a body.
+    FirstByteTimeout { hold_ms: u64 },
+    /// Streams emit the first event and then a protocol error event; other
+    /// requests close the connection halfway through the body.
+    MidStreamError,
+    /// Error response with the given status and a protocol-shaped error body.
+    Status { status_code: u16 },
+    /// Anthropic-style 400 rejecting the signature of a thinking block.
+    InvalidThinkingSignature,
+    /// `200 OK` with no body at all.
+    EmptyStream,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub struct MockUpstreamScript {
+    /// Behaviors applied to successive requests, in order.
+    pub steps: Vec<MockUpstreamBehavior>,
+    /// Behavior once `steps` is exhausted.
+    pub fallback: MockUpstreamBehavior,
+    /// Pause between streamed events.
+    pub chunk_interval_ms: u64,
+    pub response_text: String,
+}
+
+impl Default for MockUpstreamScript {
+    fn default() -> Self {
+        Self {
+            steps: Vec::new(),
+            fallback: MockUpstreamBehavior::Success,
+            chunk_interval_ms: 0,
+            response_text: DEFAULT_RESPONSE_TEXT.to_string(),
+        }
+    }
+}
+
+impl MockUpstreamScript {
+    pub fn with_steps(steps: Vec<MockUpstreamBehavior>) -> Self {
+        Self {
+            steps,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MockUpstreamRequestRecord {
+    pub received_at: DateTime<Utc>,
+    pub method: String,
+    pub path: String,
+    pub protocol: Option<AiProtocol>,
+    pub model: Option<String>,
+    pub stream: bool,
+    pub behavior: MockUpstreamBehavior,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MockUpstreamStatus {
+    pub running: bool,
+    pub base_url: Option<String>,
+    pub request_count: usize,
+    pub script: Option<MockUpstreamScript>,
+    pub recent_requests: Vec<MockUpstreamRequestRecord>,
+}
+
+#[derive(Default)]
+struct MockUpstreamShared {
+    script: MockUpstreamScript,
+    next_step: usize,
+    request_count: usize,
+    requests: Vec<MockUpstreamRequestRecord>,
+}
+
+impl MockUpstreamShared {
+    fn next_behavior(&mut self) -> MockUpstreamBehavior {
+        let behavior = self
+            .script
+            .steps
+            .get(self.next_step)
+            .cloned()
+            .unwrap_or_else(|| self.script.fallback.clone());
+        self.next_step = self.next_step.saturating_add(1);
+        behavior
+    }
+}
+
+pub struct MockUpstreamServer {
+    base_url: String,
+    shared: Arc<Mutex<MockUpstreamShared>>,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+impl MockUpstreamServer {
+    /// Starts on an ephemeral loopback port.
+    pub fn start(script: MockUpstreamScript) -> Result<Self, String> {
+        Self::start_on_port(0, script)
+    }
+
+    pub fn start_on_port(port: u16, script: MockUpstreamScript) -> Result<Self, String> {
+        let listener = std::net::TcpListener::bind(("127.0.0.1", port))
+            .map_err(|error| format!("Failed to bind mock upstream on port {port}: {error}"))?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|error| format!("Failed to configure mock upstream listener: {error}"))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|error| format!("Failed to read mock upstream address: {error}"))?;
+        let shared = Arc::new(Mutex::new(MockUpstreamShared {
+            script,
+            ..MockUpstreamShared::default()
+        }));
+        let task_shared = shared.clone();
+        let task = tauri::async_runtime::spawn(async move {
+            match TcpListener::from_std(listener) {
+                Ok(listener) => run_mock_upstream(listener, task_shared).await,
+                Err(error) => log::warn!("Failed to start mock upstream listener: {error}"),
+            }
+        });
+        Ok(Self {
+            base_url: format!("http://{addr}"),
+            shared,
+            task,
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Replaces the script and restarts it from the first step.
+    pub fn set_script(&self, script: MockUpstreamScript) {
+        if let Ok(mut shared) = self.shared.lock() {
+            shared.script = script;
+            shared.next_step = 0;
+        }
+    }
+
+    pub fn requests(&self) -> Vec<MockUpstreamRequestRecord> {
+        self.shared
+            .lock()
+            .map(|shared| shared.requests.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn status(&self) -> MockUpstreamStatus {
+        let shared = self.shared.lock().ok();
+        MockUpstreamStatus {
+            running: true,
+            base_url: Some(self.base_url.clone()),
+            request_count: shared.as_ref().map_or(0, |shared| shared.request_count),
+            script: shared.as_ref().map(|shared| shared.script.clone()),
+            recent_requests: shared
+                .map(|shared| shared.requests.iter().rev().take(20).cloned().collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Drop for MockUpstreamServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// App-managed slot for the mock upstream launched from the settings page.
+#[derive(Default)]
+pub struct MockUpstreamState {
+    pub server: Mutex<Option<MockUpstreamServer>>,
+}
+
+async fn run_mock_upstream(listener: TcpListener, shared: Arc<Mutex<MockUpstreamShared>>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let shared = shared.clone();
+                tokio::spawn(async move {
+                    if let Err(error) = handle_mock_connection(stream, shared).await {
+                        log::debug!("[mock-upstream] connection error: {error}");
+                    }
+                });
+            }
+            Err(_) => tokio::time::sleep(Duration::from_millis(25)).await,
+        }
+    }
+}
+
+struct MockRequest {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+async fn handle_mock_connection(
+    mut stream: TcpStream,
+    shared: Arc<Mutex<MockUpstreamShared>>,
+) -> std::io::Result<()> {
+    let Some(request) = read_mock_request(&mut stream).await? else {
+        return Ok(());
+    };
+    let protocol = detect_protocol(&request.path);
+    let body = serde_json::from_slice::<Value>(&request.body).unwrap_or(Value::Null);
+    let stream_response = wants_stream(protocol, &request.path, &body);
+    let model = request_model(&request.path, &body);
+    let (behavior, script) = {
+        let mut shared = shared
+            .lock()
+            .map_err(|_| std::io::Error::other("mock upstream state poisoned"))?;
+        let behavior = protocol.map(|_| shared.next_behavior()).unwrap_or_default();
+        shared.request_count = shared.request_count.saturating_add(1);
+        shared.requests.push(MockUpstreamRequestRecord {
+            received_at: Utc::now(),
+            method: request.method.clone(),
+            path: request.path.clone(),
+            protocol,
+            model: model.clone(),
+            stream: stream_response,
+            behavior: behavior.clone(),
+        });
+        if shared.requests.len() > MAX_RECORDED_REQUESTS {
+            shared.requests.remove(0);
+        }
+        (behavior, shared.script.clone())
+    };
+
+    let Some(protocol) = protocol else {
+        let body =
+            json!({"error": {"message": format!("mock upstream does not serve {}", request.path)}});
+        return write_json_response(&mut stream, 404, &body).await;
+    };
+    let model = model.unwrap_or_else(|| "mock-model".to_string());
+    let reply = MockReply {
+        protocol,
+        model: &model,
+        text: &script.response_text,
+        chunk_interval: Duration::from_millis(script.chunk_interval_ms),
+    };
+
+    match behavior {
+        MockUpstreamBehavior::Success => reply.write_success(&mut stream, stream_response).await,
+        MockUpstreamBehavior::Delay { delay_ms } => {
+            tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            reply.write_success(&mut stream, stream_response).await
+        }
+        MockUpstreamBehavior::FirstByteTimeout { hold_ms } => {
+            if stream_response {
+                write_stream_headers(&mut stream).await?;
+            }
+            tokio::time::sleep(Duration::from_millis(hold_ms)).await;
+            Ok(())
+        }
+        MockUpstreamBehavior::MidStreamError => {
+            if stream_response {
+                reply.write_mid_stream_error(&mut stream).await
+            } else {
+                let body = serde_json::to_vec(&reply.json_body()).unwrap_or_default();
+                write_head(&mut stream, 200, "application/json", Some(body.len())).await?;
+                stream.write_all(&body[..body.len() / 2]).await?;
+                stream.flush().await
+            }
+        }
+        MockUpstreamBehavior::Status { status_code } => {
+            let body = error_body(protocol, status_code, &error_message(status_code));
+            write_json_response(&mut stream, status_code, &body).await
+        }
+        MockUpstreamBehavior::InvalidThinkingSignature => {
+            let body = error_body(protocol, 400, INVALID_THINKING_SIGNATURE_MESSAGE);
+            write_json_response(&mut stream, 400, &body).await
+        }
+        MockUpstreamBehavior::EmptyStream => {
+            if stream_response {
+                write_stream_headers(&mut stream).await?;
+            } else {
+                write_head(&mut stream, 200, "application/json", Some(0)).await?;
+            }
+            stream.flush().await
+        }
+    }
+}
+
+async fn read_mock_request(stream: &mut TcpStream) -> std::io::Result<Option<MockRequest>> {
+    let mut raw = Vec::new();
+    let mut buffer = [0_u8; 8192];
+    let header_end = loop {
+        if let Some(index) = raw.windows(4).position(|window| window == b"\r\n\r\n") {
+            break index + 4;
+        }
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        raw.extend_from_slice(&buffer[..read]);
+        if raw.len() > MAX_REQUEST_BYTES {
+            return Err(std::io::Error::other("mock upstream request too large"));
+        }
+    };
+    let head = String::from_utf8_lossy(&raw[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or("/").to_string();
+    let content_length = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0)
+        .min(MAX_REQUEST_BYTES);
+    let mut body = raw[header_end..].to_vec();
+    while body.len() < content_length {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&buffer[..read]);
+    }
+    Ok(Some(MockRequest { method, path, body }))
+}
+
+fn detect_protocol(path: &str) -> Option<AiProtocol> {
+    let path = path.split('?').next().unwrap_or_default();
+    if path.ends_with("/messages") {
+        Some(AiProtocol::AnthropicMessages)
+    } else if path.ends_with("/chat/completions") {
+        Some(AiProtocol::OpenAiChat)
+    } else if path.ends_with("/responses") {
+        Some(AiProtocol::OpenAiResponses)
+    } else if path.contains(":generateContent") || path.contains(":streamGenerateContent") {
+        Some(AiProtocol::GeminiNative)
+    } else {
+        None
+    }
+}
+
+fn wants_stream(protocol: Option<AiProtocol>, path: &str, body: &Value) -> bool {
+    match protocol {
+        Some(AiProtocol::GeminiNative) => path.contains(":streamGenerateContent"),
+        Some(_) => body.get("stream").and_then(Value::as_bool).unwrap_or(false),
+        None => false,
+    }
+}
+
+fn request_model(path: &str, body: &Value) -> Option<String> {
+    if let Some(model) = body.get("model").and_then(Value::as_str) {
+        return Some(model.to_string());
+    }
+    let after_models = path.split("/models/").nth(1)?;
+    after_models
+        .split(':')
+        .next()
+        .filter(|model| !model.is_empty())
+        .map(str::to_string)
+}
+
+fn error_message(status_code: u16) -> String {
+    match status_code {
+        429 => "Mock upstream rate limit exceeded".to_string(),
+        code if code >= 500 => format!("Mock upstream server error ({code})"),
+        code => format!("Mock upstream rejected the request ({code})"),
+    }
+}
+
+fn error_body(protocol: AiProtocol, status_code: u16, message: &str) -> Value {
+    match protocol {
+        AiProtocol::AnthropicMessages => {
+            let error_type = match status_code {
+                400 => "invalid_request_error",
+                401 => "authentication_error",
+                429 => "rate_limit_error",
+                529 => "overloaded_error",
+                _ => "api_error",
+            };
+            json!({"type": "error", "error": {"type": error_type, "message": message}})
+        }
+        AiProtocol::OpenAiChat | AiProtocol::OpenAiResponses => {
+            let error_type = match status_code {
+                429 => "rate_limit_exceeded",
+                code if code >= 500 => "server_error",
+                _ => "invalid_request_error",
+            };
+            json!({"error": {"message": message, "type": error_type, "code": null}})
+        }
+        AiProtocol::GeminiNative => {
+            let status = match status_code {
+                400 => "INVALID_ARGUMENT",
+                429 => "RESOURCE_EXHAUSTED",
+                503 => "UNAVAILABLE",
+                _ => "INTERNAL",
+            };
+            json!({"error": {"code": status_code, "message": message, "status": status}})
+        }
+    }
+}
+
+struct MockReply<'a> {
+    protocol: AiProtocol,
+    model: &'a str,
+    text: &'a str,
+    chunk_interval: Duration,
+}
+
+impl MockReply<'_> {
+    async fn write_success(
+        &self,
+        stream: &mut TcpStream,
+        stream_response: bool,
+    ) -> std::io::Result<()> {
+        if !stream_response {
+            return write_json_response(stream, 200, &self.json_body()).await;
+        }
+        write_stream_headers(stream).await?;
+        for event in self.stream_events() {
+            self.write_event(stream, &event).await?;
+        }
+        Ok(())
+    }
+
+    async fn write_mid_stream_error(&self, stream: &mut TcpStream) -> std::io::Result<()> {
+        write_stream_headers(stream).await?;
+        if let Some(first) = self.stream_events().into_iter().next() {
+            self.write_event(stream, &first).await?;
+        }
+        let error = error_body(self.protocol, 500, "Mock upstream failed mid-stream");
+        let event = match self.protocol {
+            AiProtocol::AnthropicMessages => (Some("error"), error.to_string()),
+            AiProtocol::OpenAiResponses => (
+                Some("error"),
+                json!({"type": "error", "message": "Mock upstream failed mid-stream", "code": "server_error"})
+                    .to_string(),
+            ),
+            AiProtocol::OpenAiChat | AiProtocol::GeminiNative => (None, error.to_string()),
+        };
+        self.write_event(stream, &event).await
+    }
+
+    async fn write_event(
+        &self,
+        stream: &mut TcpStream,
+        (event, data): &(Option<&'static str>, String),
+    ) -> std::io::Result<()> {
+        let mut chunk = String::new();
+        if let Some(event) = event {
+            chunk.push_str(&format!("event: {event}\n"));
+        }
+        chunk.push_str(&format!("data: {data}\n\n"));
+        stream.write_all(chunk.as_bytes()).await?;
+        stream.flush().await?;
+        if !self.chunk_interval.is_zero() {
+            tokio::time::sleep(self.chunk_interval).await;
+        }
+        Ok(())
+    }
+
+    fn text_parts(&self) -> (&str, &str) {
+        let middle = self
+            .text
+            .char_indices()
+            .map(|(index, _)| index)
+            .nth(self.text.chars().count() / 2)
+            .unwrap_or(self.text.len());
+        self.text.split_at(middle)
+    }
+
+    fn json_body(&self) -> Value {
+        let model = self.model;
+        let text = self.text;
+        match self.protocol {
+            AiProtocol::AnthropicMessages => json!({
+                "id": "msg_mock",
+                "type": "message",
+                "role": "assistant",
+                "model": model,
+                "content": [{"type": "text", "text": text}],
+                "stop_reason": "end_turn",
+                "stop_sequence": null,
+                "usage": {"input_tokens": 10, "output_tokens": 5}
+            }),
+            AiProtocol::OpenAiChat => json!({
+                "id": "chatcmpl_mock",
+                "object": "chat.completion",
+                "created": 0,
+                "model": model,
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": text},
+                    "finish_reason": "stop"
+                }],
+                "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+            }),
+            AiProtocol::OpenAiResponses => self.responses_object("completed", true),
+            AiProtocol::GeminiNative => json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"text": text}]},
+                    "finishReason": "STOP",
+                    "index": 0
+                }],
+                "usageMetadata": {"promptTokenCount": 10, "candidatesTokenCount": 5, "totalTokenCount": 15},
+                "modelVersion": model,
+                "responseId": "gemini_mock"
+            }),
+        }
+    }
+
+    fn responses_object(&self, status: &str, with_output: bool) -> Value {
+        let output = if with_output {
+            json!([{
+                "type": "message",
+                "id": "msg_mock",
+                "role": "assistant",
+                "status": "completed",
+                "content": [{"type": "output_text", "text": self.text, "annotations": []}]
+            }])
+        } else {
+            json!([])
+        };
+        json!({
+            "id": "resp_mock",
+            "object": "response",
+            "created_at": 0,
+            "status": status,
+            "model": self.model,
+            "output": output,
+            "usage": {"input_tokens": 10, "output_tokens": 5, "total_tokens": 15}
+        })
+    }
+
+    fn stream_events(&self) -> Vec<(Option<&'static str>, String)> {
+        let model = self.model;
+        let (first, second) = self.text_parts();
+        let events: Vec<(Option<&'static str>, Value)> = match self.protocol {
+            AiProtocol::AnthropicMessages => vec![
+                (
+                    Some("message_start"),
+                    json!({"type": "message_start", "message": {
+                        "id": "msg_mock", "type": "message", "role": "assistant", "model": model,
+                        "content": [], "stop_reason": null, "stop_sequence": null,
+                        "usage": {"input_tokens": 10, "output_tokens": 0}
+                    }}),
+                ),
+                (
+                    Some("content_block_start"),
+                    json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+                ),
+                (
+                    Some("content_block_delta"),
+                    json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": first}}),
+                ),
+                (
+                    Some("content_block_delta"),
+                    json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": second}}),
+                ),
+                (
+                    Some("content_block_stop"),
+                    json!({"type": "content_block_stop", "index": 0}),
+                ),
+                (
+                    Some("message_delta"),
+                    json!({"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_sequence": null}, "usage": {"output_tokens": 5}}),
+                ),
+                (Some("message_stop"), json!({"type": "message_stop"})),
+            ],
+            AiProtocol::OpenAiChat => {
+                let chunk = |delta: Value, finish_reason: Value| {
+                    json!({
+                        "id": "chatcmpl_mock", "object": "chat.completion.chunk", "created": 0, "model": model,
+                        "choices": [{"index": 0, "delta": delta, "finish_reason": finish_reason}]
+                    })
+                };
+                let mut usage = chunk(json!({}), json!("stop"));
+                usage["usage"] =
+                    json!({"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15});
+                vec![
+                    (
+                        None,
+                        chunk(json!({"role": "assistant", "content": first}), Value::Null),
+                    ),
+                    (None, chunk(json!({"content": second}), Value::Null)),
+                    (None, usage),
+                ]
+            }
+            AiProtocol::OpenAiResponses => vec![
+                (
+                    Some("response.created"),
+                    json!({"type": "response.created", "response": self.responses_object("in_progress", false)}),
+                ),
+                (
+                    Some("response.output_item.added"),
+                    json!({"type": "response.output_item.added", "output_index": 0, "item": {
+                        "type": "message", "id": "msg_mock", "role": "assistant", "status": "in_progress", "content": []
+                    }}),
+                ),
+                (
+                    Some("response.content_part.added"),
+                    json!({"type": "response.content_part.added", "item_id": "msg_mock", "output_index": 0, "content_index": 0, "part": {"type": "output_text", "text": "", "annotations": []}}),
+                ),
+                (
+                    Some("response.output_text.delta"),
+                    json!({"type": "response.output_text.delta", "item_id": "msg_mock", "output_index": 0, "content_index": 0, "delta": first}),
+                ),
+                (
+                    Some("response.output_text.delta"),
+                    json!({"type": "response.output_text.delta", "item_id": "msg_mock", "output_index": 0, "content_index": 0, "delta": second}),
+                ),
+                (
+                    Some("response.output_text.done"),
+                    json!({"type": "response.output_text.done", "item_id": "msg_mock", "output_index": 0, "content_index": 0, "text": self.text}),
+                ),
+                (
+                    Some("response.output_item.done"),
+                    json!({"type": "response.output_item.done", "output_index": 0, "item": {
+                        "type": "message", "id": "msg_mock", "role": "assistant", "status": "completed",
+                        "content": [{"type": "output_text", "text": self.text, "annotations": []}]
+                    }}),
+                ),
+                (
+                    Some("response.completed"),
+                    json!({"type": "response.completed", "response": self.responses_object("completed", true)}),
+                ),
+            ],
+            AiProtocol::GeminiNative => vec![
+                (
+                    None,
+                    json!({"candidates": [{"content": {"role": "model", "parts": [{"text": first}]}, "index": 0}], "modelVersion": model, "responseId": "gemini_mock"}),
+                ),
+                (
+                    None,
+                    json!({"candidates": [{"content": {"role": "model", "parts": [{"text": second}]}, "finishReason": "STOP", "index": 0}],
+                    "usageMetadata": {"promptTokenCount": 10, "candidatesTokenCount": 5, "totalTokenCount": 15},
+                    "modelVersion": model, "responseId": "gemini_mock"}),
+                ),
+            ],
+        };
+        let mut events = events
+            .into_iter()
+            .map(|(event, data)| (event, data.to_string()))
+            .collect::<Vec<_>>();
+        if self.protocol == AiProtocol::OpenAiChat {
+            events.push((None, "[DONE]".to_string()));
+        }
+        events
+    }
+}
+
+async fn write_head(
+    stream: &mut TcpStream,
+    status_code: u16,
+    content_type: &str,
+    content_length: Option<usize>,
+) -> std::io::Result<()> {
+    let reason = reqwest::StatusCode::from_u16(status_code)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("Unknown");
+    let mut head = format!(
+        "HTTP/1.1 {status_code} {reason}\r\nContent-Type: {content_type}\r\nConnection: close\r\n"
+    );
+    if let Some(length) = content_length {
+        head.push_str(&format!("Content-Length: {length}\r\n"));
+    } else {
+        head.push_str("Cache-Control: no-cache\r\n");
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes()).await
+}
+
+async fn write_stream_headers(stream: &mut TcpStream) -> std::io::Result<()> {
+    write_head(stream, 200, "text/event-stream", None).await?;
+    stream.flush().await
+}
+
+async fn write_json_response(
+    stream: &mut TcpStream,
+    status_code: u16,
+    body: &Value,
+) -> std::io::Result<()> {
+    let body = serde_json::to_vec(body).unwrap_or_default();
+    write_head(stream, status_code, "application/json", Some(body.len())).await?;
+    stream.write_all(&body).await?;
+    stream.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    fn send(base_url: &str, path: &str, body: &str) -> String {
+        let addr = base_url.trim_start_matches("http://");
+        let mut stream = std::net::TcpStream::connect(addr).expect("connect mock upstream");
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .expect("set read timeout");
+        write!(
+            stream,
+            "POST {path} HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .expect("write request");
+        let mut response = String::new();
+        stream.read_to_string(&mut response).expect("read response");
+        response
+    }
+
+    #[test]
+    fn detects_protocol_and_stream_flag_from_request() {
+        assert_eq!(
+            detect_protocol("/v1/messages?beta=true"),
+            Some(AiProtocol::AnthropicMessages)
+        );
+        assert_eq!(
+            detect_protocol("/v1/chat/completions"),
+            Some(AiProtocol::OpenAiChat)
+        );
+        assert_eq!(
+            detect_protocol("/v1/responses"),
+            Some(AiProtocol::OpenAiResponses)
+        );
+        let gemini_path = "/v1beta/models/gemini-2.5-flash:streamGenerateContent?alt=sse";
+        assert_eq!(detect_protocol(gemini_path), Some(AiProtocol::GeminiNative));
+        assert!(wants_stream(
+            Some(AiProtocol::GeminiNative),
+            gemini_path,
+            &Value::Null
+        ));
+        assert_eq!(
+            request_model(gemini_path, &Value::Null).as_deref(),
+            Some("gemini-2.5-flash")
+        );
+        assert_eq!(detect_protocol("/v1/models"), None);
+    }
+
+    #[test]
+    fn scripted_steps_run_in_order_then_fall_back() {
+        let server = MockUpstreamServer::start(MockUpstreamScript::with_steps(vec![
+            MockUpstreamBehavior::Status { status_code: 429 },
+            MockUpstreamBehavior::InvalidThinkingSignature,
+        ]))
+        .expect("start mock upstream");
+        let body = r#"{"model":"m","messages":[]}"#;
+
+        let rate_limited = send(server.base_url(), "/v1/messages", body);
+        assert!(rate_limited.starts_with("HTTP/1.1 429 Too Many Requests"));
+        assert!(rate_limited.contains("rate_limit_error"));
+        let rejected = send(server.base_url(), "/v1/messages", body);
+        assert!(rejected.starts_with("HTTP/1.1 400"));
+        assert!(rejected.contains("Invalid 'signature' in 'thinking' block"));
+        let ok = send(server.base_url(), "/v1/messages", body);
+        assert!(ok.starts_with("HTTP/1.1 200 OK"));
+        assert!(ok.contains(DEFAULT_RESPONSE_TEXT));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[2].behavior, MockUpstreamBehavior::Success);
+        assert_eq!(requests[0].model.as_deref(), Some("m"));
+    }
+
+    #[test]
+    fn streams_each_protocol_and_breaks_mid_stream() {
+        let server = MockUpstreamServer::start(MockUpstreamScript::default()).expect("start");
+        let chat = send(
+            server.base_url(),
+            "/v1/chat/completions",
+            r#"{"model":"m","stream":true,"messages":[]}"#,
+        );
+        assert!(chat.contains("text/event-stream"));
+        assert!(chat.contains("chat.completion.chunk"));
+        assert!(chat.trim_end().ends_with("data: [DONE]"));
+
+        let responses = send(
+            server.base_url(),
+            "/v1/responses",
+            r#"{"model":"m","stream":true,"input":"hi"}"#,
+        );
+        assert!(responses.contains("event: response.completed"));
+
+        server.set_script(MockUpstreamScript::with_steps(vec![
+            MockUpstreamBehavior::MidStreamError,
+            MockUpstreamBehavior::EmptyStream,
+        ]));
+        let broken = send(
+            server.base_url(),
+            "/v1/messages",
+            r#"{"model":"m","stream":true,"messages":[]}"#,
+        );
+        assert!(broken.contains("event: message_start"));
+        assert!(broken.contains("event: error"));
+        assert!(!broken.contains("message_stop"));
+        let empty = send(
+            server.base_url(),
+            "/v1beta/models/g:streamGenerateContent?alt=sse",
+            "{}",
+        );
+        assert!(empty.starts_with("HTTP/1.1 200 OK"));
+        assert!(empty.ends_with("\r\n\r\n"));
+    }
+}
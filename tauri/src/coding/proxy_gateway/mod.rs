@@ -1,6 +1,11 @@
+pub mod access_tokens;
+pub mod budgets;
 pub mod cli_proxy;
 pub mod commands;
+pub mod fixture_capture;
 pub mod listen;
+pub mod load_balance;
+pub mod mock_upstream;
 pub mod model_health;
 pub mod paths;
 pub mod pricing;
@@ -8,6 +13,7 @@ pub mod provider_profiles;
 pub(crate) mod provider_protocol;
 pub mod provider_switch;
 pub mod request_log;
+pub mod response_cache;
 pub(crate) mod retryable_status;
 mod runtime;
 pub mod session_import;
@@ -18,4 +24,5 @@ pub mod usage_parser;
 pub mod usage_stats;
 
 pub use commands::*;
+pub use mock_upstream::MockUpstreamState;
 pub use runtime::ProxyGatewayState;
@@ -19,6 +19,7 @@ pub enum GatewayFailureKind {
     RequestSchema,
     ClientCancelled,
     GatewayParse,
+    ContentFilter,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -92,6 +93,11 @@ pub fn classify_failure(kind: GatewayFailureKind) -> FailureWeight {
             score: 0,
             category: "gateway_parse",
         },
+        GatewayFailureKind::ContentFilter => FailureWeight {
+            scope: FailureScope::None,
+            score: 0,
+            category: "content_filter",
+        },
     }
 }
 
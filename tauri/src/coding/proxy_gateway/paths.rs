@@ -36,6 +36,14 @@ impl ProxyGatewayPaths {
     pub fn request_log_root(&self) -> PathBuf {
         self.root.join("request-logs")
     }
+
+    pub fn fixture_capture_root(&self) -> PathBuf {
+        self.root.join("fixtures")
+    }
+
+    pub fn response_cache_dir(&self) -> PathBuf {
+        self.root.join("response-cache")
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use super::types::ModelPricing;
+use super::types::{ModelPricing, ModelPricingTier};
 use crate::db::SqliteDbState;
 use rusqlite::params;
 use rust_decimal::Decimal;
@@ -9,7 +9,9 @@ pub fn get_model_pricing_list(db_state: &SqliteDbState) -> Result<Vec<ModelPrici
         let mut stmt = conn
             .prepare(
                 "SELECT model_id, display_name, input_cost_per_million, output_cost_per_million,
-                        cache_read_cost_per_million, cache_creation_cost_per_million
+                        cache_read_cost_per_million, cache_creation_cost_per_million,
+                        pricing_tiers, reasoning_cost_per_million, image_cost, request_cost,
+                        batch_discount_percent
                  FROM model_pricing
                  ORDER BY LOWER(display_name), LOWER(model_id)",
             )
@@ -24,6 +26,11 @@ pub fn get_model_pricing_list(db_state: &SqliteDbState) -> Result<Vec<ModelPrici
                     output_cost_per_million: row.get(3)?,
                     cache_read_cost_per_million: row.get(4)?,
                     cache_creation_cost_per_million: row.get(5)?,
+                    tiers: parse_pricing_tiers(row.get::<_, Option<String>>(6)?.as_deref()),
+                    reasoning_cost_per_million: row.get(7)?,
+                    image_cost: row.get(8)?,
+                    request_cost: row.get(9)?,
+                    batch_discount_percent: row.get(10)?,
                 })
             })
             .map_err(|error| format!("Failed to query model pricing list: {error}"))?;
@@ -42,18 +49,27 @@ pub fn upsert_model_pricing(
     pricing: ModelPricing,
 ) -> Result<ModelPricing, String> {
     let normalized_pricing = normalize_model_pricing(pricing)?;
+    let tiers_json = serde_json::to_string(&normalized_pricing.tiers)
+        .map_err(|error| format!("Failed to serialize pricing tiers: {error}"))?;
     db_state.with_conn(|conn| {
         conn.execute(
             "INSERT INTO model_pricing (
                 model_id, display_name, input_cost_per_million, output_cost_per_million,
-                cache_read_cost_per_million, cache_creation_cost_per_million
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                cache_read_cost_per_million, cache_creation_cost_per_million,
+                pricing_tiers, reasoning_cost_per_million, image_cost, request_cost,
+                batch_discount_percent
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
             ON CONFLICT(model_id) DO UPDATE SET
                 display_name = excluded.display_name,
                 input_cost_per_million = excluded.input_cost_per_million,
                 output_cost_per_million = excluded.output_cost_per_million,
                 cache_read_cost_per_million = excluded.cache_read_cost_per_million,
-                cache_creation_cost_per_million = excluded.cache_creation_cost_per_million",
+                cache_creation_cost_per_million = excluded.cache_creation_cost_per_million,
+                pricing_tiers = excluded.pricing_tiers,
+                reasoning_cost_per_million = excluded.reasoning_cost_per_million,
+                image_cost = excluded.image_cost,
+                request_cost = excluded.request_cost,
+                batch_discount_percent = excluded.batch_discount_percent",
             params![
                 normalized_pricing.model_id,
                 normalized_pricing.display_name,
@@ -61,6 +77,11 @@ pub fn upsert_model_pricing(
                 normalized_pricing.output_cost_per_million,
                 normalized_pricing.cache_read_cost_per_million,
                 normalized_pricing.cache_creation_cost_per_million,
+                tiers_json,
+                normalized_pricing.reasoning_cost_per_million,
+                normalized_pricing.image_cost,
+                normalized_pricing.request_cost,
+                normalized_pricing.batch_discount_percent,
             ],
         )
         .map_err(|error| format!("Failed to upsert model pricing: {error}"))?;
@@ -115,9 +136,87 @@ fn normalize_model_pricing(pricing: ModelPricing) -> Result<ModelPricing, String
             "cache_creation_cost_per_million",
             &pricing.cache_creation_cost_per_million,
         )?,
+        tiers: normalize_pricing_tiers(pricing.tiers)?,
+        reasoning_cost_per_million: normalize_optional_cost(
+            "reasoning_cost_per_million",
+            pricing.reasoning_cost_per_million,
+        )?,
+        image_cost: normalize_optional_cost("image_cost", pricing.image_cost)?,
+        request_cost: normalize_optional_cost("request_cost", pricing.request_cost)?,
+        batch_discount_percent: normalize_batch_discount(pricing.batch_discount_percent)?,
     })
 }
 
+/// Validates tier prices and returns the tiers sorted by ascending threshold.
+pub fn normalize_pricing_tiers(
+    tiers: Vec<ModelPricingTier>,
+) -> Result<Vec<ModelPricingTier>, String> {
+    let mut normalized_tiers = Vec::with_capacity(tiers.len());
+    for tier in tiers {
+        if tier.above_input_tokens == 0 {
+            return Err("Pricing tier threshold must be greater than zero".to_string());
+        }
+        normalized_tiers.push(ModelPricingTier {
+            above_input_tokens: tier.above_input_tokens,
+            input_cost_per_million: validate_non_negative_decimal(
+                "tier input_cost_per_million",
+                &tier.input_cost_per_million,
+            )?,
+            output_cost_per_million: validate_non_negative_decimal(
+                "tier output_cost_per_million",
+                &tier.output_cost_per_million,
+            )?,
+            cache_read_cost_per_million: normalize_optional_cost(
+                "tier cache_read_cost_per_million",
+                tier.cache_read_cost_per_million,
+            )?,
+            cache_creation_cost_per_million: normalize_optional_cost(
+                "tier cache_creation_cost_per_million",
+                tier.cache_creation_cost_per_million,
+            )?,
+        });
+    }
+    normalized_tiers.sort_by_key(|tier| tier.above_input_tokens);
+    if normalized_tiers
+        .windows(2)
+        .any(|pair| pair[0].above_input_tokens == pair[1].above_input_tokens)
+    {
+        return Err("Pricing tier thresholds must be unique".to_string());
+    }
+    Ok(normalized_tiers)
+}
+
+/// Empty optional prices are stored as NULL rather than "0" so that "not
+/// billed separately" stays distinguishable from "free".
+pub fn normalize_optional_cost(
+    label: &str,
+    value: Option<String>,
+) -> Result<Option<String>, String> {
+    match value {
+        Some(value) if !value.trim().is_empty() => {
+            validate_non_negative_decimal(label, &value).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+pub fn normalize_batch_discount(value: Option<String>) -> Result<Option<String>, String> {
+    let Some(discount) = normalize_optional_cost("batch_discount_percent", value)? else {
+        return Ok(None);
+    };
+    if Decimal::from_str(&discount).unwrap_or_default() > Decimal::ONE_HUNDRED {
+        return Err("batch_discount_percent must not exceed 100".to_string());
+    }
+    Ok(Some(discount))
+}
+
+/// Tiers stored on a pricing row; NULL means they were never set.
+pub(crate) fn parse_pricing_tiers(value: Option<&str>) -> Vec<ModelPricingTier> {
+    value
+        .and_then(|value| serde_json::from_str(value).ok())
+        .unwrap_or_default()
+}
+
 fn validate_non_negative_decimal(label: &str, value: &str) -> Result<String, String> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -143,6 +242,11 @@ mod tests {
             output_cost_per_million: "2.5".to_string(),
             cache_read_cost_per_million: "0.125".to_string(),
             cache_creation_cost_per_million: "0.75".to_string(),
+            tiers: Vec::new(),
+            reasoning_cost_per_million: None,
+            image_cost: None,
+            request_cost: None,
+            batch_discount_percent: None,
         }
     }
 
@@ -166,6 +270,80 @@ mod tests {
             .any(|pricing| pricing.model_id == "test-model-pricing-crud"));
     }
 
+    #[test]
+    fn model_pricing_round_trips_sorted_tiers_and_extra_fees() {
+        let db_state = SqliteDbState::in_memory_for_test().expect("sqlite");
+        let mut pricing = sample_pricing();
+        pricing.tiers = vec![
+            ModelPricingTier {
+                above_input_tokens: 1_000_000,
+                input_cost_per_million: "4".to_string(),
+                output_cost_per_million: "8".to_string(),
+                cache_read_cost_per_million: None,
+                cache_creation_cost_per_million: None,
+            },
+            ModelPricingTier {
+                above_input_tokens: 200_000,
+                input_cost_per_million: "2.5".to_string(),
+                output_cost_per_million: "5".to_string(),
+                cache_read_cost_per_million: Some("0.25".to_string()),
+                cache_creation_cost_per_million: Some(" ".to_string()),
+            },
+        ];
+        pricing.request_cost = Some("0.01".to_string());
+        pricing.reasoning_cost_per_million = Some("10".to_string());
+        pricing.image_cost = Some(String::new());
+        pricing.batch_discount_percent = Some("50".to_string());
+
+        upsert_model_pricing(&db_state, pricing).expect("upsert");
+        let saved = get_model_pricing_list(&db_state)
+            .expect("list")
+            .into_iter()
+            .find(|pricing| pricing.model_id == "test-model-pricing-crud")
+            .expect("saved pricing");
+
+        let thresholds: Vec<u64> = saved
+            .tiers
+            .iter()
+            .map(|tier| tier.above_input_tokens)
+            .collect();
+        assert_eq!(thresholds, vec![200_000, 1_000_000]);
+        assert_eq!(saved.tiers[0].cache_creation_cost_per_million, None);
+        assert_eq!(saved.request_cost.as_deref(), Some("0.01"));
+        assert_eq!(saved.reasoning_cost_per_million.as_deref(), Some("10"));
+        assert_eq!(saved.image_cost, None);
+        assert_eq!(saved.batch_discount_percent.as_deref(), Some("50"));
+
+        let mut cleared = saved;
+        cleared.request_cost = Some(" ".to_string());
+        upsert_model_pricing(&db_state, cleared).expect("clear request cost");
+        let saved = get_model_pricing_list(&db_state)
+            .expect("list")
+            .into_iter()
+            .find(|pricing| pricing.model_id == "test-model-pricing-crud")
+            .expect("saved pricing");
+        assert_eq!(saved.request_cost, None);
+    }
+
+    #[test]
+    fn model_pricing_rejects_duplicate_tier_thresholds_and_large_discounts() {
+        let db_state = SqliteDbState::in_memory_for_test().expect("sqlite");
+        let tier = ModelPricingTier {
+            above_input_tokens: 200_000,
+            input_cost_per_million: "2".to_string(),
+            output_cost_per_million: "4".to_string(),
+            cache_read_cost_per_million: None,
+            cache_creation_cost_per_million: None,
+        };
+        let mut pricing = sample_pricing();
+        pricing.tiers = vec![tier.clone(), tier];
+        assert!(upsert_model_pricing(&db_state, pricing).is_err());
+
+        let mut pricing = sample_pricing();
+        pricing.batch_discount_percent = Some("150".to_string());
+        assert!(upsert_model_pricing(&db_state, pricing).is_err());
+    }
+
     #[test]
     fn model_pricing_rejects_negative_cost() {
         let db_state = SqliteDbState::in_memory_for_test().expect("sqlite");
@@ -2,7 +2,7 @@ use super::cli_proxy;
 use super::paths::ProxyGatewayPaths;
 use super::provider_protocol;
 use super::runtime::ProxyGatewayState;
-use super::types::{GatewayCliKey, GatewayCliTakeoverStatus, GatewayProxyMode, ProxyGatewayStatus};
+use super::types::{GatewayCliKey, GatewayCliTakeoverStatus, ProxyGatewayStatus};
 use crate::db::helpers::db_get;
 use crate::db::schema::DbTable;
 use crate::db::SqliteDbState;
@@ -98,10 +98,16 @@ pub async fn apply_or_switch_provider<R: Runtime>(
     )
     .await?;
 
-    if previous_mode == GatewayProxyMode::Failover {
+    if previous_mode.is_multi_provider() {
         let refreshed_gateway_status = current_gateway_status(&gateway_state)?;
-        next_status =
-            cli_proxy::engage_failover_cli(db, &paths, cli_key, &refreshed_gateway_status).await?;
+        next_status = cli_proxy::engage_multi_provider_cli(
+            db,
+            &paths,
+            cli_key,
+            &refreshed_gateway_status,
+            previous_mode,
+        )
+        .await?;
     }
 
     gateway_state.clear_provider_cache()?;
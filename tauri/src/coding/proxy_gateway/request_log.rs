@@ -50,6 +50,56 @@ pub fn is_sensitive_header(normalized_name: &str) -> bool {
         || normalized_name.ends_with("-api-key")
 }
 
+/// Whether a JSON body field holds a credential, e.g. `api_key` or
+/// `client_secret`. Matches on the name only, whatever the value's type.
+pub fn is_sensitive_body_key(key: &str) -> bool {
+    let normalized = key.to_ascii_lowercase();
+    let separator_normalized = normalized
+        .chars()
+        .map(|character| match character {
+            '-' | ' ' => '_',
+            _ => character,
+        })
+        .collect::<String>();
+    is_sensitive_header(&normalized)
+        || is_sensitive_header(&separator_normalized)
+        || normalized.contains("secret")
+        || separator_normalized.contains("secret")
+        || normalized.contains("password")
+        || separator_normalized.contains("password")
+        || normalized.contains("credential")
+        || separator_normalized.contains("credential")
+        || normalized == "token"
+        || separator_normalized == "token"
+        || normalized.contains("access_token")
+        || separator_normalized.contains("access_token")
+        || normalized.contains("refresh_token")
+        || separator_normalized.contains("refresh_token")
+        || normalized == "key"
+        || separator_normalized == "key"
+        || normalized == "apikey"
+        || separator_normalized == "api_key"
+        || separator_normalized == "apikey"
+}
+
+/// Whether a whitespace-separated token looks like a provider or GitHub API
+/// key. Expects the token already lowercased.
+pub fn is_secret_like_token(lower: &str) -> bool {
+    [
+        "sk-",
+        "sk_",
+        "xai-",
+        "ghp_",
+        "gho_",
+        "ghu_",
+        "ghs_",
+        "ghr_",
+        "github_pat_",
+    ]
+    .iter()
+    .any(|prefix| lower.starts_with(prefix))
+}
+
 pub fn redact_request_path(path: &str) -> String {
     let trimmed = path.trim();
     if trimmed.is_empty() {
@@ -586,6 +636,7 @@ mod tests {
             output_tokens: Some(20),
             cache_read_tokens: Some(0),
             cache_creation_tokens: Some(0),
+            reasoning_tokens: None,
             total_tokens: Some(30),
             request_body_bytes: 2,
             response_body_bytes: 11,
@@ -593,6 +644,10 @@ mod tests {
             first_token_ms: None,
             detail_file: None,
             detail_offset: None,
+            client_label: None,
+            cache_hit: false,
+            image_count: 0,
+            batch_priced: false,
         };
         let record = new_request_log_record(GatewayRequestLogDetail {
             summary,
@@ -0,0 +1,460 @@
+//! Exact-match response cache for deterministic gateway requests.
+//!
+//! Entries are keyed on the canonicalized client request body together with
+//! the CLI route, target provider and upstream model, and stored as one JSON
+//! file per key under the gateway data directory. Streaming responses are kept
+//! as parsed SSE events so a hit is re-framed as a clean event stream for the
+//! client protocol.
+
+use super::paths::ProxyGatewayPaths;
+use super::request_log;
+use super::transformer::{fixture_events_to_sse, FixtureStreamEvent};
+use super::types::{GatewayCliKey, GatewayResponseCacheStats, ProxyGatewaySettings};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const RESPONSE_CACHE_SCHEMA_VERSION: u32 = 1;
+const CACHE_ENTRY_EXTENSION: &str = "json";
+
+/// Top-level request fields that identify the caller or session rather than
+/// shape the model output. They are dropped before hashing so side calls from
+/// different sessions can share an entry.
+const NON_SEMANTIC_REQUEST_FIELDS: &[&str] =
+    &["metadata", "user", "prompt_cache_key", "safety_identifier"];
+
+pub struct ResponseCacheKeyInput<'a> {
+    pub cli_key: GatewayCliKey,
+    pub method: &'a str,
+    /// Client request target. Secrets in the query are redacted before hashing.
+    pub path: &'a str,
+    pub provider_id: &'a str,
+    pub upstream_model_id: &'a str,
+    pub request_body: &'a [u8],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CachedResponse {
+    pub schema_version: u32,
+    pub created_at: DateTime<Utc>,
+    pub status_code: u16,
+    pub content_type: Option<String>,
+    pub body: CachedResponseBody,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CachedResponseBody {
+    Buffered { body: String },
+    Stream { events: Vec<FixtureStreamEvent> },
+}
+
+impl CachedResponse {
+    pub fn new(
+        created_at: DateTime<Utc>,
+        status_code: u16,
+        content_type: Option<String>,
+        body: CachedResponseBody,
+    ) -> Self {
+        Self {
+            schema_version: RESPONSE_CACHE_SCHEMA_VERSION,
+            created_at,
+            status_code,
+            content_type,
+            body,
+        }
+    }
+
+    pub fn is_streaming(&self) -> bool {
+        matches!(self.body, CachedResponseBody::Stream { .. })
+    }
+
+    /// Client-facing body bytes; stream events are re-framed as SSE.
+    pub fn body_bytes(&self) -> Vec<u8> {
+        match &self.body {
+            CachedResponseBody::Buffered { body } => body.as_bytes().to_vec(),
+            CachedResponseBody::Stream { events } => fixture_events_to_sse(events),
+        }
+    }
+}
+
+/// Whether a request may be answered from the cache: non-streaming requests,
+/// or streaming requests that pin sampling with temperature 0.
+pub fn request_is_cacheable(request_body: &[u8], streaming: bool) -> bool {
+    let Ok(Value::Object(body)) = serde_json::from_slice::<Value>(request_body) else {
+        return false;
+    };
+    !streaming || request_temperature(&body).is_some_and(|temperature| temperature == 0.0)
+}
+
+fn request_temperature(body: &Map<String, Value>) -> Option<f64> {
+    body.get("temperature")
+        .or_else(|| {
+            body.get("generationConfig")
+                .and_then(|config| config.get("temperature"))
+        })
+        .and_then(Value::as_f64)
+}
+
+/// Hex SHA-256 cache key, or `None` when the body is not a JSON object.
+pub fn response_cache_key(input: &ResponseCacheKeyInput<'_>) -> Option<String> {
+    let Ok(Value::Object(mut body)) = serde_json::from_slice::<Value>(input.request_body) else {
+        return None;
+    };
+    for field in NON_SEMANTIC_REQUEST_FIELDS {
+        body.remove(*field);
+    }
+    let material = json!({
+        "version": RESPONSE_CACHE_SCHEMA_VERSION,
+        "cli_key": input.cli_key.as_str(),
+        "method": input.method.to_ascii_uppercase(),
+        "path": request_log::redact_request_path(input.path),
+        "provider_id": input.provider_id,
+        "upstream_model_id": input.upstream_model_id,
+        "body": canonicalize_json(Value::Object(body)),
+    });
+    let bytes = serde_json::to_vec(&material).ok()?;
+    Some(hex::encode(Sha256::digest(bytes)))
+}
+
+/// Recursively sorts object keys so key order in the client body does not
+/// change the cache key.
+fn canonicalize_json(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries = object.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize_json(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(canonicalize_json).collect()),
+        other => other,
+    }
+}
+
+/// Returns the entry for `key` when it exists and is younger than the TTL.
+/// Expired or unreadable entries are removed.
+pub fn lookup_cached_response(
+    paths: &ProxyGatewayPaths,
+    settings: &ProxyGatewaySettings,
+    key: &str,
+    now: DateTime<Utc>,
+) -> Option<CachedResponse> {
+    let path = entry_path(paths, key);
+    let text = fs::read_to_string(&path).ok()?;
+    let entry = match serde_json::from_str::<CachedResponse>(&text) {
+        Ok(entry) if entry.schema_version == RESPONSE_CACHE_SCHEMA_VERSION => entry,
+        _ => {
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+    };
+    let age_secs = now
+        .signed_duration_since(entry.created_at)
+        .num_seconds()
+        .max(0) as u64;
+    if age_secs >= settings.response_cache_ttl_secs {
+        let _ = fs::remove_file(&path);
+        return None;
+    }
+    Some(entry)
+}
+
+/// Writes an entry and evicts expired and oldest entries until the cache fits
+/// the configured size. Returns `Ok(false)` when the entry alone exceeds it.
+pub fn store_cached_response(
+    paths: &ProxyGatewayPaths,
+    settings: &ProxyGatewaySettings,
+    key: &str,
+    entry: &CachedResponse,
+) -> Result<bool, String> {
+    let max_bytes = settings
+        .response_cache_max_size_mb
+        .saturating_mul(1024 * 1024);
+    let text = serde_json::to_string(entry)
+        .map_err(|error| format!("Failed to serialize response cache entry: {error}"))?;
+    if text.len() as u64 > max_bytes {
+        return Ok(false);
+    }
+    let dir = paths.response_cache_dir();
+    fs::create_dir_all(&dir).map_err(|error| {
+        format!(
+            "Failed to create response cache directory {}: {error}",
+            dir.display()
+        )
+    })?;
+    let path = entry_path(paths, key);
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, text).map_err(|error| {
+        format!(
+            "Failed to write response cache entry {}: {error}",
+            temp_path.display()
+        )
+    })?;
+    fs::rename(&temp_path, &path).map_err(|error| {
+        let _ = fs::remove_file(&temp_path);
+        format!(
+            "Failed to write response cache entry {}: {error}",
+            path.display()
+        )
+    })?;
+    enforce_cache_limits(&dir, settings.response_cache_ttl_secs, max_bytes)?;
+    Ok(true)
+}
+
+pub fn response_cache_stats(
+    paths: &ProxyGatewayPaths,
+) -> Result<GatewayResponseCacheStats, String> {
+    let entries = list_cache_entries(&paths.response_cache_dir())?;
+    Ok(GatewayResponseCacheStats {
+        entry_count: entries.len() as u64,
+        total_bytes: entries.iter().map(|entry| entry.size).sum(),
+    })
+}
+
+pub fn clear_response_cache(
+    paths: &ProxyGatewayPaths,
+) -> Result<GatewayResponseCacheStats, String> {
+    let stats = response_cache_stats(paths)?;
+    let dir = paths.response_cache_dir();
+    if dir.is_dir() {
+        fs::remove_dir_all(&dir).map_err(|error| {
+            format!(
+                "Failed to clear response cache directory {}: {error}",
+                dir.display()
+            )
+        })?;
+    }
+    Ok(stats)
+}
+
+fn entry_path(paths: &ProxyGatewayPaths, key: &str) -> PathBuf {
+    paths
+        .response_cache_dir()
+        .join(format!("{key}.{CACHE_ENTRY_EXTENSION}"))
+}
+
+struct CacheEntryFile {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+fn list_cache_entries(dir: &Path) -> Result<Vec<CacheEntryFile>, String> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let read_dir = fs::read_dir(dir).map_err(|error| {
+        format!(
+            "Failed to read response cache directory {}: {error}",
+            dir.display()
+        )
+    })?;
+    let mut entries = Vec::new();
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|extension| extension.to_str()) != Some(CACHE_ENTRY_EXTENSION)
+        {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        entries.push(CacheEntryFile {
+            path,
+            size: metadata.len(),
+            modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+        });
+    }
+    Ok(entries)
+}
+
+fn enforce_cache_limits(dir: &Path, ttl_secs: u64, max_bytes: u64) -> Result<(), String> {
+    let mut entries = list_cache_entries(dir)?;
+    let now = SystemTime::now();
+    entries.retain(|entry| {
+        let expired = now
+            .duration_since(entry.modified)
+            .is_ok_and(|age| age.as_secs() >= ttl_secs);
+        if expired {
+            let _ = fs::remove_file(&entry.path);
+        }
+        !expired
+    });
+    let mut total_bytes = entries.iter().map(|entry| entry.size).sum::<u64>();
+    if total_bytes <= max_bytes {
+        return Ok(());
+    }
+    entries.sort_by_key(|entry| entry.modified);
+    for entry in entries {
+        if total_bytes <= max_bytes {
+            break;
+        }
+        if fs::remove_file(&entry.path).is_ok() {
+            total_bytes = total_bytes.saturating_sub(entry.size);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_input(body: &[u8]) -> ResponseCacheKeyInput<'_> {
+        ResponseCacheKeyInput {
+            cli_key: GatewayCliKey::Claude,
+            method: "POST",
+            path: "/anthropic/v1/messages",
+            provider_id: "provider-a",
+            upstream_model_id: "claude-haiku",
+            request_body: body,
+        }
+    }
+
+    fn buffered_entry(created_at: DateTime<Utc>, body: &str) -> CachedResponse {
+        CachedResponse::new(
+            created_at,
+            200,
+            Some("application/json".to_string()),
+            CachedResponseBody::Buffered {
+                body: body.to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn cache_key_ignores_key_order_and_session_identifiers() {
+        let first = response_cache_key(&key_input(
+            br#"{"model":"claude-haiku","max_tokens":32,"messages":[{"role":"user","content":"title"}],"metadata":{"user_id":"session-a"}}"#,
+        ))
+        .unwrap();
+        let second = response_cache_key(&key_input(
+            br#"{"messages":[{"content":"title","role":"user"}],"max_tokens":32,"model":"claude-haiku","metadata":{"user_id":"session-b"}}"#,
+        ))
+        .unwrap();
+        let different_prompt = response_cache_key(&key_input(
+            br#"{"model":"claude-haiku","max_tokens":32,"messages":[{"role":"user","content":"other"}]}"#,
+        ))
+        .unwrap();
+        let other_provider = response_cache_key(&ResponseCacheKeyInput {
+            provider_id: "provider-b",
+            ..key_input(
+                br#"{"model":"claude-haiku","max_tokens":32,"messages":[{"role":"user","content":"title"}]}"#,
+            )
+        })
+        .unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, different_prompt);
+        assert_ne!(first, other_provider);
+        assert!(response_cache_key(&key_input(b"not json")).is_none());
+    }
+
+    #[test]
+    fn only_non_streaming_or_temperature_zero_requests_are_cacheable() {
+        assert!(request_is_cacheable(br#"{"messages":[]}"#, false));
+        assert!(!request_is_cacheable(
+            br#"{"messages":[],"stream":true}"#,
+            true
+        ));
+        assert!(request_is_cacheable(
+            br#"{"messages":[],"stream":true,"temperature":0}"#,
+            true
+        ));
+        assert!(request_is_cacheable(
+            br#"{"contents":[],"generationConfig":{"temperature":0.0}}"#,
+            true
+        ));
+        assert!(!request_is_cacheable(b"[]", false));
+    }
+
+    #[test]
+    fn lookup_honours_ttl_and_store_evicts_oldest_entries_over_the_size_cap() {
+        let temp = tempfile::tempdir().unwrap();
+        let paths = ProxyGatewayPaths::new(temp.path());
+        let mut settings = ProxyGatewaySettings {
+            response_cache_ttl_secs: 60,
+            ..ProxyGatewaySettings::default()
+        };
+        let now = Utc::now();
+
+        assert!(
+            store_cached_response(&paths, &settings, "fresh", &buffered_entry(now, "{}")).unwrap()
+        );
+        assert!(lookup_cached_response(&paths, &settings, "fresh", now).is_some());
+        assert!(lookup_cached_response(
+            &paths,
+            &settings,
+            "fresh",
+            now + chrono::Duration::seconds(61)
+        )
+        .is_none());
+        assert_eq!(response_cache_stats(&paths).unwrap().entry_count, 0);
+
+        settings.response_cache_max_size_mb = 1;
+        let large_body = "x".repeat(400 * 1024);
+        for key in ["first", "second", "third"] {
+            store_cached_response(&paths, &settings, key, &buffered_entry(now, &large_body))
+                .unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        let stats = response_cache_stats(&paths).unwrap();
+        assert_eq!(stats.entry_count, 2);
+        assert!(stats.total_bytes <= 1024 * 1024);
+        assert!(lookup_cached_response(&paths, &settings, "first", now).is_none());
+        assert!(lookup_cached_response(&paths, &settings, "third", now).is_some());
+
+        let too_large = "x".repeat(2 * 1024 * 1024);
+        assert!(!store_cached_response(
+            &paths,
+            &settings,
+            "huge",
+            &buffered_entry(now, &too_large)
+        )
+        .unwrap());
+        assert_eq!(clear_response_cache(&paths).unwrap().entry_count, 2);
+        assert_eq!(response_cache_stats(&paths).unwrap().entry_count, 0);
+    }
+
+    #[test]
+    fn cached_streams_replay_as_framed_sse() {
+        let entry = CachedResponse::new(
+            Utc::now(),
+            200,
+            Some("text/event-stream".to_string()),
+            CachedResponseBody::Stream {
+                events: vec![
+                    FixtureStreamEvent {
+                        last_event_id: String::new(),
+                        event_type: "message_start".to_string(),
+                        data: r#"{"type":"message_start"}"#.to_string(),
+                    },
+                    FixtureStreamEvent {
+                        last_event_id: String::new(),
+                        event_type: String::new(),
+                        data: "[DONE]".to_string(),
+                    },
+                ],
+            },
+        );
+
+        assert!(entry.is_streaming());
+        assert_eq!(
+            String::from_utf8(entry.body_bytes()).unwrap(),
+            "event: message_start\ndata: {\"type\":\"message_start\"}\n\ndata: [DONE]\n\n"
+        );
+    }
+}
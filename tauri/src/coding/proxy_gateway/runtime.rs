@@ -4,14 +4,17 @@ mod connectivity_test;
 mod content_encoding;
 mod header_preserving_client;
 mod http_io;
+mod metrics;
 mod middleware;
 mod observability;
 mod pipeline;
 mod providers;
+mod response_cache;
 mod routes;
 mod side_stores;
 mod thinking_budget;
 mod upstream;
+mod upstream_auth;
 
 pub(crate) use self::connectivity_test::test_gateway_provider_model_connectivity;
 #[cfg(test)]
@@ -40,7 +43,10 @@ use self::upstream::build_upstream_headers;
 use self::upstream::route_request;
 #[cfg(test)]
 use self::upstream::{route_request_with_options, GatewayRequestOptions};
+use super::access_tokens::{self, GatewayAccessDecision, GatewayAccessDenial, StoredAccessToken};
+use super::budgets::{self, BudgetTracker};
 use super::listen::bind_gateway_listener;
+use super::load_balance::LoadBalancer;
 use super::model_health::ModelHealthRegistry;
 use super::paths::ProxyGatewayPaths;
 #[cfg(test)]
@@ -60,7 +66,7 @@ use serde_json::json;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::io::{Read, Write};
-use std::net::{SocketAddr, TcpStream};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
@@ -79,10 +85,11 @@ const BUSY_RESPONSE_BODY: &[u8] = br#"{"error":"gateway_busy","message":"too man
 
 fn bind_gateway_listener_with_retry(
     settings: &ProxyGatewaySettings,
+    allow_lan: bool,
 ) -> Result<super::listen::BoundGatewayListener, String> {
     let mut last_error = None;
     for attempt in 0..RESTART_BIND_RETRY_ATTEMPTS {
-        match bind_gateway_listener(settings) {
+        match bind_gateway_listener(settings, allow_lan) {
             Ok(bound) => return Ok(bound),
             Err(error) => {
                 last_error = Some(error);
@@ -109,6 +116,22 @@ impl ProxyGatewayState {
             .map_err(|_| "Proxy gateway manager lock poisoned".to_string())?;
         manager.clear_provider_cache()
     }
+
+    pub fn reload_access_tokens(&self) -> Result<(), String> {
+        let manager = self
+            .manager
+            .lock()
+            .map_err(|_| "Proxy gateway manager lock poisoned".to_string())?;
+        manager.reload_access_tokens()
+    }
+
+    pub fn reload_budget_rules(&self) -> Result<(), String> {
+        let manager = self
+            .manager
+            .lock()
+            .map_err(|_| "Proxy gateway manager lock poisoned".to_string())?;
+        manager.reload_budget_rules()
+    }
 }
 
 pub struct ProxyGatewayManager {
@@ -238,7 +261,8 @@ impl ProxyGatewayManager {
             runtime.stop();
         }
 
-        let bound = match bind_gateway_listener_with_retry(&bind_settings) {
+        let allow_lan = access_tokens::lan_listen_allowed(&db);
+        let bound = match bind_gateway_listener_with_retry(&bind_settings, allow_lan) {
             Ok(bound) => bound,
             Err(error) => {
                 return Err(self.fail_restart_after_stop(restart_settings, error));
@@ -292,7 +316,7 @@ impl ProxyGatewayManager {
             return Ok(self.status());
         }
 
-        let bound = match bind_gateway_listener(&settings) {
+        let bound = match bind_gateway_listener(&settings, context.lan_listen_allowed()) {
             Ok(bound) => bound,
             Err(error) => {
                 self.last_error = Some(error.clone());
@@ -336,6 +360,20 @@ impl ProxyGatewayManager {
         Ok(())
     }
 
+    pub fn reload_access_tokens(&self) -> Result<(), String> {
+        if let Some(runtime) = self.runtime.as_ref() {
+            runtime.context.reload_access_tokens()?;
+        }
+        Ok(())
+    }
+
+    pub fn reload_budget_rules(&self) -> Result<(), String> {
+        if let Some(runtime) = self.runtime.as_ref() {
+            runtime.context.reload_budget_rules()?;
+        }
+        Ok(())
+    }
+
     pub fn status(&self) -> ProxyGatewayStatus {
         match &self.runtime {
             Some(runtime) => ProxyGatewayStatus {
@@ -418,10 +456,18 @@ impl ProxyGatewayRuntime {
         bound: super::listen::BoundGatewayListener,
         context: GatewayRuntimeContext,
     ) -> Result<Self, String> {
-        let addr = bound
+        let mut addr = bound
             .listener
             .local_addr()
             .map_err(|error| format!("Failed to read gateway listener address: {error}"))?;
+        // A wildcard bind cannot be dialed on every platform; health checks and
+        // the shutdown wake-up connect through loopback instead.
+        if addr.ip().is_unspecified() {
+            addr.set_ip(match addr.ip() {
+                IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            });
+        }
         let running = Arc::new(AtomicBool::new(true));
         let server_running = running.clone();
         let settings = context.settings.clone();
@@ -510,6 +556,10 @@ struct GatewayRuntimeContext {
     health_path: Option<PathBuf>,
     app_handle: Option<AppHandle>,
     provider_cache: Arc<Mutex<HashMap<GatewayCliKey, ProviderCacheEntry>>>,
+    load_balancer: Arc<Mutex<LoadBalancer>>,
+    access_tokens: Arc<RwLock<Vec<StoredAccessToken>>>,
+    budget_tracker: Arc<Mutex<BudgetTracker>>,
+    metrics: Arc<metrics::GatewayMetrics>,
     side_stores: side_stores::GatewaySideStores,
 }
 
@@ -567,6 +617,26 @@ impl GatewayRuntimeContext {
             };
             Arc::new(Mutex::new(registry))
         });
+        let access_tokens = db
+            .as_ref()
+            .map(|db| {
+                db.with_conn(access_tokens::load_access_tokens)
+                    .unwrap_or_else(|error| {
+                        log::warn!("Failed to load proxy gateway access tokens: {error}");
+                        Vec::new()
+                    })
+            })
+            .unwrap_or_default();
+        let budget_rules = db
+            .as_ref()
+            .map(|db| {
+                db.with_conn(budgets::load_budget_rules)
+                    .unwrap_or_else(|error| {
+                        log::warn!("Failed to load proxy gateway budget rules: {error}");
+                        Vec::new()
+                    })
+            })
+            .unwrap_or_default();
         Self {
             db,
             paths,
@@ -576,6 +646,10 @@ impl GatewayRuntimeContext {
             health_path,
             app_handle: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            load_balancer: Arc::new(Mutex::new(LoadBalancer::new())),
+            access_tokens: Arc::new(RwLock::new(access_tokens)),
+            budget_tracker: Arc::new(Mutex::new(BudgetTracker::new(budget_rules))),
+            metrics: Arc::new(metrics::GatewayMetrics::default()),
             side_stores: side_stores::GatewaySideStores::default(),
         }
     }
@@ -615,6 +689,56 @@ impl GatewayRuntimeContext {
         }
     }
 
+    fn lan_listen_allowed(&self) -> bool {
+        self.access_tokens
+            .read()
+            .map(|tokens| !tokens.is_empty())
+            .unwrap_or(false)
+    }
+
+    fn reload_access_tokens(&self) -> Result<(), String> {
+        let Some(db) = self.db.as_ref() else {
+            return Ok(());
+        };
+        let tokens = db.with_conn(access_tokens::load_access_tokens)?;
+        let mut current = self
+            .access_tokens
+            .write()
+            .map_err(|_| "Proxy gateway access token lock poisoned".to_string())?;
+        *current = tokens;
+        Ok(())
+    }
+
+    fn reload_budget_rules(&self) -> Result<(), String> {
+        let Some(db) = self.db.as_ref() else {
+            return Ok(());
+        };
+        let rules = db.with_conn(budgets::load_budget_rules)?;
+        self.budget_tracker
+            .lock()
+            .map_err(|_| "Proxy gateway budget lock poisoned".to_string())?
+            .replace_rules(rules);
+        Ok(())
+    }
+
+    /// Authenticate the client before any routing happens. A poisoned token
+    /// lock fails closed: remote clients are rejected, local ones unlabelled.
+    fn authorize_inbound(
+        &self,
+        request: &self::http_io::DebugHttpRequest,
+        peer_is_local: bool,
+    ) -> GatewayAccessDecision {
+        let cli_key = routes::match_gateway_route(&request.path).map(|route| route.cli_key);
+        match self.access_tokens.read() {
+            Ok(tokens) => {
+                access_tokens::authorize_request(&tokens, &request.headers, cli_key, peer_is_local)
+            }
+            Err(_) => {
+                access_tokens::authorize_request(&[], &request.headers, cli_key, peer_is_local)
+            }
+        }
+    }
+
     fn clear_provider_cache(&self) -> Result<(), String> {
         let mut cache = self
             .provider_cache
@@ -806,26 +930,45 @@ async fn handle_connection(
     let started_instant = Instant::now();
     let settings = context.settings_snapshot();
 
-    // Codex Desktop official-login clients may send zstd-compressed JSON bodies.
-    // Decode before routing/JSON parsing so passthrough and conversion both see plain JSON.
-    let mut response = match decode_inbound_request_body(&mut request) {
-        Ok(()) => route_request(&request, context).await,
-        Err(message) => {
-            let mut response = json_response(
-                400,
-                "Bad Request",
-                json!({
-                    "error": "invalid_request",
-                    "message": message,
-                }),
-                "request_decode",
-                None,
-                &message,
-            );
-            response.error_category = Some("invalid_request".to_string());
-            response
-        }
-    };
+    let (mut response, client_label) =
+        match context.authorize_inbound(&request, is_local_peer(stream)) {
+            GatewayAccessDecision::Allowed { client_label } => {
+                // Codex Desktop official-login clients may send zstd-compressed JSON bodies.
+                // Decode before routing/JSON parsing so passthrough and conversion both see plain JSON.
+                let response = match decode_inbound_request_body(&mut request) {
+                    Ok(()) => route_request(&request, context).await,
+                    Err(message) => {
+                        let mut response = json_response(
+                            400,
+                            "Bad Request",
+                            json!({
+                                "error": "invalid_request",
+                                "message": message,
+                            }),
+                            "request_decode",
+                            None,
+                            &message,
+                        );
+                        response.error_category = Some("invalid_request".to_string());
+                        response
+                    }
+                };
+                (response, client_label)
+            }
+            GatewayAccessDecision::Denied(denial) => {
+                let peer = stream
+                    .peer_addr()
+                    .map(|addr| addr.to_string())
+                    .unwrap_or_else(|_| "unknown".to_string());
+                log::warn!(
+                    "[proxy-gateway] access_denied peer={} path={} reason={:?}",
+                    peer,
+                    super::request_log::redact_request_path(&request.path),
+                    denial
+                );
+                (access_denied_response(denial), None)
+            }
+        };
     let write_result = write_response(stream, &mut response, started_instant, &settings).await;
     let ended_at = Utc::now();
     if let Err(error) = &write_result {
@@ -849,10 +992,53 @@ async fn handle_connection(
         }
     }
     amend_health_after_stream(context, &response);
-    observability::record_gateway_observability(&request, &response, context, started_at, ended_at);
+    observability::record_gateway_observability(
+        &request,
+        &response,
+        context,
+        client_label.as_deref(),
+        started_at,
+        ended_at,
+    );
     write_result
 }
 
+/// Connections from this machine: loopback, or the address the gateway itself
+/// is bound to (CLIs pointed at a LAN bind address connect from it).
+fn is_local_peer(stream: &TokioTcpStream) -> bool {
+    match (stream.peer_addr(), stream.local_addr()) {
+        (Ok(peer), Ok(local)) => {
+            let peer_ip = peer.ip().to_canonical();
+            peer_ip.is_loopback() || peer_ip == local.ip().to_canonical()
+        }
+        _ => false,
+    }
+}
+
+fn access_denied_response(denial: GatewayAccessDenial) -> self::http_io::DebugHttpResponse {
+    let (status_code, status_text, error, message) = match denial {
+        GatewayAccessDenial::Unauthorized(message) => {
+            (401, "Unauthorized", "gateway_access_unauthorized", message)
+        }
+        GatewayAccessDenial::Forbidden(message) => {
+            (403, "Forbidden", "gateway_access_forbidden", message)
+        }
+    };
+    let mut response = json_response(
+        status_code,
+        status_text,
+        json!({
+            "error": error,
+            "message": message,
+        }),
+        "access",
+        None,
+        &message,
+    );
+    response.error_category = Some(error.to_string());
+    response
+}
+
 /// Reconcile provider health with the *actual* stream outcome. The first-chunk
 /// probe (`upstream.rs`) records `record_health_success` as soon as the probe
 /// passes — before `write_streaming_body` consumes the stream — so a mid-stream
@@ -1012,9 +1198,14 @@ pub(crate) async fn health_check_socket_async(addr: SocketAddr) -> ProxyGatewayH
 mod tests {
     use super::*;
     use crate::coding::proxy_gateway::cli_proxy::manifest::CliProxyManifest;
+    use crate::coding::proxy_gateway::mock_upstream::{
+        MockUpstreamBehavior, MockUpstreamScript, MockUpstreamServer,
+    };
     use crate::coding::proxy_gateway::model_health::GatewayFailureKind;
     use crate::coding::proxy_gateway::request_log;
+    use crate::coding::proxy_gateway::response_cache::response_cache_stats;
     use crate::coding::proxy_gateway::types::{
+        AppProxyConfig, GatewayBudgetAction, GatewayBudgetPeriod, GatewayBudgetRuleInput,
         GatewayProxyMode, ProviderModelHealthKey, ProxyGatewayRequestLogListInput,
     };
     use crate::db::helpers::{db_create, db_put};
@@ -1704,6 +1895,112 @@ base_url = "https://openai.example.com/v1"
         assert!(captured.contains(r#""content":"say hi""#));
     }
 
+    #[test]
+    fn route_request_serves_repeated_request_from_response_cache() {
+        // The test upstream accepts a single connection, so the second response
+        // can only succeed when it is replayed from the cache.
+        let (base_url, captured_rx) = start_test_upstream();
+        let body =
+            br#"{"model":"claude-sonnet-4-6","messages":[{"role":"user","content":"say hi"}]}"#;
+        let request = debug_request("POST", "/anthropic/v1/messages", body);
+
+        let (_dir, db) = tauri::async_runtime::block_on(create_test_db());
+        tauri::async_runtime::block_on(async {
+            let settings_config = json!({
+                "env": {
+                    "ANTHROPIC_BASE_URL": base_url,
+                    "ANTHROPIC_AUTH_TOKEN": "provider-key"
+                }
+            })
+            .to_string();
+            insert_claude_provider(
+                &db,
+                json!({
+                    "name": "Local Upstream",
+                    "category": "custom",
+                    "settings_config": settings_config,
+                    "extra_settings_config": "{}",
+                    "is_applied": true,
+                    "is_disabled": false,
+                }),
+            );
+        });
+
+        let app_dir = tempfile::tempdir().expect("temp app dir");
+        let mut settings = ProxyGatewaySettings::default();
+        settings.app_configs.insert(
+            GatewayCliKey::Claude,
+            AppProxyConfig {
+                response_cache_enabled: Some(true),
+                ..AppProxyConfig::default()
+            },
+        );
+        let paths = ProxyGatewayPaths::new(app_dir.path());
+        let context = GatewayRuntimeContext::new(settings, Some(db), Some(paths.clone()));
+        let cache_header = |response: &http_io::DebugHttpResponse| {
+            response
+                .headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case("x-gateway-cache"))
+                .map(|(_, value)| value.clone())
+        };
+
+        let first = tauri::async_runtime::block_on(route_request(&request, &context));
+        assert_eq!(first.status_code, 200);
+        assert!(!first.cache_hit);
+        assert_eq!(cache_header(&first), None);
+        captured_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("captured upstream request");
+        // The entry is written on the blocking pool after the response returns.
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while response_cache_stats(&paths).map_or(true, |stats| stats.entry_count == 0) {
+            assert!(Instant::now() < deadline, "cache entry written");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let second = tauri::async_runtime::block_on(route_request(&request, &context));
+        assert_eq!(second.status_code, 200);
+        assert!(second.cache_hit);
+        assert_eq!(second.body, br#"{"ok":true}"#);
+        assert_eq!(cache_header(&second).as_deref(), Some("HIT"));
+
+        // Cache hits cost nothing upstream, so an exhausted reject budget
+        // must not block them.
+        let db = context.db.as_ref().expect("test db");
+        db.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO usage_daily_rollups
+                    (date, app_type, provider_id, model, request_count, success_count, total_cost_usd)
+                 VALUES (date('now', 'localtime'), 'claude', 'earlier', 'claude-sonnet-4-6', 3, 3, '12.5')",
+                [],
+            )
+            .map_err(|error| error.to_string())
+        })
+        .expect("seed rollup");
+        budgets::save_budget_rule(
+            db,
+            GatewayBudgetRuleInput {
+                id: None,
+                name: "Claude daily".to_string(),
+                enabled: true,
+                cli_key: Some(GatewayCliKey::Claude),
+                provider_id: None,
+                model: None,
+                period: GatewayBudgetPeriod::Daily,
+                limit_usd: Some("10".to_string()),
+                limit_tokens: None,
+                action: GatewayBudgetAction::Reject,
+            },
+        )
+        .expect("save budget");
+        context.reload_budget_rules().expect("reload budgets");
+
+        let third = tauri::async_runtime::block_on(route_request(&request, &context));
+        assert_eq!(third.status_code, 200);
+        assert!(third.cache_hit);
+    }
+
     #[test]
     fn route_request_preserves_upstream_response_body_for_converted_response() {
         let upstream_body = br#"{"id":"resp_test","object":"response","created_at":1764561600,"model":"gpt-4o","output":[{"type":"message","role":"assistant","content":[{"type":"output_text","text":"converted hello","annotations":[]}],"status":"completed"}],"status":"completed","usage":{"input_tokens":8,"input_tokens_details":{"cached_tokens":0},"output_tokens":2,"output_tokens_details":{"reasoning_tokens":0},"total_tokens":10}}"#;
@@ -1782,6 +2079,29 @@ base_url = "https://openai.example.com/v1"
         assert_eq!(response.attempt_count, 0);
     }
 
+    #[test]
+    fn route_request_serves_metrics_only_when_endpoint_is_enabled() {
+        let request = debug_request("GET", "/metrics", b"");
+
+        let context = GatewayRuntimeContext::new(ProxyGatewaySettings::default(), None, None);
+        let response = tauri::async_runtime::block_on(route_request(&request, &context));
+        assert_eq!(response.status_code, 404);
+
+        let settings = ProxyGatewaySettings {
+            metrics_endpoint_enabled: true,
+            ..ProxyGatewaySettings::default()
+        };
+        let context = GatewayRuntimeContext::new(settings, None, None);
+        let response = tauri::async_runtime::block_on(route_request(&request, &context));
+        assert_eq!(response.status_code, 200);
+        assert!(response
+            .headers
+            .iter()
+            .any(|(name, value)| name == "Content-Type" && value.starts_with("text/plain")));
+        let body = String::from_utf8(response.body).expect("utf8 metrics");
+        assert!(body.contains("# TYPE ai_toolbox_gateway_requests_total counter"));
+    }
+
     #[test]
     fn route_request_answers_desktop_hello_probe_locally() {
         // Claude Desktop 3P pings `HEAD /api/hello` on the gateway base URL.
@@ -2084,6 +2404,107 @@ data: {"type":"response.completed","response":{"id":"resp_stream","status":"comp
         assert!(second_captured.contains(r#""model":"second-sonnet""#));
     }
 
+    fn insert_mock_claude_providers(db: &SqliteDbState, base_urls: &[&str]) -> Vec<String> {
+        base_urls
+            .iter()
+            .enumerate()
+            .map(|(index, base_url)| {
+                let settings_config = json!({
+                    "env": {
+                        "ANTHROPIC_BASE_URL": base_url,
+                        "ANTHROPIC_AUTH_TOKEN": format!("mock-key-{index}")
+                    },
+                    "sonnetModel": format!("mock-sonnet-{index}")
+                })
+                .to_string();
+                insert_claude_provider(
+                    db,
+                    json!({
+                        "name": format!("Mock Upstream {index}"),
+                        "category": "custom",
+                        "settings_config": settings_config,
+                        "extra_settings_config": "{}",
+                        "is_applied": false,
+                        "is_disabled": false,
+                        "sort_index": index,
+                    }),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn route_request_fails_over_from_rate_limited_mock_upstream_and_records_health() {
+        let limited = MockUpstreamServer::start(MockUpstreamScript {
+            fallback: MockUpstreamBehavior::Status { status_code: 429 },
+            ..MockUpstreamScript::default()
+        })
+        .expect("start limited mock");
+        let healthy = MockUpstreamServer::start(MockUpstreamScript::default()).expect("start mock");
+        let body =
+            br#"{"model":"claude-sonnet-4-6","messages":[{"role":"user","content":"say hi"}]}"#;
+        let request = debug_request("POST", "/anthropic/v1/messages", body);
+
+        let (_dir, db) = tauri::async_runtime::block_on(create_test_db());
+        let provider_ids =
+            insert_mock_claude_providers(&db, &[limited.base_url(), healthy.base_url()]);
+        let app_dir = tempfile::tempdir().expect("temp app dir");
+        let context = GatewayRuntimeContext::new(
+            ProxyGatewaySettings::default(),
+            Some(db),
+            Some(ProxyGatewayPaths::new(app_dir.path())),
+        );
+        let response = tauri::async_runtime::block_on(route_request(&request, &context));
+
+        assert_eq!(response.status_code, 200);
+        assert!(response.failover);
+        assert_eq!(response.provider_name.as_deref(), Some("Mock Upstream 1"));
+        assert_eq!(limited.requests().len(), 1);
+        assert_eq!(
+            limited.requests()[0].model.as_deref(),
+            Some("mock-sonnet-0")
+        );
+        let failed_health = context
+            .health_items()
+            .unwrap_or_default()
+            .into_iter()
+            .find(|item| item.provider_id == provider_ids[0])
+            .expect("rate-limited provider health");
+        assert!(failed_health.failure_score > 0);
+    }
+
+    #[test]
+    fn route_request_fails_over_when_mock_upstream_stalls_before_first_byte() {
+        let stalled = MockUpstreamServer::start(MockUpstreamScript::with_steps(vec![
+            MockUpstreamBehavior::FirstByteTimeout { hold_ms: 3_000 },
+        ]))
+        .expect("start stalled mock");
+        let healthy = MockUpstreamServer::start(MockUpstreamScript::default()).expect("start mock");
+        let body = br#"{"model":"claude-sonnet-4-6","stream":true,"messages":[{"role":"user","content":"say hi"}]}"#;
+        let request = debug_request("POST", "/anthropic/v1/messages", body);
+
+        let (_dir, db) = tauri::async_runtime::block_on(create_test_db());
+        insert_mock_claude_providers(&db, &[stalled.base_url(), healthy.base_url()]);
+        let context = GatewayRuntimeContext::new(
+            ProxyGatewaySettings {
+                streaming_first_byte_timeout_secs: 1,
+                ..ProxyGatewaySettings::default()
+            },
+            Some(db),
+            None,
+        );
+        let mut response = tauri::async_runtime::block_on(route_request(&request, &context));
+
+        assert_eq!(response.status_code, 200);
+        assert!(response.failover);
+        assert_eq!(response.provider_name.as_deref(), Some("Mock Upstream 1"));
+        let mut body_stream = response.body_stream.take().expect("stream body");
+        let first_chunk = tauri::async_runtime::block_on(body_stream.next())
+            .expect("first stream chunk")
+            .expect("first stream chunk ok");
+        assert!(String::from_utf8_lossy(&first_chunk).contains("message_start"));
+    }
+
     #[test]
     fn route_request_fails_over_when_non_streaming_response_is_empty() {
         let (first_base_url, first_rx) = start_test_upstream_with_response(200, "OK", b"");
@@ -2333,6 +2754,71 @@ data: {"type":"response.completed","response":{"id":"resp_stream","status":"comp
         assert!(captured.contains(r#""model":"provider-sonnet""#));
     }
 
+    #[test]
+    fn route_request_rejects_locally_when_reject_budget_is_exhausted() {
+        let (base_url, captured_rx) = start_test_upstream();
+        let body =
+            br#"{"model":"claude-opus-4-7","messages":[{"role":"user","content":"say hi"}]}"#;
+        let request = debug_request("POST", "/anthropic/v1/messages", body);
+
+        let (_dir, db) = tauri::async_runtime::block_on(create_test_db());
+        let settings_config = json!({
+            "env": {
+                "ANTHROPIC_BASE_URL": base_url,
+                "ANTHROPIC_AUTH_TOKEN": "provider-key"
+            }
+        })
+        .to_string();
+        insert_claude_provider(
+            &db,
+            json!({
+                "name": "Budgeted Upstream",
+                "category": "custom",
+                "settings_config": settings_config,
+                "extra_settings_config": "{}",
+                "is_applied": true,
+                "is_disabled": false,
+            }),
+        );
+        db.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO usage_daily_rollups
+                    (date, app_type, provider_id, model, request_count, success_count, total_cost_usd)
+                 VALUES (date('now', 'localtime'), 'claude', 'earlier', 'claude-opus-4-7', 3, 3, '12.5')",
+                [],
+            )
+            .map_err(|error| error.to_string())
+        })
+        .expect("seed rollup");
+        budgets::save_budget_rule(
+            &db,
+            GatewayBudgetRuleInput {
+                id: None,
+                name: "Claude daily".to_string(),
+                enabled: true,
+                cli_key: Some(GatewayCliKey::Claude),
+                provider_id: None,
+                model: None,
+                period: GatewayBudgetPeriod::Daily,
+                limit_usd: Some("10".to_string()),
+                limit_tokens: None,
+                action: GatewayBudgetAction::Reject,
+            },
+        )
+        .expect("save budget");
+
+        let context = GatewayRuntimeContext::new(ProxyGatewaySettings::default(), Some(db), None);
+        let response = tauri::async_runtime::block_on(route_request(&request, &context));
+        assert_eq!(response.status_code, 429);
+        assert_eq!(response.error_category.as_deref(), Some("budget_exceeded"));
+        let body: Value = serde_json::from_slice(&response.body).expect("json body");
+        assert_eq!(body["type"], "error");
+        assert_eq!(body["error"]["type"], "rate_limit_error");
+        assert!(captured_rx
+            .recv_timeout(Duration::from_millis(200))
+            .is_err());
+    }
+
     #[test]
     fn route_request_fails_over_to_next_provider_after_retryable_failure() {
         let (first_base_url, first_rx) =
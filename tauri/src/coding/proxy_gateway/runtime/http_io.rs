@@ -1,7 +1,7 @@
 use super::content_encoding::{
     decompress_body, get_content_encoding_from_pairs, is_supported_content_encoding,
 };
-use crate::coding::proxy_gateway::transformer::AiProtocol;
+use crate::coding::proxy_gateway::transformer::{AiProtocol, ConversionRoute};
 use crate::coding::proxy_gateway::types::{
     GatewayCliKey, GatewayProviderAttempt, GatewayStreamOutcome, ProxyGatewaySettings,
 };
@@ -254,6 +254,12 @@ pub(super) struct DebugHttpResponse {
     /// Client-facing protocol of the request that produced this response. Used to
     /// render a protocol-dialect error event when the stream ends abnormally.
     pub(super) source_protocol: Option<AiProtocol>,
+    /// Request-direction protocol conversion applied for this response, if any.
+    /// Fixture capture uses it to file the exchange under the right protocols.
+    pub(super) conversion_route: Option<ConversionRoute>,
+    /// Served from the gateway response cache without contacting upstream.
+    /// Usage stats record such requests at zero cost.
+    pub(super) cache_hit: bool,
     /// How the streaming response actually ended for the client. Set by
     /// `write_streaming_body` from the terminal-event verdict; `NotStreaming`
     /// for non-streaming responses. Drives `success` in observability instead of
@@ -444,6 +450,8 @@ pub(super) fn json_response(
         note: note.to_string(),
         source_protocol: None,
         stream_outcome: GatewayStreamOutcome::NotStreaming,
+        conversion_route: None,
+        cache_hit: false,
     }
 }
 
@@ -486,6 +494,8 @@ pub(super) fn empty_response(
         note: note.to_string(),
         source_protocol: None,
         stream_outcome: GatewayStreamOutcome::NotStreaming,
+        conversion_route: None,
+        cache_hit: false,
     }
 }
 
@@ -0,0 +1,457 @@
+//! In-memory Prometheus metrics for the gateway listener.
+//!
+//! Counters live only for the lifetime of one gateway run; a restart starts
+//! them from zero, which Prometheus treats as an ordinary counter reset.
+
+use super::http_io::{empty_response, DebugHttpResponse};
+use super::GatewayRuntimeContext;
+use crate::coding::proxy_gateway::types::{
+    GatewayCliKey, GatewayModelHealthItem, GatewayModelHealthScope, ModelHealthStateKind,
+};
+use crate::coding::proxy_gateway::usage_parser::TokenUsage;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+
+const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+const LATENCY_BUCKETS_SECONDS: [f64; 11] = [
+    0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+];
+const HEALTH_STATES: [ModelHealthStateKind; 4] = [
+    ModelHealthStateKind::Healthy,
+    ModelHealthStateKind::Degraded,
+    ModelHealthStateKind::CoolingDown,
+    ModelHealthStateKind::Probing,
+];
+
+#[derive(Default)]
+pub(super) struct GatewayMetrics {
+    state: Mutex<MetricsState>,
+}
+
+#[derive(Default)]
+struct MetricsState {
+    requests: BTreeMap<(SeriesKey, u16), u64>,
+    series: BTreeMap<SeriesKey, SeriesMetrics>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct SeriesKey {
+    cli: String,
+    provider: String,
+    model: String,
+}
+
+#[derive(Default)]
+struct SeriesMetrics {
+    duration: Histogram,
+    first_byte: Histogram,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_tokens: u64,
+    cache_creation_tokens: u64,
+    cost_usd: Decimal,
+    failovers: u64,
+}
+
+#[derive(Default)]
+struct Histogram {
+    buckets: [u64; LATENCY_BUCKETS_SECONDS.len()],
+    count: u64,
+    sum_seconds: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, upper_bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS_SECONDS) {
+            if seconds <= upper_bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum_seconds += seconds;
+    }
+}
+
+/// One finished request as seen by the metrics registry.
+pub(super) struct MetricsSample<'a> {
+    pub(super) cli_key: GatewayCliKey,
+    pub(super) provider_id: Option<&'a str>,
+    pub(super) model: Option<&'a str>,
+    pub(super) status_code: u16,
+    pub(super) duration_ms: u64,
+    pub(super) first_byte_ms: Option<u64>,
+    pub(super) usage: &'a TokenUsage,
+    /// `None` for unpriced requests, including response-cache hits.
+    pub(super) cost_usd: Option<Decimal>,
+    pub(super) failover: bool,
+}
+
+impl GatewayMetrics {
+    pub(super) fn record(&self, sample: MetricsSample<'_>) {
+        let key = SeriesKey {
+            cli: sample.cli_key.as_str().to_string(),
+            provider: label_or_unknown(sample.provider_id),
+            model: label_or_unknown(sample.model),
+        };
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        *state
+            .requests
+            .entry((key.clone(), sample.status_code))
+            .or_default() += 1;
+        let series = state.series.entry(key).or_default();
+        series.duration.observe(sample.duration_ms as f64 / 1000.0);
+        if let Some(first_byte_ms) = sample.first_byte_ms {
+            series.first_byte.observe(first_byte_ms as f64 / 1000.0);
+        }
+        series.input_tokens += sample.usage.input_tokens.unwrap_or(0);
+        series.output_tokens += sample.usage.output_tokens.unwrap_or(0);
+        series.cache_read_tokens += sample.usage.cache_read_tokens.unwrap_or(0);
+        series.cache_creation_tokens += sample.usage.cache_creation_tokens.unwrap_or(0);
+        if let Some(cost_usd) = sample.cost_usd {
+            series.cost_usd += cost_usd;
+        }
+        if sample.failover {
+            series.failovers += 1;
+        }
+    }
+
+    /// Renders every metric in the Prometheus text exposition format.
+    pub(super) fn render(
+        &self,
+        health_items: &[GatewayModelHealthItem],
+        active_connections: u32,
+    ) -> String {
+        let mut output = String::new();
+        write_header(
+            &mut output,
+            "ai_toolbox_gateway_active_connections",
+            "gauge",
+            "Client connections currently open on the gateway listener.",
+        );
+        let _ = writeln!(
+            output,
+            "ai_toolbox_gateway_active_connections {active_connections}"
+        );
+
+        if let Ok(state) = self.state.lock() {
+            render_requests(&mut output, &state);
+            render_histogram(
+                &mut output,
+                &state,
+                "ai_toolbox_gateway_request_duration_seconds",
+                "Time from receiving a request to finishing the response.",
+                |series| &series.duration,
+            );
+            render_histogram(
+                &mut output,
+                &state,
+                "ai_toolbox_gateway_first_byte_seconds",
+                "Time until the first upstream token reached the client.",
+                |series| &series.first_byte,
+            );
+            render_tokens(&mut output, &state);
+            write_header(
+                &mut output,
+                "ai_toolbox_gateway_cost_usd_total",
+                "counter",
+                "Estimated spend in USD from model pricing.",
+            );
+            for (key, series) in &state.series {
+                let _ = writeln!(
+                    output,
+                    "ai_toolbox_gateway_cost_usd_total{{{}}} {}",
+                    series_labels(key),
+                    series.cost_usd.round_dp(6).normalize()
+                );
+            }
+            write_header(
+                &mut output,
+                "ai_toolbox_gateway_failovers_total",
+                "counter",
+                "Requests that were served after failing over from another provider.",
+            );
+            for (key, series) in &state.series {
+                let _ = writeln!(
+                    output,
+                    "ai_toolbox_gateway_failovers_total{{{}}} {}",
+                    series_labels(key),
+                    series.failovers
+                );
+            }
+        }
+
+        render_circuit_state(&mut output, health_items);
+        output
+    }
+}
+
+pub(super) fn metrics_response(context: &GatewayRuntimeContext) -> DebugHttpResponse {
+    let health_items = context.health_items().unwrap_or_default();
+    let body = context.metrics.render(
+        &health_items,
+        context.active_connections.load(Ordering::SeqCst),
+    );
+    let mut response = empty_response(200, "OK", "metrics", "local metrics endpoint");
+    response.headers = vec![("Content-Type".to_string(), METRICS_CONTENT_TYPE.to_string())];
+    response.response_body_bytes = body.len() as u64;
+    response.body = body.into_bytes();
+    response
+}
+
+fn render_requests(output: &mut String, state: &MetricsState) {
+    write_header(
+        output,
+        "ai_toolbox_gateway_requests_total",
+        "counter",
+        "Requests handled by the gateway, by final HTTP status.",
+    );
+    for ((key, status_code), count) in &state.requests {
+        let _ = writeln!(
+            output,
+            "ai_toolbox_gateway_requests_total{{{},status=\"{status_code}\"}} {count}",
+            series_labels(key)
+        );
+    }
+}
+
+fn render_histogram(
+    output: &mut String,
+    state: &MetricsState,
+    name: &str,
+    help: &str,
+    histogram_of: impl Fn(&SeriesMetrics) -> &Histogram,
+) {
+    write_header(output, name, "histogram", help);
+    for (key, series) in &state.series {
+        let histogram = histogram_of(series);
+        if histogram.count == 0 {
+            continue;
+        }
+        let labels = series_labels(key);
+        for (count, upper_bound) in histogram.buckets.iter().zip(LATENCY_BUCKETS_SECONDS) {
+            let _ = writeln!(
+                output,
+                "{name}_bucket{{{labels},le=\"{upper_bound}\"}} {count}"
+            );
+        }
+        let _ = writeln!(
+            output,
+            "{name}_bucket{{{labels},le=\"+Inf\"}} {}",
+            histogram.count
+        );
+        let _ = writeln!(output, "{name}_sum{{{labels}}} {}", histogram.sum_seconds);
+        let _ = writeln!(output, "{name}_count{{{labels}}} {}", histogram.count);
+    }
+}
+
+fn render_tokens(output: &mut String, state: &MetricsState) {
+    write_header(
+        output,
+        "ai_toolbox_gateway_tokens_total",
+        "counter",
+        "Tokens reported by upstream usage, by token type.",
+    );
+    for (key, series) in &state.series {
+        let labels = series_labels(key);
+        for (token_type, count) in [
+            ("input", series.input_tokens),
+            ("output", series.output_tokens),
+            ("cache_read", series.cache_read_tokens),
+            ("cache_creation", series.cache_creation_tokens),
+        ] {
+            let _ = writeln!(
+                output,
+                "ai_toolbox_gateway_tokens_total{{{labels},type=\"{token_type}\"}} {count}"
+            );
+        }
+    }
+}
+
+fn render_circuit_state(output: &mut String, health_items: &[GatewayModelHealthItem]) {
+    write_header(
+        output,
+        "ai_toolbox_gateway_circuit_state",
+        "gauge",
+        "Model health circuit state; 1 for the current state of each provider or model.",
+    );
+    for item in health_items {
+        let labels = health_labels(item);
+        for state in HEALTH_STATES {
+            let _ = writeln!(
+                output,
+                "ai_toolbox_gateway_circuit_state{{{labels},state=\"{}\"}} {}",
+                health_state_label(state),
+                u8::from(item.state == state)
+            );
+        }
+    }
+    write_header(
+        output,
+        "ai_toolbox_gateway_circuit_failure_score",
+        "gauge",
+        "Weighted failure score inside the current failure window.",
+    );
+    for item in health_items {
+        let _ = writeln!(
+            output,
+            "ai_toolbox_gateway_circuit_failure_score{{{}}} {}",
+            health_labels(item),
+            item.failure_score
+        );
+    }
+}
+
+fn write_header(output: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(output, "# HELP {name} {help}");
+    let _ = writeln!(output, "# TYPE {name} {kind}");
+}
+
+fn series_labels(key: &SeriesKey) -> String {
+    format!(
+        "cli=\"{}\",provider=\"{}\",model=\"{}\"",
+        escape_label(&key.cli),
+        escape_label(&key.provider),
+        escape_label(&key.model)
+    )
+}
+
+fn health_labels(item: &GatewayModelHealthItem) -> String {
+    let scope = match item.scope {
+        GatewayModelHealthScope::Model => "model",
+        GatewayModelHealthScope::Provider => "provider",
+    };
+    format!(
+        "cli=\"{}\",provider=\"{}\",model=\"{}\",scope=\"{scope}\"",
+        item.cli_key.as_str(),
+        escape_label(&item.provider_id),
+        escape_label(item.upstream_model_id.as_deref().unwrap_or(""))
+    )
+}
+
+fn health_state_label(state: ModelHealthStateKind) -> &'static str {
+    match state {
+        ModelHealthStateKind::Healthy => "healthy",
+        ModelHealthStateKind::Degraded => "degraded",
+        ModelHealthStateKind::CoolingDown => "cooling_down",
+        ModelHealthStateKind::Probing => "probing",
+    }
+}
+
+fn label_or_unknown(value: Option<&str>) -> String {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(input_tokens: u64, output_tokens: u64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: Some(input_tokens),
+            output_tokens: Some(output_tokens),
+            ..TokenUsage::default()
+        }
+    }
+
+    #[test]
+    fn render_exposes_counters_histograms_and_costs_per_series() {
+        let metrics = GatewayMetrics::default();
+        let first_usage = usage(100, 20);
+        let second_usage = usage(50, 5);
+        metrics.record(MetricsSample {
+            cli_key: GatewayCliKey::Claude,
+            provider_id: Some("provider-a"),
+            model: Some("claude-sonnet"),
+            status_code: 200,
+            duration_ms: 800,
+            first_byte_ms: Some(300),
+            usage: &first_usage,
+            cost_usd: Some(Decimal::new(125, 2)),
+            failover: false,
+        });
+        metrics.record(MetricsSample {
+            cli_key: GatewayCliKey::Claude,
+            provider_id: Some("provider-a"),
+            model: Some("claude-sonnet"),
+            status_code: 200,
+            duration_ms: 4_000,
+            first_byte_ms: None,
+            usage: &second_usage,
+            cost_usd: Some(Decimal::new(25, 2)),
+            failover: true,
+        });
+
+        let output = metrics.render(&[], 3);
+        let labels = r#"cli="claude",provider="provider-a",model="claude-sonnet""#;
+
+        assert!(output.contains("ai_toolbox_gateway_active_connections 3\n"));
+        assert!(output.contains(&format!(
+            "ai_toolbox_gateway_requests_total{{{labels},status=\"200\"}} 2\n"
+        )));
+        assert!(output.contains(&format!(
+            "ai_toolbox_gateway_request_duration_seconds_bucket{{{labels},le=\"1\"}} 1\n"
+        )));
+        assert!(output.contains(&format!(
+            "ai_toolbox_gateway_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} 2\n"
+        )));
+        assert!(output.contains(&format!(
+            "ai_toolbox_gateway_first_byte_seconds_count{{{labels}}} 1\n"
+        )));
+        assert!(output.contains(&format!(
+            "ai_toolbox_gateway_tokens_total{{{labels},type=\"input\"}} 150\n"
+        )));
+        assert!(output.contains(&format!(
+            "ai_toolbox_gateway_cost_usd_total{{{labels}}} 1.5\n"
+        )));
+        assert!(output.contains(&format!(
+            "ai_toolbox_gateway_failovers_total{{{labels}}} 1\n"
+        )));
+    }
+
+    #[test]
+    fn render_reports_one_hot_circuit_state_with_escaped_labels() {
+        let item = GatewayModelHealthItem {
+            scope: GatewayModelHealthScope::Model,
+            cli_key: GatewayCliKey::Codex,
+            provider_id: "provider-\"b\"".to_string(),
+            provider_name: None,
+            upstream_model_id: Some("gpt-5".to_string()),
+            state: ModelHealthStateKind::CoolingDown,
+            failure_score: 7,
+            consecutive_open_count: 1,
+            half_open_success_count: 0,
+            next_retry_at: None,
+            last_failure_at: None,
+            last_error_category: None,
+        };
+
+        let output = GatewayMetrics::default().render(&[item], 0);
+        let labels = r#"cli="codex",provider="provider-\"b\"",model="gpt-5",scope="model""#;
+
+        assert!(output.contains(&format!(
+            "ai_toolbox_gateway_circuit_state{{{labels},state=\"cooling_down\"}} 1\n"
+        )));
+        assert!(output.contains(&format!(
+            "ai_toolbox_gateway_circuit_state{{{labels},state=\"healthy\"}} 0\n"
+        )));
+        assert!(output.contains(&format!(
+            "ai_toolbox_gateway_circuit_failure_score{{{labels}}} 7\n"
+        )));
+    }
+}
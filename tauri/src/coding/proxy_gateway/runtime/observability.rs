@@ -1,15 +1,23 @@
 use super::http_io::{DebugHttpRequest, DebugHttpResponse};
+use super::metrics::MetricsSample;
 use super::routes::split_request_target;
 use super::GatewayRuntimeContext;
+use crate::coding::proxy_gateway::fixture_capture::{self, FixtureCaptureInput};
 use crate::coding::proxy_gateway::paths::ProxyGatewayPaths;
 use crate::coding::proxy_gateway::request_log;
 use crate::coding::proxy_gateway::types::{
     GatewayRequestLogDetail, GatewayRequestLogSummary, GatewayStreamOutcome,
     GatewayUsageRecordedEvent, ProxyGatewaySettings,
 };
-use crate::coding::proxy_gateway::usage_parser::stable_usage_request_id;
-use crate::coding::proxy_gateway::usage_stats::{self, RecordRequestSummaryOutcome};
+use crate::coding::proxy_gateway::usage_parser::{
+    request_billing_traits, stable_usage_request_id, RequestBillingTraits,
+};
+use crate::coding::proxy_gateway::usage_stats::{
+    self, RecordRequestSummaryOutcome, RequestCostInput,
+};
+use crate::db::SqliteDbState;
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use std::sync::OnceLock;
 use tauri::Emitter;
 
@@ -19,6 +27,7 @@ pub(super) fn record_gateway_observability(
     request: &DebugHttpRequest,
     response: &DebugHttpResponse,
     context: &GatewayRuntimeContext,
+    client_label: Option<&str>,
     started_at: DateTime<Utc>,
     ended_at: DateTime<Utc>,
 ) {
@@ -39,13 +48,20 @@ pub(super) fn record_gateway_observability(
     let cache_read_tokens = response.token_usage.cache_read_tokens;
     let cache_creation_tokens = response.token_usage.cache_creation_tokens;
     let total_tokens = response.token_usage.total_tokens();
+    let billing = request_billing_traits(&request.body);
     let settings = context.settings_snapshot();
+    if settings.metrics_endpoint_enabled {
+        record_live_metrics(response, context, billing, duration_ms);
+    }
     let fallback_trace_id = process_local_trace_id(request);
     let upstream_response_body_snapshot = response.upstream_response_body_snapshot();
     // Prefer upstream envelope id for stable usage keys (cc-switch c9ac6efd).
     // Fallback remains process-local so request-list/detail still have a unique id.
+    // Cache hits replay the original envelope id, so they always use the fallback
+    // to avoid being deduplicated against the request that filled the entry.
     let trace_id = response
         .cli_key
+        .filter(|_| !response.cache_hit)
         .map(|cli_key| {
             stable_usage_request_id(
                 cli_key,
@@ -55,6 +71,15 @@ pub(super) fn record_gateway_observability(
             )
         })
         .unwrap_or(fallback_trace_id);
+    maybe_capture_fixture(
+        paths,
+        &settings,
+        request,
+        response,
+        &trace_id,
+        upstream_response_body_snapshot.as_ref(),
+        ended_at,
+    );
 
     let should_record_summary = settings.request_log_enabled || settings.metrics_enabled;
     if should_record_summary {
@@ -62,10 +87,7 @@ pub(super) fn record_gateway_observability(
         // Once `write_streaming_body` has written `HTTP/1.1 200`, a later
         // mid-stream failure can no longer change that code; the outcome
         // enum records what actually reached the client.
-        let success = match response.stream_outcome {
-            GatewayStreamOutcome::NotStreaming => is_success_status(response.status_code),
-            _ => response.stream_outcome.is_success(),
-        };
+        let success = response_succeeded(response);
         // Build compact fields first (no body/header yet) so usage-key resolution can
         // decide skip/collision before we write expensive JSONL detail.
         let mut detail = GatewayRequestLogDetail {
@@ -100,6 +122,7 @@ pub(super) fn record_gateway_observability(
                 output_tokens,
                 cache_read_tokens,
                 cache_creation_tokens,
+                reasoning_tokens: response.token_usage.reasoning_tokens,
                 total_tokens,
                 request_body_bytes: request.body.len() as u64,
                 response_body_bytes: response.response_body_bytes,
@@ -107,6 +130,10 @@ pub(super) fn record_gateway_observability(
                 first_token_ms: response.first_token_ms,
                 detail_file: None,
                 detail_offset: None,
+                client_label: client_label.map(str::to_string),
+                cache_hit: response.cache_hit,
+                image_count: billing.image_count,
+                batch_priced: billing.batch,
             },
             request_headers: None,
             request_body: None,
@@ -249,6 +276,62 @@ fn maybe_write_request_detail(
     }
 }
 
+fn maybe_capture_fixture(
+    paths: &ProxyGatewayPaths,
+    settings: &ProxyGatewaySettings,
+    request: &DebugHttpRequest,
+    response: &DebugHttpResponse,
+    trace_id: &str,
+    upstream_response_body_snapshot: Option<&(Vec<u8>, u64)>,
+    captured_at: DateTime<Utc>,
+) {
+    let Some(route) = response.conversion_route else {
+        return;
+    };
+    let (request_path, _) = split_request_target(&request.path);
+    // Compact requests go through dedicated converters that replay cannot reproduce.
+    if request_path.ends_with("/compact")
+        || !response_succeeded(response)
+        || !fixture_capture::should_capture(
+            settings,
+            response.requested_model.as_deref(),
+            &request_path,
+        )
+    {
+        return;
+    }
+    let upstream_response_body = match upstream_response_body_snapshot {
+        Some((body, original_len)) if (body.len() as u64) < *original_len => {
+            log::debug!("Skipping fixture capture for {trace_id}: upstream body was truncated");
+            return;
+        }
+        Some((body, _)) => body.as_slice(),
+        None if !response.is_streaming => response.body.as_slice(),
+        None => return,
+    };
+    let input = FixtureCaptureInput {
+        trace_id,
+        captured_at,
+        cli_key: response.cli_key,
+        method: &request.method,
+        path: &request.path,
+        requested_model: response.requested_model.as_deref(),
+        upstream_model_id: response.upstream_model_id.as_deref(),
+        provider_type: response.provider_type.as_deref(),
+        status_code: response.status_code,
+        route,
+        request_headers: &request.headers,
+        request_body: &request.body,
+        response_headers: &response.headers,
+        upstream_request_body: response.upstream_request_body.as_deref(),
+        upstream_response_body,
+        is_streaming: response.is_streaming,
+    };
+    if let Err(error) = fixture_capture::write_fixture_capture(paths, &input) {
+        log::warn!("Failed to write proxy gateway fixture capture: {error}");
+    }
+}
+
 fn emit_usage_recorded_event(context: &GatewayRuntimeContext, summary: &GatewayRequestLogSummary) {
     let Some(app_handle) = context.app_handle.as_ref() else {
         return;
@@ -269,7 +352,7 @@ fn emit_usage_recorded_event(context: &GatewayRuntimeContext, summary: &GatewayR
 }
 
 fn should_skip_observability(method: &str, request_path: &str) -> bool {
-    if method == "GET" && request_path == "/health" {
+    if method == "GET" && matches!(request_path, "/health" | "/metrics") {
         return true;
     }
     matches!(method, "GET" | "HEAD")
@@ -279,12 +362,75 @@ fn should_skip_observability(method: &str, request_path: &str) -> bool {
         )
 }
 
+fn record_live_metrics(
+    response: &DebugHttpResponse,
+    context: &GatewayRuntimeContext,
+    billing: RequestBillingTraits,
+    duration_ms: u64,
+) {
+    let Some(cli_key) = response.cli_key else {
+        return;
+    };
+    let model = response
+        .upstream_model_id
+        .as_deref()
+        .or(response.requested_model.as_deref());
+    let cost_usd = live_metrics_cost_usd(response, context.db.as_ref(), model, billing);
+    context.metrics.record(MetricsSample {
+        cli_key,
+        provider_id: response.provider_id.as_deref(),
+        model,
+        status_code: response.status_code,
+        duration_ms,
+        first_byte_ms: response.first_token_ms,
+        usage: &response.token_usage,
+        cost_usd,
+        failover: response.failover,
+    });
+}
+
+/// Cost reported to the metrics endpoint. Cache hits never reached upstream,
+/// so they stay unpriced just like their request-log rows.
+fn live_metrics_cost_usd(
+    response: &DebugHttpResponse,
+    db: Option<&SqliteDbState>,
+    model: Option<&str>,
+    billing: RequestBillingTraits,
+) -> Option<Decimal> {
+    if response.cache_hit {
+        return None;
+    }
+    let (db, upstream_model) = db.zip(model)?;
+    response.token_usage.total_tokens()?;
+    db.with_conn(|conn| {
+        Ok(usage_stats::request_cost_usd(
+            conn,
+            &RequestCostInput {
+                requested_model: response.requested_model.as_deref(),
+                upstream_model,
+                pricing_model_source: response.pricing_model_source.as_deref(),
+                cost_multiplier: response.cost_multiplier.as_deref(),
+                usage: &response.token_usage,
+                billing,
+            },
+        ))
+    })
+    .ok()
+}
+
 fn process_local_trace_id(request: &DebugHttpRequest) -> String {
     let run_id = TRACE_RUN_ID
         .get_or_init(|| format!("{}-{}", std::process::id(), Utc::now().timestamp_micros()));
     format!("gw-{}-{}", run_id, request.id)
 }
 
+fn response_succeeded(response: &DebugHttpResponse) -> bool {
+    match response.stream_outcome {
+        GatewayStreamOutcome::NotStreaming => is_success_status(response.status_code),
+        _ => response.stream_outcome.is_success(),
+    }
+}
+
 fn is_success_status(status_code: u16) -> bool {
     (200..=399).contains(&status_code)
 }
@@ -316,7 +462,9 @@ fn stored_body_text(
 
 #[cfg(test)]
 mod tests {
+    use super::super::http_io::empty_response;
     use super::*;
+    use crate::coding::proxy_gateway::usage_parser::TokenUsage;
 
     fn request_with_id(id: u64) -> DebugHttpRequest {
         DebugHttpRequest {
@@ -328,6 +476,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn live_metrics_leave_cache_hits_unpriced() {
+        let db = SqliteDbState::in_memory_for_test().expect("open test db");
+        db.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO model_pricing (
+                    model_id, display_name, input_cost_per_million, output_cost_per_million,
+                    cache_read_cost_per_million, cache_creation_cost_per_million
+                ) VALUES ('metrics-model', 'metrics-model', '1', '2', '0', '0')",
+                [],
+            )
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+        })
+        .expect("insert pricing");
+        let mut response = empty_response(200, "OK", "anthropic", "cached");
+        response.token_usage = TokenUsage {
+            input_tokens: Some(1_000_000),
+            output_tokens: Some(1_000_000),
+            ..TokenUsage::default()
+        };
+        let cost = |response: &DebugHttpResponse| {
+            live_metrics_cost_usd(
+                response,
+                Some(&db),
+                Some("metrics-model"),
+                RequestBillingTraits::default(),
+            )
+        };
+
+        assert_eq!(cost(&response), Some(Decimal::new(3, 0)));
+        response.cache_hit = true;
+        assert_eq!(cost(&response), None);
+    }
+
     #[test]
     fn process_local_trace_id_contains_process_run_prefix() {
         let trace = process_local_trace_id(&request_with_id(1));
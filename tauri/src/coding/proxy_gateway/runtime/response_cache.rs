@@ -0,0 +1,276 @@
+use super::http_io::{empty_response, DebugBodyStream, DebugHttpRequest, DebugHttpResponse};
+use super::providers::UpstreamProvider;
+use super::routes::GatewayRoute;
+use super::GatewayRuntimeContext;
+use crate::coding::proxy_gateway::paths::ProxyGatewayPaths;
+use crate::coding::proxy_gateway::response_cache::{
+    lookup_cached_response, request_is_cacheable, response_cache_key, store_cached_response,
+    CachedResponse, CachedResponseBody, ResponseCacheKeyInput,
+};
+use crate::coding::proxy_gateway::transformer::{
+    fixture_events_to_sse, sse_to_fixture_events, AiProtocol,
+};
+use crate::coding::proxy_gateway::types::ProxyGatewaySettings;
+use crate::coding::proxy_gateway::usage_parser::{
+    from_response_body_with_provider_type, SseTerminalKind, SseUsageCollector,
+};
+use chrono::Utc;
+use futures_util::StreamExt;
+
+const RESPONSE_CACHE_HEADER: &str = "X-Gateway-Cache";
+
+/// Cache eligibility of one client request, resolved once before the provider
+/// loop. Keys still depend on the provider and upstream model of each attempt.
+pub(super) struct ResponseCacheRequest<'a> {
+    paths: ProxyGatewayPaths,
+    settings: ProxyGatewaySettings,
+    request: &'a DebugHttpRequest,
+    route: &'a GatewayRoute,
+}
+
+impl<'a> ResponseCacheRequest<'a> {
+    pub(super) fn new(
+        context: &GatewayRuntimeContext,
+        settings: &ProxyGatewaySettings,
+        request: &'a DebugHttpRequest,
+        route: &'a GatewayRoute,
+        streaming: bool,
+    ) -> Option<Self> {
+        let paths = context.paths.as_ref()?;
+        if !settings.response_cache_enabled_for(route.cli_key)
+            || request.method != "POST"
+            || route.forwarded_path.ends_with("/compact")
+            || !request_is_cacheable(&request.body, streaming)
+        {
+            return None;
+        }
+        Some(Self {
+            paths: paths.clone(),
+            settings: settings.clone(),
+            request,
+            route,
+        })
+    }
+
+    pub(super) fn key_for(
+        &self,
+        provider: &UpstreamProvider,
+        upstream_model_id: &str,
+    ) -> Option<String> {
+        response_cache_key(&ResponseCacheKeyInput {
+            cli_key: self.route.cli_key,
+            method: &self.request.method,
+            path: &self.request.path,
+            provider_id: &provider.id,
+            upstream_model_id,
+            request_body: &self.request.body,
+        })
+    }
+
+    /// Builds the client response for a live entry. Gateway bookkeeping fields
+    /// (models, attempts) are filled in by the caller like any other response.
+    /// The entry is read (and evicted when stale) on the blocking pool.
+    pub(super) async fn lookup(
+        &self,
+        key: &str,
+        provider: &UpstreamProvider,
+        source_protocol: Option<AiProtocol>,
+    ) -> Option<DebugHttpResponse> {
+        let paths = self.paths.clone();
+        let settings = self.settings.clone();
+        let key = key.to_string();
+        let entry = tauri::async_runtime::spawn_blocking(move || {
+            lookup_cached_response(&paths, &settings, &key, Utc::now())
+        })
+        .await
+        .ok()
+        .flatten()?;
+        let status_text = reqwest::StatusCode::from_u16(entry.status_code)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or("OK");
+        let mut response = empty_response(
+            entry.status_code,
+            status_text,
+            self.route.route_name,
+            &format!(
+                "served from response cache for provider id={} name={}",
+                provider.id, provider.name
+            ),
+        );
+        if let Some(content_type) = entry.content_type.as_ref() {
+            response
+                .headers
+                .push(("Content-Type".to_string(), content_type.clone()));
+        }
+        response
+            .headers
+            .push((RESPONSE_CACHE_HEADER.to_string(), "HIT".to_string()));
+        let body = entry.body_bytes();
+        match &entry.body {
+            CachedResponseBody::Buffered { .. } => {
+                response.token_usage = from_response_body_with_provider_type(
+                    provider.cli_key,
+                    provider.meta.provider_type.as_deref(),
+                    &body,
+                );
+                response.response_body_bytes = body.len() as u64;
+                response.body = body;
+            }
+            CachedResponseBody::Stream { events } => {
+                // One chunk per event keeps the replay shaped like a live stream;
+                // usage and the terminal verdict come from `write_streaming_body`.
+                let chunks = events
+                    .iter()
+                    .map(|event| Ok(fixture_events_to_sse(std::slice::from_ref(event))))
+                    .collect::<Vec<_>>();
+                response.body_stream = Some(Box::pin(futures_util::stream::iter(chunks)));
+                response.is_streaming = true;
+            }
+        }
+        response.cli_key = Some(provider.cli_key);
+        response.provider_id = Some(provider.id.clone());
+        response.provider_name = Some(provider.name.clone());
+        response.provider_type = provider.meta.provider_type.clone();
+        response.cost_multiplier = Some(provider.meta.cost_multiplier.clone());
+        response.pricing_model_source = Some(provider.meta.pricing_model_source.clone());
+        response.source_protocol = source_protocol;
+        response.cache_hit = true;
+        Some(response)
+    }
+
+    /// Stores a successful response under `key`. Buffered bodies are written
+    /// on the blocking pool right away; SSE bodies are teed and written only
+    /// after the stream ends with a success terminal event, so truncated
+    /// streams are never replayed.
+    pub(super) fn store(&self, key: String, response: &mut DebugHttpResponse) {
+        if !(200..300).contains(&response.status_code) || response.error_category.is_some() {
+            return;
+        }
+        let content_type = response
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| value.clone());
+        if let Some(stream) = response.body_stream.take() {
+            let is_sse = content_type
+                .as_deref()
+                .is_some_and(|value| value.to_ascii_lowercase().contains("text/event-stream"));
+            response.body_stream = Some(if is_sse {
+                tee_stream_into_cache(
+                    stream,
+                    StreamCacheSink {
+                        paths: self.paths.clone(),
+                        settings: self.settings.clone(),
+                        key,
+                        status_code: response.status_code,
+                        content_type,
+                        provider_type: response.provider_type.clone(),
+                    },
+                )
+            } else {
+                stream
+            });
+            return;
+        }
+        let Ok(body) = String::from_utf8(response.body.clone()) else {
+            return;
+        };
+        let entry = CachedResponse::new(
+            Utc::now(),
+            response.status_code,
+            content_type,
+            CachedResponseBody::Buffered { body },
+        );
+        let paths = self.paths.clone();
+        let settings = self.settings.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(error) = store_cached_response(&paths, &settings, &key, &entry) {
+                log::warn!("Failed to store gateway response cache entry: {error}");
+            }
+        });
+    }
+}
+
+struct StreamCacheSink {
+    paths: ProxyGatewayPaths,
+    settings: ProxyGatewaySettings,
+    key: String,
+    status_code: u16,
+    content_type: Option<String>,
+    provider_type: Option<String>,
+}
+
+impl StreamCacheSink {
+    fn finish(self, body: &[u8]) {
+        let events = sse_to_fixture_events(body);
+        if events.is_empty() {
+            return;
+        }
+        let entry = CachedResponse::new(
+            Utc::now(),
+            self.status_code,
+            self.content_type,
+            CachedResponseBody::Stream { events },
+        );
+        if let Err(error) = store_cached_response(&self.paths, &self.settings, &self.key, &entry) {
+            log::warn!("Failed to store gateway response cache entry: {error}");
+        }
+    }
+}
+
+fn tee_stream_into_cache(stream: DebugBodyStream, sink: StreamCacheSink) -> DebugBodyStream {
+    struct State {
+        inner: DebugBodyStream,
+        sink: Option<StreamCacheSink>,
+        body: Vec<u8>,
+        max_bytes: usize,
+        terminal: SseUsageCollector,
+    }
+
+    let max_bytes = sink
+        .settings
+        .response_cache_max_size_mb
+        .saturating_mul(1024 * 1024)
+        .min(usize::MAX as u64) as usize;
+    let terminal = SseUsageCollector::with_provider_type(sink.provider_type.as_deref());
+    Box::pin(futures_util::stream::unfold(
+        State {
+            inner: stream,
+            sink: Some(sink),
+            body: Vec::new(),
+            max_bytes,
+            terminal,
+        },
+        |mut state| async move {
+            match state.inner.next().await {
+                Some(Ok(chunk)) => {
+                    if state.sink.is_some() {
+                        if state.body.len().saturating_add(chunk.len()) > state.max_bytes {
+                            state.sink = None;
+                            state.body = Vec::new();
+                        } else {
+                            state.body.extend_from_slice(&chunk);
+                            state.terminal.observe_chunk(&chunk);
+                        }
+                    }
+                    Some((Ok(chunk), state))
+                }
+                Some(Err(error)) => {
+                    state.sink = None;
+                    Some((Err(error), state))
+                }
+                None => {
+                    if let Some(sink) = state.sink.take() {
+                        state.terminal.drain_terminal();
+                        if state.terminal.terminal_kind() == Some(SseTerminalKind::Success) {
+                            let body = std::mem::take(&mut state.body);
+                            tauri::async_runtime::spawn_blocking(move || sink.finish(&body));
+                        }
+                    }
+                    None
+                }
+            }
+        },
+    ))
+}
@@ -1,5 +1,7 @@
 use crate::coding::proxy_gateway::types::GatewayCliKey;
 
+const DEFAULT_AZURE_OPENAI_API_VERSION: &str = "2025-04-01-preview";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) struct GatewayRoute {
     pub(super) cli_key: GatewayCliKey,
@@ -93,6 +95,61 @@ mod tests {
         assert!(match_gateway_route("/grok/v1/chat/completions").is_none());
         assert!(match_gateway_route("/grok/v1/responses/compact").is_none());
     }
+
+    #[test]
+    fn azure_openai_urls_use_deployments_and_api_version() {
+        let chat = build_azure_openai_target_url(
+            "https://res.openai.azure.com/openai",
+            "/v1/chat/completions",
+            "gpt-4o-prod",
+            None,
+            Some("trace=1&api-version=ignored"),
+        )
+        .unwrap();
+        assert_eq!(
+            chat.as_str(),
+            "https://res.openai.azure.com/openai/deployments/gpt-4o-prod/chat/completions?trace=1&api-version=2025-04-01-preview"
+        );
+
+        let responses = build_azure_openai_target_url(
+            "https://res.openai.azure.com?api-version=2025-03-01-preview",
+            "/v1/responses",
+            "gpt-4o-prod",
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            responses.as_str(),
+            "https://res.openai.azure.com/openai/responses?api-version=2025-03-01-preview"
+        );
+
+        let v1 = build_azure_openai_target_url(
+            "https://res.openai.azure.com/openai/v1/",
+            "/chat/completions",
+            "gpt-4o-prod",
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            v1.as_str(),
+            "https://res.openai.azure.com/openai/v1/chat/completions"
+        );
+
+        let configured = build_azure_openai_target_url(
+            "https://res.openai.azure.com?api-version=2025-03-01-preview",
+            "/v1/chat/completions",
+            "gpt-4o-prod",
+            Some("2024-10-21"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            configured.as_str(),
+            "https://res.openai.azure.com/openai/deployments/gpt-4o-prod/chat/completions?api-version=2024-10-21"
+        );
+    }
 }
 
 fn is_gemini_versioned_path(path: &str) -> bool {
@@ -171,3 +228,70 @@ fn strip_leading_gemini_api_version(path: &str) -> Option<&str> {
     }
     None
 }
+
+/// Build an Azure OpenAI URL. A base ending in `/openai/v1` uses the
+/// deployment-agnostic v1 API (model in the body, no `api-version` needed);
+/// any other base gets `/openai/deployments/{deployment}` for deployment-scoped
+/// operations and an `api-version` query. An explicit `api_version` wins over
+/// one carried by the base URL, which wins over the default.
+pub(super) fn build_azure_openai_target_url(
+    base_url: &str,
+    forwarded_path: &str,
+    deployment: &str,
+    api_version: Option<&str>,
+    query: Option<&str>,
+) -> Result<reqwest::Url, String> {
+    let mut url = reqwest::Url::parse(base_url)
+        .map_err(|error| format!("Invalid upstream base URL '{}': {error}", base_url))?;
+    let base_api_version = api_version.map(str::to_string).or_else(|| {
+        url.query_pairs()
+            .find(|(key, _)| key == "api-version")
+            .map(|(_, value)| value.into_owned())
+    });
+    let base_path = url.path().trim_end_matches('/').to_string();
+    let operation = forwarded_path
+        .strip_prefix("/v1")
+        .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+        .unwrap_or(forwarded_path);
+
+    let (path, api_version) = if let Some(root) = base_path.strip_suffix("/openai/v1") {
+        (format!("{root}/openai/v1{operation}"), base_api_version)
+    } else {
+        let root = base_path.strip_suffix("/openai").unwrap_or(&base_path);
+        let path = if is_azure_deployment_operation(operation) {
+            format!("{root}/openai/deployments/{deployment}{operation}")
+        } else {
+            format!("{root}/openai{operation}")
+        };
+        (
+            path,
+            Some(base_api_version.unwrap_or_else(|| DEFAULT_AZURE_OPENAI_API_VERSION.to_string())),
+        )
+    };
+
+    let mut params: Vec<String> = query
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty() && !pair.starts_with("api-version="))
+        .map(str::to_string)
+        .collect();
+    if let Some(api_version) = api_version {
+        params.push(format!("api-version={api_version}"));
+    }
+    url.set_path(&path);
+    url.set_query((!params.is_empty()).then(|| params.join("&")).as_deref());
+    Ok(url)
+}
+
+fn is_azure_deployment_operation(operation: &str) -> bool {
+    [
+        "/chat/completions",
+        "/completions",
+        "/embeddings",
+        "/audio/",
+        "/images/",
+    ]
+    .iter()
+    .any(|prefix| operation == prefix.trim_end_matches('/') || operation.starts_with(prefix))
+}
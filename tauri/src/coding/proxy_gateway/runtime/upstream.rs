@@ -22,23 +22,34 @@ use super::middleware::{
     BillingHeaderCchMiddleware, EnsureMaxTokensMiddleware, Middleware, PipelineContext,
 };
 use super::pipeline::Pipeline;
-use super::providers::{ProviderAuthStrategy, UpstreamModelMapping, UpstreamProvider};
-use super::routes::{build_target_url, match_gateway_route, split_request_target, GatewayRoute};
+use super::providers::{
+    is_azure_openai_provider, ProviderAuthStrategy, UpstreamModelMapping, UpstreamProvider,
+};
+use super::response_cache::ResponseCacheRequest;
+use super::routes::{
+    build_azure_openai_target_url, build_target_url, match_gateway_route, split_request_target,
+    GatewayRoute,
+};
 use super::side_stores::{
     record_gemini_sse_stream, record_responses_sse_stream, GeminiShadowSessionKey,
 };
+use super::upstream_auth::{self, AwsSigV4Credentials};
 use super::GatewayRuntimeContext;
 use super::{cache_injector, thinking_budget};
+use crate::coding::proxy_gateway::budgets::{self, BudgetCandidate};
+use crate::coding::proxy_gateway::fixture_capture::FIXTURE_CAPTURE_MAX_BODY_BYTES;
+use crate::coding::proxy_gateway::load_balance::{self, BalanceCandidate};
 use crate::coding::proxy_gateway::model_health::{self, GatewayFailureKind};
 use crate::coding::proxy_gateway::transformer::{
     append_utf8_safe, check_lossy_conversion, convert_error_response_body,
     convert_request_body_with_context, convert_response_body_with_context,
-    convert_sse_stream_with_context, strip_sse_field, AiProtocol, ConversionContext,
-    ConversionRoute,
+    convert_sse_stream_with_context, is_content_filter_error, strip_sse_field, AiProtocol,
+    ConversionContext, ConversionRoute,
 };
 use crate::coding::proxy_gateway::types::{
     CodexChatReasoningMeta, GatewayCliKey, GatewayFailoverEvent, GatewayProviderAttempt,
-    GatewayProxyMode, GatewayStreamOutcome, ProviderGatewayMeta, ProviderModelHealthKey,
+    GatewayProxyMode, GatewayStreamOutcome, ProviderGatewayMeta, ProviderHealthKey,
+    ProviderModelHealthKey,
 };
 use crate::coding::proxy_gateway::usage_parser::{
     from_response_body_with_provider_type, TokenUsage,
@@ -144,6 +155,36 @@ fn snapshot_response_stream(
 pub(super) struct UpstreamHeaders {
     map: HeaderMap,
     preserved: Vec<PreservedHeader>,
+    /// Set for `AwsSigV4` providers; the signature depends on the final URL
+    /// and body, so it is added per send in `send_request_once`.
+    aws_sigv4: Option<AwsSigV4Credentials>,
+}
+
+impl UpstreamHeaders {
+    fn sign_for_request(
+        mut self,
+        method: &reqwest::Method,
+        upstream_url: &reqwest::Url,
+        upstream_body: &[u8],
+    ) -> Result<Self, String> {
+        let Some(credentials) = self.aws_sigv4.take() else {
+            return Ok(self);
+        };
+        for (name, value) in upstream_auth::sign_aws_request(
+            &credentials,
+            method.as_str(),
+            upstream_url,
+            upstream_body,
+        )? {
+            let value = HeaderValue::from_str(&value)
+                .map_err(|error| format!("Invalid AWS SigV4 header value for '{name}': {error}"))?;
+            self.map.remove(name);
+            self.preserved
+                .retain(|header| !header.name.eq_ignore_ascii_case(name));
+            append_preserved_header(&mut self.map, &mut self.preserved, name, value)?;
+        }
+        Ok(self)
+    }
 }
 
 #[cfg(test)]
@@ -641,6 +682,12 @@ pub(super) async fn route_request_with_options(
             "local health endpoint",
         );
     }
+    if request.method == "GET"
+        && request_path == "/metrics"
+        && context.settings_snapshot().metrics_endpoint_enabled
+    {
+        return super::metrics::metrics_response(context);
+    }
 
     let Some(route) = match_gateway_route(&request.path) else {
         return json_response(
@@ -804,6 +851,11 @@ async fn forward_to_upstream(
             .selection
             .as_ref()
             .is_some_and(|selection| selection.mode == GatewayProxyMode::Single);
+    let is_load_balanced = allow_provider_model_mapping
+        && provider_candidates
+            .selection
+            .as_ref()
+            .is_some_and(|selection| selection.mode == GatewayProxyMode::LoadBalance);
     let providers = provider_candidates.providers;
 
     let settings = context.settings_snapshot();
@@ -819,10 +871,84 @@ async fn forward_to_upstream(
                 .into_iter()
                 .collect()
         });
-    let upstream_response_snapshot_limit = settings
+    let mut upstream_response_snapshot_limit = settings
         .store_response_body
         .then(|| settings.log_max_body_size_kb.saturating_mul(1024) as usize);
+    if settings.fixture_capture_enabled {
+        // Fixture capture needs the whole upstream body even when request detail
+        // does not store response bodies.
+        upstream_response_snapshot_limit = Some(
+            upstream_response_snapshot_limit
+                .unwrap_or_default()
+                .max(FIXTURE_CAPTURE_MAX_BODY_BYTES),
+        );
+    }
     refresh_health_registry(context);
+    let providers = if is_load_balanced {
+        order_load_balanced_providers(
+            context,
+            db,
+            request,
+            route.cli_key,
+            &settings,
+            providers,
+            |provider| {
+                resolve_upstream_model_id(
+                    request,
+                    &requested_model,
+                    provider,
+                    apply_failover_model_mapping,
+                    allow_provider_model_mapping,
+                )
+            },
+        )
+    } else {
+        providers
+    };
+    // Connectivity tests pin a provider to probe it, so they never use the cache.
+    let response_cache = if options.provider_override_id.is_none() {
+        ResponseCacheRequest::new(
+            context,
+            &settings,
+            request,
+            route,
+            request_declares_streaming(request) || route_declares_streaming(route),
+        )
+    } else {
+        None
+    };
+    // Cached answers cost nothing upstream, so they are served before budget
+    // rules get a chance to reject or reroute the request.
+    if let Some(cache) = response_cache.as_ref() {
+        for provider in &providers {
+            let upstream_model_id = resolve_upstream_model_id(
+                request,
+                &requested_model,
+                provider,
+                apply_failover_model_mapping,
+                allow_provider_model_mapping,
+            );
+            let Some(key) = cache.key_for(provider, &upstream_model_id) else {
+                continue;
+            };
+            if let Some(mut response) = cache
+                .lookup(&key, provider, source_protocol_from_route(route))
+                .await
+            {
+                response.requested_model = Some(requested_model.clone());
+                response.upstream_model_id = Some(upstream_model_id);
+                return response;
+            }
+        }
+    }
+    let (providers, budget_warnings) = if allow_provider_model_mapping {
+        match apply_budget_rules(context, db, route, &requested_model, providers) {
+            BudgetDecision::Forward(providers, warnings) => (providers, warnings),
+            BudgetDecision::Reject(response) => return *response,
+        }
+    } else {
+        (providers, Vec::new())
+    };
     let mut health_changed = false;
     let mut attempt_count = 0_u32;
     let mut retry_count = 0_u32;
@@ -845,6 +971,9 @@ async fn forward_to_upstream(
             provider_id: provider.id.clone(),
             upstream_model_id: upstream_model_id.clone(),
         };
+        let response_cache_key = response_cache
+            .as_ref()
+            .and_then(|cache| cache.key_for(&provider, &upstream_model_id));
 
         // 单渠道代理跳过健康过滤，始终尝试转发。
         if !is_single_provider && !is_model_available(context, &health_key) {
@@ -944,6 +1073,7 @@ async fn forward_to_upstream(
 
                     if let Some(failure_kind) = classify_success_protocol_error(&response)
                         .or_else(|| classify_empty_success_response(&response))
+                        .or_else(|| classify_content_filter_failure(&response))
                         .or_else(|| classify_status_failure(response.status_code))
                     {
                         let category = model_health::classify_failure(failure_kind).category;
@@ -999,7 +1129,10 @@ async fn forward_to_upstream(
                     );
                     provider_attempts.push(provider_attempt_log(&response));
                     response.provider_attempts = provider_attempts;
-                    return response;
+                    if let Some((cache, key)) = response_cache.as_ref().zip(response_cache_key) {
+                        cache.store(key, &mut response);
+                    }
+                    return with_budget_warnings(response, &budget_warnings);
                 }
                 Err(error) => {
                     // Local deterministic errors must not same-provider retry or failover.
@@ -1019,7 +1152,7 @@ async fn forward_to_upstream(
                             attempted_provider_count > 1,
                         );
                         response.provider_attempts = provider_attempts;
-                        return response;
+                        return with_budget_warnings(response, &budget_warnings);
                     }
                     let category = model_health::classify_failure(error.kind).category;
                     if !options.disable_health_mutation {
@@ -1074,7 +1207,7 @@ async fn forward_to_upstream(
     save_health_registry_if_needed(context, health_changed);
     if let Some(mut response) = last_failure_response {
         response.provider_attempts = provider_attempts;
-        return response;
+        return with_budget_warnings(response, &budget_warnings);
     }
 
     let mut response = json_response(
@@ -1093,7 +1226,7 @@ async fn forward_to_upstream(
     response.requested_model = Some(requested_model);
     response.error_category = Some("cooling_down".to_string());
     response.provider_attempts = provider_attempts;
-    response
+    with_budget_warnings(response, &budget_warnings)
 }
 
 async fn send_upstream_request(
@@ -1180,6 +1313,13 @@ async fn send_upstream_request(
         effective_provider.api_key = copilot_token;
         effective_provider.auth_strategy = ProviderAuthStrategy::Bearer;
     }
+    if effective_provider.auth_strategy == ProviderAuthStrategy::GoogleServiceAccount {
+        effective_provider.api_key =
+            upstream_auth::exchange_service_account_token(&client, &effective_provider.api_key)
+                .await
+                .map_err(|message| GatewayForwardError::new(message, GatewayFailureKind::Auth))?;
+        effective_provider.auth_strategy = ProviderAuthStrategy::Bearer;
+    }
     let provider = &effective_provider;
     let headers =
         build_upstream_headers(request, provider, Some(&upstream_body)).map_err(|message| {
@@ -1543,6 +1683,9 @@ async fn send_request_once(
     stream_idle_timeout_secs: u64,
     header_preserving_proxy: Option<Option<String>>,
 ) -> Result<UpstreamResponse, GatewayForwardError> {
+    let headers = headers
+        .sign_for_request(&method, upstream_url, &upstream_body)
+        .map_err(|message| GatewayForwardError::new(message, GatewayFailureKind::GatewayParse))?;
     if should_use_header_preserving_raw(upstream_url) {
         if let Some(proxy_url) = header_preserving_proxy {
             match send_header_preserving_request(
@@ -1730,6 +1873,8 @@ async fn build_gateway_response(
             failover: false,
             source_protocol: None,
             stream_outcome: GatewayStreamOutcome::NotStreaming,
+            conversion_route,
+            cache_hit: false,
             note: format!(
                 "aggregated {} streaming response from provider id={} name={}",
                 aggregate_kind.label(),
@@ -1825,6 +1970,8 @@ async fn build_gateway_response(
             failover: false,
             source_protocol,
             stream_outcome: GatewayStreamOutcome::NotStreaming,
+            conversion_route,
+            cache_hit: false,
             note: format!(
                 "streaming forwarded to provider id={} name={}",
                 provider.id, provider.name
@@ -1977,6 +2124,8 @@ async fn build_gateway_response(
         failover: false,
         source_protocol: source_protocol_from_route(route),
         stream_outcome: GatewayStreamOutcome::NotStreaming,
+        conversion_route,
+        cache_hit: false,
         note: format!(
             "forwarded to provider id={} name={}",
             provider.id, provider.name
@@ -4002,6 +4151,8 @@ fn buffered_gateway_response(
         failover: false,
         source_protocol: source_protocol_from_route(route),
         stream_outcome: GatewayStreamOutcome::NotStreaming,
+        conversion_route: None,
+        cache_hit: false,
         note: format!(
             "forwarded to provider id={} name={}",
             provider.id, provider.name
@@ -4747,7 +4898,9 @@ fn can_retry_current_provider(
     retry_count: u32,
     max_retry_count: u32,
 ) -> bool {
-    should_retry_failure(failure_kind)
+    // The same provider applies the same content policy, so only fail over.
+    failure_kind != GatewayFailureKind::ContentFilter
+        && should_retry_failure(failure_kind)
         && provider_retry_count < per_provider_retry_count
         && retry_count < max_retry_count
 }
@@ -5036,7 +5189,13 @@ fn build_upstream_body_for_provider(
     } else {
         Vec::new()
     };
-    let upstream_model_for_body = strip_one_m_context_marker(upstream_model_id);
+    let mut upstream_model_for_body = strip_one_m_context_marker(upstream_model_id);
+    if let Some(provider) = provider.filter(|provider| is_azure_openai_provider(provider)) {
+        // Azure resolves the body model against deployment names, not model ids.
+        upstream_model_for_body = provider
+            .model_mapping
+            .azure_deployment_for(upstream_model_for_body);
+    }
     if let Some(model_value) = value.get_mut("model") {
         if model_value.is_string() {
             *model_value = Value::String(upstream_model_for_body.to_string());
@@ -8371,6 +8530,22 @@ fn build_provider_target_url(
         return build_full_target_url(&provider.base_url, query.as_deref());
     }
 
+    if is_azure_openai_provider(provider) {
+        let query = if conversion_route.is_some() {
+            converted_route_query(route_query, conversion_route, target_streaming)
+        } else {
+            route_query.map(str::to_string)
+        };
+        let model = strip_one_m_context_marker(upstream_model_id);
+        return build_azure_openai_target_url(
+            &provider.base_url,
+            forwarded_path,
+            provider.model_mapping.azure_deployment_for(model),
+            provider.model_mapping.azure_api_version.as_deref(),
+            query.as_deref(),
+        );
+    }
+
     if is_deepseek_legacy_completion_forward(provider, forwarded_path) {
         return build_deepseek_completion_beta_url(&provider.base_url, route_query);
     }
@@ -8719,9 +8894,17 @@ pub(super) fn build_upstream_headers(
         &mut preserved,
     )?;
     inject_custom_headers(provider, &mut headers, &mut preserved)?;
+    let aws_sigv4 = match provider.auth_strategy {
+        ProviderAuthStrategy::AwsSigV4 => Some(upstream_auth::resolve_aws_credentials(
+            &provider.api_key,
+            &provider.base_url,
+        )?),
+        _ => None,
+    };
     Ok(UpstreamHeaders {
         map: headers,
         preserved,
+        aws_sigv4,
     })
 }
 
@@ -9408,6 +9591,7 @@ fn should_skip_forwarded_request_header(name: &str) -> bool {
         "x-api-key",
         "x-goog-api-key",
         "x-goog-api-client",
+        crate::coding::proxy_gateway::access_tokens::ACCESS_TOKEN_HEADER,
     ]
     .iter()
     .any(|skip| name.eq_ignore_ascii_case(skip))
@@ -9461,6 +9645,19 @@ fn inject_provider_auth(
                 HeaderValue::from_static("GeminiCLI/1.0"),
             )?;
         }
+        ProviderAuthStrategy::AzureApiKey => {
+            let value = HeaderValue::from_str(provider.api_key.trim())
+                .map_err(|error| format!("Invalid Azure API key header value: {error}"))?;
+            append_preserved_header(headers, preserved, "api-key", value)?;
+        }
+        // Signed in `send_request_once` once the final URL and body are known.
+        ProviderAuthStrategy::AwsSigV4 => {}
+        ProviderAuthStrategy::GoogleServiceAccount => {
+            return Err(
+                "Google service account key must be exchanged for an access token first"
+                    .to_string(),
+            );
+        }
     }
     if provider.target_protocol == AiProtocol::AnthropicMessages {
         let anthropic_version = anthropic_header_version(provider);
@@ -9590,6 +9787,25 @@ fn classify_empty_success_response(response: &DebugHttpResponse) -> Option<Gatew
     (!response_has_meaningful_content(response)).then_some(GatewayFailureKind::EmptyResponse)
 }
 
+/// Content-filter rejections (Azure OpenAI `code: content_filter`) depend on the
+/// prompt and the provider's policy, so they fail over without a health penalty.
+fn classify_content_filter_failure(response: &DebugHttpResponse) -> Option<GatewayFailureKind> {
+    if (200..400).contains(&response.status_code) {
+        return None;
+    }
+    let reports_content_filter = |body: &[u8]| {
+        serde_json::from_slice::<Value>(body)
+            .ok()
+            .is_some_and(|value| is_content_filter_error(&value))
+    };
+    (response
+        .upstream_response_body
+        .as_deref()
+        .is_some_and(reports_content_filter)
+        || reports_content_filter(&response.body))
+    .then_some(GatewayFailureKind::ContentFilter)
+}
+
 fn response_body_reports_protocol_error(response: &DebugHttpResponse) -> bool {
     gateway_body_reports_error(&response.body)
         || response
@@ -9908,7 +10124,10 @@ fn should_retry_status_or_kind(
     kind: GatewayFailureKind,
     retryable_status_codes: &std::collections::BTreeSet<u16>,
 ) -> bool {
-    if kind == GatewayFailureKind::EmptyResponse {
+    if matches!(
+        kind,
+        GatewayFailureKind::EmptyResponse | GatewayFailureKind::ContentFilter
+    ) {
         return should_retry_failure(kind);
     }
     if let Some(status) = status_code {
@@ -9978,6 +10197,242 @@ fn refresh_health_registry(context: &GatewayRuntimeContext) {
     }
 }
 
+/// Outcome of [`apply_budget_rules`] for one request.
+enum BudgetDecision {
+    /// Providers to try, in order, plus warning headers for the response.
+    Forward(Vec<UpstreamProvider>, Vec<(String, String)>),
+    /// Every candidate is blocked by a reject rule.
+    Reject(Box<DebugHttpResponse>),
+}
+
+/// Enforce budget rules on the failover chain before any upstream attempt.
+fn apply_budget_rules(
+    context: &GatewayRuntimeContext,
+    db: &SqliteDbState,
+    route: &GatewayRoute,
+    requested_model: &str,
+    providers: Vec<UpstreamProvider>,
+) -> BudgetDecision {
+    let provider_ids = providers
+        .iter()
+        .map(|provider| provider.id.as_str())
+        .collect::<Vec<_>>();
+    let now = std::time::Instant::now();
+    let local_now = chrono::Local::now();
+    let period_start = |period| budgets::period_start(period, local_now);
+
+    // Spend is read from SQLite without the tracker lock held, so concurrent
+    // requests only wait on the lock for cache lookups.
+    let to_load = match context.budget_tracker.lock() {
+        Ok(tracker) if tracker.has_active_rules() => tracker.rules_to_load(
+            route.cli_key,
+            requested_model,
+            &provider_ids,
+            now,
+            period_start,
+        ),
+        _ => return BudgetDecision::Forward(providers, Vec::new()),
+    };
+    let loaded = to_load
+        .into_iter()
+        .map(|(rule, since)| {
+            let usage = db.with_conn(|conn| budgets::rule_usage(conn, &rule, since));
+            (rule.id, since, usage)
+        })
+        .collect::<Vec<_>>();
+
+    let Ok(mut tracker) = context.budget_tracker.lock() else {
+        return BudgetDecision::Forward(providers, Vec::new());
+    };
+    for (rule_id, since, usage) in loaded {
+        tracker.record_usage(&rule_id, since, now, usage);
+    }
+    let exceeded = tracker.exceeded_rules(
+        route.cli_key,
+        requested_model,
+        &provider_ids,
+        now,
+        period_start,
+    );
+    if exceeded.is_empty() {
+        return BudgetDecision::Forward(providers, Vec::new());
+    }
+    for item in &exceeded {
+        if !tracker.should_emit_event(&item.rule.id, now) {
+            continue;
+        }
+        if let Some(app_handle) = context.app_handle.as_ref() {
+            if let Err(error) =
+                app_handle.emit("gateway-budget-exceeded", item.to_event(route.cli_key))
+            {
+                log::warn!("Failed to emit gateway budget event: {error}");
+            }
+        }
+    }
+    drop(tracker);
+
+    let candidates = providers
+        .iter()
+        .map(|provider| BudgetCandidate {
+            provider_id: provider.id.clone(),
+            cost_multiplier: provider
+                .meta
+                .cost_multiplier
+                .parse()
+                .unwrap_or(rust_decimal::Decimal::ONE),
+        })
+        .collect::<Vec<_>>();
+    let plan = budgets::plan_providers(&exceeded, &candidates);
+    if let Some(rejected_by) = plan.rejected_by {
+        let exceeded_budget = &exceeded[rejected_by];
+        let message = exceeded_budget.reject_message();
+        let mut response = json_response(
+            429,
+            "Too Many Requests",
+            budgets::quota_error_body(source_protocol_from_route(route), &message),
+            route.route_name,
+            None,
+            "request rejected locally by a gateway budget rule",
+        );
+        response.cli_key = Some(route.cli_key);
+        response.requested_model = Some(requested_model.to_string());
+        response.error_category = Some("budget_exceeded".to_string());
+        return BudgetDecision::Reject(Box::new(response));
+    }
+
+    let warnings = plan
+        .warnings
+        .iter()
+        .map(|index| {
+            (
+                budgets::BUDGET_WARNING_HEADER.to_string(),
+                exceeded[*index].warning_header_value(),
+            )
+        })
+        .collect();
+    let mut slots = providers.into_iter().map(Some).collect::<Vec<_>>();
+    let ordered = plan
+        .order
+        .iter()
+        .filter_map(|index| slots[*index].take())
+        .collect();
+    BudgetDecision::Forward(ordered, warnings)
+}
+
+fn with_budget_warnings(
+    mut response: DebugHttpResponse,
+    warnings: &[(String, String)],
+) -> DebugHttpResponse {
+    response.headers.extend(warnings.iter().cloned());
+    response
+}
+
+/// Put the load balancer's pick first; the remaining providers keep their
+/// failover order so a failed attempt still falls through the pool.
+fn order_load_balanced_providers<F>(
+    context: &GatewayRuntimeContext,
+    db: &SqliteDbState,
+    request: &DebugHttpRequest,
+    cli_key: GatewayCliKey,
+    settings: &crate::coding::proxy_gateway::types::ProxyGatewaySettings,
+    providers: Vec<UpstreamProvider>,
+    upstream_model_for: F,
+) -> Vec<UpstreamProvider>
+where
+    F: Fn(&UpstreamProvider) -> String,
+{
+    if providers.len() < 2 {
+        return providers;
+    }
+    // The stats query runs without the balancer lock so concurrent requests
+    // never queue behind SQLite; the lock is only held to read or store the
+    // snapshot and to pick.
+    let now = std::time::Instant::now();
+    let Ok(cached) = context
+        .load_balancer
+        .lock()
+        .map(|balancer| balancer.fresh_provider_stats(cli_key, now))
+    else {
+        return providers;
+    };
+    let stats = match cached {
+        Some(stats) => stats,
+        None => {
+            let since = chrono::Utc::now().timestamp() - load_balance::STATS_WINDOW_SECS;
+            let loaded = crate::coding::proxy_gateway::usage_stats::provider_stats(
+                db,
+                Some(since),
+                None,
+                Some(cli_key),
+            );
+            let Ok(mut balancer) = context.load_balancer.lock() else {
+                return providers;
+            };
+            balancer.store_provider_stats(cli_key, now, loaded)
+        }
+    };
+
+    let health_now = chrono::Utc::now();
+    let registry = context
+        .health_registry
+        .as_ref()
+        .and_then(|registry| registry.lock().ok());
+    let candidates: Vec<BalanceCandidate> = providers
+        .iter()
+        .map(|provider| {
+            let health_key = ProviderModelHealthKey {
+                cli_key,
+                provider_id: provider.id.clone(),
+                upstream_model_id: upstream_model_for(provider),
+            };
+            let (available, failure_score) = registry.as_ref().map_or((true, 0), |registry| {
+                let score = registry
+                    .model_entry(&health_key)
+                    .map_or(0, |entry| entry.failure_score)
+                    + registry
+                        .provider_entry(&ProviderHealthKey::from(&health_key))
+                        .map_or(0, |entry| entry.failure_score);
+                (registry.is_model_available(&health_key, health_now), score)
+            });
+            let provider_stats = stats
+                .get(&provider.id)
+                .filter(|item| item.request_count > 0);
+            BalanceCandidate {
+                provider_id: provider.id.clone(),
+                weight: provider.meta.load_balance_weight.unwrap_or(1),
+                available,
+                failure_score,
+                avg_latency_ms: provider_stats.map(|item| item.avg_latency_ms),
+                success_ratio: provider_stats.map(|item| f64::from(item.success_rate) / 100.0),
+            }
+        })
+        .collect();
+    drop(registry);
+
+    let session_key = serde_json::from_slice::<Value>(&request.body)
+        .ok()
+        .and_then(|body| gateway_session_id_hint(request, &body));
+    let Ok(mut balancer) = context.load_balancer.lock() else {
+        return providers;
+    };
+    let order = balancer.order(
+        cli_key,
+        settings.load_balance_strategy,
+        &candidates,
+        session_key.as_deref(),
+        Duration::from_secs(settings.load_balance_sticky_ttl_secs),
+        now,
+        rand::random::<f64>(),
+    );
+    drop(balancer);
+
+    let mut slots: Vec<Option<UpstreamProvider>> = providers.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .filter_map(|index| slots[index].take())
+        .collect()
+}
+
 fn is_model_available(
     context: &GatewayRuntimeContext,
     health_key: &ProviderModelHealthKey,
@@ -11392,6 +11847,62 @@ data: {data}\r\n\r\n"
         assert!(!headers.contains_key("x-api-key"));
     }
 
+    #[test]
+    fn anthropic_bedrock_sigv4_provider_signs_the_final_url_and_body() {
+        let provider = UpstreamProvider {
+            target_protocol: AiProtocol::AnthropicMessages,
+            auth_strategy: ProviderAuthStrategy::AwsSigV4,
+            base_url: "https://bedrock-runtime.us-west-2.amazonaws.com".to_string(),
+            api_key: json!({
+                "AWS_ACCESS_KEY_ID": "AKID",
+                "AWS_SECRET_ACCESS_KEY": "secret",
+                "AWS_SESSION_TOKEN": "session"
+            })
+            .to_string(),
+            meta: ProviderGatewayMeta {
+                provider_type: Some("bedrock".to_string()),
+                ..ProviderGatewayMeta::default()
+            },
+            ..provider_for_cli(GatewayCliKey::Claude)
+        };
+        let url = build_provider_target_url(
+            &provider,
+            "/v1/messages",
+            None,
+            None,
+            false,
+            "anthropic.claude-sonnet-4-20250514-v1:0",
+        )
+        .unwrap();
+        let headers = build_upstream_headers(&debug_request(b"{}"), &provider, None).unwrap();
+        assert!(!headers.contains_key(AUTHORIZATION));
+
+        let signed = headers
+            .sign_for_request(&reqwest::Method::POST, &url, b"{}")
+            .unwrap();
+        let authorization = signed
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .unwrap();
+        assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKID/"));
+        assert!(authorization.contains("/us-west-2/bedrock/aws4_request"));
+        assert!(signed.contains_key("x-amz-date"));
+        assert_eq!(
+            signed
+                .get("x-amz-security-token")
+                .and_then(|value| value.to_str().ok()),
+            Some("session")
+        );
+        assert_eq!(
+            signed
+                .preserved
+                .iter()
+                .filter(|header| header.name.eq_ignore_ascii_case("authorization"))
+                .count(),
+            1
+        );
+    }
+
     #[test]
     fn anthropic_bedrock_body_clears_model_and_stream() {
         let body = apply_outbound_adapter_compat_for_provider_type(
@@ -12260,6 +12771,8 @@ data: {data}\r\n\r\n"
             failover: false,
             source_protocol: None,
             stream_outcome: GatewayStreamOutcome::NotStreaming,
+            conversion_route: None,
+            cache_hit: false,
             note: String::new(),
         }
     }
@@ -14518,6 +15031,8 @@ data: {data}\r\n\r\n"
             failover: false,
             source_protocol: None,
             stream_outcome: GatewayStreamOutcome::NotStreaming,
+            conversion_route: None,
+            cache_hit: false,
             note: String::new(),
         };
 
@@ -14578,6 +15093,8 @@ data: {data}\r\n\r\n"
             failover: false,
             source_protocol: None,
             stream_outcome: GatewayStreamOutcome::NotStreaming,
+            conversion_route: None,
+            cache_hit: false,
             note: String::new(),
         };
 
@@ -17163,6 +17680,98 @@ data: {"id":"chatcmpl_1","object":"chat.completion.chunk","model":"qwen3","choic
         assert_eq!(weight.category, "upstream_bad_request");
     }
 
+    #[test]
+    fn azure_content_filter_fails_over_without_health_penalty() {
+        let mut response = protocol_error_debug_response(
+            br#"{"error":{"message":"The response was filtered","type":null,"param":"prompt","code":"content_filter","status":400,"innererror":{"code":"ResponsibleAIPolicyViolation"}}}"#,
+        );
+        response.status_code = 400;
+        assert_eq!(
+            classify_content_filter_failure(&response),
+            Some(GatewayFailureKind::ContentFilter)
+        );
+        let weight = model_health::classify_failure(GatewayFailureKind::ContentFilter);
+        assert_eq!(weight.scope, model_health::FailureScope::None);
+        assert_eq!(weight.category, "content_filter");
+        assert!(should_retry_status_or_kind(
+            Some(400),
+            GatewayFailureKind::ContentFilter,
+            &std::collections::BTreeSet::new(),
+        ));
+        assert!(!can_retry_current_provider(
+            GatewayFailureKind::ContentFilter,
+            0,
+            3,
+            0,
+            3
+        ));
+
+        response.body = br#"{"error":{"message":"bad","code":"invalid_value"}}"#.to_vec();
+        assert_eq!(classify_content_filter_failure(&response), None);
+    }
+
+    #[test]
+    fn azure_openai_provider_uses_deployment_url_api_key_and_body_model() {
+        let provider = UpstreamProvider {
+            target_protocol: AiProtocol::OpenAiChat,
+            auth_strategy: ProviderAuthStrategy::AzureApiKey,
+            base_url: "https://res.openai.azure.com/openai?api-version=2024-10-21".to_string(),
+            api_key: "azure-key".to_string(),
+            meta: ProviderGatewayMeta {
+                provider_type: Some("azure".to_string()),
+                ..ProviderGatewayMeta::default()
+            },
+            model_mapping: UpstreamModelMapping {
+                azure_deployments: [("gpt-4o".to_string(), "gpt-4o-prod".to_string())]
+                    .into_iter()
+                    .collect(),
+                ..UpstreamModelMapping::default()
+            },
+            ..provider_for_cli(GatewayCliKey::Codex)
+        };
+        let url = build_provider_target_url(
+            &provider,
+            "/v1/chat/completions",
+            None,
+            None,
+            false,
+            "gpt-4o",
+        )
+        .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://res.openai.azure.com/openai/deployments/gpt-4o-prod/chat/completions?api-version=2024-10-21"
+        );
+
+        let headers = build_upstream_headers(&debug_request(b"{}"), &provider, None).unwrap();
+        assert_eq!(
+            headers.get("api-key").and_then(|value| value.to_str().ok()),
+            Some("azure-key")
+        );
+        assert!(!headers.contains_key(AUTHORIZATION));
+
+        let request = debug_request(br#"{"model":"gpt-4o","messages":[]}"#);
+        let prepared = build_upstream_body_for_provider(
+            &request,
+            "gpt-4o",
+            "gpt-4o",
+            false,
+            false,
+            GatewayCliKey::Codex,
+            Some(AiProtocol::OpenAiChat),
+            AiProtocol::OpenAiChat,
+            None,
+            Some(&provider.meta),
+            None,
+            Some(&provider),
+            false,
+            CodexResponsesCompactCompat::none(),
+        )
+        .unwrap();
+        let body = serde_json::from_slice::<Value>(&prepared.body).unwrap();
+        assert_eq!(body["model"], "gpt-4o-prod");
+    }
+
     #[test]
     fn payment_required_is_retryable_provider_auth_failure() {
         assert_eq!(classify_status_failure(402), Some(GatewayFailureKind::Auth));
@@ -1,4 +1,5 @@
-use super::listen::validate_settings;
+use super::access_tokens;
+use super::listen::{validate_listen_host, validate_settings};
 use super::types::ProxyGatewaySettings;
 use crate::db::helpers::{db_get, db_put};
 use crate::db::schema::DbTable;
@@ -23,6 +24,10 @@ pub fn save_settings_to_sqlite_state(
     settings: ProxyGatewaySettings,
 ) -> Result<ProxyGatewaySettings, String> {
     let settings = normalize_settings(settings)?;
+    validate_listen_host(
+        &settings.listen_host,
+        access_tokens::lan_listen_allowed(sqlite_state),
+    )?;
     let data = serde_json::to_value(&settings)
         .map_err(|error| format!("Failed to serialize proxy gateway settings: {error}"))?;
     sqlite_state
@@ -67,6 +72,7 @@ fn normalize_common_settings(settings: &mut ProxyGatewaySettings) -> Result<(),
     settings.retryable_status_codes = super::retryable_status::normalize_retryable_status_codes(
         &settings.retryable_status_codes,
     )?;
+    settings.fixture_capture_filter = settings.fixture_capture_filter.trim().to_string();
     Ok(())
 }
 
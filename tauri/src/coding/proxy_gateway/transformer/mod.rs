@@ -10,6 +10,7 @@ mod gemini;
 mod kernel;
 mod llm;
 mod openai;
+mod replay;
 mod shared;
 mod sse;
 mod stream;
@@ -28,8 +29,14 @@ pub use kernel::{
     convert_target_response_body_to_responses_compact,
 };
 pub use kernel::{convert_sse_stream, convert_sse_stream_with_context, ConversionContext};
+pub use replay::{
+    diff_json, fixture_events_to_jsonl, fixture_events_to_sse, fixture_protocol_dir,
+    parse_fixture_jsonl, replay_conversion, sse_to_fixture_events, stream_events_value,
+    ConversionReplayOutput, FixtureResponseBody, FixtureStreamEvent, JsonDifference,
+};
 pub use shared::lossy::{check_lossy_conversion, LossyConversionIssue};
 pub use shared::tool_schema::flatten_namespace_tool_name;
 pub(crate) use sse::{append_utf8_safe, strip_sse_field, take_sse_block};
 pub use types::{AiProtocol, ConversionRoute};
 pub(crate) use gemini::gemini_stream_error;
+pub(crate) use shared::is_content_filter_error;
@@ -0,0 +1,380 @@
+//! Replay helpers for protocol-conversion fixtures.
+//!
+//! Captured gateway traffic is stored in the same shape as the hand-written
+//! fixtures under `fixtures/`: JSON bodies as `*.request.json` /
+//! `*.response.json` and SSE bodies as `*.stream.jsonl`, one event per line.
+//! Replaying feeds those bodies back through the conversion kernel and diffs
+//! the result against a previously recorded output.
+
+use super::error::ProtocolConversionError;
+use super::kernel::{convert_request_body, convert_response_body, convert_sse_stream};
+use super::sse::{strip_sse_field, take_sse_block};
+use super::types::{AiProtocol, ConversionRoute};
+use futures_util::{stream, FutureExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One SSE event in the fixture JSONL layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixtureStreamEvent {
+    #[serde(rename = "LastEventID", default)]
+    pub last_event_id: String,
+    #[serde(rename = "Type", default)]
+    pub event_type: String,
+    #[serde(rename = "Data", default)]
+    pub data: String,
+}
+
+/// Upstream response body of a captured exchange.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FixtureResponseBody {
+    Json(Value),
+    Stream(Vec<FixtureStreamEvent>),
+}
+
+/// Output of running a captured exchange through the conversion kernel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ConversionReplayOutput {
+    pub upstream_request: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_response: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_stream: Option<Vec<FixtureStreamEvent>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct JsonDifference {
+    /// JSON pointer of the differing value; empty for the document root.
+    pub path: String,
+    pub expected: Option<Value>,
+    pub actual: Option<Value>,
+}
+
+/// Directory name used for a protocol in the fixture tree.
+pub fn fixture_protocol_dir(protocol: AiProtocol) -> &'static str {
+    match protocol {
+        AiProtocol::AnthropicMessages => "anthropic",
+        AiProtocol::OpenAiResponses => "openai_responses",
+        AiProtocol::OpenAiChat => "openai_chat",
+        AiProtocol::GeminiNative => "gemini",
+    }
+}
+
+/// Splits a raw SSE body into fixture events. Trailing bytes without a closing
+/// blank line are kept as a final event so truncated captures stay visible.
+pub fn sse_to_fixture_events(body: &[u8]) -> Vec<FixtureStreamEvent> {
+    let mut buffer = String::from_utf8_lossy(body).replace("\r\n", "\n");
+    let mut events = Vec::new();
+    while let Some(block) = take_sse_block(&mut buffer) {
+        push_sse_block(&mut events, &block);
+    }
+    if !buffer.trim().is_empty() {
+        push_sse_block(&mut events, &buffer);
+    }
+    events
+}
+
+fn push_sse_block(events: &mut Vec<FixtureStreamEvent>, block: &str) {
+    let mut event = FixtureStreamEvent {
+        last_event_id: String::new(),
+        event_type: String::new(),
+        data: String::new(),
+    };
+    let mut data_lines = Vec::new();
+    for line in block.lines() {
+        if let Some(value) = strip_sse_field(line, "event") {
+            event.event_type = value.to_string();
+        } else if let Some(value) = strip_sse_field(line, "data") {
+            data_lines.push(value);
+        } else if let Some(value) = strip_sse_field(line, "id") {
+            event.last_event_id = value.to_string();
+        }
+    }
+    if data_lines.is_empty() && event.event_type.is_empty() {
+        return;
+    }
+    event.data = data_lines.join("\n");
+    events.push(event);
+}
+
+/// Renders fixture events back into an SSE body.
+pub fn fixture_events_to_sse(events: &[FixtureStreamEvent]) -> Vec<u8> {
+    let mut sse = String::new();
+    for event in events {
+        if !event.last_event_id.is_empty() {
+            sse.push_str("id: ");
+            sse.push_str(&event.last_event_id);
+            sse.push('\n');
+        }
+        if !event.event_type.is_empty() {
+            sse.push_str("event: ");
+            sse.push_str(&event.event_type);
+            sse.push('\n');
+        }
+        for line in event.data.split('\n') {
+            sse.push_str("data: ");
+            sse.push_str(line);
+            sse.push('\n');
+        }
+        sse.push('\n');
+    }
+    sse.into_bytes()
+}
+
+pub fn fixture_events_to_jsonl(events: &[FixtureStreamEvent]) -> Result<String, String> {
+    let mut text = String::new();
+    for event in events {
+        text.push_str(
+            &serde_json::to_string(event)
+                .map_err(|error| format!("Failed to serialize stream event: {error}"))?,
+        );
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+pub fn parse_fixture_jsonl(text: &str) -> Result<Vec<FixtureStreamEvent>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|error| format!("Invalid stream fixture line {}: {error}", index + 1))
+        })
+        .collect()
+}
+
+/// Converts the client request and the upstream response of one exchange.
+///
+/// `route` is the request direction (client protocol to provider protocol);
+/// the response is converted along the reverse route, as the gateway does.
+pub fn replay_conversion(
+    route: ConversionRoute,
+    request_body: &[u8],
+    response: &FixtureResponseBody,
+) -> Result<ConversionReplayOutput, String> {
+    let upstream_request = convert_request_body(route, request_body)
+        .map_err(|error| conversion_error("request", error))
+        .and_then(|body| parse_json_output("request", &body))?;
+    let response_route = route.reverse();
+    match response {
+        FixtureResponseBody::Json(body) => {
+            let body = serde_json::to_vec(body)
+                .map_err(|error| format!("Failed to serialize response fixture: {error}"))?;
+            let client_response = convert_response_body(response_route, &body)
+                .map_err(|error| conversion_error("response", error))
+                .and_then(|body| parse_json_output("response", &body))?;
+            Ok(ConversionReplayOutput {
+                upstream_request,
+                client_response: Some(client_response),
+                client_stream: None,
+            })
+        }
+        FixtureResponseBody::Stream(events) => {
+            let converted =
+                collect_converted_stream(response_route, fixture_events_to_sse(events))?;
+            Ok(ConversionReplayOutput {
+                upstream_request,
+                client_response: None,
+                client_stream: Some(sse_to_fixture_events(&converted)),
+            })
+        }
+    }
+}
+
+fn conversion_error(stage: &str, error: ProtocolConversionError) -> String {
+    format!("Failed to convert {stage}: {error}")
+}
+
+fn parse_json_output(stage: &str, body: &[u8]) -> Result<Value, String> {
+    serde_json::from_slice(body)
+        .map_err(|error| format!("Converted {stage} is not valid JSON: {error}"))
+}
+
+/// Drives the conversion stream to completion without an async runtime. The
+/// input is fully in memory, so every poll is immediately ready.
+fn collect_converted_stream(route: ConversionRoute, sse: Vec<u8>) -> Result<Vec<u8>, String> {
+    let input = stream::iter(vec![Ok(sse)]);
+    let chunks = convert_sse_stream(route, Box::pin(input))
+        .collect::<Vec<_>>()
+        .now_or_never()
+        .ok_or_else(|| "Stream conversion did not complete synchronously".to_string())?;
+    let mut bytes = Vec::new();
+    for chunk in chunks {
+        bytes.extend(chunk.map_err(|error| format!("Failed to convert stream: {error}"))?);
+    }
+    Ok(bytes)
+}
+
+/// Stream events as JSON, with `Data` parsed where possible so diffs point
+/// into the payload instead of reporting the whole line.
+pub fn stream_events_value(events: &[FixtureStreamEvent]) -> Value {
+    Value::Array(
+        events
+            .iter()
+            .map(|event| {
+                let data = serde_json::from_str(&event.data)
+                    .unwrap_or_else(|_| Value::String(event.data.clone()));
+                serde_json::json!({ "type": event.event_type, "data": data })
+            })
+            .collect(),
+    )
+}
+
+/// Lists every leaf where `actual` differs from `expected`.
+pub fn diff_json(expected: &Value, actual: &Value) -> Vec<JsonDifference> {
+    let mut differences = Vec::new();
+    diff_json_at(
+        String::new(),
+        Some(expected),
+        Some(actual),
+        &mut differences,
+    );
+    differences
+}
+
+fn diff_json_at(
+    path: String,
+    expected: Option<&Value>,
+    actual: Option<&Value>,
+    differences: &mut Vec<JsonDifference>,
+) {
+    match (expected, actual) {
+        (Some(Value::Object(expected)), Some(Value::Object(actual))) => {
+            let mut keys = expected.keys().chain(actual.keys()).collect::<Vec<_>>();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                diff_json_at(
+                    format!("{path}/{}", escape_pointer_token(key)),
+                    expected.get(key),
+                    actual.get(key),
+                    differences,
+                );
+            }
+        }
+        (Some(Value::Array(expected)), Some(Value::Array(actual))) => {
+            for index in 0..expected.len().max(actual.len()) {
+                diff_json_at(
+                    format!("{path}/{index}"),
+                    expected.get(index),
+                    actual.get(index),
+                    differences,
+                );
+            }
+        }
+        (expected, actual) if expected != actual => differences.push(JsonDifference {
+            path,
+            expected: expected.cloned(),
+            actual: actual.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sse_fixture_events_roundtrip() {
+        let sse = "event: message_start\ndata: {\"type\":\"message_start\"}\n\ndata: [DONE]\n\n";
+        let events = sse_to_fixture_events(sse.as_bytes());
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type, "message_start");
+        assert_eq!(events[1].data, "[DONE]");
+        let jsonl = fixture_events_to_jsonl(&events).unwrap();
+        assert!(jsonl.starts_with("{\"LastEventID\":\"\",\"Type\":\"message_start\""));
+        assert_eq!(parse_fixture_jsonl(&jsonl).unwrap(), events);
+        assert_eq!(
+            String::from_utf8(fixture_events_to_sse(&events)).unwrap(),
+            sse
+        );
+    }
+
+    #[test]
+    fn replays_json_exchange_through_both_directions() {
+        let route = ConversionRoute::new(AiProtocol::AnthropicMessages, AiProtocol::OpenAiChat);
+        let request = json!({
+            "model": "m",
+            "max_tokens": 16,
+            "messages": [{"role": "user", "content": "hi"}]
+        });
+        let response = json!({
+            "id": "chatcmpl_1",
+            "object": "chat.completion",
+            "model": "m",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "hello"},
+                "finish_reason": "stop"
+            }],
+            "usage": {"prompt_tokens": 3, "completion_tokens": 1, "total_tokens": 4}
+        });
+
+        let output = replay_conversion(
+            route,
+            request.to_string().as_bytes(),
+            &FixtureResponseBody::Json(response),
+        )
+        .unwrap();
+
+        assert_eq!(
+            output.upstream_request["messages"][0]["content"][0]["text"],
+            "hi"
+        );
+        let client_response = output.client_response.unwrap();
+        assert_eq!(client_response["type"], "message");
+        assert_eq!(client_response["content"][0]["text"], "hello");
+        assert!(output.client_stream.is_none());
+    }
+
+    #[test]
+    fn replays_stream_exchange_without_async_runtime() {
+        let route = ConversionRoute::new(AiProtocol::AnthropicMessages, AiProtocol::OpenAiChat);
+        let upstream = sse_to_fixture_events(
+            concat!(
+                "data: {\"id\":\"c1\",\"model\":\"m\",\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"hel\"}}]}\n\n",
+                "data: {\"id\":\"c1\",\"model\":\"m\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"lo\"},\"finish_reason\":\"stop\"}]}\n\n",
+                "data: [DONE]\n\n",
+            )
+            .as_bytes(),
+        );
+
+        let output = replay_conversion(
+            route,
+            br#"{"model":"m","max_tokens":16,"stream":true,"messages":[{"role":"user","content":"hi"}]}"#,
+            &FixtureResponseBody::Stream(upstream),
+        )
+        .unwrap();
+
+        let events = output.client_stream.unwrap();
+        assert_eq!(events.first().unwrap().event_type, "message_start");
+        assert_eq!(events.last().unwrap().event_type, "message_stop");
+        assert!(events
+            .iter()
+            .any(|event| event.event_type == "content_block_delta" && event.data.contains("hel")));
+    }
+
+    #[test]
+    fn diff_json_reports_pointer_paths() {
+        let expected = json!({"a": {"b/c": 1}, "list": [1, 2], "same": true});
+        let actual = json!({"a": {"b/c": 2}, "list": [1], "extra": null, "same": true});
+
+        let paths = diff_json(&expected, &actual)
+            .into_iter()
+            .map(|difference| difference.path)
+            .collect::<Vec<_>>();
+
+        assert_eq!(paths, vec!["/a/b~1c", "/extra", "/list/1"]);
+        assert!(diff_json(&expected, &expected).is_empty());
+    }
+}
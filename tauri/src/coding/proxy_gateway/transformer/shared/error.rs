@@ -50,6 +50,24 @@ pub(crate) fn extract_error_type(error: &Value) -> Option<String> {
             "/status",
         ],
     )
+    .or_else(|| is_content_filter_error(error).then(|| "invalid_request_error".to_string()))
+}
+
+/// Azure OpenAI content-filter rejections carry `type: null` with
+/// `code: content_filter` (or `innererror.code: ResponsibleAIPolicyViolation`).
+pub(crate) fn is_content_filter_error(error: &Value) -> bool {
+    [
+        "/error/code",
+        "/code",
+        "/error/innererror/code",
+        "/innererror/code",
+    ]
+    .iter()
+    .filter_map(|pointer| error.pointer(pointer).and_then(Value::as_str))
+    .any(|code| {
+        code.eq_ignore_ascii_case("content_filter")
+            || code.eq_ignore_ascii_case("ResponsibleAIPolicyViolation")
+    })
 }
 
 pub(crate) fn extract_error_code(error: &Value) -> Option<Value> {
@@ -10,6 +10,7 @@ use super::llm::ApiFormat;
 
 pub(crate) use error::{
     extract_error_code, extract_error_message, extract_error_param, extract_error_type,
+    is_content_filter_error,
 };
 pub use messages::{
     content_text, extract_reasoning_field_text, json_string, message_parts,
@@ -1,5 +1,7 @@
+use super::transformer::{ConversionReplayOutput, ConversionRoute, JsonDifference};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -41,6 +43,8 @@ impl GatewayCliKey {
 pub enum GatewayProxyMode {
     Single,
     Failover,
+    /// Spread requests across the weighted provider pool, failing over inside it.
+    LoadBalance,
 }
 
 impl GatewayProxyMode {
@@ -48,8 +52,27 @@ impl GatewayProxyMode {
         match self {
             Self::Single => "single",
             Self::Failover => "failover",
+            Self::LoadBalance => "load_balance",
         }
     }
+
+    /// Whether requests may be routed to providers other than the primary one.
+    pub fn is_multi_provider(self) -> bool {
+        !matches!(self, Self::Single)
+    }
+}
+
+/// How `GatewayProxyMode::LoadBalance` picks the provider for a new session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GatewayLoadBalanceStrategy {
+    /// Random pick proportional to each provider's `load_balance_weight`.
+    #[default]
+    Weighted,
+    /// Lowest average latency over the recent request log.
+    LeastLatency,
+    /// Lowest recent error rate, penalized by model health failures.
+    LeastErrorRate,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -63,6 +86,9 @@ pub struct AppProxyConfig {
     pub retry_interval_secs: Option<u64>,
     pub cost_multiplier: Option<String>,
     pub pricing_model_source: Option<String>,
+    /// Serve repeated non-streaming or temperature-0 requests from the gateway
+    /// response cache instead of forwarding them upstream.
+    pub response_cache_enabled: Option<bool>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -93,6 +119,10 @@ pub struct ProviderGatewayMeta {
     /// fingerprint managed by `inject_copilot_headers`.
     #[serde(default, rename = "customHeaders", alias = "custom_headers")]
     pub custom_headers: Option<Vec<CustomHeaderOverride>>,
+    /// Share of traffic in load-balance mode (default 1). `0` keeps the
+    /// provider as a standby that is only used after the weighted pool fails.
+    #[serde(alias = "loadBalanceWeight")]
+    pub load_balance_weight: Option<u32>,
 }
 
 /// One request-header override operation, mirroring axonhub's flat
@@ -164,6 +194,7 @@ impl Default for ProviderGatewayMeta {
             cost_multiplier: "1.0".to_string(),
             pricing_model_source: "upstream".to_string(),
             custom_headers: None,
+            load_balance_weight: None,
         }
     }
 }
@@ -181,6 +212,8 @@ pub struct ProxyGatewaySettings {
     pub request_log_enabled: bool,
     pub request_log_level: String,
     pub metrics_enabled: bool,
+    /// Serve Prometheus text metrics at `GET /metrics` on the gateway listener.
+    pub metrics_endpoint_enabled: bool,
     pub store_request_body: bool,
     pub store_headers: bool,
     pub store_response_body: bool,
@@ -207,6 +240,21 @@ pub struct ProxyGatewaySettings {
     pub model_base_cooldown_seconds: u64,
     pub model_max_cooldown_seconds: u64,
     pub half_open_success_required: u32,
+    pub load_balance_strategy: GatewayLoadBalanceStrategy,
+    /// Keep a session on the provider it was balanced to for this long after
+    /// its last request, so upstream prompt caches stay warm. `0` disables it.
+    pub load_balance_sticky_ttl_secs: u64,
+    /// Write sanitized request/response pairs of protocol-converted requests
+    /// into the transformer fixture layout under the gateway data directory.
+    pub fixture_capture_enabled: bool,
+    /// Comma-separated substrings matched against the requested model and the
+    /// request path. Empty captures every converted request.
+    pub fixture_capture_filter: String,
+    /// Lifetime of a response cache entry; older entries are treated as misses.
+    pub response_cache_ttl_secs: u64,
+    /// Upper bound for the on-disk response cache. The oldest entries are
+    /// evicted once a write pushes the cache over it.
+    pub response_cache_max_size_mb: u64,
 }
 
 impl Default for ProxyGatewaySettings {
@@ -221,6 +269,7 @@ impl Default for ProxyGatewaySettings {
             request_log_enabled: true,
             request_log_level: "summary".to_string(),
             metrics_enabled: true,
+            metrics_endpoint_enabled: false,
             store_request_body: false,
             store_headers: false,
             store_response_body: false,
@@ -246,6 +295,12 @@ impl Default for ProxyGatewaySettings {
             model_base_cooldown_seconds: 120,
             model_max_cooldown_seconds: 1800,
             half_open_success_required: 2,
+            load_balance_strategy: GatewayLoadBalanceStrategy::Weighted,
+            load_balance_sticky_ttl_secs: 1800,
+            fixture_capture_enabled: false,
+            fixture_capture_filter: String::new(),
+            response_cache_ttl_secs: 3600,
+            response_cache_max_size_mb: 256,
         }
     }
 }
@@ -285,6 +340,12 @@ impl ProxyGatewaySettings {
         }
     }
 
+    pub fn response_cache_enabled_for(&self, cli_key: GatewayCliKey) -> bool {
+        self.app_configs
+            .get(&cli_key)
+            .is_some_and(|config| config.response_cache_enabled == Some(true))
+    }
+
     pub fn default_cost_multiplier_for(&self, cli_key: GatewayCliKey) -> String {
         self.app_configs
             .get(&cli_key)
@@ -321,6 +382,39 @@ pub struct ModelPricing {
     pub output_cost_per_million: String,
     pub cache_read_cost_per_million: String,
     pub cache_creation_cost_per_million: String,
+    /// Long-context tiers, ascending by threshold. The flat prices above are
+    /// the base tier used until the prompt crosses the first threshold.
+    #[serde(default)]
+    pub tiers: Vec<ModelPricingTier>,
+    /// Price for the reasoning part of output tokens; reasoning is billed at
+    /// the output price when unset.
+    #[serde(default)]
+    pub reasoning_cost_per_million: Option<String>,
+    /// Fee per image the request sends to the model.
+    #[serde(default)]
+    pub image_cost: Option<String>,
+    /// Flat fee charged once per request on top of token costs.
+    #[serde(default)]
+    pub request_cost: Option<String>,
+    /// Discount on the whole request when it asks for the batch-priced
+    /// service tier (`service_tier` `flex` or `batch`).
+    #[serde(default)]
+    pub batch_discount_percent: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ModelPricingTier {
+    /// The tier applies once prompt tokens (input plus cache reads and writes)
+    /// exceed this count.
+    pub above_input_tokens: u64,
+    pub input_cost_per_million: String,
+    pub output_cost_per_million: String,
+    /// Falls back to the base cache prices when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read_cost_per_million: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_creation_cost_per_million: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -357,6 +451,95 @@ pub struct GatewayFailoverEvent {
     pub to_provider_name: Option<String>,
 }
 
+/// Window a budget rule accumulates spend over, aligned to local midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GatewayBudgetPeriod {
+    #[default]
+    Daily,
+    /// Starts on Monday.
+    Weekly,
+    Monthly,
+}
+
+/// What the gateway does with a request once a matching budget is exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GatewayBudgetAction {
+    /// Forward as usual, emit a desktop event and tag the response header.
+    #[default]
+    Warn,
+    /// Move matching providers behind the rest of the chain, cheapest first.
+    Fallback,
+    /// Answer locally with a quota error in the client's protocol.
+    Reject,
+}
+
+/// A spending cap. Empty scope fields match everything, so a rule with only
+/// `cli_key` set caps the whole CLI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GatewayBudgetRule {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    pub cli_key: Option<GatewayCliKey>,
+    pub provider_id: Option<String>,
+    pub model: Option<String>,
+    pub period: GatewayBudgetPeriod,
+    /// Decimal USD string, same format as `total_cost_usd`.
+    pub limit_usd: Option<String>,
+    pub limit_tokens: Option<u64>,
+    pub action: GatewayBudgetAction,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GatewayBudgetRuleInput {
+    /// Set to update an existing rule.
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    pub enabled: bool,
+    #[serde(default)]
+    pub cli_key: Option<GatewayCliKey>,
+    #[serde(default)]
+    pub provider_id: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    pub period: GatewayBudgetPeriod,
+    #[serde(default)]
+    pub limit_usd: Option<String>,
+    #[serde(default)]
+    pub limit_tokens: Option<u64>,
+    pub action: GatewayBudgetAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GatewayBudgetStatus {
+    pub rule: GatewayBudgetRule,
+    pub period_start: i64,
+    pub spent_usd: String,
+    pub spent_tokens: u64,
+    pub exceeded: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GatewayBudgetExceededEvent {
+    pub cli_key: GatewayCliKey,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub action: GatewayBudgetAction,
+    pub period: GatewayBudgetPeriod,
+    pub spent_usd: String,
+    pub limit_usd: Option<String>,
+    pub spent_tokens: u64,
+    pub limit_tokens: Option<u64>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct ProxyGatewayPortCheckInput {
@@ -364,6 +547,34 @@ pub struct ProxyGatewayPortCheckInput {
     pub listen_port: u16,
 }
 
+/// Gateway-issued client token as shown to the UI. Only the SHA-256 digest of
+/// the secret is persisted; `token_hint` keeps its last characters so users can
+/// tell tokens apart.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GatewayAccessToken {
+    pub id: String,
+    pub label: String,
+    pub token_hint: String,
+    pub cli_keys: Vec<GatewayCliKey>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GatewayAccessTokenInput {
+    pub label: String,
+    pub cli_keys: Vec<GatewayCliKey>,
+}
+
+/// Returned once on creation; the plaintext `secret` cannot be read back later.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GatewayAccessTokenCreated {
+    pub token: GatewayAccessToken,
+    pub secret: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct ProxyGatewayPortCheckResult {
@@ -565,6 +776,10 @@ pub struct GatewayRequestLogFilters {
     /// a terminal event to the client).
     #[serde(default)]
     pub only_failed: Option<bool>,
+    /// Restrict to requests authenticated with the gateway access token that
+    /// carries this label.
+    #[serde(default)]
+    pub client_label: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -601,6 +816,10 @@ pub struct GatewayRequestLogItem {
     pub total_cost_usd: String,
     pub is_streaming: bool,
     pub first_token_ms: Option<u64>,
+    #[serde(default)]
+    pub client_label: Option<String>,
+    #[serde(default)]
+    pub cache_hit: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -614,6 +833,9 @@ pub struct GatewayUsageSummary {
     pub total_cache_creation_tokens: u64,
     pub success_rate: f32,
     pub total_tokens: u64,
+    /// Requests answered from the gateway response cache.
+    #[serde(default)]
+    pub cache_hit_requests: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -660,6 +882,18 @@ pub struct GatewayModelStats {
     pub avg_latency_ms: u64,
 }
 
+/// Usage grouped by the label of the gateway access token a request used.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GatewayClientStats {
+    pub client_label: String,
+    pub request_count: u64,
+    pub total_tokens: u64,
+    pub total_cost_usd: String,
+    pub success_rate: f32,
+    pub avg_latency_ms: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct GatewayRequestLogSummary {
@@ -708,6 +942,9 @@ pub struct GatewayRequestLogSummary {
     pub cache_read_tokens: Option<u64>,
     #[serde(default)]
     pub cache_creation_tokens: Option<u64>,
+    /// Part of `output_tokens` the upstream reported as reasoning.
+    #[serde(default)]
+    pub reasoning_tokens: Option<u64>,
     pub total_tokens: Option<u64>,
     pub request_body_bytes: u64,
     pub response_body_bytes: u64,
@@ -719,6 +956,19 @@ pub struct GatewayRequestLogSummary {
     pub detail_file: Option<String>,
     #[serde(default)]
     pub detail_offset: Option<u64>,
+    /// Label of the gateway access token that authenticated the request;
+    /// `None` for local clients that did not present one.
+    #[serde(default)]
+    pub client_label: Option<String>,
+    /// Answered from the gateway response cache; recorded at zero cost.
+    #[serde(default)]
+    pub cache_hit: bool,
+    /// Images the client request sent, billed at the model's per-image fee.
+    #[serde(default)]
+    pub image_count: u64,
+    /// The request asked for the batch-priced service tier.
+    #[serde(default)]
+    pub batch_priced: bool,
 }
 
 /// How a streaming gateway response actually ended for the client.
@@ -817,6 +1067,70 @@ pub struct GatewayRequestLogRecord {
     pub detail: GatewayRequestLogDetail,
 }
 
+/// Manifest written next to a captured fixture pair. Fixture paths are
+/// relative to the capture root, which keeps the transformer fixture layout
+/// (`live_provider/<protocol>/<name>.*`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GatewayFixtureCapture {
+    pub schema_version: u32,
+    pub name: String,
+    pub captured_at: DateTime<Utc>,
+    pub trace_id: String,
+    pub cli_key: Option<GatewayCliKey>,
+    pub method: String,
+    pub path: String,
+    pub requested_model: Option<String>,
+    pub upstream_model_id: Option<String>,
+    pub provider_type: Option<String>,
+    pub status_code: u16,
+    pub route: ConversionRoute,
+    pub request_headers: BTreeMap<String, String>,
+    pub response_headers: BTreeMap<String, String>,
+    pub request_fixture: String,
+    pub response_fixture: String,
+    /// Body the gateway actually sent upstream, including middleware changes
+    /// that plain protocol conversion does not reproduce. Kept for reference.
+    #[serde(default)]
+    pub observed_upstream_request: Option<Value>,
+    /// Conversion output recorded at capture time; replay diffs against it.
+    #[serde(default)]
+    pub expected: Option<ConversionReplayOutput>,
+    #[serde(default)]
+    pub expected_error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GatewayFixtureCaptureSummary {
+    pub name: String,
+    pub manifest_path: String,
+    pub captured_at: DateTime<Utc>,
+    pub cli_key: Option<GatewayCliKey>,
+    pub path: String,
+    pub requested_model: Option<String>,
+    pub route: ConversionRoute,
+    pub status_code: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GatewayFixtureReplayReport {
+    pub name: String,
+    pub route: ConversionRoute,
+    pub passed: bool,
+    pub differences: Vec<JsonDifference>,
+    pub output: Option<ConversionReplayOutput>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GatewayResponseCacheStats {
+    pub entry_count: u64,
+    pub total_bytes: u64,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum GatewayModelHealthScope {
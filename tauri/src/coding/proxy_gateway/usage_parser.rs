@@ -9,6 +9,8 @@ pub struct TokenUsage {
     pub output_tokens: Option<u64>,
     pub cache_read_tokens: Option<u64>,
     pub cache_creation_tokens: Option<u64>,
+    /// Part of `output_tokens` the upstream reported as reasoning.
+    pub reasoning_tokens: Option<u64>,
     /// Upstream envelope id when available (Claude message id, OpenAI/Codex response id,
     /// Gemini responseId). Used to build stable usage request keys.
     pub envelope_id: Option<String>,
@@ -33,12 +35,72 @@ impl TokenUsage {
         self.cache_read_tokens = max_option(self.cache_read_tokens, other.cache_read_tokens);
         self.cache_creation_tokens =
             max_option(self.cache_creation_tokens, other.cache_creation_tokens);
+        self.reasoning_tokens = max_option(self.reasoning_tokens, other.reasoning_tokens);
         if self.envelope_id.is_none() {
             self.envelope_id = other.envelope_id;
         }
     }
 }
 
+/// Billable traits of a client request that the response usage does not carry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestBillingTraits {
+    /// Images the request sends to the model, in any client protocol shape.
+    pub image_count: u64,
+    /// The request asked for the batch-priced service tier (`flex`/`batch`).
+    pub batch: bool,
+}
+
+pub fn request_billing_traits(body: &[u8]) -> RequestBillingTraits {
+    // Most requests carry neither, so skip parsing the body for them.
+    if !contains_bytes(body, b"image") && !contains_bytes(body, b"service_tier") {
+        return RequestBillingTraits::default();
+    }
+    let Ok(value) = serde_json::from_slice::<Value>(body) else {
+        return RequestBillingTraits::default();
+    };
+    RequestBillingTraits {
+        image_count: count_request_images(&value),
+        batch: value
+            .get("service_tier")
+            .and_then(Value::as_str)
+            .map(|tier| tier.trim().to_ascii_lowercase())
+            .is_some_and(|tier| matches!(tier.as_str(), "flex" | "batch")),
+    }
+}
+
+/// Counts Anthropic `image`, OpenAI `image_url`/`input_image` and Gemini
+/// inline or file image parts anywhere in the request, history included.
+fn count_request_images(value: &Value) -> u64 {
+    match value {
+        Value::Array(items) => items.iter().map(count_request_images).sum(),
+        Value::Object(map) => {
+            let typed_image = map
+                .get("type")
+                .and_then(Value::as_str)
+                .is_some_and(|kind| matches!(kind, "image" | "image_url" | "input_image"));
+            let gemini_image = ["inlineData", "inline_data", "fileData", "file_data"]
+                .iter()
+                .filter_map(|key| map.get(*key))
+                .filter_map(|data| data.get("mimeType").or_else(|| data.get("mime_type")))
+                .filter_map(Value::as_str)
+                .any(|mime_type| mime_type.starts_with("image/"));
+            if typed_image || gemini_image {
+                1
+            } else {
+                map.values().map(count_request_images).sum()
+            }
+        }
+        _ => 0,
+    }
+}
+
+fn contains_bytes(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
 /// Build a stable usage `request_id` from an upstream envelope id.
 ///
 /// Claude keeps bare `SESSION:{id}` so proxy rows converge with session JSONL import.
@@ -249,9 +311,7 @@ fn classify_response_status(status: &str) -> Option<SseTerminalKind> {
 /// under `response`). Mirrors the `value.error` / `response.error` arms of
 /// `upstream.rs` `gateway_json_reports_error`.
 fn json_carries_error(value: &Value) -> bool {
-    value
-        .get("error")
-        .is_some_and(|error| !error.is_null())
+    value.get("error").is_some_and(|error| !error.is_null())
         || value
             .get("response")
             .and_then(|response| response.get("error"))
@@ -546,6 +606,7 @@ fn claude_usage(value: &Value, provider_type: Option<&str>) -> TokenUsage {
             }),
         cache_read_tokens,
         cache_creation_tokens,
+        reasoning_tokens: None,
         envelope_id: extract_envelope_id(GatewayCliKey::Claude, value),
     }
 }
@@ -661,6 +722,13 @@ fn openai_usage(value: &Value) -> TokenUsage {
             }),
         cache_read_tokens,
         cache_creation_tokens,
+        reasoning_tokens: first_u64_at_paths(
+            usage,
+            &[
+                "/output_tokens_details/reasoning_tokens",
+                "/completion_tokens_details/reasoning_tokens",
+            ],
+        ),
         envelope_id: extract_envelope_id(GatewayCliKey::Codex, value),
     }
 }
@@ -695,6 +763,22 @@ fn gemini_usage(value: &Value) -> TokenUsage {
         )
     });
 
+    // Gemini reports thinking apart from candidates but bills it as output,
+    // matching how the transformer folds it into completion tokens.
+    let reasoning_tokens = first_u64_at_paths(usage, &["/thoughtsTokenCount"]).or_else(|| {
+        first_u64_at_paths(
+            value,
+            &[
+                "/usageMetadata/thoughtsTokenCount",
+                "/response/usageMetadata/thoughtsTokenCount",
+            ],
+        )
+    });
+    let output_tokens = match (output_tokens, reasoning_tokens) {
+        (Some(output), Some(reasoning)) => Some(output.saturating_add(reasoning)),
+        (output, reasoning) => output.or(reasoning),
+    };
+
     let cache_read_tokens =
         first_u64_at_paths(usage, &["/cachedContentTokenCount", "/cache_read_tokens"]).or_else(
             || {
@@ -713,6 +797,7 @@ fn gemini_usage(value: &Value) -> TokenUsage {
         output_tokens,
         cache_read_tokens,
         cache_creation_tokens: None,
+        reasoning_tokens,
         envelope_id: extract_envelope_id(GatewayCliKey::Gemini, value),
     }
 }
@@ -986,6 +1071,66 @@ data: [DONE]
         assert_eq!(usage.total_tokens(), Some(17));
     }
 
+    #[test]
+    fn parses_reasoning_tokens_from_openai_and_gemini_usage() {
+        let chat = from_response_body(
+            GatewayCliKey::Codex,
+            br#"{"id":"chatcmpl_1","usage":{"prompt_tokens":20,"completion_tokens":50,"completion_tokens_details":{"reasoning_tokens":30}}}"#,
+        );
+        assert_eq!(chat.output_tokens, Some(50));
+        assert_eq!(chat.reasoning_tokens, Some(30));
+
+        let responses = from_response_body(
+            GatewayCliKey::Codex,
+            br#"{"id":"resp_1","usage":{"input_tokens":20,"output_tokens":40,"output_tokens_details":{"reasoning_tokens":25}}}"#,
+        );
+        assert_eq!(responses.reasoning_tokens, Some(25));
+
+        let gemini = from_response_body(
+            GatewayCliKey::Gemini,
+            br#"{"usageMetadata":{"promptTokenCount":10,"candidatesTokenCount":7,"thoughtsTokenCount":5}}"#,
+        );
+        assert_eq!(gemini.output_tokens, Some(12));
+        assert_eq!(gemini.reasoning_tokens, Some(5));
+    }
+
+    #[test]
+    fn request_billing_traits_count_images_and_detect_batch_tier() {
+        let anthropic = br#"{"model":"claude","messages":[{"role":"user","content":[
+            {"type":"image","source":{"type":"base64","media_type":"image/png","data":"AA"}},
+            {"type":"tool_result","tool_use_id":"t1","content":[{"type":"image","source":{"type":"url","url":"https://x"}}]},
+            {"type":"text","text":"describe the image"}]}]}"#;
+        assert_eq!(
+            request_billing_traits(anthropic),
+            RequestBillingTraits {
+                image_count: 2,
+                batch: false
+            }
+        );
+
+        let openai =
+            br#"{"model":"gpt","service_tier":"flex","messages":[{"role":"user","content":[
+            {"type":"image_url","image_url":{"url":"data:image/png;base64,AA"}}]}]}"#;
+        assert_eq!(
+            request_billing_traits(openai),
+            RequestBillingTraits {
+                image_count: 1,
+                batch: true
+            }
+        );
+
+        let gemini = br#"{"contents":[{"role":"user","parts":[
+            {"inlineData":{"mimeType":"image/jpeg","data":"AA"}},
+            {"inlineData":{"mimeType":"application/pdf","data":"AA"}},
+            {"fileData":{"mimeType":"image/png","fileUri":"gs://x"}}]}]}"#;
+        assert_eq!(request_billing_traits(gemini).image_count, 2);
+
+        assert_eq!(
+            request_billing_traits(br#"{"service_tier":"default","input":"hi"}"#),
+            RequestBillingTraits::default()
+        );
+    }
+
     #[test]
     fn gemini_usage_does_not_infer_output_from_total_tokens() {
         let usage = from_response_body(
@@ -1290,9 +1435,7 @@ data: [DONE]
     #[test]
     fn sse_block_classify_terminal_nested_response_status_incomplete_is_incomplete() {
         assert_eq!(
-            sse_block_classify_terminal(
-                b"data: {\"response\":{\"status\":\"incomplete\"}}\n\n"
-            ),
+            sse_block_classify_terminal(b"data: {\"response\":{\"status\":\"incomplete\"}}\n\n"),
             Some(SseTerminalKind::Incomplete)
         );
     }
@@ -1,10 +1,13 @@
+use super::pricing;
 use super::request_log;
 use super::types::{
-    normalize_pricing_model_source, GatewayCliKey, GatewayModelStats, GatewayPaginatedRequestLogs,
-    GatewayProviderStats, GatewayRequestLogDetail, GatewayRequestLogFilters, GatewayRequestLogItem,
-    GatewayRequestLogSummary, GatewayStreamOutcome, GatewayUsageSummary, GatewayUsageSummaryByCli,
-    GatewayUsageTrendPoint, ProxyGatewaySettings,
+    normalize_pricing_model_source, GatewayCliKey, GatewayClientStats, GatewayModelStats,
+    GatewayPaginatedRequestLogs, GatewayProviderStats, GatewayRequestLogDetail,
+    GatewayRequestLogFilters, GatewayRequestLogItem, GatewayRequestLogSummary,
+    GatewayStreamOutcome, GatewayUsageSummary, GatewayUsageSummaryByCli, GatewayUsageTrendPoint,
+    ProxyGatewaySettings,
 };
+use super::usage_parser::{RequestBillingTraits, TokenUsage};
 use crate::db::SqliteDbState;
 use chrono::{Duration, Local, TimeZone, Utc};
 use rusqlite::{Connection, OptionalExtension, ToSql};
@@ -81,6 +84,7 @@ struct SummaryAccumulator {
     output_tokens: u64,
     cache_read_tokens: u64,
     cache_creation_tokens: u64,
+    cache_hit_requests: u64,
 }
 
 impl SummaryAccumulator {
@@ -96,6 +100,9 @@ impl SummaryAccumulator {
         self.cache_creation_tokens = self
             .cache_creation_tokens
             .saturating_add(other.cache_creation_tokens);
+        self.cache_hit_requests = self
+            .cache_hit_requests
+            .saturating_add(other.cache_hit_requests);
     }
 
     fn total_tokens(&self) -> u64 {
@@ -116,6 +123,7 @@ impl SummaryAccumulator {
             total_cache_creation_tokens: self.cache_creation_tokens,
             success_rate,
             total_tokens: self.total_tokens(),
+            cache_hit_requests: self.cache_hit_requests,
         }
     }
 }
@@ -126,6 +134,49 @@ struct ModelPricing {
     output_cost_per_million: Decimal,
     cache_read_cost_per_million: Decimal,
     cache_creation_cost_per_million: Decimal,
+    /// Sorted by ascending `above_input_tokens`.
+    tiers: Vec<PricingTier>,
+    /// Rate for reasoning output tokens; they use the output rate when unset.
+    reasoning_cost_per_million: Option<Decimal>,
+    image_cost: Decimal,
+    request_cost: Decimal,
+    batch_discount_percent: Decimal,
+}
+
+#[derive(Debug, Clone)]
+struct PricingTier {
+    above_input_tokens: u64,
+    input_cost_per_million: Decimal,
+    output_cost_per_million: Decimal,
+    cache_read_cost_per_million: Decimal,
+    cache_creation_cost_per_million: Decimal,
+}
+
+impl ModelPricing {
+    /// Per-million token rates for a request whose prompt (fresh input plus
+    /// cache reads and writes) is `prompt_tokens` long. Providers bill the
+    /// whole request at the long-context rate once the prompt crosses a tier.
+    fn rates_for_prompt(&self, prompt_tokens: u64) -> (Decimal, Decimal, Decimal, Decimal) {
+        match self
+            .tiers
+            .iter()
+            .rev()
+            .find(|tier| prompt_tokens > tier.above_input_tokens)
+        {
+            Some(tier) => (
+                tier.input_cost_per_million,
+                tier.output_cost_per_million,
+                tier.cache_read_cost_per_million,
+                tier.cache_creation_cost_per_million,
+            ),
+            None => (
+                self.input_cost_per_million,
+                self.output_cost_per_million,
+                self.cache_read_cost_per_million,
+                self.cache_creation_cost_per_million,
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -134,6 +185,10 @@ struct CostBreakdown {
     output_cost_usd: Decimal,
     cache_read_cost_usd: Decimal,
     cache_creation_cost_usd: Decimal,
+    /// Per-image and per-request fees; only reflected in the total since the
+    /// log has no dedicated columns for them.
+    image_cost_usd: Decimal,
+    request_cost_usd: Decimal,
 }
 
 impl CostBreakdown {
@@ -142,6 +197,8 @@ impl CostBreakdown {
             + self.output_cost_usd
             + self.cache_read_cost_usd
             + self.cache_creation_cost_usd
+            + self.image_cost_usd
+            + self.request_cost_usd
     }
 
     fn apply_multiplier(mut self, multiplier: Decimal) -> Self {
@@ -149,6 +206,8 @@ impl CostBreakdown {
         self.output_cost_usd *= multiplier;
         self.cache_read_cost_usd *= multiplier;
         self.cache_creation_cost_usd *= multiplier;
+        self.image_cost_usd *= multiplier;
+        self.request_cost_usd *= multiplier;
         self
     }
 }
@@ -226,7 +285,12 @@ pub fn record_request_summary(
         let cache_creation_tokens = summary.cache_creation_tokens.unwrap_or(0) as i64;
         let first_token_ms = summary.first_token_ms.map(|value| value as i64);
         let latency_ms = first_token_ms.unwrap_or(summary.duration_ms as i64);
-        let pricing = find_summary_model_pricing(conn, summary, upstream_model);
+        let pricing = find_request_model_pricing(
+            conn,
+            summary.pricing_model_source.as_deref(),
+            summary.requested_model.as_deref(),
+            upstream_model,
+        );
         let cost_multiplier = parse_decimal_or_default(
             summary.cost_multiplier.as_deref().unwrap_or("1.0"),
             Decimal::new(1, 0),
@@ -240,17 +304,24 @@ pub fn record_request_summary(
         let route_name = optional_compact_string(&summary.route_name);
         let method = optional_compact_string(&summary.method);
         let path = optional_compact_string(&request_log::redact_request_path(&summary.path));
+        // Cache hits never reached upstream, so they are free regardless of tokens.
         let costs = pricing
             .as_ref()
+            .filter(|_| !summary.cache_hit)
             .map(|pricing| {
-                calculate_cost(
-                    input_tokens as u64,
-                    output_tokens as u64,
-                    cache_read_tokens as u64,
-                    cache_creation_tokens as u64,
-                    pricing,
-                )
-                .apply_multiplier(cost_multiplier)
+                let usage = TokenUsage {
+                    input_tokens: Some(input_tokens as u64),
+                    output_tokens: Some(output_tokens as u64),
+                    cache_read_tokens: Some(cache_read_tokens as u64),
+                    cache_creation_tokens: Some(cache_creation_tokens as u64),
+                    reasoning_tokens: summary.reasoning_tokens,
+                    envelope_id: None,
+                };
+                let billing = RequestBillingTraits {
+                    image_count: summary.image_count,
+                    batch: summary.batch_priced,
+                };
+                calculate_cost(&usage, billing, pricing).apply_multiplier(cost_multiplier)
             })
             .unwrap_or_default();
 
@@ -339,7 +410,8 @@ pub fn record_request_summary(
                 status_code, error_message, session_id, provider_type, is_streaming,
                 cost_multiplier, pricing_model_source, created_at, data_source, detail_file,
                 detail_offset, route_name, method, path, upstream_status_code,
-                stream_outcome, error_category, attempt_count, total_attempt_count
+                stream_outcome, error_category, attempt_count, total_attempt_count,
+                client_label, cache_hit
             ) VALUES (
                 ?1, ?2, ?3, ?4, ?5,
                 ?6, ?7, ?8, ?9,
@@ -347,7 +419,7 @@ pub fn record_request_summary(
                 ?14, ?15, ?16, ?17,
                 ?18, ?19, ?20, ?21, ?22,
                 ?23, ?24, ?25, 'proxy', ?26, ?27, ?28, ?29, ?30, ?31,
-                ?32, ?33, ?34, ?35
+                ?32, ?33, ?34, ?35, ?36, ?37
             )"
         );
         let affected_rows = conn
@@ -389,6 +461,8 @@ pub fn record_request_summary(
                     summary.error_category,
                     i64::from(summary.attempt_count.max(1)),
                     i64::from(summary.total_attempt_count.max(1)),
+                    summary.client_label,
+                    i64::from(summary.cache_hit),
                 ],
             )
             .map_err(|error| format!("Failed to record proxy gateway request summary: {error}"))?;
@@ -662,7 +736,7 @@ pub fn request_logs(
                     input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens,
                     total_cost_usd, latency_ms, first_token_ms, duration_ms,
                     status_code, error_message, created_at, is_streaming,
-                    route_name, method, path, stream_outcome
+                    route_name, method, path, stream_outcome, client_label, cache_hit
              FROM proxy_request_logs l
              {where_clause}
              ORDER BY created_at DESC
@@ -721,6 +795,8 @@ pub fn request_logs(
                     first_token_ms: row
                         .get::<_, Option<i64>>(11)?
                         .map(|value| value.max(0) as u64),
+                    client_label: row.get(21)?,
+                    cache_hit: row.get::<_, i64>(22)? != 0,
                 }))
             })
             .map_err(|error| format!("Failed to query proxy gateway request logs: {error}"))?;
@@ -768,7 +844,8 @@ pub fn usage_summary(
                             COALESCE(SUM(output_tokens), 0),
                             COALESCE(SUM(cache_read_tokens), 0),
                             COALESCE(SUM(cache_creation_tokens), 0),
-                            COALESCE(SUM(CASE WHEN (l.stream_outcome = 'completed' OR (l.stream_outcome IS NULL AND l.status_code >= 200 AND l.status_code < 400)) THEN 1 ELSE 0 END), 0)
+                            COALESCE(SUM(CASE WHEN (l.stream_outcome = 'completed' OR (l.stream_outcome IS NULL AND l.status_code >= 200 AND l.status_code < 400)) THEN 1 ELSE 0 END), 0),
+                            COALESCE(SUM(l.cache_hit), 0)
                      FROM proxy_request_logs l {detail_where}"
                 ),
                 refs.as_slice(),
@@ -1030,6 +1107,129 @@ pub fn model_stats(
     })
 }
 
+/// Usage per gateway access token label. Rolled-up history does not keep the
+/// label, so this only covers requests still inside the detail retention window.
+pub fn client_stats(
+    db: &SqliteDbState,
+    start_date: Option<i64>,
+    end_date: Option<i64>,
+    cli_key: Option<GatewayCliKey>,
+) -> Result<Vec<GatewayClientStats>, String> {
+    db.with_conn(|conn| {
+        let mut params = Vec::<Box<dyn ToSql>>::new();
+        let mut where_clause =
+            build_usage_stats_where(start_date, end_date, cli_key, "l", true, &mut params);
+        append_static_where_condition(&mut where_clause, "l.client_label IS NOT NULL");
+        let refs = to_param_refs(&params);
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT client_label,
+                        COUNT(*),
+                        COALESCE(SUM(input_tokens + output_tokens + cache_read_tokens + cache_creation_tokens), 0),
+                        COALESCE(SUM(CAST(total_cost_usd AS REAL)), 0),
+                        COALESCE(SUM(CASE WHEN (l.stream_outcome = 'completed' OR (l.stream_outcome IS NULL AND l.status_code >= 200 AND l.status_code < 400)) THEN 1 ELSE 0 END), 0),
+                        COALESCE(AVG(latency_ms), 0)
+                 FROM proxy_request_logs l
+                 {where_clause}
+                 GROUP BY client_label
+                 ORDER BY 2 DESC"
+            ))
+            .map_err(|error| format!("Failed to prepare client stats query: {error}"))?;
+        let rows = stmt
+            .query_map(refs.as_slice(), |row| {
+                let request_count = row.get::<_, i64>(1)?.max(0) as u64;
+                let success_count = row.get::<_, i64>(4)?.max(0) as u64;
+                Ok(GatewayClientStats {
+                    client_label: row.get(0)?,
+                    request_count,
+                    total_tokens: row.get::<_, i64>(2)?.max(0) as u64,
+                    total_cost_usd: format_decimal_cost(row_decimal(row, 3)?),
+                    success_rate: percent(success_count, request_count),
+                    avg_latency_ms: row.get::<_, f64>(5)?.max(0.0).round() as u64,
+                })
+            })
+            .map_err(|error| format!("Failed to query client stats: {error}"))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|error| format!("Failed to read gateway client stats row: {error}"))
+    })
+}
+
+/// Cost and total tokens since `since` for a budget scope, combining detail
+/// rows with `usage_daily_rollups` for days that were already rolled up.
+pub fn budget_usage(
+    conn: &Connection,
+    since: i64,
+    cli_key: Option<GatewayCliKey>,
+    provider_id: Option<&str>,
+    model: Option<&str>,
+) -> Result<(Decimal, u64), String> {
+    let mut params = Vec::<Box<dyn ToSql>>::new();
+    let mut conditions = build_stats_conditions(Some(since), None, cli_key, "l", &mut params);
+    conditions.push(usage_applicable_detail_condition("l", true));
+    if let Some(provider_id) = provider_id {
+        push_condition(
+            &mut conditions,
+            &mut params,
+            "l.provider_id",
+            provider_id.to_string(),
+        );
+    }
+    if let Some(model) = model {
+        conditions.push(format!(
+            "(LOWER(l.model) = LOWER(?{index}) OR LOWER(l.request_model) = LOWER(?{index}))",
+            index = params.len() + 1
+        ));
+        params.push(Box::new(model.to_string()));
+    }
+    let where_clause = format_where_clause(conditions);
+    let refs = to_param_refs(&params);
+    let (detail_cost, detail_tokens) = conn
+        .query_row(
+            &format!(
+                "SELECT COALESCE(SUM(CAST(total_cost_usd AS REAL)), 0),
+                        COALESCE(SUM(input_tokens + output_tokens + cache_read_tokens + cache_creation_tokens), 0)
+                 FROM proxy_request_logs l {where_clause}"
+            ),
+            refs.as_slice(),
+            |row| Ok((row_decimal(row, 0)?, row.get::<_, i64>(1)?.max(0) as u64)),
+        )
+        .map_err(|error| format!("Failed to query gateway budget usage: {error}"))?;
+
+    let mut params = Vec::<Box<dyn ToSql>>::new();
+    let mut where_clause = build_rollup_where(Some(since), None, cli_key, Some("r"), &mut params);
+    if let Some(provider_id) = provider_id {
+        append_static_where_condition(
+            &mut where_clause,
+            &format!("r.provider_id = ?{}", params.len() + 1),
+        );
+        params.push(Box::new(provider_id.to_string()));
+    }
+    if let Some(model) = model {
+        append_static_where_condition(
+            &mut where_clause,
+            &format!("LOWER(r.model) = LOWER(?{})", params.len() + 1),
+        );
+        params.push(Box::new(model.to_string()));
+    }
+    let refs = to_param_refs(&params);
+    let (rollup_cost, rollup_tokens) = conn
+        .query_row(
+            &format!(
+                "SELECT COALESCE(SUM(CAST(r.total_cost_usd AS REAL)), 0),
+                        COALESCE(SUM(r.input_tokens + r.output_tokens + r.cache_read_tokens + r.cache_creation_tokens), 0)
+                 FROM usage_daily_rollups r {where_clause}"
+            ),
+            refs.as_slice(),
+            |row| Ok((row_decimal(row, 0)?, row.get::<_, i64>(1)?.max(0) as u64)),
+        )
+        .map_err(|error| format!("Failed to query gateway budget rollups: {error}"))?;
+
+    Ok((
+        detail_cost + rollup_cost,
+        detail_tokens.saturating_add(rollup_tokens),
+    ))
+}
+
 pub fn data_source_breakdown(
     db: &SqliteDbState,
     input: super::types::DataSourceBreakdownInput,
@@ -1255,7 +1455,8 @@ fn rollup_summary(
                     COALESCE(SUM(output_tokens), 0),
                     COALESCE(SUM(cache_read_tokens), 0),
                     COALESCE(SUM(cache_creation_tokens), 0),
-                    COALESCE(SUM(success_count), 0)
+                    COALESCE(SUM(success_count), 0),
+                    COALESCE(SUM(cache_hit_count), 0)
              FROM usage_daily_rollups {where_clause}"
         ),
         refs.as_slice(),
@@ -1271,6 +1472,7 @@ fn row_to_summary_accumulator(row: &rusqlite::Row<'_>) -> rusqlite::Result<Summa
     let cache_read = row.get::<_, i64>(4)?.max(0) as u64;
     let cache_creation = row.get::<_, i64>(5)?.max(0) as u64;
     let success_count = row.get::<_, i64>(6)?.max(0) as u64;
+    let cache_hit_requests = row.get::<_, i64>(7)?.max(0) as u64;
     Ok(SummaryAccumulator {
         total_requests,
         success_count,
@@ -1279,6 +1481,7 @@ fn row_to_summary_accumulator(row: &rusqlite::Row<'_>) -> rusqlite::Result<Summa
         output_tokens: output,
         cache_read_tokens: cache_read,
         cache_creation_tokens: cache_creation,
+        cache_hit_requests,
     })
 }
 
@@ -1305,7 +1508,7 @@ fn rollup_and_prune(conn: &Connection, retain_days: i64) -> Result<(), String> {
             "INSERT OR REPLACE INTO usage_daily_rollups
             (date, app_type, provider_id, model, request_count, success_count,
              input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens,
-             total_cost_usd, avg_latency_ms)
+             total_cost_usd, avg_latency_ms, cache_hit_count)
          SELECT
             agg.d, agg.app_type, agg.provider_id, agg.rollup_model,
             COALESCE(old.request_count, 0) + agg.request_count,
@@ -1319,7 +1522,8 @@ fn rollup_and_prune(conn: &Connection, retain_days: i64) -> Result<(), String> {
                 THEN (COALESCE(old.avg_latency_ms, 0) * COALESCE(old.request_count, 0)
                       + agg.avg_latency_ms * agg.request_count)
                      / (COALESCE(old.request_count, 0) + agg.request_count)
-                ELSE 0 END
+                ELSE 0 END,
+            COALESCE(old.cache_hit_count, 0) + agg.cache_hit_count
          FROM (
             SELECT date(l.created_at, 'unixepoch', 'localtime') AS d,
                    l.app_type,
@@ -1332,7 +1536,8 @@ fn rollup_and_prune(conn: &Connection, retain_days: i64) -> Result<(), String> {
                    COALESCE(SUM(l.cache_read_tokens), 0) AS cache_read_tokens,
                    COALESCE(SUM(l.cache_creation_tokens), 0) AS cache_creation_tokens,
                    COALESCE(SUM(CAST(l.total_cost_usd AS REAL)), 0) AS total_cost,
-                   COALESCE(AVG(l.latency_ms), 0) AS avg_latency_ms
+                   COALESCE(AVG(l.latency_ms), 0) AS avg_latency_ms,
+                   COALESCE(SUM(l.cache_hit), 0) AS cache_hit_count
             FROM proxy_request_logs l
             WHERE l.created_at < ?1
               AND {usage_condition}
@@ -1465,6 +1670,19 @@ fn build_detail_where(
         }
         conditions.push(format!("({})", parts.join(" OR ")));
     }
+    if let Some(client_label) = filters
+        .client_label
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        push_condition(
+            &mut conditions,
+            params,
+            "l.client_label",
+            client_label.to_string(),
+        );
+    }
     if filters.exclude_model_list.unwrap_or(false) {
         conditions.push(format!("NOT {}", model_list_request_sql_condition("l")));
     }
@@ -1796,21 +2014,84 @@ fn to_param_refs(params: &[Box<dyn ToSql>]) -> Vec<&dyn ToSql> {
     params.iter().map(|param| param.as_ref()).collect()
 }
 
+/// Model and token usage of one finished request, priced the same way
+/// `record_request_summary` prices the stored row.
+pub struct RequestCostInput<'a> {
+    pub requested_model: Option<&'a str>,
+    pub upstream_model: &'a str,
+    pub pricing_model_source: Option<&'a str>,
+    pub cost_multiplier: Option<&'a str>,
+    pub usage: &'a TokenUsage,
+    pub billing: RequestBillingTraits,
+}
+
+/// Cost of a single request in USD, or zero when the model has no pricing.
+pub fn request_cost_usd(conn: &Connection, input: &RequestCostInput<'_>) -> Decimal {
+    let Some(pricing) = find_request_model_pricing(
+        conn,
+        input.pricing_model_source,
+        input.requested_model,
+        input.upstream_model,
+    ) else {
+        return Decimal::ZERO;
+    };
+    let cost_multiplier =
+        parse_decimal_or_default(input.cost_multiplier.unwrap_or("1.0"), Decimal::new(1, 0));
+    calculate_cost(input.usage, input.billing, &pricing)
+        .apply_multiplier(cost_multiplier)
+        .total()
+}
+
+/// Gateway pricing for one model, resolved once so callers pricing many usage
+/// records of the same model (such as every turn of a CLI session file) skip
+/// the candidate lookups per record.
+pub struct ResolvedModelPricing(ModelPricing);
+
+impl ResolvedModelPricing {
+    pub fn lookup(conn: &Connection, model_id: &str) -> Option<Self> {
+        find_model_pricing(conn, model_id).map(Self)
+    }
+
+    pub fn cost_usd(&self, usage: &TokenUsage) -> Decimal {
+        calculate_cost(usage, RequestBillingTraits::default(), &self.0).total()
+    }
+}
+
 fn calculate_cost(
-    input_tokens: u64,
-    output_tokens: u64,
-    cache_read_tokens: u64,
-    cache_creation_tokens: u64,
+    usage: &TokenUsage,
+    billing: RequestBillingTraits,
     pricing: &ModelPricing,
 ) -> CostBreakdown {
-    CostBreakdown {
-        input_cost_usd: token_cost(input_tokens, pricing.input_cost_per_million),
-        output_cost_usd: token_cost(output_tokens, pricing.output_cost_per_million),
-        cache_read_cost_usd: token_cost(cache_read_tokens, pricing.cache_read_cost_per_million),
-        cache_creation_cost_usd: token_cost(
-            cache_creation_tokens,
-            pricing.cache_creation_cost_per_million,
-        ),
+    let input_tokens = usage.input_tokens.unwrap_or(0);
+    let output_tokens = usage.output_tokens.unwrap_or(0);
+    let cache_read_tokens = usage.cache_read_tokens.unwrap_or(0);
+    let cache_creation_tokens = usage.cache_creation_tokens.unwrap_or(0);
+    let prompt_tokens = input_tokens
+        .saturating_add(cache_read_tokens)
+        .saturating_add(cache_creation_tokens);
+    let (input_rate, output_rate, cache_read_rate, cache_creation_rate) =
+        pricing.rates_for_prompt(prompt_tokens);
+    let output_cost_usd = match pricing.reasoning_cost_per_million {
+        Some(reasoning_rate) => {
+            let reasoning_tokens = usage.reasoning_tokens.unwrap_or(0).min(output_tokens);
+            token_cost(output_tokens - reasoning_tokens, output_rate)
+                + token_cost(reasoning_tokens, reasoning_rate)
+        }
+        None => token_cost(output_tokens, output_rate),
+    };
+    let costs = CostBreakdown {
+        input_cost_usd: token_cost(input_tokens, input_rate),
+        output_cost_usd,
+        cache_read_cost_usd: token_cost(cache_read_tokens, cache_read_rate),
+        cache_creation_cost_usd: token_cost(cache_creation_tokens, cache_creation_rate),
+        image_cost_usd: Decimal::from(billing.image_count) * pricing.image_cost,
+        request_cost_usd: pricing.request_cost,
+    };
+    if billing.batch && pricing.batch_discount_percent > Decimal::ZERO {
+        // The batch tier discounts every component of the request alike.
+        costs.apply_multiplier(Decimal::ONE - pricing.batch_discount_percent / Decimal::ONE_HUNDRED)
+    } else {
+        costs
     }
 }
 
@@ -1853,18 +2134,16 @@ fn find_model_pricing(conn: &Connection, model_id: &str) -> Option<ModelPricing>
     None
 }
 
-fn find_summary_model_pricing(
+fn find_request_model_pricing(
     conn: &Connection,
-    summary: &GatewayRequestLogSummary,
+    pricing_model_source: Option<&str>,
+    requested_model: Option<&str>,
     upstream_model: &str,
 ) -> Option<ModelPricing> {
-    let pricing_source = summary
-        .pricing_model_source
-        .as_deref()
+    let pricing_source = pricing_model_source
         .unwrap_or("upstream")
         .trim()
         .to_ascii_lowercase();
-    let requested_model = summary.requested_model.as_deref();
     let candidates = if matches!(pricing_source.as_str(), "request" | "requested") {
         [requested_model, Some(upstream_model)]
     } else {
@@ -1879,7 +2158,9 @@ fn find_summary_model_pricing(
 fn query_model_pricing_exact(conn: &Connection, model_id: &str) -> Option<ModelPricing> {
     conn.query_row(
         "SELECT input_cost_per_million, output_cost_per_million,
-                cache_read_cost_per_million, cache_creation_cost_per_million
+                cache_read_cost_per_million, cache_creation_cost_per_million,
+                pricing_tiers, request_cost, reasoning_cost_per_million,
+                image_cost, batch_discount_percent
          FROM model_pricing
          WHERE LOWER(model_id) = LOWER(?1)
          LIMIT 1",
@@ -1895,7 +2176,9 @@ fn query_model_pricing_prefix(conn: &Connection, model_id: &str) -> Option<Model
     let like_pattern = format!("{}-%", model_id.to_ascii_lowercase());
     conn.query_row(
         "SELECT input_cost_per_million, output_cost_per_million,
-                cache_read_cost_per_million, cache_creation_cost_per_million
+                cache_read_cost_per_million, cache_creation_cost_per_million,
+                pricing_tiers, request_cost, reasoning_cost_per_million,
+                image_cost, batch_discount_percent
          FROM model_pricing
          WHERE LOWER(model_id) LIKE ?1
          ORDER BY LENGTH(model_id) ASC
@@ -1909,20 +2192,61 @@ fn query_model_pricing_prefix(conn: &Connection, model_id: &str) -> Option<Model
 }
 
 fn row_to_model_pricing(row: &rusqlite::Row<'_>) -> rusqlite::Result<ModelPricing> {
+    let cache_read_cost_per_million =
+        parse_decimal_or_default(&row.get::<_, String>(2)?, Decimal::ZERO);
+    let cache_creation_cost_per_million =
+        parse_decimal_or_default(&row.get::<_, String>(3)?, Decimal::ZERO);
+    let tiers = pricing::parse_pricing_tiers(row.get::<_, Option<String>>(4)?.as_deref())
+        .into_iter()
+        .map(|tier| PricingTier {
+            above_input_tokens: tier.above_input_tokens,
+            input_cost_per_million: parse_decimal_or_default(
+                &tier.input_cost_per_million,
+                Decimal::ZERO,
+            ),
+            output_cost_per_million: parse_decimal_or_default(
+                &tier.output_cost_per_million,
+                Decimal::ZERO,
+            ),
+            cache_read_cost_per_million: tier
+                .cache_read_cost_per_million
+                .as_deref()
+                .map_or(cache_read_cost_per_million, |value| {
+                    parse_decimal_or_default(value, Decimal::ZERO)
+                }),
+            cache_creation_cost_per_million: tier
+                .cache_creation_cost_per_million
+                .as_deref()
+                .map_or(cache_creation_cost_per_million, |value| {
+                    parse_decimal_or_default(value, Decimal::ZERO)
+                }),
+        })
+        .collect();
     Ok(ModelPricing {
         input_cost_per_million: parse_decimal_or_default(&row.get::<_, String>(0)?, Decimal::ZERO),
         output_cost_per_million: parse_decimal_or_default(&row.get::<_, String>(1)?, Decimal::ZERO),
-        cache_read_cost_per_million: parse_decimal_or_default(
-            &row.get::<_, String>(2)?,
-            Decimal::ZERO,
-        ),
-        cache_creation_cost_per_million: parse_decimal_or_default(
-            &row.get::<_, String>(3)?,
-            Decimal::ZERO,
-        ),
+        cache_read_cost_per_million,
+        cache_creation_cost_per_million,
+        tiers,
+        reasoning_cost_per_million: row
+            .get::<_, Option<String>>(6)?
+            .as_deref()
+            .and_then(|value| Decimal::from_str(value.trim()).ok()),
+        image_cost: optional_row_decimal(row, 7)?,
+        request_cost: optional_row_decimal(row, 5)?,
+        batch_discount_percent: optional_row_decimal(row, 8)?,
     })
 }
 
+fn optional_row_decimal(row: &rusqlite::Row<'_>, index: usize) -> rusqlite::Result<Decimal> {
+    Ok(row
+        .get::<_, Option<String>>(index)?
+        .as_deref()
+        .map_or(Decimal::ZERO, |value| {
+            parse_decimal_or_default(value, Decimal::ZERO)
+        }))
+}
+
 fn model_pricing_candidates(model_id: &str) -> Vec<String> {
     let cleaned = clean_model_id_for_pricing(model_id);
     if is_placeholder_pricing_model(&cleaned) {
@@ -2174,7 +2498,8 @@ pub fn request_log_detail_from_summary(
                     created_at, is_streaming, total_cost_usd, provider_type,
                     cost_multiplier, pricing_model_source, detail_file, detail_offset,
                     route_name, method, path, upstream_status_code,
-                    stream_outcome, error_category, attempt_count, total_attempt_count
+                    stream_outcome, error_category, attempt_count, total_attempt_count,
+                    client_label, cache_hit
              FROM proxy_request_logs
              WHERE request_id = ?1",
             [trace_id],
@@ -2250,6 +2575,7 @@ pub fn request_log_detail_from_summary(
                         output_tokens: Some(output_tokens),
                         cache_read_tokens: Some(cache_read_tokens),
                         cache_creation_tokens: Some(cache_creation_tokens),
+                        reasoning_tokens: None,
                         total_tokens: Some(total_tokens),
                         request_body_bytes: 0,
                         response_body_bytes: 0,
@@ -2261,6 +2587,10 @@ pub fn request_log_detail_from_summary(
                         detail_offset: row
                             .get::<_, Option<i64>>(21)?
                             .map(|value| value.max(0) as u64),
+                        client_label: row.get(30)?,
+                        cache_hit: row.get::<_, i64>(31)? != 0,
+                        image_count: 0,
+                        batch_priced: false,
                     },
                     request_headers: None,
                     request_body: None,
@@ -2488,6 +2818,7 @@ mod tests {
                 output_tokens: Some(output_tokens),
                 cache_read_tokens: Some(0),
                 cache_creation_tokens: Some(0),
+                reasoning_tokens: None,
                 total_tokens: Some(input_tokens + output_tokens),
                 request_body_bytes: 512,
                 response_body_bytes: 1024,
@@ -2495,6 +2826,10 @@ mod tests {
                 first_token_ms: None,
                 detail_file: None,
                 detail_offset: None,
+                client_label: None,
+                cache_hit: false,
+                image_count: 0,
+                batch_priced: false,
             },
             request_headers: Some(request_headers),
             request_body: Some(
@@ -3074,6 +3409,137 @@ mod tests {
         assert_eq!(row.1, "requested");
     }
 
+    #[test]
+    fn record_request_summary_bills_long_context_tier_and_request_fee() {
+        let db = test_db();
+        insert_model_pricing(&db, "pricing-tiered-model", "1", "2");
+        db.with_conn(|conn| {
+            conn.execute(
+                "UPDATE model_pricing
+                 SET pricing_tiers = ?1, request_cost = '0.5'
+                 WHERE model_id = 'pricing-tiered-model'",
+                params![
+                    r#"[{"above_input_tokens":200000,"input_cost_per_million":"2","output_cost_per_million":"4"}]"#
+                ],
+            )
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+        })
+        .expect("set pricing tiers");
+
+        for (trace_id, input_tokens) in [
+            ("trace-short-prompt", 100_000),
+            ("trace-long-prompt", 300_000),
+        ] {
+            let mut detail = make_detail(trace_id, "provider-alpha", 200, input_tokens, 1_000_000);
+            detail.summary.requested_model = Some("pricing-tiered-model".to_string());
+            detail.summary.upstream_model_id = Some("pricing-tiered-model".to_string());
+            record_request_summary(&db, &ProxyGatewaySettings::default(), &detail)
+                .expect("record summary");
+        }
+
+        let total_cost = |trace_id: &str| {
+            db.with_conn(|conn| {
+                conn.query_row(
+                    "SELECT total_cost_usd FROM proxy_request_logs WHERE request_id = ?1",
+                    [trace_id],
+                    |row| row.get::<_, String>(0),
+                )
+                .map_err(|error| error.to_string())
+            })
+            .expect("cost row")
+        };
+        assert_eq!(total_cost("trace-short-prompt"), "2.600000");
+        assert_eq!(total_cost("trace-long-prompt"), "5.100000");
+    }
+
+    fn set_model_pricing_columns(db: &SqliteDbState, model_id: &str, assignments: &str) {
+        db.with_conn(|conn| {
+            conn.execute(
+                &format!("UPDATE model_pricing SET {assignments} WHERE model_id = ?1"),
+                [model_id],
+            )
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+        })
+        .expect("update model pricing");
+    }
+
+    fn recorded_total_cost(db: &SqliteDbState, trace_id: &str) -> String {
+        db.with_conn(|conn| {
+            conn.query_row(
+                "SELECT total_cost_usd FROM proxy_request_logs WHERE request_id = ?1",
+                [trace_id],
+                |row| row.get::<_, String>(0),
+            )
+            .map_err(|error| error.to_string())
+        })
+        .expect("cost row")
+    }
+
+    #[test]
+    fn record_request_summary_bills_reasoning_tokens_at_reasoning_price() {
+        let db = test_db();
+        insert_model_pricing(&db, "pricing-reasoning-model", "1", "2");
+        set_model_pricing_columns(
+            &db,
+            "pricing-reasoning-model",
+            "reasoning_cost_per_million = '10'",
+        );
+
+        for (trace_id, reasoning_tokens) in [
+            ("trace-no-reasoning", None),
+            ("trace-with-reasoning", Some(250_000)),
+        ] {
+            let mut detail = make_detail(trace_id, "provider-alpha", 200, 1_000_000, 1_000_000);
+            detail.summary.upstream_model_id = Some("pricing-reasoning-model".to_string());
+            detail.summary.reasoning_tokens = reasoning_tokens;
+            record_request_summary(&db, &ProxyGatewaySettings::default(), &detail)
+                .expect("record summary");
+        }
+
+        assert_eq!(recorded_total_cost(&db, "trace-no-reasoning"), "3.000000");
+        // 750k plain output at $2 plus 250k reasoning at $10.
+        assert_eq!(recorded_total_cost(&db, "trace-with-reasoning"), "5.000000");
+    }
+
+    #[test]
+    fn record_request_summary_bills_per_image_fee() {
+        let db = test_db();
+        insert_model_pricing(&db, "pricing-image-model", "1", "2");
+        set_model_pricing_columns(&db, "pricing-image-model", "image_cost = '0.01'");
+
+        let mut detail = make_detail("trace-images", "provider-alpha", 200, 1_000_000, 0);
+        detail.summary.upstream_model_id = Some("pricing-image-model".to_string());
+        detail.summary.image_count = 3;
+        record_request_summary(&db, &ProxyGatewaySettings::default(), &detail)
+            .expect("record summary");
+
+        assert_eq!(recorded_total_cost(&db, "trace-images"), "1.030000");
+    }
+
+    #[test]
+    fn record_request_summary_applies_batch_discount_to_batch_tier_requests() {
+        let db = test_db();
+        insert_model_pricing(&db, "pricing-batch-model", "1", "2");
+        set_model_pricing_columns(
+            &db,
+            "pricing-batch-model",
+            "batch_discount_percent = '50', request_cost = '0.5'",
+        );
+
+        for (trace_id, batch_priced) in [("trace-standard", false), ("trace-batch", true)] {
+            let mut detail = make_detail(trace_id, "provider-alpha", 200, 1_000_000, 1_000_000);
+            detail.summary.upstream_model_id = Some("pricing-batch-model".to_string());
+            detail.summary.batch_priced = batch_priced;
+            record_request_summary(&db, &ProxyGatewaySettings::default(), &detail)
+                .expect("record summary");
+        }
+
+        assert_eq!(recorded_total_cost(&db, "trace-standard"), "3.500000");
+        assert_eq!(recorded_total_cost(&db, "trace-batch"), "1.750000");
+    }
+
     #[test]
     fn load_provider_names_resolves_grok_provider_display_name() {
         let db = test_db();
@@ -3390,6 +3856,37 @@ mod tests {
         assert_eq!(trend_rows[0].total_tokens, 39);
     }
 
+    #[test]
+    fn rollup_and_prune_keeps_cache_hit_counts() {
+        let db = test_db();
+        let old_created_at = Utc::now().timestamp() - 40 * 24 * 60 * 60;
+        db.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO proxy_request_logs (
+                    request_id, provider_id, app_type, model, request_model,
+                    input_tokens, output_tokens, total_cost_usd, latency_ms, status_code,
+                    created_at, method, path, cache_hit
+                ) VALUES
+                ('old-upstream', 'provider-alpha', 'claude', 'claude-sonnet-4-5', 'claude-sonnet-4-5',
+                    10, 5, '0.100000', 200, 200, ?1, 'POST', '/v1/messages', 0),
+                ('old-cached-1', 'provider-alpha', 'claude', 'claude-sonnet-4-5', 'claude-sonnet-4-5',
+                    10, 5, '0', 1, 200, ?1, 'POST', '/v1/messages', 1),
+                ('old-cached-2', 'provider-alpha', 'claude', 'claude-sonnet-4-5', 'claude-sonnet-4-5',
+                    10, 5, '0', 1, 200, ?1, 'POST', '/v1/messages', 1)",
+                [old_created_at],
+            )
+            .map_err(|error| error.to_string())?;
+
+            rollup_and_prune(conn, 30)
+        })
+        .expect("roll up old cached logs");
+
+        let summary = usage_summary(&db, None, None, Some(GatewayCliKey::Claude))
+            .expect("summary from rollups");
+        assert_eq!(summary.total_requests, 3);
+        assert_eq!(summary.cache_hit_requests, 2);
+    }
+
     #[test]
     fn rollup_and_prune_preserves_valid_compact_model_and_hides_no_model_compact() {
         let db = test_db();
@@ -3902,4 +4399,25 @@ mod tests {
         // Both rows survive (the replay idempotency did not swallow the failed one).
         assert_eq!(logs.total, 2);
     }
+
+    #[test]
+    fn resolved_model_pricing_matches_dated_model_ids() {
+        let db = test_db();
+        insert_model_pricing(&db, "claude-sonnet-4-5", "3", "15");
+
+        let cost = db
+            .with_conn(|conn| {
+                let pricing = ResolvedModelPricing::lookup(conn, "claude-sonnet-4-5-20250929")
+                    .ok_or_else(|| "pricing not found".to_string())?;
+                assert!(ResolvedModelPricing::lookup(conn, "unknown-model").is_none());
+                Ok(pricing.cost_usd(&TokenUsage {
+                    input_tokens: Some(100_000),
+                    output_tokens: Some(100_000),
+                    ..TokenUsage::default()
+                }))
+            })
+            .expect("price usage");
+
+        assert_eq!(cost, Decimal::new(18, 1));
+    }
 }
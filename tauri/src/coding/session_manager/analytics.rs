@@ -0,0 +1,654 @@
+//! Token, cost and tool-call analytics over CLI session files.
+//!
+//! Aggregates the per-message `usage`, `model`, `cost_usd` and `duration_ms`
+//! the loaders already extract, so spend is visible for sessions that never
+//! went through the gateway. Messages without a recorded cost are priced with
+//! the gateway `model_pricing` table.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{Local, TimeZone};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use super::{SessionMessage, SessionMessageUsage, SessionMeta};
+use crate::coding::proxy_gateway::usage_parser::TokenUsage;
+use crate::coding::proxy_gateway::usage_stats::ResolvedModelPricing;
+use crate::db::SqliteDbState;
+
+const UNKNOWN: &str = "unknown";
+const NO_PROJECT_KEY: &str = "";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AnalyticsGroupBy {
+    Session,
+    Project,
+    Tool,
+    Day,
+}
+
+impl AnalyticsGroupBy {
+    pub(super) fn parse(raw: Option<&str>) -> Result<Self, String> {
+        match raw.map(str::trim).filter(|value| !value.is_empty()) {
+            None | Some("tool") => Ok(Self::Tool),
+            Some("session") => Ok(Self::Session),
+            Some("project") => Ok(Self::Project),
+            Some("day") => Ok(Self::Day),
+            Some(value) => Err(format!("Unsupported session analytics grouping: {value}")),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Session => "session",
+            Self::Project => "project",
+            Self::Tool => "tool",
+            Self::Day => "day",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionModelUsageStat {
+    pub model: String,
+    pub message_count: u64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_creation_tokens: i64,
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionToolCallStat {
+    pub tool_name: String,
+    pub call_count: u64,
+    pub error_count: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionUsageStats {
+    pub session_count: u64,
+    pub message_count: u64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_creation_tokens: i64,
+    pub total_tokens: i64,
+    /// Share of prompt tokens (fresh input plus cache reads and writes) that
+    /// were served from cache.
+    pub cache_hit_rate: f64,
+    /// Cost the CLI itself wrote into the session file.
+    pub recorded_cost_usd: f64,
+    /// Recorded cost where present, otherwise the gateway pricing estimate.
+    pub cost_usd: f64,
+    /// Models with token usage but neither a recorded cost nor a pricing row.
+    pub unpriced_models: Vec<String>,
+    pub tool_call_count: u64,
+    pub tool_error_count: u64,
+    pub tool_error_rate: f64,
+    /// Sum over sessions of the span between their first and last message.
+    pub wall_time_ms: i64,
+    /// Sum of per-message durations reported by the CLI.
+    pub duration_ms: i64,
+    pub models: Vec<SessionModelUsageStat>,
+    pub tool_calls: Vec<SessionToolCallStat>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionAnalyticsBucket {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_path: Option<String>,
+    pub stats: SessionUsageStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionAnalyticsResult {
+    pub group_by: String,
+    pub buckets: Vec<SessionAnalyticsBucket>,
+    pub totals: SessionUsageStats,
+    pub failed_count: usize,
+}
+
+/// Prices message usage with the gateway `model_pricing` table, caching the
+/// lookup per model so the connection is only held briefly per model.
+pub(super) struct GatewayPricer<'a> {
+    db: &'a SqliteDbState,
+    cache: HashMap<String, Option<ResolvedModelPricing>>,
+}
+
+impl<'a> GatewayPricer<'a> {
+    pub(super) fn new(db: &'a SqliteDbState) -> Self {
+        Self {
+            db,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub(super) fn price(&mut self, model: &str, usage: &SessionMessageUsage) -> Option<f64> {
+        let db = self.db;
+        let pricing = self.cache.entry(model.to_string()).or_insert_with(|| {
+            db.with_conn(|conn| Ok(ResolvedModelPricing::lookup(conn, model)))
+                .ok()
+                .flatten()
+        });
+        let token_usage = TokenUsage {
+            input_tokens: Some(non_negative(usage.input_tokens)),
+            output_tokens: Some(non_negative(usage.output_tokens)),
+            cache_read_tokens: Some(non_negative(usage.cache_read_input_tokens)),
+            cache_creation_tokens: Some(non_negative(usage.cache_creation_input_tokens)),
+            reasoning_tokens: None,
+            envelope_id: None,
+        };
+        pricing
+            .as_ref()
+            .map(|pricing| pricing.cost_usd(&token_usage).to_f64().unwrap_or(0.0))
+    }
+}
+
+fn non_negative(value: Option<i64>) -> u64 {
+    value.unwrap_or(0).max(0) as u64
+}
+
+#[derive(Default)]
+struct ModelAccumulator {
+    message_count: u64,
+    input_tokens: i64,
+    output_tokens: i64,
+    cache_read_tokens: i64,
+    cache_creation_tokens: i64,
+    cost_usd: f64,
+}
+
+#[derive(Default)]
+struct StatsAccumulator {
+    sessions: HashSet<String>,
+    message_count: u64,
+    recorded_cost_usd: f64,
+    cost_usd: f64,
+    unpriced_models: HashSet<String>,
+    duration_ms: i64,
+    /// First and last message timestamp per session.
+    session_spans: HashMap<String, (i64, i64)>,
+    models: BTreeMap<String, ModelAccumulator>,
+    /// Call and error counts per normalized tool name.
+    tool_calls: BTreeMap<String, (u64, u64)>,
+}
+
+impl StatsAccumulator {
+    fn add_message(
+        &mut self,
+        session_key: &str,
+        message: &SessionMessage,
+        ts: Option<i64>,
+        tool_names: &mut HashMap<String, String>,
+        price: &mut dyn FnMut(&str, &SessionMessageUsage) -> Option<f64>,
+    ) {
+        self.sessions.insert(session_key.to_string());
+        self.message_count += 1;
+        self.duration_ms += message.duration_ms.unwrap_or(0).max(0);
+        if let Some(ts) = ts {
+            let span = self
+                .session_spans
+                .entry(session_key.to_string())
+                .or_insert((ts, ts));
+            span.0 = span.0.min(ts);
+            span.1 = span.1.max(ts);
+        }
+
+        let model_name = message
+            .model
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty());
+        let message_cost = match (message.cost_usd, message.usage.as_ref()) {
+            (Some(recorded), _) => {
+                self.recorded_cost_usd += recorded;
+                Some(recorded)
+            }
+            (None, Some(usage)) => match model_name.and_then(|model| price(model, usage)) {
+                Some(estimated) => Some(estimated),
+                None => {
+                    self.unpriced_models
+                        .insert(model_name.unwrap_or(UNKNOWN).to_string());
+                    None
+                }
+            },
+            (None, None) => None,
+        };
+        self.cost_usd += message_cost.unwrap_or(0.0);
+
+        if message.usage.is_some() || message_cost.is_some() {
+            let model = self
+                .models
+                .entry(model_name.unwrap_or(UNKNOWN).to_string())
+                .or_default();
+            model.message_count += 1;
+            model.cost_usd += message_cost.unwrap_or(0.0);
+            if let Some(usage) = message.usage.as_ref() {
+                model.input_tokens += usage.input_tokens.unwrap_or(0);
+                model.output_tokens += usage.output_tokens.unwrap_or(0);
+                model.cache_read_tokens += usage.cache_read_input_tokens.unwrap_or(0);
+                model.cache_creation_tokens += usage.cache_creation_input_tokens.unwrap_or(0);
+            }
+        }
+
+        for block in &message.blocks {
+            let is_error = block.is_error == Some(true) || block.status.as_deref() == Some("error");
+            match block.kind.as_str() {
+                "tool_call" | "tool_execution" => {
+                    let name = block
+                        .normalized_tool_name
+                        .clone()
+                        .unwrap_or_else(|| UNKNOWN.to_string());
+                    if let Some(tool_id) = block.tool_id.as_deref() {
+                        tool_names.insert(tool_id.to_string(), name.clone());
+                    }
+                    let counts = self.tool_calls.entry(name).or_default();
+                    counts.0 += 1;
+                    if is_error {
+                        counts.1 += 1;
+                    }
+                }
+                "tool_result" if is_error => {
+                    let name = block
+                        .tool_id
+                        .as_deref()
+                        .and_then(|tool_id| tool_names.get(tool_id).cloned())
+                        .or_else(|| block.normalized_tool_name.clone())
+                        .unwrap_or_else(|| UNKNOWN.to_string());
+                    self.tool_calls.entry(name).or_default().1 += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn finish(&self) -> SessionUsageStats {
+        let mut stats = SessionUsageStats {
+            session_count: self.sessions.len() as u64,
+            message_count: self.message_count,
+            recorded_cost_usd: self.recorded_cost_usd,
+            cost_usd: self.cost_usd,
+            duration_ms: self.duration_ms,
+            wall_time_ms: self
+                .session_spans
+                .values()
+                .map(|(first, last)| last - first)
+                .sum(),
+            ..SessionUsageStats::default()
+        };
+
+        for (model, usage) in &self.models {
+            stats.input_tokens += usage.input_tokens;
+            stats.output_tokens += usage.output_tokens;
+            stats.cache_read_tokens += usage.cache_read_tokens;
+            stats.cache_creation_tokens += usage.cache_creation_tokens;
+            stats.models.push(SessionModelUsageStat {
+                model: model.clone(),
+                message_count: usage.message_count,
+                input_tokens: usage.input_tokens,
+                output_tokens: usage.output_tokens,
+                cache_read_tokens: usage.cache_read_tokens,
+                cache_creation_tokens: usage.cache_creation_tokens,
+                cost_usd: usage.cost_usd,
+            });
+        }
+        stats.models.sort_by(|left, right| {
+            right
+                .cost_usd
+                .total_cmp(&left.cost_usd)
+                .then_with(|| left.model.cmp(&right.model))
+        });
+        stats.total_tokens = stats.input_tokens
+            + stats.output_tokens
+            + stats.cache_read_tokens
+            + stats.cache_creation_tokens;
+        let prompt_tokens =
+            stats.input_tokens + stats.cache_read_tokens + stats.cache_creation_tokens;
+        stats.cache_hit_rate = ratio(stats.cache_read_tokens as f64, prompt_tokens as f64);
+
+        for (tool_name, (call_count, error_count)) in &self.tool_calls {
+            stats.tool_call_count += call_count;
+            stats.tool_error_count += error_count;
+            stats.tool_calls.push(SessionToolCallStat {
+                tool_name: tool_name.clone(),
+                call_count: *call_count,
+                error_count: *error_count,
+            });
+        }
+        stats.tool_calls.sort_by(|left, right| {
+            right
+                .call_count
+                .cmp(&left.call_count)
+                .then_with(|| left.tool_name.cmp(&right.tool_name))
+        });
+        stats.tool_error_rate = ratio(stats.tool_error_count as f64, stats.tool_call_count as f64);
+
+        let mut unpriced_models: Vec<String> = self.unpriced_models.iter().cloned().collect();
+        unpriced_models.sort();
+        stats.unpriced_models = unpriced_models;
+        stats
+    }
+}
+
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator > 0.0 {
+        numerator / denominator
+    } else {
+        0.0
+    }
+}
+
+struct BucketAccumulator {
+    tool: Option<String>,
+    title: Option<String>,
+    source_path: Option<String>,
+    stats: StatsAccumulator,
+}
+
+/// Accumulates sessions into buckets keyed by the requested grouping, plus
+/// an overall total.
+pub(super) struct SessionAnalytics {
+    group_by: AnalyticsGroupBy,
+    buckets: BTreeMap<String, BucketAccumulator>,
+    totals: StatsAccumulator,
+    failed_count: usize,
+}
+
+impl SessionAnalytics {
+    pub(super) fn new(group_by: AnalyticsGroupBy) -> Self {
+        Self {
+            group_by,
+            buckets: BTreeMap::new(),
+            totals: StatsAccumulator::default(),
+            failed_count: 0,
+        }
+    }
+
+    pub(super) fn record_failure(&mut self) {
+        self.failed_count += 1;
+    }
+
+    pub(super) fn add_session(
+        &mut self,
+        tool: &str,
+        meta: &SessionMeta,
+        messages: &[SessionMessage],
+        price: &mut dyn FnMut(&str, &SessionMessageUsage) -> Option<f64>,
+    ) {
+        let session_key = format!("{tool}:{}", meta.source_path);
+        let fallback_ts = meta.last_active_at.or(meta.created_at);
+        let mut tool_names = HashMap::new();
+        // Totals keep their own id map so bucket boundaries (days) never
+        // lose the call a later tool_result refers to.
+        let mut total_tool_names = HashMap::new();
+
+        for message in messages {
+            let ts = message.ts.or(fallback_ts);
+            self.totals
+                .add_message(&session_key, message, ts, &mut total_tool_names, price);
+
+            let bucket_key = match self.group_by {
+                AnalyticsGroupBy::Session => session_key.clone(),
+                AnalyticsGroupBy::Project => meta
+                    .project_dir
+                    .clone()
+                    .unwrap_or_else(|| NO_PROJECT_KEY.to_string()),
+                AnalyticsGroupBy::Tool => tool.to_string(),
+                AnalyticsGroupBy::Day => day_key(ts),
+            };
+            let bucket = self
+                .buckets
+                .entry(bucket_key)
+                .or_insert_with(|| BucketAccumulator {
+                    tool: None,
+                    title: None,
+                    source_path: None,
+                    stats: StatsAccumulator::default(),
+                });
+            if matches!(
+                self.group_by,
+                AnalyticsGroupBy::Session | AnalyticsGroupBy::Tool
+            ) {
+                bucket.tool = Some(tool.to_string());
+            }
+            if self.group_by == AnalyticsGroupBy::Session {
+                bucket.title = meta.title.clone();
+                bucket.source_path = Some(meta.source_path.clone());
+            }
+            bucket
+                .stats
+                .add_message(&session_key, message, ts, &mut tool_names, price);
+        }
+    }
+
+    pub(super) fn finish(self) -> SessionAnalyticsResult {
+        let mut buckets: Vec<SessionAnalyticsBucket> = self
+            .buckets
+            .into_iter()
+            .map(|(key, bucket)| SessionAnalyticsBucket {
+                key,
+                tool: bucket.tool,
+                title: bucket.title,
+                source_path: bucket.source_path,
+                stats: bucket.stats.finish(),
+            })
+            .collect();
+        if self.group_by == AnalyticsGroupBy::Day {
+            buckets.reverse();
+        } else {
+            buckets.sort_by(|left, right| {
+                right
+                    .stats
+                    .cost_usd
+                    .total_cmp(&left.stats.cost_usd)
+                    .then_with(|| right.stats.total_tokens.cmp(&left.stats.total_tokens))
+                    .then_with(|| left.key.cmp(&right.key))
+            });
+        }
+
+        SessionAnalyticsResult {
+            group_by: self.group_by.as_str().to_string(),
+            buckets,
+            totals: self.totals.finish(),
+            failed_count: self.failed_count,
+        }
+    }
+}
+
+fn day_key(ts: Option<i64>) -> String {
+    ts.and_then(|value| Local.timestamp_millis_opt(value).single())
+        .map(|value| value.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| UNKNOWN.to_string())
+}
+
+/// Statistics for a single session.
+pub(super) fn session_usage_stats(
+    tool: &str,
+    meta: &SessionMeta,
+    messages: &[SessionMessage],
+    price: &mut dyn FnMut(&str, &SessionMessageUsage) -> Option<f64>,
+) -> SessionUsageStats {
+    let mut analytics = SessionAnalytics::new(AnalyticsGroupBy::Session);
+    analytics.add_session(tool, meta, messages, price);
+    analytics.finish().totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::message_blocks::{
+        message_from_blocks, text_block, tool_call_block, tool_result_block,
+    };
+    use super::*;
+    use serde_json::json;
+
+    const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+    fn meta(source_path: &str, project_dir: &str) -> SessionMeta {
+        SessionMeta {
+            provider_id: "claudecode".to_string(),
+            session_id: source_path.to_string(),
+            title: Some(format!("Session {source_path}")),
+            summary: None,
+            project_dir: Some(project_dir.to_string()),
+            created_at: None,
+            last_active_at: None,
+            source_path: source_path.to_string(),
+            resume_command: None,
+            runtime_source: None,
+            runtime_distro: None,
+        }
+    }
+
+    fn assistant(
+        ts: i64,
+        model: &str,
+        input: i64,
+        output: i64,
+        cache_read: i64,
+        cost_usd: Option<f64>,
+    ) -> SessionMessage {
+        let mut message = message_from_blocks("assistant", Some(ts), vec![text_block("ok")]);
+        message.model = Some(model.to_string());
+        message.usage = Some(SessionMessageUsage {
+            input_tokens: Some(input),
+            output_tokens: Some(output),
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: Some(cache_read),
+        });
+        message.cost_usd = cost_usd;
+        message
+    }
+
+    fn session_messages(start: i64) -> Vec<SessionMessage> {
+        let calls = message_from_blocks(
+            "assistant",
+            Some(start + 1_000),
+            vec![
+                tool_call_block(Some("t1".to_string()), "Bash", Some(json!({}))),
+                tool_call_block(Some("t2".to_string()), "Read", Some(json!({}))),
+            ],
+        );
+        let results = message_from_blocks(
+            "user",
+            Some(start + 2_000),
+            vec![
+                tool_result_block(
+                    Some("t1".to_string()),
+                    None,
+                    Some(json!("boom")),
+                    Some(true),
+                ),
+                tool_result_block(Some("t2".to_string()), None, Some(json!("fine")), None),
+            ],
+        );
+        vec![
+            message_from_blocks("user", Some(start), vec![text_block("hi")]),
+            calls,
+            results,
+            assistant(start + 60_000, "priced-model", 100, 50, 300, None),
+            assistant(start + 90_000, "mystery-model", 10, 5, 0, None),
+            assistant(start + 120_000, "recorded-model", 1, 1, 0, Some(0.5)),
+        ]
+    }
+
+    fn price(model: &str, usage: &SessionMessageUsage) -> Option<f64> {
+        (model == "priced-model").then(|| usage.input_tokens.unwrap_or(0) as f64 / 100.0)
+    }
+
+    #[test]
+    fn session_stats_aggregate_tokens_cost_tools_and_wall_time() {
+        let stats = session_usage_stats(
+            "claudecode",
+            &meta("/a.jsonl", "/repo"),
+            &session_messages(1_700_000_000_000),
+            &mut price,
+        );
+
+        assert_eq!(stats.session_count, 1);
+        assert_eq!(stats.message_count, 6);
+        assert_eq!(stats.input_tokens, 111);
+        assert_eq!(stats.cache_read_tokens, 300);
+        assert_eq!(stats.total_tokens, 111 + 56 + 300);
+        assert!((stats.cache_hit_rate - 300.0 / 411.0).abs() < 1e-9);
+        assert!((stats.recorded_cost_usd - 0.5).abs() < 1e-9);
+        assert!((stats.cost_usd - 1.5).abs() < 1e-9);
+        assert_eq!(stats.unpriced_models, vec!["mystery-model".to_string()]);
+        assert_eq!(stats.tool_call_count, 2);
+        assert_eq!(stats.tool_error_count, 1);
+        assert!((stats.tool_error_rate - 0.5).abs() < 1e-9);
+        assert_eq!(stats.tool_calls[0].tool_name, "bash");
+        assert_eq!(stats.tool_calls[0].error_count, 1);
+        assert_eq!(stats.wall_time_ms, 120_000);
+        assert_eq!(stats.models[0].model, "priced-model");
+    }
+
+    #[test]
+    fn analytics_group_by_project_and_day() {
+        let start = 1_700_000_000_000;
+        let mut by_project = SessionAnalytics::new(AnalyticsGroupBy::Project);
+        by_project.add_session(
+            "claudecode",
+            &meta("/a.jsonl", "/repo-a"),
+            &session_messages(start),
+            &mut price,
+        );
+        by_project.add_session(
+            "codex",
+            &meta("/b.jsonl", "/repo-b"),
+            &session_messages(start),
+            &mut price,
+        );
+        by_project.add_session(
+            "codex",
+            &meta("/c.jsonl", "/repo-a"),
+            &session_messages(start + DAY_MS),
+            &mut price,
+        );
+        let result = by_project.finish();
+        assert_eq!(result.group_by, "project");
+        assert_eq!(result.totals.session_count, 3);
+        assert_eq!(result.buckets[0].key, "/repo-a");
+        assert_eq!(result.buckets[0].stats.session_count, 2);
+        assert!(result.buckets[0].tool.is_none());
+
+        let mut by_day = SessionAnalytics::new(AnalyticsGroupBy::Day);
+        by_day.add_session(
+            "claudecode",
+            &meta("/a.jsonl", "/repo-a"),
+            &session_messages(start),
+            &mut price,
+        );
+        by_day.add_session(
+            "codex",
+            &meta("/c.jsonl", "/repo-a"),
+            &session_messages(start + DAY_MS),
+            &mut price,
+        );
+        let result = by_day.finish();
+        assert_eq!(result.buckets.len(), 2);
+        assert!(result.buckets[0].key > result.buckets[1].key);
+        assert_eq!(result.buckets[0].stats.session_count, 1);
+    }
+
+    #[test]
+    fn group_by_parses_known_values() {
+        assert_eq!(AnalyticsGroupBy::parse(None), Ok(AnalyticsGroupBy::Tool));
+        assert_eq!(
+            AnalyticsGroupBy::parse(Some("day")),
+            Ok(AnalyticsGroupBy::Day)
+        );
+        assert!(AnalyticsGroupBy::parse(Some("week")).is_err());
+    }
+}
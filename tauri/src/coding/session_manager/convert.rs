@@ -0,0 +1,927 @@
+//! Cross-tool session conversion.
+//!
+//! A session from any readable CLI is flattened into a transcript of user
+//! turns, assistant turns and paired tool calls, then rendered as a native
+//! Codex rollout or Claude Code project session and written through the same
+//! snapshot importers used by session import. Tool names are mapped through
+//! `tool_normalizer`; every tool call is paired with an output (synthesized if
+//! the source never recorded one) because both upstream APIs reject orphans.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+use serde_json::{json, Map, Value};
+
+use super::tool_normalizer::{claude_code_tool_name, codex_tool_name, normalize_tool_name};
+use super::utils::truncate_summary;
+use super::{claude_code, codex, SessionMessage, SessionMeta, ToolSessionContext};
+
+const MISSING_TOOL_OUTPUT: &str = "[No tool output was recorded in the original session]";
+const CONVERTED_CLI_VERSION: &str = "0.0.0";
+/// Provider Codex itself assumes when config.toml sets no `model_provider`.
+const DEFAULT_CODEX_MODEL_PROVIDER: &str = "openai";
+/// Context that the source CLI injected as user turns; the target CLI adds
+/// its own on resume, so replaying these would only duplicate it.
+const INJECTED_CONTEXT_PREFIXES: [&str; 3] = [
+    "<environment_context>",
+    "<user_instructions>",
+    "<system-reminder>",
+];
+const SHELL_WRAPPER_PREFIXES: [&str; 4] = ["bash -lc ", "bash -c ", "zsh -lc ", "sh -c "];
+
+#[derive(Debug, Clone, PartialEq)]
+enum TranscriptItem {
+    UserText {
+        text: String,
+        ts: Option<i64>,
+    },
+    AssistantText {
+        text: String,
+        ts: Option<i64>,
+        model: Option<String>,
+    },
+    ToolCall {
+        call_id: String,
+        raw_name: String,
+        normalized_name: String,
+        input: Value,
+        ts: Option<i64>,
+        model: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ToolOutput {
+    text: String,
+    is_error: bool,
+}
+
+#[derive(Debug, Default)]
+struct Transcript {
+    items: Vec<TranscriptItem>,
+    outputs: HashMap<String, ToolOutput>,
+}
+
+impl Transcript {
+    fn output_for(&self, call_id: &str) -> ToolOutput {
+        self.outputs.get(call_id).cloned().unwrap_or(ToolOutput {
+            text: MISSING_TOOL_OUTPUT.to_string(),
+            is_error: false,
+        })
+    }
+
+    fn first_ts(&self) -> Option<i64> {
+        self.items.iter().find_map(item_ts)
+    }
+
+    fn last_ts(&self) -> Option<i64> {
+        self.items.iter().rev().find_map(item_ts)
+    }
+}
+
+/// Writes `messages` as a new native session of the tool behind `target` and
+/// returns the new session id.
+pub(super) fn write_converted_session(
+    target: &ToolSessionContext,
+    meta: &SessionMeta,
+    messages: &[SessionMessage],
+) -> Result<String, String> {
+    let transcript = build_transcript(messages);
+    if !transcript
+        .items
+        .iter()
+        .any(|item| matches!(item, TranscriptItem::UserText { .. }))
+    {
+        return Err("Session has no user messages to convert".to_string());
+    }
+
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now();
+    match target {
+        ToolSessionContext::Codex { sessions_root } => {
+            let model_provider = codex_model_provider(sessions_root);
+            let snapshot =
+                build_codex_snapshot(&session_id, meta, &transcript, &model_provider, now);
+            codex::import_native_snapshot(sessions_root, &session_id, &snapshot)?;
+        }
+        ToolSessionContext::ClaudeCode { projects_root } => {
+            let snapshot = build_claude_code_snapshot(&session_id, meta, &transcript, now)?;
+            claude_code::import_native_snapshot(projects_root, &session_id, &snapshot)?;
+        }
+        _ => {
+            return Err(
+                "Session conversion currently supports Codex and Claude Code as targets"
+                    .to_string(),
+            )
+        }
+    }
+
+    Ok(session_id)
+}
+
+fn build_transcript(messages: &[SessionMessage]) -> Transcript {
+    let mut transcript = Transcript::default();
+    let mut generated_call_ids = 0usize;
+
+    for message in messages {
+        if matches!(message.role.as_str(), "system" | "developer")
+            || message.is_sidechain == Some(true)
+        {
+            continue;
+        }
+
+        if message.blocks.is_empty() {
+            push_text(&mut transcript.items, message, &message.content);
+            continue;
+        }
+
+        for block in &message.blocks {
+            match block.kind.as_str() {
+                "text" => {
+                    if let Some(text) = block.text.as_deref() {
+                        push_text(&mut transcript.items, message, text);
+                    }
+                }
+                "tool_call" | "tool_execution" => {
+                    let raw_name = block
+                        .tool_name
+                        .clone()
+                        .unwrap_or_else(|| "unknown".to_string());
+                    let call_id = block
+                        .tool_id
+                        .as_deref()
+                        .map(sanitize_tool_identifier)
+                        .filter(|value| !value.is_empty())
+                        .unwrap_or_else(|| {
+                            generated_call_ids += 1;
+                            format!("call_converted_{generated_call_ids}")
+                        });
+                    if block.kind == "tool_execution" {
+                        if let Some(output) = block.output.as_ref() {
+                            transcript.outputs.insert(
+                                call_id.clone(),
+                                ToolOutput {
+                                    text: tool_output_text(output),
+                                    is_error: block.is_error == Some(true),
+                                },
+                            );
+                        }
+                    }
+                    transcript.items.push(TranscriptItem::ToolCall {
+                        call_id,
+                        normalized_name: block
+                            .normalized_tool_name
+                            .clone()
+                            .unwrap_or_else(|| normalize_tool_name(&raw_name)),
+                        raw_name,
+                        input: block.input.clone().unwrap_or(Value::Null),
+                        ts: message.ts,
+                        model: message.model.clone(),
+                    });
+                }
+                "tool_result" => {
+                    let (Some(tool_id), Some(output)) =
+                        (block.tool_id.as_deref(), block.output.as_ref())
+                    else {
+                        continue;
+                    };
+                    transcript.outputs.insert(
+                        sanitize_tool_identifier(tool_id),
+                        ToolOutput {
+                            text: tool_output_text(output),
+                            is_error: block.is_error == Some(true),
+                        },
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    transcript
+}
+
+fn push_text(items: &mut Vec<TranscriptItem>, message: &SessionMessage, text: &str) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+
+    match message.role.as_str() {
+        "user" => {
+            if INJECTED_CONTEXT_PREFIXES
+                .iter()
+                .any(|prefix| text.starts_with(prefix))
+            {
+                return;
+            }
+            items.push(TranscriptItem::UserText {
+                text: text.to_string(),
+                ts: message.ts,
+            });
+        }
+        "assistant" => items.push(TranscriptItem::AssistantText {
+            text: text.to_string(),
+            ts: message.ts,
+            model: message.model.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn item_ts(item: &TranscriptItem) -> Option<i64> {
+    match item {
+        TranscriptItem::UserText { ts, .. }
+        | TranscriptItem::AssistantText { ts, .. }
+        | TranscriptItem::ToolCall { ts, .. } => *ts,
+    }
+}
+
+fn tool_output_text(output: &Value) -> String {
+    match output {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => {
+            let texts: Vec<&str> = items
+                .iter()
+                .filter_map(|item| item.get("text").and_then(Value::as_str))
+                .collect();
+            if texts.len() == items.len() && !texts.is_empty() {
+                texts.join("\n")
+            } else {
+                output.to_string()
+            }
+        }
+        Value::Null => String::new(),
+        _ => output.to_string(),
+    }
+}
+
+/// Both Anthropic tool-use ids and tool names must match `[A-Za-z0-9_-]+`.
+fn sanitize_tool_identifier(value: &str) -> String {
+    value
+        .trim()
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn bash_command_text(input: &Value) -> Option<String> {
+    let command = match input.get("command").or_else(|| input.get("cmd"))? {
+        Value::String(command) => command.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => return None,
+    };
+    let unwrapped = SHELL_WRAPPER_PREFIXES
+        .iter()
+        .find_map(|prefix| command.strip_prefix(prefix))
+        .unwrap_or(&command);
+    Some(unwrapped.to_string())
+}
+
+fn format_ts(ts: Option<i64>, fallback: DateTime<Utc>) -> String {
+    ts.and_then(|value| Utc.timestamp_millis_opt(value).single())
+        .unwrap_or(fallback)
+        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        .to_string()
+}
+
+/// `model_provider` from the config.toml of the Codex home that owns
+/// `sessions_root`. `codex resume` only lists sessions recorded under the
+/// active provider, so a hardcoded value would hide the converted session.
+fn codex_model_provider(sessions_root: &Path) -> String {
+    sessions_root
+        .parent()
+        .and_then(|codex_home| fs::read_to_string(codex_home.join("config.toml")).ok())
+        .and_then(|config| config.parse::<toml::Table>().ok())
+        .and_then(|config| {
+            config
+                .get("model_provider")?
+                .as_str()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| DEFAULT_CODEX_MODEL_PROVIDER.to_string())
+}
+
+fn build_codex_snapshot(
+    session_id: &str,
+    meta: &SessionMeta,
+    transcript: &Transcript,
+    model_provider: &str,
+    now: DateTime<Utc>,
+) -> Value {
+    let started_at = Local::now();
+    let relative_session_path = format!(
+        "{}/rollout-{}-{session_id}.jsonl",
+        started_at.format("%Y/%m/%d"),
+        started_at.format("%Y-%m-%dT%H-%M-%S"),
+    );
+    let thread_name = meta
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or_default();
+
+    let session_file_content =
+        render_codex_session(session_id, meta, transcript, model_provider, now);
+
+    json!({
+        "relativeSessionPath": relative_session_path,
+        "sessionFileContent": session_file_content,
+        "sessionIndexEntry": {
+            "id": session_id,
+            "thread_name": thread_name,
+            "updated_at": now.to_rfc3339(),
+        },
+    })
+}
+
+fn render_codex_session(
+    session_id: &str,
+    meta: &SessionMeta,
+    transcript: &Transcript,
+    model_provider: &str,
+    now: DateTime<Utc>,
+) -> String {
+    let started_at = format_ts(transcript.first_ts(), now);
+    let mut records = vec![json!({
+        "timestamp": started_at,
+        "type": "session_meta",
+        "payload": {
+            "id": session_id,
+            "timestamp": started_at,
+            "cwd": meta.project_dir.as_deref().unwrap_or_default(),
+            "originator": "codex_cli_rs",
+            "cli_version": CONVERTED_CLI_VERSION,
+            "instructions": null,
+            "source": "cli",
+            "model_provider": model_provider,
+        },
+    })];
+
+    for item in &transcript.items {
+        let timestamp = format_ts(item_ts(item), now);
+        let mut push = |record_type: &str, payload: Value| {
+            records.push(json!({
+                "timestamp": timestamp,
+                "type": record_type,
+                "payload": payload,
+            }));
+        };
+        match item {
+            TranscriptItem::UserText { text, .. } => {
+                push(
+                    "response_item",
+                    json!({
+                        "type": "message",
+                        "role": "user",
+                        "content": [{ "type": "input_text", "text": text }],
+                    }),
+                );
+                // Codex only lists sessions in `codex resume` once it has seen
+                // a user_message event.
+                push(
+                    "event_msg",
+                    json!({ "type": "user_message", "message": text, "images": [] }),
+                );
+            }
+            TranscriptItem::AssistantText { text, .. } => {
+                push(
+                    "response_item",
+                    json!({
+                        "type": "message",
+                        "role": "assistant",
+                        "content": [{ "type": "output_text", "text": text }],
+                    }),
+                );
+                push(
+                    "event_msg",
+                    json!({ "type": "agent_message", "message": text }),
+                );
+            }
+            TranscriptItem::ToolCall {
+                call_id,
+                raw_name,
+                normalized_name,
+                input,
+                ..
+            } => {
+                let output = transcript.output_for(call_id);
+                let patch = input.get("patch").and_then(Value::as_str);
+                if let (Some("apply_patch"), Some(patch)) =
+                    (codex_tool_name(normalized_name), patch)
+                {
+                    push(
+                        "response_item",
+                        json!({
+                            "type": "custom_tool_call",
+                            "status": "completed",
+                            "call_id": call_id,
+                            "name": "apply_patch",
+                            "input": patch,
+                        }),
+                    );
+                    push(
+                        "response_item",
+                        json!({
+                            "type": "custom_tool_call_output",
+                            "call_id": call_id,
+                            "output": output.text,
+                        }),
+                    );
+                    continue;
+                }
+
+                let name = codex_tool_name(normalized_name)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| raw_name.clone());
+                let arguments = match (normalized_name.as_str(), bash_command_text(input)) {
+                    ("bash", Some(command)) => json!({ "command": ["bash", "-lc", command] }),
+                    _ => input.clone(),
+                };
+                push(
+                    "response_item",
+                    json!({
+                        "type": "function_call",
+                        "name": name,
+                        "arguments": arguments.to_string(),
+                        "call_id": call_id,
+                    }),
+                );
+                push(
+                    "response_item",
+                    json!({
+                        "type": "function_call_output",
+                        "call_id": call_id,
+                        "output": output.text,
+                    }),
+                );
+            }
+        }
+    }
+
+    render_jsonl(&records)
+}
+
+fn build_claude_code_snapshot(
+    session_id: &str,
+    meta: &SessionMeta,
+    transcript: &Transcript,
+    now: DateTime<Utc>,
+) -> Result<Value, String> {
+    let project_dir = meta
+        .project_dir
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| {
+            "Claude Code sessions are stored per project; the source session has no project directory"
+                .to_string()
+        })?;
+
+    Ok(json!({
+        "projectRelativeDir": claude_code_project_dir_name(project_dir),
+        "sessionFileName": format!("{session_id}.jsonl"),
+        "sessionFileContent": render_claude_code_session(session_id, project_dir, transcript, now),
+        "indexEntry": {
+            "sessionId": session_id,
+            "projectPath": project_dir,
+            "summary": meta.summary.as_deref().map(|value| truncate_summary(value, 160)),
+            "created": transcript.first_ts().unwrap_or_else(|| now.timestamp_millis()),
+            "modified": transcript.last_ts().unwrap_or_else(|| now.timestamp_millis()),
+            "firstPrompt": meta.title,
+        },
+    }))
+}
+
+/// Claude Code keys project history by the working directory with every
+/// non-alphanumeric character replaced by `-`.
+fn claude_code_project_dir_name(project_dir: &str) -> String {
+    project_dir
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '-' })
+        .collect()
+}
+
+struct ClaudeCodeWriter<'a> {
+    session_id: &'a str,
+    cwd: &'a str,
+    now: DateTime<Utc>,
+    parent_uuid: Option<String>,
+    records: Vec<Value>,
+}
+
+impl ClaudeCodeWriter<'_> {
+    fn push(&mut self, record_type: &str, message: Value, ts: Option<i64>) {
+        let uuid = uuid::Uuid::new_v4().to_string();
+        self.records.push(json!({
+            "parentUuid": self.parent_uuid,
+            "isSidechain": false,
+            "userType": "external",
+            "cwd": self.cwd,
+            "sessionId": self.session_id,
+            "version": CONVERTED_CLI_VERSION,
+            "type": record_type,
+            "message": message,
+            "uuid": uuid,
+            "timestamp": format_ts(ts, self.now),
+        }));
+        self.parent_uuid = Some(uuid);
+    }
+}
+
+#[derive(Default)]
+struct PendingAssistantTurn {
+    content: Vec<Value>,
+    call_ids: Vec<String>,
+    ts: Option<i64>,
+    model: Option<String>,
+}
+
+fn render_claude_code_session(
+    session_id: &str,
+    cwd: &str,
+    transcript: &Transcript,
+    now: DateTime<Utc>,
+) -> String {
+    let mut writer = ClaudeCodeWriter {
+        session_id,
+        cwd,
+        now,
+        parent_uuid: None,
+        records: Vec::new(),
+    };
+    let mut pending = PendingAssistantTurn::default();
+
+    for item in &transcript.items {
+        match item {
+            TranscriptItem::UserText { text, ts } => {
+                flush_claude_assistant_turn(&mut writer, transcript, &mut pending);
+                writer.push("user", json!({ "role": "user", "content": text }), *ts);
+            }
+            TranscriptItem::AssistantText { text, ts, model } => {
+                // Tool results must directly follow the assistant message that
+                // issued the calls, so text after a call starts a new turn.
+                if !pending.call_ids.is_empty() {
+                    flush_claude_assistant_turn(&mut writer, transcript, &mut pending);
+                }
+                pending.ts = pending.ts.or(*ts);
+                pending.model = pending.model.take().or_else(|| model.clone());
+                pending
+                    .content
+                    .push(json!({ "type": "text", "text": text }));
+            }
+            TranscriptItem::ToolCall {
+                call_id,
+                raw_name,
+                normalized_name,
+                input,
+                ts,
+                model,
+            } => {
+                let name = claude_code_tool_name(normalized_name)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| sanitize_tool_identifier(raw_name));
+                let input = match (normalized_name.as_str(), bash_command_text(input)) {
+                    ("bash", Some(command)) => json!({ "command": command }),
+                    _ if input.is_object() => input.clone(),
+                    _ if input.is_null() => Value::Object(Map::new()),
+                    _ => json!({ "input": input }),
+                };
+                pending.ts = pending.ts.or(*ts);
+                pending.model = pending.model.take().or_else(|| model.clone());
+                pending.content.push(json!({
+                    "type": "tool_use",
+                    "id": call_id,
+                    "name": name,
+                    "input": input,
+                }));
+                pending.call_ids.push(call_id.clone());
+            }
+        }
+    }
+    flush_claude_assistant_turn(&mut writer, transcript, &mut pending);
+
+    render_jsonl(&writer.records)
+}
+
+fn flush_claude_assistant_turn(
+    writer: &mut ClaudeCodeWriter<'_>,
+    transcript: &Transcript,
+    pending: &mut PendingAssistantTurn,
+) {
+    let turn = std::mem::take(pending);
+    if turn.content.is_empty() {
+        return;
+    }
+
+    writer.push(
+        "assistant",
+        json!({
+            "id": format!("msg_{}", uuid::Uuid::new_v4().simple()),
+            "type": "message",
+            "role": "assistant",
+            "model": turn.model.as_deref().unwrap_or("<synthetic>"),
+            "content": turn.content,
+            "stop_reason": null,
+            "stop_sequence": null,
+            "usage": { "input_tokens": 0, "output_tokens": 0 },
+        }),
+        turn.ts,
+    );
+
+    if turn.call_ids.is_empty() {
+        return;
+    }
+    let results: Vec<Value> = turn
+        .call_ids
+        .iter()
+        .map(|call_id| {
+            let output = transcript.output_for(call_id);
+            json!({
+                "type": "tool_result",
+                "tool_use_id": call_id,
+                "content": output.text,
+                "is_error": output.is_error,
+            })
+        })
+        .collect();
+    writer.push(
+        "user",
+        json!({ "role": "user", "content": results }),
+        turn.ts,
+    );
+}
+
+fn render_jsonl(records: &[Value]) -> String {
+    let mut content = records
+        .iter()
+        .map(Value::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    content.push('\n');
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::message_blocks::{
+        message_from_blocks, text_block, thinking_block, tool_call_block, tool_result_block,
+    };
+    use super::super::utils::build_resume_command;
+    use super::*;
+
+    fn source_meta(project_dir: Option<&str>) -> SessionMeta {
+        SessionMeta {
+            provider_id: "claudecode".to_string(),
+            session_id: "source-session".to_string(),
+            title: Some("Fix the build".to_string()),
+            summary: Some("Build fixed".to_string()),
+            project_dir: project_dir.map(str::to_string),
+            created_at: Some(1_700_000_000_000),
+            last_active_at: Some(1_700_000_060_000),
+            source_path: "/tmp/source.jsonl".to_string(),
+            resume_command: None,
+            runtime_source: None,
+            runtime_distro: None,
+        }
+    }
+
+    fn source_messages() -> Vec<SessionMessage> {
+        let mut user = message_from_blocks(
+            "user",
+            Some(1_700_000_000_000),
+            vec![text_block("Why does cargo build fail?")],
+        );
+        user.model = None;
+        let mut assistant = message_from_blocks(
+            "assistant",
+            Some(1_700_000_010_000),
+            vec![
+                thinking_block("Let me check"),
+                text_block("Running the build."),
+                tool_call_block(
+                    Some("toolu_01".to_string()),
+                    "Bash",
+                    Some(json!({ "command": "cargo build", "description": "Build" })),
+                ),
+                tool_call_block(
+                    Some("toolu_02".to_string()),
+                    "Read",
+                    Some(json!({ "file_path": "/repo/Cargo.toml" })),
+                ),
+            ],
+        );
+        assistant.model = Some("claude-sonnet-4-5".to_string());
+        let tool = message_from_blocks(
+            "tool",
+            Some(1_700_000_020_000),
+            vec![
+                tool_result_block(
+                    Some("toolu_01".to_string()),
+                    None,
+                    Some(json!("error[E0425]: cannot find value")),
+                    Some(true),
+                ),
+                tool_result_block(Some("orphan".to_string()), None, Some(json!("x")), None),
+            ],
+        );
+        let reply = message_from_blocks(
+            "assistant",
+            Some(1_700_000_030_000),
+            vec![text_block("A variable is misspelled.")],
+        );
+        let system = message_from_blocks("system", None, vec![text_block("[Task Started]")]);
+        vec![user, assistant, tool, reply, system]
+    }
+
+    #[test]
+    fn claude_code_session_converts_into_codex_rollout() {
+        let root = tempfile::tempdir().expect("temp dir");
+        let context = ToolSessionContext::Codex {
+            sessions_root: root.path().to_path_buf(),
+        };
+
+        let session_id =
+            write_converted_session(&context, &source_meta(Some("/repo")), &source_messages())
+                .expect("convert");
+
+        let sessions = codex::scan_sessions(root.path());
+        assert_eq!(sessions.len(), 1);
+        let session = &sessions[0];
+        assert_eq!(session.session_id, session_id);
+        assert_eq!(session.title.as_deref(), Some("Fix the build"));
+        assert_eq!(
+            session.resume_command.as_deref(),
+            Some(
+                build_resume_command(Some("/repo"), &format!("codex resume {session_id}")).as_str()
+            )
+        );
+
+        let messages = codex::load_messages(std::path::Path::new(&session.source_path))
+            .expect("load converted");
+        let tool_names: Vec<&str> = messages
+            .iter()
+            .flat_map(|message| message.blocks.iter())
+            .filter(|block| block.kind == "tool_call")
+            .filter_map(|block| block.tool_name.as_deref())
+            .collect();
+        assert_eq!(tool_names, vec!["Bash", "Read"]);
+        let bash_input = messages
+            .iter()
+            .flat_map(|message| message.blocks.iter())
+            .find(|block| block.tool_name.as_deref() == Some("Bash"))
+            .and_then(|block| block.input.clone())
+            .expect("bash input");
+        assert_eq!(bash_input["command"], json!("bash -lc cargo build"));
+
+        let content = std::fs::read_to_string(&session.source_path).expect("read rollout");
+        assert!(content.contains("\"type\":\"user_message\""));
+        assert!(content.contains("\"model_provider\":\"openai\""));
+        assert!(content.contains(MISSING_TOOL_OUTPUT));
+        assert!(!content.contains("orphan"));
+        assert!(!content.contains("Let me check"));
+    }
+
+    #[test]
+    fn codex_rollout_records_the_configured_model_provider() {
+        let codex_home = tempfile::tempdir().expect("temp dir");
+        let sessions_root = codex_home.path().join("sessions");
+        std::fs::write(
+            codex_home.path().join("config.toml"),
+            "model_provider = \"azure\"\n\n[model_providers.azure]\nname = \"Azure\"\n",
+        )
+        .expect("write config");
+        let context = ToolSessionContext::Codex {
+            sessions_root: sessions_root.clone(),
+        };
+
+        write_converted_session(&context, &source_meta(Some("/repo")), &source_messages())
+            .expect("convert");
+
+        let sessions = codex::scan_sessions(&sessions_root);
+        assert_eq!(sessions.len(), 1);
+        let content = std::fs::read_to_string(&sessions[0].source_path).expect("read rollout");
+        assert!(content.contains("\"model_provider\":\"azure\""));
+    }
+
+    #[test]
+    fn codex_session_converts_into_claude_code_project_session() {
+        let root = tempfile::tempdir().expect("temp dir");
+        let context = ToolSessionContext::ClaudeCode {
+            projects_root: root.path().to_path_buf(),
+        };
+        let mut messages = vec![message_from_blocks(
+            "user",
+            None,
+            vec![text_block("<environment_context>cwd</environment_context>")],
+        )];
+        messages.extend(source_messages());
+        messages.push(message_from_blocks(
+            "assistant",
+            None,
+            vec![tool_call_block(
+                Some("call.abc".to_string()),
+                "shell",
+                Some(json!({ "command": "bash -lc ls" })),
+            )],
+        ));
+
+        let session_id =
+            write_converted_session(&context, &source_meta(Some("/home/dev/my_repo")), &messages)
+                .expect("convert");
+
+        let session_path = root
+            .path()
+            .join("-home-dev-my-repo")
+            .join(format!("{session_id}.jsonl"));
+        let sessions = claude_code::scan_sessions(root.path());
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(
+            sessions[0].resume_command.as_deref(),
+            Some(
+                build_resume_command(
+                    Some("/home/dev/my_repo"),
+                    &format!("claude --resume {session_id}")
+                )
+                .as_str()
+            )
+        );
+
+        let records: Vec<Value> = std::fs::read_to_string(&session_path)
+            .expect("read session")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("jsonl"))
+            .collect();
+        let roles: Vec<&str> = records
+            .iter()
+            .map(|record| record["type"].as_str().unwrap_or_default())
+            .collect();
+        assert_eq!(
+            roles,
+            vec!["user", "assistant", "user", "assistant", "user"]
+        );
+        assert!(records[0]["parentUuid"].is_null());
+        assert_eq!(records[1]["parentUuid"], records[0]["uuid"]);
+        let tool_results = records[2]["message"]["content"]
+            .as_array()
+            .expect("tool results");
+        assert_eq!(tool_results[0]["tool_use_id"], json!("toolu_01"));
+        assert_eq!(tool_results[0]["is_error"], json!(true));
+        assert_eq!(tool_results[1]["content"], json!(MISSING_TOOL_OUTPUT));
+        let last_call = &records[3]["message"]["content"][1];
+        assert_eq!(last_call["name"], json!("Bash"));
+        assert_eq!(last_call["id"], json!("call_abc"));
+        assert_eq!(last_call["input"], json!({ "command": "ls" }));
+
+        let loaded = claude_code::load_messages(&session_path).expect("load converted");
+        assert!(loaded
+            .iter()
+            .all(|message| !message.content.contains("environment_context")));
+    }
+
+    #[test]
+    fn conversion_requires_a_user_turn_and_supported_target() {
+        let root = tempfile::tempdir().expect("temp dir");
+        let codex_context = ToolSessionContext::Codex {
+            sessions_root: root.path().to_path_buf(),
+        };
+        let only_assistant = vec![message_from_blocks(
+            "assistant",
+            None,
+            vec![text_block("hello")],
+        )];
+        assert!(
+            write_converted_session(&codex_context, &source_meta(None), &only_assistant).is_err()
+        );
+
+        let grok_context = ToolSessionContext::Grok {
+            sessions_root: root.path().to_path_buf(),
+        };
+        assert!(
+            write_converted_session(&grok_context, &source_meta(None), &source_messages()).is_err()
+        );
+
+        let claude_context = ToolSessionContext::ClaudeCode {
+            projects_root: root.path().to_path_buf(),
+        };
+        assert!(
+            write_converted_session(&claude_context, &source_meta(None), &source_messages())
+                .is_err()
+        );
+    }
+}
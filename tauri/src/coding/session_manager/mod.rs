@@ -1,6 +1,8 @@
+mod analytics;
 mod claude_code;
 mod claude_desktop;
 mod codex;
+mod convert;
 mod dsh;
 mod gemini_cli;
 mod grok;
@@ -10,7 +12,10 @@ mod open_claw;
 mod open_code;
 mod oh_my_pi;
 mod pi;
+mod search_index;
 mod tool_normalizer;
+mod transcript;
+mod trash;
 mod utils;
 
 use std::collections::{HashMap, HashSet};
@@ -32,6 +37,18 @@ use crate::coding::runtime_location::{
 use crate::db::helpers::db_get;
 use crate::db::schema::DbTable;
 use crate::db::SqliteDbState;
+use analytics::{
+    AnalyticsGroupBy, GatewayPricer, SessionAnalytics, SessionAnalyticsResult, SessionUsageStats,
+};
+use search_index::{
+    IndexedContentMatches, SessionSearchFilters, SessionSearchResult, DEFAULT_SEARCH_LIMIT,
+    INDEXED_SESSION_TOOLS, MAX_SEARCH_LIMIT,
+};
+use trash::{
+    RestoreSessionTrashResult, SessionTrash, SessionTrashEntry, DEFAULT_TRASH_RETENTION_DAYS,
+};
+
+pub use trash::session_trash_dir;
 
 const SESSION_CACHE_TTL: Duration = Duration::from_secs(15);
 const MAX_SESSION_CACHE_ENTRIES: usize = 16;
@@ -422,7 +439,7 @@ struct SessionContextSet {
     available_sources: Vec<SessionSourceOption>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum SessionTool {
     Codex,
     ClaudeCode,
@@ -505,6 +522,27 @@ impl ToolSessionContext {
             Self::Dsh { sessions_root } => format!("dsh:{}", sessions_root.display()),
         }
     }
+
+    /// Directories that hold this tool's native session files.
+    fn session_roots(&self) -> Vec<&Path> {
+        match self {
+            Self::Codex { sessions_root }
+            | Self::Pi { sessions_root }
+            | Self::OhMyPi { sessions_root }
+            | Self::Grok { sessions_root }
+            | Self::ClaudeDesktop { sessions_root }
+            | Self::Hermes { sessions_root }
+            | Self::Dsh { sessions_root } => vec![sessions_root.as_path()],
+            Self::ClaudeCode { projects_root } => vec![projects_root.as_path()],
+            Self::GeminiCli { tmp_root } => vec![tmp_root.as_path()],
+            Self::OpenClaw { agents_root } => vec![agents_root.as_path()],
+            Self::OpenCode {
+                data_root,
+                state_root,
+                ..
+            } => vec![data_root.as_path(), state_root.as_path()],
+        }
+    }
 }
 
 #[tauri::command]
@@ -528,8 +566,21 @@ pub async fn list_tool_sessions(
     let source_mode = SessionSourceMode::parse(source_mode)?;
     let load_mode = SessionListLoadMode::parse(load_mode)?;
     let contexts = resolve_session_contexts(&state.db(), session_tool).await?;
+    let db = state.db().clone();
 
     tauri::async_runtime::spawn_blocking(move || {
+        let indexed_content_matches = query
+            .as_deref()
+            .filter(|_| load_mode != SessionListLoadMode::CacheFirst)
+            .and_then(|query_text| {
+                search_index::indexed_content_matches(
+                    &db,
+                    session_tool,
+                    &contexts,
+                    query_text,
+                    force_refresh,
+                )
+            });
         list_sessions_blocking(
             contexts,
             source_mode,
@@ -539,12 +590,186 @@ pub async fn list_tool_sessions(
             page_size as usize,
             force_refresh,
             load_mode,
+            indexed_content_matches,
         )
     })
     .await
     .map_err(|error| format!("Failed to list sessions: {error}"))?
 }
 
+/// Searches session transcripts of every indexed CLI (or the given subset)
+/// through the persistent full-text index, refreshing changed sessions first.
+#[tauri::command]
+pub async fn search_tool_sessions(
+    state: tauri::State<'_, SqliteDbState>,
+    query: String,
+    tools: Option<Vec<String>>,
+    project_dir: Option<String>,
+    model: Option<String>,
+    date_from: Option<i64>,
+    date_to: Option<i64>,
+    limit: Option<u32>,
+    force_refresh: Option<bool>,
+) -> Result<SessionSearchResult, String> {
+    let session_tools = parse_session_tools(tools)?;
+    let filters = SessionSearchFilters {
+        project_dir: normalize_query(project_dir),
+        model: normalize_query(model),
+        date_from,
+        date_to,
+    };
+    let limit = limit
+        .map(|value| value as usize)
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+    let force_refresh = force_refresh.unwrap_or(false);
+
+    let mut tool_contexts = Vec::new();
+    for session_tool in &session_tools {
+        match resolve_session_contexts(&state.db(), *session_tool).await {
+            Ok(contexts) => tool_contexts.push((*session_tool, contexts)),
+            Err(error) => log::warn!(
+                "Skipping {} sessions in search index: {error}",
+                session_tool.as_str()
+            ),
+        }
+    }
+    let db = state.db().clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut result = SessionSearchResult {
+            items: Vec::new(),
+            total: 0,
+            indexed_count: 0,
+            reindexed_count: 0,
+            failed_count: 0,
+        };
+        for (session_tool, contexts) in &tool_contexts {
+            let stats =
+                search_index::refresh_tool_index(&db, *session_tool, contexts, force_refresh)?;
+            result.indexed_count += stats.indexed_count;
+            result.reindexed_count += stats.reindexed_count;
+            result.failed_count += stats.failed_paths.len();
+        }
+
+        let (items, total) =
+            search_index::search_index(&db, &session_tools, query.trim(), &filters, limit)?;
+        result.items = items;
+        result.total = total;
+        for (session_tool, contexts) in &tool_contexts {
+            let rescanned = search_index::search_truncated_sessions(
+                &db,
+                *session_tool,
+                contexts,
+                query.trim(),
+                &filters,
+            )?;
+            result.total += rescanned.len();
+            let room = limit.saturating_sub(result.items.len());
+            result.items.extend(rescanned.into_iter().take(room));
+        }
+        Ok(result)
+    })
+    .await
+    .map_err(|error| format!("Failed to search sessions: {error}"))?
+}
+
+/// Aggregates token usage, cost, tool calls and wall time across the session
+/// files of one or more CLIs, grouped by session, project, tool or day.
+#[tauri::command]
+pub async fn get_tool_session_analytics(
+    state: tauri::State<'_, SqliteDbState>,
+    tools: Option<Vec<String>>,
+    group_by: Option<String>,
+    project_dir: Option<String>,
+    date_from: Option<i64>,
+    date_to: Option<i64>,
+) -> Result<SessionAnalyticsResult, String> {
+    let session_tools = parse_session_tools(tools)?;
+    let group_by = AnalyticsGroupBy::parse(group_by.as_deref())?;
+    let project_dir = normalize_query(project_dir).map(|value| value.to_lowercase());
+
+    let mut tool_contexts = Vec::new();
+    for session_tool in &session_tools {
+        match resolve_session_contexts(&state.db(), *session_tool).await {
+            Ok(contexts) => tool_contexts.push((*session_tool, contexts)),
+            Err(error) => log::warn!(
+                "Skipping {} sessions in analytics: {error}",
+                session_tool.as_str()
+            ),
+        }
+    }
+    let db = state.db().clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut pricer = GatewayPricer::new(&db);
+        let mut price = |model: &str, usage: &SessionMessageUsage| pricer.price(model, usage);
+        let mut analytics = SessionAnalytics::new(group_by);
+        for (session_tool, contexts) in &tool_contexts {
+            for session in collect_sessions_with_context(contexts, SessionSourceMode::All, false) {
+                let meta = &session.meta;
+                if let Some(project_dir) = project_dir.as_deref() {
+                    let matches_project = meta
+                        .project_dir
+                        .as_deref()
+                        .is_some_and(|value| value.to_lowercase().contains(project_dir));
+                    if !matches_project {
+                        continue;
+                    }
+                }
+                let active_at = meta.last_active_at.or(meta.created_at);
+                if date_from.is_some_and(|from| active_at.is_none_or(|value| value < from))
+                    || date_to.is_some_and(|to| active_at.is_none_or(|value| value > to))
+                {
+                    continue;
+                }
+
+                let context = &contexts.entries[session.context_index].context;
+                match load_messages(context, &meta.source_path) {
+                    Ok(messages) => {
+                        analytics.add_session(session_tool.as_str(), meta, &messages, &mut price)
+                    }
+                    Err(error) => {
+                        log::warn!(
+                            "Skipping session {} in analytics: {error}",
+                            meta.source_path
+                        );
+                        analytics.record_failure();
+                    }
+                }
+            }
+        }
+        Ok(analytics.finish())
+    })
+    .await
+    .map_err(|error| format!("Failed to compute session analytics: {error}"))?
+}
+
+#[tauri::command]
+pub async fn get_tool_session_usage(
+    state: tauri::State<'_, SqliteDbState>,
+    tool: String,
+    source_path: String,
+) -> Result<SessionUsageStats, String> {
+    let session_tool = SessionTool::parse(tool.trim())?;
+    let contexts = resolve_session_contexts(&state.db(), session_tool).await?;
+    let db = state.db().clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let (entry, meta) = find_session_with_context(&contexts, &source_path, false)?;
+        let messages = load_messages(&entry.context, &meta.source_path)?;
+        let mut pricer = GatewayPricer::new(&db);
+        Ok(analytics::session_usage_stats(
+            session_tool.as_str(),
+            &meta,
+            &messages,
+            &mut |model, usage| pricer.price(model, usage),
+        ))
+    })
+    .await
+    .map_err(|error| format!("Failed to compute session usage: {error}"))?
+}
+
 #[tauri::command]
 pub async fn list_tool_session_paths(
     state: tauri::State<'_, SqliteDbState>,
@@ -614,32 +839,125 @@ pub async fn get_tool_subagent_session_detail(
     .map_err(|error| format!("Failed to load subagent session detail: {error}"))?
 }
 
+/// Deleted sessions go to the session trash unless `permanent` is set.
 #[tauri::command]
 pub async fn delete_tool_session(
+    app: tauri::AppHandle,
     state: tauri::State<'_, SqliteDbState>,
     tool: String,
     source_path: String,
+    permanent: Option<bool>,
 ) -> Result<(), String> {
     let session_tool = SessionTool::parse(tool.trim())?;
     let contexts = resolve_session_contexts(&state.db(), session_tool).await?;
+    let trash = resolve_session_trash(&app, session_tool, permanent)?;
 
-    tauri::async_runtime::spawn_blocking(move || delete_session_blocking(contexts, source_path))
-        .await
-        .map_err(|error| format!("Failed to delete session: {error}"))?
+    tauri::async_runtime::spawn_blocking(move || {
+        delete_session_blocking(contexts, source_path, trash.as_ref())
+    })
+    .await
+    .map_err(|error| format!("Failed to delete session: {error}"))?
 }
 
 #[tauri::command]
 pub async fn delete_tool_sessions(
+    app: tauri::AppHandle,
     state: tauri::State<'_, SqliteDbState>,
     tool: String,
     source_paths: Vec<String>,
+    permanent: Option<bool>,
 ) -> Result<DeleteToolSessionsResult, String> {
     let session_tool = SessionTool::parse(tool.trim())?;
     let contexts = resolve_session_contexts(&state.db(), session_tool).await?;
+    let trash = resolve_session_trash(&app, session_tool, permanent)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        delete_sessions_blocking(contexts, source_paths, trash.as_ref())
+    })
+    .await
+    .map_err(|error| format!("Failed to delete sessions: {error}"))
+}
+
+#[tauri::command]
+pub async fn list_session_trash(app: tauri::AppHandle) -> Result<Vec<SessionTrashEntry>, String> {
+    let trash_root = session_trash_dir(&app)?;
 
-    tauri::async_runtime::spawn_blocking(move || delete_sessions_blocking(contexts, source_paths))
+    tauri::async_runtime::spawn_blocking(move || trash::list_entries(&trash_root))
         .await
-        .map_err(|error| format!("Failed to delete sessions: {error}"))
+        .map_err(|error| format!("Failed to list session trash: {error}"))?
+}
+
+#[tauri::command]
+pub async fn restore_session_trash(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SqliteDbState>,
+    ids: Vec<String>,
+) -> Result<RestoreSessionTrashResult, String> {
+    let trash_root = session_trash_dir(&app)?;
+    let requested_ids = ids.iter().map(|id| id.trim()).collect::<HashSet<_>>();
+    let mut contexts_by_tool = HashMap::new();
+    for entry in trash::list_entries(&trash_root)? {
+        if !requested_ids.contains(entry.id.as_str()) || contexts_by_tool.contains_key(&entry.tool)
+        {
+            continue;
+        }
+        // A tool whose contexts cannot be resolved only fails its own entries.
+        let contexts = match SessionTool::parse(&entry.tool) {
+            Ok(session_tool) => resolve_session_contexts(&state.db(), session_tool).await,
+            Err(error) => Err(error),
+        };
+        contexts_by_tool.insert(entry.tool, contexts);
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        trash::restore_entries(&trash_root, ids, &contexts_by_tool)
+    })
+    .await
+    .map_err(|error| format!("Failed to restore sessions from trash: {error}"))
+}
+
+/// Purge the given trash entries, or everything older than `older_than_days`,
+/// or the whole trash when neither is provided.
+#[tauri::command]
+pub async fn purge_session_trash(
+    app: tauri::AppHandle,
+    ids: Option<Vec<String>>,
+    older_than_days: Option<u32>,
+) -> Result<usize, String> {
+    let trash_root = session_trash_dir(&app)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        trash::purge_entries(
+            &trash_root,
+            ids.as_deref(),
+            older_than_days,
+            Utc::now().timestamp_millis(),
+        )
+    })
+    .await
+    .map_err(|error| format!("Failed to purge session trash: {error}"))?
+}
+
+/// Trash destination for a delete request; expired entries are purged on the way.
+fn resolve_session_trash(
+    app: &tauri::AppHandle,
+    tool: SessionTool,
+    permanent: Option<bool>,
+) -> Result<Option<SessionTrash>, String> {
+    if permanent.unwrap_or(false) {
+        return Ok(None);
+    }
+
+    let trash_root = session_trash_dir(app)?;
+    if let Err(error) = trash::purge_entries(
+        &trash_root,
+        None,
+        Some(DEFAULT_TRASH_RETENTION_DAYS),
+        Utc::now().timestamp_millis(),
+    ) {
+        log::warn!("Failed to purge expired session trash entries: {error}");
+    }
+    Ok(Some(SessionTrash::new(trash_root, tool.as_str())))
 }
 
 #[tauri::command]
@@ -726,6 +1044,37 @@ pub async fn rename_tool_session(
     .map_err(|error| format!("Failed to rename session: {error}"))?
 }
 
+#[tauri::command]
+pub async fn convert_tool_session(
+    state: tauri::State<'_, SqliteDbState>,
+    tool: String,
+    source_path: String,
+    target_tool: String,
+) -> Result<SessionMeta, String> {
+    let session_tool = SessionTool::parse(tool.trim())?;
+    let target_session_tool = SessionTool::parse(target_tool.trim())?;
+    if session_tool == target_session_tool {
+        return Err("Source and target tools must differ".to_string());
+    }
+    if !matches!(
+        target_session_tool,
+        SessionTool::Codex | SessionTool::ClaudeCode
+    ) {
+        return Err(format!(
+            "Converting sessions into {} is not supported",
+            target_session_tool.as_str()
+        ));
+    }
+    let contexts = resolve_session_contexts(&state.db(), session_tool).await?;
+    let target_context = resolve_context(&state.db(), target_session_tool).await?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        convert_session_blocking(contexts, source_path, target_context)
+    })
+    .await
+    .map_err(|error| format!("Failed to convert session: {error}"))?
+}
+
 #[derive(Debug, Clone)]
 struct SessionWithContext {
     context_index: usize,
@@ -936,6 +1285,7 @@ fn filter_sessions_by_query_with_context(
     sessions: Vec<SessionWithContext>,
     query: &str,
     include_message_content: bool,
+    indexed_content_matches: Option<&IndexedContentMatches>,
 ) -> (Vec<SessionWithContext>, bool) {
     let query_lower = query.to_lowercase();
     let exact_session_id_matches: Vec<SessionWithContext> = sessions
@@ -958,6 +1308,15 @@ fn filter_sessions_by_query_with_context(
                 return false;
             }
 
+            if let Some(matches) = indexed_content_matches {
+                if matches.matched.contains(&session.meta.source_path) {
+                    return true;
+                }
+                if !matches.needs_scan.contains(&session.meta.source_path) {
+                    return false;
+                }
+            }
+
             contexts
                 .entries
                 .get(session.context_index)
@@ -985,6 +1344,7 @@ fn list_sessions_blocking(
     page_size: usize,
     force_refresh: bool,
     load_mode: SessionListLoadMode,
+    indexed_content_matches: Option<IndexedContentMatches>,
 ) -> Result<SessionListPage, String> {
     let use_quick_initial_page =
         page == 1 && page_size <= 10 && query.is_none() && path_filter.is_none() && !force_refresh;
@@ -1063,6 +1423,7 @@ fn list_sessions_blocking(
             path_filtered_sessions,
             query_text,
             include_message_content,
+            indexed_content_matches.as_ref(),
         )
     } else {
         (path_filtered_sessions, false)
@@ -1147,10 +1508,14 @@ fn list_session_paths_blocking(
     Ok(build_session_paths(&sessions, limit))
 }
 
-fn delete_session_blocking(contexts: SessionContextSet, source_path: String) -> Result<(), String> {
+fn delete_session_blocking(
+    contexts: SessionContextSet,
+    source_path: String,
+    trash: Option<&SessionTrash>,
+) -> Result<(), String> {
     match find_session_with_context(&contexts, &source_path, true) {
         Ok((entry, session)) => {
-            delete_session_from_meta(&entry.context, &session)?;
+            remove_session(&entry.context, &session, trash)?;
             invalidate_cache(&entry.context);
             Ok(())
         }
@@ -1196,6 +1561,17 @@ fn session_dedupe_key(context: &ToolSessionContext, source_path: &str) -> String
     format!("{}:{}", context.cache_key(), source_key)
 }
 
+fn remove_session(
+    context: &ToolSessionContext,
+    session: &SessionMeta,
+    trash: Option<&SessionTrash>,
+) -> Result<(), String> {
+    match trash {
+        Some(trash) => trash.trash_session(context, session),
+        None => delete_session_from_meta(context, session),
+    }
+}
+
 fn delete_session_from_meta(
     context: &ToolSessionContext,
     session: &SessionMeta,
@@ -1242,6 +1618,7 @@ fn delete_session_from_meta(
 fn delete_sessions_blocking(
     contexts: SessionContextSet,
     source_paths: Vec<String>,
+    trash: Option<&SessionTrash>,
 ) -> DeleteToolSessionsResult {
     let mut deleted_count = 0usize;
     let mut failed_items = Vec::new();
@@ -1270,7 +1647,7 @@ fn delete_sessions_blocking(
             continue;
         }
 
-        match delete_session_from_meta(&entry.context, &session) {
+        match remove_session(&entry.context, &session, trash) {
             Ok(()) => {
                 deleted_count += 1;
                 invalidate_cache(&entry.context);
@@ -1439,6 +1816,8 @@ fn build_exported_session_file(
 fn export_file_extension<'a>(tool: &str, export_format: &'a str) -> Result<&'a str, String> {
     match export_format {
         "ai_toolbox" => Ok("json"),
+        "markdown" => Ok("md"),
+        "html" => Ok("html"),
         "grok_markdown" if tool == "grok" => Ok("md"),
         "grok_native" if tool == "grok" => Ok("json"),
         "grok_markdown" | "grok_native" => {
@@ -1469,6 +1848,20 @@ fn export_session_to_path(
                 build_exported_session_file(context, tool.to_string(), session_detail)?;
             write_exported_session_file(&exported_file, export_path)
         }
+        "markdown" | "html" => {
+            let messages = load_messages(context, &meta.source_path)?;
+            let session_detail = SessionDetail {
+                meta: meta.clone(),
+                messages,
+            };
+            let subagents = load_transcript_subagents(context, &meta.source_path);
+            let rendered = if export_format == "markdown" {
+                transcript::render_markdown(tool, &session_detail, &subagents)
+            } else {
+                transcript::render_html(tool, &session_detail, &subagents)
+            };
+            write_transcript_file(&rendered, export_path)
+        }
         "grok_markdown" => match context {
             ToolSessionContext::Grok { sessions_root } => {
                 grok::export_markdown(sessions_root, &meta.session_id, export_path)
@@ -1518,6 +1911,39 @@ fn write_json_value_to_path(value: &Value, path: &Path) -> Result<(), String> {
     })
 }
 
+/// Subagents whose transcript cannot be read are left out rather than failing
+/// the whole export.
+fn load_transcript_subagents(
+    context: &ToolSessionContext,
+    source_path: &str,
+) -> Vec<transcript::TranscriptSubagent> {
+    list_subagent_sessions(context, source_path)
+        .into_iter()
+        .filter_map(|meta| {
+            let messages = load_messages(context, &meta.source_path).ok()?;
+            Some(transcript::TranscriptSubagent { meta, messages })
+        })
+        .collect()
+}
+
+fn write_transcript_file(content: &str, export_path_ref: &Path) -> Result<(), String> {
+    if let Some(parent_dir) = export_path_ref.parent() {
+        std::fs::create_dir_all(parent_dir).map_err(|error| {
+            format!(
+                "Failed to create export directory {}: {error}",
+                parent_dir.display()
+            )
+        })?;
+    }
+
+    std::fs::write(export_path_ref, content).map_err(|error| {
+        format!(
+            "Failed to write session transcript {}: {error}",
+            export_path_ref.display()
+        )
+    })
+}
+
 fn write_exported_session_file(
     exported_file: &ExportedSessionFile,
     export_path_ref: &Path,
@@ -1807,6 +2233,22 @@ fn rename_session_blocking(
     }
 }
 
+fn convert_session_blocking(
+    contexts: SessionContextSet,
+    source_path: String,
+    target: ToolSessionContext,
+) -> Result<SessionMeta, String> {
+    let (entry, meta) = find_session_with_context(&contexts, &source_path, false)?;
+    let messages = load_messages(&entry.context, &meta.source_path)?;
+    let session_id = convert::write_converted_session(&target, &meta, &messages)?;
+    invalidate_cache(&target);
+
+    get_cached_sessions(&target, true)
+        .into_iter()
+        .find(|session| session.session_id == session_id)
+        .ok_or_else(|| format!("Converted session {session_id} was written but not found"))
+}
+
 fn build_native_snapshot(
     source_path: &str,
     meta: &SessionMeta,
@@ -2209,6 +2651,22 @@ fn contains_query(value: &str, query_lower: &str) -> bool {
     value.to_lowercase().contains(query_lower)
 }
 
+/// Parses an optional tool filter, defaulting to every tool with readable
+/// session files.
+fn parse_session_tools(tools: Option<Vec<String>>) -> Result<Vec<SessionTool>, String> {
+    let Some(tools) = tools.filter(|tools| !tools.is_empty()) else {
+        return Ok(INDEXED_SESSION_TOOLS.to_vec());
+    };
+    let mut parsed: Vec<SessionTool> = Vec::new();
+    for tool in tools {
+        let session_tool = SessionTool::parse(tool.trim())?;
+        if !parsed.contains(&session_tool) {
+            parsed.push(session_tool);
+        }
+    }
+    Ok(parsed)
+}
+
 fn normalize_query(query: Option<String>) -> Option<String> {
     query
         .map(|value| value.trim().to_string())
@@ -2924,8 +3382,13 @@ mod tests {
             },
         ];
 
-        let (filtered, exact_session_id_match) =
-            filter_sessions_by_query_with_context(&contexts, sessions, exact_session_id, true);
+        let (filtered, exact_session_id_match) = filter_sessions_by_query_with_context(
+            &contexts,
+            sessions,
+            exact_session_id,
+            true,
+            None,
+        );
 
         assert!(exact_session_id_match);
         assert_eq!(filtered.len(), 1);
@@ -3005,6 +3468,7 @@ mod tests {
             10,
             false,
             SessionListLoadMode::CacheFirst,
+            None,
         )
         .expect("cache-first list should succeed");
 
@@ -3026,6 +3490,7 @@ mod tests {
             10,
             false,
             SessionListLoadMode::Full,
+            None,
         )
         .expect("full list should succeed");
 
@@ -3256,6 +3721,7 @@ mod tests {
                 missing_session_path.to_string_lossy().to_string(),
                 another_session_path.to_string_lossy().to_string(),
             ],
+            None,
         );
 
         assert_eq!(result.deleted_count, 2);
@@ -3417,6 +3883,7 @@ mod tests {
         delete_session_blocking(
             single_context_set(context),
             message_dir.to_string_lossy().to_string(),
+            None,
         )
         .expect("opencode direct delete should succeed without prescan");
 
@@ -3459,6 +3926,7 @@ mod tests {
         delete_session_blocking(
             single_context_set(context),
             missing_message_dir.to_string_lossy().to_string(),
+            None,
         )
         .expect("missing opencode delete should remain idempotent");
     }
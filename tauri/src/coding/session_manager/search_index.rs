@@ -0,0 +1,1010 @@
+//! Persistent full-text index over session transcripts.
+//!
+//! Every indexed session owns one row in `session_search_documents` (keyed by
+//! tool + `source_path`) and one row in the trigram `session_search_fts` table
+//! sharing its rowid. A refresh compares each session's source stamp (file
+//! mtime or last activity) with the stored one and only re-reads transcripts
+//! that changed, so repeated searches no longer reparse every session file.
+//! Transcripts cut at the indexing cap or that failed to load are still
+//! scanned from disk so content matches past the indexed text aren't lost.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use chrono::Utc;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, params_from_iter, Connection};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    annotate_session_source, find_session_with_context, get_cached_sessions, load_messages,
+    scan_session_content_for_query, SessionContextSet, SessionMessage, SessionMeta, SessionTool,
+};
+use crate::db::SqliteDbState;
+
+pub(super) const DEFAULT_SEARCH_LIMIT: usize = 50;
+pub(super) const MAX_SEARCH_LIMIT: usize = 200;
+/// Transcripts larger than this are truncated before indexing and flagged so
+/// content searches rescan them.
+const MAX_INDEXED_CONTENT_CHARS: usize = 2_000_000;
+/// The trigram tokenizer cannot match terms shorter than three characters, so
+/// those queries fall back to a `LIKE` scan over the indexed text.
+const MIN_TRIGRAM_TERM_CHARS: usize = 3;
+/// Trigram tokens are roughly characters, so this is close to the FTS5 maximum.
+const SNIPPET_TOKENS: i32 = 64;
+const SNIPPET_FALLBACK_CHARS: i64 = 160;
+/// Document columns read by `query_hits`, followed by `snippet` and `rank`.
+const HIT_COLUMNS: &str = "d.tool, d.source_path, d.session_id, d.title, d.summary, d.project_dir,
+    d.created_at, d.last_active_at, d.resume_command, d.runtime_source, d.runtime_distro,
+    d.models, d.provider_id";
+
+/// Tools whose transcripts are included in the cross-tool index. Claude
+/// Desktop is left out because its sessions have no searchable content.
+pub(super) const INDEXED_SESSION_TOOLS: [SessionTool; 10] = [
+    SessionTool::Codex,
+    SessionTool::ClaudeCode,
+    SessionTool::GeminiCli,
+    SessionTool::OpenCode,
+    SessionTool::OpenClaw,
+    SessionTool::Pi,
+    SessionTool::OhMyPi,
+    SessionTool::Grok,
+    SessionTool::Hermes,
+    SessionTool::Dsh,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSearchHit {
+    pub tool: String,
+    pub session: SessionMeta,
+    pub snippet: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub models: Vec<String>,
+    /// bm25 score; lower is a better match. Zero for substring fallbacks.
+    pub rank: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSearchResult {
+    pub items: Vec<SessionSearchHit>,
+    pub total: usize,
+    pub indexed_count: usize,
+    pub reindexed_count: usize,
+    pub failed_count: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct SessionSearchFilters {
+    pub project_dir: Option<String>,
+    pub model: Option<String>,
+    pub date_from: Option<i64>,
+    pub date_to: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct IndexRefreshStats {
+    pub indexed_count: usize,
+    pub reindexed_count: usize,
+    /// Sessions whose transcript could not be loaded. Their stored document,
+    /// if any, keeps its old stamp so the next refresh retries them.
+    pub failed_paths: Vec<String>,
+}
+
+/// Content matches for one tool as far as the index can answer them.
+#[derive(Debug, Clone, Default)]
+pub(super) struct IndexedContentMatches {
+    pub matched: HashSet<String>,
+    /// Truncated or unloadable sessions the index can't rule out; callers
+    /// scan these files directly.
+    pub needs_scan: HashSet<String>,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedSessionDocument {
+    meta: SessionMeta,
+    source_stamp: i64,
+    models: Vec<String>,
+    content: String,
+    content_truncated: bool,
+}
+
+/// Brings the index for one tool in line with the sessions currently on disk:
+/// new or changed transcripts are (re)indexed and vanished ones are dropped.
+pub(super) fn refresh_tool_index(
+    db: &SqliteDbState,
+    tool: SessionTool,
+    contexts: &SessionContextSet,
+    force_refresh: bool,
+) -> Result<IndexRefreshStats, String> {
+    let tool_key = tool.as_str();
+    let mut current = Vec::new();
+    for entry in &contexts.entries {
+        for session in get_cached_sessions(&entry.context, force_refresh) {
+            current.push((entry, annotate_session_source(session, entry)));
+        }
+    }
+
+    let stored = db.with_conn(|conn| load_stored_stamps(conn, tool_key))?;
+    let mut stats = IndexRefreshStats {
+        indexed_count: current.len(),
+        ..Default::default()
+    };
+    let mut documents = Vec::new();
+    for (entry, meta) in &current {
+        let source_stamp = session_source_stamp(meta);
+        let unchanged = stored
+            .get(&meta.source_path)
+            .map(|(stamp, title)| *stamp == source_stamp && *title == meta.title)
+            .unwrap_or(false);
+        if unchanged && !force_refresh {
+            continue;
+        }
+
+        match load_messages(&entry.context, &meta.source_path) {
+            Ok(messages) => documents.push(build_document(meta.clone(), source_stamp, &messages)),
+            Err(error) => {
+                log::warn!(
+                    "Failed to index session {} for search: {error}",
+                    meta.source_path
+                );
+                stats.failed_paths.push(meta.source_path.clone());
+            }
+        }
+    }
+    stats.reindexed_count = documents.len();
+
+    let live_paths: HashSet<&str> = current
+        .iter()
+        .map(|(_, meta)| meta.source_path.as_str())
+        .collect();
+    let removed_paths: Vec<String> = stored
+        .keys()
+        .filter(|path| !live_paths.contains(path.as_str()))
+        .cloned()
+        .collect();
+
+    if documents.is_empty() && removed_paths.is_empty() {
+        return Ok(stats);
+    }
+
+    db.with_conn_mut(|conn| write_documents(conn, tool_key, &documents, &removed_paths))?;
+    Ok(stats)
+}
+
+pub(super) fn search_index(
+    db: &SqliteDbState,
+    tools: &[SessionTool],
+    query: &str,
+    filters: &SessionSearchFilters,
+    limit: usize,
+) -> Result<(Vec<SessionSearchHit>, usize), String> {
+    db.with_conn(|conn| search_documents(conn, tools, query, filters, limit))
+}
+
+/// Source paths of `tool` sessions whose transcript contains `query`, served
+/// from the index so `list_tool_sessions` no longer rescans every file. Returns
+/// `None` when the tool is not indexed or the index is unavailable, in which
+/// case callers fall back to the per-file scanners.
+pub(super) fn indexed_content_matches(
+    db: &SqliteDbState,
+    tool: SessionTool,
+    contexts: &SessionContextSet,
+    query: &str,
+    force_refresh: bool,
+) -> Option<IndexedContentMatches> {
+    if !INDEXED_SESSION_TOOLS
+        .iter()
+        .any(|indexed| indexed.as_str() == tool.as_str())
+    {
+        return None;
+    }
+
+    let result = refresh_tool_index(db, tool, contexts, force_refresh).and_then(|stats| {
+        db.with_conn(|conn| {
+            let matched = matching_source_paths(conn, tool.as_str(), query)?;
+            let mut needs_scan = truncated_source_paths(conn, tool.as_str())?;
+            needs_scan.extend(stats.failed_paths);
+            needs_scan.retain(|path| !matched.contains(path));
+            Ok(IndexedContentMatches {
+                matched,
+                needs_scan,
+            })
+        })
+    });
+    match result {
+        Ok(matches) => Some(matches),
+        Err(error) => {
+            log::warn!(
+                "Session search index unavailable for {}: {error}",
+                tool.as_str()
+            );
+            None
+        }
+    }
+}
+
+/// Case-insensitive substring match of the whole query, mirroring the
+/// semantics of the per-tool `scan_messages_for_query` helpers.
+fn matching_source_paths(
+    conn: &Connection,
+    tool: &str,
+    query: &str,
+) -> Result<HashSet<String>, String> {
+    let (condition, value) = if query.chars().count() >= MIN_TRIGRAM_TERM_CHARS {
+        (
+            "session_search_fts.content MATCH ?2",
+            build_match_expression(&[query]),
+        )
+    } else {
+        (
+            "session_search_fts.content LIKE ?2 ESCAPE '\\'",
+            like_pattern(query),
+        )
+    };
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT d.source_path
+             FROM session_search_fts
+             JOIN session_search_documents d ON d.id = session_search_fts.rowid
+             WHERE d.tool = ?1 AND {condition}"
+        ))
+        .map_err(|error| format!("Failed to prepare session content search: {error}"))?;
+    let rows = stmt
+        .query_map(params![tool, value], |row| row.get::<_, String>(0))
+        .map_err(|error| format!("Failed to search session content: {error}"))?;
+
+    rows.collect::<Result<HashSet<_>, _>>()
+        .map_err(|error| format!("Failed to read session content match: {error}"))
+}
+
+fn truncated_source_paths(conn: &Connection, tool: &str) -> Result<HashSet<String>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT source_path FROM session_search_documents
+             WHERE tool = ?1 AND content_truncated = 1",
+        )
+        .map_err(|error| format!("Failed to prepare session index query: {error}"))?;
+    let rows = stmt
+        .query_map([tool], |row| row.get::<_, String>(0))
+        .map_err(|error| format!("Failed to read session index: {error}"))?;
+
+    rows.collect::<Result<HashSet<_>, _>>()
+        .map_err(|error| format!("Failed to read session index row: {error}"))
+}
+
+fn session_source_stamp(meta: &SessionMeta) -> i64 {
+    let modified_ms = std::fs::metadata(Path::new(&meta.source_path))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0);
+    modified_ms.max(meta.last_active_at.unwrap_or(0))
+}
+
+fn build_document(
+    meta: SessionMeta,
+    source_stamp: i64,
+    messages: &[SessionMessage],
+) -> IndexedSessionDocument {
+    let mut models = BTreeSet::new();
+    let mut content = String::new();
+    let mut content_truncated = false;
+    for message in messages {
+        if let Some(model) = message.model.as_deref().map(str::trim) {
+            if !model.is_empty() {
+                models.insert(model.to_string());
+            }
+        }
+        append_search_text(&mut content, &message.content);
+        for block in &message.blocks {
+            if let Some(text) = block.text.as_deref() {
+                if text != message.content {
+                    append_search_text(&mut content, text);
+                }
+            }
+        }
+        if content.len() >= MAX_INDEXED_CONTENT_CHARS {
+            content_truncated = true;
+            break;
+        }
+    }
+    if content.len() > MAX_INDEXED_CONTENT_CHARS {
+        let mut cut = MAX_INDEXED_CONTENT_CHARS;
+        while !content.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        content.truncate(cut);
+    }
+
+    IndexedSessionDocument {
+        meta,
+        source_stamp,
+        models: models.into_iter().collect(),
+        content,
+        content_truncated,
+    }
+}
+
+fn append_search_text(content: &mut String, text: &str) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    if !content.is_empty() {
+        content.push('\n');
+    }
+    content.push_str(text);
+}
+
+fn document_title_text(meta: &SessionMeta) -> String {
+    [
+        meta.title.as_deref(),
+        meta.summary.as_deref(),
+        Some(meta.session_id.as_str()),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|value| !value.trim().is_empty())
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn load_stored_stamps(
+    conn: &Connection,
+    tool: &str,
+) -> Result<HashMap<String, (i64, Option<String>)>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT source_path, source_stamp, title
+             FROM session_search_documents WHERE tool = ?1",
+        )
+        .map_err(|error| format!("Failed to prepare session index query: {error}"))?;
+    let rows = stmt
+        .query_map([tool], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                (row.get::<_, i64>(1)?, row.get::<_, Option<String>>(2)?),
+            ))
+        })
+        .map_err(|error| format!("Failed to read session index: {error}"))?;
+
+    rows.collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|error| format!("Failed to read session index row: {error}"))
+}
+
+fn write_documents(
+    conn: &mut Connection,
+    tool: &str,
+    documents: &[IndexedSessionDocument],
+    removed_paths: &[String],
+) -> Result<(), String> {
+    let tx = conn
+        .transaction()
+        .map_err(|error| format!("Failed to start session index transaction: {error}"))?;
+    let indexed_at = Utc::now().to_rfc3339();
+
+    for source_path in removed_paths
+        .iter()
+        .map(String::as_str)
+        .chain(documents.iter().map(|doc| doc.meta.source_path.as_str()))
+    {
+        delete_document(&tx, tool, source_path)?;
+    }
+
+    for document in documents {
+        let meta = &document.meta;
+        tx.execute(
+            "INSERT INTO session_search_documents (
+                tool, source_path, source_stamp, provider_id, session_id, title, summary,
+                project_dir, models, created_at, last_active_at, resume_command,
+                runtime_source, runtime_distro, indexed_at, content_truncated
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+            params![
+                tool,
+                meta.source_path,
+                document.source_stamp,
+                meta.provider_id,
+                meta.session_id,
+                meta.title,
+                meta.summary,
+                meta.project_dir,
+                document.models.join("\n"),
+                meta.created_at,
+                meta.last_active_at,
+                meta.resume_command,
+                meta.runtime_source,
+                meta.runtime_distro,
+                indexed_at,
+                document.content_truncated,
+            ],
+        )
+        .map_err(|error| format!("Failed to index session {}: {error}", meta.source_path))?;
+        let rowid = tx.last_insert_rowid();
+        tx.execute(
+            "INSERT INTO session_search_fts (rowid, title, content) VALUES (?1, ?2, ?3)",
+            params![rowid, document_title_text(meta), document.content],
+        )
+        .map_err(|error| format!("Failed to index session {}: {error}", meta.source_path))?;
+    }
+
+    tx.commit()
+        .map_err(|error| format!("Failed to commit session index: {error}"))
+}
+
+fn delete_document(conn: &Connection, tool: &str, source_path: &str) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM session_search_fts WHERE rowid IN (
+            SELECT id FROM session_search_documents WHERE tool = ?1 AND source_path = ?2
+        )",
+        params![tool, source_path],
+    )
+    .map_err(|error| format!("Failed to remove session {source_path} from index: {error}"))?;
+    conn.execute(
+        "DELETE FROM session_search_documents WHERE tool = ?1 AND source_path = ?2",
+        params![tool, source_path],
+    )
+    .map_err(|error| format!("Failed to remove session {source_path} from index: {error}"))?;
+    Ok(())
+}
+
+fn search_documents(
+    conn: &Connection,
+    tools: &[SessionTool],
+    query: &str,
+    filters: &SessionSearchFilters,
+    limit: usize,
+) -> Result<(Vec<SessionSearchHit>, usize), String> {
+    let terms: Vec<&str> = query.split_whitespace().collect();
+    let use_fts = uses_fts(&terms);
+
+    let mut conditions = Vec::new();
+    let mut values: Vec<SqlValue> = Vec::new();
+    push_term_conditions(&terms, use_fts, &mut conditions, &mut values);
+
+    let tool_placeholders = vec!["?"; tools.len()].join(", ");
+    conditions.push(format!("d.tool IN ({tool_placeholders})"));
+    values.extend(
+        tools
+            .iter()
+            .map(|tool| SqlValue::Text(tool.as_str().to_string())),
+    );
+    push_filter_conditions(filters, &mut conditions, &mut values);
+
+    let from_clause = format!(
+        "FROM session_search_fts
+         JOIN session_search_documents d ON d.id = session_search_fts.rowid
+         WHERE {}",
+        conditions.join(" AND ")
+    );
+
+    let total: i64 = conn
+        .query_row(
+            &format!("SELECT COUNT(*) {from_clause}"),
+            params_from_iter(values.iter()),
+            |row| row.get(0),
+        )
+        .map_err(|error| format!("Failed to count session search results: {error}"))?;
+
+    let mut select_values = Vec::new();
+    let (snippet_expr, rank_expr, order_clause) = if use_fts {
+        (
+            format!("snippet(session_search_fts, -1, '', '', '…', {SNIPPET_TOKENS})"),
+            "bm25(session_search_fts, 4.0, 1.0)".to_string(),
+            "ORDER BY rank ASC, COALESCE(d.last_active_at, d.created_at, 0) DESC",
+        )
+    } else if let Some(first_term) = terms.first() {
+        // LIKE is ASCII case-insensitive, so mirror that when locating the snippet.
+        select_values.push(SqlValue::Text(first_term.to_ascii_lowercase()));
+        (
+            format!(
+                "substr(session_search_fts.content, max(instr(lower(session_search_fts.content), ?) - {half}, 1), {SNIPPET_FALLBACK_CHARS})",
+                half = SNIPPET_FALLBACK_CHARS / 2
+            ),
+            "0.0".to_string(),
+            "ORDER BY COALESCE(d.last_active_at, d.created_at, 0) DESC",
+        )
+    } else {
+        (
+            format!("substr(COALESCE(d.summary, session_search_fts.content), 1, {SNIPPET_FALLBACK_CHARS})"),
+            "0.0".to_string(),
+            "ORDER BY COALESCE(d.last_active_at, d.created_at, 0) DESC",
+        )
+    };
+
+    let mut ordered_values = select_values;
+    ordered_values.extend(values);
+    ordered_values.push(SqlValue::Integer(limit as i64));
+
+    let sql = format!(
+        "SELECT {HIT_COLUMNS}, {snippet_expr} AS snippet, {rank_expr} AS rank
+         {from_clause}
+         {order_clause}
+         LIMIT ?"
+    );
+    let hits = query_hits(conn, &sql, &ordered_values)?;
+
+    Ok((hits, total.max(0) as usize))
+}
+
+/// Only the first `MAX_INDEXED_CONTENT_CHARS` of a truncated transcript are
+/// indexed, so truncated `tool` sessions whose indexed text misses the query
+/// are rescanned from disk. Every term must appear in the title or the
+/// transcript, matching the index semantics; hits carry a zero rank.
+pub(super) fn search_truncated_sessions(
+    db: &SqliteDbState,
+    tool: SessionTool,
+    contexts: &SessionContextSet,
+    query: &str,
+    filters: &SessionSearchFilters,
+) -> Result<Vec<SessionSearchHit>, String> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let candidates =
+        db.with_conn(|conn| truncated_documents_missing_query(conn, tool, query, filters))?;
+    Ok(candidates
+        .into_iter()
+        .filter(|hit| {
+            let Ok((entry, _)) =
+                find_session_with_context(contexts, &hit.session.source_path, false)
+            else {
+                return false;
+            };
+            let title = document_title_text(&hit.session).to_lowercase();
+            terms.iter().all(|term| {
+                title.contains(term.as_str())
+                    || scan_session_content_for_query(
+                        &entry.context,
+                        &hit.session.source_path,
+                        term,
+                    )
+                    .unwrap_or(false)
+            })
+        })
+        .collect())
+}
+
+fn truncated_documents_missing_query(
+    conn: &Connection,
+    tool: SessionTool,
+    query: &str,
+    filters: &SessionSearchFilters,
+) -> Result<Vec<SessionSearchHit>, String> {
+    let terms: Vec<&str> = query.split_whitespace().collect();
+    let mut term_conditions = Vec::new();
+    let mut values: Vec<SqlValue> = Vec::new();
+    push_term_conditions(&terms, uses_fts(&terms), &mut term_conditions, &mut values);
+
+    let mut conditions = vec![
+        "d.content_truncated = 1".to_string(),
+        "d.tool = ?".to_string(),
+        format!(
+            "d.id NOT IN (SELECT rowid FROM session_search_fts WHERE {})",
+            term_conditions.join(" AND ")
+        ),
+    ];
+    values.insert(0, SqlValue::Text(tool.as_str().to_string()));
+    push_filter_conditions(filters, &mut conditions, &mut values);
+
+    let sql = format!(
+        "SELECT {HIT_COLUMNS}, substr(COALESCE(d.summary, d.title, ''), 1, {SNIPPET_FALLBACK_CHARS}) AS snippet, 0.0 AS rank
+         FROM session_search_documents d
+         WHERE {}
+         ORDER BY COALESCE(d.last_active_at, d.created_at, 0) DESC",
+        conditions.join(" AND ")
+    );
+    query_hits(conn, &sql, &values)
+}
+
+fn uses_fts(terms: &[&str]) -> bool {
+    !terms.is_empty()
+        && terms
+            .iter()
+            .all(|term| term.chars().count() >= MIN_TRIGRAM_TERM_CHARS)
+}
+
+fn push_term_conditions(
+    terms: &[&str],
+    use_fts: bool,
+    conditions: &mut Vec<String>,
+    values: &mut Vec<SqlValue>,
+) {
+    if use_fts {
+        conditions.push("session_search_fts MATCH ?".to_string());
+        values.push(SqlValue::Text(build_match_expression(terms)));
+    } else {
+        for term in terms {
+            conditions.push(
+                "(session_search_fts.title LIKE ? ESCAPE '\\' OR session_search_fts.content LIKE ? ESCAPE '\\')".to_string(),
+            );
+            let pattern = like_pattern(term);
+            values.push(SqlValue::Text(pattern.clone()));
+            values.push(SqlValue::Text(pattern));
+        }
+    }
+}
+
+fn push_filter_conditions(
+    filters: &SessionSearchFilters,
+    conditions: &mut Vec<String>,
+    values: &mut Vec<SqlValue>,
+) {
+    if let Some(project_dir) = filters.project_dir.as_deref() {
+        conditions.push("d.project_dir LIKE ? ESCAPE '\\'".to_string());
+        values.push(SqlValue::Text(like_pattern(project_dir)));
+    }
+    if let Some(model) = filters.model.as_deref() {
+        conditions.push("d.models LIKE ? ESCAPE '\\'".to_string());
+        values.push(SqlValue::Text(like_pattern(model)));
+    }
+    if let Some(date_from) = filters.date_from {
+        conditions.push("COALESCE(d.last_active_at, d.created_at, 0) >= ?".to_string());
+        values.push(SqlValue::Integer(date_from));
+    }
+    if let Some(date_to) = filters.date_to {
+        conditions.push("COALESCE(d.last_active_at, d.created_at, 0) <= ?".to_string());
+        values.push(SqlValue::Integer(date_to));
+    }
+}
+
+fn query_hits(
+    conn: &Connection,
+    sql: &str,
+    values: &[SqlValue],
+) -> Result<Vec<SessionSearchHit>, String> {
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|error| format!("Failed to prepare session search: {error}"))?;
+    let rows = stmt
+        .query_map(params_from_iter(values.iter()), |row| {
+            let models: String = row.get(11)?;
+            Ok(SessionSearchHit {
+                tool: row.get(0)?,
+                session: SessionMeta {
+                    provider_id: row.get(12)?,
+                    session_id: row.get(2)?,
+                    title: row.get(3)?,
+                    summary: row.get(4)?,
+                    project_dir: row.get(5)?,
+                    created_at: row.get(6)?,
+                    last_active_at: row.get(7)?,
+                    source_path: row.get(1)?,
+                    resume_command: row.get(8)?,
+                    runtime_source: row.get(9)?,
+                    runtime_distro: row.get(10)?,
+                },
+                snippet: row
+                    .get::<_, Option<String>>(13)?
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+                models: models
+                    .lines()
+                    .filter(|model| !model.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+                rank: row.get(14)?,
+            })
+        })
+        .map_err(|error| format!("Failed to search sessions: {error}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to read session search result: {error}"))
+}
+
+/// Quotes every term as an FTS5 phrase so user input can't inject query syntax;
+/// adjacent phrases are implicitly AND-ed.
+fn build_match_expression(terms: &[&str]) -> String {
+    terms
+        .iter()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn like_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(source_path: &str, title: &str, project_dir: &str, last_active_at: i64) -> SessionMeta {
+        SessionMeta {
+            provider_id: "codex".to_string(),
+            session_id: format!("id-{title}"),
+            title: Some(title.to_string()),
+            summary: None,
+            project_dir: Some(project_dir.to_string()),
+            created_at: Some(last_active_at - 1_000),
+            last_active_at: Some(last_active_at),
+            source_path: source_path.to_string(),
+            resume_command: None,
+            runtime_source: Some("local".to_string()),
+            runtime_distro: None,
+        }
+    }
+
+    fn message(content: &str, model: Option<&str>) -> SessionMessage {
+        SessionMessage {
+            role: "user".to_string(),
+            content: content.to_string(),
+            ts: None,
+            id: None,
+            parent_id: None,
+            message_type: None,
+            blocks: Vec::new(),
+            model: model.map(str::to_string),
+            usage: None,
+            duration_ms: None,
+            cost_usd: None,
+            is_sidechain: None,
+            metadata: None,
+        }
+    }
+
+    fn seed(db: &SqliteDbState) {
+        let codex_docs = vec![
+            build_document(
+                meta("/s/a.jsonl", "Refactor parser", "/work/alpha", 3_000),
+                1,
+                &[
+                    message("Please refactor the tokenizer module", None),
+                    message("Done: tokenizer refactored", Some("gpt-5.4")),
+                ],
+            ),
+            build_document(
+                meta("/s/b.jsonl", "Fix UI glitch", "/work/beta", 2_000),
+                1,
+                &[message(
+                    "The UI flickers when resizing",
+                    Some("gpt-5.4-mini"),
+                )],
+            ),
+        ];
+        let claude_docs = vec![build_document(
+            meta("/c/c.jsonl", "修复登录页面", "/work/alpha", 1_000),
+            1,
+            &[message(
+                "登录页面在刷新后丢失令牌 tokenizer",
+                Some("claude-sonnet-4-5"),
+            )],
+        )];
+        db.with_conn_mut(|conn| {
+            write_documents(conn, "codex", &codex_docs, &[])?;
+            write_documents(conn, "claudecode", &claude_docs, &[])
+        })
+        .expect("seed index");
+    }
+
+    fn all_tools() -> Vec<SessionTool> {
+        INDEXED_SESSION_TOOLS.to_vec()
+    }
+
+    #[test]
+    fn search_ranks_across_tools_with_snippets() {
+        let db = SqliteDbState::in_memory_for_test().expect("db");
+        seed(&db);
+
+        let (hits, total) = search_index(
+            &db,
+            &all_tools(),
+            "tokenizer",
+            &SessionSearchFilters::default(),
+            DEFAULT_SEARCH_LIMIT,
+        )
+        .expect("search");
+        assert_eq!(total, 2);
+        let tools: BTreeSet<&str> = hits.iter().map(|hit| hit.tool.as_str()).collect();
+        assert_eq!(tools, BTreeSet::from(["claudecode", "codex"]));
+        for hit in &hits {
+            assert!(hit.snippet.contains("tokenizer"), "{}", hit.snippet);
+        }
+        assert!(hits.iter().all(|hit| hit.rank < 0.0));
+
+        let (hits, _) = search_index(
+            &db,
+            &all_tools(),
+            "登录页面",
+            &SessionSearchFilters::default(),
+            DEFAULT_SEARCH_LIMIT,
+        )
+        .expect("cjk search");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].session.source_path, "/c/c.jsonl");
+    }
+
+    #[test]
+    fn short_terms_fall_back_to_substring_match() {
+        let db = SqliteDbState::in_memory_for_test().expect("db");
+        seed(&db);
+
+        let (hits, total) = search_index(
+            &db,
+            &all_tools(),
+            "ui",
+            &SessionSearchFilters::default(),
+            DEFAULT_SEARCH_LIMIT,
+        )
+        .expect("search");
+        assert_eq!(total, 1);
+        assert_eq!(hits[0].session.title.as_deref(), Some("Fix UI glitch"));
+        assert!(hits[0].snippet.contains("UI flickers"));
+        assert_eq!(hits[0].rank, 0.0);
+    }
+
+    #[test]
+    fn filters_narrow_by_project_model_date_and_tool() {
+        let db = SqliteDbState::in_memory_for_test().expect("db");
+        seed(&db);
+
+        let search = |tools: &[SessionTool], filters: SessionSearchFilters| {
+            search_index(&db, tools, "tokenizer", &filters, DEFAULT_SEARCH_LIMIT)
+                .expect("search")
+                .0
+                .into_iter()
+                .map(|hit| hit.session.source_path)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            search(
+                &all_tools(),
+                SessionSearchFilters {
+                    model: Some("gpt-5.4".to_string()),
+                    ..Default::default()
+                }
+            ),
+            vec!["/s/a.jsonl"]
+        );
+        assert_eq!(
+            search(
+                &all_tools(),
+                SessionSearchFilters {
+                    project_dir: Some("ALPHA".to_string()),
+                    date_to: Some(1_500),
+                    ..Default::default()
+                }
+            ),
+            vec!["/c/c.jsonl"]
+        );
+        assert_eq!(
+            search(
+                &all_tools(),
+                SessionSearchFilters {
+                    date_from: Some(2_500),
+                    ..Default::default()
+                }
+            ),
+            vec!["/s/a.jsonl"]
+        );
+        assert_eq!(
+            search(&[SessionTool::ClaudeCode], SessionSearchFilters::default()),
+            vec!["/c/c.jsonl"]
+        );
+    }
+
+    #[test]
+    fn rewriting_documents_replaces_and_prunes_entries() {
+        let db = SqliteDbState::in_memory_for_test().expect("db");
+        seed(&db);
+
+        let updated = build_document(
+            meta("/s/a.jsonl", "Refactor parser", "/work/alpha", 4_000),
+            2,
+            &[message("Switched to a hand written lexer", None)],
+        );
+        db.with_conn_mut(|conn| {
+            write_documents(conn, "codex", &[updated], &["/s/b.jsonl".to_string()])
+        })
+        .expect("rewrite");
+
+        let stamps = db
+            .with_conn(|conn| load_stored_stamps(conn, "codex"))
+            .expect("stamps");
+        assert_eq!(stamps.len(), 1);
+        assert_eq!(stamps["/s/a.jsonl"].0, 2);
+
+        let filters = SessionSearchFilters::default();
+        let (hits, _) =
+            search_index(&db, &[SessionTool::Codex], "lexer", &filters, 10).expect("search");
+        assert_eq!(hits.len(), 1);
+        let (hits, _) =
+            search_index(&db, &[SessionTool::Codex], "tokenizer", &filters, 10).expect("search");
+        assert!(hits.is_empty());
+        let fts_rows: i64 = db
+            .with_conn(|conn| {
+                conn.query_row("SELECT COUNT(*) FROM session_search_fts", [], |row| {
+                    row.get(0)
+                })
+                .map_err(|error| error.to_string())
+            })
+            .expect("count");
+        assert_eq!(fts_rows, 2);
+    }
+
+    #[test]
+    fn content_matches_use_whole_query_substring() {
+        let db = SqliteDbState::in_memory_for_test().expect("db");
+        seed(&db);
+
+        let matches = |tool: &str, query: &str| {
+            let mut paths: Vec<String> = db
+                .with_conn(|conn| matching_source_paths(conn, tool, query))
+                .expect("matches")
+                .into_iter()
+                .collect();
+            paths.sort();
+            paths
+        };
+
+        assert_eq!(matches("codex", "TOKENIZER MODULE"), vec!["/s/a.jsonl"]);
+        assert!(matches("codex", "module tokenizer").is_empty());
+        assert_eq!(matches("codex", "UI"), vec!["/s/b.jsonl"]);
+        assert_eq!(matches("claudecode", "令牌"), vec!["/c/c.jsonl"]);
+    }
+
+    #[test]
+    fn oversized_transcripts_are_flagged_for_rescanning() {
+        let db = SqliteDbState::in_memory_for_test().expect("db");
+        seed(&db);
+
+        let head = "a".repeat(MAX_INDEXED_CONTENT_CHARS);
+        let truncated = build_document(
+            meta("/s/big.jsonl", "Long run", "/work/alpha", 5_000),
+            1,
+            &[message(&head, None), message("needle past the cap", None)],
+        );
+        assert!(truncated.content_truncated);
+        assert!(!truncated.content.contains("needle"));
+        db.with_conn_mut(|conn| write_documents(conn, "codex", &[truncated], &[]))
+            .expect("write");
+
+        let truncated_paths = db
+            .with_conn(|conn| truncated_source_paths(conn, "codex"))
+            .expect("truncated paths");
+        assert_eq!(truncated_paths, HashSet::from(["/s/big.jsonl".to_string()]));
+
+        let missing = |query: &str, filters: SessionSearchFilters| {
+            db.with_conn(|conn| {
+                truncated_documents_missing_query(conn, SessionTool::Codex, query, &filters)
+            })
+            .expect("candidates")
+            .into_iter()
+            .map(|hit| hit.session.source_path)
+            .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            missing("needle", SessionSearchFilters::default()),
+            vec!["/s/big.jsonl"]
+        );
+        assert!(missing("aaaa", SessionSearchFilters::default()).is_empty());
+        assert!(missing(
+            "needle",
+            SessionSearchFilters {
+                project_dir: Some("beta".to_string()),
+                ..Default::default()
+            }
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn match_expression_quotes_user_input() {
+        assert_eq!(
+            build_match_expression(&["foo\"bar", "NEAR(x"]),
+            "\"foo\"\"bar\" \"NEAR(x\""
+        );
+        assert_eq!(like_pattern("50%_a\\b"), "%50\\%\\_a\\\\b%");
+    }
+}
@@ -138,6 +138,39 @@ pub(super) fn normalize_tool_name(raw_tool_name: &str) -> String {
     "unknown".to_string()
 }
 
+/// Claude Code's built-in name for a normalized tool, used when converting
+/// sessions from other CLIs so replayed tool calls look native.
+pub(super) fn claude_code_tool_name(normalized_tool_name: &str) -> Option<&'static str> {
+    Some(match normalized_tool_name {
+        "bash" => "Bash",
+        "read" => "Read",
+        "write" => "Write",
+        "edit" => "Edit",
+        "multi_edit" => "MultiEdit",
+        "notebook_edit" => "NotebookEdit",
+        "grep" => "Grep",
+        "glob" => "Glob",
+        "web_fetch" => "WebFetch",
+        "web_search" => "WebSearch",
+        "todo_write" => "TodoWrite",
+        "exit_plan_mode" => "ExitPlanMode",
+        "ask_user_question" => "AskUserQuestion",
+        "task" | "agent" => "Task",
+        _ => return None,
+    })
+}
+
+/// Codex's built-in name for a normalized tool; tools Codex has no
+/// equivalent for keep their original name.
+pub(super) fn codex_tool_name(normalized_tool_name: &str) -> Option<&'static str> {
+    Some(match normalized_tool_name {
+        "bash" => "shell",
+        "apply_patch" => "apply_patch",
+        "update_plan" => "update_plan",
+        _ => return None,
+    })
+}
+
 pub(super) fn infer_tool_variant(normalized_tool_name: &str, raw_tool_name: &str) -> String {
     match normalized_tool_name {
         "bash" => "terminal",
@@ -210,4 +243,21 @@ mod tests {
         assert_eq!(infer_tool_variant("exit_plan_mode", "ExitPlanMode"), "task");
         assert_eq!(infer_tool_variant("unknown", "custom"), "neutral");
     }
+
+    #[test]
+    fn native_tool_names_round_trip_through_normalizer() {
+        for name in ["Bash", "Read", "MultiEdit", "Grep", "WebFetch", "TodoWrite"] {
+            assert_eq!(
+                claude_code_tool_name(&normalize_tool_name(name)),
+                Some(name)
+            );
+        }
+        assert_eq!(codex_tool_name(&normalize_tool_name("Bash")), Some("shell"));
+        assert_eq!(
+            codex_tool_name(&normalize_tool_name("update_plan")),
+            Some("update_plan")
+        );
+        assert_eq!(codex_tool_name(&normalize_tool_name("Read")), None);
+        assert_eq!(claude_code_tool_name("mcp"), None);
+    }
 }
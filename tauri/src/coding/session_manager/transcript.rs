@@ -0,0 +1,739 @@
+//! Human-readable transcript export.
+//!
+//! Renders any `SessionDetail` (plus its subagent sessions) as Markdown or as
+//! a single self-contained HTML page. Both renderers walk the normalized
+//! message blocks, so every session tool gets the same output.
+
+use chrono::{TimeZone, Utc};
+use serde_json::Value;
+
+use super::{SessionDetail, SessionMessage, SessionMessageBlock, SessionSubagentMeta};
+
+/// Tool output beyond this is cut so a single `cat` of a large file does not
+/// drown the transcript.
+const MAX_TOOL_OUTPUT_CHARS: usize = 20_000;
+
+pub(super) struct TranscriptSubagent {
+    pub meta: SessionSubagentMeta,
+    pub messages: Vec<SessionMessage>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct UsageTotals {
+    input_tokens: i64,
+    output_tokens: i64,
+    cache_read_tokens: i64,
+    cache_write_tokens: i64,
+    cost_usd: f64,
+    has_usage: bool,
+    has_cost: bool,
+}
+
+impl UsageTotals {
+    fn from_messages<'a>(messages: impl IntoIterator<Item = &'a SessionMessage>) -> Self {
+        let mut totals = Self::default();
+        for message in messages {
+            if let Some(usage) = message.usage.as_ref() {
+                totals.has_usage = true;
+                totals.input_tokens += usage.input_tokens.unwrap_or(0);
+                totals.output_tokens += usage.output_tokens.unwrap_or(0);
+                totals.cache_read_tokens += usage.cache_read_input_tokens.unwrap_or(0);
+                totals.cache_write_tokens += usage.cache_creation_input_tokens.unwrap_or(0);
+            }
+            if let Some(cost) = message.cost_usd {
+                totals.has_cost = true;
+                totals.cost_usd += cost;
+            }
+        }
+        totals
+    }
+
+    fn token_summary(&self) -> Option<String> {
+        self.has_usage.then(|| {
+            format_token_counts(
+                self.input_tokens,
+                self.output_tokens,
+                self.cache_read_tokens,
+                self.cache_write_tokens,
+            )
+        })
+    }
+}
+
+fn format_token_counts(input: i64, output: i64, cache_read: i64, cache_write: i64) -> String {
+    let mut parts = vec![format!("in {input}"), format!("out {output}")];
+    if cache_read > 0 {
+        parts.push(format!("cache read {cache_read}"));
+    }
+    if cache_write > 0 {
+        parts.push(format!("cache write {cache_write}"));
+    }
+    parts.join(" · ")
+}
+
+fn format_timestamp(ts: Option<i64>) -> Option<String> {
+    ts.and_then(|value| Utc.timestamp_millis_opt(value).single())
+        .map(|value| value.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+}
+
+fn format_cost(cost: f64) -> String {
+    format!("${cost:.4}")
+}
+
+fn session_title(detail: &SessionDetail) -> String {
+    detail
+        .meta
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(&detail.meta.session_id)
+        .to_string()
+}
+
+fn session_models(messages: &[SessionMessage]) -> Vec<String> {
+    let mut models: Vec<String> = Vec::new();
+    for model in messages
+        .iter()
+        .filter_map(|message| message.model.as_deref())
+    {
+        if !models.iter().any(|existing| existing == model) {
+            models.push(model.to_string());
+        }
+    }
+    models
+}
+
+fn summary_rows(tool: &str, detail: &SessionDetail) -> Vec<(&'static str, String)> {
+    let meta = &detail.meta;
+    let mut rows = vec![
+        ("Tool", tool.to_string()),
+        ("Session ID", meta.session_id.clone()),
+    ];
+    if let Some(project_dir) = meta.project_dir.as_deref() {
+        rows.push(("Project", project_dir.to_string()));
+    }
+    if let Some(created) = format_timestamp(meta.created_at) {
+        rows.push(("Created", created));
+    }
+    if let Some(last_active) = format_timestamp(meta.last_active_at) {
+        rows.push(("Last active", last_active));
+    }
+    let models = session_models(&detail.messages);
+    if !models.is_empty() {
+        rows.push(("Models", models.join(", ")));
+    }
+    let totals = UsageTotals::from_messages(&detail.messages);
+    if let Some(tokens) = totals.token_summary() {
+        rows.push(("Tokens", tokens));
+    }
+    if totals.has_cost {
+        rows.push(("Cost", format_cost(totals.cost_usd)));
+    }
+    rows.push(("Messages", detail.messages.len().to_string()));
+    rows
+}
+
+fn message_footer(message: &SessionMessage) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(usage) = message.usage.as_ref() {
+        parts.push(format_token_counts(
+            usage.input_tokens.unwrap_or(0),
+            usage.output_tokens.unwrap_or(0),
+            usage.cache_read_input_tokens.unwrap_or(0),
+            usage.cache_creation_input_tokens.unwrap_or(0),
+        ));
+    }
+    if let Some(cost) = message.cost_usd {
+        parts.push(format_cost(cost));
+    }
+    if let Some(duration_ms) = message.duration_ms {
+        parts.push(format!("{:.1}s", duration_ms as f64 / 1000.0));
+    }
+    (!parts.is_empty()).then(|| parts.join(" · "))
+}
+
+fn message_heading(message: &SessionMessage) -> String {
+    let mut role = message.role.clone();
+    if let Some(first) = role.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    let mut parts = vec![role];
+    if let Some(ts) = format_timestamp(message.ts) {
+        parts.push(ts);
+    }
+    if let Some(model) = message.model.as_deref() {
+        parts.push(model.to_string());
+    }
+    parts.join(" · ")
+}
+
+fn tool_label(block: &SessionMessageBlock) -> String {
+    let name = block
+        .tool_name
+        .as_deref()
+        .or(block.normalized_tool_name.as_deref())
+        .unwrap_or("tool");
+    match block.status.as_deref() {
+        Some(status) if !status.is_empty() => format!("{name} ({status})"),
+        _ => name.to_string(),
+    }
+}
+
+fn tool_output_text(output: &Value) -> String {
+    let text = match output {
+        Value::String(text) => text.clone(),
+        Value::Array(items)
+            if items
+                .iter()
+                .all(|item| item.get("text").and_then(Value::as_str).is_some()) =>
+        {
+            items
+                .iter()
+                .filter_map(|item| item.get("text").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        Value::Null => String::new(),
+        _ => serde_json::to_string_pretty(output).unwrap_or_else(|_| output.to_string()),
+    };
+    truncate_output(text)
+}
+
+fn truncate_output(text: String) -> String {
+    let total_chars = text.chars().count();
+    if total_chars <= MAX_TOOL_OUTPUT_CHARS {
+        return text;
+    }
+    let mut truncated: String = text.chars().take(MAX_TOOL_OUTPUT_CHARS).collect();
+    truncated.push_str(&format!(
+        "\n… ({} more characters truncated)",
+        total_chars - MAX_TOOL_OUTPUT_CHARS
+    ));
+    truncated
+}
+
+/// Edit-style tool inputs rendered as a unified-diff-like listing; `None`
+/// when the call does not describe a file change.
+fn tool_input_diff(block: &SessionMessageBlock) -> Option<String> {
+    let input = block.input.as_ref()?;
+    if let Some(patch) = input.get("patch").and_then(Value::as_str) {
+        return Some(patch.trim_end().to_string());
+    }
+
+    let file_path = input
+        .get("file_path")
+        .or_else(|| input.get("path"))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let mut edits: Vec<(&str, &str)> = Vec::new();
+    if let (Some(old), Some(new)) = (
+        input.get("old_string").and_then(Value::as_str),
+        input.get("new_string").and_then(Value::as_str),
+    ) {
+        edits.push((old, new));
+    }
+    if let Some(items) = input.get("edits").and_then(Value::as_array) {
+        edits.extend(items.iter().filter_map(|item| {
+            Some((
+                item.get("old_string").and_then(Value::as_str)?,
+                item.get("new_string").and_then(Value::as_str)?,
+            ))
+        }));
+    }
+    if edits.is_empty() {
+        return None;
+    }
+
+    let mut lines = vec![format!("--- {file_path}"), format!("+++ {file_path}")];
+    for (old, new) in edits {
+        lines.push("@@".to_string());
+        lines.extend(old.lines().map(|line| format!("-{line}")));
+        lines.extend(new.lines().map(|line| format!("+{line}")));
+    }
+    Some(lines.join("\n"))
+}
+
+fn tool_input_text(block: &SessionMessageBlock) -> Option<String> {
+    let input = block.input.as_ref().filter(|input| !input.is_null())?;
+    if let Some(command) = input.get("command").and_then(Value::as_str) {
+        if block.normalized_tool_name.as_deref() == Some("bash") {
+            return Some(command.to_string());
+        }
+    }
+    Some(serde_json::to_string_pretty(input).unwrap_or_else(|_| input.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Markdown
+// ---------------------------------------------------------------------------
+
+pub(super) fn render_markdown(
+    tool: &str,
+    detail: &SessionDetail,
+    subagents: &[TranscriptSubagent],
+) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "# {}\n\n",
+        escape_markdown_inline(&session_title(detail))
+    ));
+    out.push_str("| Field | Value |\n| --- | --- |\n");
+    for (label, value) in summary_rows(tool, detail) {
+        out.push_str(&format!(
+            "| {label} | {} |\n",
+            escape_markdown_inline(&value).replace('|', "\\|")
+        ));
+    }
+
+    for message in &detail.messages {
+        push_markdown_message(&mut out, message, 2);
+    }
+
+    if !subagents.is_empty() {
+        out.push_str("\n---\n\n## Subagents\n");
+        for subagent in subagents {
+            out.push_str(&format!(
+                "\n### {}\n\n",
+                escape_markdown_inline(&subagent.meta.title)
+            ));
+            if let Some(subagent_type) = subagent.meta.subagent_type.as_deref() {
+                out.push_str(&format!("Type: `{subagent_type}`\n\n"));
+            }
+            for message in &subagent.messages {
+                push_markdown_message(&mut out, message, 4);
+            }
+        }
+    }
+
+    out
+}
+
+fn push_markdown_message(out: &mut String, message: &SessionMessage, level: usize) {
+    out.push_str(&format!(
+        "\n{} {}\n\n",
+        "#".repeat(level),
+        escape_markdown_inline(&message_heading(message))
+    ));
+
+    if message.blocks.is_empty() {
+        out.push_str(message.content.trim());
+        out.push_str("\n\n");
+    }
+    for block in &message.blocks {
+        push_markdown_block(out, block);
+    }
+
+    if let Some(footer) = message_footer(message) {
+        out.push_str(&format!("_{footer}_\n\n"));
+    }
+}
+
+fn push_markdown_block(out: &mut String, block: &SessionMessageBlock) {
+    let text = block.text.as_deref().unwrap_or_default().trim();
+    match block.kind.as_str() {
+        "text" => {
+            if !text.is_empty() {
+                out.push_str(text);
+                out.push_str("\n\n");
+            }
+        }
+        "thinking" | "redacted_thinking" => {
+            let title = block.title.as_deref().unwrap_or("Thinking");
+            out.push_str(&format!("<details>\n<summary>{title}</summary>\n\n"));
+            out.push_str(&fenced(text, ""));
+            out.push_str("</details>\n\n");
+        }
+        "tool_call" | "tool_execution" | "tool_result" => {
+            out.push_str(&format!(
+                "**Tool: {}**",
+                escape_markdown_inline(&tool_label(block))
+            ));
+            if let Some(tool_id) = block.tool_id.as_deref() {
+                out.push_str(&format!(" `{tool_id}`"));
+            }
+            out.push_str("\n\n");
+            if block.kind != "tool_result" {
+                if let Some(diff) = tool_input_diff(block) {
+                    out.push_str(&fenced(&diff, "diff"));
+                } else if let Some(input) = tool_input_text(block) {
+                    let language = if block.normalized_tool_name.as_deref() == Some("bash") {
+                        "bash"
+                    } else {
+                        "json"
+                    };
+                    out.push_str(&fenced(&input, language));
+                }
+            }
+            if let Some(output) = block.output.as_ref().map(tool_output_text) {
+                let summary = if block.is_error == Some(true) {
+                    "Output (error)"
+                } else {
+                    "Output"
+                };
+                out.push_str(&format!("<details>\n<summary>{summary}</summary>\n\n"));
+                out.push_str(&fenced(&output, ""));
+                out.push_str("</details>\n\n");
+            }
+        }
+        _ => {
+            if !text.is_empty() {
+                out.push_str(&fenced(text, ""));
+            }
+        }
+    }
+}
+
+/// Wraps `content` in a code fence longer than any backtick run inside it.
+fn fenced(content: &str, language: &str) -> String {
+    let mut longest_run = 0;
+    let mut current_run = 0;
+    for ch in content.chars() {
+        if ch == '`' {
+            current_run += 1;
+            longest_run = longest_run.max(current_run);
+        } else {
+            current_run = 0;
+        }
+    }
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{fence}{language}\n{}\n{fence}\n\n", content.trim_end())
+}
+
+fn escape_markdown_inline(text: &str) -> String {
+    text.replace('\n', " ")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+const HTML_STYLE: &str = r#"
+:root { color-scheme: light dark; --fg: #1f2328; --muted: #656d76; --bg: #ffffff; --panel: #f6f8fa; --border: #d0d7de; --user: #ddf4ff; --add: #dafbe1; --del: #ffebe9; --error: #cf222e; }
+@media (prefers-color-scheme: dark) { :root { --fg: #e6edf3; --muted: #8d96a0; --bg: #0d1117; --panel: #161b22; --border: #30363d; --user: #0c2d48; --add: #12361f; --del: #42181b; --error: #ff7b72; } }
+* { box-sizing: border-box; }
+body { margin: 0 auto; max-width: 960px; padding: 24px; font: 14px/1.6 -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; color: var(--fg); background: var(--bg); }
+h1 { font-size: 22px; margin: 0 0 16px; }
+h2 { font-size: 18px; margin: 32px 0 8px; }
+table.meta { border-collapse: collapse; margin-bottom: 24px; }
+table.meta th, table.meta td { text-align: left; padding: 4px 12px 4px 0; vertical-align: top; }
+table.meta th { color: var(--muted); font-weight: 500; }
+.message { border: 1px solid var(--border); border-radius: 8px; padding: 12px 16px; margin: 12px 0; }
+.message.user { background: var(--user); }
+.message header { color: var(--muted); font-size: 12px; margin-bottom: 8px; }
+.message header .role { font-weight: 600; color: var(--fg); text-transform: capitalize; }
+.message footer { color: var(--muted); font-size: 12px; margin-top: 8px; }
+.text { white-space: pre-wrap; word-break: break-word; }
+pre { background: var(--panel); border: 1px solid var(--border); border-radius: 6px; padding: 8px 12px; overflow-x: auto; font: 12px/1.5 ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; white-space: pre-wrap; word-break: break-word; }
+details { margin: 8px 0; }
+summary { cursor: pointer; color: var(--muted); }
+.tool { margin: 8px 0; }
+.tool-name { font-weight: 600; }
+.tool-id { color: var(--muted); font-family: ui-monospace, monospace; font-size: 12px; }
+.error summary, .error .tool-name { color: var(--error); }
+.diff .add { background: var(--add); display: block; }
+.diff .del { background: var(--del); display: block; }
+.subagent { border-left: 3px solid var(--border); padding-left: 16px; }
+"#;
+
+pub(super) fn render_html(
+    tool: &str,
+    detail: &SessionDetail,
+    subagents: &[TranscriptSubagent],
+) -> String {
+    let title = escape_html(&session_title(detail));
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str(&format!(
+        "<title>{title}</title>\n<style>{HTML_STYLE}</style>\n"
+    ));
+    out.push_str("</head>\n<body>\n");
+    out.push_str(&format!("<h1>{title}</h1>\n<table class=\"meta\">\n"));
+    for (label, value) in summary_rows(tool, detail) {
+        out.push_str(&format!(
+            "<tr><th>{label}</th><td>{}</td></tr>\n",
+            escape_html(&value)
+        ));
+    }
+    out.push_str("</table>\n");
+
+    for message in &detail.messages {
+        push_html_message(&mut out, message);
+    }
+
+    if !subagents.is_empty() {
+        out.push_str("<h2>Subagents</h2>\n");
+        for subagent in subagents {
+            out.push_str("<section class=\"subagent\">\n");
+            out.push_str(&format!("<h2>{}</h2>\n", escape_html(&subagent.meta.title)));
+            for message in &subagent.messages {
+                push_html_message(&mut out, message);
+            }
+            out.push_str("</section>\n");
+        }
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn push_html_message(out: &mut String, message: &SessionMessage) {
+    out.push_str(&format!(
+        "<article class=\"message {}\">\n<header><span class=\"role\">{}</span>",
+        escape_html(&message.role),
+        escape_html(&message.role)
+    ));
+    if let Some(ts) = format_timestamp(message.ts) {
+        out.push_str(&format!(" · {ts}"));
+    }
+    if let Some(model) = message.model.as_deref() {
+        out.push_str(&format!(" · {}", escape_html(model)));
+    }
+    out.push_str("</header>\n");
+
+    if message.blocks.is_empty() {
+        out.push_str(&format!(
+            "<div class=\"text\">{}</div>\n",
+            escape_html(message.content.trim())
+        ));
+    }
+    for block in &message.blocks {
+        push_html_block(out, block);
+    }
+
+    if let Some(footer) = message_footer(message) {
+        out.push_str(&format!("<footer>{}</footer>\n", escape_html(&footer)));
+    }
+    out.push_str("</article>\n");
+}
+
+fn push_html_block(out: &mut String, block: &SessionMessageBlock) {
+    let text = block.text.as_deref().unwrap_or_default().trim();
+    match block.kind.as_str() {
+        "text" => {
+            if !text.is_empty() {
+                out.push_str(&format!(
+                    "<div class=\"text\">{}</div>\n",
+                    escape_html(text)
+                ));
+            }
+        }
+        "thinking" | "redacted_thinking" => {
+            out.push_str(&format!(
+                "<details><summary>{}</summary><pre>{}</pre></details>\n",
+                escape_html(block.title.as_deref().unwrap_or("Thinking")),
+                escape_html(text)
+            ));
+        }
+        "tool_call" | "tool_execution" | "tool_result" => {
+            let is_error = block.is_error == Some(true);
+            out.push_str(&format!(
+                "<div class=\"tool{}\"><span class=\"tool-name\">{}</span>",
+                if is_error { " error" } else { "" },
+                escape_html(&tool_label(block))
+            ));
+            if let Some(tool_id) = block.tool_id.as_deref() {
+                out.push_str(&format!(
+                    " <span class=\"tool-id\">{}</span>",
+                    escape_html(tool_id)
+                ));
+            }
+            out.push('\n');
+            if block.kind != "tool_result" {
+                if let Some(diff) = tool_input_diff(block) {
+                    out.push_str(&render_html_diff(&diff));
+                } else if let Some(input) = tool_input_text(block) {
+                    out.push_str(&format!("<pre>{}</pre>\n", escape_html(&input)));
+                }
+            }
+            if let Some(output) = block.output.as_ref().map(tool_output_text) {
+                out.push_str(&format!(
+                    "<details><summary>{}</summary><pre>{}</pre></details>\n",
+                    if is_error { "Output (error)" } else { "Output" },
+                    escape_html(&output)
+                ));
+            }
+            out.push_str("</div>\n");
+        }
+        _ => {
+            if !text.is_empty() {
+                out.push_str(&format!("<pre>{}</pre>\n", escape_html(text)));
+            }
+        }
+    }
+}
+
+fn render_html_diff(diff: &str) -> String {
+    let lines: Vec<String> = diff
+        .lines()
+        .map(|line| {
+            let class = if line.starts_with('+') && !line.starts_with("+++") {
+                "add"
+            } else if line.starts_with('-') && !line.starts_with("---") {
+                "del"
+            } else {
+                ""
+            };
+            if class.is_empty() {
+                escape_html(line)
+            } else {
+                format!("<span class=\"{class}\">{}</span>", escape_html(line))
+            }
+        })
+        .collect();
+    // `.add`/`.del` spans are block-level, so only plain lines need a break.
+    let mut body = String::new();
+    for line in lines {
+        let is_span = line.starts_with("<span");
+        body.push_str(&line);
+        if !is_span {
+            body.push('\n');
+        }
+    }
+    format!("<pre class=\"diff\">{body}</pre>\n")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::message_blocks::{
+        message_from_blocks, text_block, thinking_block, tool_call_block, tool_result_block,
+    };
+    use super::super::{SessionMessageUsage, SessionMeta};
+    use super::*;
+    use serde_json::json;
+
+    fn sample_detail() -> SessionDetail {
+        let user = message_from_blocks(
+            "user",
+            Some(1_700_000_000_000),
+            vec![text_block("Rename <Foo> please")],
+        );
+        let mut assistant = message_from_blocks(
+            "assistant",
+            Some(1_700_000_005_000),
+            vec![
+                thinking_block("Need to edit lib.rs"),
+                tool_call_block(
+                    Some("toolu_1".to_string()),
+                    "Edit",
+                    Some(json!({
+                        "file_path": "src/lib.rs",
+                        "old_string": "struct Foo;",
+                        "new_string": "struct Bar;",
+                    })),
+                ),
+                tool_result_block(
+                    Some("toolu_1".to_string()),
+                    Some("Edit".to_string()),
+                    Some(json!("```\nok\n```")),
+                    None,
+                ),
+            ],
+        );
+        assistant.model = Some("claude-sonnet-4-5".to_string());
+        assistant.usage = Some(SessionMessageUsage {
+            input_tokens: Some(1200),
+            output_tokens: Some(80),
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: Some(900),
+        });
+        assistant.cost_usd = Some(0.0123);
+
+        SessionDetail {
+            meta: SessionMeta {
+                provider_id: "claudecode".to_string(),
+                session_id: "session-1".to_string(),
+                title: Some("Rename Foo".to_string()),
+                summary: None,
+                project_dir: Some("/repo".to_string()),
+                created_at: Some(1_700_000_000_000),
+                last_active_at: Some(1_700_000_005_000),
+                source_path: "/tmp/session-1.jsonl".to_string(),
+                resume_command: None,
+                runtime_source: None,
+                runtime_distro: None,
+            },
+            messages: vec![user, assistant],
+        }
+    }
+
+    fn sample_subagent() -> TranscriptSubagent {
+        TranscriptSubagent {
+            meta: SessionSubagentMeta {
+                id: "agent-1".to_string(),
+                source_path: "/tmp/agent-1.jsonl".to_string(),
+                title: "Explore callers".to_string(),
+                summary: None,
+                subagent_type: Some("Explore".to_string()),
+                message_count: 1,
+                first_message_time: None,
+                last_message_time: None,
+            },
+            messages: vec![message_from_blocks(
+                "assistant",
+                None,
+                vec![text_block("Found 3 callers")],
+            )],
+        }
+    }
+
+    #[test]
+    fn markdown_transcript_includes_blocks_usage_and_subagents() {
+        let markdown = render_markdown("claudecode", &sample_detail(), &[sample_subagent()]);
+
+        assert!(markdown.starts_with("# Rename Foo\n"));
+        assert!(markdown.contains("| Models | claude-sonnet-4-5 |"));
+        assert!(markdown.contains("| Tokens | in 1200 · out 80 · cache read 900 |"));
+        assert!(markdown.contains("| Cost | $0.0123 |"));
+        assert!(markdown.contains("## Assistant · 2023-11-14 22:13:25 UTC · claude-sonnet-4-5"));
+        assert!(markdown.contains("<summary>Thinking</summary>"));
+        assert!(markdown.contains("**Tool: Edit (success)** `toolu_1`"));
+        assert!(markdown.contains(
+            "```diff\n--- src/lib.rs\n+++ src/lib.rs\n@@\n-struct Foo;\n+struct Bar;\n```"
+        ));
+        assert!(markdown.contains("````\n```\nok\n```\n````"));
+        assert!(markdown.contains("_in 1200 · out 80 · cache read 900 · $0.0123_"));
+        assert!(markdown.contains("## Subagents\n\n### Explore callers\n\nType: `Explore`"));
+        assert!(markdown.contains("#### Assistant"));
+        assert!(markdown.contains("\n\nRename <Foo> please\n\n"));
+    }
+
+    #[test]
+    fn html_transcript_is_self_contained_and_escaped() {
+        let html = render_html("claudecode", &sample_detail(), &[sample_subagent()]);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<link"));
+        assert!(html.contains("Rename &lt;Foo&gt; please"));
+        assert!(html.contains("<span class=\"del\">-struct Foo;</span>"));
+        assert!(html.contains("<span class=\"add\">+struct Bar;</span>"));
+        assert!(html.contains("<section class=\"subagent\">"));
+        assert!(html.contains("<tr><th>Cost</th><td>$0.0123</td></tr>"));
+    }
+
+    #[test]
+    fn long_tool_output_is_truncated() {
+        let output = tool_output_text(&json!("x".repeat(MAX_TOOL_OUTPUT_CHARS + 5)));
+        assert!(output.ends_with("… (5 more characters truncated)"));
+    }
+}
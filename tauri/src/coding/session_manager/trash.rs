@@ -0,0 +1,700 @@
+//! App-managed trash for deleted sessions.
+//!
+//! Every trashed session gets its own directory under `<app data>/session-trash/`
+//! holding an `entry.json` that records where the session came from, plus either an
+//! AI Toolbox export whose native snapshot recreates the original layout on restore
+//! (Claude project dirs, OpenCode storage records, Grok session directories, ...) or a
+//! verbatim copy of the session files for tools without snapshot support.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use walkdir::WalkDir;
+
+use super::{
+    build_exported_session_file, delete_session_from_meta, import_session_blocking,
+    invalidate_cache, load_messages, write_exported_session_file, SessionContextSet, SessionDetail,
+    SessionMeta, ToolSessionContext,
+};
+
+const SESSION_TRASH_DIR_NAME: &str = "session-trash";
+const TRASH_ENTRY_FILE: &str = "entry.json";
+const TRASH_SNAPSHOT_FILE: &str = "session.json";
+const TRASH_FILES_DIR: &str = "files";
+pub(super) const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+const MILLIS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+/// Error code for sessions the trash cannot stage; the caller must ask before
+/// deleting them permanently.
+pub(super) const SESSION_TRASH_UNSUPPORTED: &str = "SESSION_TRASH_UNSUPPORTED";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionTrashStorage {
+    /// `session.json` export restored through the tool's native snapshot import.
+    Snapshot,
+    /// Verbatim copy of the session file or directory under `files/`.
+    Files,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTrashEntry {
+    pub id: String,
+    pub tool: String,
+    pub session_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_dir: Option<String>,
+    pub source_path: String,
+    /// Native file or directory that was removed and is recreated on restore.
+    pub original_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime_source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime_distro: Option<String>,
+    pub deleted_at: i64,
+    pub storage: SessionTrashStorage,
+    #[serde(default)]
+    pub context_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTrashFailure {
+    pub id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreSessionTrashResult {
+    pub restored_count: usize,
+    pub failed_items: Vec<SessionTrashFailure>,
+}
+
+pub fn session_trash_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|error| format!("Failed to get app data dir: {error}"))?;
+    Ok(app_data_dir.join(SESSION_TRASH_DIR_NAME))
+}
+
+/// Destination for sessions deleted from one tool.
+#[derive(Debug, Clone)]
+pub(super) struct SessionTrash {
+    root: PathBuf,
+    tool: String,
+}
+
+impl SessionTrash {
+    pub(super) fn new(root: PathBuf, tool: &str) -> Self {
+        Self {
+            root,
+            tool: tool.to_string(),
+        }
+    }
+
+    /// Stage a restorable copy of the session, then delete it natively.
+    ///
+    /// Sessions that cannot be staged (Hermes SQLite rows, read-only Claude Desktop
+    /// sessions) are refused with [`SESSION_TRASH_UNSUPPORTED`] and left untouched.
+    pub(super) fn trash_session(
+        &self,
+        context: &ToolSessionContext,
+        meta: &SessionMeta,
+    ) -> Result<(), String> {
+        if !supports_trash(context, meta) {
+            return Err(format!("{SESSION_TRASH_UNSUPPORTED}|{}", meta.source_path));
+        }
+
+        let deleted_at = Utc::now().timestamp_millis();
+        let id = format!("{deleted_at}-{}", uuid::Uuid::new_v4().simple());
+        let entry_dir = self.root.join(&id);
+        std::fs::create_dir_all(&entry_dir).map_err(|error| {
+            format!(
+                "Failed to create session trash directory {}: {error}",
+                entry_dir.display()
+            )
+        })?;
+
+        let staged = stage_session(context, &self.tool, meta, &entry_dir).and_then(
+            |(storage, original_path)| {
+                let entry = SessionTrashEntry {
+                    id: id.clone(),
+                    tool: self.tool.clone(),
+                    session_id: meta.session_id.clone(),
+                    title: meta.title.clone().or_else(|| meta.summary.clone()),
+                    project_dir: meta.project_dir.clone(),
+                    source_path: meta.source_path.clone(),
+                    original_path,
+                    runtime_source: meta.runtime_source.clone(),
+                    runtime_distro: meta.runtime_distro.clone(),
+                    deleted_at,
+                    storage,
+                    context_key: context.cache_key(),
+                };
+                write_entry(&entry_dir, &entry)
+            },
+        );
+
+        if let Err(error) = staged {
+            let _ = std::fs::remove_dir_all(&entry_dir);
+            return Err(error);
+        }
+
+        delete_session_from_meta(context, meta).inspect_err(|_| {
+            let _ = std::fs::remove_dir_all(&entry_dir);
+        })
+    }
+}
+
+/// Whether a deleted session can be staged in the trash.
+pub(super) fn supports_trash(context: &ToolSessionContext, meta: &SessionMeta) -> bool {
+    match context {
+        ToolSessionContext::ClaudeDesktop { .. } => false,
+        ToolSessionContext::Hermes { .. } => !meta.source_path.starts_with("sqlite:"),
+        _ => true,
+    }
+}
+
+fn stage_session(
+    context: &ToolSessionContext,
+    tool: &str,
+    meta: &SessionMeta,
+    entry_dir: &Path,
+) -> Result<(SessionTrashStorage, String), String> {
+    match context {
+        ToolSessionContext::Hermes { .. } => {
+            let source = Path::new(&meta.source_path);
+            copy_into_trash(source, entry_dir)?;
+            Ok((SessionTrashStorage::Files, meta.source_path.clone()))
+        }
+        ToolSessionContext::Dsh { .. } => {
+            let session_dir = Path::new(&meta.source_path)
+                .parent()
+                .ok_or_else(|| "Invalid dsh session directory".to_string())?;
+            copy_into_trash(session_dir, entry_dir)?;
+            Ok((
+                SessionTrashStorage::Files,
+                session_dir.to_string_lossy().to_string(),
+            ))
+        }
+        _ => {
+            let snapshot = load_messages(context, &meta.source_path).and_then(|messages| {
+                build_exported_session_file(
+                    context,
+                    tool.to_string(),
+                    SessionDetail {
+                        meta: meta.clone(),
+                        messages,
+                    },
+                )
+            });
+            match snapshot {
+                Ok(exported_file) => {
+                    write_exported_session_file(
+                        &exported_file,
+                        &entry_dir.join(TRASH_SNAPSHOT_FILE),
+                    )?;
+                    Ok((SessionTrashStorage::Snapshot, meta.source_path.clone()))
+                }
+                // Sessions that no longer parse still get a verbatim copy of their file,
+                // so a corrupt session can be deleted without losing it for good.
+                Err(error) => {
+                    let source = Path::new(&meta.source_path);
+                    if !source.exists() || !is_within_session_roots(context, source) {
+                        return Err(error);
+                    }
+                    log::warn!(
+                        "Session {} could not be snapshotted ({error}); trashing its raw file",
+                        meta.source_path
+                    );
+                    copy_into_trash(source, entry_dir)?;
+                    Ok((SessionTrashStorage::Files, meta.source_path.clone()))
+                }
+            }
+        }
+    }
+}
+
+/// True when `path` is an absolute path strictly inside one of the tool's session
+/// roots, with no `.`/`..` components that could walk back out of it.
+fn is_within_session_roots(context: &ToolSessionContext, path: &Path) -> bool {
+    if !path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, Component::CurDir | Component::ParentDir))
+    {
+        return false;
+    }
+    context
+        .session_roots()
+        .into_iter()
+        .any(|root| path != root && path.starts_with(root))
+}
+
+fn copy_into_trash(source: &Path, entry_dir: &Path) -> Result<(), String> {
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| format!("Invalid session path: {}", source.display()))?;
+    copy_path(source, &entry_dir.join(TRASH_FILES_DIR).join(file_name))
+}
+
+fn copy_path(source: &Path, target: &Path) -> Result<(), String> {
+    if source.is_file() {
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|error| {
+                format!("Failed to create directory {}: {error}", parent.display())
+            })?;
+        }
+        std::fs::copy(source, target).map_err(|error| {
+            format!(
+                "Failed to copy {} to {}: {error}",
+                source.display(),
+                target.display()
+            )
+        })?;
+        return Ok(());
+    }
+
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(|error| format!("Failed to read session entry: {error}"))?;
+        let relative_path = entry
+            .path()
+            .strip_prefix(source)
+            .map_err(|error| format!("Failed to get relative path: {error}"))?;
+        let destination = target.join(relative_path);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&destination).map_err(|error| {
+                format!(
+                    "Failed to create directory {}: {error}",
+                    destination.display()
+                )
+            })?;
+        } else {
+            copy_path(entry.path(), &destination)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_entry(entry_dir: &Path, entry: &SessionTrashEntry) -> Result<(), String> {
+    let serialized = serde_json::to_string_pretty(entry)
+        .map_err(|error| format!("Failed to serialize session trash entry: {error}"))?;
+    let entry_path = entry_dir.join(TRASH_ENTRY_FILE);
+    std::fs::write(&entry_path, serialized).map_err(|error| {
+        format!(
+            "Failed to write session trash entry {}: {error}",
+            entry_path.display()
+        )
+    })
+}
+
+fn read_entry(entry_dir: &Path) -> Result<SessionTrashEntry, String> {
+    let entry_path = entry_dir.join(TRASH_ENTRY_FILE);
+    let data = std::fs::read_to_string(&entry_path).map_err(|error| {
+        format!(
+            "Failed to read session trash entry {}: {error}",
+            entry_path.display()
+        )
+    })?;
+    serde_json::from_str(&data).map_err(|error| {
+        format!(
+            "Invalid session trash entry {}: {error}",
+            entry_path.display()
+        )
+    })
+}
+
+/// Resolve a trash id to its directory, rejecting ids that would escape the root.
+fn entry_dir(root: &Path, id: &str) -> Result<PathBuf, String> {
+    let trimmed = id.trim();
+    if trimmed.is_empty() || trimmed.contains(['/', '\\']) || trimmed == "." || trimmed == ".." {
+        return Err(format!("Invalid session trash id: {id}"));
+    }
+    Ok(root.join(trimmed))
+}
+
+/// Trashed sessions, most recently deleted first. Unreadable entries are skipped.
+pub(super) fn list_entries(root: &Path) -> Result<Vec<SessionTrashEntry>, String> {
+    if !root.exists() {
+        return Ok(Vec::new());
+    }
+
+    let read_dir = std::fs::read_dir(root).map_err(|error| {
+        format!(
+            "Failed to read session trash directory {}: {error}",
+            root.display()
+        )
+    })?;
+    let mut entries = read_dir
+        .filter_map(Result::ok)
+        .filter(|dir_entry| dir_entry.path().is_dir())
+        .filter_map(|dir_entry| read_entry(&dir_entry.path()).ok())
+        .collect::<Vec<_>>();
+    entries.sort_by(|left, right| {
+        right
+            .deleted_at
+            .cmp(&left.deleted_at)
+            .then_with(|| left.id.cmp(&right.id))
+    });
+    Ok(entries)
+}
+
+/// Put a trashed session back where it was deleted from and drop its trash entry.
+pub(super) fn restore_entry(
+    root: &Path,
+    id: &str,
+    contexts: &SessionContextSet,
+) -> Result<(), String> {
+    let entry_dir = entry_dir(root, id)?;
+    let entry = read_entry(&entry_dir)?;
+    let context = contexts
+        .entries
+        .iter()
+        .find(|context_entry| context_entry.context.cache_key() == entry.context_key)
+        .or_else(|| contexts.entries.first())
+        .map(|context_entry| context_entry.context.clone())
+        .ok_or_else(|| format!("No session context available for {}", entry.tool))?;
+
+    match entry.storage {
+        SessionTrashStorage::Snapshot => {
+            let snapshot_path = entry_dir.join(TRASH_SNAPSHOT_FILE);
+            import_session_blocking(
+                context,
+                entry.tool.clone(),
+                snapshot_path.to_string_lossy().to_string(),
+            )?;
+        }
+        SessionTrashStorage::Files => {
+            let original_path = PathBuf::from(&entry.original_path);
+            if !is_within_session_roots(&context, &original_path) {
+                return Err(format!(
+                    "Cannot restore session: {} is outside the {} session directories",
+                    original_path.display(),
+                    entry.tool
+                ));
+            }
+            if original_path.exists() {
+                return Err(format!(
+                    "Cannot restore session: {} already exists",
+                    original_path.display()
+                ));
+            }
+            let file_name = original_path
+                .file_name()
+                .ok_or_else(|| format!("Invalid session path: {}", entry.original_path))?;
+            copy_path(
+                &entry_dir.join(TRASH_FILES_DIR).join(file_name),
+                &original_path,
+            )?;
+            invalidate_cache(&context);
+        }
+    }
+
+    std::fs::remove_dir_all(&entry_dir).map_err(|error| {
+        format!(
+            "Session restored but failed to remove trash entry {}: {error}",
+            entry_dir.display()
+        )
+    })
+}
+
+pub(super) fn restore_entries(
+    root: &Path,
+    ids: Vec<String>,
+    contexts_by_tool: &HashMap<String, Result<SessionContextSet, String>>,
+) -> RestoreSessionTrashResult {
+    let mut restored_count = 0usize;
+    let mut failed_items = Vec::new();
+    let mut seen_ids = HashSet::new();
+
+    for id in ids {
+        if !seen_ids.insert(id.clone()) {
+            continue;
+        }
+        let result = entry_dir(root, &id)
+            .and_then(|dir| read_entry(&dir))
+            .and_then(|entry| match contexts_by_tool.get(&entry.tool) {
+                Some(Ok(contexts)) => Ok(contexts),
+                Some(Err(error)) => Err(error.clone()),
+                None => Err(format!("Unsupported session tool: {}", entry.tool)),
+            })
+            .and_then(|contexts| restore_entry(root, &id, contexts));
+        match result {
+            Ok(()) => restored_count += 1,
+            Err(error) => failed_items.push(SessionTrashFailure { id, error }),
+        }
+    }
+
+    RestoreSessionTrashResult {
+        restored_count,
+        failed_items,
+    }
+}
+
+/// Permanently remove trash entries. With `ids` only those entries are purged;
+/// otherwise entries older than `older_than_days` go, and with neither the whole
+/// trash is emptied. Returns the number of purged entries.
+pub(super) fn purge_entries(
+    root: &Path,
+    ids: Option<&[String]>,
+    older_than_days: Option<u32>,
+    now_ms: i64,
+) -> Result<usize, String> {
+    let cutoff = older_than_days.map(|days| now_ms - i64::from(days) * MILLIS_PER_DAY);
+    let mut purged = 0usize;
+
+    let targets = match ids {
+        Some(ids) => ids
+            .iter()
+            .map(|id| entry_dir(root, id))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => list_entries(root)?
+            .into_iter()
+            .filter(|entry| cutoff.is_none_or(|cutoff| entry.deleted_at < cutoff))
+            .map(|entry| root.join(entry.id))
+            .collect(),
+    };
+
+    for target in targets {
+        if !target.exists() {
+            continue;
+        }
+        std::fs::remove_dir_all(&target).map_err(|error| {
+            format!(
+                "Failed to purge session trash entry {}: {error}",
+                target.display()
+            )
+        })?;
+        purged += 1;
+    }
+
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::session_manager::{single_context_set, SessionTool};
+
+    fn write_codex_session(sessions_root: &Path, session_id: &str) -> PathBuf {
+        let session_path = sessions_root
+            .join("2026")
+            .join("05")
+            .join("01")
+            .join(format!("rollout-2026-05-01T10-00-00-{session_id}.jsonl"));
+        std::fs::create_dir_all(session_path.parent().unwrap()).expect("create session dir");
+        let line = serde_json::json!({
+            "timestamp": "2026-05-01T10:00:00Z",
+            "type": "session_meta",
+            "payload": { "id": session_id, "timestamp": "2026-05-01T10:00:00Z", "cwd": "/repo" }
+        });
+        std::fs::write(&session_path, format!("{line}\n")).expect("write session");
+        session_path
+    }
+
+    fn codex_meta(session_id: &str, source_path: &Path) -> SessionMeta {
+        SessionMeta {
+            provider_id: "codex".to_string(),
+            session_id: session_id.to_string(),
+            title: Some("Fix the build".to_string()),
+            summary: None,
+            project_dir: Some("/repo".to_string()),
+            created_at: None,
+            last_active_at: None,
+            source_path: source_path.to_string_lossy().to_string(),
+            resume_command: None,
+            runtime_source: None,
+            runtime_distro: None,
+        }
+    }
+
+    #[test]
+    fn trashed_codex_session_restores_to_its_original_path() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let sessions_root = temp.path().join("sessions");
+        let session_path = write_codex_session(&sessions_root, "session-a");
+        let original_content = std::fs::read_to_string(&session_path).unwrap();
+        let context = ToolSessionContext::Codex {
+            sessions_root: sessions_root.clone(),
+        };
+        let trash_root = temp.path().join("trash");
+        let trash = SessionTrash::new(trash_root.clone(), SessionTool::Codex.as_str());
+
+        trash
+            .trash_session(&context, &codex_meta("session-a", &session_path))
+            .expect("trash session");
+        assert!(!session_path.exists());
+
+        let entries = list_entries(&trash_root).expect("list trash");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].session_id, "session-a");
+        assert_eq!(entries[0].storage, SessionTrashStorage::Snapshot);
+        assert_eq!(entries[0].title.as_deref(), Some("Fix the build"));
+
+        restore_entry(&trash_root, &entries[0].id, &single_context_set(context))
+            .expect("restore session");
+        assert_eq!(
+            std::fs::read_to_string(&session_path).unwrap(),
+            original_content
+        );
+        assert!(list_entries(&trash_root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn trashed_dsh_session_directory_is_copied_back_verbatim() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let sessions_root = temp.path().join("dsh");
+        let session_dir = sessions_root.join("session-b");
+        std::fs::create_dir_all(session_dir.join("blobs")).unwrap();
+        std::fs::write(session_dir.join("session.jsonl"), "{}\n").unwrap();
+        std::fs::write(session_dir.join("blobs").join("a.bin"), [1u8, 2, 3]).unwrap();
+        let context = ToolSessionContext::Dsh {
+            sessions_root: sessions_root.clone(),
+        };
+        let meta = SessionMeta {
+            provider_id: "dsh".to_string(),
+            ..codex_meta("session-b", &session_dir.join("session.jsonl"))
+        };
+        let trash_root = temp.path().join("trash");
+        let trash = SessionTrash::new(trash_root.clone(), SessionTool::Dsh.as_str());
+
+        trash.trash_session(&context, &meta).expect("trash session");
+        assert!(!session_dir.exists());
+        let entries = list_entries(&trash_root).expect("list trash");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].storage, SessionTrashStorage::Files);
+        assert_eq!(entries[0].original_path, session_dir.to_string_lossy());
+
+        restore_entry(&trash_root, &entries[0].id, &single_context_set(context))
+            .expect("restore session");
+        assert_eq!(
+            std::fs::read(session_dir.join("blobs").join("a.bin")).unwrap(),
+            vec![1u8, 2, 3]
+        );
+        assert!(list_entries(&trash_root).unwrap().is_empty());
+        assert!(entry_dir(&trash_root, "../escape").is_err());
+    }
+
+    #[test]
+    fn sessions_without_trash_support_are_refused_and_kept() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let sessions_root = temp.path().join("hermes");
+        std::fs::create_dir_all(&sessions_root).unwrap();
+        let context = ToolSessionContext::Hermes {
+            sessions_root: sessions_root.clone(),
+        };
+        let meta = SessionMeta {
+            provider_id: "hermes".to_string(),
+            source_path: format!(
+                "sqlite:{}#session-c",
+                sessions_root.join("state.db").display()
+            ),
+            ..codex_meta("session-c", &sessions_root)
+        };
+        let trash_root = temp.path().join("trash");
+        let trash = SessionTrash::new(trash_root.clone(), SessionTool::Hermes.as_str());
+
+        let error = trash.trash_session(&context, &meta).unwrap_err();
+        assert!(error.starts_with(SESSION_TRASH_UNSUPPORTED));
+        assert!(list_entries(&trash_root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn files_restore_rejects_paths_outside_session_roots() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let sessions_root = temp.path().join("dsh");
+        std::fs::create_dir_all(&sessions_root).unwrap();
+        let context = ToolSessionContext::Dsh {
+            sessions_root: sessions_root.clone(),
+        };
+        let trash_root = temp.path().join("trash");
+        let escape = temp.path().join("escape");
+        for (id, original_path) in [
+            ("outside", escape.clone()),
+            ("dotdot", sessions_root.join("..").join("escape")),
+        ] {
+            let dir = trash_root.join(id);
+            std::fs::create_dir_all(dir.join(TRASH_FILES_DIR)).unwrap();
+            std::fs::write(dir.join(TRASH_FILES_DIR).join("escape"), "x").unwrap();
+            write_entry(
+                &dir,
+                &SessionTrashEntry {
+                    id: id.to_string(),
+                    tool: "dsh".to_string(),
+                    session_id: id.to_string(),
+                    title: None,
+                    project_dir: None,
+                    source_path: String::new(),
+                    original_path: original_path.to_string_lossy().to_string(),
+                    runtime_source: None,
+                    runtime_distro: None,
+                    deleted_at: 0,
+                    storage: SessionTrashStorage::Files,
+                    context_key: context.cache_key(),
+                },
+            )
+            .unwrap();
+        }
+
+        let contexts = HashMap::from([("dsh".to_string(), Ok(single_context_set(context)))]);
+        let result = restore_entries(
+            &trash_root,
+            vec!["outside".to_string(), "dotdot".to_string()],
+            &contexts,
+        );
+        assert_eq!(result.restored_count, 0);
+        assert_eq!(result.failed_items.len(), 2);
+        assert!(!escape.exists());
+        assert_eq!(list_entries(&trash_root).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn purge_respects_retention_and_explicit_ids() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let root = temp.path();
+        let now = 100 * MILLIS_PER_DAY;
+        for (id, age_days) in [("old", 45), ("recent", 3), ("picked", 1)] {
+            let dir = root.join(id);
+            std::fs::create_dir_all(&dir).unwrap();
+            write_entry(
+                &dir,
+                &SessionTrashEntry {
+                    id: id.to_string(),
+                    tool: "codex".to_string(),
+                    session_id: id.to_string(),
+                    title: None,
+                    project_dir: None,
+                    source_path: String::new(),
+                    original_path: String::new(),
+                    runtime_source: None,
+                    runtime_distro: None,
+                    deleted_at: now - age_days * MILLIS_PER_DAY,
+                    storage: SessionTrashStorage::Snapshot,
+                    context_key: String::new(),
+                },
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            purge_entries(root, None, Some(DEFAULT_TRASH_RETENTION_DAYS), now).unwrap(),
+            1
+        );
+        assert_eq!(
+            purge_entries(root, Some(&["picked".to_string()]), None, now).unwrap(),
+            1
+        );
+        let remaining = list_entries(root).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, "recent");
+        assert_eq!(purge_entries(root, None, None, now).unwrap(), 1);
+        assert!(list_entries(root).unwrap().is_empty());
+    }
+}
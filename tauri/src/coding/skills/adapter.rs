@@ -1,5 +1,6 @@
 use serde_json::Value;
 
+use super::safety_scan::{normalize_policy, POLICY_WARN};
 use super::tool_adapters::CustomTool;
 use super::types::{Skill, SkillGroupRecord, SkillPreferences, SkillRepo, SkillTarget};
 use crate::coding::db_extract_id;
@@ -55,6 +56,18 @@ pub fn from_db_skill(value: Value) -> Skill {
             .and_then(|v| v.as_str())
             .unwrap_or("active")
             .to_string(),
+        pinned_ref: value
+            .get("pinned_ref")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        semver_updates: value
+            .get("semver_updates")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        safety_report: value
+            .get("safety_report")
+            .filter(|v| !v.is_null())
+            .and_then(|v| serde_json::from_value(v.clone()).ok()),
         sort_index: value
             .get("sort_index")
             .and_then(|v| v.as_i64())
@@ -106,6 +119,9 @@ pub fn to_clean_skill_payload(skill: &Skill) -> Value {
         "updated_at": skill.updated_at,
         "last_sync_at": skill.last_sync_at,
         "status": skill.status,
+        "pinned_ref": skill.pinned_ref,
+        "semver_updates": skill.semver_updates,
+        "safety_report": skill.safety_report,
         "sort_index": skill.sort_index,
         "user_group": skill.user_group,
         "group_id": skill.group_id,
@@ -284,6 +300,11 @@ pub fn from_db_skill_repo(value: Value) -> SkillRepo {
             .get("created_at")
             .and_then(|v| v.as_i64())
             .unwrap_or(0),
+        url: value
+            .get("url")
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string()),
     }
 }
 
@@ -295,6 +316,7 @@ pub fn to_skill_repo_payload(repo: &SkillRepo) -> Value {
         "branch": repo.branch,
         "enabled": repo.enabled,
         "created_at": repo.created_at,
+        "url": repo.url,
     })
 }
 
@@ -351,6 +373,12 @@ pub fn from_db_skill_preferences(value: Value) -> SkillPreferences {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string())
             .unwrap_or_else(|| "0 3 * * *".to_string()),
+        safety_scan_policy: normalize_policy(
+            value
+                .get("safety_scan_policy")
+                .and_then(|v| v.as_str())
+                .unwrap_or(POLICY_WARN),
+        ),
         updated_at: value
             .get("updated_at")
             .and_then(|v| v.as_i64())
@@ -377,6 +405,7 @@ pub fn to_skill_preferences_payload(prefs: &SkillPreferences) -> Value {
         "show_skills_in_tray": prefs.show_skills_in_tray,
         "auto_update_enabled": prefs.auto_update_enabled,
         "auto_update_schedule": prefs.auto_update_schedule,
+        "safety_scan_policy": normalize_policy(&prefs.safety_scan_policy),
         "updated_at": prefs.updated_at,
     })
 }
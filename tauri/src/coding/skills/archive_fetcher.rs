@@ -0,0 +1,605 @@
+//! Built-in archive fetcher for skill sources.
+//!
+//! Tarball and zip URLs are always downloaded over HTTP. Repositories on a
+//! known forge (GitHub, GitLab, Gitea/Forgejo) are downloaded the same way
+//! when no system `git` is available, through the forge's archive endpoint.
+//! Downloads follow the git proxy mode so both paths reach the same network.
+
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::git_fetcher::{get_proxy_mode, GitProxyMode, RemoteRef};
+use crate::http_client::{self, ProxyMode};
+
+const ARCHIVE_TIMEOUT_SECS: u64 = 300;
+const MAX_ARCHIVE_BYTES: usize = 256 * 1024 * 1024;
+const MAX_EXTRACTED_BYTES: u64 = 1024 * 1024 * 1024;
+const ARCHIVE_EXTENSIONS: [&str; 4] = [".tar.gz", ".tgz", ".tar", ".zip"];
+
+/// Hosting flavour of a git remote, which decides its archive URL layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitForge {
+    GitHub,
+    GitLab,
+    Gitea,
+    Unknown,
+}
+
+/// Guess the forge from a host name. Self-hosted instances are only
+/// recognised by their conventional `gitlab.` / `gitea.` / `forgejo.` prefix;
+/// their tree URLs (`/-/tree/`, `/src/branch/`) identify them otherwise.
+pub fn forge_of_host(host: &str) -> GitForge {
+    let host = host.to_ascii_lowercase();
+    if host == "github.com" || host == "www.github.com" {
+        GitForge::GitHub
+    } else if host == "gitlab.com" || host.starts_with("gitlab.") {
+        GitForge::GitLab
+    } else if host == "codeberg.org"
+        || host == "gitea.com"
+        || host.starts_with("gitea.")
+        || host.starts_with("forgejo.")
+    {
+        GitForge::Gitea
+    } else {
+        GitForge::Unknown
+    }
+}
+
+/// True when `url` is an http(s) link to a tarball or zip file
+pub fn is_archive_url(url: &str) -> bool {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return false;
+    };
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return false;
+    }
+    let path = parsed.path().to_ascii_lowercase();
+    ARCHIVE_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+}
+
+/// Strip a trailing archive extension (`.tar.gz`, `.zip`, ...) from a file name
+pub fn strip_archive_extension(name: &str) -> &str {
+    let lower = name.to_ascii_lowercase();
+    ARCHIVE_EXTENSIONS
+        .iter()
+        .find(|ext| lower.ends_with(*ext))
+        .map(|ext| &name[..name.len() - ext.len()])
+        .unwrap_or(name)
+}
+
+/// Web base URL (`https://host[:port]`) and repository path of a git remote
+/// given in https, ssh or scp (`git@host:group/repo.git`) form.
+pub fn split_remote(clone_url: &str) -> Option<(String, String)> {
+    let clone_url = clone_url.trim();
+    let (base, path) = match url::Url::parse(clone_url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {
+            let host = parsed.host_str()?;
+            let base = match parsed.port() {
+                Some(port) => format!("{}://{}:{}", parsed.scheme(), host, port),
+                None => format!("{}://{}", parsed.scheme(), host),
+            };
+            (base, parsed.path().to_string())
+        }
+        // The ssh port never serves the web UI, so it is dropped
+        Ok(parsed) if matches!(parsed.scheme(), "ssh" | "git") => (
+            format!("https://{}", parsed.host_str()?),
+            parsed.path().to_string(),
+        ),
+        _ if clone_url.contains("://") => return None,
+        _ => {
+            let (left, path) = clone_url.split_once(':')?;
+            let host = left.rsplit('@').next()?;
+            // A one-letter "host" is a Windows drive path
+            if host.len() <= 1 || host.contains('/') {
+                return None;
+            }
+            (format!("https://{}", host), path.to_string())
+        }
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path).to_string();
+    if !path.contains('/') {
+        return None;
+    }
+    Some((base, path))
+}
+
+/// Archive download URL of `reference` (branch, tag or commit) in a forge
+/// repository, or of its default branch when `reference` is None.
+pub async fn forge_archive_url(
+    clone_url: &str,
+    forge: GitForge,
+    reference: Option<&str>,
+) -> Result<String> {
+    let Some((base, path)) = split_remote(clone_url) else {
+        anyhow::bail!("GIT_NOT_FOUND");
+    };
+    match forge {
+        GitForge::GitHub => Ok(format!(
+            "https://codeload.github.com/{}/tar.gz/{}",
+            path,
+            reference.unwrap_or("HEAD")
+        )),
+        GitForge::GitLab => {
+            let mut url = format!(
+                "{}/api/v4/projects/{}/repository/archive.tar.gz",
+                base,
+                encode_component(&path)
+            );
+            if let Some(reference) = reference {
+                url.push_str("?sha=");
+                url.push_str(&encode_component(reference));
+            }
+            Ok(url)
+        }
+        GitForge::Gitea => {
+            let reference = match reference {
+                Some(reference) => reference.to_string(),
+                None => gitea_default_branch(&base, &path).await?,
+            };
+            Ok(format!("{}/{}/archive/{}.tar.gz", base, path, reference))
+        }
+        GitForge::Unknown => anyhow::bail!("GIT_NOT_FOUND"),
+    }
+}
+
+fn encode_component(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// Gitea's archive route needs an explicit ref; ask the API for the default branch.
+async fn gitea_default_branch(base: &str, path: &str) -> Result<String> {
+    let url = format!("{}/api/v1/repos/{}", base, path);
+    default_branch_of(&url).await
+}
+
+async fn default_branch_of(repo_api_url: &str) -> Result<String> {
+    forge_json(repo_api_url)
+        .await?
+        .get("default_branch")
+        .and_then(|value| value.as_str())
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            anyhow::anyhow!("ARCHIVE_DOWNLOAD_FAILED|{}|no default branch", repo_api_url)
+        })
+}
+
+async fn forge_json(url: &str) -> Result<serde_json::Value> {
+    let response = archive_client()?
+        .get(url)
+        .header(reqwest::header::USER_AGENT, "AI-Toolbox")
+        .send()
+        .await
+        .map_err(|err| anyhow::anyhow!("ARCHIVE_DOWNLOAD_FAILED|{}|{}", url, err))?;
+    if !response.status().is_success() {
+        anyhow::bail!("ARCHIVE_DOWNLOAD_FAILED|{}|HTTP {}", url, response.status());
+    }
+    response
+        .json()
+        .await
+        .map_err(|err| anyhow::anyhow!("ARCHIVE_DOWNLOAD_FAILED|{}|{}", url, err))
+}
+
+/// Branches, tags and `HEAD` of a forge repository read from its REST API,
+/// in the shape `git ls-remote` gives, for update checks without `git`.
+/// Only the first page (100 entries) of branches and tags is read.
+pub async fn forge_remote_refs(clone_url: &str, forge: GitForge) -> Result<Vec<RemoteRef>> {
+    let Some((base, path)) = split_remote(clone_url) else {
+        anyhow::bail!("GIT_NOT_FOUND");
+    };
+    let repo_api = match forge {
+        GitForge::GitHub => format!("https://api.github.com/repos/{}", path),
+        GitForge::GitLab => format!("{}/api/v4/projects/{}", base, encode_component(&path)),
+        GitForge::Gitea => format!("{}/api/v1/repos/{}", base, path),
+        GitForge::Unknown => anyhow::bail!("GIT_NOT_FOUND"),
+    };
+    let (branches_url, tags_url) = match forge {
+        GitForge::GitLab => (
+            format!("{}/repository/branches?per_page=100", repo_api),
+            format!("{}/repository/tags?per_page=100", repo_api),
+        ),
+        GitForge::Gitea => (
+            format!("{}/branches?limit=100", repo_api),
+            format!("{}/tags?limit=100", repo_api),
+        ),
+        _ => (
+            format!("{}/branches?per_page=100", repo_api),
+            format!("{}/tags?per_page=100", repo_api),
+        ),
+    };
+
+    let default_branch = default_branch_of(&repo_api).await?;
+    let branches = forge_json(&branches_url).await?;
+    let tags = forge_json(&tags_url).await?;
+    Ok(refs_from_forge_lists(&default_branch, &branches, &tags))
+}
+
+/// Map forge branch/tag lists (`[{name, commit: {sha | id}}]`) to remote refs.
+fn refs_from_forge_lists(
+    default_branch: &str,
+    branches: &serde_json::Value,
+    tags: &serde_json::Value,
+) -> Vec<RemoteRef> {
+    let named_commits = |list: &serde_json::Value| -> Vec<(String, String)> {
+        list.as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| {
+                        let name = item.get("name")?.as_str()?;
+                        let commit = item.get("commit")?;
+                        let revision = commit.get("sha").or_else(|| commit.get("id"))?.as_str()?;
+                        Some((name.to_string(), revision.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let mut refs = Vec::new();
+    for (name, revision) in named_commits(branches) {
+        if name == default_branch {
+            refs.push(RemoteRef {
+                name: "HEAD".to_string(),
+                revision: revision.clone(),
+            });
+        }
+        refs.push(RemoteRef {
+            name: format!("refs/heads/{}", name),
+            revision,
+        });
+    }
+    for (name, revision) in named_commits(tags) {
+        refs.push(RemoteRef {
+            name: format!("refs/tags/{}", name),
+            revision,
+        });
+    }
+    refs
+}
+
+fn archive_client() -> Result<reqwest::Client> {
+    let (mode, proxy_url) = match get_proxy_mode() {
+        GitProxyMode::Direct => (ProxyMode::Direct, String::new()),
+        GitProxyMode::Custom(url) => (ProxyMode::Custom, url),
+        GitProxyMode::System => (ProxyMode::System, String::new()),
+    };
+    http_client::client_for_proxy_mode(mode, &proxy_url, ARCHIVE_TIMEOUT_SECS)
+        .map_err(anyhow::Error::msg)
+}
+
+/// Download an archive into memory, refusing anything over the size cap.
+pub async fn download_archive(url: &str) -> Result<Vec<u8>> {
+    let mut response = archive_client()?
+        .get(url)
+        .header(reqwest::header::USER_AGENT, "AI-Toolbox")
+        .send()
+        .await
+        .map_err(|err| anyhow::anyhow!("ARCHIVE_DOWNLOAD_FAILED|{}|{}", url, err))?;
+    if !response.status().is_success() {
+        anyhow::bail!("ARCHIVE_DOWNLOAD_FAILED|{}|HTTP {}", url, response.status());
+    }
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_ARCHIVE_BYTES as u64)
+    {
+        anyhow::bail!("ARCHIVE_TOO_LARGE|{}|{}", url, MAX_ARCHIVE_BYTES);
+    }
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|err| anyhow::anyhow!("ARCHIVE_DOWNLOAD_FAILED|{}|{}", url, err))?
+    {
+        if bytes.len() + chunk.len() > MAX_ARCHIVE_BYTES {
+            anyhow::bail!("ARCHIVE_TOO_LARGE|{}|{}", url, MAX_ARCHIVE_BYTES);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+/// Unpack a tar, tar.gz or zip archive into `dest`, replacing it.
+///
+/// The format is sniffed from the content, not the URL. A single top-level
+/// folder (forges wrap archives in `repo-ref/`) is stripped, and only regular
+/// files and directories are written. Returns the commit id `git archive`
+/// embeds in the archive, or a content digest when there is none.
+pub fn extract_archive(bytes: &[u8], dest: &Path) -> Result<String> {
+    let staging = PathBuf::from(format!("{}.extracting", dest.display()));
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)
+            .with_context(|| format!("failed to clear {:?}", staging))?;
+    }
+    std::fs::create_dir_all(&staging).with_context(|| format!("failed to create {:?}", staging))?;
+
+    let unpacked = if bytes.starts_with(b"PK\x03\x04") {
+        unpack_zip(bytes, &staging)
+    } else if bytes.starts_with(&[0x1f, 0x8b]) {
+        unpack_tar(flate2::read::GzDecoder::new(bytes), &staging)
+    } else if bytes.get(257..262) == Some(b"ustar".as_slice()) {
+        unpack_tar(bytes, &staging)
+    } else {
+        Err(anyhow::anyhow!(
+            "ARCHIVE_UNSUPPORTED|expected a .tar.gz, .tar or .zip file"
+        ))
+    };
+    let commit = match unpacked {
+        Ok(commit) => commit,
+        Err(err) => {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(err);
+        }
+    };
+
+    let root = single_child_dir(&staging).unwrap_or_else(|| staging.clone());
+    if dest.exists() {
+        std::fs::remove_dir_all(dest).with_context(|| format!("failed to clear {:?}", dest))?;
+    }
+    std::fs::rename(&root, dest)
+        .with_context(|| format!("failed to move {:?} -> {:?}", root, dest))?;
+    if root != staging {
+        let _ = std::fs::remove_dir_all(&staging);
+    }
+
+    Ok(commit.unwrap_or_else(|| archive_digest(bytes)))
+}
+
+fn unpack_tar<R: Read>(reader: R, staging: &Path) -> Result<Option<String>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut commit = None;
+    let mut total: u64 = 0;
+    for entry in archive
+        .entries()
+        .context("ARCHIVE_INVALID|unreadable tar")?
+    {
+        let mut entry = entry.context("ARCHIVE_INVALID|corrupt tar entry")?;
+        let entry_type = entry.header().entry_type();
+        if entry_type == tar::EntryType::XGlobalHeader {
+            if let Some(extensions) = entry.pax_extensions()? {
+                for extension in extensions.flatten() {
+                    if extension.key() == Ok("comment") {
+                        commit = extension.value().ok().and_then(commit_id);
+                    }
+                }
+            }
+            continue;
+        }
+        if !entry_type.is_file() && !entry_type.is_dir() {
+            continue;
+        }
+        total += entry.header().size().unwrap_or(0);
+        if total > MAX_EXTRACTED_BYTES {
+            anyhow::bail!("ARCHIVE_TOO_LARGE|extracted|{}", MAX_EXTRACTED_BYTES);
+        }
+        // unpack_in refuses absolute paths and `..` components
+        entry
+            .unpack_in(staging)
+            .context("ARCHIVE_INVALID|failed to unpack tar entry")?;
+    }
+    Ok(commit)
+}
+
+fn unpack_zip(bytes: &[u8], staging: &Path) -> Result<Option<String>> {
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(bytes)).context("ARCHIVE_INVALID|unreadable zip")?;
+    let commit = std::str::from_utf8(archive.comment())
+        .ok()
+        .and_then(commit_id);
+    let mut total: u64 = 0;
+    for index in 0..archive.len() {
+        let mut file = archive
+            .by_index(index)
+            .context("ARCHIVE_INVALID|corrupt zip entry")?;
+        let Some(relative) = file.enclosed_name() else {
+            continue;
+        };
+        if file.is_symlink() {
+            continue;
+        }
+        let out_path = staging.join(relative);
+        if file.is_dir() {
+            std::fs::create_dir_all(&out_path)?;
+            continue;
+        }
+        // The declared size is only a hint: the copy itself is capped too
+        let remaining = MAX_EXTRACTED_BYTES - total;
+        if file.size() > remaining {
+            anyhow::bail!("ARCHIVE_TOO_LARGE|extracted|{}", MAX_EXTRACTED_BYTES);
+        }
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut out = std::fs::File::create(&out_path)
+            .with_context(|| format!("failed to create {:?}", out_path))?;
+        let written = std::io::copy(&mut (&mut file).take(remaining + 1), &mut out)
+            .with_context(|| format!("failed to write {:?}", out_path))?;
+        if written > remaining {
+            anyhow::bail!("ARCHIVE_TOO_LARGE|extracted|{}", MAX_EXTRACTED_BYTES);
+        }
+        total += written;
+        #[cfg(unix)]
+        if let Some(mode) = file.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            let _ =
+                std::fs::set_permissions(&out_path, std::fs::Permissions::from_mode(mode & 0o777));
+        }
+    }
+    Ok(commit)
+}
+
+fn commit_id(value: &str) -> Option<String> {
+    let value = value.trim();
+    (value.len() == 40 && value.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| value.to_ascii_lowercase())
+}
+
+fn archive_digest(bytes: &[u8]) -> String {
+    use sha2::Digest;
+    let digest = hex::encode(sha2::Sha256::digest(bytes));
+    format!("sha256-{}", &digest[..16])
+}
+
+fn single_child_dir(dir: &Path) -> Option<PathBuf> {
+    let mut entries = std::fs::read_dir(dir).ok()?.flatten();
+    let only = entries.next()?;
+    if entries.next().is_some() || !only.path().is_dir() {
+        return None;
+    }
+    Some(only.path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const COMMIT: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    #[test]
+    fn split_remote_handles_https_ssh_and_scp() {
+        assert_eq!(
+            split_remote("https://gitlab.example.com:8443/group/sub/repo.git"),
+            Some((
+                "https://gitlab.example.com:8443".to_string(),
+                "group/sub/repo".to_string()
+            ))
+        );
+        assert_eq!(
+            split_remote("ssh://git@codeberg.org:2222/owner/repo.git"),
+            Some(("https://codeberg.org".to_string(), "owner/repo".to_string()))
+        );
+        assert_eq!(
+            split_remote("git@github.com:owner/repo.git"),
+            Some(("https://github.com".to_string(), "owner/repo".to_string()))
+        );
+        assert_eq!(split_remote("C:/skills/repo"), None);
+        assert_eq!(split_remote("https://example.com/repo"), None);
+    }
+
+    #[tokio::test]
+    async fn archive_urls_follow_each_forge_layout() {
+        let github = forge_archive_url("git@github.com:owner/repo.git", GitForge::GitHub, None)
+            .await
+            .expect("github url");
+        assert_eq!(github, "https://codeload.github.com/owner/repo/tar.gz/HEAD");
+
+        let gitlab = forge_archive_url(
+            "https://gitlab.com/group/sub/repo.git",
+            GitForge::GitLab,
+            Some("release/1.0"),
+        )
+        .await
+        .expect("gitlab url");
+        assert_eq!(
+            gitlab,
+            "https://gitlab.com/api/v4/projects/group%2Fsub%2Frepo/repository/archive.tar.gz?sha=release%2F1.0"
+        );
+
+        let gitea = forge_archive_url(
+            "https://codeberg.org/owner/repo",
+            GitForge::Gitea,
+            Some("v1.2.0"),
+        )
+        .await
+        .expect("gitea url");
+        assert_eq!(
+            gitea,
+            "https://codeberg.org/owner/repo/archive/v1.2.0.tar.gz"
+        );
+
+        assert!(is_archive_url(
+            "https://example.com/dl/skill.tar.gz?token=1"
+        ));
+        assert!(!is_archive_url("https://example.com/owner/repo"));
+        assert_eq!(strip_archive_extension("my-skill.tar.gz"), "my-skill");
+    }
+
+    #[test]
+    fn forge_lists_map_to_ls_remote_refs() {
+        let branches = serde_json::json!([
+            { "name": "main", "commit": { "sha": "a1" } },
+            { "name": "dev", "commit": { "id": "b2" } },
+        ]);
+        let tags = serde_json::json!([{ "name": "v1.2.0", "commit": { "sha": "c3" } }]);
+        let refs = refs_from_forge_lists("main", &branches, &tags);
+        let pairs: Vec<(&str, &str)> = refs
+            .iter()
+            .map(|r| (r.name.as_str(), r.revision.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("HEAD", "a1"),
+                ("refs/heads/main", "a1"),
+                ("refs/heads/dev", "b2"),
+                ("refs/tags/v1.2.0", "c3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_tar_gz_strips_wrapper_and_reads_commit() {
+        let mut builder = tar::Builder::new(Vec::new());
+
+        let pax = format!("52 comment={}\n", COMMIT);
+        let mut header = tar::Header::new_ustar();
+        header.set_entry_type(tar::EntryType::XGlobalHeader);
+        header.set_path("pax_global_header").unwrap();
+        header.set_size(pax.len() as u64);
+        header.set_cksum();
+        builder.append(&header, pax.as_bytes()).unwrap();
+
+        for (path, body) in [
+            ("repo-main/SKILL.md", "---\nname: demo\n---\n"),
+            ("repo-main/scripts/run.sh", "echo hi\n"),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(body.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, body.as_bytes())
+                .unwrap();
+        }
+        let tar_bytes = builder.into_inner().unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        encoder.write_all(&tar_bytes).unwrap();
+        let gz = encoder.finish().unwrap();
+
+        let temp = tempfile::tempdir().unwrap();
+        let dest = temp.path().join("cache");
+        let revision = extract_archive(&gz, &dest).expect("extract");
+
+        assert_eq!(revision, COMMIT);
+        assert!(dest.join("SKILL.md").is_file());
+        assert!(dest.join("scripts/run.sh").is_file());
+        assert!(!temp.path().join("cache.extracting").exists());
+    }
+
+    #[test]
+    fn extract_zip_keeps_multiple_roots_and_skips_escapes() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("alpha/SKILL.md", options).unwrap();
+        writer.write_all(b"alpha").unwrap();
+        writer.start_file("beta/SKILL.md", options).unwrap();
+        writer.write_all(b"beta").unwrap();
+        writer.start_file("../escape.txt", options).unwrap();
+        writer.write_all(b"nope").unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let temp = tempfile::tempdir().unwrap();
+        let dest = temp.path().join("cache");
+        let revision = extract_archive(&bytes, &dest).expect("extract");
+
+        assert!(revision.starts_with("sha256-"));
+        assert!(dest.join("alpha/SKILL.md").is_file());
+        assert!(dest.join("beta/SKILL.md").is_file());
+        assert!(!temp.path().join("escape.txt").exists());
+    }
+}
@@ -4,6 +4,9 @@
 //! Mirrors the `auth_refresh` scheduler pattern: a startup pass plus a tick-loop that
 //! fires whenever the scheduled time after the last run has passed. Failures are logged
 //! only, never surfaced as UI: auto-update is background/silent by design.
+//!
+//! Pinned git skills are reinstalled at their pin (a no-op when unchanged);
+//! pins with `semver_updates` move to the newest compatible tag first.
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
@@ -94,7 +94,8 @@ fn cleanup_git_cache_dirs_in(cache_dir: &Path, max_age: Duration) -> Result<usiz
             continue;
         }
 
-        if !path.join(".git").exists() {
+        // Archive downloads have no .git but carry the same cache meta file
+        if !path.join(".git").exists() && !path.join(CACHE_META_FILE).exists() {
             continue;
         }
 
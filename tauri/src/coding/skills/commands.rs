@@ -17,17 +17,25 @@ use super::central_repo::{
 };
 use super::content_hash::hash_dir;
 use super::cron_utils::parse_cron;
-use super::git_fetcher::{set_proxy, GitProxyMode};
+use super::git_fetcher::RemoteRef;
 use super::installer::{
-    install_git_skill, install_git_skill_from_selection, install_local_skill,
-    install_local_skill_from_selection, list_git_skills, list_local_skills,
-    update_managed_skill_from_source,
+    fetch_git_skill_source, git_remote_of_source, init_proxy_from_settings, install_git_skill,
+    install_git_skill_from_selection, install_local_skill, install_local_skill_from_selection,
+    is_archive_source, list_git_skills, list_local_skills, list_source_refs,
+    update_managed_skill_from_source, update_managed_skill_to_lock,
+};
+use super::lockfile::{
+    build_lockfile, check_skill_update, failed_update_check, parse_lockfile, parse_semver_tag,
+    revision_matches, semver_update_target,
 };
 use super::onboarding::build_onboarding_plan;
 use super::path_executor::{
     remove_skill_target_checked, sync_skill_to_target, target_path_changed,
     validate_skill_sync_target,
 };
+use super::revision_diff::diff_skill_dirs;
+use super::safety_scan::{normalize_policy, scan_skill_dir, POLICY_WARN};
+use super::skill_lint::{default_lint_tools, lint_skill_dir, read_manifest_name};
 use super::skill_store;
 use super::sync_engine::{copy_dir_recursive, ensure_source_target_not_overlapping};
 use super::tool_adapters::{
@@ -43,12 +51,13 @@ use super::types::{
     DetectedCentralSkillDto, GitSkillCandidate, InstallResultDto, ManagedSkillDto,
     ManagedSkillSummaryDto, OnboardingPlan, Skill, SkillGroupDto, SkillGroupRecord,
     SkillInventoryGroupJson, SkillInventoryJson, SkillInventoryPreviewDto, SkillInventorySkillJson,
-    SkillRepo, SkillRepoDto, SkillTarget, SkillTargetDto, SyncResultDto, SkillDocumentDto,
-    ToolInfoDto, ToolStatusDto, UpdateAllErrorDto, UpdateAllResultDto, UpdateResultDto,
-    SkillsUpdateProgress,
+    SkillLintReportDto,
+    SkillLockfileApplyResultDto, SkillRepo, SkillRepoDto, SkillSafetyReport, SkillTarget,
+    SkillTargetDto,
+    SkillUpdateCheckDto, SkillUpdatePreviewDto, SyncResultDto, SkillDocumentDto, ToolInfoDto,
+    ToolStatusDto, UpdateAllErrorDto, UpdateAllResultDto, UpdateResultDto, SkillsUpdateProgress,
 };
 use crate::coding::runtime_location;
-use crate::http_client;
 use crate::SqliteDbState;
 
 fn format_error(err: anyhow::Error) -> String {
@@ -58,6 +67,7 @@ fn format_error(err: anyhow::Error) -> String {
         || first.starts_with("TARGET_EXISTS|")
         || first.starts_with("TOOL_NOT_INSTALLED|")
         || first.starts_with("SKILL_DISABLED|")
+        || first.starts_with("SKILL_UNSAFE|")
     {
         return first;
     }
@@ -856,6 +866,9 @@ async fn adopt_detected_central_skill(
         updated_at: now,
         last_sync_at: None,
         status: "ok".to_string(),
+        pinned_ref: None,
+        semver_updates: false,
+        safety_report: Some(scan_skill_dir(&source_path)),
         sort_index: 0,
         user_group: None,
         group_id: None,
@@ -1228,6 +1241,10 @@ pub async fn skills_get_managed_skills(
             name: skill.name,
             source_type: skill.source_type,
             source_ref: skill.source_ref,
+            source_revision: skill.source_revision,
+            pinned_ref: skill.pinned_ref,
+            semver_updates: skill.semver_updates,
+            safety_report: skill.safety_report,
             central_path: resolved_path.to_string_lossy().to_string(),
             created_at: skill.created_at,
             updated_at: skill.updated_at,
@@ -1588,24 +1605,9 @@ pub async fn skills_list_git_skills(
     repoUrl: String,
     branch: Option<String>,
 ) -> Result<Vec<GitSkillCandidate>, String> {
-    // Initialize proxy from app settings
-    let proxy_result = http_client::get_proxy_from_settings(&state).await.ok();
-    let proxy_mode = match proxy_result {
-        Some((http_client::ProxyMode::Direct, _)) => GitProxyMode::Direct,
-        Some((http_client::ProxyMode::Custom, url)) if !url.is_empty() => GitProxyMode::Custom(url),
-        _ => GitProxyMode::System,
-    };
-    set_proxy(proxy_mode);
-
-    let ttl = get_git_cache_ttl_secs(&state).await;
-    let branch_clone = branch.clone();
-
-    tokio::task::spawn_blocking(move || {
-        list_git_skills(&app, ttl, &repoUrl, branch_clone.as_deref())
-    })
-    .await
-    .map_err(|e| e.to_string())?
-    .map_err(|e| format_error(e))
+    list_git_skills(&app, &state, &repoUrl, branch.as_deref())
+        .await
+        .map_err(|e| format_error(e))
 }
 
 #[tauri::command]
@@ -1639,7 +1641,7 @@ pub async fn skills_install_git_selection(
 
 // --- Sync Skills ---
 
-async fn resolve_skill_source_path<R: Runtime>(
+pub(crate) async fn resolve_skill_source_path<R: Runtime>(
     app: &AppHandle<R>,
     state: &SqliteDbState,
     skill: &Skill,
@@ -1944,9 +1946,10 @@ async fn update_managed_skill_internal(
                 updated_targets,
             });
         }
+        advance_semver_pin(state, &skill).await?;
     }
 
-    let res = update_managed_skill_from_source(app, state, skill_id)
+    let res = update_managed_skill_from_source(app, state, skill_id, None)
         .await
         .map_err(|e| format_error(e))?;
 
@@ -2042,6 +2045,321 @@ pub async fn skills_update_all(
     update_all_skills_internal(&app, &state, true).await
 }
 
+// --- Version Pins & Lockfile ---
+
+/// Move a semver-tracking pin to the newest compatible tag so the update that
+/// follows installs it. Every other skill keeps its pin (or branch) as is.
+async fn advance_semver_pin(state: &SqliteDbState, skill: &Skill) -> Result<(), String> {
+    if skill.source_type != "git" || !skill.semver_updates {
+        return Ok(());
+    }
+    let Some(source_ref) = skill.source_ref.as_deref() else {
+        return Ok(());
+    };
+    if is_archive_source(source_ref) {
+        return Ok(());
+    }
+    init_proxy_from_settings(state).await;
+    let refs = match list_source_refs(source_ref).await {
+        Ok(refs) => refs,
+        // No git and no forge API to ask: update within the current pin
+        Err(error) if error.to_string() == "GIT_NOT_FOUND" => {
+            log::info!(
+                "[skills] cannot list tags of '{}' without git, keeping its pin",
+                skill.name
+            );
+            return Ok(());
+        }
+        Err(error) => return Err(format_error(error)),
+    };
+    if let Some(tag) = semver_update_target(skill, &refs) {
+        log::info!("[skills] moving pin of '{}' to {}", skill.name, tag);
+        skill_store::set_skill_pin(state, &skill.id, Some(tag), true).await?;
+    }
+    Ok(())
+}
+
+async fn get_git_skill(state: &SqliteDbState, skill_id: &str) -> Result<Skill, String> {
+    let skill = skill_store::get_skill_by_id(state, skill_id)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skill_id))?;
+    if skill.source_type != "git" || skill.source_ref.is_none() {
+        return Err(format!("Skill '{}' is not installed from git", skill.name));
+    }
+    Ok(skill)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_set_pin(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    pinnedRef: Option<String>,
+    semverUpdates: bool,
+) -> Result<(), String> {
+    let skill = get_git_skill(&state, &skillId).await?;
+    let pinned_ref = normalize_optional_text(pinnedRef);
+    let pins_version_tag = pinned_ref.as_deref().and_then(parse_semver_tag).is_some();
+    if semverUpdates && !pins_version_tag {
+        return Err("Semver updates need a pin on a version tag such as v1.2.0".to_string());
+    }
+    skill_store::set_skill_pin(&state, &skill.id, pinned_ref.clone(), semverUpdates).await?;
+
+    // Install a new pin right away, unless it names what is already installed.
+    let installed = match (&pinned_ref, &skill.source_revision) {
+        (None, _) => true,
+        (Some(reference), Some(revision)) => {
+            revision_matches(revision, reference)
+                || skill.pinned_ref.as_deref() == Some(reference.as_str())
+        }
+        (Some(_), None) => false,
+    };
+    if !installed {
+        update_managed_skill_from_source(&app, &state, &skill.id, None)
+            .await
+            .map_err(format_error)?;
+        let _ = app.emit("skills-changed", "window");
+    }
+    Ok(())
+}
+
+/// Check git skills for upstream changes with `git ls-remote`; nothing is
+/// downloaded or applied. Checks all git skills when `skillIds` is None.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_check_updates(
+    state: State<'_, SqliteDbState>,
+    skillIds: Option<Vec<String>>,
+) -> Result<Vec<SkillUpdateCheckDto>, String> {
+    init_proxy_from_settings(&state).await;
+    let skills = skill_store::get_managed_skills(&state).await?;
+
+    // Skills from the same repository share one ref listing.
+    let mut refs_by_url: HashMap<String, Result<Vec<RemoteRef>, String>> = HashMap::new();
+    let mut results = Vec::new();
+    for skill in skills {
+        if skill.source_type != "git" {
+            continue;
+        }
+        if let Some(ids) = &skillIds {
+            if !ids.contains(&skill.id) {
+                continue;
+            }
+        }
+        let Some(source_ref) = skill.source_ref.as_deref() else {
+            continue;
+        };
+        // Archives have no refs to compare; updating downloads them again
+        if is_archive_source(source_ref) {
+            continue;
+        }
+        let (clone_url, branch) = git_remote_of_source(source_ref);
+        if !refs_by_url.contains_key(&clone_url) {
+            let refs = list_source_refs(source_ref).await.map_err(format_error);
+            refs_by_url.insert(clone_url.clone(), refs);
+        }
+        let refs = &refs_by_url[&clone_url];
+        results.push(match refs {
+            Ok(refs) => check_skill_update(&skill, refs, branch.as_deref()),
+            Err(error) => failed_update_check(&skill, Some(error.clone())),
+        });
+    }
+    Ok(results)
+}
+
+/// Per-file diff of the central repo copy against `reference` (a commit or
+/// tag), for review before accepting an update.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_preview_update(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    reference: String,
+) -> Result<SkillUpdatePreviewDto, String> {
+    let skill = get_git_skill(&state, &skillId).await?;
+    let reference = reference.trim().to_string();
+    if reference.is_empty() {
+        return Err("Update reference is required".to_string());
+    }
+    init_proxy_from_settings(&state).await;
+    let source_ref = skill.source_ref.clone().unwrap_or_default();
+    let (candidate, revision) = fetch_git_skill_source(&app, &state, &source_ref, Some(&reference))
+        .await
+        .map_err(format_error)?;
+    let current = resolve_skill_source_path(&app, &state, &skill).await?;
+    let files = diff_skill_dirs(&current, &candidate).map_err(format_error)?;
+
+    Ok(SkillUpdatePreviewDto {
+        skill_id: skill.id,
+        name: skill.name,
+        from_revision: skill.source_revision,
+        to_ref: reference,
+        to_revision: revision,
+        files,
+    })
+}
+
+/// Install a reviewed update. A pinned skill moves its pin to `reference`; an
+/// unpinned one installs exactly the reviewed revision and keeps tracking its
+/// branch afterwards.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_accept_update(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    reference: String,
+) -> Result<UpdateResultDto, String> {
+    let skill = get_git_skill(&state, &skillId).await?;
+    let reference = reference.trim().to_string();
+    if reference.is_empty() {
+        return Err("Update reference is required".to_string());
+    }
+
+    let res = if skill.pinned_ref.is_some() {
+        let semver_updates = skill.semver_updates && parse_semver_tag(&reference).is_some();
+        skill_store::set_skill_pin(&state, &skill.id, Some(reference), semver_updates).await?;
+        update_managed_skill_from_source(&app, &state, &skill.id, None).await
+    } else {
+        update_managed_skill_from_source(&app, &state, &skill.id, Some(&reference)).await
+    }
+    .map_err(format_error)?;
+
+    let _ = app.emit("skills-changed", "window");
+
+    Ok(UpdateResultDto {
+        skill_id: res.skill_id,
+        name: res.name,
+        content_hash: res.content_hash,
+        source_revision: res.source_revision,
+        updated_targets: res.updated_targets,
+    })
+}
+
+#[tauri::command]
+pub async fn skills_export_lockfile(state: State<'_, SqliteDbState>) -> Result<String, String> {
+    build_lockfile_json(&state).await
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_export_lockfile_file(
+    state: State<'_, SqliteDbState>,
+    filePath: String,
+) -> Result<String, String> {
+    let json = build_lockfile_json(&state).await?;
+    let path = PathBuf::from(filePath.trim());
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create lockfile directory: {}", e))?;
+    }
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write lockfile: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+async fn build_lockfile_json(state: &SqliteDbState) -> Result<String, String> {
+    let skills = skill_store::get_managed_skills(state).await?;
+    let json = serde_json::to_string_pretty(&build_lockfile(&skills))
+        .map_err(|e| format!("Failed to serialize lockfile: {}", e))?;
+    Ok(format!("{}\n", json))
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_apply_lockfile(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    lockfileJson: String,
+) -> Result<SkillLockfileApplyResultDto, String> {
+    apply_lockfile(&app, &state, &lockfileJson).await
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_apply_lockfile_file(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    filePath: String,
+) -> Result<SkillLockfileApplyResultDto, String> {
+    let raw = std::fs::read_to_string(filePath.trim())
+        .map_err(|e| format!("Failed to read lockfile: {}", e))?;
+    apply_lockfile(&app, &state, &raw).await
+}
+
+/// Pin installed git skills to the lockfile refs and install any whose
+/// revision differs. Skills are matched by name and repository; entries with
+/// no match are reported, not installed.
+async fn apply_lockfile(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    raw: &str,
+) -> Result<SkillLockfileApplyResultDto, String> {
+    let lockfile = parse_lockfile(raw)?;
+    let skills = skill_store::get_managed_skills(state).await?;
+    let mut result = SkillLockfileApplyResultDto {
+        pinned: Vec::new(),
+        updated: Vec::new(),
+        missing: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for entry in lockfile.skills {
+        let (entry_url, _) = git_remote_of_source(&entry.source);
+        let Some(skill) = skills.iter().find(|skill| {
+            skill.source_type == "git"
+                && skill.name == entry.name
+                && skill
+                    .source_ref
+                    .as_deref()
+                    .is_some_and(|source| git_remote_of_source(source).0 == entry_url)
+        }) else {
+            result.missing.push(entry.name);
+            continue;
+        };
+
+        let pinned_ref = entry.pinned_ref.trim().to_string();
+        // Same rule as skills_set_pin: only a version tag can track semver.
+        let semver_updates = entry.semver_updates && parse_semver_tag(&pinned_ref).is_some();
+        skill_store::set_skill_pin(state, &skill.id, Some(pinned_ref.clone()), semver_updates)
+            .await?;
+        result.pinned.push(skill.name.clone());
+
+        let up_to_date = match (skill.source_revision.as_deref(), entry.revision.as_deref()) {
+            (Some(installed), Some(locked)) => installed == locked,
+            (Some(installed), None) => revision_matches(installed, &pinned_ref),
+            (None, _) => false,
+        };
+        if up_to_date {
+            continue;
+        }
+
+        // Fetch the locked commit itself rather than the (movable) pinned ref,
+        // and refuse content whose hash differs before the central copy is
+        // replaced.
+        match update_managed_skill_to_lock(
+            app,
+            state,
+            &skill.id,
+            entry.revision.as_deref(),
+            entry.content_hash.as_deref(),
+        )
+        .await
+        {
+            Ok(_) => result.updated.push(skill.name.clone()),
+            Err(error) => result.errors.push(UpdateAllErrorDto {
+                skill_id: skill.id.clone(),
+                name: skill.name.clone(),
+                error: format_error(error),
+            }),
+        }
+    }
+
+    let _ = app.emit("skills-changed", "window");
+    Ok(result)
+}
+
 #[tauri::command]
 #[allow(non_snake_case)]
 pub async fn skills_delete_managed(
@@ -2101,6 +2419,117 @@ pub async fn skills_delete_managed(
     Ok(())
 }
 
+// --- Safety Scan ---
+
+/// Rescan the central copy of a skill and store the report. Symlinks were
+/// already resolved when the skill was copied in, so a rescan reports what the
+/// tools actually see.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_scan_safety(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+) -> Result<SkillSafetyReport, String> {
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    let central_dir = resolve_central_repo_path(&app, &state)
+        .await
+        .map_err(format_error)?;
+    let central_path = resolve_skill_central_path(&skill.central_path, &central_dir);
+    if !central_path.exists() {
+        return Err(format!("central path not found: {:?}", central_path));
+    }
+    let report = scan_skill_dir(&central_path);
+    skill_store::set_skill_safety_report(&state, &skill.id, report.clone()).await?;
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn skills_get_safety_policy(state: State<'_, SqliteDbState>) -> Result<String, String> {
+    let raw = skill_store::get_setting(&state, "safety_scan_policy")
+        .await
+        .ok()
+        .flatten();
+    Ok(normalize_policy(raw.as_deref().unwrap_or(POLICY_WARN)))
+}
+
+#[tauri::command]
+pub async fn skills_set_safety_policy(
+    state: State<'_, SqliteDbState>,
+    policy: String,
+) -> Result<(), String> {
+    skill_store::set_setting(&state, "safety_scan_policy", &policy).await
+}
+
+// --- Lint ---
+
+/// Lint managed skills against `tools` (the tools with dedicated limits when
+/// None). Duplicate names are counted across every managed skill, so `only`
+/// narrows the reports, not the comparison.
+async fn lint_managed_skills(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    only: Option<&str>,
+    tools: Option<Vec<String>>,
+) -> Result<Vec<SkillLintReportDto>, String> {
+    let tools = tools
+        .map(|tools| normalize_tool_ids(&tools))
+        .unwrap_or_else(default_lint_tools);
+    let central_dir = resolve_central_repo_path(app, state)
+        .await
+        .map_err(format_error)?;
+    let skills = skill_store::get_managed_skills(state).await?;
+    if let Some(skill_id) = only {
+        if !skills.iter().any(|skill| skill.id == skill_id) {
+            return Err(format!("Skill not found: {}", skill_id));
+        }
+    }
+
+    let dirs: Vec<(Skill, PathBuf)> = skills
+        .into_iter()
+        .map(|skill| {
+            let dir = resolve_skill_central_path(&skill.central_path, &central_dir);
+            (skill, dir)
+        })
+        .collect();
+    let mut name_counts: HashMap<String, usize> = HashMap::new();
+    for (skill, dir) in &dirs {
+        let name = read_manifest_name(dir).unwrap_or_else(|| skill.name.clone());
+        *name_counts.entry(name).or_default() += 1;
+    }
+
+    Ok(dirs
+        .iter()
+        .filter(|(skill, _)| only.is_none_or(|id| skill.id == id))
+        .map(|(skill, dir)| lint_skill_dir(&skill.id, &skill.name, dir, &tools, &name_counts))
+        .collect())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_lint(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    tools: Option<Vec<String>>,
+) -> Result<SkillLintReportDto, String> {
+    lint_managed_skills(&app, &state, Some(&skillId), tools)
+        .await?
+        .pop()
+        .ok_or_else(|| format!("Skill not found: {}", skillId))
+}
+
+#[tauri::command]
+pub async fn skills_lint_all(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    tools: Option<Vec<String>>,
+) -> Result<Vec<SkillLintReportDto>, String> {
+    lint_managed_skills(&app, &state, None, tools).await
+}
+
 // --- Onboarding ---
 
 #[tauri::command]
@@ -3012,6 +3441,7 @@ pub async fn skills_get_repos(
             branch: r.branch,
             enabled: r.enabled,
             created_at: r.created_at,
+            url: r.url,
         })
         .collect())
 }
@@ -3022,6 +3452,7 @@ pub async fn skills_add_repo(
     owner: String,
     name: String,
     branch: Option<String>,
+    url: Option<String>,
 ) -> Result<(), String> {
     let repo = SkillRepo {
         id: format!("{}/{}", owner, name),
@@ -3030,6 +3461,7 @@ pub async fn skills_add_repo(
         branch: branch.unwrap_or_else(|| "main".to_string()),
         enabled: true,
         created_at: now_ms(),
+        url: normalize_optional_text(url),
     };
     skill_store::save_skill_repo(&state, &repo).await
 }
@@ -3066,6 +3498,7 @@ pub async fn skills_init_default_repos(state: State<'_, SqliteDbState>) -> Resul
             branch: branch.to_string(),
             enabled: true,
             created_at: now_ms(),
+            url: None,
         };
         skill_store::save_skill_repo(&state, &repo).await?;
     }
@@ -26,7 +26,7 @@ pub fn set_proxy(proxy_mode: GitProxyMode) {
 }
 
 /// Get the current proxy configuration
-fn get_proxy_mode() -> GitProxyMode {
+pub(crate) fn get_proxy_mode() -> GitProxyMode {
     PROXY_MODE
         .get()
         .and_then(|storage| storage.read().ok())
@@ -65,6 +65,11 @@ pub fn clone_or_pull(repo_url: &str, dest: &Path, branch: Option<&str>) -> Resul
     }
 }
 
+/// Whether a usable system `git` binary was found
+pub fn git_available() -> bool {
+    resolve_git_bin().is_some()
+}
+
 fn git_timeout() -> Duration {
     let secs = std::env::var("SKILLS_GIT_TIMEOUT_SECS")
         .ok()
@@ -306,3 +311,237 @@ fn clone_or_pull_via_git_cli(repo_url: &str, dest: &Path, branch: Option<&str>)
     }
     Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
 }
+
+/// A branch or tag advertised by a remote, as listed by `git ls-remote`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRef {
+    /// Full ref name, e.g. `refs/heads/main`, `refs/tags/v1.2.0` or `HEAD`
+    pub name: String,
+    /// Commit the ref points at (annotated tags are peeled to their commit)
+    pub revision: String,
+}
+
+/// List the branches and tags of a remote without fetching any content.
+///
+/// Used to detect "update available" for skills: nothing in the cache or the
+/// central repo is touched.
+pub fn list_remote_refs(repo_url: &str) -> Result<Vec<RemoteRef>> {
+    if resolve_git_bin().is_none() {
+        anyhow::bail!("GIT_NOT_FOUND");
+    }
+    let out = run_cmd_with_timeout(
+        {
+            let mut cmd = git_cmd();
+            cmd.arg("ls-remote")
+                .arg(repo_url)
+                .arg("HEAD")
+                .arg("refs/heads/*")
+                .arg("refs/tags/*");
+            cmd
+        },
+        git_fetch_timeout(),
+        format!("git ls-remote {}", repo_url),
+    )?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        anyhow::bail!("GIT_LS_REMOTE_FAILED|{}|{}", repo_url, stderr);
+    }
+    Ok(parse_ls_remote(&String::from_utf8_lossy(&out.stdout)))
+}
+
+/// Parse `git ls-remote` output. Peeled entries (`refs/tags/x^{}`) replace the
+/// tag object id with the commit it points at, so every ref maps to a commit.
+fn parse_ls_remote(output: &str) -> Vec<RemoteRef> {
+    let mut refs: Vec<RemoteRef> = Vec::new();
+    for line in output.lines() {
+        // Symbolic ref lines ("ref: refs/heads/main\tHEAD") carry no commit
+        if line.starts_with("ref:") {
+            continue;
+        }
+        let mut parts = line.split('\t');
+        let (Some(revision), Some(name)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let (revision, name) = (revision.trim(), name.trim());
+        if revision.is_empty() || name.is_empty() {
+            continue;
+        }
+        if let Some(tag) = name.strip_suffix("^{}") {
+            match refs.iter_mut().find(|existing| existing.name == tag) {
+                Some(existing) => existing.revision = revision.to_string(),
+                None => refs.push(RemoteRef {
+                    name: tag.to_string(),
+                    revision: revision.to_string(),
+                }),
+            }
+            continue;
+        }
+        if refs.iter().any(|existing| existing.name == name) {
+            continue;
+        }
+        refs.push(RemoteRef {
+            name: name.to_string(),
+            revision: revision.to_string(),
+        });
+    }
+    refs
+}
+
+/// Check out an exact commit or tag into `dest` and return the resolved commit.
+///
+/// `dest` is a bare-bones repo dedicated to pinned revisions: it is never
+/// reset to a branch head, so a pinned skill keeps installing the same content
+/// no matter how far upstream has moved.
+pub fn fetch_revision(repo_url: &str, dest: &Path, revision: &str) -> Result<String> {
+    if resolve_git_bin().is_none() {
+        anyhow::bail!("GIT_NOT_FOUND");
+    }
+    let revision = revision.trim();
+    if revision.is_empty() || revision.starts_with('-') {
+        anyhow::bail!("GIT_INVALID_REVISION|{}", revision);
+    }
+
+    if !dest.join(".git").exists() {
+        std::fs::create_dir_all(dest)
+            .with_context(|| format!("failed to create repo dir {:?}", dest))?;
+        run_git_checked(dest, &["init", "--quiet"], "GIT_INIT_FAILED")?;
+        run_git_checked(
+            dest,
+            &["remote", "add", "origin", repo_url],
+            "GIT_REMOTE_FAILED",
+        )?;
+    }
+
+    // Already checked out (a commit pin re-applied): nothing to download.
+    if let Ok(head) = run_git_checked(dest, &["rev-parse", "HEAD"], "GIT_REVPARSE_FAILED") {
+        if head.starts_with(revision) && revision.len() >= 7 {
+            return Ok(head);
+        }
+    }
+
+    // Tags and most hosts' "reachable sha" fetches work shallowly; fall back to
+    // a full fetch for servers that refuse to serve a bare commit id.
+    let shallow = run_cmd_with_timeout(
+        {
+            let mut cmd = git_cmd();
+            cmd.arg("-C")
+                .arg(dest)
+                .args(["fetch", "--depth", "1", "--no-tags", "origin", revision]);
+            cmd
+        },
+        git_fetch_timeout(),
+        format!("git fetch {} in {:?}", revision, dest),
+    )?;
+    let checkout_target = if shallow.status.success() {
+        "FETCH_HEAD".to_string()
+    } else {
+        let out = run_cmd_with_timeout(
+            {
+                let mut cmd = git_cmd();
+                cmd.arg("-C")
+                    .arg(dest)
+                    .args(["fetch", "--tags", "--force", "origin"]);
+                cmd
+            },
+            git_timeout(),
+            format!("git fetch --tags in {:?}", dest),
+        )?;
+        if !out.status.success() {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            anyhow::bail!("GIT_FETCH_FAILED|{}", stderr);
+        }
+        revision.to_string()
+    };
+
+    run_git_checked(
+        dest,
+        &["checkout", "--quiet", "--force", "--detach", &checkout_target],
+        "GIT_CHECKOUT_FAILED",
+    )
+    .map_err(|err| anyhow::anyhow!("{:#}|{}", err, revision))?;
+    run_git_checked(dest, &["rev-parse", "HEAD"], "GIT_REVPARSE_FAILED")
+}
+
+fn run_git_checked(dest: &Path, args: &[&str], error_code: &str) -> Result<String> {
+    let out = run_cmd_with_timeout(
+        {
+            let mut cmd = git_cmd();
+            cmd.arg("-C").arg(dest).args(args);
+            cmd
+        },
+        git_fetch_timeout(),
+        format!("git {} in {:?}", args.join(" "), dest),
+    )?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        anyhow::bail!("{}|{}", error_code, stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ls_remote_peels_annotated_tags() {
+        let output = "ref: refs/heads/main\tHEAD\n\
+            aaa111\tHEAD\n\
+            aaa111\trefs/heads/main\n\
+            bbb222\trefs/tags/v1.0.0\n\
+            ccc333\trefs/tags/v1.1.0\n\
+            ddd444\trefs/tags/v1.1.0^{}\n";
+
+        let refs = parse_ls_remote(output);
+
+        assert_eq!(refs.len(), 4);
+        assert_eq!(refs[0].name, "HEAD");
+        assert_eq!(refs[1].revision, "aaa111");
+        assert_eq!(
+            refs.iter()
+                .find(|item| item.name == "refs/tags/v1.1.0")
+                .map(|item| item.revision.as_str()),
+            Some("ddd444")
+        );
+    }
+
+    #[test]
+    fn fetch_revision_checks_out_pinned_tag_and_commit() {
+        if resolve_git_bin().is_none() {
+            return;
+        }
+        let temp = tempfile::tempdir().expect("temp dir");
+        let upstream = temp.path().join("upstream");
+        std::fs::create_dir_all(&upstream).expect("create upstream");
+        let git = |args: &[&str]| {
+            run_git_checked(&upstream, args, "TEST_GIT_FAILED").expect("git command")
+        };
+        git(&["init", "--quiet"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "test"]);
+        std::fs::write(upstream.join("SKILL.md"), "v1").expect("write v1");
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "v1"]);
+        git(&["tag", "v1.0.0"]);
+        let first = git(&["rev-parse", "HEAD"]);
+        std::fs::write(upstream.join("SKILL.md"), "v2").expect("write v2");
+        git(&["commit", "--quiet", "-am", "v2"]);
+
+        let url = format!("file://{}", upstream.display());
+        let dest = temp.path().join("pinned");
+
+        let tagged = fetch_revision(&url, &dest, "v1.0.0").expect("fetch tag");
+        assert_eq!(tagged, first);
+        assert_eq!(std::fs::read_to_string(dest.join("SKILL.md")).unwrap(), "v1");
+
+        let head = git(&["rev-parse", "HEAD"]);
+        let latest = fetch_revision(&url, &dest, &head).expect("fetch commit");
+        assert_eq!(latest, head);
+        assert_eq!(std::fs::read_to_string(dest.join("SKILL.md")).unwrap(), "v2");
+
+        let refs = list_remote_refs(&url).expect("ls-remote");
+        assert!(refs
+            .iter()
+            .any(|item| item.name == "refs/tags/v1.0.0" && item.revision == first));
+    }
+}
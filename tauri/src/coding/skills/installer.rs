@@ -6,16 +6,24 @@ use serde::{Deserialize, Serialize};
 use tauri::Manager;
 use uuid::Uuid;
 
+use super::archive_fetcher::{
+    download_archive, extract_archive, forge_archive_url, forge_of_host, forge_remote_refs,
+    is_archive_url, split_remote, strip_archive_extension, GitForge,
+};
 use super::cache_cleanup::get_git_cache_ttl_secs;
 use super::central_repo::{
     ensure_central_repo, resolve_central_repo_path, resolve_skill_central_path,
     to_relative_central_path,
 };
 use super::content_hash::hash_dir;
-use super::git_fetcher::{clone_or_pull, set_proxy, GitProxyMode};
+use super::git_fetcher::{
+    clone_or_pull, fetch_revision, git_available, list_remote_refs, set_proxy, GitProxyMode,
+    RemoteRef,
+};
 use super::path_executor::{
     remove_skill_target_checked, sync_copy_target_path, sync_skill_to_target, target_path_changed,
 };
+use super::safety_scan::{danger_summary, is_blocked, scan_skill_dir, LEVEL_CLEAN, POLICY_WARN};
 use super::skill_store;
 use super::sync_engine::{
     copy_dir_recursive, copy_skill_dir, ensure_source_target_not_overlapping,
@@ -24,7 +32,9 @@ use super::tool_adapters::{
     adapter_by_key, is_tool_installed_with_state_async,
     resolve_runtime_skills_path_with_state_async, runtime_adapter_by_key, RuntimeToolAdapter,
 };
-use super::types::{now_ms, GitSkillCandidate, InstallResult, Skill, UpdateResult};
+use super::types::{
+    now_ms, GitSkillCandidate, InstallResult, Skill, SkillSafetyReport, UpdateResult,
+};
 use crate::http_client;
 use crate::SqliteDbState;
 
@@ -43,6 +53,7 @@ pub async fn install_local_skill(
         .file_name()
         .map(|v| v.to_string_lossy().to_string())
         .unwrap_or_else(|| "unnamed-skill".to_string());
+    let safety_report = scan_skill_source(state, &name, source_path).await?;
 
     let central_dir = resolve_central_repo_path(app, state).await?;
     ensure_central_repo(&central_dir)?;
@@ -89,6 +100,9 @@ pub async fn install_local_skill(
         updated_at: now,
         last_sync_at: None,
         status: "ok".to_string(),
+        pinned_ref: None,
+        semver_updates: false,
+        safety_report: Some(safety_report),
         sort_index: 0,
         user_group: existing_skill
             .as_ref()
@@ -178,6 +192,7 @@ pub async fn install_local_skill_from_selection(
             .map(|v| v.to_string_lossy().to_string())
             .unwrap_or_else(|| "unnamed-skill".to_string())
     });
+    let safety_report = scan_skill_source(state, &name, &copy_src).await?;
 
     let central_dir = resolve_central_repo_path(app, state).await?;
     ensure_central_repo(&central_dir)?;
@@ -230,6 +245,9 @@ pub async fn install_local_skill_from_selection(
         updated_at: now,
         last_sync_at: None,
         status: "ok".to_string(),
+        pinned_ref: None,
+        semver_updates: false,
+        safety_report: Some(safety_report),
         sort_index: 0,
         user_group: existing_skill
             .as_ref()
@@ -275,13 +293,12 @@ pub async fn install_git_skill(
     // Initialize proxy from app settings
     init_proxy_from_settings(state).await;
 
-    let parsed = parse_github_url(repo_url);
+    let parsed = parse_git_source(repo_url);
     // Use provided branch, or fall back to parsed branch from URL, or default to "main"
     let effective_branch = branch.or(parsed.branch.as_deref());
 
-    // Clone first, then read skill name from SKILL.md
-    let ttl = get_git_cache_ttl_secs(state).await;
-    let (repo_dir, rev) = clone_to_cache(app, ttl, &parsed.clone_url, effective_branch)?;
+    // Fetch first, then read skill name from SKILL.md
+    let (repo_dir, rev) = fetch_source_to_cache(app, state, &parsed, effective_branch).await?;
 
     let copy_src = if let Some(subpath) = &parsed.subpath {
         let sub_src = repo_dir.join(subpath);
@@ -322,6 +339,7 @@ pub async fn install_git_skill(
     // Try to read name from SKILL.md, fallback to URL-derived name
     let name = read_skill_name_from_dir(&copy_src)
         .unwrap_or_else(|| derive_name_from_repo_url(&parsed.clone_url));
+    let safety_report = scan_skill_source(state, &name, &copy_src).await?;
 
     let central_dir = resolve_central_repo_path(app, state).await?;
     ensure_central_repo(&central_dir)?;
@@ -353,18 +371,12 @@ pub async fn install_git_skill(
         // Using repo root
         repo_url.to_string()
     } else {
-        // Using a subdirectory - build GitHub tree URL
+        // Using a subdirectory - build the host's tree URL
         let subpath = copy_src
             .strip_prefix(&repo_dir)
             .map(|p| p.to_string_lossy().replace('\\', "/"))
             .unwrap_or_default();
-        let branch_for_url = effective_branch.unwrap_or("main");
-        format!(
-            "{}/tree/{}/{}",
-            parsed.clone_url.trim_end_matches(".git"),
-            branch_for_url,
-            subpath
-        )
+        parsed.source_ref_for(effective_branch, &subpath)
     };
 
     let now = now_ms();
@@ -386,6 +398,9 @@ pub async fn install_git_skill(
         updated_at: now,
         last_sync_at: None,
         status: "ok".to_string(),
+        pinned_ref: None,
+        semver_updates: false,
+        safety_report: Some(safety_report),
         sort_index: 0,
         user_group: existing_skill
             .as_ref()
@@ -420,18 +435,20 @@ pub async fn install_git_skill(
     })
 }
 
-/// List skills in a Git repository
-pub fn list_git_skills(
+/// List skills in a Git repository or archive
+pub async fn list_git_skills(
     app: &tauri::AppHandle,
-    cache_ttl_secs: i64,
+    state: &SqliteDbState,
     repo_url: &str,
     branch: Option<&str>,
 ) -> Result<Vec<GitSkillCandidate>> {
-    let parsed = parse_github_url(repo_url);
+    // Initialize proxy from app settings
+    init_proxy_from_settings(state).await;
+
+    let parsed = parse_git_source(repo_url);
     // Use provided branch, or fall back to parsed branch from URL
     let effective_branch = branch.or(parsed.branch.as_deref());
-    let (repo_dir, _rev) =
-        clone_to_cache(app, cache_ttl_secs, &parsed.clone_url, effective_branch)?;
+    let (repo_dir, _rev) = fetch_source_to_cache(app, state, &parsed, effective_branch).await?;
 
     let mut out: Vec<GitSkillCandidate> = Vec::new();
 
@@ -487,13 +504,13 @@ pub async fn install_git_skill_from_selection(
     // Initialize proxy from app settings
     init_proxy_from_settings(state).await;
 
-    let parsed = parse_github_url(repo_url);
+    let parsed = parse_git_source(repo_url);
     // Use provided branch, or fall back to parsed branch from URL
     let effective_branch = branch.or(parsed.branch.as_deref());
 
-    // Clone first, then read skill name from SKILL.md
-    let ttl = get_git_cache_ttl_secs(state).await;
-    let (repo_dir, revision) = clone_to_cache(app, ttl, &parsed.clone_url, effective_branch)?;
+    // Fetch first, then read skill name from SKILL.md
+    let (repo_dir, revision) =
+        fetch_source_to_cache(app, state, &parsed, effective_branch).await?;
 
     let copy_src = if subpath == "." {
         repo_dir.clone()
@@ -511,6 +528,7 @@ pub async fn install_git_skill_from_selection(
             .map(|s| s.to_string_lossy().to_string())
             .unwrap_or_else(|| derive_name_from_repo_url(&parsed.clone_url))
     });
+    let safety_report = scan_skill_source(state, &display_name, &copy_src).await?;
 
     let central_dir = resolve_central_repo_path(app, state).await?;
     ensure_central_repo(&central_dir)?;
@@ -538,17 +556,11 @@ pub async fn install_git_skill_from_selection(
         .with_context(|| format!("copy {:?} -> {:?}", copy_src, central_path))?;
 
     // Build full source_ref URL including subpath for later updates
-    let branch_for_url = effective_branch.unwrap_or("main");
     let full_source_ref = if subpath == "." {
         repo_url.to_string()
     } else {
-        // Build GitHub tree URL: https://github.com/owner/repo/tree/branch/subpath
-        format!(
-            "{}/tree/{}/{}",
-            parsed.clone_url.trim_end_matches(".git"),
-            branch_for_url,
-            subpath
-        )
+        // e.g. https://github.com/owner/repo/tree/branch/subpath
+        parsed.source_ref_for(effective_branch, subpath)
     };
 
     let now = now_ms();
@@ -569,6 +581,9 @@ pub async fn install_git_skill_from_selection(
         updated_at: now,
         last_sync_at: None,
         status: "ok".to_string(),
+        pinned_ref: None,
+        semver_updates: false,
+        safety_report: Some(safety_report),
         sort_index: 0,
         user_group: existing_skill
             .as_ref()
@@ -602,11 +617,38 @@ pub async fn install_git_skill_from_selection(
     })
 }
 
-/// Update a managed skill from its source
+/// Update a managed skill from its source.
+///
+/// Git skills install `revision` when given (an update accepted after review),
+/// else their pinned ref, else the head of the tracked branch.
 pub async fn update_managed_skill_from_source(
     app: &tauri::AppHandle,
     state: &SqliteDbState,
     skill_id: &str,
+    revision: Option<&str>,
+) -> Result<UpdateResult> {
+    update_managed_skill(app, state, skill_id, revision, None).await
+}
+
+/// Install a git skill at the revision a lockfile recorded (else its pinned
+/// ref), refusing to replace the central copy when the fetched revision or
+/// content hash differs from the locked one.
+pub async fn update_managed_skill_to_lock(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    skill_id: &str,
+    revision: Option<&str>,
+    content_hash: Option<&str>,
+) -> Result<UpdateResult> {
+    update_managed_skill(app, state, skill_id, revision, content_hash).await
+}
+
+async fn update_managed_skill(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    skill_id: &str,
+    revision: Option<&str>,
+    expected_hash: Option<&str>,
 ) -> Result<UpdateResult> {
     // Initialize proxy from app settings (for git source types)
     init_proxy_from_settings(state).await;
@@ -636,27 +678,17 @@ pub async fn update_managed_skill_from_source(
     }
 
     let mut new_revision: Option<String> = None;
+    let safety_report: SkillSafetyReport;
 
     if record.source_type == "git" {
         let repo_url = record
             .source_ref
             .as_deref()
             .ok_or_else(|| anyhow::anyhow!("missing source_ref for git skill"))?;
-        let parsed = parse_github_url(repo_url);
-
-        let ttl = get_git_cache_ttl_secs(state).await;
-        let (repo_dir, rev) =
-            clone_to_cache(app, ttl, &parsed.clone_url, parsed.branch.as_deref())?;
+        let reference = revision.or(record.pinned_ref.as_deref());
+        let (copy_src, rev) = fetch_git_skill_source(app, state, repo_url, reference).await?;
         new_revision = Some(rev);
-
-        let copy_src = if let Some(subpath) = &parsed.subpath {
-            repo_dir.join(subpath)
-        } else {
-            repo_dir.clone()
-        };
-        if !copy_src.exists() {
-            anyhow::bail!("path not found in repo: {:?}", copy_src);
-        }
+        safety_report = scan_skill_source(state, &record.name, &copy_src).await?;
 
         copy_skill_dir(&copy_src, &staging_dir)
             .with_context(|| format!("copy {:?} -> {:?}", copy_src, staging_dir))?;
@@ -669,6 +701,7 @@ pub async fn update_managed_skill_from_source(
         if !source_path.exists() {
             anyhow::bail!("source path not found: {:?}", source_path);
         }
+        safety_report = scan_skill_source(state, &record.name, &source_path).await?;
         copy_skill_dir(&source_path, &staging_dir)
             .with_context(|| format!("copy {:?} -> {:?}", source_path, staging_dir))?;
     } else {
@@ -682,6 +715,16 @@ pub async fn update_managed_skill_from_source(
     // refreshed (updated_at / git revision / status="ok") so this path matches the
     // other refresh entries: "success writes the timestamp; failure writes nothing".
     let staged_hash = compute_content_hash(&staging_dir);
+    if let Some(expected) = expected_hash {
+        if staged_hash.as_deref() != Some(expected) {
+            let _ = std::fs::remove_dir_all(&staging_dir);
+            anyhow::bail!(
+                "Fetched content hash {} differs from the lockfile ({})",
+                staged_hash.as_deref().unwrap_or("<none>"),
+                expected
+            );
+        }
+    }
     let current_hash = compute_content_hash(&central_path);
     if staged_hash.is_some() && staged_hash == current_hash {
         let _ = std::fs::remove_dir_all(&staging_dir);
@@ -708,6 +751,9 @@ pub async fn update_managed_skill_from_source(
             updated_at: now,
             last_sync_at: record.last_sync_at,
             status: "ok".to_string(),
+            pinned_ref: record.pinned_ref.clone(),
+            semver_updates: record.semver_updates,
+            safety_report: Some(safety_report),
             sort_index: record.sort_index,
             user_group: record.user_group.clone(),
             group_id: record.group_id.clone(),
@@ -763,6 +809,9 @@ pub async fn update_managed_skill_from_source(
         updated_at: now,
         last_sync_at: record.last_sync_at,
         status: "ok".to_string(),
+        pinned_ref: record.pinned_ref.clone(),
+        semver_updates: record.semver_updates,
+        safety_report: Some(safety_report),
         sort_index: record.sort_index,
         user_group: record.user_group.clone(),
         group_id: record.group_id.clone(),
@@ -876,6 +925,34 @@ pub async fn update_managed_skill_from_source(
     })
 }
 
+/// Scan a skill's source before it is copied into the central repo. Under the
+/// "block" policy a danger finding aborts with `SKILL_UNSAFE|name|summary`;
+/// otherwise the report is returned to be stored on the skill record.
+async fn scan_skill_source(
+    state: &SqliteDbState,
+    name: &str,
+    source: &Path,
+) -> Result<SkillSafetyReport> {
+    let report = scan_skill_dir(source);
+    let policy = skill_store::get_setting(state, "safety_scan_policy")
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| POLICY_WARN.to_string());
+    if is_blocked(&report, &policy) {
+        anyhow::bail!("SKILL_UNSAFE|{}|{}", name, danger_summary(&report));
+    }
+    if report.level != LEVEL_CLEAN {
+        log::warn!(
+            "[safety] '{}' has {} finding(s), level {}",
+            name,
+            report.findings.len(),
+            report.level
+        );
+    }
+    Ok(report)
+}
+
 /// Copy the current central content to `{app_data}/skills-backup/{id}-{ts}`
 /// before an update replaces it, so direct user edits (or edits made through a
 /// symlinked tool directory) can be recovered. Keeps the newest 5 copies per
@@ -935,6 +1012,55 @@ fn backup_central_before_replace(
     Ok(())
 }
 
+/// Fetch the content of a git skill at `reference` (a commit or tag), or at
+/// the head of its tracked branch when None, without touching the central repo.
+/// Returns the skill directory inside the git cache and the resolved commit.
+pub(crate) async fn fetch_git_skill_source(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    source_ref: &str,
+    reference: Option<&str>,
+) -> Result<(PathBuf, String)> {
+    let parsed = parse_git_source(source_ref);
+    let (repo_dir, rev) = match reference {
+        Some(reference) => fetch_pinned_source_to_cache(app, state, &parsed, reference).await?,
+        None => fetch_source_to_cache(app, state, &parsed, parsed.branch.as_deref()).await?,
+    };
+
+    let copy_src = if let Some(subpath) = &parsed.subpath {
+        repo_dir.join(subpath)
+    } else {
+        repo_dir.clone()
+    };
+    if !copy_src.exists() {
+        anyhow::bail!("path not found in repo: {:?}", copy_src);
+    }
+    Ok((copy_src, rev))
+}
+
+/// Clone URL and tracked branch (None = remote HEAD) of a git skill source
+pub(crate) fn git_remote_of_source(source_ref: &str) -> (String, Option<String>) {
+    let parsed = parse_git_source(source_ref);
+    (parsed.clone_url, parsed.branch)
+}
+
+/// Branches and tags of a git skill's remote: `git ls-remote` when `git` is
+/// available, else the forge's REST API. Without either this fails with
+/// `GIT_NOT_FOUND`.
+pub(crate) async fn list_source_refs(source_ref: &str) -> Result<Vec<RemoteRef>> {
+    let parsed = parse_git_source(source_ref);
+    if git_available() {
+        return run_blocking(move || list_remote_refs(&parsed.clone_url)).await;
+    }
+    forge_remote_refs(&parsed.clone_url, parsed.forge).await
+}
+
+/// Whether a skill source is a tarball/zip download, which has no refs to
+/// list or pin: updates simply download it again.
+pub(crate) fn is_archive_source(source_ref: &str) -> bool {
+    parse_git_source(source_ref).archive
+}
+
 // --- Git URL parsing ---
 
 #[derive(Clone, Debug)]
@@ -942,9 +1068,73 @@ struct ParsedGitSource {
     clone_url: String,
     branch: Option<String>,
     subpath: Option<String>,
+    /// Hosting forge, which decides the tree URL form and archive layout
+    forge: GitForge,
+    /// `clone_url` is a tarball/zip download rather than a git remote
+    archive: bool,
+}
+
+impl ParsedGitSource {
+    /// Source ref of `subpath` on `branch`, in the host's own tree URL form
+    /// where it has one, else as `<url>#<ref>:<subpath>`. Parses back to self.
+    fn source_ref_for(&self, branch: Option<&str>, subpath: &str) -> String {
+        let web_repo = self.clone_url.trim_end_matches(".git");
+        let is_web = !self.archive && self.clone_url.starts_with("http");
+        match (self.forge, branch) {
+            (GitForge::GitHub, _) if is_web => {
+                format!("{}/tree/{}/{}", web_repo, branch.unwrap_or("main"), subpath)
+            }
+            (GitForge::GitLab, Some(branch)) if is_web => {
+                format!("{}/-/tree/{}/{}", web_repo, branch, subpath)
+            }
+            (GitForge::Gitea, Some(branch)) if is_web => {
+                format!("{}/src/branch/{}/{}", web_repo, branch, subpath)
+            }
+            _ => format!("{}#{}:{}", self.clone_url, branch.unwrap_or(""), subpath),
+        }
+    }
 }
 
-fn parse_github_url(input: &str) -> ParsedGitSource {
+/// Parse a skill source: a GitHub, GitLab or Gitea URL (optionally a tree URL
+/// of a folder), any git remote (https, ssh, scp), or a tarball/zip URL.
+///
+/// A trailing `#<ref>`, `#<ref>:<subpath>` or `#:<subpath>` selects a branch,
+/// tag or commit and/or a folder; on archives a bare `#<subpath>` also works.
+fn parse_git_source(input: &str) -> ParsedGitSource {
+    let trimmed = input.trim();
+    let (location, selector) = match trimmed.rsplit_once('#') {
+        Some((location, selector)) => (location, Some(selector)),
+        None => (trimmed, None),
+    };
+    let mut parsed = parse_git_location(location);
+
+    if let Some(selector) = selector {
+        let (reference, subpath) = match selector.split_once(':') {
+            Some((reference, subpath)) => (reference, Some(subpath)),
+            None if parsed.archive => ("", Some(selector)),
+            None => (selector, None),
+        };
+        let reference = reference.trim();
+        if !reference.is_empty() && !parsed.archive {
+            parsed.branch = Some(reference.to_string());
+        }
+        if let Some(subpath) = subpath.map(normalize_subpath).filter(|p| !p.is_empty()) {
+            parsed.subpath = Some(subpath);
+        }
+    }
+    parsed
+}
+
+/// Drop empty, `.` and `..` segments so a subpath never leaves the checkout
+fn normalize_subpath(subpath: &str) -> String {
+    subpath
+        .split(['/', '\\'])
+        .filter(|segment| !segment.is_empty() && *segment != "." && *segment != "..")
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn parse_git_location(input: &str) -> ParsedGitSource {
     let trimmed = input.trim().trim_end_matches('/');
 
     // Convenience: allow GitHub shorthand inputs
@@ -961,23 +1151,36 @@ fn parse_github_url(input: &str) -> ParsedGitSource {
     };
 
     let trimmed = normalized.trim_end_matches('/');
-    let gh_prefix = "https://github.com/";
-    if !trimmed.starts_with(gh_prefix) {
+    let plain = |clone_url: &str, forge: GitForge| ParsedGitSource {
+        clone_url: clone_url.to_string(),
+        branch: None,
+        subpath: None,
+        forge,
+        archive: false,
+    };
+
+    if is_archive_url(trimmed) {
         return ParsedGitSource {
-            clone_url: trimmed.to_string(),
-            branch: None,
-            subpath: None,
+            archive: true,
+            ..plain(trimmed, GitForge::Unknown)
         };
     }
 
+    let gh_prefix = "https://github.com/";
+    if !trimmed.starts_with(gh_prefix) {
+        return parse_forge_tree_url(trimmed).unwrap_or_else(|| {
+            let forge = split_remote(trimmed)
+                .and_then(|(base, _)| url::Url::parse(&base).ok())
+                .and_then(|base| base.host_str().map(forge_of_host))
+                .unwrap_or(GitForge::Unknown);
+            plain(trimmed, forge)
+        });
+    }
+
     let rest = &trimmed[gh_prefix.len()..];
     let parts: Vec<&str> = rest.split('/').collect();
     if parts.len() < 2 {
-        return ParsedGitSource {
-            clone_url: trimmed.to_string(),
-            branch: None,
-            subpath: None,
-        };
+        return plain(trimmed, GitForge::GitHub);
     }
 
     let owner = parts[0];
@@ -995,17 +1198,49 @@ fn parse_github_url(input: &str) -> ParsedGitSource {
             None
         };
         return ParsedGitSource {
-            clone_url,
             branch,
             subpath,
+            ..plain(&clone_url, GitForge::GitHub)
         };
     }
 
-    ParsedGitSource {
-        clone_url,
-        branch: None,
-        subpath: None,
+    plain(&clone_url, GitForge::GitHub)
+}
+
+/// GitLab (`<repo>/-/tree/<ref>/<path>`) and Gitea/Forgejo
+/// (`<owner>/<repo>/src/branch/<ref>/<path>`) folder URLs on any host.
+fn parse_forge_tree_url(input: &str) -> Option<ParsedGitSource> {
+    let url = url::Url::parse(input).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let (base, _) = split_remote(input)?;
+    let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+
+    let gitlab = segments
+        .windows(2)
+        .position(|pair| pair[0] == "-" && matches!(pair[1], "tree" | "blob"))
+        .map(|index| (index, index + 2, GitForge::GitLab));
+    let gitea = segments
+        .windows(2)
+        .position(|pair| pair[0] == "src" && matches!(pair[1], "branch" | "tag" | "commit"))
+        .filter(|index| *index == 2)
+        .map(|index| (index, index + 2, GitForge::Gitea));
+    let (repo_end, ref_index, forge) = gitlab.or(gitea)?;
+    if repo_end < 2 || ref_index >= segments.len() {
+        return None;
     }
+
+    let repo_path = segments[..repo_end].join("/");
+    let repo_path = repo_path.strip_suffix(".git").unwrap_or(&repo_path);
+    let subpath = normalize_subpath(&segments[ref_index + 1..].join("/"));
+    Some(ParsedGitSource {
+        clone_url: format!("{}/{}.git", base, repo_path),
+        branch: Some(segments[ref_index].to_string()),
+        subpath: (!subpath.is_empty()).then_some(subpath),
+        forge,
+        archive: false,
+    })
 }
 
 fn looks_like_github_shorthand(input: &str) -> bool {
@@ -1052,14 +1287,17 @@ fn looks_like_github_shorthand(input: &str) -> bool {
 }
 
 fn derive_name_from_repo_url(repo_url: &str) -> String {
-    let mut name = repo_url
-        .split('/')
+    let path = repo_url.split(['?', '#']).next().unwrap_or(repo_url);
+    let mut name = path
+        .trim_end_matches('/')
+        .split(['/', ':'])
         .next_back()
         .unwrap_or("skill")
         .to_string();
     if let Some(stripped) = name.strip_suffix(".git") {
         name = stripped.to_string();
     }
+    name = strip_archive_extension(&name).to_string();
     if name.is_empty() {
         "skill".to_string()
     } else {
@@ -1187,14 +1425,7 @@ struct RepoCacheMeta {
 
 static GIT_CACHE_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
 
-fn clone_to_cache(
-    app: &tauri::AppHandle,
-    cache_ttl_secs: i64,
-    clone_url: &str,
-    branch: Option<&str>,
-) -> Result<(PathBuf, String)> {
-    use tauri::Manager;
-
+fn git_cache_root(app: &tauri::AppHandle) -> Result<PathBuf> {
     let cache_dir = app
         .path()
         .app_cache_dir()
@@ -1202,6 +1433,16 @@ fn clone_to_cache(
     let cache_root = cache_dir.join("skills-git-cache");
     std::fs::create_dir_all(&cache_root)
         .with_context(|| format!("failed to create cache dir {:?}", cache_root))?;
+    Ok(cache_root)
+}
+
+fn clone_to_cache(
+    app: &tauri::AppHandle,
+    cache_ttl_secs: i64,
+    clone_url: &str,
+    branch: Option<&str>,
+) -> Result<(PathBuf, String)> {
+    let cache_root = git_cache_root(app)?;
 
     let repo_dir = cache_root.join(repo_cache_key(clone_url, branch));
     let meta_path = repo_dir.join(".skills-cache.json");
@@ -1211,15 +1452,8 @@ fn clone_to_cache(
 
     // Check cache freshness
     if repo_dir.join(".git").exists() {
-        if let Ok(meta) = std::fs::read_to_string(&meta_path) {
-            if let Ok(meta) = serde_json::from_str::<RepoCacheMeta>(&meta) {
-                if let Some(head) = meta.head {
-                    let ttl_ms = cache_ttl_secs.saturating_mul(1000);
-                    if ttl_ms > 0 && now_ms().saturating_sub(meta.last_fetched_ms) < ttl_ms {
-                        return Ok((repo_dir, head));
-                    }
-                }
-            }
+        if let Some(head) = fresh_cache_head(&meta_path, cache_ttl_secs) {
+            return Ok((repo_dir, head));
         }
     }
 
@@ -1246,6 +1480,147 @@ fn clone_to_cache(
     Ok((repo_dir, rev))
 }
 
+/// Head recorded in a cache meta file, if it was fetched within the TTL
+fn fresh_cache_head(meta_path: &Path, cache_ttl_secs: i64) -> Option<String> {
+    let meta = std::fs::read_to_string(meta_path).ok()?;
+    let meta = serde_json::from_str::<RepoCacheMeta>(&meta).ok()?;
+    let ttl_ms = cache_ttl_secs.saturating_mul(1000);
+    if ttl_ms > 0 && now_ms().saturating_sub(meta.last_fetched_ms) < ttl_ms {
+        meta.head
+    } else {
+        None
+    }
+}
+
+/// Fetch the tracked `branch` of a source into the cache: a clone when `git`
+/// is available, else the forge's archive download. Tarball/zip sources are
+/// always downloaded.
+async fn fetch_source_to_cache(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    parsed: &ParsedGitSource,
+    branch: Option<&str>,
+) -> Result<(PathBuf, String)> {
+    let ttl = get_git_cache_ttl_secs(state).await;
+    if parsed.archive {
+        return download_archive_to_cache(app, ttl, &parsed.clone_url).await;
+    }
+    if git_available() {
+        let (app, clone_url) = (app.clone(), parsed.clone_url.clone());
+        let branch = branch.map(str::to_string);
+        return run_blocking(move || clone_to_cache(&app, ttl, &clone_url, branch.as_deref()))
+            .await;
+    }
+    log::info!(
+        "[installer] git not found, downloading archive of {}",
+        parsed.clone_url
+    );
+    let archive_url = forge_archive_url(&parsed.clone_url, parsed.forge, branch).await?;
+    download_archive_to_cache(app, ttl, &archive_url).await
+}
+
+/// Pinned counterpart of [`fetch_source_to_cache`] for a commit or tag.
+async fn fetch_pinned_source_to_cache(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    parsed: &ParsedGitSource,
+    reference: &str,
+) -> Result<(PathBuf, String)> {
+    if parsed.archive {
+        anyhow::bail!("Archive sources cannot be pinned to a revision");
+    }
+    if git_available() {
+        let (app, clone_url) = (app.clone(), parsed.clone_url.clone());
+        let reference = reference.to_string();
+        return run_blocking(move || fetch_revision_to_cache(&app, &clone_url, &reference)).await;
+    }
+    let ttl = get_git_cache_ttl_secs(state).await;
+    let archive_url = forge_archive_url(&parsed.clone_url, parsed.forge, Some(reference)).await?;
+    download_archive_to_cache(app, ttl, &archive_url).await
+}
+
+/// Download and unpack an archive into its own cache dir. The recorded head
+/// is the commit embedded by `git archive`, or a digest of the download.
+async fn download_archive_to_cache(
+    app: &tauri::AppHandle,
+    cache_ttl_secs: i64,
+    archive_url: &str,
+) -> Result<(PathBuf, String)> {
+    let cache_root = git_cache_root(app)?;
+    let repo_dir = cache_root.join(repo_cache_key(archive_url, Some("@archive")));
+    let meta_path = repo_dir.join(".skills-cache.json");
+    if repo_dir.is_dir() {
+        if let Some(head) = fresh_cache_head(&meta_path, cache_ttl_secs) {
+            return Ok((repo_dir, head));
+        }
+    }
+
+    let bytes = download_archive(archive_url).await?;
+
+    run_blocking(move || {
+        let lock = GIT_CACHE_LOCK.get_or_init(|| Mutex::new(()));
+        let _guard = lock.lock().unwrap_or_else(|err| err.into_inner());
+        let rev = extract_archive(&bytes, &repo_dir)?;
+        let _ = std::fs::write(
+            &meta_path,
+            serde_json::to_string(&RepoCacheMeta {
+                last_fetched_ms: now_ms(),
+                head: Some(rev.clone()),
+            })
+            .unwrap_or_else(|_| "{}".to_string()),
+        );
+        Ok((repo_dir, rev))
+    })
+    .await
+}
+
+/// Run git and archive work, which blocks on processes, disk and the std
+/// `GIT_CACHE_LOCK`, on the blocking pool instead of the async runtime.
+async fn run_blocking<T, F>(work: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| anyhow::anyhow!("spawn_blocking failed: {}", e))?
+}
+
+/// Check out a pinned commit or tag in a per-repo cache dir kept apart from
+/// the branch-tracking clone, so a pinned fetch never moves the shared head.
+fn fetch_revision_to_cache(
+    app: &tauri::AppHandle,
+    clone_url: &str,
+    reference: &str,
+) -> Result<(PathBuf, String)> {
+    let cache_root = git_cache_root(app)?;
+    let repo_dir = cache_root.join(repo_cache_key(clone_url, Some("@pinned")));
+
+    let lock = GIT_CACHE_LOCK.get_or_init(|| Mutex::new(()));
+    let _guard = lock.lock().unwrap_or_else(|err| err.into_inner());
+
+    let rev = match fetch_revision(clone_url, &repo_dir, reference) {
+        Ok(rev) => rev,
+        Err(err) => {
+            if repo_dir.exists() {
+                let _ = std::fs::remove_dir_all(&repo_dir);
+            }
+            fetch_revision(clone_url, &repo_dir, reference).with_context(|| format!("{:#}", err))?
+        }
+    };
+
+    let _ = std::fs::write(
+        repo_dir.join(".skills-cache.json"),
+        serde_json::to_string(&RepoCacheMeta {
+            last_fetched_ms: now_ms(),
+            head: Some(rev.clone()),
+        })
+        .unwrap_or_else(|_| "{}".to_string()),
+    );
+
+    Ok((repo_dir, rev))
+}
+
 fn repo_cache_key(clone_url: &str, branch: Option<&str>) -> String {
     use sha2::Digest;
     let mut hasher = sha2::Sha256::new();
@@ -1258,7 +1633,7 @@ fn repo_cache_key(clone_url: &str, branch: Option<&str>) -> String {
 }
 
 /// Initialize proxy settings from app settings database
-async fn init_proxy_from_settings(state: &SqliteDbState) {
+pub(crate) async fn init_proxy_from_settings(state: &SqliteDbState) {
     let proxy_result = http_client::get_proxy_from_settings(state).await.ok();
     let proxy_mode = match proxy_result {
         Some((http_client::ProxyMode::Direct, _)) => GitProxyMode::Direct,
@@ -1267,3 +1642,52 @@ async fn init_proxy_from_settings(state: &SqliteDbState) {
     };
     set_proxy(proxy_mode);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_git_source_reads_forge_tree_urls_and_selectors() {
+        let gitlab = parse_git_source("https://gitlab.com/group/sub/repo/-/tree/main/skills/demo");
+        assert_eq!(gitlab.clone_url, "https://gitlab.com/group/sub/repo.git");
+        assert_eq!(gitlab.branch.as_deref(), Some("main"));
+        assert_eq!(gitlab.subpath.as_deref(), Some("skills/demo"));
+        assert_eq!(gitlab.forge, GitForge::GitLab);
+
+        let gitea = parse_git_source("https://git.example.com/owner/repo/src/branch/dev/demo");
+        assert_eq!(gitea.clone_url, "https://git.example.com/owner/repo.git");
+        assert_eq!(gitea.forge, GitForge::Gitea);
+        assert_eq!(
+            parse_git_source(&gitea.source_ref_for(Some("dev"), "demo")).subpath.as_deref(),
+            Some("demo")
+        );
+
+        let ssh = parse_git_source("git@codeberg.org:owner/repo.git#v1.2.0:skills/../demo");
+        assert_eq!(ssh.clone_url, "git@codeberg.org:owner/repo.git");
+        assert_eq!(ssh.branch.as_deref(), Some("v1.2.0"));
+        assert_eq!(ssh.subpath.as_deref(), Some("skills/demo"));
+        assert_eq!(ssh.forge, GitForge::Gitea);
+        let round_trip = parse_git_source(&ssh.source_ref_for(Some("v1.2.0"), "skills/demo"));
+        assert_eq!(round_trip.clone_url, ssh.clone_url);
+        assert_eq!(round_trip.subpath, ssh.subpath);
+
+        let archive = parse_git_source("https://example.com/dl/skills.tar.gz#demo");
+        assert!(archive.archive);
+        assert_eq!(archive.branch, None);
+        assert_eq!(archive.subpath.as_deref(), Some("demo"));
+
+        let github = parse_git_source("owner/repo");
+        assert_eq!(github.clone_url, "https://github.com/owner/repo.git");
+        assert_eq!(github.forge, GitForge::GitHub);
+    }
+
+    #[test]
+    fn derive_name_strips_archive_and_git_suffixes() {
+        assert_eq!(
+            derive_name_from_repo_url("https://example.com/dl/my-skill.tar.gz?token=1"),
+            "my-skill"
+        );
+        assert_eq!(derive_name_from_repo_url("git@host.example:repo.git"), "repo");
+    }
+}
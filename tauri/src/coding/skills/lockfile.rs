@@ -0,0 +1,336 @@
+//! Version pins and the skills lockfile.
+//!
+//! Git skills can be pinned to a commit or tag. Update checks compare the pin
+//! (or the tracked branch for unpinned skills) with `git ls-remote` output, so
+//! "update available" is known before anything is downloaded. A skill pinned
+//! to a semver tag with `semver_updates` may move to newer tags of the same
+//! major version; every other pin only moves when an update is accepted.
+
+use std::collections::HashSet;
+
+use super::git_fetcher::RemoteRef;
+use super::types::{Skill, SkillLockEntryJson, SkillLockfileJson, SkillUpdateCheckDto};
+
+pub const LOCKFILE_VERSION: u32 = 1;
+
+/// Build a lockfile from the installed git skills.
+///
+/// Unpinned skills are locked to the commit they were installed from.
+pub fn build_lockfile(skills: &[Skill]) -> SkillLockfileJson {
+    let mut entries: Vec<SkillLockEntryJson> = skills
+        .iter()
+        .filter(|skill| skill.source_type == "git")
+        .filter_map(|skill| {
+            let source = skill.source_ref.clone()?;
+            let pinned_ref = skill
+                .pinned_ref
+                .clone()
+                .or_else(|| skill.source_revision.clone())?;
+            Some(SkillLockEntryJson {
+                name: skill.name.clone(),
+                source,
+                pinned_ref,
+                revision: skill.source_revision.clone(),
+                semver_updates: skill.semver_updates,
+                content_hash: skill.content_hash.clone(),
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    SkillLockfileJson {
+        lockfile_version: LOCKFILE_VERSION,
+        skills: entries,
+    }
+}
+
+pub fn parse_lockfile(raw: &str) -> Result<SkillLockfileJson, String> {
+    let lockfile: SkillLockfileJson =
+        serde_json::from_str(raw).map_err(|e| format!("Invalid skills lockfile: {}", e))?;
+    if lockfile.lockfile_version != LOCKFILE_VERSION {
+        return Err(format!(
+            "Unsupported skills lockfile version: {}",
+            lockfile.lockfile_version
+        ));
+    }
+    let mut names = HashSet::new();
+    for entry in &lockfile.skills {
+        if entry.pinned_ref.trim().is_empty() {
+            return Err(format!("Lockfile entry '{}' has an empty ref", entry.name));
+        }
+        if !names.insert(entry.name.as_str()) {
+            return Err(format!("Lockfile lists '{}' more than once", entry.name));
+        }
+    }
+    Ok(lockfile)
+}
+
+/// Parse `v1.2.3` / `1.2.3` / `1.2` tags. Pre-release and build suffixes are
+/// rejected so they are never picked as an automatic update.
+pub fn parse_semver_tag(tag: &str) -> Option<(u64, u64, u64)> {
+    let tag = tag.strip_prefix("refs/tags/").unwrap_or(tag);
+    let version = tag.strip_prefix('v').unwrap_or(tag);
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = match parts.next() {
+        Some(patch) => patch.parse().ok()?,
+        None => 0,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// Whether `candidate` is a semver-compatible upgrade of `current` (caret
+/// semantics: same major, or same minor while the major is 0).
+fn is_compatible_upgrade(current: (u64, u64, u64), candidate: (u64, u64, u64)) -> bool {
+    if candidate <= current || candidate.0 != current.0 {
+        return false;
+    }
+    current.0 != 0 || candidate.1 == current.1
+}
+
+/// Newest tag above `pinned`; restricted to compatible versions when
+/// `compatible_only` is set.
+pub fn newest_tag<'a>(
+    pinned: &str,
+    refs: &'a [RemoteRef],
+    compatible_only: bool,
+) -> Option<(&'a str, &'a RemoteRef)> {
+    let current = parse_semver_tag(pinned)?;
+    refs.iter()
+        .filter_map(|remote| {
+            let tag = remote.name.strip_prefix("refs/tags/")?;
+            let version = parse_semver_tag(tag)?;
+            let eligible = if compatible_only {
+                is_compatible_upgrade(current, version)
+            } else {
+                version > current
+            };
+            eligible.then_some((version, tag, remote))
+        })
+        .max_by_key(|(version, _, _)| *version)
+        .map(|(_, tag, remote)| (tag, remote))
+}
+
+/// Tag a semver-tracking skill should move to, if a compatible one is newer.
+pub fn semver_update_target(skill: &Skill, refs: &[RemoteRef]) -> Option<String> {
+    if !skill.semver_updates {
+        return None;
+    }
+    let pinned = skill.pinned_ref.as_deref()?;
+    newest_tag(pinned, refs, true).map(|(tag, _)| tag.to_string())
+}
+
+fn branch_head<'a>(refs: &'a [RemoteRef], branch: Option<&str>) -> Option<&'a RemoteRef> {
+    let wanted = branch
+        .map(|branch| format!("refs/heads/{}", branch))
+        .unwrap_or_else(|| "HEAD".to_string());
+    refs.iter().find(|remote| remote.name == wanted)
+}
+
+/// Compare a git skill with its remote refs.
+///
+/// Tag pins are offered the newest tag (compatible ones only with
+/// `semver_updates`); commit pins and unpinned skills are offered the head of
+/// the tracked branch.
+pub fn check_skill_update(
+    skill: &Skill,
+    refs: &[RemoteRef],
+    branch: Option<&str>,
+) -> SkillUpdateCheckDto {
+    let mut result = failed_update_check(skill, None);
+
+    if let Some(pinned) = skill
+        .pinned_ref
+        .as_deref()
+        .filter(|pinned| parse_semver_tag(pinned).is_some())
+    {
+        match newest_tag(pinned, refs, skill.semver_updates) {
+            Some((tag, remote)) => {
+                result.latest_ref = Some(tag.to_string());
+                result.latest_revision = Some(remote.revision.clone());
+                result.update_available = true;
+                result.update_ref = Some(tag.to_string());
+            }
+            None => {
+                result.latest_ref = Some(pinned.to_string());
+                result.latest_revision = skill.source_revision.clone();
+            }
+        }
+        return result;
+    }
+
+    let Some(head) = branch_head(refs, branch) else {
+        result.error = Some(format!(
+            "Branch '{}' not found on remote",
+            branch.unwrap_or("HEAD")
+        ));
+        return result;
+    };
+    result.latest_ref = Some(branch.unwrap_or("HEAD").to_string());
+    result.latest_revision = Some(head.revision.clone());
+    result.update_available = skill.source_revision.as_deref() != Some(head.revision.as_str());
+    if result.update_available {
+        result.update_ref = Some(head.revision.clone());
+    }
+    result
+}
+
+/// Update check result carrying no remote information, e.g. when the remote
+/// could not be reached.
+pub fn failed_update_check(skill: &Skill, error: Option<String>) -> SkillUpdateCheckDto {
+    SkillUpdateCheckDto {
+        skill_id: skill.id.clone(),
+        name: skill.name.clone(),
+        source_revision: skill.source_revision.clone(),
+        pinned_ref: skill.pinned_ref.clone(),
+        latest_ref: None,
+        latest_revision: None,
+        update_available: false,
+        update_ref: None,
+        error,
+    }
+}
+
+/// Whether `revision` (a full commit id) is what `reference` names, when the
+/// reference is itself a (possibly abbreviated) commit id.
+pub fn revision_matches(revision: &str, reference: &str) -> bool {
+    reference.len() >= 7
+        && reference.chars().all(|c| c.is_ascii_hexdigit())
+        && revision.starts_with(reference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(name: &str, revision: &str) -> RemoteRef {
+        RemoteRef {
+            name: name.to_string(),
+            revision: revision.to_string(),
+        }
+    }
+
+    fn git_skill(name: &str, pinned_ref: Option<&str>, semver_updates: bool) -> Skill {
+        Skill {
+            id: format!("{name}-id"),
+            name: name.to_string(),
+            source_type: "git".to_string(),
+            source_ref: Some(format!("https://github.com/acme/{name}")),
+            source_revision: Some("aaa111".to_string()),
+            central_path: name.to_string(),
+            content_hash: Some("hash".to_string()),
+            created_at: 0,
+            updated_at: 0,
+            last_sync_at: None,
+            status: "ok".to_string(),
+            pinned_ref: pinned_ref.map(str::to_string),
+            semver_updates,
+            safety_report: None,
+            sort_index: 0,
+            user_group: None,
+            group_id: None,
+            user_note: None,
+            management_enabled: true,
+            disabled_previous_tools: Vec::new(),
+            tags: Vec::new(),
+            enabled_tools: Vec::new(),
+            sync_details: None,
+        }
+    }
+
+    fn tag_refs() -> Vec<RemoteRef> {
+        vec![
+            remote("HEAD", "fff999"),
+            remote("refs/heads/main", "fff999"),
+            remote("refs/tags/v1.2.0", "aaa111"),
+            remote("refs/tags/v1.3.1", "bbb222"),
+            remote("refs/tags/v1.4.0-rc.1", "ccc333"),
+            remote("refs/tags/v2.0.0", "ddd444"),
+        ]
+    }
+
+    #[test]
+    fn semver_tags_parse_and_skip_prereleases() {
+        assert_eq!(parse_semver_tag("v1.2.3"), Some((1, 2, 3)));
+        assert_eq!(parse_semver_tag("refs/tags/2.0"), Some((2, 0, 0)));
+        assert_eq!(parse_semver_tag("v1.4.0-rc.1"), None);
+        assert_eq!(parse_semver_tag("main"), None);
+    }
+
+    #[test]
+    fn semver_pins_only_move_within_major_version() {
+        let refs = tag_refs();
+        let tracking = git_skill("docs", Some("v1.2.0"), true);
+        assert_eq!(
+            semver_update_target(&tracking, &refs).as_deref(),
+            Some("v1.3.1")
+        );
+
+        let exact = git_skill("docs", Some("v1.2.0"), false);
+        assert_eq!(semver_update_target(&exact, &refs), None);
+
+        let zero = vec![
+            remote("refs/tags/v0.3.0", "x"),
+            remote("refs/tags/v0.2.5", "y"),
+        ];
+        let early = git_skill("docs", Some("v0.2.1"), true);
+        assert_eq!(
+            semver_update_target(&early, &zero).as_deref(),
+            Some("v0.2.5")
+        );
+    }
+
+    #[test]
+    fn update_check_offers_newest_tag_or_branch_head() {
+        let refs = tag_refs();
+
+        let exact_tag = check_skill_update(&git_skill("a", Some("v1.2.0"), false), &refs, None);
+        assert!(exact_tag.update_available);
+        assert_eq!(exact_tag.update_ref.as_deref(), Some("v2.0.0"));
+
+        let commit_pin = check_skill_update(&git_skill("b", Some("aaa111"), false), &refs, None);
+        assert!(commit_pin.update_available);
+        assert_eq!(commit_pin.update_ref.as_deref(), Some("fff999"));
+
+        let mut current = git_skill("c", None, false);
+        current.source_revision = Some("fff999".to_string());
+        let unpinned = check_skill_update(&current, &refs, Some("main"));
+        assert!(!unpinned.update_available);
+
+        let missing = check_skill_update(&current, &refs, Some("dev"));
+        assert!(missing.error.is_some());
+    }
+
+    #[test]
+    fn lockfile_is_sorted_and_round_trips() {
+        let mut local = git_skill("local", None, false);
+        local.source_type = "local".to_string();
+        let skills = vec![
+            git_skill("zeta", None, false),
+            git_skill("alpha", Some("v1.2.0"), true),
+            local,
+        ];
+
+        let lockfile = build_lockfile(&skills);
+        let raw = serde_json::to_string_pretty(&lockfile).expect("serialize lockfile");
+        let parsed = parse_lockfile(&raw).expect("parse lockfile");
+
+        let names: Vec<&str> = parsed.skills.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["alpha", "zeta"]);
+        assert_eq!(parsed.skills[0].pinned_ref, "v1.2.0");
+        assert!(parsed.skills[0].semver_updates);
+        assert_eq!(parsed.skills[1].pinned_ref, "aaa111");
+        assert!(raw.contains("\"ref\": \"v1.2.0\""));
+    }
+
+    #[test]
+    fn lockfile_rejects_duplicates_and_unknown_versions() {
+        let entry = r#"{"name":"a","source":"s","ref":"v1","revision":null,"content_hash":null}"#;
+        let duplicated = format!(r#"{{"lockfile_version":1,"skills":[{entry},{entry}]}}"#);
+        assert!(parse_lockfile(&duplicated).is_err());
+        assert!(parse_lockfile(r#"{"lockfile_version":9,"skills":[]}"#).is_err());
+    }
+}
@@ -2,6 +2,7 @@
 // Unified management for AI coding tool skills
 
 pub mod adapter;
+pub mod archive_fetcher;
 pub mod auto_update;
 pub mod cache_cleanup;
 pub mod central_repo;
@@ -10,8 +11,12 @@ pub mod content_hash;
 pub mod cron_utils;
 pub mod git_fetcher;
 pub mod installer;
+pub mod lockfile;
 pub mod onboarding;
 pub mod path_executor;
+pub mod revision_diff;
+pub mod safety_scan;
+pub mod skill_lint;
 pub mod skill_store;
 pub mod sync_engine;
 pub mod tool_adapters;
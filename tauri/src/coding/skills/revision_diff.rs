@@ -0,0 +1,279 @@
+//! Per-file diff between the central copy of a skill and an update candidate,
+//! shown for review before the update is accepted.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use walkdir::WalkDir;
+
+use super::types::SkillFileDiffDto;
+
+/// Entries never shown in a review: VCS data and the git cache metadata file.
+const IGNORE_NAMES: [&str; 4] = [".git", ".DS_Store", "Thumbs.db", ".skills-cache.json"];
+/// Text files above this size are reported as modified without a line diff.
+const MAX_DIFF_BYTES: usize = 256 * 1024;
+/// Upper bound on the LCS table (old lines x new lines).
+const MAX_DIFF_CELLS: usize = 4_000_000;
+const CONTEXT_LINES: usize = 3;
+
+/// Diff every file under `current` against the same path under `candidate`.
+pub fn diff_skill_dirs(current: &Path, candidate: &Path) -> Result<Vec<SkillFileDiffDto>> {
+    let current_files = list_files(current)?;
+    let candidate_files = list_files(candidate)?;
+    let all: BTreeSet<&String> = current_files.iter().chain(candidate_files.iter()).collect();
+
+    let mut diffs = Vec::new();
+    for relative in all {
+        let old = current_files
+            .contains(relative)
+            .then(|| read_file(&current.join(relative)))
+            .transpose()?;
+        let new = candidate_files
+            .contains(relative)
+            .then(|| read_file(&candidate.join(relative)))
+            .transpose()?;
+        let status = match (&old, &new) {
+            (Some(old), Some(new)) if old == new => continue,
+            (Some(_), Some(_)) => "modified",
+            (None, Some(_)) => "added",
+            (Some(_), None) => "removed",
+            (None, None) => continue,
+        };
+
+        let old_text = old.as_deref().map(as_text);
+        let new_text = new.as_deref().map(as_text);
+        let binary = matches!(old_text, Some(None)) || matches!(new_text, Some(None));
+        let diff = if binary {
+            None
+        } else {
+            unified_diff(
+                old_text.flatten().unwrap_or(""),
+                new_text.flatten().unwrap_or(""),
+            )
+        };
+
+        diffs.push(SkillFileDiffDto {
+            path: relative.clone(),
+            status: status.to_string(),
+            diff,
+            binary,
+        });
+    }
+    Ok(diffs)
+}
+
+fn list_files(root: &Path) -> Result<BTreeSet<String>> {
+    let mut files = BTreeSet::new();
+    if !root.exists() {
+        return Ok(files);
+    }
+    for entry in WalkDir::new(root)
+        .follow_links(true)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !IGNORE_NAMES.iter().any(|ignored| *ignored == name.as_ref())
+        })
+    {
+        let entry = entry.with_context(|| format!("walk {:?}", root))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative: PathBuf = entry
+            .path()
+            .strip_prefix(root)
+            .with_context(|| format!("strip prefix {:?}", entry.path()))?
+            .to_path_buf();
+        files.insert(relative.to_string_lossy().replace('\\', "/"));
+    }
+    Ok(files)
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).with_context(|| format!("read file {:?}", path))
+}
+
+fn as_text(bytes: &[u8]) -> Option<&str> {
+    if bytes.contains(&0) {
+        return None;
+    }
+    std::str::from_utf8(bytes).ok()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum LineOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Unified diff with `CONTEXT_LINES` of context. None when the inputs are too
+/// large for a line diff.
+fn unified_diff(old: &str, new: &str) -> Option<String> {
+    if old.len() > MAX_DIFF_BYTES || new.len() > MAX_DIFF_BYTES {
+        return None;
+    }
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    if (old_lines.len() + 1).saturating_mul(new_lines.len() + 1) > MAX_DIFF_CELLS {
+        return None;
+    }
+
+    let ops = line_ops(&old_lines, &new_lines);
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _))| *op != LineOp::Equal)
+        .map(|(index, _)| index)
+        .collect();
+
+    // Merge change positions into hunks that share context lines.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for index in changes {
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + CONTEXT_LINES + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    // Line numbers (1-based) before each op, for the hunk headers.
+    let mut old_before = Vec::with_capacity(ops.len());
+    let mut new_before = Vec::with_capacity(ops.len());
+    let (mut old_line, mut new_line) = (1usize, 1usize);
+    for (op, _) in &ops {
+        old_before.push(old_line);
+        new_before.push(new_line);
+        match op {
+            LineOp::Equal => {
+                old_line += 1;
+                new_line += 1;
+            }
+            LineOp::Delete => old_line += 1,
+            LineOp::Insert => new_line += 1,
+        }
+    }
+
+    let mut out = String::new();
+    for (start, end) in hunks {
+        let slice = &ops[start..end];
+        let old_len = slice.iter().filter(|(op, _)| *op != LineOp::Insert).count();
+        let new_len = slice.iter().filter(|(op, _)| *op != LineOp::Delete).count();
+        let old_start = if old_len == 0 {
+            old_before[start] - 1
+        } else {
+            old_before[start]
+        };
+        let new_start = if new_len == 0 {
+            new_before[start] - 1
+        } else {
+            new_before[start]
+        };
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start, old_len, new_start, new_len
+        ));
+        for (op, line) in slice {
+            let prefix = match op {
+                LineOp::Equal => ' ',
+                LineOp::Delete => '-',
+                LineOp::Insert => '+',
+            };
+            out.push(prefix);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    Some(out)
+}
+
+/// Longest-common-subsequence line alignment.
+fn line_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(LineOp, &'a str)> {
+    let width = new.len() + 1;
+    let mut table = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            table[i * width + j] = if old[i] == new[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            ops.push((LineOp::Equal, old[i]));
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            ops.push((LineOp::Delete, old[i]));
+            i += 1;
+        } else {
+            ops.push((LineOp::Insert, new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|line| (LineOp::Delete, *line)));
+    ops.extend(new[j..].iter().map(|line| (LineOp::Insert, *line)));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unified_diff_reports_changed_lines_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let new = "a\nb\nc\nD\ne\nf\ng\nh\ni\n";
+
+        let diff = unified_diff(old, new).expect("diff");
+
+        assert_eq!(
+            diff,
+            "@@ -1,8 +1,9 @@\n a\n b\n c\n-d\n+D\n e\n f\n g\n h\n+i\n"
+        );
+    }
+
+    #[test]
+    fn diff_skill_dirs_lists_added_removed_and_modified_files() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let current = temp.path().join("current");
+        let candidate = temp.path().join("candidate");
+        for dir in [&current, &candidate] {
+            std::fs::create_dir_all(dir.join("scripts")).expect("create dirs");
+            std::fs::write(dir.join("README.md"), "same").expect("write readme");
+            std::fs::write(
+                dir.join(".skills-cache.json"),
+                dir.to_string_lossy().as_bytes(),
+            )
+            .expect("write cache meta");
+        }
+        std::fs::write(current.join("SKILL.md"), "name: demo\nold\n").expect("write old");
+        std::fs::write(candidate.join("SKILL.md"), "name: demo\nnew\n").expect("write new");
+        std::fs::write(current.join("scripts/old.sh"), "echo old").expect("write removed");
+        std::fs::write(candidate.join("scripts/tool.bin"), [0u8, 1, 2]).expect("write binary");
+
+        let diffs = diff_skill_dirs(&current, &candidate).expect("diff dirs");
+
+        let summary: Vec<(&str, &str, bool)> = diffs
+            .iter()
+            .map(|item| (item.path.as_str(), item.status.as_str(), item.binary))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("SKILL.md", "modified", false),
+                ("scripts/old.sh", "removed", false),
+                ("scripts/tool.bin", "added", true),
+            ]
+        );
+        assert!(diffs[0].diff.as_deref().unwrap().contains("-old\n+new\n"));
+        assert!(diffs[2].diff.is_none());
+    }
+}
@@ -0,0 +1,571 @@
+//! SKILL.md validation.
+//!
+//! Checks the YAML frontmatter (`name`, `description`), the name against the
+//! skill directory, duplicate names across the central repo, relative links to
+//! files inside the skill, and the name/description limits of each target
+//! tool. Issues without a tool apply to every tool; per-tool limits produce
+//! issues tagged with that tool so the UI can show what would break before a
+//! skill is synced.
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::types::{SkillLintIssueDto, SkillLintReportDto, SkillLintToolStatusDto};
+
+pub const SEVERITY_ERROR: &str = "error";
+pub const SEVERITY_WARNING: &str = "warning";
+pub const STATUS_OK: &str = "ok";
+
+/// Limits a tool applies when loading skills.
+struct ToolLintRule {
+    tool: &'static str,
+    name_max: usize,
+    description_max: usize,
+    /// Description must fit on one line (no block scalars with newlines).
+    single_line: bool,
+    /// The tool refuses skills whose name breaks the naming rules or differs
+    /// from the directory name, instead of only warning.
+    strict_name: bool,
+}
+
+/// Agent Skills defaults, used for tools without a dedicated rule.
+const DEFAULT_RULE: ToolLintRule = ToolLintRule {
+    tool: "",
+    name_max: 64,
+    description_max: 1024,
+    single_line: false,
+    strict_name: false,
+};
+
+const TOOL_RULES: [ToolLintRule; 4] = [
+    ToolLintRule {
+        tool: "claude_code",
+        ..DEFAULT_RULE
+    },
+    ToolLintRule {
+        tool: "codex",
+        name_max: 100,
+        description_max: 500,
+        single_line: true,
+        strict_name: false,
+    },
+    ToolLintRule {
+        tool: "opencode",
+        strict_name: true,
+        ..DEFAULT_RULE
+    },
+    ToolLintRule {
+        tool: "gemini_cli",
+        ..DEFAULT_RULE
+    },
+];
+
+/// Tools linted when the caller does not name any.
+pub fn default_lint_tools() -> Vec<String> {
+    TOOL_RULES
+        .iter()
+        .map(|rule| rule.tool.to_string())
+        .collect()
+}
+
+fn rule_for(tool: &str) -> &'static ToolLintRule {
+    TOOL_RULES
+        .iter()
+        .find(|rule| rule.tool == tool)
+        .unwrap_or(&DEFAULT_RULE)
+}
+
+/// Inline markdown links and images: `[text](target "title")`.
+static MARKDOWN_LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"!?\[[^\]]*\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#)
+        .expect("valid markdown link pattern")
+});
+
+/// Lowercase letters, digits and single hyphens between them.
+static SKILL_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-z0-9]+(?:-[a-z0-9]+)*$").expect("valid name pattern"));
+
+/// Parsed SKILL.md frontmatter.
+pub struct SkillManifest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// 1-based line of the first body line
+    body_line: usize,
+    body: String,
+    /// 1-based lines of the `name:` / `description:` keys
+    name_line: Option<usize>,
+    description_line: Option<usize>,
+}
+
+/// Split and parse the YAML frontmatter of a SKILL.md.
+pub fn parse_skill_manifest(content: &str) -> Result<SkillManifest, String> {
+    let normalized = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut lines = normalized.lines();
+    if lines.next().map(str::trim_end) != Some("---") {
+        return Err("SKILL.md must start with YAML frontmatter delimited by ---".to_string());
+    }
+
+    let mut frontmatter = Vec::new();
+    let mut closed = false;
+    for line in lines.by_ref() {
+        if matches!(line.trim_end(), "---" | "...") {
+            closed = true;
+            break;
+        }
+        frontmatter.push(line);
+    }
+    if !closed {
+        return Err("SKILL.md frontmatter is missing a closing --- delimiter".to_string());
+    }
+    let body_line = frontmatter.len() + 3;
+    let body = lines.collect::<Vec<_>>().join("\n");
+
+    let yaml = frontmatter.join("\n");
+    let value = serde_yaml::from_str::<serde_yaml::Value>(&yaml)
+        .map_err(|error| format!("Failed to parse YAML frontmatter: {error}"))?;
+    let mapping = match value {
+        serde_yaml::Value::Mapping(mapping) => mapping,
+        serde_yaml::Value::Null => serde_yaml::Mapping::new(),
+        _ => return Err("SKILL.md frontmatter must be a YAML mapping".to_string()),
+    };
+
+    let key_line = |key: &str| {
+        let prefix = format!("{key}:");
+        frontmatter
+            .iter()
+            .position(|line| line.starts_with(&prefix))
+            .map(|index| index + 2)
+    };
+    let text_field = |key: &str| match mapping.get(key) {
+        Some(serde_yaml::Value::String(text)) => Some(text.clone()),
+        Some(serde_yaml::Value::Number(number)) => Some(number.to_string()),
+        Some(serde_yaml::Value::Bool(flag)) => Some(flag.to_string()),
+        _ => None,
+    };
+
+    Ok(SkillManifest {
+        name: text_field("name"),
+        description: text_field("description"),
+        body_line,
+        body,
+        name_line: key_line("name"),
+        description_line: key_line("description"),
+    })
+}
+
+/// Frontmatter name of the skill in `dir`, for duplicate detection.
+pub fn read_manifest_name(dir: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(dir.join("SKILL.md")).ok()?;
+    parse_skill_manifest(&content)
+        .ok()?
+        .name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Lint the skill in `dir` for `tools`.
+///
+/// `name_counts` holds how many skills in the central repo use each
+/// frontmatter name (see `read_manifest_name`).
+pub fn lint_skill_dir(
+    skill_id: &str,
+    skill_name: &str,
+    dir: &Path,
+    tools: &[String],
+    name_counts: &HashMap<String, usize>,
+) -> SkillLintReportDto {
+    let mut issues = Vec::new();
+    let mut frontmatter_name = None;
+
+    match std::fs::read_to_string(dir.join("SKILL.md")) {
+        Err(_) => issues.push(issue(
+            "missing_skill_md",
+            SEVERITY_ERROR,
+            "SKILL.md not found",
+            None,
+            None,
+        )),
+        Ok(content) => match parse_skill_manifest(&content) {
+            Err(message) => issues.push(issue(
+                "invalid_frontmatter",
+                SEVERITY_ERROR,
+                message,
+                Some(1),
+                None,
+            )),
+            Ok(manifest) => {
+                lint_manifest(dir, &manifest, tools, name_counts, &mut issues);
+                frontmatter_name = manifest.name.map(|name| name.trim().to_string());
+            }
+        },
+    }
+
+    let tool_status = tools
+        .iter()
+        .map(|tool| SkillLintToolStatusDto {
+            tool: tool.clone(),
+            status: worst_status(
+                issues
+                    .iter()
+                    .filter(|item| item.tool.as_deref().is_none_or(|t| t == tool)),
+            )
+            .to_string(),
+        })
+        .collect();
+
+    SkillLintReportDto {
+        skill_id: skill_id.to_string(),
+        name: skill_name.to_string(),
+        frontmatter_name,
+        status: worst_status(issues.iter()).to_string(),
+        issues,
+        tools: tool_status,
+    }
+}
+
+fn lint_manifest(
+    dir: &Path,
+    manifest: &SkillManifest,
+    tools: &[String],
+    name_counts: &HashMap<String, usize>,
+    issues: &mut Vec<SkillLintIssueDto>,
+) {
+    let dir_name = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = manifest
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty());
+    let description = manifest
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty());
+
+    match name {
+        None => issues.push(issue(
+            "missing_name",
+            SEVERITY_ERROR,
+            "Frontmatter has no name",
+            Some(manifest.name_line.unwrap_or(1)),
+            None,
+        )),
+        Some(name) => {
+            let name_ok = SKILL_NAME.is_match(name);
+            let dir_ok = name == dir_name;
+            if !name_ok {
+                issues.push(issue(
+                    "invalid_name",
+                    SEVERITY_WARNING,
+                    format!("Name '{name}' should use lowercase letters, digits and hyphens"),
+                    manifest.name_line,
+                    None,
+                ));
+            }
+            if !dir_ok {
+                issues.push(issue(
+                    "name_dir_mismatch",
+                    SEVERITY_WARNING,
+                    format!("Name '{name}' does not match the directory name '{dir_name}'"),
+                    manifest.name_line,
+                    None,
+                ));
+            }
+            if name_counts.get(name).copied().unwrap_or(0) > 1 {
+                issues.push(issue(
+                    "duplicate_name",
+                    SEVERITY_ERROR,
+                    format!("Another skill in the central repo is also named '{name}'"),
+                    manifest.name_line,
+                    None,
+                ));
+            }
+            for tool in tools {
+                let rule = rule_for(tool);
+                if name.chars().count() > rule.name_max {
+                    issues.push(issue(
+                        "name_too_long",
+                        SEVERITY_ERROR,
+                        format!("Name is longer than {} characters", rule.name_max),
+                        manifest.name_line,
+                        Some(tool),
+                    ));
+                }
+                if rule.strict_name && (!name_ok || !dir_ok) {
+                    issues.push(issue(
+                        "name_rejected",
+                        SEVERITY_ERROR,
+                        "Name must be lowercase-hyphenated and match the directory name",
+                        manifest.name_line,
+                        Some(tool),
+                    ));
+                }
+            }
+        }
+    }
+
+    match description {
+        None => issues.push(issue(
+            "missing_description",
+            SEVERITY_ERROR,
+            "Frontmatter has no description",
+            Some(manifest.description_line.unwrap_or(1)),
+            None,
+        )),
+        Some(description) => {
+            let length = description.chars().count();
+            for tool in tools {
+                let rule = rule_for(tool);
+                if length > rule.description_max {
+                    issues.push(issue(
+                        "description_too_long",
+                        SEVERITY_ERROR,
+                        format!(
+                            "Description has {} characters; the limit is {}",
+                            length, rule.description_max
+                        ),
+                        manifest.description_line,
+                        Some(tool),
+                    ));
+                }
+                if rule.single_line && description.contains('\n') {
+                    issues.push(issue(
+                        "description_multiline",
+                        SEVERITY_WARNING,
+                        "Description should fit on a single line",
+                        manifest.description_line,
+                        Some(tool),
+                    ));
+                }
+            }
+        }
+    }
+
+    lint_references(dir, manifest, issues);
+}
+
+/// Check that relative links in the SKILL.md body point at files inside the
+/// skill directory.
+fn lint_references(dir: &Path, manifest: &SkillManifest, issues: &mut Vec<SkillLintIssueDto>) {
+    for (index, line) in manifest.body.lines().enumerate() {
+        for capture in MARKDOWN_LINK.captures_iter(line) {
+            let target = &capture[1];
+            let Some(relative) = local_link_target(target) else {
+                continue;
+            };
+            let line_no = Some(manifest.body_line + index);
+            match resolve_inside(dir, &relative) {
+                None => issues.push(issue(
+                    "reference_outside",
+                    SEVERITY_WARNING,
+                    format!("'{target}' points outside the skill directory"),
+                    line_no,
+                    None,
+                )),
+                Some(path) if !path.exists() => issues.push(issue(
+                    "missing_reference",
+                    SEVERITY_WARNING,
+                    format!("Referenced file '{target}' does not exist"),
+                    line_no,
+                    None,
+                )),
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+/// Relative file path of a link target; None for URLs, anchors and absolute
+/// paths.
+fn local_link_target(target: &str) -> Option<String> {
+    if target.contains("://")
+        || target.starts_with('#')
+        || target.starts_with('/')
+        || target.starts_with("mailto:")
+        || target.starts_with("data:")
+    {
+        return None;
+    }
+    let path = target.split(['#', '?']).next().unwrap_or("");
+    if path.is_empty() {
+        return None;
+    }
+    Some(path.replace("%20", " "))
+}
+
+/// `dir.join(relative)` with `..` resolved lexically; None when the result
+/// leaves `dir`.
+fn resolve_inside(dir: &Path, relative: &str) -> Option<PathBuf> {
+    let mut parts: Vec<&std::ffi::OsStr> = Vec::new();
+    for component in Path::new(relative).components() {
+        match component {
+            Component::Normal(part) => parts.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                parts.pop()?;
+            }
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(
+        parts
+            .iter()
+            .fold(dir.to_path_buf(), |path, part| path.join(part)),
+    )
+}
+
+fn worst_status<'a>(issues: impl Iterator<Item = &'a SkillLintIssueDto>) -> &'static str {
+    let mut status = STATUS_OK;
+    for item in issues {
+        if item.severity == SEVERITY_ERROR {
+            return SEVERITY_ERROR;
+        }
+        status = SEVERITY_WARNING;
+    }
+    status
+}
+
+fn issue(
+    code: &str,
+    severity: &str,
+    message: impl Into<String>,
+    line: Option<usize>,
+    tool: Option<&str>,
+) -> SkillLintIssueDto {
+    SkillLintIssueDto {
+        code: code.to_string(),
+        severity: severity.to_string(),
+        message: message.into(),
+        line,
+        tool: tool.map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_skill(root: &Path, dir: &str, skill_md: &str) -> PathBuf {
+        let path = root.join(dir);
+        std::fs::create_dir_all(&path).expect("create skill dir");
+        std::fs::write(path.join("SKILL.md"), skill_md).expect("write SKILL.md");
+        path
+    }
+
+    fn codes(report: &SkillLintReportDto) -> Vec<(&str, Option<&str>)> {
+        report
+            .issues
+            .iter()
+            .map(|item| (item.code.as_str(), item.tool.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn valid_skill_passes_for_every_tool() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let dir = write_skill(
+            temp.path(),
+            "pdf-tools",
+            "---\nname: pdf-tools\ndescription: >\n  Fill and merge PDF forms.\n---\nSee [the reference](reference.md#forms).\n",
+        );
+        std::fs::write(dir.join("reference.md"), "forms").expect("write reference");
+
+        let report = lint_skill_dir(
+            "id",
+            "pdf-tools",
+            &dir,
+            &default_lint_tools(),
+            &HashMap::new(),
+        );
+
+        assert_eq!(report.status, STATUS_OK, "{:?}", report.issues);
+        assert!(report.tools.iter().all(|tool| tool.status == STATUS_OK));
+        assert_eq!(report.frontmatter_name.as_deref(), Some("pdf-tools"));
+    }
+
+    #[test]
+    fn reports_frontmatter_name_and_reference_problems() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let dir = write_skill(
+            temp.path(),
+            "docs",
+            "---\nname: Docs Helper\n---\n# Docs\nUse [script](scripts/run.py) and [up](../../etc/passwd).\n",
+        );
+        let counts = HashMap::from([("Docs Helper".to_string(), 2)]);
+
+        let report = lint_skill_dir(
+            "id",
+            "docs",
+            &dir,
+            &["claude_code".to_string(), "opencode".to_string()],
+            &counts,
+        );
+
+        assert_eq!(
+            codes(&report),
+            vec![
+                ("invalid_name", None),
+                ("name_dir_mismatch", None),
+                ("duplicate_name", None),
+                ("name_rejected", Some("opencode")),
+                ("missing_description", None),
+                ("missing_reference", None),
+                ("reference_outside", None),
+            ]
+        );
+        assert_eq!(report.issues[0].line, Some(2));
+        assert_eq!(report.issues[5].line, Some(5));
+        assert_eq!(report.status, SEVERITY_ERROR);
+    }
+
+    #[test]
+    fn description_limits_differ_per_tool() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let long = "x".repeat(600);
+        let dir = write_skill(
+            temp.path(),
+            "long",
+            &format!("---\nname: long\ndescription: {long}\n---\n"),
+        );
+
+        let report = lint_skill_dir("id", "long", &dir, &default_lint_tools(), &HashMap::new());
+
+        let status: Vec<(&str, &str)> = report
+            .tools
+            .iter()
+            .map(|tool| (tool.tool.as_str(), tool.status.as_str()))
+            .collect();
+        assert_eq!(
+            status,
+            vec![
+                ("claude_code", STATUS_OK),
+                ("codex", SEVERITY_ERROR),
+                ("opencode", STATUS_OK),
+                ("gemini_cli", STATUS_OK),
+            ]
+        );
+    }
+
+    #[test]
+    fn broken_frontmatter_is_a_single_error() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let missing = write_skill(temp.path(), "a", "# no frontmatter\n");
+        let unclosed = write_skill(temp.path(), "b", "---\nname: b\n");
+        let invalid = write_skill(temp.path(), "c", "---\nname: [c\n---\n");
+
+        for dir in [missing, unclosed, invalid] {
+            let report = lint_skill_dir("id", "x", &dir, &[], &HashMap::new());
+            assert_eq!(codes(&report), vec![("invalid_frontmatter", None)]);
+        }
+        let empty = temp.path().join("d");
+        std::fs::create_dir_all(&empty).expect("create empty");
+        let report = lint_skill_dir("id", "d", &empty, &[], &HashMap::new());
+        assert_eq!(codes(&report), vec![("missing_skill_md", None)]);
+    }
+}
@@ -14,8 +14,11 @@ use super::adapter::{
     to_clean_skill_payload, to_skill_group_payload, to_skill_preferences_payload,
     to_skill_repo_payload,
 };
+use super::safety_scan::normalize_policy;
 use super::tool_adapters::CustomTool;
-use super::types::{now_ms, Skill, SkillGroupRecord, SkillPreferences, SkillRepo, SkillTarget};
+use super::types::{
+    now_ms, Skill, SkillGroupRecord, SkillPreferences, SkillRepo, SkillSafetyReport, SkillTarget,
+};
 
 const SKILL_PREFERENCES_ID: &str = "default";
 
@@ -362,6 +365,36 @@ pub async fn update_skill_content_hash(
     Ok(())
 }
 
+/// Set or clear the version pin of a git skill.
+pub async fn set_skill_pin(
+    state: &SqliteDbState,
+    skill_id: &str,
+    pinned_ref: Option<String>,
+    semver_updates: bool,
+) -> Result<(), String> {
+    sqlite_patch_skill(state, skill_id, |skill| {
+        skill.pinned_ref = pinned_ref;
+        skill.semver_updates = semver_updates;
+        skill.updated_at = now_ms();
+    })?
+    .ok_or_else(|| format!("Skill not found: {}", skill_id))?;
+    Ok(())
+}
+
+/// Store the result of a safety rescan (does not touch updated_at: the
+/// content itself did not change).
+pub async fn set_skill_safety_report(
+    state: &SqliteDbState,
+    skill_id: &str,
+    report: SkillSafetyReport,
+) -> Result<(), String> {
+    sqlite_patch_skill(state, skill_id, |skill| {
+        skill.safety_report = Some(report);
+    })?
+    .ok_or_else(|| format!("Skill not found: {}", skill_id))?;
+    Ok(())
+}
+
 /// Update user-managed group for multiple skills.
 pub async fn update_skills_group(
     state: &SqliteDbState,
@@ -577,6 +610,7 @@ pub async fn get_setting(state: &SqliteDbState, key: &str) -> Result<Option<Stri
         "show_skills_in_tray" => Some(prefs.show_skills_in_tray.to_string()),
         "auto_update_enabled" => Some(prefs.auto_update_enabled.to_string()),
         "auto_update_schedule" => Some(prefs.auto_update_schedule.clone()),
+        "safety_scan_policy" => Some(prefs.safety_scan_policy.clone()),
         _ => None,
     };
 
@@ -616,6 +650,9 @@ pub async fn set_setting(state: &SqliteDbState, key: &str, value: &str) -> Resul
         "auto_update_schedule" => {
             prefs.auto_update_schedule = value.to_string();
         }
+        "safety_scan_policy" => {
+            prefs.safety_scan_policy = normalize_policy(value);
+        }
         _ => return Err(format!("Unknown setting key: {}", key)),
     };
 
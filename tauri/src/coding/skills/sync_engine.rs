@@ -315,7 +315,8 @@ pub fn copy_skill_dir(source: &Path, target: &Path) -> Result<()> {
         let entry = entry?;
         let name = entry.file_name();
 
-        if name == ".git" {
+        // Skip VCS data and the git cache metadata of root-level repo skills
+        if name == ".git" || name == ".skills-cache.json" {
             continue;
         }
 
@@ -20,6 +20,15 @@ pub struct Skill {
     pub last_sync_at: Option<i64>,
     pub status: String,
 
+    // Version pin for git skills: a commit or tag that updates install instead of
+    // the branch head. With semver_updates, updates may move the pin to newer
+    // tags of the same major version.
+    pub pinned_ref: Option<String>,
+    pub semver_updates: bool,
+
+    // Result of the last static safety scan (install, update or manual rescan).
+    pub safety_report: Option<SkillSafetyReport>,
+
     // Sort order for drag-and-drop reordering
     pub sort_index: i32,
 
@@ -69,6 +78,9 @@ pub struct SkillRepo {
     pub branch: String, // default: "main"
     pub enabled: bool,  // default: true
     pub created_at: i64,
+    /// Full source URL for repos outside GitHub (GitLab, Gitea, ssh, archives);
+    /// None means https://github.com/{owner}/{name}
+    pub url: Option<String>,
 }
 
 /// Skill preferences - user preference settings (structured wide table)
@@ -84,6 +96,7 @@ pub struct SkillPreferences {
     pub show_skills_in_tray: bool,            // Show skills in system tray quick menu
     pub auto_update_enabled: bool,            // Enable scheduled auto-update of all skills
     pub auto_update_schedule: String,         // 5-field cron expression (min hour dom mon dow)
+    pub safety_scan_policy: String,           // "warn" | "block" on danger findings
     pub updated_at: i64,
 }
 
@@ -100,6 +113,7 @@ impl Default for SkillPreferences {
             show_skills_in_tray: false,
             auto_update_enabled: false,
             auto_update_schedule: "0 3 * * *".to_string(),
+            safety_scan_policy: "warn".to_string(),
             updated_at: 0,
         }
     }
@@ -140,6 +154,10 @@ pub struct ManagedSkillDto {
     pub name: String,
     pub source_type: String,
     pub source_ref: Option<String>,
+    pub source_revision: Option<String>,
+    pub pinned_ref: Option<String>,
+    pub semver_updates: bool,
+    pub safety_report: Option<SkillSafetyReport>,
     pub central_path: String,
     pub created_at: i64,
     pub updated_at: i64,
@@ -436,6 +454,128 @@ pub struct SkillsUpdateProgress {
     pub message: String,
 }
 
+/// Result of checking one git skill against its remote (nothing is applied)
+#[derive(Debug, Serialize)]
+pub struct SkillUpdateCheckDto {
+    pub skill_id: String,
+    pub name: String,
+    pub source_revision: Option<String>,
+    pub pinned_ref: Option<String>,
+    /// Newest tag for tag pins, otherwise the tracked branch (display only)
+    pub latest_ref: Option<String>,
+    pub latest_revision: Option<String>,
+    pub update_available: bool,
+    /// Ref to preview and accept: the tag, or the exact head commit so the
+    /// reviewed content is what gets installed
+    pub update_ref: Option<String>,
+    pub error: Option<String>,
+}
+
+/// One changed file between the central copy and an update candidate
+#[derive(Debug, Serialize)]
+pub struct SkillFileDiffDto {
+    pub path: String,
+    pub status: String, // "added" | "removed" | "modified"
+    /// Unified diff of text files; None for binary or oversized files
+    pub diff: Option<String>,
+    pub binary: bool,
+}
+
+/// Per-file diff of an update candidate against the central repo copy
+#[derive(Debug, Serialize)]
+pub struct SkillUpdatePreviewDto {
+    pub skill_id: String,
+    pub name: String,
+    pub from_revision: Option<String>,
+    pub to_ref: String,
+    pub to_revision: String,
+    pub files: Vec<SkillFileDiffDto>,
+}
+
+/// Committable lockfile pinning every git skill to a commit or tag.
+///
+/// Entries are sorted by name and carry no timestamps so re-exporting an
+/// unchanged setup produces an identical file.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SkillLockfileJson {
+    pub lockfile_version: u32,
+    pub skills: Vec<SkillLockEntryJson>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SkillLockEntryJson {
+    pub name: String,
+    pub source: String,
+    /// Commit or tag the skill is pinned to
+    #[serde(rename = "ref")]
+    pub pinned_ref: String,
+    /// Commit the ref resolved to when the lockfile was written
+    pub revision: Option<String>,
+    #[serde(default)]
+    pub semver_updates: bool,
+    pub content_hash: Option<String>,
+}
+
+/// DTO for applying a lockfile
+#[derive(Debug, Serialize)]
+pub struct SkillLockfileApplyResultDto {
+    pub pinned: Vec<String>,
+    pub updated: Vec<String>,
+    /// Lockfile entries with no installed git skill of the same name and source
+    pub missing: Vec<String>,
+    pub errors: Vec<UpdateAllErrorDto>,
+}
+
+/// Static safety scan of a skill directory
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillSafetyReport {
+    pub scanned_at: i64,
+    pub level: String, // "clean" | "warning" | "danger"
+    pub findings: Vec<SkillSafetyFinding>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillSafetyFinding {
+    /// "executable_file" | "remote_pipe_exec" | "encoded_payload" |
+    /// "credential_exfiltration" | "oversized_binary" | "symlink_escape"
+    pub rule: String,
+    pub severity: String, // "warning" | "danger"
+    /// Path relative to the skill directory
+    pub path: String,
+    pub line: Option<usize>,
+    pub message: String,
+    pub excerpt: Option<String>,
+}
+
+/// One SKILL.md validation problem
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillLintIssueDto {
+    pub code: String,
+    pub severity: String, // "error" | "warning"
+    pub message: String,
+    /// 1-based line in SKILL.md
+    pub line: Option<usize>,
+    /// Tool whose limits the issue breaks; None when it affects every tool
+    pub tool: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkillLintToolStatusDto {
+    pub tool: String,
+    pub status: String, // "ok" | "warning" | "error"
+}
+
+/// SKILL.md validation result of one managed skill
+#[derive(Debug, Serialize)]
+pub struct SkillLintReportDto {
+    pub skill_id: String,
+    pub name: String,
+    pub frontmatter_name: Option<String>,
+    pub status: String, // "ok" | "warning" | "error"
+    pub issues: Vec<SkillLintIssueDto>,
+    pub tools: Vec<SkillLintToolStatusDto>,
+}
+
 /// DTO for scheduled auto-update configuration
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -561,6 +701,7 @@ pub struct SkillRepoDto {
     pub branch: String,
     pub enabled: bool,
     pub created_at: i64,
+    pub url: Option<String>,
 }
 
 /// A document file (e.g. SKILL.md / README.md) inside a Skill's central dir,
@@ -364,12 +364,24 @@ pub async fn ssh_set_active_connection(
     Ok(())
 }
 
-/// Test an SSH connection (async, non-blocking)
+/// Test an SSH connection (async, non-blocking).
+/// A first-seen host key is only pinned when `trusted_fingerprint` matches it;
+/// otherwise the result carries the unconfirmed key for the user to review.
 #[tauri::command]
-pub async fn ssh_test_connection(mut connection: SSHConnection) -> SSHConnectionResult {
+pub async fn ssh_test_connection(
+    mut connection: SSHConnection,
+    trusted_fingerprint: Option<String>,
+) -> SSHConnectionResult {
     normalise_key_fields(&mut connection);
 
-    sync::test_connection(&connection).await
+    sync::test_connection(&connection, trusted_fingerprint.as_deref()).await
+}
+
+/// Forget the app-recorded host key of a host so the next connection learns it again.
+/// `~/.ssh/known_hosts` is never modified; returns the number of removed entries.
+#[tauri::command]
+pub async fn ssh_forget_host_key(host: String, port: u16) -> Result<usize, String> {
+    super::known_hosts::HostKeyVerifier::from_default_locations().forget(host.trim(), port)
 }
 
 // ============================================================================
@@ -0,0 +1,608 @@
+//! SSH 主机密钥校验（known_hosts）
+//!
+//! 在 `StrictHostKeyChecking=accept-new` 语义上增加用户确认：
+//! - 已知主机：密钥必须与记录一致，否则连接直接失败（可能是中间人攻击）
+//! - 未知主机：握手阶段先拒绝并返回指纹（此时尚未发送任何凭据），用户确认后
+//!   带上该指纹重连，密钥一致才写入应用自管的 known_hosts
+//! - `@revoked` 标记的密钥一律拒绝
+//!
+//! 读取来源：`~/.ssh/known_hosts`（支持 `|1|salt|hash` 哈希主机名、通配符和 `!` 取反）
+//! 以及应用数据目录下的 `ssh_known_hosts`。应用只写自己的文件，不改用户的 known_hosts。
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use russh::keys::ssh_key::{Algorithm, HashAlg, PublicKey};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+
+const APP_KNOWN_HOSTS_FILE: &str = "ssh_known_hosts";
+
+static STORE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// 设置应用自管 known_hosts 所在目录（应用数据目录）
+pub fn set_store_dir(dir: PathBuf) {
+    let _ = STORE_DIR.set(dir);
+}
+
+/// 应用自管 known_hosts 路径
+pub fn app_known_hosts_path() -> Option<PathBuf> {
+    STORE_DIR.get().map(|dir| dir.join(APP_KNOWN_HOSTS_FILE))
+}
+
+/// 用户 OpenSSH known_hosts 路径
+pub fn user_known_hosts_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ssh").join("known_hosts"))
+}
+
+/// 单次主机密钥校验的结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HostKeyStatus {
+    /// 与已记录的密钥一致
+    Known,
+    /// 首次连接，用户已确认指纹，已接受并记录
+    Learned,
+    /// 首次连接，等待用户确认指纹（未记录，本次拒绝）
+    Unconfirmed,
+    /// 与已记录的同类型密钥不一致
+    Changed,
+    /// 命中 `@revoked` 记录
+    Revoked,
+}
+
+/// 主机密钥校验结果（由 SSH handler 记录，供连接结果展示指纹）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostKeyCheck {
+    pub host: String,
+    pub port: u16,
+    pub status: HostKeyStatus,
+    pub algorithm: String,
+    /// 服务器实际出示的密钥指纹（SHA256:...）
+    pub fingerprint: String,
+    /// 命中的 known_hosts 文件与行号
+    pub source: Option<String>,
+    /// 密钥变化时，记录中的旧指纹
+    pub known_fingerprint: Option<String>,
+}
+
+impl HostKeyCheck {
+    /// 是否允许继续握手
+    pub fn accepted(&self) -> bool {
+        matches!(self.status, HostKeyStatus::Known | HostKeyStatus::Learned)
+    }
+
+    /// 校验失败时给用户看的错误信息
+    pub fn rejection_message(&self) -> Option<String> {
+        let target = host_port_pattern(&self.host, self.port);
+        let source = self.source.as_deref().unwrap_or("known_hosts");
+        match self.status {
+            HostKeyStatus::Known | HostKeyStatus::Learned => None,
+            HostKeyStatus::Unconfirmed => Some(format!(
+                "首次连接 {}，服务器 {} 主机密钥指纹为 {}。请先在 SSH 同步设置中测试连接并确认信任该主机密钥。",
+                target, self.algorithm, self.fingerprint
+            )),
+            HostKeyStatus::Changed => Some(format!(
+                "远程主机 {} 的主机密钥已变化，可能存在中间人攻击！服务器当前 {} 密钥指纹为 {}，而 {} 中记录的是 {}。如果确认服务器已重装或更换了密钥，请先删除旧记录（用户 known_hosts 可执行 `ssh-keygen -R \"{}\"`）后再重试。",
+                target,
+                self.algorithm,
+                self.fingerprint,
+                source,
+                self.known_fingerprint.as_deref().unwrap_or("-"),
+                target
+            )),
+            HostKeyStatus::Revoked => Some(format!(
+                "远程主机 {} 出示的 {} 密钥（{}）已在 {} 中被标记为 @revoked，已拒绝连接。",
+                target, self.algorithm, self.fingerprint, source
+            )),
+        }
+    }
+}
+
+/// known_hosts 中的一行有效记录
+#[derive(Debug, Clone)]
+struct KnownHostEntry {
+    marker: Option<KnownHostMarker>,
+    patterns: String,
+    key: PublicKey,
+    line: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KnownHostMarker {
+    CertAuthority,
+    Revoked,
+}
+
+/// 解析 known_hosts 内容。无法解析的行（不支持的密钥类型、损坏的行）直接跳过，
+/// 不能让一行坏数据导致整个校验失败。
+fn parse_known_hosts(content: &str) -> Vec<KnownHostEntry> {
+    let mut entries = Vec::new();
+    for (index, raw_line) in content.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let Some(mut first) = fields.next() else {
+            continue;
+        };
+        let marker = match first {
+            "@cert-authority" => Some(KnownHostMarker::CertAuthority),
+            "@revoked" => Some(KnownHostMarker::Revoked),
+            _ => None,
+        };
+        if marker.is_some() {
+            let Some(patterns) = fields.next() else {
+                continue;
+            };
+            first = patterns;
+        }
+        let (Some(_key_type), Some(key_base64)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let Ok(key) = russh::keys::parse_public_key_base64(key_base64) else {
+            continue;
+        };
+        entries.push(KnownHostEntry {
+            marker,
+            patterns: first.to_string(),
+            key,
+            line: index + 1,
+        });
+    }
+    entries
+}
+
+/// OpenSSH 对非 22 端口使用 `[host]:port` 形式
+fn host_port_pattern(host: &str, port: u16) -> String {
+    if port == 22 {
+        host.to_string()
+    } else {
+        format!("[{}]:{}", host, port)
+    }
+}
+
+/// 判断 known_hosts 的主机字段是否匹配目标主机。
+/// 取反模式（`!pattern`）命中时整行都不匹配。
+fn patterns_match(patterns: &str, host: &str, port: u16) -> bool {
+    let target = host_port_pattern(host, port);
+    let mut matched = false;
+    for pattern in patterns.split(',') {
+        if let Some(hashed) = pattern.strip_prefix("|1|") {
+            if hashed_host_matches(hashed, &target) {
+                matched = true;
+            }
+            continue;
+        }
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        if wildcard_match(&pattern.to_ascii_lowercase(), &target.to_ascii_lowercase()) {
+            if negated {
+                return false;
+            }
+            matched = true;
+        }
+    }
+    matched
+}
+
+/// `|1|base64(salt)|base64(HMAC-SHA1(salt, host))`
+fn hashed_host_matches(hashed: &str, target: &str) -> bool {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let Some((salt, hash)) = hashed.split_once('|') else {
+        return false;
+    };
+    let (Ok(salt), Ok(hash)) = (engine.decode(salt), engine.decode(hash)) else {
+        return false;
+    };
+    let Ok(mac) = Hmac::<Sha1>::new_from_slice(&salt) else {
+        return false;
+    };
+    mac.chain_update(target.as_bytes())
+        .verify_slice(&hash)
+        .is_ok()
+}
+
+/// 支持 `*` 与 `?` 的主机名通配符匹配
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    while p < pattern.len() && pattern[p] == '*' {
+        p += 1;
+    }
+    p == pattern.len()
+}
+
+fn same_key_family(left: &Algorithm, right: &Algorithm) -> bool {
+    if matches!(left, Algorithm::Rsa { .. }) && matches!(right, Algorithm::Rsa { .. }) {
+        return true;
+    }
+    left == right
+}
+
+pub fn key_fingerprint(key: &PublicKey) -> String {
+    key.fingerprint(HashAlg::Sha256).to_string()
+}
+
+/// 主机密钥校验器：按顺序读取多个 known_hosts，只向 `store` 写入新密钥
+#[derive(Debug, Clone)]
+pub struct HostKeyVerifier {
+    sources: Vec<PathBuf>,
+    store: Option<PathBuf>,
+}
+
+impl HostKeyVerifier {
+    /// 默认来源：用户 `~/.ssh/known_hosts` + 应用自管文件
+    pub fn from_default_locations() -> Self {
+        let store = app_known_hosts_path();
+        let mut sources: Vec<PathBuf> = user_known_hosts_path().into_iter().collect();
+        sources.extend(store.clone());
+        Self { sources, store }
+    }
+
+    pub fn with_paths(sources: Vec<PathBuf>, store: Option<PathBuf>) -> Self {
+        Self { sources, store }
+    }
+
+    fn matching_entries(&self, host: &str, port: u16) -> Vec<(PathBuf, KnownHostEntry)> {
+        let mut matches = Vec::new();
+        for source in &self.sources {
+            let Ok(content) = fs::read_to_string(source) else {
+                continue;
+            };
+            for entry in parse_known_hosts(&content) {
+                if patterns_match(&entry.patterns, host, port) {
+                    matches.push((source.clone(), entry));
+                }
+            }
+        }
+        matches
+    }
+
+    /// 已记录的主机密钥算法，用于握手时优先协商这些算法，
+    /// 避免服务器同时有多种主机密钥时因协商到未记录的类型而误判
+    pub fn known_algorithms(&self, host: &str, port: u16) -> Vec<Algorithm> {
+        let mut algorithms: Vec<Algorithm> = Vec::new();
+        for (_, entry) in self.matching_entries(host, port) {
+            if entry.marker.is_some() {
+                continue;
+            }
+            let algorithm = entry.key.algorithm();
+            if !algorithms.contains(&algorithm) {
+                algorithms.push(algorithm);
+            }
+        }
+        algorithms
+    }
+
+    /// 校验服务器密钥。未知主机只有在 `trusted_fingerprint`（用户已确认的指纹）
+    /// 与之一致时才记录，否则返回 `Unconfirmed` 供用户确认
+    pub fn check(
+        &self,
+        host: &str,
+        port: u16,
+        key: &PublicKey,
+        trusted_fingerprint: Option<&str>,
+    ) -> HostKeyCheck {
+        let mut check = HostKeyCheck {
+            host: host.to_string(),
+            port,
+            status: HostKeyStatus::Learned,
+            algorithm: key.algorithm().as_str().to_string(),
+            fingerprint: key_fingerprint(key),
+            source: None,
+            known_fingerprint: None,
+        };
+
+        let entries = self.matching_entries(host, port);
+        let describe = |source: &Path, line: usize| format!("{}:{}", source.display(), line);
+
+        if let Some((source, entry)) = entries.iter().find(|(_, entry)| {
+            entry.marker == Some(KnownHostMarker::Revoked) && entry.key.key_data() == key.key_data()
+        }) {
+            check.status = HostKeyStatus::Revoked;
+            check.source = Some(describe(source, entry.line));
+            return check;
+        }
+
+        let plain: Vec<&(PathBuf, KnownHostEntry)> = entries
+            .iter()
+            .filter(|(_, entry)| entry.marker.is_none())
+            .collect();
+
+        if let Some((source, entry)) = plain
+            .iter()
+            .find(|(_, entry)| entry.key.key_data() == key.key_data())
+        {
+            check.status = HostKeyStatus::Known;
+            check.source = Some(describe(source, entry.line));
+            return check;
+        }
+
+        // 同类型密钥不一致：典型的主机密钥变更
+        let changed = plain
+            .iter()
+            .find(|(_, entry)| same_key_family(&entry.key.algorithm(), &key.algorithm()))
+            // 记录了其他类型但服务器不再提供：按变更处理，而不是悄悄接受新类型
+            .or_else(|| plain.first());
+        if let Some((source, entry)) = changed {
+            check.status = HostKeyStatus::Changed;
+            check.source = Some(describe(source, entry.line));
+            check.known_fingerprint = Some(key_fingerprint(&entry.key));
+            return check;
+        }
+
+        if trusted_fingerprint != Some(check.fingerprint.as_str()) {
+            check.status = HostKeyStatus::Unconfirmed;
+            return check;
+        }
+
+        match self.learn(host, port, key) {
+            Ok(Some(store)) => {
+                log::info!(
+                    "SSH 首次连接 {}，已记录 {} 主机密钥 {} 到 {}",
+                    host_port_pattern(host, port),
+                    check.algorithm,
+                    check.fingerprint,
+                    store.display()
+                );
+                check.source = Some(store.display().to_string());
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("记录 SSH 主机密钥失败（本次仍然接受）: {}", e),
+        }
+        check
+    }
+
+    /// 把主机密钥追加到应用自管 known_hosts
+    fn learn(&self, host: &str, port: u16, key: &PublicKey) -> Result<Option<PathBuf>, String> {
+        let Some(store) = &self.store else {
+            return Ok(None);
+        };
+        if let Some(parent) = store.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+        }
+        let encoded = key
+            .to_openssh()
+            .map_err(|e| format!("编码主机密钥失败: {}", e))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(store)
+            .map_err(|e| format!("打开 {} 失败: {}", store.display(), e))?;
+        let needs_newline = fs::read(store)
+            .map(|content| !content.is_empty() && !content.ends_with(b"\n"))
+            .unwrap_or(false);
+        let mut line = String::new();
+        if needs_newline {
+            line.push('\n');
+        }
+        line.push_str(&host_port_pattern(host, port));
+        line.push(' ');
+        line.push_str(encoded.trim());
+        line.push('\n');
+        file.write_all(line.as_bytes())
+            .map_err(|e| format!("写入 {} 失败: {}", store.display(), e))?;
+        Ok(Some(store.clone()))
+    }
+
+    /// 删除应用自管 known_hosts 中该主机的记录（不会修改用户的 ~/.ssh/known_hosts），
+    /// 返回删除的行数
+    pub fn forget(&self, host: &str, port: u16) -> Result<usize, String> {
+        let Some(store) = &self.store else {
+            return Ok(0);
+        };
+        let Ok(content) = fs::read_to_string(store) else {
+            return Ok(0);
+        };
+        let mut removed = 0;
+        let kept: Vec<&str> = content
+            .lines()
+            .filter(|line| {
+                let trimmed = line.trim();
+                if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('@') {
+                    return true;
+                }
+                let patterns = trimmed.split_whitespace().next().unwrap_or_default();
+                let matched = patterns_match(patterns, host, port);
+                if matched {
+                    removed += 1;
+                }
+                !matched
+            })
+            .collect();
+        if removed > 0 {
+            let mut output = kept.join("\n");
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            fs::write(store, output).map_err(|e| format!("写入 {} 失败: {}", store.display(), e))?;
+        }
+        Ok(removed)
+    }
+}
+
+/// 把已记录的主机密钥算法排到协商列表最前面
+pub fn preferred_host_key_algorithms(known: &[Algorithm], defaults: &[Algorithm]) -> Vec<Algorithm> {
+    let (mut preferred, rest): (Vec<Algorithm>, Vec<Algorithm>) = defaults
+        .iter()
+        .cloned()
+        .partition(|candidate| known.iter().any(|known| same_key_family(known, candidate)));
+    preferred.extend(rest);
+    preferred
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_A: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIOkwDJ2nBE+kl2VMJdvgmlIY43CW/k2UWbmOWKEDoXIG";
+    const KEY_B: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIH7afIBruTwoJGNUOVTdSkzJRYs93WouGcyuIdoNhCh2";
+    // HMAC-SHA1 with salt 0x00..0x13, generated with OpenSSH's `|1|` scheme
+    const HASHED_EXAMPLE: &str = "|1|AAECAwQFBgcICQoLDA0ODxAREhM=|nnUK16ANsXd3hL31YfAkGOluSjU=";
+    const HASHED_EXAMPLE_2222: &str =
+        "|1|AAECAwQFBgcICQoLDA0ODxAREhM=|Wgcx+Fm+LmaWwC7rQ80eIf2uHe0=";
+
+    fn key(base64: &str) -> PublicKey {
+        russh::keys::parse_public_key_base64(base64).expect("key")
+    }
+
+    fn verifier_with(content: &str) -> (tempfile::TempDir, HostKeyVerifier) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let user = dir.path().join("known_hosts");
+        fs::write(&user, content).expect("write");
+        let store = dir.path().join("app").join(APP_KNOWN_HOSTS_FILE);
+        let verifier = HostKeyVerifier::with_paths(vec![user, store.clone()], Some(store));
+        (dir, verifier)
+    }
+
+    #[test]
+    fn fingerprint_matches_ssh_keygen_output() {
+        assert_eq!(
+            key_fingerprint(&key(KEY_A)),
+            "SHA256:se5SHzwQSGYL74+92QRQKUrQjVc8lAaYAXqgq04+bMo"
+        );
+    }
+
+    #[test]
+    fn hashed_entries_match_host_and_port() {
+        assert!(patterns_match(HASHED_EXAMPLE, "example.com", 22));
+        assert!(!patterns_match(HASHED_EXAMPLE, "example.org", 22));
+        assert!(patterns_match(HASHED_EXAMPLE_2222, "example.com", 2222));
+        assert!(!patterns_match(HASHED_EXAMPLE_2222, "example.com", 22));
+    }
+
+    #[test]
+    fn plain_patterns_support_wildcards_negation_and_ports() {
+        assert!(patterns_match("*.corp.example,10.0.0.?", "build.corp.example", 22));
+        assert!(patterns_match("*.corp.example,10.0.0.?", "10.0.0.7", 22));
+        assert!(!patterns_match("*.corp.example,!secret.corp.example", "secret.corp.example", 22));
+        assert!(patterns_match("[devbox]:2200", "devbox", 2200));
+        assert!(!patterns_match("devbox", "devbox", 2200));
+        assert!(patterns_match("DevBox", "devbox", 22));
+    }
+
+    #[test]
+    fn parser_skips_comments_markers_and_broken_lines() {
+        let content = format!(
+            "# comment\n\nhost-a ssh-ed25519 {KEY_A}\nhost-b ssh-unknown AAAA\n@revoked host-c ssh-ed25519 {KEY_B}\nbroken-line\n"
+        );
+        let entries = parse_known_hosts(&content);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].line, 3);
+        assert_eq!(entries[1].marker, Some(KnownHostMarker::Revoked));
+        assert_eq!(entries[1].line, 5);
+    }
+
+    #[test]
+    fn known_key_is_accepted_without_writing() {
+        let (_dir, verifier) = verifier_with(&format!("{HASHED_EXAMPLE} ssh-ed25519 {KEY_A}\n"));
+        let check = verifier.check("example.com", 22, &key(KEY_A), None);
+        assert_eq!(check.status, HostKeyStatus::Known);
+        assert!(check.accepted());
+        assert!(!verifier.store.as_ref().unwrap().exists());
+    }
+
+    #[test]
+    fn changed_key_is_rejected_with_both_fingerprints() {
+        let (_dir, verifier) = verifier_with(&format!("example.com ssh-ed25519 {KEY_A}\n"));
+        let check = verifier.check("example.com", 22, &key(KEY_B), None);
+        assert_eq!(check.status, HostKeyStatus::Changed);
+        assert!(!check.accepted());
+        assert_eq!(
+            check.known_fingerprint.as_deref(),
+            Some("SHA256:se5SHzwQSGYL74+92QRQKUrQjVc8lAaYAXqgq04+bMo")
+        );
+        let message = check.rejection_message().expect("message");
+        assert!(message.contains(&check.fingerprint));
+        assert!(message.contains("known_hosts:1"));
+    }
+
+    #[test]
+    fn revoked_key_is_rejected_even_when_also_listed() {
+        let (_dir, verifier) = verifier_with(&format!(
+            "example.com ssh-ed25519 {KEY_A}\n@revoked * ssh-ed25519 {KEY_A}\n"
+        ));
+        let check = verifier.check("example.com", 22, &key(KEY_A), None);
+        assert_eq!(check.status, HostKeyStatus::Revoked);
+    }
+
+    #[test]
+    fn unknown_host_is_pinned_only_after_its_fingerprint_is_confirmed() {
+        let (_dir, verifier) = verifier_with("");
+        let unconfirmed = verifier.check("devbox", 2200, &key(KEY_A), None);
+        assert_eq!(unconfirmed.status, HostKeyStatus::Unconfirmed);
+        assert!(!unconfirmed.accepted());
+        assert!(unconfirmed
+            .rejection_message()
+            .expect("message")
+            .contains(&unconfirmed.fingerprint));
+        assert!(!verifier.store.as_ref().unwrap().exists());
+
+        let other_key = key_fingerprint(&key(KEY_B));
+        let mismatched = verifier.check("devbox", 2200, &key(KEY_A), Some(&other_key));
+        assert_eq!(mismatched.status, HostKeyStatus::Unconfirmed);
+
+        let first = verifier.check("devbox", 2200, &key(KEY_A), Some(&unconfirmed.fingerprint));
+        assert_eq!(first.status, HostKeyStatus::Learned);
+        assert!(first.accepted());
+
+        let second = verifier.check("devbox", 2200, &key(KEY_A), None);
+        assert_eq!(second.status, HostKeyStatus::Known);
+
+        let swapped = verifier.check("devbox", 2200, &key(KEY_B), Some(&other_key));
+        assert_eq!(swapped.status, HostKeyStatus::Changed);
+
+        assert_eq!(verifier.forget("devbox", 2200).expect("forget"), 1);
+        let relearned = verifier.check("devbox", 2200, &key(KEY_B), Some(&other_key));
+        assert_eq!(relearned.status, HostKeyStatus::Learned);
+    }
+
+    #[test]
+    fn known_algorithms_are_moved_to_the_front() {
+        let defaults = vec![
+            Algorithm::Ed25519,
+            Algorithm::Rsa {
+                hash: Some(HashAlg::Sha512),
+            },
+            Algorithm::Rsa { hash: None },
+        ];
+        let known = vec![Algorithm::Rsa { hash: None }];
+        let preferred = preferred_host_key_algorithms(&known, &defaults);
+        assert_eq!(
+            preferred,
+            vec![
+                Algorithm::Rsa {
+                    hash: Some(HashAlg::Sha512),
+                },
+                Algorithm::Rsa { hash: None },
+                Algorithm::Ed25519,
+            ]
+        );
+    }
+}
@@ -1,6 +1,7 @@
 mod adapter;
 mod commands;
 pub mod key_file;
+pub mod known_hosts;
 mod mcp_sync;
 mod session;
 mod skills_sync;
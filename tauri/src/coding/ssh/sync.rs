@@ -64,10 +64,13 @@ fn pattern_file_display_path(expanded_pattern: &str, file_path: &Path) -> String
 // ============================================================================
 
 /// 测试 SSH 连接（独立短连接，不复用主连接）
-/// 用于测试未保存的连接配置
-pub async fn test_connection(conn: &SSHConnection) -> SSHConnectionResult {
-    match session::test_connection_with_command(conn, "uname -a").await {
-        Ok(output) => {
+/// 用于测试未保存的连接配置；`trusted_fingerprint` 为用户已确认的首次连接主机密钥指纹
+pub async fn test_connection(
+    conn: &SSHConnection,
+    trusted_fingerprint: Option<&str>,
+) -> SSHConnectionResult {
+    match session::test_connection_with_command(conn, "uname -a", trusted_fingerprint).await {
+        Ok((output, host_key_check)) => {
             let server_info = output.trim().to_string();
             SSHConnectionResult {
                 connected: true,
@@ -77,12 +80,14 @@ pub async fn test_connection(conn: &SSHConnection) -> SSHConnectionResult {
                 } else {
                     Some(server_info)
                 },
+                host_key: Some(host_key_check),
             }
         }
         Err(e) => SSHConnectionResult {
             connected: false,
-            error: Some(e),
+            error: Some(e.message),
             server_info: None,
+            host_key: e.host_key,
         },
     }
 }
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 // Re-use SyncResult and SyncProgress from wsl module
 pub use super::super::wsl::{SyncProgress, SyncResult};
+use super::known_hosts::HostKeyCheck;
 use crate::coding::runtime_location::WslDirectModuleStatus;
 
 pub const DEFAULT_DIRECTORY_EXCLUDES: &[&str] = &[
@@ -208,6 +209,9 @@ pub struct SSHConnectionResult {
     pub connected: bool,
     pub error: Option<String>,
     pub server_info: Option<String>,
+    /// 主机密钥校验结果，首次连接（status = learned）时前端展示指纹
+    #[serde(default)]
+    pub host_key: Option<HostKeyCheck>,
 }
 
 /// SSH status result
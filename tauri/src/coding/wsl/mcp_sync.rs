@@ -507,6 +507,7 @@ mod tests {
             }),
             enabled_tools: vec![],
             sync_details: None,
+            probe_result: None,
             description: None,
             user_group: None,
             user_note: None,
@@ -307,6 +307,11 @@ pub fn sync_file_mapping(mapping: &FileMapping, distro: &str) -> Result<Vec<Stri
         if !Path::new(&windows_path).exists() {
             return Ok(vec![]);
         }
+        // Credential files (auth.json, settings.json, .env) must not carry
+        // `secret://` references from a locked secret store into WSL.
+        if let Ok(content) = std::fs::read_to_string(&windows_path) {
+            crate::secret_store::ensure_resolved(&content)?;
+        }
         sync_single_file(&windows_path, &mapping.wsl_path, distro)
     }
 }
@@ -18,7 +18,7 @@ pub fn db_list(
         "SELECT id, json(data) AS data_json, created_at, updated_at FROM {table_name}{}",
         order.map(OrderSpec::to_sql).unwrap_or_default()
     );
-    query_rows(conn, &sql, &[])
+    query_table_rows(conn, table_name, &sql, &[])
 }
 
 pub fn db_put(conn: &Connection, table: DbTable, id: &str, data: &Value) -> Result<(), String> {
@@ -39,6 +39,7 @@ pub fn db_create(conn: &Connection, table: DbTable, data: &Value) -> Result<Valu
 
 pub fn db_delete(conn: &Connection, table: DbTable, id: &str) -> Result<bool, String> {
     let table_name = table.name();
+    crate::secret_store::forget_record(conn, table_name, id)?;
     let affected = conn
         .execute(&format!("DELETE FROM {table_name} WHERE id = ?1"), [id])
         .map_err(|error| format!("Failed to delete from {table_name}: {error}"))?;
@@ -47,6 +48,7 @@ pub fn db_delete(conn: &Connection, table: DbTable, id: &str) -> Result<bool, St
 
 pub fn db_delete_all(conn: &Connection, table: DbTable) -> Result<usize, String> {
     let table_name = table.name();
+    crate::secret_store::forget_table(conn, table_name)?;
     conn.execute(&format!("DELETE FROM {table_name}"), [])
         .map_err(|error| format!("Failed to delete all records from {table_name}: {error}"))
 }
@@ -80,28 +82,28 @@ pub fn db_query_by_field(
         Value::Null => {
             sql.push_str(&format!("{field_expr} IS NULL"));
             append_order_and_limit(&mut sql, order, limit);
-            query_rows(conn, &sql, &[])
+            query_table_rows(conn, table_name, &sql, &[])
         }
         Value::Bool(value) => {
             let expected_integer = if *value { 1_i64 } else { 0_i64 };
             sql.push_str(&format!("CAST({field_expr} AS INTEGER) = ?1"));
             append_order_and_limit(&mut sql, order, limit);
-            query_rows(conn, &sql, &[&expected_integer])
+            query_table_rows(conn, table_name, &sql, &[&expected_integer])
         }
         Value::Number(number) => {
             if let Some(value) = number.as_i64() {
                 sql.push_str(&format!("CAST({field_expr} AS INTEGER) = ?1"));
                 append_order_and_limit(&mut sql, order, limit);
-                query_rows(conn, &sql, &[&value])
+                query_table_rows(conn, table_name, &sql, &[&value])
             } else if let Some(value) = number.as_u64().and_then(|value| i64::try_from(value).ok())
             {
                 sql.push_str(&format!("CAST({field_expr} AS INTEGER) = ?1"));
                 append_order_and_limit(&mut sql, order, limit);
-                query_rows(conn, &sql, &[&value])
+                query_table_rows(conn, table_name, &sql, &[&value])
             } else if let Some(value) = number.as_f64() {
                 sql.push_str(&format!("CAST({field_expr} AS REAL) = ?1"));
                 append_order_and_limit(&mut sql, order, limit);
-                query_rows(conn, &sql, &[&value])
+                query_table_rows(conn, table_name, &sql, &[&value])
             } else {
                 Err("Unsupported JSON number value".to_string())
             }
@@ -109,7 +111,7 @@ pub fn db_query_by_field(
         Value::String(value) => {
             sql.push_str(&format!("{field_expr} = ?1"));
             append_order_and_limit(&mut sql, order, limit);
-            query_rows(conn, &sql, &[value])
+            query_table_rows(conn, table_name, &sql, &[value])
         }
         Value::Array(_) | Value::Object(_) => {
             let expected_json = serde_json::to_string(expected).map_err(|error| {
@@ -117,7 +119,7 @@ pub fn db_query_by_field(
             })?;
             sql.push_str(&format!("json({field_expr}) = json(?1)"));
             append_order_and_limit(&mut sql, order, limit);
-            query_rows(conn, &sql, &[&expected_json])
+            query_table_rows(conn, table_name, &sql, &[&expected_json])
         }
     }
 }
@@ -297,7 +299,11 @@ fn db_get_from_table(
         .optional()
         .map_err(|error| format!("Failed to read record from {table_name}: {error}"))?;
 
-    row.map(row_tuple_to_value).transpose()
+    let mut record = row.map(row_tuple_to_value).transpose()?;
+    if let Some(record) = record.as_mut() {
+        crate::secret_store::reveal_record(conn, table_name, record);
+    }
+    Ok(record)
 }
 
 fn db_put_into_table(
@@ -336,6 +342,7 @@ fn db_put_into_table(
     if !has_json_updated_at {
         stored_object.insert("updated_at".to_string(), Value::String(updated_at.clone()));
     }
+    crate::secret_store::seal_record(conn, table_name, id, &mut stored_data)?;
     let data_json = serde_json::to_string(&stored_data)
         .map_err(|error| format!("Failed to serialize JSON payload for {table_name}: {error}"))?;
 
@@ -370,6 +377,19 @@ fn json_timestamp_to_column_string(value: &Value) -> Option<String> {
     }
 }
 
+fn query_table_rows(
+    conn: &Connection,
+    table_name: &str,
+    sql: &str,
+    params: &[&dyn ToSql],
+) -> Result<Vec<Value>, String> {
+    let mut records = query_rows(conn, sql, params)?;
+    for record in &mut records {
+        crate::secret_store::reveal_record(conn, table_name, record);
+    }
+    Ok(records)
+}
+
 fn query_rows(conn: &Connection, sql: &str, params: &[&dyn ToSql]) -> Result<Vec<Value>, String> {
     let mut statement = conn
         .prepare(sql)
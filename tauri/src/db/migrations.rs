@@ -2,7 +2,7 @@ use rusqlite::Connection;
 
 use super::schema::{sql_string_literal, DbTable, JsonFieldPath, ALL_TABLES};
 
-pub const TARGET_SCHEMA_VERSION: i32 = 14;
+pub const TARGET_SCHEMA_VERSION: i32 = 22;
 const FUTURE_SCHEMA_ERROR_PREFIX: &str = "AI_TOOLBOX_SQLITE_SCHEMA_TOO_NEW";
 
 pub fn run_all(conn: &mut Connection) -> Result<(), String> {
@@ -50,6 +50,30 @@ pub fn run_all(conn: &mut Connection) -> Result<(), String> {
     if current_version < 14 {
         run_migration_step(conn, 14, migrate_v14)?;
     }
+    if current_version < 15 {
+        run_migration_step(conn, 15, migrate_v15)?;
+    }
+    if current_version < 16 {
+        run_migration_step(conn, 16, migrate_v16)?;
+    }
+    if current_version < 17 {
+        run_migration_step(conn, 17, migrate_v17)?;
+    }
+    if current_version < 18 {
+        run_migration_step(conn, 18, migrate_v18)?;
+    }
+    if current_version < 19 {
+        run_migration_step(conn, 19, migrate_v19)?;
+    }
+    if current_version < 20 {
+        run_migration_step(conn, 20, migrate_v20)?;
+    }
+    if current_version < 21 {
+        run_migration_step(conn, 21, migrate_v21)?;
+    }
+    if current_version < 22 {
+        run_migration_step(conn, 22, migrate_v22)?;
+    }
 
     Ok(())
 }
@@ -321,6 +345,118 @@ fn migrate_v14(conn: &Connection) -> Result<(), String> {
     .map_err(|error| format!("Failed to create proxy gateway stream outcome index: {error}"))
 }
 
+fn migrate_v15(conn: &Connection) -> Result<(), String> {
+    // Encrypted credentials referenced from other records (see `secret_store`).
+    create_jsonb_table(conn, DbTable::SecretValue)?;
+    create_json_index(
+        conn,
+        DbTable::SecretValue,
+        &JsonFieldPath::new("owner_table")?,
+    )?;
+    create_json_index(conn, DbTable::SecretValue, &JsonFieldPath::new("owner_id")?)
+}
+
+fn migrate_v16(conn: &Connection) -> Result<(), String> {
+    // Gateway-issued client tokens; requests authenticated with one carry its
+    // label into the request log so per-client usage can be broken out.
+    create_jsonb_table(conn, DbTable::ProxyGatewayAccessToken)?;
+    add_column_if_missing(conn, "proxy_request_logs", "client_label", "TEXT")?;
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_request_logs_client_label
+            ON proxy_request_logs(client_label);",
+    )
+    .map_err(|error| format!("Failed to create proxy gateway client label index: {error}"))
+}
+
+fn migrate_v17(conn: &Connection) -> Result<(), String> {
+    create_jsonb_table(conn, DbTable::ProxyGatewayBudgetRule)
+}
+
+fn migrate_v18(conn: &Connection) -> Result<(), String> {
+    // Existing rows keep their flat prices as the base tier. NULL tiers mark a
+    // row whose tiers and fees were never set, so the next seed or remote
+    // pricing sync fills them; rows saved from the editor store `[]` instead.
+    add_column_if_missing(conn, "model_pricing", "pricing_tiers", "TEXT")?;
+    add_column_if_missing(conn, "model_pricing", "reasoning_cost_per_million", "TEXT")?;
+    add_column_if_missing(conn, "model_pricing", "image_cost", "TEXT")?;
+    add_column_if_missing(conn, "model_pricing", "request_cost", "TEXT")?;
+    add_column_if_missing(conn, "model_pricing", "batch_discount_percent", "TEXT")
+}
+
+fn migrate_v19(conn: &Connection) -> Result<(), String> {
+    // Session search index: one document row per transcript, keyed by tool and
+    // source path, with the searchable text in a trigram FTS5 table sharing its
+    // rowid. `source_stamp` lets refreshes skip transcripts that did not change.
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS session_search_documents (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            tool TEXT NOT NULL,
+            source_path TEXT NOT NULL,
+            source_stamp INTEGER NOT NULL,
+            provider_id TEXT NOT NULL,
+            session_id TEXT NOT NULL,
+            title TEXT,
+            summary TEXT,
+            project_dir TEXT,
+            models TEXT NOT NULL DEFAULT '',
+            created_at INTEGER,
+            last_active_at INTEGER,
+            resume_command TEXT,
+            runtime_source TEXT,
+            runtime_distro TEXT,
+            indexed_at TEXT NOT NULL,
+            UNIQUE (tool, source_path)
+        );
+        CREATE INDEX IF NOT EXISTS idx_session_search_documents_last_active
+            ON session_search_documents(last_active_at DESC);
+        CREATE VIRTUAL TABLE IF NOT EXISTS session_search_fts USING fts5(
+            title,
+            content,
+            tokenize = 'trigram'
+        );",
+    )
+    .map_err(|error| format!("Failed to create session search index tables: {error}"))
+}
+
+fn migrate_v20(conn: &Connection) -> Result<(), String> {
+    // Requests answered from the gateway response cache are logged with zero
+    // cost; the flag keeps them distinguishable from free upstream calls, and
+    // daily rollups keep the count once the logs are pruned.
+    add_column_if_missing(
+        conn,
+        "proxy_request_logs",
+        "cache_hit",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    add_column_if_missing(
+        conn,
+        "usage_daily_rollups",
+        "cache_hit_count",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_v21(conn: &Connection) -> Result<(), String> {
+    // Registered repositories with their own MCP servers and skills, written to
+    // project-level tool files instead of the user-level ones.
+    create_jsonb_table(conn, DbTable::ProjectWorkspace)
+}
+
+fn migrate_v22(conn: &Connection) -> Result<(), String> {
+    // Transcripts cut at the indexing cap are flagged so content searches can
+    // rescan them. Earlier rows can't tell truncation or a failed load apart
+    // from a short transcript, so their stamps are reset to force a reindex.
+    add_column_if_missing(
+        conn,
+        "session_search_documents",
+        "content_truncated",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    conn.execute("UPDATE session_search_documents SET source_stamp = -1", [])
+        .map(|_| ())
+        .map_err(|error| format!("Failed to reset session search index stamps: {error}"))
+}
+
 fn create_jsonb_table(conn: &Connection, table: DbTable) -> Result<(), String> {
     let table_name = table.name();
     conn.execute_batch(&format!(
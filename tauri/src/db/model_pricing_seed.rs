@@ -8,6 +8,10 @@ use rusqlite::{params, Connection};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use crate::coding::proxy_gateway::pricing::{
+    normalize_batch_discount, normalize_optional_cost, normalize_pricing_tiers,
+};
+use crate::coding::proxy_gateway::types::ModelPricingTier;
 use crate::db::SqliteDbState;
 use crate::http_client;
 
@@ -25,6 +29,16 @@ struct ModelPricingSeedItem {
     output_cost_per_million: String,
     cache_read_cost_per_million: String,
     cache_creation_cost_per_million: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tiers: Vec<ModelPricingTier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reasoning_cost_per_million: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    image_cost: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_cost: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    batch_discount_percent: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -178,11 +192,29 @@ fn parse_pricing_items(
             "cache_creation_cost_per_million",
             &item.cache_creation_cost_per_million,
         )?;
+        normalize_extended_pricing(item).map_err(|error| {
+            format!(
+                "{source_label} has invalid pricing for {}: {error}",
+                item.model_id
+            )
+        })?;
     }
 
     Ok(pricing_items)
 }
 
+fn normalize_extended_pricing(item: &mut ModelPricingSeedItem) -> Result<(), String> {
+    item.tiers = normalize_pricing_tiers(std::mem::take(&mut item.tiers))?;
+    item.reasoning_cost_per_million = normalize_optional_cost(
+        "reasoning_cost_per_million",
+        item.reasoning_cost_per_million.take(),
+    )?;
+    item.image_cost = normalize_optional_cost("image_cost", item.image_cost.take())?;
+    item.request_cost = normalize_optional_cost("request_cost", item.request_cost.take())?;
+    item.batch_discount_percent = normalize_batch_discount(item.batch_discount_percent.take())?;
+    Ok(())
+}
+
 fn validate_cost(
     source_label: &str,
     model_id: &str,
@@ -217,14 +249,33 @@ fn insert_pricing_items(
         .prepare(
             "INSERT OR IGNORE INTO model_pricing (
                 model_id, display_name, input_cost_per_million, output_cost_per_million,
-                cache_read_cost_per_million, cache_creation_cost_per_million
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                cache_read_cost_per_million, cache_creation_cost_per_million,
+                pricing_tiers, reasoning_cost_per_million, image_cost, request_cost,
+                batch_discount_percent
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
         )
         .map_err(|error| format!("Failed to prepare {source_label} seed statement: {error}"))?;
+    // Existing rows keep their flat prices (they may be user edits). Rows
+    // created before tiered pricing existed have NULL tiers and pick up the
+    // tiers and fees here; rows saved from the editor store `[]` or their own
+    // tiers and are left alone, so deliberately cleared tiers stay cleared.
+    let mut fill_statement = conn
+        .prepare(
+            "UPDATE model_pricing SET
+                pricing_tiers = ?2,
+                reasoning_cost_per_million = COALESCE(reasoning_cost_per_million, ?3),
+                image_cost = COALESCE(image_cost, ?4),
+                request_cost = COALESCE(request_cost, ?5),
+                batch_discount_percent = COALESCE(batch_discount_percent, ?6)
+             WHERE model_id = ?1 AND pricing_tiers IS NULL",
+        )
+        .map_err(|error| format!("Failed to prepare {source_label} fill statement: {error}"))?;
 
     let mut inserted_count = 0;
     for item in pricing_items {
-        inserted_count += statement
+        let tiers_json = serde_json::to_string(&item.tiers)
+            .map_err(|error| format!("Failed to serialize pricing tiers: {error}"))?;
+        let inserted = statement
             .execute(params![
                 item.model_id,
                 item.display_name,
@@ -232,6 +283,11 @@ fn insert_pricing_items(
                 item.output_cost_per_million,
                 item.cache_read_cost_per_million,
                 item.cache_creation_cost_per_million,
+                tiers_json,
+                item.reasoning_cost_per_million,
+                item.image_cost,
+                item.request_cost,
+                item.batch_discount_percent,
             ])
             .map_err(|error| {
                 format!(
@@ -239,6 +295,24 @@ fn insert_pricing_items(
                     item.model_id
                 )
             })?;
+        if inserted == 0 {
+            fill_statement
+                .execute(params![
+                    item.model_id,
+                    tiers_json,
+                    item.reasoning_cost_per_million,
+                    item.image_cost,
+                    item.request_cost,
+                    item.batch_discount_percent,
+                ])
+                .map_err(|error| {
+                    format!(
+                        "Failed to update model pricing {} from {source_label}: {error}",
+                        item.model_id
+                    )
+                })?;
+        }
+        inserted_count += inserted;
     }
 
     Ok(inserted_count)
@@ -277,7 +351,12 @@ mod tests {
                 input_cost_per_million TEXT NOT NULL DEFAULT '0',
                 output_cost_per_million TEXT NOT NULL DEFAULT '0',
                 cache_read_cost_per_million TEXT NOT NULL DEFAULT '0',
-                cache_creation_cost_per_million TEXT NOT NULL DEFAULT '0'
+                cache_creation_cost_per_million TEXT NOT NULL DEFAULT '0',
+                pricing_tiers TEXT,
+                reasoning_cost_per_million TEXT,
+                image_cost TEXT,
+                request_cost TEXT,
+                batch_discount_percent TEXT
             );",
         )
         .expect("create model_pricing table");
@@ -360,6 +439,111 @@ mod tests {
         assert_eq!(preserved_input, "9");
     }
 
+    #[test]
+    fn seed_fills_tiers_on_existing_rows_without_touching_flat_prices() {
+        let conn = Connection::open_in_memory().expect("sqlite");
+        create_test_table(&conn);
+        conn.execute(
+            "INSERT INTO model_pricing (model_id, display_name, input_cost_per_million)
+             VALUES ('tiered-model', 'Tiered Model', '9')",
+            [],
+        )
+        .expect("insert legacy row");
+
+        let json = r#"[
+          {
+            "model_id": "tiered-model",
+            "display_name": "Tiered Model",
+            "input_cost_per_million": "3",
+            "output_cost_per_million": "15",
+            "cache_read_cost_per_million": "0.3",
+            "cache_creation_cost_per_million": "3.75",
+            "tiers": [
+              {
+                "above_input_tokens": 200000,
+                "input_cost_per_million": "6",
+                "output_cost_per_million": "22.5"
+              }
+            ],
+            "request_cost": "0.001"
+          }
+        ]"#;
+
+        assert_eq!(seed_from_json_str(&conn, json, "test pricing").unwrap(), 0);
+        let (input, tiers, request_cost): (String, String, Option<String>) = conn
+            .query_row(
+                "SELECT input_cost_per_million, pricing_tiers, request_cost
+                 FROM model_pricing WHERE model_id = 'tiered-model'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .expect("query filled row");
+        assert_eq!(input, "9");
+        assert!(tiers.contains("\"above_input_tokens\":200000"));
+        assert_eq!(request_cost.as_deref(), Some("0.001"));
+
+        // Tiers and fees the user cleared in the editor are not refilled.
+        conn.execute(
+            "UPDATE model_pricing SET pricing_tiers = '[]', request_cost = NULL
+             WHERE model_id = 'tiered-model'",
+            [],
+        )
+        .expect("clear tiers");
+        assert_eq!(seed_from_json_str(&conn, json, "test pricing").unwrap(), 0);
+        let (tiers, request_cost): (String, Option<String>) = conn
+            .query_row(
+                "SELECT pricing_tiers, request_cost
+                 FROM model_pricing WHERE model_id = 'tiered-model'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("query cleared row");
+        assert_eq!(tiers, "[]");
+        assert_eq!(request_cost, None);
+    }
+
+    #[test]
+    fn seed_fills_reasoning_image_and_batch_prices() {
+        let conn = Connection::open_in_memory().expect("sqlite");
+        create_test_table(&conn);
+        conn.execute(
+            "INSERT INTO model_pricing (model_id, display_name, input_cost_per_million)
+             VALUES ('reasoning-model', 'Reasoning Model', '2')",
+            [],
+        )
+        .expect("insert legacy row");
+
+        let json = r#"[
+          {
+            "model_id": "reasoning-model",
+            "display_name": "Reasoning Model",
+            "input_cost_per_million": "2",
+            "output_cost_per_million": "8",
+            "cache_read_cost_per_million": "0.5",
+            "cache_creation_cost_per_million": "0",
+            "reasoning_cost_per_million": "12",
+            "image_cost": "0.002",
+            "batch_discount_percent": "50"
+          }
+        ]"#;
+
+        assert_eq!(seed_from_json_str(&conn, json, "test pricing").unwrap(), 0);
+        let (reasoning, image, batch): (Option<String>, Option<String>, Option<String>) = conn
+            .query_row(
+                "SELECT reasoning_cost_per_million, image_cost, batch_discount_percent
+                 FROM model_pricing WHERE model_id = 'reasoning-model'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .expect("query filled row");
+        assert_eq!(reasoning.as_deref(), Some("12"));
+        assert_eq!(image.as_deref(), Some("0.002"));
+        assert_eq!(batch.as_deref(), Some("50"));
+
+        let invalid = json.replace("\"50\"", "\"150\"");
+        assert!(seed_from_json_str(&conn, &invalid, "test pricing").is_err());
+    }
+
     #[test]
     fn seed_rejects_duplicate_model_ids() {
         let json = r#"[
@@ -57,6 +57,10 @@ pub enum DbTable {
     ImageChannel,
     ImageJob,
     ImageAsset,
+    SecretValue,
+    ProxyGatewayAccessToken,
+    ProxyGatewayBudgetRule,
+    ProjectWorkspace,
 }
 
 pub const ALL_TABLES: &[DbTable] = &[
@@ -115,6 +119,10 @@ pub const ALL_TABLES: &[DbTable] = &[
     DbTable::ImageChannel,
     DbTable::ImageJob,
     DbTable::ImageAsset,
+    DbTable::SecretValue,
+    DbTable::ProxyGatewayAccessToken,
+    DbTable::ProxyGatewayBudgetRule,
+    DbTable::ProjectWorkspace,
 ];
 
 impl DbTable {
@@ -175,6 +183,10 @@ impl DbTable {
             DbTable::ImageChannel => "image_channel",
             DbTable::ImageJob => "image_job",
             DbTable::ImageAsset => "image_asset",
+            DbTable::SecretValue => "secret_value",
+            DbTable::ProxyGatewayAccessToken => "proxy_gateway_access_token",
+            DbTable::ProxyGatewayBudgetRule => "proxy_gateway_budget_rule",
+            DbTable::ProjectWorkspace => "project_workspace",
         }
     }
 }
@@ -202,6 +202,23 @@ pub fn create_client_no_proxy(timeout_secs: u64) -> Result<Client, String> {
         .map_err(|e| format!("Failed to create HTTP client: {}", e))
 }
 
+/// Create an HTTP client for an already resolved proxy mode.
+///
+/// For callers that mirror another component's proxy configuration, such as
+/// the skills archive fetcher which follows the git proxy mode.
+///
+/// # Arguments
+/// * `proxy_mode` - Proxy mode to apply
+/// * `proxy_url` - Proxy URL, only used when proxy_mode is custom
+/// * `timeout_secs` - Request timeout in seconds
+pub fn client_for_proxy_mode(
+    proxy_mode: ProxyMode,
+    proxy_url: &str,
+    timeout_secs: u64,
+) -> Result<Client, String> {
+    build_client(proxy_mode, proxy_url, Some(timeout_secs), true)
+}
+
 /// Test proxy connectivity by making a request to a test URL.
 ///
 /// This function is used by the settings page to validate proxy configuration.
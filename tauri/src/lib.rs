@@ -24,9 +24,11 @@ use std::sync::Mutex as StdMutex;
 
 // Module declarations
 pub mod auto_launch;
+pub(crate) mod aws_sigv4;
 pub mod coding;
 pub mod db;
 pub mod http_client;
+pub mod secret_store;
 pub mod settings;
 pub mod single_instance;
 pub mod tray;
@@ -917,6 +919,15 @@ pub fn run() {
             db::model_pricing_seed::set_cache_dir(app_data_dir.clone());
             info!("模型定价缓存目录已初始化");
 
+            // App-managed SSH known_hosts (accept-new host key pinning)
+            coding::ssh::known_hosts::set_store_dir(app_data_dir.clone());
+
+            // Encrypted credential store (keyring / key file / passphrase)
+            secret_store::set_store_dir(app_data_dir.clone());
+            if let Err(e) = secret_store::init() {
+                error!("密钥库初始化失败，凭据引用将保持未解析: {}", e);
+            }
+
             let sqlite_db_path = app_data_dir.join(db::SQLITE_DATABASE_FILE);
             info!("正在初始化 SQLite 主数据库: {:?}", sqlite_db_path);
             let db_state = match SqliteDbState::open(sqlite_db_path) {
@@ -984,6 +995,7 @@ pub fn run() {
                 info!("SQLite 主数据库状态已注册到应用");
 
                 app.manage(coding::proxy_gateway::ProxyGatewayState::default());
+                app.manage(coding::proxy_gateway::MockUpstreamState::default());
                 info!("网关状态已注册到应用");
 
                 // Deep-link (`aitoolbox://`) provider import: register state and
@@ -1819,10 +1831,17 @@ pub fn run() {
             coding::proxy_gateway::proxy_gateway_status,
             coding::proxy_gateway::proxy_gateway_health_check,
             coding::proxy_gateway::proxy_gateway_check_port_available,
+            coding::proxy_gateway::proxy_gateway_list_access_tokens,
+            coding::proxy_gateway::proxy_gateway_create_access_token,
+            coding::proxy_gateway::proxy_gateway_delete_access_token,
+            coding::proxy_gateway::proxy_gateway_budget_statuses,
+            coding::proxy_gateway::proxy_gateway_save_budget_rule,
+            coding::proxy_gateway::proxy_gateway_delete_budget_rule,
             coding::proxy_gateway::proxy_gateway_cli_statuses,
             coding::proxy_gateway::proxy_gateway_cli_status,
             coding::proxy_gateway::proxy_gateway_engage_single,
             coding::proxy_gateway::proxy_gateway_engage_failover,
+            coding::proxy_gateway::proxy_gateway_engage_load_balance,
             coding::proxy_gateway::proxy_gateway_disengage_failover,
             coding::proxy_gateway::proxy_gateway_restore_cli_direct,
             coding::proxy_gateway::proxy_gateway_switch_primary_provider,
@@ -1830,11 +1849,19 @@ pub fn run() {
             coding::proxy_gateway::proxy_gateway_request_logs,
             coding::proxy_gateway::proxy_gateway_request_log_detail,
             coding::proxy_gateway::proxy_gateway_export_request_log_detail,
+            coding::proxy_gateway::proxy_gateway_list_fixture_captures,
+            coding::proxy_gateway::proxy_gateway_replay_fixture_capture,
+            coding::proxy_gateway::proxy_gateway_response_cache_stats,
+            coding::proxy_gateway::proxy_gateway_clear_response_cache,
+            coding::proxy_gateway::proxy_gateway_mock_upstream_status,
+            coding::proxy_gateway::proxy_gateway_mock_upstream_start,
+            coding::proxy_gateway::proxy_gateway_mock_upstream_stop,
             coding::proxy_gateway::proxy_gateway_usage_summary,
             coding::proxy_gateway::proxy_gateway_usage_summary_by_cli,
             coding::proxy_gateway::proxy_gateway_usage_trends,
             coding::proxy_gateway::proxy_gateway_provider_stats,
             coding::proxy_gateway::proxy_gateway_model_stats,
+            coding::proxy_gateway::proxy_gateway_client_stats,
             coding::proxy_gateway::proxy_gateway_data_source_breakdown,
             coding::proxy_gateway::proxy_gateway_import_session_usage,
             coding::proxy_gateway::proxy_gateway_test_provider_model_connectivity,
@@ -1843,6 +1870,12 @@ pub fn run() {
             coding::proxy_gateway::delete_model_pricing,
             coding::proxy_gateway::fetch_remote_model_pricing,
             coding::proxy_gateway::proxy_gateway_model_health_entries,
+            // Secret store
+            secret_store::secret_store_status,
+            secret_store::secret_store_enable,
+            secret_store::secret_store_unlock,
+            secret_store::secret_store_lock,
+            secret_store::secret_store_disable,
             // Backup - Local
             settings::backup::backup_database,
             settings::backup::restore_database,
@@ -1854,6 +1887,11 @@ pub fn run() {
             settings::backup::restore_from_webdav,
             settings::backup::test_webdav_connection,
             settings::backup::delete_webdav_backup,
+            settings::backup::backup_to_s3,
+            settings::backup::list_s3_backups,
+            settings::backup::restore_from_s3,
+            settings::backup::test_s3_connection,
+            settings::backup::delete_s3_backup,
             // Claude Code
             coding::claude_code::list_claude_providers,
             coding::claude_code::create_claude_provider,
@@ -2018,16 +2056,23 @@ pub fn run() {
             coding::open_code::reorder_opencode_prompt_configs,
             coding::open_code::save_opencode_local_prompt_config,
             coding::session_manager::list_tool_sessions,
+            coding::session_manager::search_tool_sessions,
+            coding::session_manager::get_tool_session_analytics,
+            coding::session_manager::get_tool_session_usage,
             coding::session_manager::list_tool_session_paths,
             coding::session_manager::get_tool_session_detail,
             coding::session_manager::list_tool_session_subagents,
             coding::session_manager::get_tool_subagent_session_detail,
             coding::session_manager::delete_tool_session,
             coding::session_manager::delete_tool_sessions,
+            coding::session_manager::list_session_trash,
+            coding::session_manager::restore_session_trash,
+            coding::session_manager::purge_session_trash,
             coding::session_manager::export_tool_session,
             coding::session_manager::export_tool_sessions,
             coding::session_manager::import_tool_session,
             coding::session_manager::rename_tool_session,
+            coding::session_manager::convert_tool_session,
             coding::all_api_hub::has_all_api_hub_extension,
             coding::all_api_hub::get_all_api_hub_provider_models,
             coding::cc_switch::has_cc_switch_db,
@@ -2292,6 +2337,7 @@ pub fn run() {
             coding::wsl::wsl_open_folder,
             // SSH Sync
             coding::ssh::ssh_test_connection,
+            coding::ssh::ssh_forget_host_key,
             coding::ssh::ssh_get_config,
             coding::ssh::ssh_save_config,
             coding::ssh::ssh_list_connections,
@@ -2329,6 +2375,19 @@ pub fn run() {
             coding::skills::skills_unsync_from_tool,
             coding::skills::skills_update_managed,
             coding::skills::skills_update_all,
+            coding::skills::skills_set_pin,
+            coding::skills::skills_check_updates,
+            coding::skills::skills_preview_update,
+            coding::skills::skills_accept_update,
+            coding::skills::skills_export_lockfile,
+            coding::skills::skills_export_lockfile_file,
+            coding::skills::skills_apply_lockfile,
+            coding::skills::skills_apply_lockfile_file,
+            coding::skills::skills_scan_safety,
+            coding::skills::skills_get_safety_policy,
+            coding::skills::skills_set_safety_policy,
+            coding::skills::skills_lint,
+            coding::skills::skills_lint_all,
             coding::skills::skills_get_auto_update,
             coding::skills::skills_set_auto_update,
             coding::skills::skills_preview_auto_update_schedule,
@@ -2378,6 +2437,8 @@ pub fn run() {
             // MCP Servers
             coding::mcp::mcp_list_servers,
             coding::mcp::mcp_resolve_package_versions,
+            coding::mcp::mcp_probe_server,
+            coding::mcp::mcp_probe_servers,
             coding::mcp::mcp_create_server,
             coding::mcp::mcp_update_server,
             coding::mcp::mcp_delete_server,
@@ -2404,6 +2465,13 @@ pub fn run() {
             coding::mcp::mcp_upsert_favorite,
             coding::mcp::mcp_delete_favorite,
             coding::mcp::mcp_init_default_favorites,
+            // Project Workspaces
+            coding::project_workspace::project_workspace_list,
+            coding::project_workspace::project_workspace_get_tool_targets,
+            coding::project_workspace::project_workspace_save,
+            coding::project_workspace::project_workspace_delete,
+            coding::project_workspace::project_workspace_sync,
+            coding::project_workspace::project_workspace_check_drift,
             // Image
             coding::image::image_get_workspace,
             coding::image::image_list_channels,
@@ -0,0 +1,242 @@
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::Engine;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+pub const KEY_LEN: usize = 32;
+pub const NONCE_LEN: usize = 12;
+pub const SALT_LEN: usize = 16;
+
+/// 256-bit master key. Wiped from memory when dropped.
+#[derive(Clone)]
+pub struct MasterKey([u8; KEY_LEN]);
+
+impl MasterKey {
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; KEY_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    pub fn from_base64(encoded: &str) -> Result<Self, String> {
+        let mut decoded = base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|error| format!("Invalid secret store key encoding: {error}"))?;
+        if decoded.len() != KEY_LEN {
+            decoded.zeroize();
+            return Err(format!(
+                "Invalid secret store key length: expected {KEY_LEN} bytes"
+            ));
+        }
+        let mut bytes = [0u8; KEY_LEN];
+        bytes.copy_from_slice(&decoded);
+        decoded.zeroize();
+        Ok(Self(bytes))
+    }
+
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.0)
+    }
+}
+
+impl Drop for MasterKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl std::fmt::Debug for MasterKey {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str("MasterKey(..)")
+    }
+}
+
+/// Argon2id cost parameters persisted next to the salt so existing vaults keep
+/// unlocking if the defaults are raised later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    /// OWASP baseline for Argon2id.
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+pub fn random_salt() -> [u8; SALT_LEN] {
+    let mut salt = [0u8; SALT_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+pub fn derive_passphrase_key(
+    passphrase: &str,
+    salt: &[u8],
+    params: KdfParams,
+) -> Result<MasterKey, String> {
+    if passphrase.is_empty() {
+        return Err("Secret store passphrase cannot be empty".to_string());
+    }
+    let argon_params = Params::new(
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        Some(KEY_LEN),
+    )
+    .map_err(|error| format!("Invalid Argon2 parameters: {error}"))?;
+    let mut bytes = [0u8; KEY_LEN];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, argon_params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut bytes)
+        .map_err(|error| format!("Failed to derive secret store key: {error}"))?;
+    Ok(MasterKey(bytes))
+}
+
+/// AES-256-GCM ciphertext with its nonce, both base64 encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedBox {
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// Encrypt `plaintext`, binding it to `aad` so a ciphertext cannot be moved to
+/// another slot without failing authentication.
+pub fn seal(key: &MasterKey, plaintext: &[u8], aad: &[u8]) -> Result<SealedBox, String> {
+    let (nonce, ciphertext) = seal_bytes(key, plaintext, aad)?;
+    let engine = base64::engine::general_purpose::STANDARD;
+    Ok(SealedBox {
+        nonce: engine.encode(nonce),
+        ciphertext: engine.encode(ciphertext),
+    })
+}
+
+pub fn open(key: &MasterKey, sealed: &SealedBox, aad: &[u8]) -> Result<Vec<u8>, String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let nonce = engine
+        .decode(&sealed.nonce)
+        .map_err(|error| format!("Invalid secret nonce: {error}"))?;
+    let ciphertext = engine
+        .decode(&sealed.ciphertext)
+        .map_err(|error| format!("Invalid secret ciphertext: {error}"))?;
+    open_bytes(key, &nonce, &ciphertext, aad)
+}
+
+/// Raw form of [`seal`] for large payloads that should not be base64 encoded.
+pub fn seal_bytes(
+    key: &MasterKey,
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<([u8; NONCE_LEN], Vec<u8>), String> {
+    let cipher = Aes256Gcm::new_from_slice(&key.0)
+        .map_err(|error| format!("Invalid secret store key: {error}"))?;
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| "Failed to encrypt secret".to_string())?;
+    Ok((nonce, ciphertext))
+}
+
+pub fn open_bytes(
+    key: &MasterKey,
+    nonce: &[u8],
+    ciphertext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, String> {
+    if nonce.len() != NONCE_LEN {
+        return Err("Invalid secret nonce length".to_string());
+    }
+    let cipher = Aes256Gcm::new_from_slice(&key.0)
+        .map_err(|error| format!("Invalid secret store key: {error}"))?;
+    cipher
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| "Failed to decrypt secret: wrong key or corrupted data".to_string())
+}
+
+pub fn open_string(key: &MasterKey, sealed: &SealedBox, aad: &[u8]) -> Result<String, String> {
+    String::from_utf8(open(key, sealed, aad)?)
+        .map_err(|_| "Decrypted secret is not valid UTF-8".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_params() -> KdfParams {
+        KdfParams {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        }
+    }
+
+    #[test]
+    fn sealed_value_round_trips_and_is_bound_to_aad() {
+        let key = MasterKey::generate();
+        let sealed = seal(&key, b"sk-live-123", b"claude_provider:a:settings_config").unwrap();
+        assert_ne!(sealed.ciphertext, "sk-live-123");
+        assert_eq!(
+            open_string(&key, &sealed, b"claude_provider:a:settings_config").unwrap(),
+            "sk-live-123"
+        );
+        assert!(open(&key, &sealed, b"claude_provider:b:settings_config").is_err());
+        assert!(open(
+            &MasterKey::generate(),
+            &sealed,
+            b"claude_provider:a:settings_config"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn nonces_are_unique_per_seal() {
+        let key = MasterKey::generate();
+        let first = seal(&key, b"same", b"aad").unwrap();
+        let second = seal(&key, b"same", b"aad").unwrap();
+        assert_ne!(first.nonce, second.nonce);
+        assert_ne!(first.ciphertext, second.ciphertext);
+    }
+
+    #[test]
+    fn passphrase_derivation_is_deterministic_per_salt() {
+        let salt = random_salt();
+        let key = derive_passphrase_key("correct horse", &salt, fast_params()).unwrap();
+        let again = derive_passphrase_key("correct horse", &salt, fast_params()).unwrap();
+        let other_salt =
+            derive_passphrase_key("correct horse", &random_salt(), fast_params()).unwrap();
+        assert_eq!(key.to_base64(), again.to_base64());
+        assert_ne!(key.to_base64(), other_salt.to_base64());
+        assert!(derive_passphrase_key("", &salt, fast_params()).is_err());
+    }
+
+    #[test]
+    fn key_base64_round_trip_rejects_wrong_length() {
+        let key = MasterKey::generate();
+        let restored = MasterKey::from_base64(&key.to_base64()).unwrap();
+        assert_eq!(key.to_base64(), restored.to_base64());
+        assert!(MasterKey::from_base64("c2hvcnQ=").is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use crate::db::SqliteDbState;
+
+use super::key_source::KeySourceKind;
+use super::SecretStoreStatus;
+
+#[tauri::command]
+pub async fn secret_store_status(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+) -> Result<SecretStoreStatus, String> {
+    sqlite_state.with_conn(super::status)
+}
+
+/// Enable the store and seal existing credentials. Returns the number of
+/// records that were sealed.
+#[tauri::command]
+pub async fn secret_store_enable(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    key_source: String,
+    passphrase: Option<String>,
+) -> Result<usize, String> {
+    let kind = KeySourceKind::parse(&key_source)?;
+    sqlite_state.with_conn_mut(|conn| super::enable(conn, kind, passphrase.as_deref()))
+}
+
+#[tauri::command]
+pub async fn secret_store_unlock(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    passphrase: String,
+) -> Result<usize, String> {
+    sqlite_state.with_conn_mut(|conn| super::unlock(conn, &passphrase))
+}
+
+#[tauri::command]
+pub async fn secret_store_lock() -> Result<(), String> {
+    super::lock()
+}
+
+/// Write credentials back as plaintext and remove the key material.
+#[tauri::command]
+pub async fn secret_store_disable(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+) -> Result<usize, String> {
+    sqlite_state.with_conn_mut(super::disable)
+}
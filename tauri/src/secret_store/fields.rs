@@ -0,0 +1,586 @@
+use rusqlite::Connection;
+use serde_json::Value;
+
+use super::cipher::MasterKey;
+use super::vault::{self, EntryOwner};
+use crate::db::schema::DbTable;
+
+/// A credential inside a JSONB record.
+pub enum SecretField {
+    /// Top-level string field, e.g. `password`
+    Plain(&'static str),
+    /// String at a JSON pointer inside the record, e.g. `/webdav/password`
+    Nested(&'static str),
+    /// String field holding serialized JSON; the secrets are the string values
+    /// at the given JSON pointers, e.g. `settings_config` -> `/env/ANTHROPIC_AUTH_TOKEN`
+    Embedded {
+        field: &'static str,
+        pointers: &'static [&'static str],
+    },
+}
+
+const CLAUDE_SETTINGS: &[SecretField] = &[SecretField::Embedded {
+    field: "settings_config",
+    pointers: &[
+        "/env/ANTHROPIC_AUTH_TOKEN",
+        "/env/ANTHROPIC_API_KEY",
+        "/env/AWS_SECRET_ACCESS_KEY",
+        "/env/AWS_SESSION_TOKEN",
+    ],
+}];
+const CODEX_SETTINGS: &[SecretField] = &[SecretField::Embedded {
+    field: "settings_config",
+    pointers: &["/auth/OPENAI_API_KEY"],
+}];
+const GEMINI_CLI_SETTINGS: &[SecretField] = &[SecretField::Embedded {
+    field: "settings_config",
+    pointers: &["/env/GEMINI_API_KEY", "/env/GOOGLE_API_KEY"],
+}];
+const GROK_SETTINGS: &[SecretField] = &[SecretField::Embedded {
+    field: "settings_config",
+    pointers: &["/auth/API_KEY"],
+}];
+const OFFICIAL_ACCOUNT: &[SecretField] = &[SecretField::Plain("auth_snapshot")];
+const SSH_CONNECTION: &[SecretField] = &[
+    SecretField::Plain("password"),
+    SecretField::Plain("passphrase"),
+    SecretField::Plain("private_key_content"),
+];
+const IMAGE_CHANNEL: &[SecretField] = &[SecretField::Plain("api_key")];
+const OPENCODE_FAVORITE_PROVIDER: &[SecretField] =
+    &[SecretField::Nested("/provider_config/options/apiKey")];
+const SETTINGS: &[SecretField] = &[
+    SecretField::Plain("backup_encryption_passphrase"),
+    SecretField::Nested("/webdav/password"),
+    SecretField::Nested("/s3/access_key"),
+    SecretField::Nested("/s3/secret_key"),
+];
+
+/// Tables whose records carry credentials.
+///
+/// Hermes, OpenClaw and Pi are not listed: their tables only hold config
+/// paths and prompts, and their provider credentials are edited in place in
+/// the tools' own config files (`config.yaml`, `openclaw.json`,
+/// `auth.json`/`models.json`) without ever being stored in SQLite.
+pub const SECRET_TABLES: &[DbTable] = &[
+    DbTable::ClaudeProvider,
+    DbTable::ClaudeDesktopProvider,
+    DbTable::CodexProvider,
+    DbTable::GeminiCliProvider,
+    DbTable::GrokProvider,
+    DbTable::CodexOfficialAccount,
+    DbTable::GeminiCliOfficialAccount,
+    DbTable::GrokOfficialAccount,
+    DbTable::SshConnection,
+    DbTable::ImageChannel,
+    DbTable::OpenCodeFavoriteProvider,
+    DbTable::Settings,
+];
+
+pub fn secret_fields(table_name: &str) -> &'static [SecretField] {
+    match table_name {
+        "claude_provider" | "claude_desktop_provider" => CLAUDE_SETTINGS,
+        "codex_provider" => CODEX_SETTINGS,
+        "gemini_cli_provider" => GEMINI_CLI_SETTINGS,
+        "grok_provider" => GROK_SETTINGS,
+        "codex_official_account" | "gemini_cli_official_account" | "grok_official_account" => {
+            OFFICIAL_ACCOUNT
+        }
+        "ssh_connection" => SSH_CONNECTION,
+        "image_channel" => IMAGE_CHANNEL,
+        "opencode_favorite_provider" => OPENCODE_FAVORITE_PROVIDER,
+        "settings" => SETTINGS,
+        _ => &[],
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum KeyState<'a> {
+    /// No secret store configured: records keep plaintext
+    Disabled,
+    /// Store configured but the master key is not loaded
+    Locked,
+    Unlocked(&'a MasterKey),
+}
+
+/// Move plaintext credentials of `data` into the vault, leaving references.
+/// Returns whether `data` changed.
+pub fn seal_record(
+    conn: &Connection,
+    key: KeyState<'_>,
+    table_name: &str,
+    record_id: &str,
+    data: &mut Value,
+) -> Result<bool, String> {
+    let fields = secret_fields(table_name);
+    if fields.is_empty() || matches!(key, KeyState::Disabled) {
+        return Ok(false);
+    }
+    let mut sealer = |label: &str, value: &str| {
+        let owner = EntryOwner {
+            table: table_name,
+            record_id,
+            field: label,
+        };
+        seal_slot(conn, key, &owner, value)
+    };
+    transform_record(fields, data, &mut sealer)
+}
+
+/// Replace references in `data` with their plaintext. Slots that cannot be
+/// resolved (locked store, missing or undecryptable entry) keep the reference.
+pub fn reveal_record(
+    conn: &Connection,
+    key: KeyState<'_>,
+    table_name: &str,
+    data: &mut Value,
+) -> bool {
+    let fields = secret_fields(table_name);
+    let KeyState::Unlocked(master_key) = key else {
+        return false;
+    };
+    if fields.is_empty() {
+        return false;
+    }
+    let mut revealer = |_label: &str, value: &str| -> Result<Option<String>, String> {
+        let Some(entry_id) = vault::parse_reference(value) else {
+            return Ok(None);
+        };
+        match vault::read(conn, master_key, entry_id) {
+            Ok(Some(plaintext)) => Ok(Some(plaintext)),
+            Ok(None) => {
+                log::warn!("Secret entry '{entry_id}' referenced by {table_name} is missing");
+                Ok(None)
+            }
+            Err(error) => {
+                log::warn!("Failed to resolve secret entry '{entry_id}': {error}");
+                Ok(None)
+            }
+        }
+    };
+    transform_record(fields, data, &mut revealer).unwrap_or(false)
+}
+
+fn entry_id(owner: &EntryOwner<'_>) -> String {
+    format!("{}:{}:{}", owner.table, owner.record_id, owner.field)
+}
+
+fn seal_slot(
+    conn: &Connection,
+    key: KeyState<'_>,
+    owner: &EntryOwner<'_>,
+    value: &str,
+) -> Result<Option<String>, String> {
+    let entry_id = entry_id(owner);
+    if value.is_empty() {
+        vault::delete(conn, &entry_id)?;
+        return Ok(None);
+    }
+
+    if let Some(referenced_id) = vault::parse_reference(value) {
+        if referenced_id == entry_id {
+            return Ok(None);
+        }
+        // A reference copied from another record (duplicate, import). Give this
+        // record its own entry so deleting the source does not orphan it.
+        let KeyState::Unlocked(master_key) = key else {
+            return Ok(None);
+        };
+        let Some(plaintext) = vault::read(conn, master_key, referenced_id)? else {
+            return Ok(None);
+        };
+        vault::put(conn, master_key, &entry_id, owner, &plaintext)?;
+        return Ok(Some(vault::reference_for(&entry_id)));
+    }
+
+    match key {
+        KeyState::Disabled => Ok(None),
+        KeyState::Locked => {
+            Err("The secret store is locked; unlock it before saving credentials".to_string())
+        }
+        KeyState::Unlocked(master_key) => {
+            vault::put(conn, master_key, &entry_id, owner, value)?;
+            Ok(Some(vault::reference_for(&entry_id)))
+        }
+    }
+}
+
+/// Maps `(slot label, current value)` to a replacement value, if any.
+type SlotTransform<'a> = dyn FnMut(&str, &str) -> Result<Option<String>, String> + 'a;
+
+/// Apply `transform(label, value)` to every secret slot of `data`, writing back
+/// the values it returns. Embedded JSON is re-serialized only when changed,
+/// keeping pretty-printing if the original had it.
+fn transform_record(
+    fields: &[SecretField],
+    data: &mut Value,
+    transform: &mut SlotTransform<'_>,
+) -> Result<bool, String> {
+    if !data.is_object() {
+        return Ok(false);
+    }
+    let mut changed = false;
+
+    for field in fields {
+        match field {
+            SecretField::Plain(name) => {
+                let Some(object) = data.as_object_mut() else {
+                    continue;
+                };
+                let Some(Value::String(current)) = object.get(*name) else {
+                    continue;
+                };
+                if let Some(next) = transform(name, current)? {
+                    object.insert((*name).to_string(), Value::String(next));
+                    changed = true;
+                }
+            }
+            SecretField::Nested(pointer) => {
+                let Some(Value::String(current)) = data.pointer(pointer) else {
+                    continue;
+                };
+                let label = pointer.trim_start_matches('/');
+                if let Some(next) = transform(label, current)? {
+                    if let Some(slot) = data.pointer_mut(pointer) {
+                        *slot = Value::String(next);
+                        changed = true;
+                    }
+                }
+            }
+            SecretField::Embedded { field, pointers } => {
+                let Some(object) = data.as_object_mut() else {
+                    continue;
+                };
+                let Some(Value::String(raw)) = object.get(*field) else {
+                    continue;
+                };
+                let Ok(mut embedded) = serde_json::from_str::<Value>(raw) else {
+                    continue;
+                };
+                let pretty = raw.contains('\n');
+                let mut embedded_changed = false;
+                for pointer in pointers.iter() {
+                    let Some(Value::String(current)) = embedded.pointer(pointer) else {
+                        continue;
+                    };
+                    let label = format!("{field}{pointer}");
+                    if let Some(next) = transform(&label, current)? {
+                        if let Some(slot) = embedded.pointer_mut(pointer) {
+                            *slot = Value::String(next);
+                            embedded_changed = true;
+                        }
+                    }
+                }
+                if embedded_changed {
+                    let serialized = if pretty {
+                        serde_json::to_string_pretty(&embedded)
+                    } else {
+                        serde_json::to_string(&embedded)
+                    }
+                    .map_err(|error| format!("Failed to serialize {field}: {error}"))?;
+                    object.insert((*field).to_string(), Value::String(serialized));
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::helpers::db_count;
+    use crate::db::SqliteDbState;
+    use serde_json::json;
+
+    fn with_conn(test: impl FnOnce(&Connection)) {
+        let state = SqliteDbState::in_memory_for_test().unwrap();
+        state
+            .with_conn(|conn| {
+                test(conn);
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn ssh_connection_secrets_round_trip_through_the_vault() {
+        with_conn(|conn| {
+            let key = MasterKey::generate();
+            let mut record = json!({
+                "name": "prod",
+                "password": "pa55",
+                "passphrase": "",
+                "private_key_content": "-----BEGIN KEY-----",
+            });
+            let changed = seal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "ssh_connection",
+                "c1",
+                &mut record,
+            )
+            .unwrap();
+            assert!(changed);
+            assert_eq!(record["password"], "secret://ssh_connection:c1:password");
+            assert_eq!(record["passphrase"], "");
+            assert_eq!(
+                record["private_key_content"],
+                "secret://ssh_connection:c1:private_key_content"
+            );
+            assert_eq!(db_count(conn, DbTable::SecretValue).unwrap(), 2);
+
+            // Sealing an already sealed record is a no-op.
+            let mut again = record.clone();
+            assert!(!seal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "ssh_connection",
+                "c1",
+                &mut again
+            )
+            .unwrap());
+
+            assert!(reveal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "ssh_connection",
+                &mut record
+            ));
+            assert_eq!(record["password"], "pa55");
+            assert_eq!(record["private_key_content"], "-----BEGIN KEY-----");
+        });
+    }
+
+    #[test]
+    fn embedded_provider_keys_are_sealed_inside_settings_config() {
+        with_conn(|conn| {
+            let key = MasterKey::generate();
+            let settings = "{\n  \"env\": {\n    \"ANTHROPIC_AUTH_TOKEN\": \"sk-ant\",\n    \"ANTHROPIC_BASE_URL\": \"https://api.example.com\"\n  }\n}";
+            let mut record = json!({ "name": "p", "settings_config": settings });
+            seal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "claude_provider",
+                "p1",
+                &mut record,
+            )
+            .unwrap();
+
+            let sealed: Value =
+                serde_json::from_str(record["settings_config"].as_str().unwrap()).unwrap();
+            assert_eq!(
+                sealed["env"]["ANTHROPIC_AUTH_TOKEN"],
+                "secret://claude_provider:p1:settings_config/env/ANTHROPIC_AUTH_TOKEN"
+            );
+            assert_eq!(
+                sealed["env"]["ANTHROPIC_BASE_URL"],
+                "https://api.example.com"
+            );
+
+            reveal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "claude_provider",
+                &mut record,
+            );
+            assert_eq!(record["settings_config"], settings);
+        });
+    }
+
+    #[test]
+    fn bedrock_credentials_are_sealed_inside_settings_config() {
+        with_conn(|conn| {
+            let key = MasterKey::generate();
+            let settings = json!({
+                "env": {
+                    "AWS_ACCESS_KEY_ID": "AKID",
+                    "AWS_SECRET_ACCESS_KEY": "aws-secret",
+                    "AWS_SESSION_TOKEN": "aws-session",
+                }
+            })
+            .to_string();
+            let mut record = json!({ "name": "p", "settings_config": settings });
+            seal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "claude_provider",
+                "p1",
+                &mut record,
+            )
+            .unwrap();
+
+            let sealed: Value =
+                serde_json::from_str(record["settings_config"].as_str().unwrap()).unwrap();
+            assert_eq!(
+                sealed["env"]["AWS_SECRET_ACCESS_KEY"],
+                "secret://claude_provider:p1:settings_config/env/AWS_SECRET_ACCESS_KEY"
+            );
+            assert_eq!(
+                sealed["env"]["AWS_SESSION_TOKEN"],
+                "secret://claude_provider:p1:settings_config/env/AWS_SESSION_TOKEN"
+            );
+            assert_eq!(sealed["env"]["AWS_ACCESS_KEY_ID"], "AKID");
+
+            reveal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "claude_provider",
+                &mut record,
+            );
+            assert_eq!(record["settings_config"], settings);
+        });
+    }
+
+    #[test]
+    fn disabled_store_leaves_plaintext_and_locked_store_refuses_new_secrets() {
+        with_conn(|conn| {
+            let mut record = json!({ "api_key": "sk-img" });
+            assert!(
+                !seal_record(conn, KeyState::Disabled, "image_channel", "i1", &mut record).unwrap()
+            );
+            assert_eq!(record["api_key"], "sk-img");
+
+            let error = seal_record(conn, KeyState::Locked, "image_channel", "i1", &mut record)
+                .unwrap_err();
+            assert!(error.contains("locked"));
+
+            // Existing references pass through a locked store untouched.
+            let mut sealed = json!({ "api_key": "secret://image_channel:i1:api_key" });
+            assert!(
+                !seal_record(conn, KeyState::Locked, "image_channel", "i1", &mut sealed).unwrap()
+            );
+            assert!(!reveal_record(
+                conn,
+                KeyState::Locked,
+                "image_channel",
+                &mut sealed
+            ));
+        });
+    }
+
+    #[test]
+    fn copied_reference_gets_its_own_entry_and_cleared_secret_drops_entry() {
+        with_conn(|conn| {
+            let key = MasterKey::generate();
+            let mut source = json!({ "api_key": "sk-source" });
+            seal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "image_channel",
+                "a",
+                &mut source,
+            )
+            .unwrap();
+
+            let mut copy = source.clone();
+            seal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "image_channel",
+                "b",
+                &mut copy,
+            )
+            .unwrap();
+            assert_eq!(copy["api_key"], "secret://image_channel:b:api_key");
+            vault::delete_owner(conn, "image_channel", "a").unwrap();
+            reveal_record(conn, KeyState::Unlocked(&key), "image_channel", &mut copy);
+            assert_eq!(copy["api_key"], "sk-source");
+
+            let mut cleared = json!({ "api_key": "" });
+            seal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "image_channel",
+                "b",
+                &mut cleared,
+            )
+            .unwrap();
+            assert_eq!(db_count(conn, DbTable::SecretValue).unwrap(), 0);
+        });
+    }
+
+    #[test]
+    fn wrong_key_keeps_reference_instead_of_leaking_garbage() {
+        with_conn(|conn| {
+            let key = MasterKey::generate();
+            let mut record = json!({ "auth_snapshot": "{\"tokens\":{}}" });
+            seal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "codex_official_account",
+                "o1",
+                &mut record,
+            )
+            .unwrap();
+            let other = MasterKey::generate();
+            assert!(!reveal_record(
+                conn,
+                KeyState::Unlocked(&other),
+                "codex_official_account",
+                &mut record
+            ));
+            assert!(vault::is_reference(
+                record["auth_snapshot"].as_str().unwrap()
+            ));
+        });
+    }
+
+    #[test]
+    fn nested_backup_and_favorite_provider_credentials_are_sealed() {
+        with_conn(|conn| {
+            let key = MasterKey::generate();
+            let mut settings = json!({
+                "language": "en-US",
+                "webdav": { "url": "https://dav.example.com", "password": "dav-pw" },
+                "s3": { "bucket": "b", "access_key": "AKIA", "secret_key": "s3-secret" },
+            });
+            assert!(seal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "settings",
+                "app",
+                &mut settings
+            )
+            .unwrap());
+            assert_eq!(
+                settings["webdav"]["password"],
+                "secret://settings:app:webdav/password"
+            );
+            assert_eq!(settings["s3"]["secret_key"], "secret://settings:app:s3/secret_key");
+            assert_eq!(settings["webdav"]["url"], "https://dav.example.com");
+
+            let mut favorite = json!({
+                "provider_id": "acme",
+                "provider_config": { "npm": "@ai-sdk/openai", "options": { "apiKey": "sk-fav" } },
+            });
+            seal_record(
+                conn,
+                KeyState::Unlocked(&key),
+                "opencode_favorite_provider",
+                "acme",
+                &mut favorite,
+            )
+            .unwrap();
+            assert!(vault::is_reference(
+                favorite["provider_config"]["options"]["apiKey"]
+                    .as_str()
+                    .unwrap()
+            ));
+
+            reveal_record(conn, KeyState::Unlocked(&key), "settings", &mut settings);
+            assert_eq!(settings["webdav"]["password"], "dav-pw");
+            assert_eq!(settings["s3"]["access_key"], "AKIA");
+        });
+    }
+
+    #[test]
+    fn every_secret_table_has_fields() {
+        for table in SECRET_TABLES {
+            assert!(!secret_fields(table.name()).is_empty(), "{}", table.name());
+        }
+        assert!(secret_fields("skill_settings").is_empty());
+    }
+}
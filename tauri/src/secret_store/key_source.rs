@@ -0,0 +1,329 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+
+use super::cipher::{self, KdfParams, MasterKey, SealedBox};
+
+const CONFIG_FILE: &str = "secret-store.json";
+const KEY_FILE: &str = "secret-store.key";
+const CONFIG_VERSION: u32 = 1;
+const KEYRING_SERVICE: &str = "ai-toolbox";
+const KEYRING_ACCOUNT: &str = "secret-store-master-key";
+const VERIFIER_PLAINTEXT: &[u8] = b"ai-toolbox-secret-store";
+const VERIFIER_AAD: &[u8] = b"secret-store:verifier";
+
+/// Where the master key lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeySourceKind {
+    /// Random key stored in the OS keychain / credential manager / kernel keyring
+    Keyring,
+    /// Random key stored in an owner-only file next to the database
+    File,
+    /// Key derived from a user passphrase with Argon2id; never stored
+    Passphrase,
+}
+
+impl KeySourceKind {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim() {
+            "keyring" => Ok(Self::Keyring),
+            "file" => Ok(Self::File),
+            "passphrase" => Ok(Self::Passphrase),
+            other => Err(format!("Unsupported secret store key source: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum KeySource {
+    Keyring,
+    File,
+    #[serde(rename_all = "camelCase")]
+    Passphrase {
+        salt: String,
+        #[serde(flatten)]
+        params: KdfParams,
+    },
+}
+
+impl KeySource {
+    pub fn kind(&self) -> KeySourceKind {
+        match self {
+            KeySource::Keyring => KeySourceKind::Keyring,
+            KeySource::File => KeySourceKind::File,
+            KeySource::Passphrase { .. } => KeySourceKind::Passphrase,
+        }
+    }
+}
+
+/// Persisted outside the database so restoring a backup never replaces the
+/// local key material.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretStoreConfig {
+    pub version: u32,
+    pub key_source: KeySource,
+    /// Known plaintext sealed with the master key, used to reject a wrong
+    /// passphrase or a stale keyring entry before touching any secret.
+    pub verifier: SealedBox,
+    pub created_at: String,
+}
+
+pub fn config_path(dir: &Path) -> PathBuf {
+    dir.join(CONFIG_FILE)
+}
+
+pub fn key_file_path(dir: &Path) -> PathBuf {
+    dir.join(KEY_FILE)
+}
+
+pub fn load_config(dir: &Path) -> Result<Option<SecretStoreConfig>, String> {
+    let path = config_path(dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+    let config: SecretStoreConfig = serde_json::from_str(&content)
+        .map_err(|error| format!("Failed to parse {}: {error}", path.display()))?;
+    if config.version > CONFIG_VERSION {
+        return Err(format!(
+            "Secret store config version {} is newer than supported version {CONFIG_VERSION}",
+            config.version
+        ));
+    }
+    Ok(Some(config))
+}
+
+/// Create a new master key for `kind` and persist the config. The caller is
+/// responsible for making sure no store is configured yet.
+pub fn create(
+    dir: &Path,
+    kind: KeySourceKind,
+    passphrase: Option<&str>,
+    params: KdfParams,
+) -> Result<(SecretStoreConfig, MasterKey), String> {
+    let (key_source, key) = match kind {
+        KeySourceKind::Keyring => {
+            let key = MasterKey::generate();
+            keyring_entry()?
+                .set_password(&key.to_base64())
+                .map_err(|error| format!("Failed to store master key in OS keyring: {error}"))?;
+            (KeySource::Keyring, key)
+        }
+        KeySourceKind::File => {
+            let key = MasterKey::generate();
+            write_private_file(&key_file_path(dir), key.to_base64().as_bytes())?;
+            (KeySource::File, key)
+        }
+        KeySourceKind::Passphrase => {
+            let passphrase = passphrase
+                .ok_or_else(|| "A passphrase is required for this key source".to_string())?;
+            let salt = cipher::random_salt();
+            let key = cipher::derive_passphrase_key(passphrase, &salt, params)?;
+            let salt = base64::engine::general_purpose::STANDARD.encode(salt);
+            (KeySource::Passphrase { salt, params }, key)
+        }
+    };
+
+    let config = SecretStoreConfig {
+        version: CONFIG_VERSION,
+        key_source,
+        verifier: cipher::seal(&key, VERIFIER_PLAINTEXT, VERIFIER_AAD)?,
+        created_at: chrono::Local::now().to_rfc3339(),
+    };
+    let content = serde_json::to_string_pretty(&config)
+        .map_err(|error| format!("Failed to serialize secret store config: {error}"))?;
+    if let Err(error) = write_private_file(&config_path(dir), content.as_bytes()) {
+        let _ = remove_key_material(dir, &config.key_source);
+        return Err(error);
+    }
+    Ok((config, key))
+}
+
+/// Load the master key and check it against the stored verifier.
+pub fn unlock(
+    dir: &Path,
+    config: &SecretStoreConfig,
+    passphrase: Option<&str>,
+) -> Result<MasterKey, String> {
+    let key = match &config.key_source {
+        KeySource::Keyring => {
+            let encoded = keyring_entry()?
+                .get_password()
+                .map_err(|error| format!("Failed to read master key from OS keyring: {error}"))?;
+            MasterKey::from_base64(&encoded)?
+        }
+        KeySource::File => {
+            let path = key_file_path(dir);
+            let encoded = fs::read_to_string(&path)
+                .map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+            MasterKey::from_base64(&encoded)?
+        }
+        KeySource::Passphrase { salt, params } => {
+            let passphrase = passphrase.ok_or_else(|| {
+                "The secret store is locked; a passphrase is required".to_string()
+            })?;
+            let salt = base64::engine::general_purpose::STANDARD
+                .decode(salt)
+                .map_err(|error| format!("Invalid secret store salt: {error}"))?;
+            cipher::derive_passphrase_key(passphrase, &salt, *params)?
+        }
+    };
+
+    match cipher::open(&key, &config.verifier, VERIFIER_AAD) {
+        Ok(plaintext) if plaintext == VERIFIER_PLAINTEXT => Ok(key),
+        _ => Err(match config.key_source {
+            KeySource::Passphrase { .. } => "Incorrect secret store passphrase".to_string(),
+            _ => "The stored master key does not match this secret store".to_string(),
+        }),
+    }
+}
+
+/// Remove the config file and any stored key material.
+pub fn destroy(dir: &Path, config: &SecretStoreConfig) -> Result<(), String> {
+    remove_key_material(dir, &config.key_source)?;
+    let path = config_path(dir);
+    if path.exists() {
+        fs::remove_file(&path)
+            .map_err(|error| format!("Failed to remove {}: {error}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn remove_key_material(dir: &Path, key_source: &KeySource) -> Result<(), String> {
+    match key_source {
+        KeySource::Keyring => match keyring_entry()?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(error) => Err(format!(
+                "Failed to remove master key from OS keyring: {error}"
+            )),
+        },
+        KeySource::File => {
+            let path = key_file_path(dir);
+            if path.exists() {
+                fs::remove_file(&path)
+                    .map_err(|error| format!("Failed to remove {}: {error}", path.display()))?;
+            }
+            Ok(())
+        }
+        KeySource::Passphrase { .. } => Ok(()),
+    }
+}
+
+fn keyring_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT)
+        .map_err(|error| format!("OS keyring is not available: {error}"))
+}
+
+fn write_private_file(path: &Path, content: &[u8]) -> Result<(), String> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| format!("{} has no parent directory", path.display()))?;
+    fs::create_dir_all(parent)
+        .map_err(|error| format!("Failed to create {}: {error}", parent.display()))?;
+    let mut temporary = NamedTempFile::new_in(parent)
+        .map_err(|error| format!("Failed to create temp file for {}: {error}", path.display()))?;
+    temporary
+        .write_all(content)
+        .map_err(|error| format!("Failed to write temp file for {}: {error}", path.display()))?;
+    temporary
+        .persist(path)
+        .map_err(|error| format!("Failed to replace {}: {}", path.display(), error.error))?;
+    crate::settings::backup::utils::harden_restored_sensitive_file(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_params() -> KdfParams {
+        KdfParams {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        }
+    }
+
+    #[test]
+    fn file_source_persists_key_and_unlocks_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let (config, key) = create(dir.path(), KeySourceKind::File, None, fast_params()).unwrap();
+        assert_eq!(config.key_source, KeySource::File);
+        assert!(key_file_path(dir.path()).exists());
+
+        let loaded = load_config(dir.path()).unwrap().expect("config written");
+        assert_eq!(loaded, config);
+        let unlocked = unlock(dir.path(), &loaded, None).unwrap();
+        assert_eq!(unlocked.to_base64(), key.to_base64());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(key_file_path(dir.path()))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn file_source_rejects_a_replaced_key_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let (config, _) = create(dir.path(), KeySourceKind::File, None, fast_params()).unwrap();
+        fs::write(key_file_path(dir.path()), MasterKey::generate().to_base64()).unwrap();
+        let error = unlock(dir.path(), &config, None).unwrap_err();
+        assert!(error.contains("does not match"), "{error}");
+    }
+
+    #[test]
+    fn passphrase_source_stores_no_key_and_checks_the_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let (config, key) = create(
+            dir.path(),
+            KeySourceKind::Passphrase,
+            Some("hunter2 hunter2"),
+            fast_params(),
+        )
+        .unwrap();
+        assert!(!key_file_path(dir.path()).exists());
+        let raw = fs::read_to_string(config_path(dir.path())).unwrap();
+        assert!(!raw.contains(&key.to_base64()));
+        assert!(raw.contains("\"type\": \"passphrase\""));
+        assert!(raw.contains("\"memoryKib\": 64"));
+
+        let unlocked = unlock(dir.path(), &config, Some("hunter2 hunter2")).unwrap();
+        assert_eq!(unlocked.to_base64(), key.to_base64());
+        assert_eq!(
+            unlock(dir.path(), &config, Some("wrong")).unwrap_err(),
+            "Incorrect secret store passphrase"
+        );
+        assert!(unlock(dir.path(), &config, None).is_err());
+    }
+
+    #[test]
+    fn destroy_removes_config_and_key_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let (config, _) = create(dir.path(), KeySourceKind::File, None, fast_params()).unwrap();
+        destroy(dir.path(), &config).unwrap();
+        assert!(load_config(dir.path()).unwrap().is_none());
+        assert!(!key_file_path(dir.path()).exists());
+    }
+
+    #[test]
+    fn key_source_kind_parses_command_values() {
+        assert_eq!(
+            KeySourceKind::parse("keyring").unwrap(),
+            KeySourceKind::Keyring
+        );
+        assert_eq!(KeySourceKind::parse(" file ").unwrap(), KeySourceKind::File);
+        assert!(KeySourceKind::parse("plaintext").is_err());
+    }
+}
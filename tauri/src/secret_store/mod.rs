@@ -0,0 +1,364 @@
+//! Encrypted storage for credentials kept in the SQLite JSONB records.
+//!
+//! When a store is configured, the credential fields listed in [`fields`] are
+//! sealed with AES-256-GCM into the `secret_value` table on every write through
+//! `db::helpers`, and the record keeps only a `secret://<entry>` reference.
+//! Reads resolve references back to plaintext, so the rest of the app (apply,
+//! WSL/SSH sync, backups) keeps working with real values. While a passphrase
+//! store is locked the references stay in place, and every consumer that hands
+//! credentials on checks [`ensure_resolved`] first.
+//!
+//! The master key comes from the OS keyring, an owner-only key file, or a
+//! passphrase (Argon2id). Its config lives in the app data directory rather than
+//! the database so a restored backup never replaces local key material. Without
+//! a configured store everything stays plaintext, exactly as before.
+
+pub mod cipher;
+pub mod commands;
+pub mod fields;
+pub mod key_source;
+pub mod vault;
+
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub use commands::*;
+
+use cipher::{KdfParams, MasterKey};
+use fields::{KeyState, SECRET_TABLES};
+use key_source::{KeySourceKind, SecretStoreConfig};
+pub use vault::is_reference;
+
+static STORE_DIR: OnceLock<PathBuf> = OnceLock::new();
+static STATE: Mutex<StoreState> = Mutex::new(StoreState {
+    config: None,
+    key: None,
+});
+
+struct StoreState {
+    config: Option<SecretStoreConfig>,
+    key: Option<MasterKey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretStoreStatus {
+    pub enabled: bool,
+    pub key_source: Option<KeySourceKind>,
+    pub unlocked: bool,
+    pub secret_count: i64,
+}
+
+/// Set the directory holding `secret-store.json` (the app data directory).
+pub fn set_store_dir(dir: PathBuf) {
+    let _ = STORE_DIR.set(dir);
+}
+
+fn store_dir() -> Result<&'static PathBuf, String> {
+    STORE_DIR
+        .get()
+        .ok_or_else(|| "Secret store directory is not initialized".to_string())
+}
+
+fn lock_state() -> Result<std::sync::MutexGuard<'static, StoreState>, String> {
+    STATE
+        .lock()
+        .map_err(|_| "Secret store state mutex is poisoned".to_string())
+}
+
+/// Load the config at startup and unlock key sources that need no user input.
+/// A passphrase store stays locked until [`unlock`] is called.
+pub fn init() -> Result<(), String> {
+    let dir = store_dir()?;
+    let Some(config) = key_source::load_config(dir)? else {
+        return Ok(());
+    };
+    let key = match config.key_source.kind() {
+        KeySourceKind::Passphrase => None,
+        _ => Some(key_source::unlock(dir, &config, None)?),
+    };
+    let mut state = lock_state()?;
+    state.config = Some(config);
+    state.key = key;
+    Ok(())
+}
+
+/// Run `operation` with the current key state.
+fn with_key_state<T>(operation: impl FnOnce(KeyState<'_>) -> T) -> T {
+    let Ok(state) = STATE.lock() else {
+        return operation(KeyState::Locked);
+    };
+    match (&state.config, &state.key) {
+        (None, _) => operation(KeyState::Disabled),
+        (Some(_), None) => operation(KeyState::Locked),
+        (Some(_), Some(key)) => operation(KeyState::Unlocked(key)),
+    }
+}
+
+/// Error returned when a credential is still a `secret://` reference because the
+/// passphrase store is locked.
+pub const LOCKED_ERROR: &str =
+    "The secret store is locked; unlock it in Settings before using stored credentials";
+
+/// Refuse `value` (a credential, or serialized config holding credentials) if it
+/// still carries an unresolved reference, so a locked store never hands
+/// `secret://...` on as if it were the credential itself.
+pub fn ensure_resolved(value: &str) -> Result<(), String> {
+    if value.contains(vault::REFERENCE_PREFIX) {
+        Err(LOCKED_ERROR.to_string())
+    } else {
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Hooks used by db::helpers
+// ---------------------------------------------------------------------------
+
+pub(crate) fn seal_record(
+    conn: &Connection,
+    table_name: &str,
+    record_id: &str,
+    data: &mut Value,
+) -> Result<(), String> {
+    if fields::secret_fields(table_name).is_empty() {
+        return Ok(());
+    }
+    with_key_state(|key| fields::seal_record(conn, key, table_name, record_id, data)).map(|_| ())
+}
+
+pub(crate) fn reveal_record(conn: &Connection, table_name: &str, data: &mut Value) {
+    if fields::secret_fields(table_name).is_empty() {
+        return;
+    }
+    with_key_state(|key| fields::reveal_record(conn, key, table_name, data));
+}
+
+pub(crate) fn forget_record(
+    conn: &Connection,
+    table_name: &str,
+    record_id: &str,
+) -> Result<(), String> {
+    if fields::secret_fields(table_name).is_empty() {
+        return Ok(());
+    }
+    vault::delete_owner(conn, table_name, record_id).map(|_| ())
+}
+
+pub(crate) fn forget_table(conn: &Connection, table_name: &str) -> Result<(), String> {
+    if fields::secret_fields(table_name).is_empty() {
+        return Ok(());
+    }
+    vault::delete_table(conn, table_name).map(|_| ())
+}
+
+// ---------------------------------------------------------------------------
+// Bulk operations
+// ---------------------------------------------------------------------------
+
+/// Seal every plaintext credential in the database. Used after enabling or
+/// unlocking the store and after restoring a backup.
+pub fn seal_all(conn: &Connection) -> Result<usize, String> {
+    with_key_state(|key| seal_all_with(conn, key))
+}
+
+fn seal_all_with(conn: &Connection, key: KeyState<'_>) -> Result<usize, String> {
+    if !matches!(key, KeyState::Unlocked(_)) {
+        return Ok(0);
+    }
+    let mut sealed = 0;
+    for table in SECRET_TABLES {
+        let table_name = table.name();
+        for (id, mut data) in vault::raw_rows(conn, table_name)? {
+            if fields::seal_record(conn, key, table_name, &id, &mut data)? {
+                vault::raw_update(conn, table_name, &id, &data)?;
+                sealed += 1;
+            }
+        }
+    }
+    Ok(sealed)
+}
+
+/// Write plaintext back into every record and empty the vault.
+fn reveal_all_with(conn: &Connection, key: KeyState<'_>) -> Result<usize, String> {
+    let mut revealed = 0;
+    for table in SECRET_TABLES {
+        let table_name = table.name();
+        for (id, mut data) in vault::raw_rows(conn, table_name)? {
+            if fields::reveal_record(conn, key, table_name, &mut data) {
+                vault::raw_update(conn, table_name, &id, &data)?;
+                revealed += 1;
+            }
+        }
+    }
+    Ok(revealed)
+}
+
+/// Prepare a database snapshot for export: resolve references to plaintext and
+/// drop the ciphertexts, so the archive restores on a machine without this
+/// master key. Returns `false` if the store is locked and the snapshot was left
+/// sealed (restorable only where this master key is available).
+pub fn inline_secrets_for_export(snapshot: &Connection) -> Result<bool, String> {
+    with_key_state(|key| match key {
+        KeyState::Disabled => Ok(true),
+        KeyState::Locked => Ok(false),
+        KeyState::Unlocked(_) => {
+            reveal_all_with(snapshot, key)?;
+            vault::delete_all(snapshot)?;
+            Ok(true)
+        }
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Lifecycle
+// ---------------------------------------------------------------------------
+
+pub fn status(conn: &Connection) -> Result<SecretStoreStatus, String> {
+    let state = lock_state()?;
+    Ok(SecretStoreStatus {
+        enabled: state.config.is_some(),
+        key_source: state.config.as_ref().map(|config| config.key_source.kind()),
+        unlocked: state.key.is_some(),
+        secret_count: vault::count(conn)?,
+    })
+}
+
+pub fn enable(
+    conn: &mut Connection,
+    kind: KeySourceKind,
+    passphrase: Option<&str>,
+) -> Result<usize, String> {
+    let dir = store_dir()?;
+    let mut state = lock_state()?;
+    if state.config.is_some() {
+        return Err("The secret store is already enabled".to_string());
+    }
+    let (config, key) = key_source::create(dir, kind, passphrase, KdfParams::default())?;
+    let sealed =
+        crate::db::helpers::db_transaction(conn, |tx| seal_all_with(tx, KeyState::Unlocked(&key)));
+    match sealed {
+        Ok(count) => {
+            state.config = Some(config);
+            state.key = Some(key);
+            Ok(count)
+        }
+        Err(error) => {
+            let _ = key_source::destroy(dir, &config);
+            Err(error)
+        }
+    }
+}
+
+pub fn unlock(conn: &mut Connection, passphrase: &str) -> Result<usize, String> {
+    let dir = store_dir()?;
+    let mut state = lock_state()?;
+    let config = state
+        .config
+        .clone()
+        .ok_or_else(|| "The secret store is not enabled".to_string())?;
+    let key = key_source::unlock(dir, &config, Some(passphrase))?;
+    // Records written while locked (e.g. a restored backup) may still hold plaintext.
+    let sealed =
+        crate::db::helpers::db_transaction(conn, |tx| seal_all_with(tx, KeyState::Unlocked(&key)))?;
+    state.key = Some(key);
+    Ok(sealed)
+}
+
+pub fn lock() -> Result<(), String> {
+    let mut state = lock_state()?;
+    if matches!(
+        state.config.as_ref().map(|config| config.key_source.kind()),
+        Some(KeySourceKind::Passphrase)
+    ) {
+        state.key = None;
+        Ok(())
+    } else {
+        Err("Only a passphrase-protected secret store can be locked".to_string())
+    }
+}
+
+pub fn disable(conn: &mut Connection) -> Result<usize, String> {
+    let dir = store_dir()?;
+    let mut state = lock_state()?;
+    let Some(config) = state.config.clone() else {
+        return Ok(0);
+    };
+    let Some(key) = state.key.as_ref() else {
+        return Err("Unlock the secret store before disabling it".to_string());
+    };
+    let revealed = crate::db::helpers::db_transaction(conn, |tx| {
+        let revealed = reveal_all_with(tx, KeyState::Unlocked(key))?;
+        vault::delete_all(tx)?;
+        Ok(revealed)
+    })?;
+    key_source::destroy(dir, &config)?;
+    state.config = None;
+    state.key = None;
+    Ok(revealed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::helpers::{db_get, db_put};
+    use crate::db::schema::DbTable;
+    use crate::db::SqliteDbState;
+    use serde_json::json;
+
+    #[test]
+    fn unresolved_references_are_refused_as_credentials() {
+        assert!(ensure_resolved("sk-plain").is_ok());
+        assert!(ensure_resolved("").is_ok());
+        assert_eq!(
+            ensure_resolved("secret://image_channel:i1:api_key").unwrap_err(),
+            LOCKED_ERROR
+        );
+        assert!(ensure_resolved(
+            r#"{"env":{"ANTHROPIC_AUTH_TOKEN":"secret://claude_provider:p1:settings_config/env/ANTHROPIC_AUTH_TOKEN"}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn bulk_seal_then_export_inlines_plaintext_and_drops_vault() {
+        let state = SqliteDbState::in_memory_for_test().unwrap();
+        state
+            .with_conn(|conn| {
+                db_put(
+                    conn,
+                    DbTable::SshConnection,
+                    "c1",
+                    &json!({ "name": "box", "password": "pw", "passphrase": "", "private_key_content": "" }),
+                )?;
+                db_put(
+                    conn,
+                    DbTable::CodexProvider,
+                    "p1",
+                    &json!({ "settings_config": "{\"auth\":{\"OPENAI_API_KEY\":\"sk-codex\"}}" }),
+                )?;
+
+                let key = MasterKey::generate();
+                assert_eq!(seal_all_with(conn, KeyState::Unlocked(&key))?, 2);
+                assert_eq!(seal_all_with(conn, KeyState::Unlocked(&key))?, 0);
+                let stored = db_get(conn, DbTable::SshConnection, "c1")?.unwrap();
+                assert_eq!(stored["password"], "secret://ssh_connection:c1:password");
+                assert_eq!(vault::count(conn)?, 2);
+
+                assert_eq!(reveal_all_with(conn, KeyState::Unlocked(&key))?, 2);
+                vault::delete_all(conn)?;
+                let stored = db_get(conn, DbTable::CodexProvider, "p1")?.unwrap();
+                assert_eq!(
+                    stored["settings_config"],
+                    "{\"auth\":{\"OPENAI_API_KEY\":\"sk-codex\"}}"
+                );
+                assert_eq!(vault::count(conn)?, 0);
+                Ok(())
+            })
+            .unwrap();
+    }
+}
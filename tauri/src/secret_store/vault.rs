@@ -0,0 +1,141 @@
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::cipher::{self, MasterKey, SealedBox};
+use crate::db::helpers::{db_count, db_delete, db_delete_all, db_get, db_put};
+use crate::db::schema::DbTable;
+
+/// Prefix of the placeholder stored in place of a sealed secret.
+pub const REFERENCE_PREFIX: &str = "secret://";
+
+pub fn reference_for(entry_id: &str) -> String {
+    format!("{REFERENCE_PREFIX}{entry_id}")
+}
+
+pub fn parse_reference(value: &str) -> Option<&str> {
+    value
+        .strip_prefix(REFERENCE_PREFIX)
+        .filter(|entry_id| !entry_id.is_empty())
+}
+
+pub fn is_reference(value: &str) -> bool {
+    parse_reference(value).is_some()
+}
+
+/// One row of `secret_value`. The entry id doubles as AEAD associated data, so
+/// a ciphertext copied onto another row fails to decrypt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VaultEntry {
+    owner_table: String,
+    owner_id: String,
+    field: String,
+    #[serde(flatten)]
+    sealed: SealedBox,
+}
+
+pub struct EntryOwner<'a> {
+    pub table: &'a str,
+    pub record_id: &'a str,
+    pub field: &'a str,
+}
+
+pub fn put(
+    conn: &Connection,
+    key: &MasterKey,
+    entry_id: &str,
+    owner: &EntryOwner<'_>,
+    plaintext: &str,
+) -> Result<(), String> {
+    let entry = VaultEntry {
+        owner_table: owner.table.to_string(),
+        owner_id: owner.record_id.to_string(),
+        field: owner.field.to_string(),
+        sealed: cipher::seal(key, plaintext.as_bytes(), entry_id.as_bytes())?,
+    };
+    let data = serde_json::to_value(&entry)
+        .map_err(|error| format!("Failed to serialize secret entry: {error}"))?;
+    db_put(conn, DbTable::SecretValue, entry_id, &data)
+}
+
+pub fn read(conn: &Connection, key: &MasterKey, entry_id: &str) -> Result<Option<String>, String> {
+    let Some(record) = db_get(conn, DbTable::SecretValue, entry_id)? else {
+        return Ok(None);
+    };
+    let entry: VaultEntry = serde_json::from_value(record)
+        .map_err(|error| format!("Failed to parse secret entry '{entry_id}': {error}"))?;
+    cipher::open_string(key, &entry.sealed, entry_id.as_bytes()).map(Some)
+}
+
+pub fn delete(conn: &Connection, entry_id: &str) -> Result<(), String> {
+    db_delete(conn, DbTable::SecretValue, entry_id).map(|_| ())
+}
+
+pub fn delete_owner(conn: &Connection, owner_table: &str, owner_id: &str) -> Result<usize, String> {
+    let table_name = DbTable::SecretValue.name();
+    conn.execute(
+        &format!(
+            "DELETE FROM {table_name}
+             WHERE json_extract(data, '$.owner_table') = ?1
+               AND json_extract(data, '$.owner_id') = ?2"
+        ),
+        [owner_table, owner_id],
+    )
+    .map_err(|error| format!("Failed to delete secrets of {owner_table}/{owner_id}: {error}"))
+}
+
+pub fn delete_table(conn: &Connection, owner_table: &str) -> Result<usize, String> {
+    let table_name = DbTable::SecretValue.name();
+    conn.execute(
+        &format!("DELETE FROM {table_name} WHERE json_extract(data, '$.owner_table') = ?1"),
+        [owner_table],
+    )
+    .map_err(|error| format!("Failed to delete secrets of {owner_table}: {error}"))
+}
+
+pub fn delete_all(conn: &Connection) -> Result<usize, String> {
+    db_delete_all(conn, DbTable::SecretValue)
+}
+
+pub fn count(conn: &Connection) -> Result<i64, String> {
+    db_count(conn, DbTable::SecretValue)
+}
+
+/// Raw `(id, data)` rows of a table, bypassing the secret hooks in
+/// `db::helpers` so callers see exactly what is stored.
+pub fn raw_rows(conn: &Connection, table_name: &str) -> Result<Vec<(String, Value)>, String> {
+    let mut statement = conn
+        .prepare(&format!("SELECT id, json(data) FROM {table_name}"))
+        .map_err(|error| format!("Failed to read {table_name}: {error}"))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|error| format!("Failed to read {table_name}: {error}"))?;
+    let mut records = Vec::new();
+    for row in rows {
+        let (id, data_json) =
+            row.map_err(|error| format!("Failed to read {table_name}: {error}"))?;
+        let data = serde_json::from_str(&data_json)
+            .map_err(|error| format!("Failed to parse {table_name}/{id}: {error}"))?;
+        records.push((id, data));
+    }
+    Ok(records)
+}
+
+/// Overwrite a record's payload without touching timestamps or secret hooks.
+pub fn raw_update(
+    conn: &Connection,
+    table_name: &str,
+    id: &str,
+    data: &Value,
+) -> Result<(), String> {
+    let data_json = serde_json::to_string(data)
+        .map_err(|error| format!("Failed to serialize {table_name}/{id}: {error}"))?;
+    conn.execute(
+        &format!("UPDATE {table_name} SET data = jsonb(?1) WHERE id = ?2"),
+        (data_json, id),
+    )
+    .map_err(|error| format!("Failed to update {table_name}/{id}: {error}"))?;
+    Ok(())
+}
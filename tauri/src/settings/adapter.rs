@@ -26,8 +26,11 @@ pub fn from_db_value(value: Value) -> AppSettings {
 
         last_backup_time: get_opt_str(&value, "last_backup_time"),
         backup_image_assets_enabled: get_bool(&value, "backup_image_assets_enabled", true),
+        backup_session_trash_enabled: get_bool(&value, "backup_session_trash_enabled", false),
         backup_cli_config_files_enabled: get_bool(&value, "backup_cli_config_files_enabled", true),
         backup_custom_entries: get_backup_custom_entries(&value),
+        backup_encryption_enabled: get_bool(&value, "backup_encryption_enabled", false),
+        backup_encryption_passphrase: get_str(&value, "backup_encryption_passphrase", ""),
         launch_on_startup: get_bool(&value, "launch_on_startup", true),
         minimize_to_tray_on_close: get_bool(&value, "minimize_to_tray_on_close", true),
         start_minimized: get_bool(&value, "start_minimized", false),
@@ -37,6 +40,7 @@ pub fn from_db_value(value: Value) -> AppSettings {
         auto_backup_enabled: get_bool(&value, "auto_backup_enabled", false),
         auto_backup_interval_days: get_u32(&value, "auto_backup_interval_days", 7),
         auto_backup_max_keep: get_u32(&value, "auto_backup_max_keep", 10),
+        auto_backup_max_age_days: get_u32(&value, "auto_backup_max_age_days", 0),
         last_auto_backup_time: get_opt_str(&value, "last_auto_backup_time"),
         auto_check_update: get_bool(&value, "auto_check_update", true),
         visible_tabs: normalize_visible_tabs_order(get_string_array(
@@ -421,6 +425,13 @@ mod tests {
         assert!(!settings.backup_image_assets_enabled);
     }
 
+    #[test]
+    fn backup_session_trash_enabled_defaults_to_false() {
+        let settings = from_db_value(json!({}));
+
+        assert!(!settings.backup_session_trash_enabled);
+    }
+
     #[test]
     fn backup_cli_config_files_enabled_defaults_to_true() {
         let settings = from_db_value(json!({}));
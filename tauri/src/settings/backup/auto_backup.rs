@@ -1,8 +1,14 @@
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
 use log::{error, info, warn};
+use regex::Regex;
 use std::time::Duration;
 use tauri::{Emitter, Manager};
 
+use super::encryption;
+use super::s3::{
+    delete_s3_backup_internal, list_s3_backups_internal, s3_backup_filename,
+    upload_s3_backup_internal,
+};
 use super::utils::{create_backup_zip, get_db_path};
 use super::webdav::{delete_webdav_backup_internal, list_webdav_backups_internal};
 use crate::db::SqliteDbState;
@@ -62,7 +68,7 @@ async fn check_and_perform_backup(app_handle: &tauri::AppHandle) -> Result<(), S
                     update_last_auto_backup_time(&sqlite_state, &db_state, &now).await?;
                     let _ = app_handle.emit("auto-backup-completed", &now);
 
-                    if settings.auto_backup_max_keep > 0 {
+                    if is_retention_enabled(&settings) {
                         if let Err(e) = cleanup_old_webdav_backups(
                             &db_state,
                             &settings.webdav.url,
@@ -70,6 +76,7 @@ async fn check_and_perform_backup(app_handle: &tauri::AppHandle) -> Result<(), S
                             &settings.webdav.password,
                             &settings.webdav.remote_path,
                             settings.auto_backup_max_keep,
+                            settings.auto_backup_max_age_days,
                         )
                         .await
                         {
@@ -104,10 +111,11 @@ async fn check_and_perform_backup(app_handle: &tauri::AppHandle) -> Result<(), S
                     update_last_auto_backup_time(&sqlite_state, &db_state, &now).await?;
                     let _ = app_handle.emit("auto-backup-completed", &now);
 
-                    if settings.auto_backup_max_keep > 0 {
+                    if is_retention_enabled(&settings) {
                         if let Err(e) = cleanup_old_local_backups(
                             &settings.local_backup_path,
                             settings.auto_backup_max_keep,
+                            settings.auto_backup_max_age_days,
                         ) {
                             warn!("Auto-backup local cleanup failed: {}", e);
                         }
@@ -125,6 +133,46 @@ async fn check_and_perform_backup(app_handle: &tauri::AppHandle) -> Result<(), S
 
             Ok(())
         }
+        "s3" => {
+            if settings.s3.bucket.is_empty() {
+                return Ok(());
+            }
+
+            info!("Auto-backup is due, performing S3 backup...");
+
+            match perform_s3_backup(app_handle, &db_state, &settings).await {
+                Ok(()) => {
+                    info!("Auto-backup (S3) completed successfully");
+
+                    let now = Utc::now().to_rfc3339();
+                    update_last_auto_backup_time(&sqlite_state, &db_state, &now).await?;
+                    let _ = app_handle.emit("auto-backup-completed", &now);
+
+                    if is_retention_enabled(&settings) {
+                        if let Err(e) = cleanup_old_s3_backups(
+                            &db_state,
+                            &settings.s3,
+                            settings.auto_backup_max_keep,
+                            settings.auto_backup_max_age_days,
+                        )
+                        .await
+                        {
+                            warn!("Auto-backup S3 cleanup failed: {}", e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!("Auto-backup (S3) failed: {}", e);
+
+                    // Update last_auto_backup_time even on failure to prevent retry every 10 minutes
+                    let now = Utc::now().to_rfc3339();
+                    update_last_auto_backup_time(&sqlite_state, &db_state, &now).await?;
+                    let _ = app_handle.emit("auto-backup-failed", &e);
+                }
+            }
+
+            Ok(())
+        }
         _ => Ok(()),
     }
 }
@@ -145,28 +193,47 @@ fn is_backup_due(last_time: &Option<String>, interval_days: u32) -> bool {
     elapsed >= interval
 }
 
-/// Perform a WebDAV backup
-async fn perform_webdav_backup(
+/// Build the backup archive, encrypted with the stored passphrase when backup
+/// encryption is enabled. Returns the data and its file extension.
+async fn create_auto_backup_archive(
     app_handle: &tauri::AppHandle,
-    db_state: &SqliteDbState,
+    db_path: &std::path::Path,
     settings: &crate::settings::types::AppSettings,
-) -> Result<(), String> {
-    let db_path = get_db_path(app_handle)?;
+) -> Result<(Vec<u8>, &'static str), String> {
+    let passphrase = encryption::backup_passphrase(settings, None)?;
     let zip_data = create_backup_zip(
         app_handle,
-        &db_path,
+        db_path,
         settings.backup_image_assets_enabled,
         settings.backup_cli_config_files_enabled,
         &settings.backup_file_filter_rules,
     )
     .await?;
+    match passphrase {
+        Some(passphrase) => Ok((
+            encryption::encrypt_backup(&zip_data, &passphrase)?,
+            encryption::backup_file_extension(true),
+        )),
+        None => Ok((zip_data, encryption::backup_file_extension(false))),
+    }
+}
+
+/// Perform a WebDAV backup
+async fn perform_webdav_backup(
+    app_handle: &tauri::AppHandle,
+    db_state: &SqliteDbState,
+    settings: &crate::settings::types::AppSettings,
+) -> Result<(), String> {
+    crate::secret_store::ensure_resolved(&settings.webdav.password)?;
+    let db_path = get_db_path(app_handle)?;
+    let (zip_data, extension) = create_auto_backup_archive(app_handle, &db_path, settings).await?;
 
     let timestamp = Local::now().format("%Y%m%d-%H%M%S");
     let host = settings.webdav.host_label.trim();
     let backup_filename = if host.is_empty() {
-        format!("ai-toolbox-backup-{}.zip", timestamp)
+        format!("ai-toolbox-backup-{}.{}", timestamp, extension)
     } else {
-        format!("ai-toolbox-backup-{}_{}.zip", timestamp, host)
+        format!("ai-toolbox-backup-{}_{}.{}", timestamp, host, extension)
     };
 
     let base_url = settings.webdav.url.trim_end_matches('/');
@@ -210,14 +277,7 @@ async fn perform_local_backup(
     settings: &crate::settings::types::AppSettings,
 ) -> Result<(), String> {
     let db_path = get_db_path(app_handle)?;
-    let zip_data = create_backup_zip(
-        app_handle,
-        &db_path,
-        settings.backup_image_assets_enabled,
-        settings.backup_cli_config_files_enabled,
-        &settings.backup_file_filter_rules,
-    )
-    .await?;
+    let (zip_data, extension) = create_auto_backup_archive(app_handle, &db_path, settings).await?;
 
     let backup_dir = std::path::Path::new(&settings.local_backup_path);
     if !backup_dir.exists() {
@@ -226,7 +286,7 @@ async fn perform_local_backup(
     }
 
     let timestamp = Local::now().format("%Y%m%d-%H%M%S");
-    let backup_filename = format!("ai-toolbox-backup-{}.zip", timestamp);
+    let backup_filename = format!("ai-toolbox-backup-{}.{}", timestamp, extension);
     let backup_file_path = backup_dir.join(&backup_filename);
 
     std::fs::write(&backup_file_path, &zip_data)
@@ -236,6 +296,36 @@ async fn perform_local_backup(
     Ok(())
 }
 
+/// Perform an S3 backup
+async fn perform_s3_backup(
+    app_handle: &tauri::AppHandle,
+    db_state: &SqliteDbState,
+    settings: &crate::settings::types::AppSettings,
+) -> Result<(), String> {
+    let db_path = get_db_path(app_handle)?;
+    let (zip_data, extension) = create_auto_backup_archive(app_handle, &db_path, settings).await?;
+
+    // S3 has no host label of its own; reuse the WebDAV one so manual and auto
+    // backups from the same machine keep the same naming.
+    let backup_filename = s3_backup_filename(
+        &settings.webdav.host_label,
+        extension == encryption::backup_file_extension(true),
+    );
+
+    let client = http_client::client_with_timeout(db_state, 300)
+        .await
+        .map_err(|e| {
+            error!("Failed to create HTTP client: {}", e);
+            e
+        })?;
+    let location = upload_s3_backup_internal(&client, &settings.s3, &backup_filename, zip_data)
+        .await
+        .map_err(|e| format!("Auto-backup upload failed: {}", e))?;
+
+    info!("Auto-backup: uploaded to {}", location);
+    Ok(())
+}
+
 /// Update last_auto_backup_time in SQLite.
 async fn update_last_auto_backup_time(
     sqlite_state: &SqliteDbState,
@@ -245,7 +335,48 @@ async fn update_last_auto_backup_time(
     store::update_last_auto_backup_time_in_sqlite_state(sqlite_state, time)
 }
 
-/// Cleanup old WebDAV backups, keeping only the latest `max_keep` files
+/// Whether either retention limit (count or age) is configured
+fn is_retention_enabled(settings: &crate::settings::types::AppSettings) -> bool {
+    settings.auto_backup_max_keep > 0 || settings.auto_backup_max_age_days > 0
+}
+
+/// Parse the local timestamp embedded in a backup filename
+/// (`ai-toolbox-backup-YYYYMMDD-HHMMSS[_host].zip[.enc]`).
+fn parse_backup_timestamp(filename: &str) -> Option<NaiveDateTime> {
+    let re = Regex::new(r"ai-toolbox-backup-(\d{8}-\d{6})").ok()?;
+    let captures = re.captures(filename)?;
+    NaiveDateTime::parse_from_str(captures.get(1)?.as_str(), "%Y%m%d-%H%M%S").ok()
+}
+
+/// Pick the backups to delete: everything beyond the newest `max_keep` files,
+/// plus any file older than `max_age_days`. A zero limit disables that rule,
+/// and files without a parsable timestamp are never deleted by age.
+fn select_backups_to_prune(
+    filenames: &[String],
+    max_keep: u32,
+    max_age_days: u32,
+    now: NaiveDateTime,
+) -> Vec<String> {
+    let mut sorted: Vec<&String> = filenames.iter().collect();
+    // Sort descending by filename (most recent first)
+    sorted.sort_by(|a, b| b.cmp(a));
+
+    let cutoff = (max_age_days > 0).then(|| now - chrono::Duration::days(max_age_days as i64));
+    sorted
+        .into_iter()
+        .enumerate()
+        .filter(|(index, filename)| {
+            let over_count = max_keep > 0 && *index >= max_keep as usize;
+            let too_old = cutoff.is_some_and(|cutoff| {
+                parse_backup_timestamp(filename).is_some_and(|time| time < cutoff)
+            });
+            over_count || too_old
+        })
+        .map(|(_, filename)| filename.clone())
+        .collect()
+}
+
+/// Cleanup old WebDAV backups according to the retention limits
 async fn cleanup_old_webdav_backups(
     db_state: &SqliteDbState,
     url: &str,
@@ -253,73 +384,163 @@ async fn cleanup_old_webdav_backups(
     password: &str,
     remote_path: &str,
     max_keep: u32,
+    max_age_days: u32,
 ) -> Result<(), String> {
     let backups =
         list_webdav_backups_internal(db_state, url, username, password, remote_path).await?;
+    let filenames: Vec<String> = backups.into_iter().map(|b| b.filename).collect();
 
-    if backups.len() <= max_keep as usize {
+    let to_delete = select_backups_to_prune(
+        &filenames,
+        max_keep,
+        max_age_days,
+        Local::now().naive_local(),
+    );
+    if to_delete.is_empty() {
         return Ok(());
     }
-
-    let to_delete = &backups[max_keep as usize..];
     info!(
         "Auto-backup cleanup: deleting {} old WebDAV backup(s)",
         to_delete.len()
     );
 
-    for backup in to_delete {
-        if let Err(e) = delete_webdav_backup_internal(
-            db_state,
-            url,
-            username,
-            password,
-            remote_path,
-            &backup.filename,
-        )
-        .await
+    for filename in &to_delete {
+        if let Err(e) =
+            delete_webdav_backup_internal(db_state, url, username, password, remote_path, filename)
+                .await
         {
-            warn!("Failed to delete old backup {}: {}", backup.filename, e);
+            warn!("Failed to delete old backup {}: {}", filename, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Cleanup old S3 backups according to the retention limits
+async fn cleanup_old_s3_backups(
+    db_state: &SqliteDbState,
+    config: &crate::settings::types::S3Config,
+    max_keep: u32,
+    max_age_days: u32,
+) -> Result<(), String> {
+    let client = http_client::client_with_timeout(db_state, 30).await?;
+    let backups = list_s3_backups_internal(&client, config).await?;
+    let filenames: Vec<String> = backups.into_iter().map(|b| b.filename).collect();
+
+    let to_delete = select_backups_to_prune(
+        &filenames,
+        max_keep,
+        max_age_days,
+        Local::now().naive_local(),
+    );
+    if to_delete.is_empty() {
+        return Ok(());
+    }
+    info!(
+        "Auto-backup cleanup: deleting {} old S3 backup(s)",
+        to_delete.len()
+    );
+
+    for filename in &to_delete {
+        if let Err(e) = delete_s3_backup_internal(&client, config, filename).await {
+            warn!("Failed to delete old backup {}: {}", filename, e);
         }
     }
 
     Ok(())
 }
 
-/// Cleanup old local backups, keeping only the latest `max_keep` files
-fn cleanup_old_local_backups(backup_path: &str, max_keep: u32) -> Result<(), String> {
+/// Cleanup old local backups according to the retention limits
+fn cleanup_old_local_backups(
+    backup_path: &str,
+    max_keep: u32,
+    max_age_days: u32,
+) -> Result<(), String> {
     let backup_dir = std::path::Path::new(backup_path);
     if !backup_dir.exists() {
         return Ok(());
     }
 
-    let mut backup_files: Vec<_> = std::fs::read_dir(backup_dir)
+    let filenames: Vec<String> = std::fs::read_dir(backup_dir)
         .map_err(|e| format!("Failed to read backup dir: {}", e))?
         .filter_map(|e| e.ok())
-        .filter(|e| {
-            let name = e.file_name();
-            let name_str = name.to_string_lossy();
-            name_str.starts_with("ai-toolbox-backup-") && name_str.ends_with(".zip")
-        })
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| encryption::is_backup_file_name(name))
         .collect();
 
-    if backup_files.len() <= max_keep as usize {
+    let to_delete = select_backups_to_prune(
+        &filenames,
+        max_keep,
+        max_age_days,
+        Local::now().naive_local(),
+    );
+    if to_delete.is_empty() {
         return Ok(());
     }
-
-    // Sort descending by filename (most recent first)
-    backup_files.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
-
-    let to_delete = &backup_files[max_keep as usize..];
     info!(
         "Auto-backup cleanup: deleting {} old local backup(s)",
         to_delete.len()
     );
 
-    for entry in to_delete {
-        if let Err(e) = std::fs::remove_file(entry.path()) {
-            warn!("Failed to delete old backup {:?}: {}", entry.file_name(), e);
+    for filename in &to_delete {
+        if let Err(e) = std::fs::remove_file(backup_dir.join(filename)) {
+            warn!("Failed to delete old backup {:?}: {}", filename, e);
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y%m%d-%H%M%S").unwrap()
+    }
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn prunes_by_count_and_age() {
+        let backups = names(&[
+            "ai-toolbox-backup-20260101-080000.zip",
+            "ai-toolbox-backup-20260110-080000_nas.zip.enc",
+            "ai-toolbox-backup-20260120-080000.zip",
+            "ai-toolbox-backup-20260130-080000.zip",
+        ]);
+        let now = at("20260131-080000");
+
+        assert_eq!(
+            select_backups_to_prune(&backups, 2, 0, now),
+            names(&[
+                "ai-toolbox-backup-20260110-080000_nas.zip.enc",
+                "ai-toolbox-backup-20260101-080000.zip",
+            ])
+        );
+        assert_eq!(
+            select_backups_to_prune(&backups, 0, 15, now),
+            names(&[
+                "ai-toolbox-backup-20260110-080000_nas.zip.enc",
+                "ai-toolbox-backup-20260101-080000.zip",
+            ])
+        );
+        assert_eq!(
+            select_backups_to_prune(&backups, 3, 25, now),
+            names(&["ai-toolbox-backup-20260101-080000.zip"])
+        );
+        assert!(select_backups_to_prune(&backups, 0, 0, now).is_empty());
+    }
+
+    #[test]
+    fn age_rule_ignores_names_without_timestamp() {
+        let backups = names(&["ai-toolbox-backup-old.zip"]);
+        assert!(select_backups_to_prune(&backups, 0, 1, at("20260131-080000")).is_empty());
+        assert_eq!(
+            parse_backup_timestamp("ai-toolbox-backup-20260102-030405_host.zip"),
+            Some(at("20260102-030405"))
+        );
+    }
+}
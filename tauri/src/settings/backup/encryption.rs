@@ -0,0 +1,359 @@
+//! Passphrase-encrypted backup archives.
+//!
+//! An encrypted backup wraps the regular backup zip:
+//!
+//! ```text
+//! MAGIC (8 bytes) | header length (u32 LE) | header JSON | nonce (12 bytes) | AES-256-GCM(zip)
+//! ```
+//!
+//! The header stays readable so the backup meta can be inspected without the
+//! passphrase. Everything before the nonce is authenticated as associated data,
+//! so editing the header makes decryption fail.
+
+use std::io::Cursor;
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+
+use super::utils::{read_backup_meta_from_archive, BackupMeta};
+use crate::db::SqliteDbState;
+use crate::secret_store::cipher::{self, KdfParams, NONCE_LEN};
+use crate::settings::store;
+use crate::settings::types::AppSettings;
+
+pub const ENCRYPTED_BACKUP_MAGIC: &[u8; 8] = b"AITB-ENC";
+pub const ENCRYPTED_BACKUP_VERSION: u32 = 1;
+const HEADER_LEN_SIZE: usize = 4;
+const MAX_HEADER_LEN: usize = 64 * 1024;
+/// Upper bounds for KDF parameters read from an untrusted header.
+const MAX_KDF_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_KDF_ITERATIONS: u32 = 64;
+
+/// Error prefixes the frontend matches to prompt for a passphrase.
+pub const PASSPHRASE_REQUIRED_ERROR: &str = "BACKUP_PASSPHRASE_REQUIRED";
+pub const PASSPHRASE_INVALID_ERROR: &str = "BACKUP_PASSPHRASE_INVALID";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedBackupHeader {
+    pub version: u32,
+    pub salt: String,
+    #[serde(flatten)]
+    pub kdf: KdfParams,
+    pub created_at: String,
+    /// Copy of `backup_meta.json` from the wrapped zip
+    pub meta: Option<BackupMeta>,
+}
+
+pub fn backup_file_extension(encrypted: bool) -> &'static str {
+    if encrypted {
+        "zip.enc"
+    } else {
+        "zip"
+    }
+}
+
+pub fn is_backup_file_name(name: &str) -> bool {
+    name.starts_with("ai-toolbox-backup-") && (name.ends_with(".zip") || name.ends_with(".zip.enc"))
+}
+
+pub fn is_encrypted_backup(data: &[u8]) -> bool {
+    data.starts_with(ENCRYPTED_BACKUP_MAGIC)
+}
+
+/// Parse the header of an encrypted backup and return it with the length of
+/// the authenticated prefix (magic, length and header bytes).
+fn parse_header(data: &[u8]) -> Result<(EncryptedBackupHeader, usize), String> {
+    let length_start = ENCRYPTED_BACKUP_MAGIC.len();
+    let header_start = length_start + HEADER_LEN_SIZE;
+    if !is_encrypted_backup(data) || data.len() < header_start {
+        return Err("Not an encrypted backup archive".to_string());
+    }
+    let mut length_bytes = [0u8; HEADER_LEN_SIZE];
+    length_bytes.copy_from_slice(&data[length_start..header_start]);
+    let header_len = u32::from_le_bytes(length_bytes) as usize;
+    if header_len > MAX_HEADER_LEN || data.len() < header_start + header_len {
+        return Err("Encrypted backup header is truncated or too large".to_string());
+    }
+    let header_end = header_start + header_len;
+    let header: EncryptedBackupHeader = serde_json::from_slice(&data[header_start..header_end])
+        .map_err(|e| format!("Failed to parse encrypted backup header: {}", e))?;
+    if header.version > ENCRYPTED_BACKUP_VERSION {
+        return Err(format!(
+            "Encrypted backup version {} is newer than supported version {}",
+            header.version, ENCRYPTED_BACKUP_VERSION
+        ));
+    }
+    if header.kdf.memory_kib > MAX_KDF_MEMORY_KIB || header.kdf.iterations > MAX_KDF_ITERATIONS {
+        return Err("Encrypted backup uses unsupported key derivation parameters".to_string());
+    }
+    Ok((header, header_end))
+}
+
+pub fn read_encrypted_header(data: &[u8]) -> Option<EncryptedBackupHeader> {
+    parse_header(data).ok().map(|(header, _)| header)
+}
+
+/// Read the backup meta of a plain or encrypted archive without decrypting it.
+pub fn read_backup_meta(data: &[u8]) -> Option<BackupMeta> {
+    if is_encrypted_backup(data) {
+        return read_encrypted_header(data).and_then(|header| header.meta);
+    }
+    let mut archive = ZipArchive::new(Cursor::new(data)).ok()?;
+    read_backup_meta_from_archive(&mut archive)
+}
+
+pub fn encrypt_backup(zip_data: &[u8], passphrase: &str) -> Result<Vec<u8>, String> {
+    encrypt_backup_with_params(zip_data, passphrase, KdfParams::default())
+}
+
+fn encrypt_backup_with_params(
+    zip_data: &[u8],
+    passphrase: &str,
+    params: KdfParams,
+) -> Result<Vec<u8>, String> {
+    let salt = cipher::random_salt();
+    let key = cipher::derive_passphrase_key(passphrase, &salt, params)?;
+    let header = EncryptedBackupHeader {
+        version: ENCRYPTED_BACKUP_VERSION,
+        salt: base64::engine::general_purpose::STANDARD.encode(salt),
+        kdf: params,
+        created_at: chrono::Local::now().to_rfc3339(),
+        meta: read_backup_meta(zip_data),
+    };
+    let header_json = serde_json::to_vec(&header)
+        .map_err(|e| format!("Failed to serialize encrypted backup header: {}", e))?;
+
+    let mut output = Vec::with_capacity(
+        ENCRYPTED_BACKUP_MAGIC.len() + HEADER_LEN_SIZE + header_json.len() + zip_data.len() + 64,
+    );
+    output.extend_from_slice(ENCRYPTED_BACKUP_MAGIC);
+    output.extend_from_slice(&(header_json.len() as u32).to_le_bytes());
+    output.extend_from_slice(&header_json);
+    let (nonce, ciphertext) = cipher::seal_bytes(&key, zip_data, &output)
+        .map_err(|_| "Failed to encrypt backup archive".to_string())?;
+    output.extend_from_slice(&nonce);
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+/// Return the plain zip bytes of a backup, decrypting it when it is encrypted.
+pub fn decrypt_backup(data: Vec<u8>, passphrase: Option<&str>) -> Result<Vec<u8>, String> {
+    if !is_encrypted_backup(&data) {
+        return Ok(data);
+    }
+    let (header, body_start) = parse_header(&data)?;
+    let passphrase = passphrase
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| {
+            format!(
+                "{}: This backup is encrypted; enter its passphrase to restore it",
+                PASSPHRASE_REQUIRED_ERROR
+            )
+        })?;
+    let salt = base64::engine::general_purpose::STANDARD
+        .decode(&header.salt)
+        .map_err(|e| format!("Invalid encrypted backup salt: {}", e))?;
+    let key = cipher::derive_passphrase_key(passphrase, &salt, header.kdf)?;
+
+    let body = &data[body_start..];
+    if body.len() < NONCE_LEN {
+        return Err("Encrypted backup is truncated".to_string());
+    }
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+    cipher::open_bytes(&key, nonce, ciphertext, &data[..body_start]).map_err(|_| {
+        format!(
+            "{}: Incorrect backup passphrase, or the backup is corrupted",
+            PASSPHRASE_INVALID_ERROR
+        )
+    })
+}
+
+/// Passphrase for a new backup: an explicit one wins, otherwise the stored one
+/// when backup encryption is enabled. `None` means a plain zip.
+pub fn backup_passphrase(
+    settings: &AppSettings,
+    explicit: Option<String>,
+) -> Result<Option<String>, String> {
+    if let Some(passphrase) = explicit.filter(|value| !value.is_empty()) {
+        return Ok(Some(passphrase));
+    }
+    if !settings.backup_encryption_enabled {
+        return Ok(None);
+    }
+    let stored = &settings.backup_encryption_passphrase;
+    if stored.is_empty() {
+        return Err("Backup encryption is enabled but no backup passphrase is set".to_string());
+    }
+    if crate::secret_store::is_reference(stored) {
+        return Err(
+            "The secret store is locked; unlock it to use the stored backup passphrase".to_string(),
+        );
+    }
+    Ok(Some(stored.clone()))
+}
+
+/// Passphrase to try on restore: an explicit one wins, otherwise the stored one.
+fn restore_passphrase(settings: Option<&AppSettings>, explicit: Option<String>) -> Option<String> {
+    explicit.filter(|value| !value.is_empty()).or_else(|| {
+        settings
+            .map(|settings| settings.backup_encryption_passphrase.clone())
+            .filter(|stored| !stored.is_empty() && !crate::secret_store::is_reference(stored))
+    })
+}
+
+/// Decrypt downloaded or local backup bytes for restore, falling back to the
+/// stored passphrase when none was entered.
+pub fn decrypt_backup_for_restore(
+    sqlite_state: &SqliteDbState,
+    data: Vec<u8>,
+    passphrase: Option<String>,
+) -> Result<Vec<u8>, String> {
+    if !is_encrypted_backup(&data) {
+        return Ok(data);
+    }
+    let settings = store::load_settings_from_sqlite_state(sqlite_state).ok();
+    let passphrase = restore_passphrase(settings.as_ref(), passphrase);
+    decrypt_backup(data, passphrase.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    fn fast_params() -> KdfParams {
+        KdfParams {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        }
+    }
+
+    fn sample_zip() -> Vec<u8> {
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut zip = ZipWriter::new(&mut buffer);
+            let options = SimpleFileOptions::default();
+            zip.start_file("backup_meta.json", options).unwrap();
+            zip.write_all(br#"{"version":1,"cli_config_files_included":true}"#)
+                .unwrap();
+            zip.start_file("sqlite/ai-toolbox.db", options).unwrap();
+            zip.write_all(b"sk-secret-provider-key").unwrap();
+            zip.finish().unwrap();
+        }
+        buffer.into_inner()
+    }
+
+    #[test]
+    fn encrypted_backup_round_trips_and_hides_the_zip() {
+        let zip_data = sample_zip();
+        let encrypted =
+            encrypt_backup_with_params(&zip_data, "nas passphrase", fast_params()).unwrap();
+
+        assert!(is_encrypted_backup(&encrypted));
+        assert!(!encrypted
+            .windows(b"sk-secret-provider-key".len())
+            .any(|window| window == b"sk-secret-provider-key"));
+        assert_eq!(
+            decrypt_backup(encrypted, Some("nas passphrase")).unwrap(),
+            zip_data
+        );
+    }
+
+    #[test]
+    fn header_exposes_backup_meta_without_the_passphrase() {
+        let zip_data = sample_zip();
+        let encrypted = encrypt_backup_with_params(&zip_data, "pw", fast_params()).unwrap();
+
+        let header = read_encrypted_header(&encrypted).expect("header");
+        assert_eq!(header.version, ENCRYPTED_BACKUP_VERSION);
+        assert_eq!(header.kdf, fast_params());
+        assert!(
+            read_backup_meta(&encrypted)
+                .unwrap()
+                .cli_config_files_included
+        );
+        assert!(
+            read_backup_meta(&zip_data)
+                .unwrap()
+                .cli_config_files_included
+        );
+    }
+
+    #[test]
+    fn missing_or_wrong_passphrase_report_distinct_errors() {
+        let encrypted = encrypt_backup_with_params(&sample_zip(), "right", fast_params()).unwrap();
+
+        let missing = decrypt_backup(encrypted.clone(), None).unwrap_err();
+        assert!(missing.starts_with(PASSPHRASE_REQUIRED_ERROR), "{missing}");
+        let wrong = decrypt_backup(encrypted, Some("wrong")).unwrap_err();
+        assert!(wrong.starts_with(PASSPHRASE_INVALID_ERROR), "{wrong}");
+    }
+
+    #[test]
+    fn tampered_header_fails_authentication() {
+        let encrypted = encrypt_backup_with_params(&sample_zip(), "pw", fast_params()).unwrap();
+        let text = String::from_utf8_lossy(&encrypted).into_owned();
+        let offset = text.find("\"createdAt\":\"").expect("createdAt in header") + 13;
+        let mut tampered = encrypted.clone();
+        tampered[offset] = if tampered[offset] == b'1' { b'2' } else { b'1' };
+
+        let error = decrypt_backup(tampered, Some("pw")).unwrap_err();
+        assert!(error.starts_with(PASSPHRASE_INVALID_ERROR), "{error}");
+    }
+
+    #[test]
+    fn plain_zip_passes_through_unchanged() {
+        let zip_data = sample_zip();
+        assert_eq!(decrypt_backup(zip_data.clone(), None).unwrap(), zip_data);
+        assert_eq!(
+            decrypt_backup(zip_data.clone(), Some("ignored")).unwrap(),
+            zip_data
+        );
+    }
+
+    #[test]
+    fn backup_passphrase_prefers_explicit_then_stored_value() {
+        let mut settings = AppSettings::default();
+        assert_eq!(backup_passphrase(&settings, None).unwrap(), None);
+        assert_eq!(
+            backup_passphrase(&settings, Some("typed".to_string())).unwrap(),
+            Some("typed".to_string())
+        );
+
+        settings.backup_encryption_enabled = true;
+        assert!(backup_passphrase(&settings, None).is_err());
+        settings.backup_encryption_passphrase =
+            "secret://settings:app:backup_encryption_passphrase".to_string();
+        assert!(backup_passphrase(&settings, None)
+            .unwrap_err()
+            .contains("locked"));
+        assert_eq!(restore_passphrase(Some(&settings), None), None);
+
+        settings.backup_encryption_passphrase = "stored".to_string();
+        assert_eq!(
+            backup_passphrase(&settings, Some(String::new())).unwrap(),
+            Some("stored".to_string())
+        );
+        assert_eq!(
+            restore_passphrase(Some(&settings), None),
+            Some("stored".to_string())
+        );
+    }
+
+    #[test]
+    fn backup_file_names_cover_both_formats() {
+        assert!(is_backup_file_name("ai-toolbox-backup-20260101-120000.zip"));
+        assert!(is_backup_file_name(
+            "ai-toolbox-backup-20260101-120000_nas.zip.enc"
+        ));
+        assert!(!is_backup_file_name(
+            "ai-toolbox-backup-20260101-120000.enc"
+        ));
+        assert!(!is_backup_file_name("notes.zip"));
+    }
+}
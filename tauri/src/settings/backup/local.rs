@@ -1,19 +1,22 @@
 use chrono::Local;
 use std::fs::{self, File};
+use std::io::Cursor;
 use std::path::{Path, PathBuf};
 use tauri::Manager;
 use zip::write::SimpleFileOptions;
 use zip::{ZipArchive, ZipWriter};
 
+use super::encryption;
 use super::utils::{
-    clear_restored_cli_custom_roots, get_claude_desktop_settings_paths, get_claude_mcp_restore_path,
+    clear_restored_cli_custom_roots, create_backup_zip, get_claude_desktop_settings_paths, get_claude_mcp_restore_path,
     get_claude_restore_dir, get_codex_restore_dir, get_db_path, get_gemini_cli_restore_dir,
     get_grok_restore_dir, get_hermes_restore_dir, get_dsh_restore_dir,
-    get_image_assets_dir, get_opencode_auth_restore_path, get_opencode_restore_dir, get_skills_dir,
+    get_image_assets_dir, get_opencode_auth_restore_path, get_opencode_restore_dir, get_session_trash_dir, get_skills_dir,
     harden_restored_sensitive_file, push_restore_warning, read_backup_meta_from_archive,
     read_root_dir_override, record_restored_external_config_wsl_module,
     resolve_external_config_restore_output_path, resolve_restore_dir_override,
-    resolve_skills_restore_output_path, restore_claude_external_config_file,
+    resolve_session_trash_restore_output_path, resolve_skills_restore_output_path,
+    restore_claude_external_config_file,
     restore_custom_backup_entries, restore_sqlite_database_snapshot_from_zip,
     sanitize_restored_claude_database_for_current_os, should_filter_external_config_entry,
     should_reapply_applied_runtime, should_skip_external_config_on_restore,
@@ -49,6 +52,7 @@ fn set_pi_auth_file_permissions(_path: &Path) {}
 pub async fn backup_database(
     app_handle: tauri::AppHandle,
     backup_path: String,
+    passphrase: Option<String>,
 ) -> Result<String, String> {
     let db_path = get_db_path(&app_handle)?;
     let sqlite_state = app_handle.state::<SqliteDbState>();
@@ -56,6 +60,7 @@ pub async fn backup_database(
     let backup_image_assets_enabled = settings.backup_image_assets_enabled;
     let backup_cli_config_files_enabled = settings.backup_cli_config_files_enabled;
     let filter_rules = settings.backup_file_filter_rules.clone();
+    let passphrase = encryption::backup_passphrase(&settings, passphrase)?;
 
     // Ensure database directory exists
     if !db_path.exists() {
@@ -72,9 +77,29 @@ pub async fn backup_database(
 
     // Generate backup filename with timestamp
     let timestamp = Local::now().format("%Y%m%d-%H%M%S");
-    let backup_filename = format!("ai-toolbox-backup-{}.zip", timestamp);
+    let backup_filename = format!(
+        "ai-toolbox-backup-{}.{}",
+        timestamp,
+        encryption::backup_file_extension(passphrase.is_some())
+    );
     let backup_file_path = backup_dir.join(&backup_filename);
 
+    // Encrypted archives are sealed as a whole, so build the zip in memory first
+    if let Some(passphrase) = passphrase {
+        let zip_data = create_backup_zip(
+            &app_handle,
+            &db_path,
+            backup_image_assets_enabled,
+            backup_cli_config_files_enabled,
+            &filter_rules,
+        )
+        .await?;
+        let encrypted = encryption::encrypt_backup(&zip_data, &passphrase)?;
+        fs::write(&backup_file_path, encrypted)
+            .map_err(|e| format!("Failed to write backup file: {}", e))?;
+        return Ok(backup_file_path.to_string_lossy().to_string());
+    }
+
     let file = File::create(&backup_file_path)
         .map_err(|e| format!("Failed to create backup file: {}", e))?;
     let mut zip = ZipWriter::new(file);
@@ -101,6 +126,7 @@ pub async fn restore_database(
     app_handle: tauri::AppHandle,
     zip_file_path: String,
     skip_cli_custom_roots: Option<bool>,
+    passphrase: Option<String>,
 ) -> Result<RestoreResult, String> {
     let db_path = get_db_path(&app_handle)?;
     let zip_path = Path::new(&zip_file_path);
@@ -110,10 +136,15 @@ pub async fn restore_database(
         return Err("Backup file does not exist".to_string());
     }
 
-    // Open zip file
-    let file = File::open(zip_path).map_err(|e| format!("Failed to open backup file: {}", e))?;
-    let mut archive =
-        ZipArchive::new(file).map_err(|e| format!("Failed to read zip archive: {}", e))?;
+    // Open zip file, decrypting it first when it is an encrypted archive
+    let data = fs::read(zip_path).map_err(|e| format!("Failed to open backup file: {}", e))?;
+    let data = encryption::decrypt_backup_for_restore(
+        &app_handle.state::<SqliteDbState>(),
+        data,
+        passphrase,
+    )?;
+    let mut archive = ZipArchive::new(Cursor::new(data))
+        .map_err(|e| format!("Failed to read zip archive: {}", e))?;
 
     // Check if this is a new format backup (with db/ prefix) or old format
     let is_new_format = (0..archive.len()).any(|i| {
@@ -903,6 +934,24 @@ pub async fn restore_database(
                     .map_err(|e| format!("Failed to create image asset file: {}", e))?;
                 std::io::copy(&mut file, &mut outfile)
                     .map_err(|e| format!("Failed to extract image asset file: {}", e))?;
+            } else if let Some(relative_path) = file_name.strip_prefix("session-trash/") {
+                let session_trash_dir = get_session_trash_dir(&app_handle)?;
+                let Some(outpath) =
+                    resolve_session_trash_restore_output_path(&session_trash_dir, relative_path)?
+                else {
+                    continue;
+                };
+                if let Some(parent) = outpath.parent() {
+                    if !parent.exists() {
+                        fs::create_dir_all(parent).map_err(|e| {
+                            format!("Failed to create session trash directory: {}", e)
+                        })?;
+                    }
+                }
+                let mut outfile = File::create(&outpath)
+                    .map_err(|e| format!("Failed to create session trash file: {}", e))?;
+                std::io::copy(&mut file, &mut outfile)
+                    .map_err(|e| format!("Failed to extract session trash file: {}", e))?;
             }
         } else {
             // Old format: all files are database files
@@ -50,6 +50,7 @@ pub fn add_sqlite_database_snapshot_to_zip<W: Write + Seek>(
             .map_err(|error| format!("Failed to create SQLite backup snapshot: {error}"))
     });
 
+    let backup_result = backup_result.and_then(|_| inline_snapshot_secrets(&temp_path));
     if let Err(error) = backup_result {
         let _ = std::fs::remove_file(&temp_path);
         return Err(error);
@@ -72,6 +73,23 @@ pub fn add_sqlite_database_snapshot_to_zip<W: Write + Seek>(
     Ok(())
 }
 
+/// Resolve secret store references inside the exported snapshot so the archive
+/// restores on machines that do not hold this install's master key.
+fn inline_snapshot_secrets(snapshot_path: &Path) -> Result<(), String> {
+    let snapshot = rusqlite::Connection::open(snapshot_path).map_err(|error| {
+        format!(
+            "Failed to open SQLite backup snapshot {}: {error}",
+            snapshot_path.display()
+        )
+    })?;
+    if !crate::secret_store::inline_secrets_for_export(&snapshot)? {
+        log::warn!(
+            "Secret store is locked; backup keeps encrypted credentials and only restores where the same master key is available"
+        );
+    }
+    Ok(())
+}
+
 pub fn restore_sqlite_database_snapshot_from_zip<R: Read + Seek>(
     archive: &mut ZipArchive<R>,
     app_handle: &tauri::AppHandle,
@@ -184,7 +202,13 @@ fn restore_sqlite_snapshot_and_migrate(
     })();
 
     let _ = std::fs::remove_file(&safety_path);
-    result
+    result?;
+
+    // Restored snapshots carry plaintext credentials; re-seal them under the
+    // local master key (no-op when the store is disabled or locked).
+    sqlite_state
+        .with_conn(crate::secret_store::seal_all)
+        .map(|_| ())
 }
 
 #[cfg(not(target_os = "windows"))]
@@ -1499,6 +1523,37 @@ pub fn resolve_skills_restore_output_path(
     Ok(Some((fallback_path, warning)))
 }
 
+/// Output path for a `session-trash/` backup entry, rejecting entries that
+/// would land outside the session trash directory.
+pub fn resolve_session_trash_restore_output_path(
+    session_trash_dir: &Path,
+    relative_path: &str,
+) -> Result<Option<PathBuf>, String> {
+    let normalized = normalize_restore_entry_name(relative_path);
+    if normalized.ends_with('/') {
+        return Ok(None);
+    }
+
+    let mut output_path = session_trash_dir.to_path_buf();
+    let mut has_segment = false;
+    for raw_segment in normalized.trim_start_matches('/').split('/') {
+        let segment = raw_segment.trim();
+        if segment.is_empty() || segment == "." {
+            continue;
+        }
+        if segment == ".." || segment.contains('\0') || segment.contains(':') {
+            return Err(format!(
+                "Invalid session trash restore path: {}",
+                relative_path
+            ));
+        }
+        output_path.push(segment);
+        has_segment = true;
+    }
+
+    Ok(has_segment.then_some(output_path))
+}
+
 pub fn resolve_external_config_restore_output_path(
     restore_dir: &Path,
     relative_path: &str,
@@ -1698,6 +1753,10 @@ pub fn get_image_assets_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, St
     Ok(app_data_dir.join("image-studio").join("assets"))
 }
 
+pub fn get_session_trash_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    crate::coding::session_manager::session_trash_dir(app_handle)
+}
+
 /// Get models.dev.json cache file path if it exists
 pub fn get_models_cache_file() -> Option<PathBuf> {
     crate::coding::open_code::free_models::get_models_cache_path().filter(|p| p.exists())
@@ -2232,6 +2291,12 @@ pub async fn get_backup_image_assets_enabled_from_db(
     Ok(crate::settings::store::load_settings_from_sqlite_state(db)?.backup_image_assets_enabled)
 }
 
+pub fn get_backup_session_trash_enabled_from_db(
+    db: &crate::db::SqliteDbState,
+) -> Result<bool, String> {
+    Ok(crate::settings::store::load_settings_from_sqlite_state(db)?.backup_session_trash_enabled)
+}
+
 pub fn add_text_to_zip<W: Write + std::io::Seek>(
     zip: &mut ZipWriter<W>,
     zip_path: &str,
@@ -3301,6 +3366,11 @@ pub(crate) async fn write_backup_zip_contents<W: Write + Seek>(
         add_image_assets_to_zip(app_handle, zip, options)?;
     }
 
+    if get_backup_session_trash_enabled_from_db(&db_state)? {
+        let session_trash_dir = get_session_trash_dir(app_handle)?;
+        add_directory_contents_to_zip(zip, &session_trash_dir, "session-trash/", options)?;
+    }
+
     let backup_custom_entries = get_backup_custom_entries_from_db(&db).await?;
     add_custom_backup_entries_to_zip(zip, &backup_custom_entries, options)?;
 
@@ -3351,7 +3421,8 @@ mod tests {
         get_gemini_cli_prompt_backup_zip_path, is_always_backup_cli_tool, is_filesystem_root_directory,
         is_optional_backup_cli_tool, normalize_backup_storage_path, normalize_restore_entry_name,
         parse_post_restore_resync_wsl_modules, record_restored_external_config_wsl_module,
-        resolve_external_config_restore_output_path, restore_custom_backup_entries,
+        resolve_external_config_restore_output_path, resolve_session_trash_restore_output_path,
+        restore_custom_backup_entries,
         should_exclude_from_backup, should_filter_external_config_entry,
         should_reapply_applied_runtime, should_skip_external_config_on_restore,
         should_use_backup_root_overrides, should_use_root_override_for_tool,
@@ -3760,6 +3831,21 @@ mod tests {
         assert!(resolve_external_config_restore_output_path(root, "../../escape.json").is_err());
     }
 
+    #[test]
+    fn session_trash_restore_path_rejects_traversal() {
+        let root = Path::new("session-trash");
+        let safe_path = resolve_session_trash_restore_output_path(root, "123-abc/entry.json")
+            .expect("resolve safe path")
+            .expect("path should exist");
+        assert_eq!(safe_path, root.join("123-abc").join("entry.json"));
+        assert!(resolve_session_trash_restore_output_path(root, "123-abc/")
+            .unwrap()
+            .is_none());
+        assert!(resolve_session_trash_restore_output_path(root, "../escape.json").is_err());
+        assert!(resolve_session_trash_restore_output_path(root, "a/..\\..\\escape").is_err());
+        assert!(resolve_session_trash_restore_output_path(root, "C:/escape.json").is_err());
+    }
+
     #[test]
     fn codex_prompt_backup_path_preserves_active_prompt_file_name() {
         assert_eq!(
@@ -6,16 +6,18 @@ use std::path::Path;
 use tauri::Manager;
 use zip::ZipArchive;
 
+use super::encryption;
 use super::utils::{
     clear_restored_cli_custom_roots, create_backup_zip, get_claude_desktop_settings_paths,
     get_claude_mcp_restore_path, get_claude_restore_dir, get_codex_restore_dir, get_db_path,
     get_gemini_cli_restore_dir, get_grok_restore_dir, get_hermes_restore_dir, get_dsh_restore_dir,
     get_image_assets_dir, get_opencode_auth_restore_path,
-    get_opencode_restore_dir, get_skills_dir, harden_restored_sensitive_file,
+    get_opencode_restore_dir, get_session_trash_dir, get_skills_dir, harden_restored_sensitive_file,
     normalize_restore_entry_name, push_restore_warning, read_backup_meta_from_archive,
     read_root_dir_override, record_restored_external_config_wsl_module,
     resolve_external_config_restore_output_path, resolve_restore_dir_override,
-    resolve_skills_restore_output_path, restore_claude_external_config_file,
+    resolve_session_trash_restore_output_path, resolve_skills_restore_output_path,
+    restore_claude_external_config_file,
     restore_custom_backup_entries, restore_sqlite_database_snapshot_from_zip,
     sanitize_restored_claude_database_for_current_os, should_filter_external_config_entry,
     should_reapply_applied_runtime, should_skip_external_config_on_restore,
@@ -182,6 +184,7 @@ pub async fn test_webdav_connection(
     remote_path: String,
 ) -> Result<(), String> {
     info!("Testing WebDAV connection to: {}", url);
+    crate::secret_store::ensure_resolved(&password)?;
 
     // Build WebDAV URL
     let base_url = url.trim_end_matches('/');
@@ -237,8 +240,10 @@ pub async fn backup_to_webdav(
     password: String,
     remote_path: String,
     host_label: String,
+    passphrase: Option<String>,
 ) -> Result<String, String> {
     info!("Starting WebDAV backup to: {}", url);
+    crate::secret_store::ensure_resolved(&password)?;
 
     let db_path = get_db_path(&app_handle)?;
 
@@ -255,9 +260,10 @@ pub async fn backup_to_webdav(
     let backup_image_assets_enabled = settings.backup_image_assets_enabled;
     let backup_cli_config_files_enabled = settings.backup_cli_config_files_enabled;
     let filter_rules = settings.backup_file_filter_rules.clone();
+    let passphrase = encryption::backup_passphrase(&settings, passphrase)?;
 
     // Create backup zip in memory
-    let zip_data = create_backup_zip(
+    let mut zip_data = create_backup_zip(
         &app_handle,
         &db_path,
         backup_image_assets_enabled,
@@ -265,14 +271,18 @@ pub async fn backup_to_webdav(
         &filter_rules,
     )
     .await?;
+    if let Some(passphrase) = &passphrase {
+        zip_data = encryption::encrypt_backup(&zip_data, passphrase)?;
+    }
 
     // Generate backup filename with timestamp and optional host label
     let timestamp = Local::now().format("%Y%m%d-%H%M%S");
     let host = host_label.trim();
+    let extension = encryption::backup_file_extension(passphrase.is_some());
     let backup_filename = if host.is_empty() {
-        format!("ai-toolbox-backup-{}.zip", timestamp)
+        format!("ai-toolbox-backup-{}.{}", timestamp, extension)
     } else {
-        format!("ai-toolbox-backup-{}_{}.zip", timestamp, host)
+        format!("ai-toolbox-backup-{}_{}.{}", timestamp, host, extension)
     };
 
     // Build WebDAV URL
@@ -329,6 +339,7 @@ pub(crate) async fn list_webdav_backups_internal(
     remote_path: &str,
 ) -> Result<Vec<BackupFileInfo>, String> {
     info!("Listing WebDAV backups from: {}", url);
+    crate::secret_store::ensure_resolved(password)?;
 
     // Build WebDAV URL
     let base_url = url.trim_end_matches('/');
@@ -386,7 +397,7 @@ pub(crate) async fn list_webdav_backups_internal(
     // WebDAV servers use different namespace prefixes: <D:response>, <d:response>, or <response>
     // e.g. 坚果云 (Jianguoyun) uses lowercase <d:response>
     use regex::Regex;
-    let filename_re = Regex::new(r"ai-toolbox-backup-.*?\d{8}-\d{6}[^.]*\.zip").unwrap();
+    let filename_re = Regex::new(r"ai-toolbox-backup-.*?\d{8}-\d{6}[^.]*\.zip(?:\.enc)?").unwrap();
     let response_re = Regex::new(r"(?i)<[\w]*:?response[>\s]").unwrap();
     let size_re =
         Regex::new(r"(?i)<[\w]*:?getcontentlength>(\d+)</[\w]*:?getcontentlength>").unwrap();
@@ -452,6 +463,7 @@ pub(crate) async fn delete_webdav_backup_internal(
     filename: &str,
 ) -> Result<(), String> {
     info!("Deleting WebDAV backup: {}", filename);
+    crate::secret_store::ensure_resolved(password)?;
 
     // Build WebDAV URL
     let base_url = url.trim_end_matches('/');
@@ -517,11 +529,11 @@ pub async fn restore_from_webdav(
     remote_path: String,
     filename: String,
     skip_cli_custom_roots: Option<bool>,
+    passphrase: Option<String>,
 ) -> Result<RestoreResult, String> {
     let skip_cli_custom_roots = skip_cli_custom_roots.unwrap_or(false);
     info!("Starting WebDAV restore from: {}/{}", url, filename);
-
-    let db_path = get_db_path(&app_handle)?;
+    crate::secret_store::ensure_resolved(&password)?;
 
     // Build WebDAV URL
     let base_url = url.trim_end_matches('/');
@@ -568,6 +580,31 @@ pub async fn restore_from_webdav(
         }
     };
 
+    let restore_result = restore_from_backup_data(
+        &app_handle,
+        zip_data.to_vec(),
+        skip_cli_custom_roots,
+        passphrase,
+    )?;
+    info!("WebDAV restore completed successfully");
+    Ok(restore_result)
+}
+
+/// Restore a downloaded backup archive (plain or encrypted). Shared by the
+/// remote backup backends.
+pub(crate) fn restore_from_backup_data(
+    app_handle: &tauri::AppHandle,
+    zip_data: Vec<u8>,
+    skip_cli_custom_roots: bool,
+    passphrase: Option<String>,
+) -> Result<RestoreResult, String> {
+    let db_path = get_db_path(app_handle)?;
+    let zip_data = encryption::decrypt_backup_for_restore(
+        &app_handle.state::<SqliteDbState>(),
+        zip_data,
+        passphrase,
+    )?;
+
     info!("Extracting backup archive...");
 
     // Extract zip contents
@@ -601,9 +638,9 @@ pub async fn restore_from_webdav(
     let skipped_optional_cli_runtime = !include_cli_config_files;
     let backup_meta = read_backup_meta_from_archive(&mut archive);
 
-    let restored_sqlite = restore_sqlite_database_snapshot_from_zip(&mut archive, &app_handle)?;
+    let restored_sqlite = restore_sqlite_database_snapshot_from_zip(&mut archive, app_handle)?;
     if restored_sqlite {
-        sanitize_restored_claude_database_for_current_os(&app_handle)?;
+        sanitize_restored_claude_database_for_current_os(app_handle)?;
         // Only clear roots on the restored snapshot — never mutate the live DB when
         // the backup did not include/replace sqlite/.
         if skip_cli_custom_roots {
@@ -1320,7 +1357,7 @@ pub async fn restore_from_webdav(
                 }
             } else if file_name.starts_with("skills/") {
                 // Restore skills directory
-                let skills_dir = get_skills_dir(&app_handle)?;
+                let skills_dir = get_skills_dir(app_handle)?;
                 if !skills_dir.exists() {
                     fs::create_dir_all(&skills_dir)
                         .map_err(|e| format!("Failed to create skills directory: {}", e))?;
@@ -1352,7 +1389,7 @@ pub async fn restore_from_webdav(
                     continue;
                 }
 
-                let image_assets_dir = get_image_assets_dir(&app_handle)?;
+                let image_assets_dir = get_image_assets_dir(app_handle)?;
                 if !image_assets_dir.exists() {
                     fs::create_dir_all(&image_assets_dir)
                         .map_err(|e| format!("Failed to create image assets directory: {}", e))?;
@@ -1370,6 +1407,24 @@ pub async fn restore_from_webdav(
                     .map_err(|e| format!("Failed to create image asset file: {}", e))?;
                 std::io::copy(&mut file, &mut outfile)
                     .map_err(|e| format!("Failed to extract image asset file: {}", e))?;
+            } else if let Some(relative_path) = file_name.strip_prefix("session-trash/") {
+                let session_trash_dir = get_session_trash_dir(app_handle)?;
+                let Some(outpath) =
+                    resolve_session_trash_restore_output_path(&session_trash_dir, relative_path)?
+                else {
+                    continue;
+                };
+                if let Some(parent) = outpath.parent() {
+                    if !parent.exists() {
+                        fs::create_dir_all(parent).map_err(|e| {
+                            format!("Failed to create session trash directory: {}", e)
+                        })?;
+                    }
+                }
+                let mut outfile = std::fs::File::create(&outpath)
+                    .map_err(|e| format!("Failed to create session trash file: {}", e))?;
+                std::io::copy(&mut file, &mut outfile)
+                    .map_err(|e| format!("Failed to extract session trash file: {}", e))?;
             }
         } else {
             // Old format: all files are database files
@@ -1398,8 +1453,7 @@ pub async fn restore_from_webdav(
     let need_reapply =
         should_reapply_applied_runtime(skipped_optional_cli_runtime, backup_meta.as_ref());
     restore_result.will_reapply_applied = need_reapply;
-    write_post_restore_flags(&app_handle, need_reapply, &restored_wsl_modules)?;
+    write_post_restore_flags(app_handle, need_reapply, &restored_wsl_modules)?;
 
-    info!("WebDAV restore completed successfully");
     Ok(restore_result)
 }
@@ -95,6 +95,8 @@ pub struct AppSettings {
     pub last_backup_time: Option<String>,
     /// Include generated image files in backup zip (default: true)
     pub backup_image_assets_enabled: bool,
+    /// Include the session manager trash in backup zip (default: false)
+    pub backup_session_trash_enabled: bool,
     /// Include optional (DB-backed) CLI runtime files under external-configs/ in backup zip
     /// (Codex / Claude / Grok / Gemini CLI; default: true).
     /// When false, those tools skip packaging/restore and re-apply from SQLite after restore.
@@ -102,6 +104,10 @@ pub struct AppSettings {
     pub backup_cli_config_files_enabled: bool,
     /// User-defined files/directories to include in backup zip
     pub backup_custom_entries: Vec<BackupCustomEntry>,
+    /// Encrypt new backup archives with `backup_encryption_passphrase` (default: false)
+    pub backup_encryption_enabled: bool,
+    /// Passphrase used for encrypted backups and restores; sealed by the secret store
+    pub backup_encryption_passphrase: String,
     /// Launch on startup (default: true)
     pub launch_on_startup: bool,
     /// Minimize to tray on close instead of exiting (default: true)
@@ -120,6 +126,8 @@ pub struct AppSettings {
     pub auto_backup_interval_days: u32,
     /// Max number of auto backups to keep, 0 = unlimited (default: 10)
     pub auto_backup_max_keep: u32,
+    /// Delete auto backups older than this many days, 0 = unlimited (default: 0)
+    pub auto_backup_max_age_days: u32,
     /// Last auto backup time in ISO 8601 format
     pub last_auto_backup_time: Option<String>,
     /// Auto check for updates on startup (default: true)
@@ -162,8 +170,11 @@ impl Default for AppSettings {
             s3: S3Config::default(),
             last_backup_time: None,
             backup_image_assets_enabled: true,
+            backup_session_trash_enabled: false,
             backup_cli_config_files_enabled: true,
             backup_custom_entries: Vec::new(),
+            backup_encryption_enabled: false,
+            backup_encryption_passphrase: String::new(),
             launch_on_startup: true,
             minimize_to_tray_on_close: true,
             start_minimized: false,
@@ -173,6 +184,7 @@ impl Default for AppSettings {
             auto_backup_enabled: false,
             auto_backup_interval_days: 7,
             auto_backup_max_keep: 10,
+            auto_backup_max_age_days: 0,
             last_auto_backup_time: None,
             auto_check_update: true,
             visible_tabs: vec![
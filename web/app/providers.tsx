@@ -20,6 +20,7 @@ import {
   fetchRemoteGatewayProviderProfiles,
   fetchRemoteModelPricing,
   GITHUB_REPO,
+  type GatewayBudgetExceededEvent,
   type UpdateInfo,
 } from '@/services';
 import { restartApp } from '@/services/settingsApi';
@@ -220,6 +221,26 @@ const AppInitializer: React.FC<{ children: React.ReactNode }> = ({ children }) =
   // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [notification]);
 
+  // Budget events come from the gateway hot path, so surface them no matter
+  // which page is open.
+  React.useEffect(() => {
+    const unlisten = listen<GatewayBudgetExceededEvent>('gateway-budget-exceeded', (event) => {
+      const { rule_name, action, spent_usd, limit_usd, spent_tokens, limit_tokens } = event.payload;
+      notification.warning({
+        key: `gateway-budget-${event.payload.rule_id}`,
+        message: i18n.t('settings.gateway.budgets.exceededTitle', { name: rule_name }),
+        description: i18n.t(`settings.gateway.budgets.exceeded.${action}`, {
+          spent: limit_usd ? `$${spent_usd} / $${limit_usd}` : `${spent_tokens} / ${limit_tokens ?? '-'}`,
+        }),
+        duration: 8,
+      });
+    });
+
+    return () => {
+      unlisten.then((fn) => fn()).catch(console.error);
+    };
+  }, [notification]);
+
   // Keep a global fallback for tray-driven config changes so inactive pages and
   // subpanels that do not maintain their own listeners still resync to disk state.
   React.useEffect(() => {
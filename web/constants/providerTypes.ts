@@ -40,3 +40,14 @@ export const PROVIDER_TYPES: ProviderTypeOption[] = [
   { value: '@ai-sdk/togetherai', label: 'Together.ai' },
   { value: '@ai-sdk/xai', label: 'xAI Grok' },
 ];
+
+/**
+ * Provider types the gateway routes as Azure OpenAI (deployment-scoped URLs,
+ * `api-key` auth). Matches `is_azure_openai_provider` on the Rust side.
+ */
+export const AZURE_PROVIDER_TYPES = ['azure', 'azure-openai', 'azureopenai', '@ai-sdk/azure'];
+
+export const isAzureProviderType = (providerType?: string | null): boolean => {
+  const normalized = providerType?.trim().toLowerCase().replace(/[_ ]/g, '-');
+  return !!normalized && AZURE_PROVIDER_TYPES.includes(normalized);
+};
@@ -156,7 +156,7 @@ const ClaudeProviderCard: React.FC<ClaudeProviderCardProps> = ({
   );
   const gatewayCanApplyProxy = canApplyProviderWithGatewayProxy(gatewayStatus);
   const gatewayMode = gatewayStatus?.mode ?? null;
-  const gatewayFailoverActive = gatewayMode === 'failover';
+  const gatewayFailoverActive = gatewayMode === 'failover' || gatewayMode === 'load_balance';
   const gatewayProxyActive = gatewayMode === 'single' || gatewayFailoverActive;
   const priorityEntry = gatewayFailoverActive
     ? gatewayStatus?.provider_priorities.find((entry) => entry.provider_id === provider.id)
@@ -71,6 +71,7 @@ import {
   getGatewayProviderProfilesVersion,
   isGatewayConfigFlagEnabled,
   providerNeedsGatewayProxy,
+  isGatewayReengageMode,
   saveProviderWithGatewayReengage,
   subscribeGatewayProviderProfiles,
 } from '@/features/coding/shared/gateway';
@@ -107,6 +108,7 @@ import {
 import type { OpenCodeAllApiHubProvider } from '@/services/opencodeApi';
 import {
   engageProxyGatewayFailover,
+  engageProxyGatewayLoadBalance,
   engageProxyGatewaySingle,
   restoreProxyGatewayCliDirect,
   type GatewayCliTakeoverStatus,
@@ -983,13 +985,14 @@ const ClaudeCodePage: React.FC = () => {
       const gatewayModeBeforeSave = gatewayCliStatus?.mode;
       const shouldReengageGatewayProxy =
         Boolean(editingProvider && !isCopyMode && !isLocalTemp && editingProvider.isApplied) &&
-        (gatewayModeBeforeSave === 'single' || gatewayModeBeforeSave === 'failover');
+        isGatewayReengageMode(gatewayModeBeforeSave);
 
       await saveProviderWithGatewayReengage({
         gatewayMode: shouldReengageGatewayProxy ? gatewayModeBeforeSave : null,
         restoreDirect: () => restoreProxyGatewayCliDirect('claude'),
         engageSingle: () => engageProxyGatewaySingle('claude', savedProviderId),
         engageFailover: () => engageProxyGatewayFailover('claude'),
+        engageLoadBalance: () => engageProxyGatewayLoadBalance('claude'),
         onGatewayStatusChange: setGatewayCliStatus,
         saveProvider: async () => {
           if (isLocalTemp) {
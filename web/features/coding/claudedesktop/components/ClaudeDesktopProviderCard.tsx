@@ -199,7 +199,7 @@ const ClaudeDesktopProviderCard: React.FC<ClaudeDesktopProviderCardProps> = ({
   );
   const gatewayCanApplyProxy = canApplyProviderWithGatewayProxy(gatewayStatus);
   const gatewayMode = gatewayStatus?.mode ?? null;
-  const gatewayFailoverActive = gatewayMode === 'failover';
+  const gatewayFailoverActive = gatewayMode === 'failover' || gatewayMode === 'load_balance';
   const gatewayProxyActive = gatewayMode === 'single' || gatewayFailoverActive;
   const priorityEntry = gatewayFailoverActive
     ? gatewayStatus?.provider_priorities.find((entry) => entry.provider_id === provider.id)
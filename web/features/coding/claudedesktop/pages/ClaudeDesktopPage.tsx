@@ -63,6 +63,7 @@ import { SessionManagerPanel } from '@/features/coding/shared/sessionManager';
 import { useRefreshStore, useSettingsStore } from '@/stores';
 import {
   engageProxyGatewayFailover,
+  engageProxyGatewayLoadBalance,
   engageProxyGatewaySingle,
   restoreProxyGatewayCliDirect,
   type GatewayCliTakeoverStatus,
@@ -75,6 +76,7 @@ import {
   getGatewayProviderApiFormatFromMeta,
   getGatewayProviderProfilesVersion,
   providerNeedsGatewayProxy,
+  isGatewayReengageMode,
   saveProviderWithGatewayReengage,
   subscribeGatewayProviderProfiles,
 } from '@/features/coding/shared/gateway';
@@ -611,13 +613,14 @@ const ClaudeDesktopPage: React.FC = () => {
       const gatewayModeBeforeSave = gatewayCliStatus?.mode;
       const shouldReengageGatewayProxy =
         Boolean(editingProvider && !isCopyMode && editingProvider.isApplied) &&
-        (gatewayModeBeforeSave === 'single' || gatewayModeBeforeSave === 'failover');
+        isGatewayReengageMode(gatewayModeBeforeSave);
 
       await saveProviderWithGatewayReengage({
         gatewayMode: shouldReengageGatewayProxy ? gatewayModeBeforeSave : null,
         restoreDirect: () => restoreProxyGatewayCliDirect('claude_desktop'),
         engageSingle: () => engageProxyGatewaySingle('claude_desktop', savedProvider?.id || ''),
         engageFailover: () => engageProxyGatewayFailover('claude_desktop'),
+        engageLoadBalance: () => engageProxyGatewayLoadBalance('claude_desktop'),
         onGatewayStatusChange: setGatewayCliStatus,
         saveProvider: async () => {
           const category = values.category || editingProvider?.category || 'custom';
@@ -16,9 +16,14 @@ import type { CodexApiFormat, CodexCatalogModel, CodexProvider, CodexProviderFor
 import { fetchCodexOfficialModels } from '@/services/codexApi';
 import { readCurrentOpenCodeProviders } from '@/services/opencodeApi';
 import type { FetchedModel, FetchModelsResponse } from '@/components/common/FetchModelsModal/types';
+import AzureOpenAICollapse from '@/features/coding/shared/providerAzure/AzureOpenAICollapse';
 import BillingConfigCollapse from '@/features/coding/shared/providerBilling/BillingConfigCollapse';
 import CustomHeadersCollapse from '@/features/coding/shared/providerHeaders/CustomHeadersCollapse';
 import ProviderNotesCollapse from '@/features/coding/shared/providerConfig/ProviderNotesCollapse';
+import {
+  getAzureConfigFromMeta,
+  mergeAzureConfigIntoMeta,
+} from '@/features/coding/shared/providerAzure/azureConfigUtils';
 import {
   getBillingConfigFromMeta,
   mergeBillingConfigIntoMeta,
@@ -305,6 +310,7 @@ const CodexProviderFormModal: React.FC<CodexProviderFormModalProps> = ({
   const [currentBaseUrl, setCurrentBaseUrl] = React.useState<string>('');
   const [billingConfig, setBillingConfig] = React.useState(() => getBillingConfigFromMeta(provider?.meta));
   const [customHeaders, setCustomHeaders] = React.useState(() => getCustomHeadersFromMeta(provider?.meta));
+  const [azureConfig, setAzureConfig] = React.useState(() => getAzureConfigFromMeta(provider?.meta));
   const gatewayProviderProfilesVersion = React.useSyncExternalStore(
     subscribeGatewayProviderProfiles,
     getGatewayProviderProfilesVersion,
@@ -428,6 +434,7 @@ const CodexProviderFormModal: React.FC<CodexProviderFormModalProps> = ({
     }
     setBillingConfig(getBillingConfigFromMeta(provider?.meta));
     setCustomHeaders(getCustomHeadersFromMeta(provider?.meta));
+    setAzureConfig(getAzureConfigFromMeta(provider?.meta));
 
     if (provider) {
       let settingsConfig: CodexSettingsConfig = {};
@@ -791,20 +798,25 @@ const CodexProviderFormModal: React.FC<CodexProviderFormModalProps> = ({
         providerEndpointId: selectedEndpoint?.id,
         settingsConfig: finalSettingsConfig,
         apiFormat: selectedApiFormat,
-        meta: mergeCustomHeadersIntoMeta(
-          mergeBillingConfigIntoMeta(
-            mergeGatewayMetaIntoProviderMeta(
-              provider?.meta,
-              gatewayProfile,
-              gatewayProfile ? undefined : selectedApiFormat,
+        meta: mergeAzureConfigIntoMeta(
+          mergeCustomHeadersIntoMeta(
+            mergeBillingConfigIntoMeta(
+              mergeGatewayMetaIntoProviderMeta(
+                provider?.meta,
+                gatewayProfile,
+                gatewayProfile ? undefined : selectedApiFormat,
+              ),
+              selectedCategory === 'official'
+                ? { enabled: false, pricingModelSource: 'inherit' }
+                : billingConfig,
             ),
             selectedCategory === 'official'
-              ? { enabled: false, pricingModelSource: 'inherit' }
-              : billingConfig,
+              ? { enabled: false, headers: [] }
+              : customHeaders,
           ),
           selectedCategory === 'official'
-            ? { enabled: false, headers: [] }
-            : customHeaders,
+            ? { ...azureConfig, enabled: false }
+            : azureConfig,
         ),
         notes: submittedValues.notes,
         sourceProviderId: mode === 'import' ? selectedProvider?.id : undefined,
@@ -1289,6 +1301,15 @@ const CodexProviderFormModal: React.FC<CodexProviderFormModalProps> = ({
         </Form.Item>
       )}
 
+      {!isOfficialMode && (
+        <Form.Item wrapperCol={sectionWrapperCol}>
+          <AzureOpenAICollapse
+            value={azureConfig}
+            onChange={setAzureConfig}
+          />
+        </Form.Item>
+      )}
+
       <Form.Item name="notes" wrapperCol={sectionWrapperCol}>
         <ProviderNotesCollapse
           title={t('codex.provider.notes')}
@@ -92,6 +92,7 @@ import {
   getGatewayProviderProfilesVersion,
   openAiApiFormatFromBaseUrl,
   providerNeedsGatewayProxy,
+  isGatewayReengageMode,
   saveProviderWithGatewayReengage,
   subscribeGatewayProviderProfiles,
 } from '@/features/coding/shared/gateway';
@@ -132,6 +133,7 @@ import { extractCodexBaseUrl, extractCodexModel } from '@/utils/codexConfigUtils
 import { parseCodexSettingsConfig } from '../utils/codexSettingsConfig';
 import {
   engageProxyGatewayFailover,
+  engageProxyGatewayLoadBalance,
   engageProxyGatewaySingle,
   restoreProxyGatewayCliDirect,
   type GatewayCliTakeoverStatus,
@@ -1246,13 +1248,14 @@ const CodexPage: React.FC = () => {
       const gatewayModeBeforeSave = gatewayCliStatus?.mode;
       const shouldReengageGatewayProxy =
         Boolean(editingProvider && !isCopyMode && !isLocalTemp && editingProvider.isApplied) &&
-        (gatewayModeBeforeSave === 'single' || gatewayModeBeforeSave === 'failover');
+        isGatewayReengageMode(gatewayModeBeforeSave);
 
       await saveProviderWithGatewayReengage({
         gatewayMode: shouldReengageGatewayProxy ? gatewayModeBeforeSave : null,
         restoreDirect: () => restoreProxyGatewayCliDirect('codex'),
         engageSingle: () => engageProxyGatewaySingle('codex', savedProviderId),
         engageFailover: () => engageProxyGatewayFailover('codex'),
+        engageLoadBalance: () => engageProxyGatewayLoadBalance('codex'),
         onGatewayStatusChange: setGatewayCliStatus,
         saveProvider: async () => {
           if (isLocalTemp) {
@@ -491,10 +491,22 @@ const GatewayRequestsView: React.FC<GatewayRequestsViewProps> = ({ refreshKey =
           <code>{requestLineText(detail, t('gateway.page.requests.requestPathUnavailable'))}</code>
           <span>{t('gateway.page.requests.fields.provider')}</span>
           <strong>{providerDisplayName(t, detail.provider_id, detail.provider_name)}</strong>
+          {detail.client_label && (
+            <>
+              <span>{t('gateway.page.requests.fields.client')}</span>
+              <strong>{detail.client_label}</strong>
+            </>
+          )}
           <span>{t('gateway.page.requests.fields.model')}</span>
           <strong>{requestDisplay.modelApplicable ? requestDisplay.modelText : t('gateway.page.requests.notApplicable')}</strong>
           <span>{t('gateway.page.requests.fields.status')}</span>
           <strong>{detail.status_code ?? '-'}</strong>
+          {detail.cache_hit && (
+            <>
+              <span>{t('gateway.page.requests.fields.responseCache')}</span>
+              <strong>{t('gateway.page.requests.cacheHit')}</strong>
+            </>
+          )}
           {detail.upstream_status_code != null && (
             <>
               <span>{t('gateway.page.requests.fields.upstreamStatus')}</span>
@@ -13,6 +13,7 @@ import {
   RefreshCw,
   Server,
   Terminal,
+  Users,
   Zap,
 } from 'lucide-react';
 import { useTranslation } from 'react-i18next';
@@ -29,11 +30,13 @@ import {
 } from 'recharts';
 import { ManagementSegmented } from '../../shared/management';
 import {
+  getProxyGatewayClientStats,
   getProxyGatewayModelStats,
   getProxyGatewayProviderStats,
   getProxyGatewayUsageSummary,
   getProxyGatewayUsageTrends,
   type GatewayCliKey,
+  type GatewayClientStats,
   type GatewayModelStats,
   type GatewayProviderStats,
   type GatewayUsageSummary,
@@ -56,7 +59,7 @@ import styles from './GatewayStatisticsView.module.less';
 const { RangePicker } = DatePicker;
 
 type GatewayCliFilter = 'all' | GatewayCliKey;
-type StatsTabKey = 'providers' | 'models';
+type StatsTabKey = 'providers' | 'models' | 'clients';
 type TrendSeriesKey = 'input' | 'output' | 'cache' | 'cost';
 
 interface GatewayStatisticsViewProps {
@@ -68,6 +71,7 @@ interface StatisticsState {
   trends: GatewayUsageTrendPoint[];
   providerStats: GatewayProviderStats[];
   modelStats: GatewayModelStats[];
+  clientStats: GatewayClientStats[];
 }
 
 const emptyState: StatisticsState = {
@@ -75,6 +79,7 @@ const emptyState: StatisticsState = {
   trends: [],
   providerStats: [],
   modelStats: [],
+  clientStats: [],
 };
 
 const cliOptions: GatewayCliFilter[] = ['all', 'claude', 'codex', 'grok', 'gemini'];
@@ -192,7 +197,7 @@ const GatewayStatisticsView: React.FC<GatewayStatisticsViewProps> = ({ refreshKe
     setError(null);
     try {
       const resolvedRange = resolveGatewayUsageRange(range);
-      const [summary, trends, providerStats, modelStats] = await Promise.all([
+      const [summary, trends, providerStats, modelStats, clientStats] = await Promise.all([
         getProxyGatewayUsageSummary(
           resolvedRange.startDate,
           resolvedRange.endDate,
@@ -213,8 +218,13 @@ const GatewayStatisticsView: React.FC<GatewayStatisticsViewProps> = ({ refreshKe
           resolvedRange.endDate,
           effectiveCliKey,
         ),
+        getProxyGatewayClientStats(
+          resolvedRange.startDate,
+          resolvedRange.endDate,
+          effectiveCliKey,
+        ),
       ]);
-      setState({ summary, trends, providerStats, modelStats });
+      setState({ summary, trends, providerStats, modelStats, clientStats });
     } catch (loadError) {
       setError(t('gateway.page.statistics.loadFailed', { error: formatGatewayError(loadError) }));
     } finally {
@@ -361,6 +371,55 @@ const GatewayStatisticsView: React.FC<GatewayStatisticsViewProps> = ({ refreshKe
     },
   ];
 
+  const clientColumns: ColumnsType<GatewayClientStats> = [
+    {
+      title: t('gateway.page.statistics.columns.client'),
+      dataIndex: 'client_label',
+      render: (value: string) => (
+        <div className={styles.tableMainCell}>
+          <strong>{value}</strong>
+        </div>
+      ),
+    },
+    {
+      title: t('gateway.page.statistics.columns.requests'),
+      dataIndex: 'request_count',
+      width: 110,
+      align: 'right',
+      render: (value: number) => formatInteger(value),
+    },
+    {
+      title: t('gateway.page.statistics.columns.tokens'),
+      dataIndex: 'total_tokens',
+      width: 130,
+      align: 'right',
+      render: (value: number) => formatCompactInteger(value),
+    },
+    {
+      title: t('gateway.page.statistics.columns.cost'),
+      dataIndex: 'total_cost_usd',
+      width: 120,
+      align: 'right',
+      render: (value: string) => formatUsd(value, 6),
+    },
+    {
+      title: t('gateway.page.statistics.columns.successRate'),
+      dataIndex: 'success_rate',
+      width: 110,
+      align: 'right',
+      render: (value: number) => (
+        <span style={{ color: statusColor(value) }}>{value.toFixed(1)}%</span>
+      ),
+    },
+    {
+      title: t('gateway.page.statistics.columns.latency'),
+      dataIndex: 'avg_latency_ms',
+      width: 110,
+      align: 'right',
+      render: (value: number) => formatDuration(value),
+    },
+  ];
+
   return (
     <div className={styles.viewStack} aria-busy={loading}>
       {error ? (
@@ -612,8 +671,10 @@ const GatewayStatisticsView: React.FC<GatewayStatisticsViewProps> = ({ refreshKe
           <span>
             {activeStatsTab === 'providers' ? (
               <Server className={styles.panelIcon} size={14} aria-hidden="true" />
-            ) : (
+            ) : activeStatsTab === 'models' ? (
               <Gauge className={styles.panelIcon} size={14} aria-hidden="true" />
+            ) : (
+              <Users className={styles.panelIcon} size={14} aria-hidden="true" />
             )}
             {t('gateway.page.statistics.breakdown')}
           </span>
@@ -622,6 +683,7 @@ const GatewayStatisticsView: React.FC<GatewayStatisticsViewProps> = ({ refreshKe
             options={[
               { value: 'providers', label: t('gateway.page.statistics.providerStats') },
               { value: 'models', label: t('gateway.page.statistics.modelStats') },
+              { value: 'clients', label: t('gateway.page.statistics.clientStats') },
             ]}
             onChange={setActiveStatsTab}
             ariaLabel={t('gateway.page.statistics.breakdown')}
@@ -637,7 +699,7 @@ const GatewayStatisticsView: React.FC<GatewayStatisticsViewProps> = ({ refreshKe
             pagination={false}
             scroll={{ x: 760 }}
           />
-        ) : (
+        ) : activeStatsTab === 'models' ? (
           <Table
             rowKey={(record) => `${record.cli_key}:${record.model}`}
             size="small"
@@ -647,6 +709,17 @@ const GatewayStatisticsView: React.FC<GatewayStatisticsViewProps> = ({ refreshKe
             pagination={false}
             scroll={{ x: 680 }}
           />
+        ) : (
+          <Table
+            rowKey="client_label"
+            size="small"
+            columns={clientColumns}
+            dataSource={state.clientStats}
+            loading={loading}
+            pagination={false}
+            scroll={{ x: 760 }}
+            locale={{ emptyText: t('gateway.page.statistics.clientStatsEmpty') }}
+          />
         )}
       </section>
 
@@ -1,8 +1,8 @@
 import React from 'react';
 import { Button, Form, Input, Modal, message } from 'antd';
-import { Plus, Save } from 'lucide-react';
+import { Plus, Save, Trash2 } from 'lucide-react';
 import { useTranslation } from 'react-i18next';
-import { upsertModelPricing, type ModelPricing } from '@/services';
+import { upsertModelPricing, type ModelPricing, type ModelPricingTier } from '@/services';
 import styles from './ModelPricingModal.module.less';
 
 interface ModelPricingEditModalProps {
@@ -20,6 +20,19 @@ interface ModelPricingFormValues {
   output_cost_per_million: string;
   cache_read_cost_per_million: string;
   cache_creation_cost_per_million: string;
+  tiers: ModelPricingTierFormValues[];
+  reasoning_cost_per_million: string;
+  image_cost: string;
+  request_cost: string;
+  batch_discount_percent: string;
+}
+
+interface ModelPricingTierFormValues {
+  above_input_tokens: string;
+  input_cost_per_million: string;
+  output_cost_per_million: string;
+  cache_read_cost_per_million: string;
+  cache_creation_cost_per_million: string;
 }
 
 const costPattern = /^\d+(?:\.\d+)?$/;
@@ -31,8 +44,34 @@ const defaultPricing: ModelPricing = {
   output_cost_per_million: '0',
   cache_read_cost_per_million: '0',
   cache_creation_cost_per_million: '0',
+  tiers: [],
+  reasoning_cost_per_million: null,
+  image_cost: null,
+  request_cost: null,
+  batch_discount_percent: null,
 };
 
+const emptyTier: ModelPricingTierFormValues = {
+  above_input_tokens: '200000',
+  input_cost_per_million: '',
+  output_cost_per_million: '',
+  cache_read_cost_per_million: '',
+  cache_creation_cost_per_million: '',
+};
+
+const optionalValue = (value: string | undefined) => {
+  const trimmedValue = value?.trim() ?? '';
+  return trimmedValue ? trimmedValue : null;
+};
+
+const toTier = (values: ModelPricingTierFormValues): ModelPricingTier => ({
+  above_input_tokens: Number(values.above_input_tokens.trim()),
+  input_cost_per_million: values.input_cost_per_million.trim(),
+  output_cost_per_million: values.output_cost_per_million.trim(),
+  cache_read_cost_per_million: optionalValue(values.cache_read_cost_per_million),
+  cache_creation_cost_per_million: optionalValue(values.cache_creation_cost_per_million),
+});
+
 const toFormValues = (pricing: ModelPricing): ModelPricingFormValues => ({
   model_id: pricing.model_id,
   display_name: pricing.display_name,
@@ -40,6 +79,17 @@ const toFormValues = (pricing: ModelPricing): ModelPricingFormValues => ({
   output_cost_per_million: pricing.output_cost_per_million,
   cache_read_cost_per_million: pricing.cache_read_cost_per_million,
   cache_creation_cost_per_million: pricing.cache_creation_cost_per_million,
+  tiers: (pricing.tiers ?? []).map((tier) => ({
+    above_input_tokens: String(tier.above_input_tokens),
+    input_cost_per_million: tier.input_cost_per_million,
+    output_cost_per_million: tier.output_cost_per_million,
+    cache_read_cost_per_million: tier.cache_read_cost_per_million ?? '',
+    cache_creation_cost_per_million: tier.cache_creation_cost_per_million ?? '',
+  })),
+  reasoning_cost_per_million: pricing.reasoning_cost_per_million ?? '',
+  image_cost: pricing.image_cost ?? '',
+  request_cost: pricing.request_cost ?? '',
+  batch_discount_percent: pricing.batch_discount_percent ?? '',
 });
 
 const trimFormValues = (values: ModelPricingFormValues): ModelPricing => ({
@@ -49,6 +99,11 @@ const trimFormValues = (values: ModelPricingFormValues): ModelPricing => ({
   output_cost_per_million: values.output_cost_per_million.trim(),
   cache_read_cost_per_million: values.cache_read_cost_per_million.trim(),
   cache_creation_cost_per_million: values.cache_creation_cost_per_million.trim(),
+  tiers: (values.tiers ?? []).map(toTier),
+  reasoning_cost_per_million: optionalValue(values.reasoning_cost_per_million),
+  image_cost: optionalValue(values.image_cost),
+  request_cost: optionalValue(values.request_cost),
+  batch_discount_percent: optionalValue(values.batch_discount_percent),
 });
 
 const ModelPricingEditModal: React.FC<ModelPricingEditModalProps> = ({
@@ -80,6 +135,39 @@ const ModelPricingEditModal: React.FC<ModelPricingEditModalProps> = ({
     [t],
   );
 
+  const validateOptionalCost = React.useCallback(
+    (_: unknown, value?: string) => {
+      const trimmedValue = typeof value === 'string' ? value.trim() : '';
+      if (trimmedValue && !costPattern.test(trimmedValue)) {
+        return Promise.reject(new Error(t('gateway.page.pricing.invalidCost')));
+      }
+      return Promise.resolve();
+    },
+    [t],
+  );
+
+  const validateDiscount = React.useCallback(
+    (_: unknown, value?: string) => {
+      const trimmedValue = typeof value === 'string' ? value.trim() : '';
+      if (trimmedValue && (!costPattern.test(trimmedValue) || Number(trimmedValue) > 100)) {
+        return Promise.reject(new Error(t('gateway.page.pricing.invalidDiscount')));
+      }
+      return Promise.resolve();
+    },
+    [t],
+  );
+
+  const validateThreshold = React.useCallback(
+    (_: unknown, value?: string) => {
+      const trimmedValue = typeof value === 'string' ? value.trim() : '';
+      if (!/^[1-9]\d*$/.test(trimmedValue)) {
+        return Promise.reject(new Error(t('gateway.page.pricing.invalidTierThreshold')));
+      }
+      return Promise.resolve();
+    },
+    [t],
+  );
+
   const handleSubmit = React.useCallback(async () => {
     const values = await form.validateFields();
     const nextPricing = trimFormValues(values);
@@ -185,6 +273,96 @@ const ModelPricingEditModal: React.FC<ModelPricingEditModalProps> = ({
         >
           <Input inputMode="decimal" />
         </Form.Item>
+        <Form.Item
+          name="reasoning_cost_per_million"
+          label={t('gateway.page.pricing.reasoningCost')}
+          rules={[{ validator: validateOptionalCost }]}
+        >
+          <Input inputMode="decimal" placeholder={t('gateway.page.pricing.sameAsOutput')} />
+        </Form.Item>
+        <Form.Item
+          name="request_cost"
+          label={t('gateway.page.pricing.requestCost')}
+          rules={[{ validator: validateOptionalCost }]}
+        >
+          <Input inputMode="decimal" placeholder="0" />
+        </Form.Item>
+        <Form.Item
+          name="image_cost"
+          label={t('gateway.page.pricing.imageCost')}
+          rules={[{ validator: validateOptionalCost }]}
+        >
+          <Input inputMode="decimal" placeholder="0" />
+        </Form.Item>
+        <Form.Item
+          name="batch_discount_percent"
+          label={t('gateway.page.pricing.batchDiscount')}
+          rules={[{ validator: validateDiscount }]}
+        >
+          <Input inputMode="decimal" placeholder="0" suffix="%" />
+        </Form.Item>
+        <Form.List name="tiers">
+          {(fields, { add, remove }) => (
+            <div className={styles.tierList}>
+              <div className={styles.tierHeader}>
+                <span>{t('gateway.page.pricing.tiers')}</span>
+                <Button
+                  size="small"
+                  icon={<Plus size={12} />}
+                  onClick={() => add({ ...emptyTier })}
+                >
+                  {t('gateway.page.pricing.addTier')}
+                </Button>
+              </div>
+              {fields.map((field) => (
+                <div key={field.key} className={styles.tierRow}>
+                  <Form.Item
+                    name={[field.name, 'above_input_tokens']}
+                    label={t('gateway.page.pricing.tierAbove')}
+                    rules={[{ validator: validateThreshold }]}
+                  >
+                    <Input inputMode="numeric" />
+                  </Form.Item>
+                  <Form.Item
+                    name={[field.name, 'input_cost_per_million']}
+                    label={t('gateway.page.pricing.tableInput')}
+                    rules={[{ validator: validateCost }]}
+                  >
+                    <Input inputMode="decimal" />
+                  </Form.Item>
+                  <Form.Item
+                    name={[field.name, 'output_cost_per_million']}
+                    label={t('gateway.page.pricing.tableOutput')}
+                    rules={[{ validator: validateCost }]}
+                  >
+                    <Input inputMode="decimal" />
+                  </Form.Item>
+                  <Form.Item
+                    name={[field.name, 'cache_read_cost_per_million']}
+                    label={t('gateway.page.pricing.tableCacheRead')}
+                    rules={[{ validator: validateOptionalCost }]}
+                  >
+                    <Input inputMode="decimal" placeholder={t('gateway.page.pricing.sameAsBase')} />
+                  </Form.Item>
+                  <Form.Item
+                    name={[field.name, 'cache_creation_cost_per_million']}
+                    label={t('gateway.page.pricing.tableCacheCreation')}
+                    rules={[{ validator: validateOptionalCost }]}
+                  >
+                    <Input inputMode="decimal" placeholder={t('gateway.page.pricing.sameAsBase')} />
+                  </Form.Item>
+                  <Button
+                    type="text"
+                    size="small"
+                    icon={<Trash2 size={14} />}
+                    aria-label={t('common.delete')}
+                    onClick={() => remove(field.name)}
+                  />
+                </div>
+              ))}
+            </div>
+          )}
+        </Form.List>
       </Form>
     </Modal>
   );
@@ -40,6 +40,11 @@ const createEmptyPricing = (): ModelPricing => ({
   output_cost_per_million: '0',
   cache_read_cost_per_million: '0',
   cache_creation_cost_per_million: '0',
+  tiers: [],
+  reasoning_cost_per_million: null,
+  image_cost: null,
+  request_cost: null,
+  batch_discount_percent: null,
 });
 
 const isConfigDirty = (
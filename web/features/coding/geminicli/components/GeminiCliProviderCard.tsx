@@ -172,7 +172,7 @@ const GeminiCliProviderCard: React.FC<GeminiCliProviderCardProps> = ({
     { cli: t('settings.gateway.cli.gemini') },
   );
   const gatewayMode = gatewayStatus?.mode ?? null;
-  const gatewayFailoverActive = gatewayMode === 'failover';
+  const gatewayFailoverActive = gatewayMode === 'failover' || gatewayMode === 'load_balance';
   const gatewayProxyActive = gatewayMode === 'single' || gatewayFailoverActive;
   const priorityEntry = gatewayFailoverActive
     ? gatewayStatus?.provider_priorities.find((entry) => entry.provider_id === provider.id)
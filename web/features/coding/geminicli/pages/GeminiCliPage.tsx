@@ -48,6 +48,7 @@ import {
   getGatewayProviderApiFormatFromMeta,
   getGatewayProviderProfilesVersion,
   providerNeedsGatewayProxy,
+  isGatewayReengageMode,
   saveProviderWithGatewayReengage,
   subscribeGatewayProviderProfiles,
 } from '@/features/coding/shared/gateway';
@@ -91,6 +92,7 @@ import type {
 } from '@/types/geminicli';
 import {
   engageProxyGatewayFailover,
+  engageProxyGatewayLoadBalance,
   engageProxyGatewaySingle,
   restoreProxyGatewayCliDirect,
   type GatewayCliTakeoverStatus,
@@ -638,13 +640,14 @@ const GeminiCliPage: React.FC = () => {
       const gatewayModeBeforeSave = gatewayCliStatus?.mode;
       const shouldReengageGatewayProxy =
         Boolean(editingProvider && !isCopyMode && !isLocalTemp && editingProvider.isApplied) &&
-        (gatewayModeBeforeSave === 'single' || gatewayModeBeforeSave === 'failover');
+        isGatewayReengageMode(gatewayModeBeforeSave);
 
       await saveProviderWithGatewayReengage({
         gatewayMode: shouldReengageGatewayProxy ? gatewayModeBeforeSave : null,
         restoreDirect: () => restoreProxyGatewayCliDirect('gemini'),
         engageSingle: () => engageProxyGatewaySingle('gemini', editingProvider?.id || ''),
         engageFailover: () => engageProxyGatewayFailover('gemini'),
+        engageLoadBalance: () => engageProxyGatewayLoadBalance('gemini'),
         onGatewayStatusChange: setGatewayCliStatus,
         saveProvider: async () => {
           if (isLocalTemp) {
@@ -276,7 +276,7 @@ const GrokProviderCard: React.FC<GrokProviderCardProps> = ({
   );
   const gatewayCanApplyProxy = canApplyProviderWithGatewayProxy(gatewayStatus);
   const gatewayMode = gatewayStatus?.mode ?? null;
-  const gatewayFailoverActive = gatewayMode === 'failover';
+  const gatewayFailoverActive = gatewayMode === 'failover' || gatewayMode === 'load_balance';
   const gatewayProxyActive = gatewayMode === 'single' || gatewayFailoverActive;
   const priorityEntry = gatewayFailoverActive
     ? gatewayStatus?.provider_priorities.find((entry) => entry.provider_id === provider.id)
@@ -98,6 +98,7 @@ import {
   getGatewayProviderApiFormatFromMeta,
   getGatewayProviderProfilesVersion,
   openAiApiFormatFromBaseUrl,
+  isGatewayReengageMode,
   saveProviderWithGatewayReengage,
   subscribeGatewayProviderProfiles,
 } from '@/features/coding/shared/gateway';
@@ -141,6 +142,7 @@ import {
 import { parseGrokSettingsConfig } from '../utils/grokSettingsConfig';
 import {
   engageProxyGatewayFailover,
+  engageProxyGatewayLoadBalance,
   engageProxyGatewaySingle,
   restoreProxyGatewayCliDirect,
   type GatewayCliTakeoverStatus,
@@ -529,6 +531,7 @@ const GrokPage: React.FC = () => {
       restoreDirect: () => restoreProxyGatewayCliDirect('grok'),
       engageSingle: () => engageProxyGatewaySingle('grok', provider.id),
       engageFailover: () => engageProxyGatewayFailover('grok'),
+      engageLoadBalance: () => engageProxyGatewayLoadBalance('grok'),
       onGatewayStatusChange: setGatewayCliStatus,
     });
     await loadConfig(true);
@@ -1459,13 +1462,14 @@ const GrokPage: React.FC = () => {
       const gatewayModeBeforeSave = gatewayCliStatus?.mode;
       const shouldReengageGatewayProxy =
         Boolean(editingProvider && !isCopyMode && !isLocalTemp && editingProvider.isApplied) &&
-        (gatewayModeBeforeSave === 'single' || gatewayModeBeforeSave === 'failover');
+        isGatewayReengageMode(gatewayModeBeforeSave);
 
       await saveProviderWithGatewayReengage({
         gatewayMode: shouldReengageGatewayProxy ? gatewayModeBeforeSave : null,
         restoreDirect: () => restoreProxyGatewayCliDirect('grok'),
         engageSingle: () => engageProxyGatewaySingle('grok', savedProviderId),
         engageFailover: () => engageProxyGatewayFailover('grok'),
+        engageLoadBalance: () => engageProxyGatewayLoadBalance('grok'),
         onGatewayStatusChange: setGatewayCliStatus,
         saveProvider: async () => {
           if (isLocalTemp) {
@@ -1,4 +1,5 @@
 import {
+  isGatewayReengageMode,
   saveProviderWithGatewayReengage,
   type GatewayReengageMode,
 } from '../../shared/gateway/providerSaveReengage';
@@ -12,6 +13,7 @@ interface SaveGrokProviderCatalogOptions<TStatus> {
   restoreDirect: () => Promise<TStatus>;
   engageSingle: () => Promise<TStatus>;
   engageFailover: () => Promise<TStatus>;
+  engageLoadBalance?: () => Promise<TStatus>;
   onGatewayStatusChange?: (status: TStatus) => void;
 }
 
@@ -23,16 +25,18 @@ export async function saveGrokProviderCatalogWithGatewayReengage<TStatus>({
   restoreDirect,
   engageSingle,
   engageFailover,
+  engageLoadBalance,
   onGatewayStatusChange,
 }: SaveGrokProviderCatalogOptions<TStatus>): Promise<GrokProvider> {
   const shouldReengageGateway = provider.isApplied
-    && (gatewayMode === 'single' || gatewayMode === 'failover');
+    && isGatewayReengageMode(gatewayMode);
 
   return saveProviderWithGatewayReengage({
     gatewayMode: shouldReengageGateway ? gatewayMode : null,
     restoreDirect,
     engageSingle,
     engageFailover,
+    engageLoadBalance,
     onGatewayStatusChange,
     saveProvider: () => updateProvider({
       ...provider,
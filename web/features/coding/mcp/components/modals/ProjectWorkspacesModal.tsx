@@ -0,0 +1,382 @@
+import React from 'react';
+import { Alert, Button, Checkbox, Empty, Form, Input, message, Modal, Select, Table, Tag } from 'antd';
+import { FolderOpenOutlined, PlusOutlined } from '@ant-design/icons';
+import { open as openDialog } from '@tauri-apps/plugin-dialog';
+import { useTranslation } from 'react-i18next';
+import { getManagedSkills } from '@/features/coding/skills/services/skillsApi';
+import type { ManagedSkill } from '@/features/coding/skills/types';
+import * as api from '../../services/mcpApi';
+import type {
+  McpServer,
+  ProjectSyncDetail,
+  ProjectSyncStatus,
+  ProjectToolTarget,
+  ProjectWorkspace,
+} from '../../types';
+import styles from './ProjectWorkspacesModal.module.less';
+
+interface ProjectWorkspacesModalProps {
+  open: boolean;
+  servers: McpServer[];
+  onClose: () => void;
+}
+
+interface ProjectWorkspaceFormValues {
+  name: string;
+  rootPath: string;
+  enabledTools: string[];
+  mcpServerIds: string[];
+  skillIds: string[];
+}
+
+const STATUS_COLORS: Record<ProjectSyncStatus, string> = {
+  ok: 'success',
+  error: 'error',
+  missing: 'default',
+  drift: 'warning',
+  stale: 'processing',
+};
+
+/** Env values and headers are written verbatim into the project files. */
+const hasInlineValues = (server: McpServer) => {
+  const config = server.server_config;
+  const values = 'env' in config ? config.env : 'headers' in config ? config.headers : undefined;
+  return Object.values(values ?? {}).some((value) => value.trim() !== '');
+};
+
+const EMPTY_FORM: ProjectWorkspaceFormValues = {
+  name: '',
+  rootPath: '',
+  enabledTools: [],
+  mcpServerIds: [],
+  skillIds: [],
+};
+
+export const ProjectWorkspacesModal: React.FC<ProjectWorkspacesModalProps> = ({
+  open,
+  servers,
+  onClose,
+}) => {
+  const { t } = useTranslation();
+  const [form] = Form.useForm<ProjectWorkspaceFormValues>();
+  const [workspaces, setWorkspaces] = React.useState<ProjectWorkspace[]>([]);
+  const [targets, setTargets] = React.useState<ProjectToolTarget[]>([]);
+  const [skills, setSkills] = React.useState<ManagedSkill[]>([]);
+  const [selectedId, setSelectedId] = React.useState<string | null>(null);
+  const [details, setDetails] = React.useState<ProjectSyncDetail[]>([]);
+  const [busy, setBusy] = React.useState(false);
+
+  const selectedWorkspace = workspaces.find((workspace) => workspace.id === selectedId) ?? null;
+  const selectedServerIds = Form.useWatch('mcpServerIds', form);
+  const serversWithInlineValues = servers.filter(
+    (server) => selectedServerIds?.includes(server.id) && hasInlineValues(server),
+  );
+
+  const loadWorkspaces = React.useCallback(async () => {
+    const nextWorkspaces = await api.listProjectWorkspaces();
+    setWorkspaces(nextWorkspaces);
+    return nextWorkspaces;
+  }, []);
+
+  React.useEffect(() => {
+    if (!open) {
+      return;
+    }
+    Promise.all([loadWorkspaces(), api.getProjectToolTargets(), getManagedSkills()])
+      .then(([, nextTargets, nextSkills]) => {
+        setTargets(nextTargets);
+        setSkills(nextSkills.filter((skill) => skill.management_enabled));
+      })
+      .catch((error) => message.error(String(error)));
+  }, [loadWorkspaces, open]);
+
+  React.useEffect(() => {
+    if (!selectedWorkspace) {
+      form.setFieldsValue(EMPTY_FORM);
+      setDetails([]);
+      return;
+    }
+    form.setFieldsValue({
+      name: selectedWorkspace.name,
+      rootPath: selectedWorkspace.root_path,
+      enabledTools: selectedWorkspace.enabled_tools,
+      mcpServerIds: selectedWorkspace.mcp_server_ids,
+      skillIds: selectedWorkspace.skill_ids,
+    });
+    setDetails(selectedWorkspace.sync_details);
+    // Only reset the form when switching workspaces, not after each save.
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [form, selectedId]);
+
+  const runAction = async (action: () => Promise<void>) => {
+    setBusy(true);
+    try {
+      await action();
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const saveWorkspace = async () => {
+    const values = await form.validateFields();
+    const saved = await api.saveProjectWorkspace({
+      id: selectedId,
+      name: values.name,
+      root_path: values.rootPath,
+      enabled_tools: values.enabledTools ?? [],
+      mcp_server_ids: values.mcpServerIds ?? [],
+      skill_ids: values.skillIds ?? [],
+    });
+    await loadWorkspaces();
+    setSelectedId(saved.id);
+    return saved;
+  };
+
+  const handleSave = () =>
+    runAction(async () => {
+      await saveWorkspace();
+      message.success(t('mcp.projectWorkspace.saved'));
+    });
+
+  const handleSync = () =>
+    runAction(async () => {
+      const saved = await saveWorkspace();
+      const synced = await api.syncProjectWorkspace(saved.id);
+      await loadWorkspaces();
+      setDetails(synced.sync_details);
+      const failed = synced.sync_details.filter((item) => item.status === 'error').length;
+      if (failed > 0) {
+        message.warning(t('mcp.projectWorkspace.syncPartialFailed', { count: failed }));
+      } else {
+        message.success(t('mcp.projectWorkspace.synced'));
+      }
+    });
+
+  const handleCheckDrift = () =>
+    runAction(async () => {
+      if (!selectedId) {
+        return;
+      }
+      const nextDetails = await api.checkProjectWorkspaceDrift(selectedId);
+      setDetails(nextDetails);
+      const changed = nextDetails.filter((item) => item.status !== 'ok').length;
+      if (changed > 0) {
+        message.warning(t('mcp.projectWorkspace.driftFound', { count: changed }));
+      } else {
+        message.success(t('mcp.projectWorkspace.inSync'));
+      }
+    });
+
+  const handleDelete = () => {
+    if (!selectedWorkspace) {
+      return;
+    }
+    const remove = (removeFiles: boolean) =>
+      runAction(async () => {
+        await api.deleteProjectWorkspace(selectedWorkspace.id, removeFiles);
+        await loadWorkspaces();
+        setSelectedId(null);
+        message.success(t('mcp.projectWorkspace.deleted'));
+      });
+    Modal.confirm({
+      title: t('mcp.projectWorkspace.deleteConfirm', { name: selectedWorkspace.name }),
+      content: t('mcp.projectWorkspace.deleteConfirmContent'),
+      okText: t('mcp.projectWorkspace.deleteWithFiles'),
+      okButtonProps: { danger: true },
+      cancelText: t('common.cancel'),
+      closable: true,
+      footer: (_, { OkBtn, CancelBtn }) => (
+        <>
+          <CancelBtn />
+          <Button onClick={() => { Modal.destroyAll(); void remove(false); }}>
+            {t('mcp.projectWorkspace.deleteKeepFiles')}
+          </Button>
+          <OkBtn />
+        </>
+      ),
+      onOk: () => remove(true),
+    });
+  };
+
+  const handleBrowse = async () => {
+    try {
+      const selected = await openDialog({
+        directory: true,
+        multiple: false,
+        title: t('mcp.projectWorkspace.rootPath'),
+      });
+      if (selected) {
+        form.setFieldValue('rootPath', selected as string);
+      }
+    } catch {
+      // User cancelled
+    }
+  };
+
+  const toolName = (key: string) =>
+    targets.find((target) => target.key === key)?.display_name ?? key;
+
+  return (
+    <Modal
+      open={open}
+      title={t('mcp.projectWorkspace.title')}
+      width={960}
+      footer={null}
+      onCancel={onClose}
+      destroyOnHidden
+      className={styles.modal}
+    >
+      <div className={styles.layout}>
+        <aside className={styles.sidebar}>
+          <Button
+            block
+            icon={<PlusOutlined />}
+            onClick={() => setSelectedId(null)}
+            type={selectedId ? 'default' : 'primary'}
+          >
+            {t('mcp.projectWorkspace.add')}
+          </Button>
+          <div className={styles.workspaceList}>
+            {workspaces.map((workspace) => (
+              <button
+                key={workspace.id}
+                type="button"
+                className={`${styles.workspaceItem}${workspace.id === selectedId ? ` ${styles.workspaceItemActive}` : ''}`}
+                onClick={() => setSelectedId(workspace.id)}
+              >
+                <span className={styles.workspaceName}>{workspace.name}</span>
+                <span className={styles.workspacePath}>{workspace.root_path}</span>
+              </button>
+            ))}
+          </div>
+        </aside>
+
+        <section className={styles.main}>
+          <p className={styles.hint}>{t('mcp.projectWorkspace.hint')}</p>
+          <Form form={form} layout="vertical" initialValues={EMPTY_FORM}>
+            <div className={styles.fieldRow}>
+              <Form.Item
+                label={t('mcp.projectWorkspace.name')}
+                name="name"
+                rules={[{ required: true, message: t('mcp.projectWorkspace.nameRequired') }]}
+              >
+                <Input placeholder={t('mcp.projectWorkspace.namePlaceholder')} />
+              </Form.Item>
+              <Form.Item
+                label={t('mcp.projectWorkspace.rootPath')}
+                name="rootPath"
+                rules={[{ required: true, message: t('mcp.projectWorkspace.rootPathRequired') }]}
+              >
+                <Input
+                  placeholder="~/projects/my-repo"
+                  addonAfter={<FolderOpenOutlined onClick={() => void handleBrowse()} />}
+                />
+              </Form.Item>
+            </div>
+            <Form.Item label={t('mcp.projectWorkspace.tools')} name="enabledTools">
+              <Checkbox.Group className={styles.toolGrid}>
+                {targets.map((target) => (
+                  <Checkbox key={target.key} value={target.key}>
+                    <span title={[target.mcp_config_path, target.skills_dir].filter(Boolean).join(' · ')}>
+                      {target.display_name}
+                    </span>
+                  </Checkbox>
+                ))}
+              </Checkbox.Group>
+            </Form.Item>
+            <Form.Item label={t('mcp.projectWorkspace.servers')} name="mcpServerIds">
+              <Select
+                mode="multiple"
+                allowClear
+                optionFilterProp="label"
+                options={servers.map((server) => ({ value: server.id, label: server.name }))}
+              />
+            </Form.Item>
+            <Form.Item label={t('mcp.projectWorkspace.skills')} name="skillIds">
+              <Select
+                mode="multiple"
+                allowClear
+                optionFilterProp="label"
+                options={skills.map((skill) => ({ value: skill.id, label: skill.name }))}
+              />
+            </Form.Item>
+          </Form>
+
+          {serversWithInlineValues.length > 0 && (
+            <Alert
+              type="warning"
+              showIcon
+              message={t('mcp.projectWorkspace.inlineSecretsWarning', {
+                names: serversWithInlineValues.map((server) => server.name).join(', '),
+              })}
+            />
+          )}
+
+          <div className={styles.actions}>
+            {selectedWorkspace && (
+              <Button danger disabled={busy} onClick={handleDelete}>
+                {t('common.delete')}
+              </Button>
+            )}
+            <span className={styles.actionsSpacer} />
+            {selectedWorkspace && (
+              <Button disabled={busy} onClick={() => void handleCheckDrift()}>
+                {t('mcp.projectWorkspace.checkDrift')}
+              </Button>
+            )}
+            <Button disabled={busy} onClick={() => void handleSave()}>
+              {t('common.save')}
+            </Button>
+            <Button type="primary" loading={busy} onClick={() => void handleSync()}>
+              {t('mcp.projectWorkspace.sync')}
+            </Button>
+          </div>
+
+          {details.length > 0 ? (
+            <Table<ProjectSyncDetail>
+              size="small"
+              pagination={false}
+              rowKey={(item) => `${item.tool}:${item.item_type}:${item.item_id}:${item.target_path}:${item.item_name}`}
+              dataSource={details}
+              columns={[
+                {
+                  title: t('mcp.projectWorkspace.columns.tool'),
+                  dataIndex: 'tool',
+                  render: (value: string) => toolName(value),
+                },
+                {
+                  title: t('mcp.projectWorkspace.columns.item'),
+                  dataIndex: 'item_name',
+                  render: (value: string, item) => (
+                    <span>
+                      <Tag>{t(`mcp.projectWorkspace.itemTypes.${item.item_type}`)}</Tag>
+                      {value}
+                    </span>
+                  ),
+                },
+                {
+                  title: t('mcp.projectWorkspace.columns.status'),
+                  dataIndex: 'status',
+                  render: (value: ProjectSyncStatus, item) => (
+                    <Tag color={STATUS_COLORS[value]} title={item.error_message ?? undefined}>
+                      {t(`mcp.projectWorkspace.statuses.${value}`)}
+                    </Tag>
+                  ),
+                },
+                {
+                  title: t('mcp.projectWorkspace.columns.target'),
+                  dataIndex: 'target_path',
+                  ellipsis: true,
+                },
+              ]}
+            />
+          ) : (
+            <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={t('mcp.projectWorkspace.noDetails')} />
+          )}
+        </section>
+      </div>
+    </Modal>
+  );
+};
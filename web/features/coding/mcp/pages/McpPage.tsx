@@ -5,6 +5,7 @@ import {
   ChevronsUp,
   ExternalLink,
   FileText,
+  FolderGit2,
   GripVertical,
   Import,
   LayoutGrid,
@@ -42,6 +43,7 @@ import { McpSettingsModal } from '../components/modals/McpSettingsModal';
 import { ImportMcpModal } from '../components/modals/ImportMcpModal';
 import { ImportJsonModal } from '../components/modals/ImportJsonModal';
 import { McpMetadataModal } from '../components/modals/McpMetadataModal';
+import { ProjectWorkspacesModal } from '../components/modals/ProjectWorkspacesModal';
 import * as mcpApi from '../services/mcpApi';
 import {
   buildMcpGroups,
@@ -97,6 +99,7 @@ const McpPage: React.FC = () => {
   const [selectedIds, setSelectedIds] = useState<Set<string>>(new Set());
   const [selectionMode, setSelectionMode] = useState(false);
   const [metadataServer, setMetadataServer] = useState<McpServer | null>(null);
+  const [projectWorkspacesOpen, setProjectWorkspacesOpen] = useState(false);
   const [batchGroupModalOpen, setBatchGroupModalOpen] = useState(false);
   const [batchGroupValue, setBatchGroupValue] = useState('');
   const [groupToolMode, setGroupToolMode] = useState(false);
@@ -701,6 +704,14 @@ const McpPage: React.FC = () => {
           >
             {t('mcp.importJson.button')}
           </ManagementButton>
+          <ManagementButton
+            variant="subtle"
+            controlSize="compact"
+            icon={<FolderGit2 size={14} aria-hidden="true" />}
+            onClick={() => setProjectWorkspacesOpen(true)}
+          >
+            {t('mcp.projectWorkspace.button')}
+          </ManagementButton>
           <ManagementButton
             variant="primary"
             controlSize="compact"
@@ -924,6 +935,14 @@ const McpPage: React.FC = () => {
         </div>
       </Modal>
 
+      {projectWorkspacesOpen && (
+        <ProjectWorkspacesModal
+          open={projectWorkspacesOpen}
+          servers={servers}
+          onClose={() => setProjectWorkspacesOpen(false)}
+        />
+      )}
+
       <McpMetadataModal
         open={!!metadataServer}
         server={metadataServer}
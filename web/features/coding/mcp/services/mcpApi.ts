@@ -9,6 +9,12 @@ import type {
   McpScanResult,
   McpPackageVersionResolveRequest,
   McpPackageVersionResolveResult,
+  McpProbeResult,
+  McpServerProbe,
+  ProjectSyncDetail,
+  ProjectToolTarget,
+  ProjectWorkspace,
+  ProjectWorkspaceInput,
 } from '../types';
 
 // Server CRUD
@@ -50,6 +56,15 @@ export const updateMcpMetadata = async (
   return invoke('mcp_update_metadata', { serverId, userGroup, userNote });
 };
 
+// Live probe
+export const probeMcpServer = async (serverId: string): Promise<McpProbeResult> => {
+  return invoke<McpProbeResult>('mcp_probe_server', { serverId });
+};
+
+export const probeMcpServers = async (serverIds?: string[]): Promise<McpServerProbe[]> => {
+  return invoke<McpServerProbe[]>('mcp_probe_servers', { serverIds });
+};
+
 // Sync operations
 export const syncMcpToTool = async (toolKey: string): Promise<McpSyncResult[]> => {
   return invoke<McpSyncResult[]>('mcp_sync_to_tool', { toolKey });
@@ -160,3 +175,28 @@ export const deleteMcpFavorite = async (favoriteId: string): Promise<void> => {
 export const initMcpDefaultFavorites = async (): Promise<number> => {
   return invoke<number>('mcp_init_default_favorites');
 };
+
+// Project workspaces
+export const listProjectWorkspaces = async (): Promise<ProjectWorkspace[]> => {
+  return invoke<ProjectWorkspace[]>('project_workspace_list');
+};
+
+export const getProjectToolTargets = async (): Promise<ProjectToolTarget[]> => {
+  return invoke<ProjectToolTarget[]>('project_workspace_get_tool_targets');
+};
+
+export const saveProjectWorkspace = async (input: ProjectWorkspaceInput): Promise<ProjectWorkspace> => {
+  return invoke<ProjectWorkspace>('project_workspace_save', { input });
+};
+
+export const deleteProjectWorkspace = async (workspaceId: string, removeFiles: boolean): Promise<void> => {
+  return invoke('project_workspace_delete', { workspaceId, removeFiles });
+};
+
+export const syncProjectWorkspace = async (workspaceId: string): Promise<ProjectWorkspace> => {
+  return invoke<ProjectWorkspace>('project_workspace_sync', { workspaceId });
+};
+
+export const checkProjectWorkspaceDrift = async (workspaceId: string): Promise<ProjectSyncDetail[]> => {
+  return invoke<ProjectSyncDetail[]>('project_workspace_check_drift', { workspaceId });
+};
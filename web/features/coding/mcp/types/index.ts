@@ -7,6 +7,7 @@ export interface McpServer {
   server_config: StdioConfig | HttpConfig;
   enabled_tools: string[];
   sync_details: McpSyncDetail[];
+  probe_result: McpProbeResult | null;
   description: string | null;
   user_group: string | null;
   user_note: string | null;
@@ -43,6 +44,46 @@ export interface McpSyncDetail {
   error_message: string | null;
 }
 
+export interface McpProbeTool {
+  name: string;
+  description: string | null;
+  input_schema: Record<string, unknown> | null;
+}
+
+export interface McpProbePrompt {
+  name: string;
+  description: string | null;
+  arguments: string[];
+}
+
+export interface McpProbeResource {
+  uri: string;
+  name: string | null;
+  mime_type: string | null;
+}
+
+export interface McpProbeResult {
+  status: 'ok' | 'error';
+  probed_at: number;
+  /** initialize round trip in ms, including process startup for stdio */
+  latency_ms: number | null;
+  protocol_version: string | null;
+  server_name: string | null;
+  server_version: string | null;
+  instructions: string | null;
+  capabilities: string[];
+  tools: McpProbeTool[];
+  prompts: McpProbePrompt[];
+  resources: McpProbeResource[];
+  error_message: string | null;
+}
+
+export interface McpServerProbe {
+  server_id: string;
+  server_name: string;
+  result: McpProbeResult;
+}
+
 export interface CreateMcpServerInput {
   name: string;
   server_type: 'stdio' | 'http' | 'sse';
@@ -121,3 +162,47 @@ export interface McpPackageVersionResolveResult {
   version: string | null;
   error_message: string | null;
 }
+
+// Project workspace types
+
+export type ProjectSyncStatus = 'ok' | 'error' | 'missing' | 'drift' | 'stale';
+
+export interface ProjectSyncDetail {
+  tool: string;
+  item_type: 'mcp' | 'skill';
+  item_id: string;
+  item_name: string;
+  target_path: string;
+  status: ProjectSyncStatus;
+  synced_at: number | null;
+  error_message: string | null;
+}
+
+export interface ProjectWorkspace {
+  id: string;
+  name: string;
+  root_path: string;
+  enabled_tools: string[];
+  mcp_server_ids: string[];
+  skill_ids: string[];
+  sync_details: ProjectSyncDetail[];
+  last_synced_at?: number | null;
+  created_at: number;
+  updated_at: number;
+}
+
+export interface ProjectWorkspaceInput {
+  id?: string | null;
+  name: string;
+  root_path: string;
+  enabled_tools: string[];
+  mcp_server_ids: string[];
+  skill_ids: string[];
+}
+
+export interface ProjectToolTarget {
+  key: string;
+  display_name: string;
+  mcp_config_path: string | null;
+  skills_dir: string | null;
+}
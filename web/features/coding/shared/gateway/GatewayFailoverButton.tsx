@@ -5,6 +5,7 @@ import { useTranslation } from 'react-i18next';
 import {
   disengageProxyGatewayFailover,
   engageProxyGatewayFailover,
+  engageProxyGatewayLoadBalance,
   getProxyGatewayCliStatus,
   restoreProxyGatewayCliDirect,
   type GatewayCliKey,
@@ -15,7 +16,7 @@ import { restoreDirectUnavailableHintKey, type GatewayProxyReason } from './prov
 import styles from './GatewayFailoverButton.module.less';
 
 type SupportedGatewayCliKey = Extract<GatewayCliKey, 'claude' | 'codex' | 'grok' | 'gemini' | 'claude_desktop'>;
-type ActionKind = 'load' | 'enableFailover' | 'disableFailover' | 'restore';
+type ActionKind = 'load' | 'enableFailover' | 'enableLoadBalance' | 'disableFailover' | 'restore';
 type NoticeKind = 'success' | 'error' | 'info';
 
 interface GatewayFailoverButtonProps {
@@ -38,7 +39,7 @@ const formatGatewayError = (error: unknown) =>
   error instanceof Error ? error.message : String(error);
 
 const isGatewayProxyActive = (status: GatewayCliTakeoverStatus | null) =>
-  status?.mode === 'single' || status?.mode === 'failover';
+  status?.mode === 'single' || status?.mode === 'failover' || status?.mode === 'load_balance';
 
 const GatewayFailoverButton: React.FC<GatewayFailoverButtonProps> = ({
   cliKey,
@@ -118,7 +119,8 @@ const GatewayFailoverButton: React.FC<GatewayFailoverButtonProps> = ({
   }, [refreshStatus]);
 
   const visible = isGatewayProxyActive(status);
-  const failoverActive = status?.mode === 'failover';
+  // Load balancing shares the failover takeover, so both count as the multi-provider state.
+  const failoverActive = status?.mode === 'failover' || status?.mode === 'load_balance';
   const canRestoreDirect = Boolean(status?.can_restore_direct);
   const restoreDirectUnavailableTitle = t(
     restoreDirectUnavailableHintKey(primaryProviderNeedsProxyReason),
@@ -176,6 +178,32 @@ const GatewayFailoverButton: React.FC<GatewayFailoverButtonProps> = ({
     }
   };
 
+  const handleEnableLoadBalance = async (event: React.MouseEvent<HTMLButtonElement>) => {
+    event.preventDefault();
+    event.stopPropagation();
+    setBusyAction('enableLoadBalance');
+    setNotice(null);
+    try {
+      const nextStatus = await engageProxyGatewayLoadBalance(cliKey);
+      setStatus(nextStatus);
+      onStatusChange?.(nextStatus);
+      refreshTrayAfterGatewayChange();
+      setNotice({
+        kind: 'success',
+        text: t('gateway.failover.notice.loadBalanceEnabled'),
+      });
+      setOpen(false);
+    } catch (error) {
+      setNotice({
+        kind: 'error',
+        text: t('gateway.failover.notice.enableFailed', { error: formatGatewayError(error) }),
+      });
+      await refreshStatus().catch(() => undefined);
+    } finally {
+      setBusyAction(null);
+    }
+  };
+
   const handleRestoreDirect = async (event: React.MouseEvent<HTMLButtonElement>) => {
     event.preventDefault();
     event.stopPropagation();
@@ -345,6 +373,22 @@ const GatewayFailoverButton: React.FC<GatewayFailoverButtonProps> = ({
                   <span>{t('gateway.proxy.restoreDirectButton')}</span>
                 </button>
               ) : null}
+              {status?.mode !== 'load_balance' ? (
+                <button
+                  type="button"
+                  className={styles.secondaryButton}
+                  disabled={busyAction !== null}
+                  title={t('gateway.failover.loadBalanceHint')}
+                  onClick={handleEnableLoadBalance}
+                >
+                  {busyAction === 'enableLoadBalance' ? (
+                    <Loader2 size={14} className={styles.spin} aria-hidden="true" />
+                  ) : (
+                    <Network size={14} aria-hidden="true" />
+                  )}
+                  <span>{t('gateway.failover.loadBalanceButton')}</span>
+                </button>
+              ) : null}
               <button
                 type="button"
                 className={styles.primaryButton}
@@ -1,4 +1,4 @@
-export type GatewayReengageMode = 'single' | 'failover' | null | undefined;
+export type GatewayReengageMode = 'single' | 'failover' | 'load_balance' | null | undefined;
 
 interface SaveProviderWithGatewayReengageOptions<TResult, TStatus> {
   gatewayMode: GatewayReengageMode;
@@ -6,13 +6,14 @@ interface SaveProviderWithGatewayReengageOptions<TResult, TStatus> {
   restoreDirect: () => Promise<TStatus>;
   engageSingle: () => Promise<TStatus>;
   engageFailover: () => Promise<TStatus>;
+  engageLoadBalance?: () => Promise<TStatus>;
   onGatewayStatusChange?: (status: TStatus) => void;
 }
 
 export const isGatewayReengageMode = (
   gatewayMode: GatewayReengageMode,
-): gatewayMode is 'single' | 'failover' =>
-  gatewayMode === 'single' || gatewayMode === 'failover';
+): gatewayMode is 'single' | 'failover' | 'load_balance' =>
+  gatewayMode === 'single' || gatewayMode === 'failover' || gatewayMode === 'load_balance';
 
 export const saveProviderWithGatewayReengage = async <TResult, TStatus>({
   gatewayMode,
@@ -20,6 +21,7 @@ export const saveProviderWithGatewayReengage = async <TResult, TStatus>({
   restoreDirect,
   engageSingle,
   engageFailover,
+  engageLoadBalance,
   onGatewayStatusChange,
 }: SaveProviderWithGatewayReengageOptions<TResult, TStatus>): Promise<TResult> => {
   if (!isGatewayReengageMode(gatewayMode)) {
@@ -34,6 +36,8 @@ export const saveProviderWithGatewayReengage = async <TResult, TStatus>({
   let nextStatus = await engageSingle();
   if (gatewayMode === 'failover') {
     nextStatus = await engageFailover();
+  } else if (gatewayMode === 'load_balance') {
+    nextStatus = await (engageLoadBalance ?? engageFailover)();
   }
   onGatewayStatusChange?.(nextStatus);
 
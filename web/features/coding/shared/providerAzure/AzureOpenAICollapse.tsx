@@ -0,0 +1,155 @@
+import React from 'react';
+import { Cloud, Plus, Trash2 } from 'lucide-react';
+import { useTranslation } from 'react-i18next';
+import ProviderConfigCollapse from '@/features/coding/shared/providerConfig/ProviderConfigCollapse';
+import styles from '@/features/coding/shared/providerHeaders/CustomHeadersCollapse.module.less';
+import {
+  emptyAzureDeploymentEntry,
+  type AzureConfigState,
+  type AzureDeploymentEntry,
+} from './azureConfigUtils';
+
+interface AzureOpenAICollapseProps {
+  value: AzureConfigState;
+  onChange: (value: AzureConfigState) => void;
+  className?: string;
+}
+
+/**
+ * Azure OpenAI settings editor: an "Azure OpenAI" toggle, the `api-version`
+ * and the model-to-deployment mapping. Shares the `CustomHeadersCollapse`
+ * layout; models without a mapping row are sent to a deployment of the same
+ * name, matching `azure_deployment_for` on the Rust side.
+ */
+const AzureOpenAICollapse: React.FC<AzureOpenAICollapseProps> = ({
+  value,
+  onChange,
+  className,
+}) => {
+  const { t } = useTranslation();
+  const [expanded, setExpanded] = React.useState(false);
+
+  React.useEffect(() => {
+    if (value.enabled) {
+      setExpanded(true);
+    }
+  }, [value.enabled]);
+
+  const update = React.useCallback(
+    (patch: Partial<AzureConfigState>) => {
+      onChange({ ...value, ...patch });
+    },
+    [onChange, value],
+  );
+
+  const updateRow = (index: number, patch: Partial<AzureDeploymentEntry>) => {
+    const next = value.deployments.map((row, i) =>
+      i === index ? { ...row, ...patch } : row,
+    );
+    update({ deployments: next });
+  };
+
+  const addRow = () => {
+    update({ deployments: [...value.deployments, emptyAzureDeploymentEntry()] });
+  };
+
+  const removeRow = (index: number) => {
+    const next = value.deployments.filter((_, i) => i !== index);
+    update({ deployments: next.length > 0 ? next : [emptyAzureDeploymentEntry()] });
+  };
+
+  const disabled = !value.enabled;
+
+  return (
+    <ProviderConfigCollapse
+      className={className}
+      title={t('providerAzure.title')}
+      expanded={expanded}
+      onExpandedChange={setExpanded}
+      icon={<Cloud />}
+      actions={
+        <div
+          className={styles.toggleWrap}
+          onClick={(event) => event.stopPropagation()}
+        >
+          <span>{t('providerAzure.useAzure')}</span>
+          <button
+            type="button"
+            className={`${styles.toggleButton} ${value.enabled ? styles.toggleButtonActive : ''}`}
+            role="switch"
+            aria-checked={value.enabled}
+            onClick={() => {
+              const enabled = !value.enabled;
+              update({ enabled });
+              if (enabled) {
+                setExpanded(true);
+              }
+            }}
+          >
+            <span className={styles.toggleKnob} />
+          </button>
+        </div>
+      }
+    >
+      <p className={styles.description}>{t('providerAzure.description')}</p>
+      <span className={styles.toolbarLabel}>{t('providerAzure.apiVersion')}</span>
+      <div className={styles.row}>
+        <input
+          className={styles.fieldInput}
+          type="text"
+          autoComplete="off"
+          placeholder={t('providerAzure.apiVersionPlaceholder')}
+          value={value.apiVersion}
+          disabled={disabled}
+          onChange={(event) => update({ apiVersion: event.target.value })}
+        />
+      </div>
+      <span className={styles.toolbarLabel}>{t('providerAzure.deployments')}</span>
+      <div className={styles.rows}>
+        {value.deployments.map((row, index) => (
+          <div key={index} className={styles.row}>
+            <input
+              className={styles.fieldInput}
+              type="text"
+              autoComplete="off"
+              placeholder={t('providerAzure.modelPlaceholder')}
+              value={row.model}
+              disabled={disabled}
+              onChange={(event) => updateRow(index, { model: event.target.value })}
+            />
+            <span className={styles.arrow}>→</span>
+            <input
+              className={styles.fieldInput}
+              type="text"
+              autoComplete="off"
+              placeholder={t('providerAzure.deploymentPlaceholder')}
+              value={row.deployment}
+              disabled={disabled}
+              onChange={(event) => updateRow(index, { deployment: event.target.value })}
+            />
+            <button
+              type="button"
+              className={styles.iconButton}
+              onClick={() => removeRow(index)}
+              disabled={disabled}
+              aria-label={t('providerAzure.remove')}
+            >
+              <Trash2 size={14} />
+            </button>
+          </div>
+        ))}
+      </div>
+      <button
+        type="button"
+        className={styles.addButton}
+        onClick={addRow}
+        disabled={disabled}
+      >
+        <Plus size={14} style={{ marginRight: 4, verticalAlign: -2 }} />
+        {t('providerAzure.add')}
+      </button>
+    </ProviderConfigCollapse>
+  );
+};
+
+export default AzureOpenAICollapse;
@@ -0,0 +1,102 @@
+/**
+ * Azure OpenAI provider meta read/merge helpers.
+ *
+ * Follows the custom-headers pattern: `getAzureConfigFromMeta` /
+ * `mergeAzureConfigIntoMeta` only touch the Azure keys, so they chain with the
+ * other `merge*IntoMeta` helpers in a form's submit `meta:` builder.
+ *
+ * Persisted shape: `meta.providerType` (an Azure type, see
+ * `AZURE_PROVIDER_TYPES`), `meta.azureDeployments: Record<model, deployment>`
+ * and `meta.azureApiVersion`. The Rust gateway reads the same keys in
+ * `runtime/providers.rs::provider_from_record`.
+ */
+import { isAzureProviderType } from '@/constants/providerTypes';
+
+export interface AzureDeploymentEntry {
+  model: string;
+  deployment: string;
+}
+
+export interface AzureConfigState {
+  enabled: boolean;
+  apiVersion: string;
+  deployments: AzureDeploymentEntry[];
+}
+
+export interface GatewayProviderAzureMeta {
+  providerType?: string;
+  /** Legacy snake_case variant, kept for parity with the Rust adapter. */
+  provider_type?: string;
+  azureDeployments?: Record<string, string>;
+  /** Legacy snake_case variant, kept for parity with the Rust adapter. */
+  azure_deployments?: Record<string, string>;
+  azureApiVersion?: string;
+  /** Legacy snake_case variant, kept for parity with the Rust adapter. */
+  azure_api_version?: string;
+}
+
+export function emptyAzureDeploymentEntry(): AzureDeploymentEntry {
+  return { model: '', deployment: '' };
+}
+
+/**
+ * Read the Azure settings from provider meta. The state is `enabled` when the
+ * provider type is an Azure type or any Azure setting is persisted.
+ */
+export function getAzureConfigFromMeta(meta?: GatewayProviderAzureMeta | null): AzureConfigState {
+  const rawDeployments = meta?.azureDeployments ?? meta?.azure_deployments;
+  const deployments = rawDeployments && typeof rawDeployments === 'object'
+    ? Object.entries(rawDeployments)
+      .filter(([, deployment]) => typeof deployment === 'string')
+      .map(([model, deployment]) => ({ model, deployment }))
+    : [];
+  const apiVersion = meta?.azureApiVersion ?? meta?.azure_api_version ?? '';
+  const providerType = meta?.providerType ?? meta?.provider_type;
+  return {
+    enabled: isAzureProviderType(providerType) || deployments.length > 0 || apiVersion.trim() !== '',
+    apiVersion,
+    deployments: deployments.length > 0 ? deployments : [emptyAzureDeploymentEntry()],
+  };
+}
+
+/**
+ * Merge the Azure state back into provider meta (field-level). Enabling keeps
+ * an existing Azure provider type (e.g. `@ai-sdk/azure`) and otherwise sets
+ * `azure`; disabling clears the Azure keys and an Azure provider type.
+ */
+export function mergeAzureConfigIntoMeta<T extends GatewayProviderAzureMeta>(
+  meta: T | undefined,
+  state: AzureConfigState,
+): T | undefined {
+  const nextMeta = { ...(meta || {}) } as T;
+  const record = nextMeta as unknown as Record<string, unknown>;
+  const providerType = nextMeta.providerType ?? nextMeta.provider_type;
+  delete record.azureDeployments;
+  delete record.azure_deployments;
+  delete record.azureApiVersion;
+  delete record.azure_api_version;
+  if (isAzureProviderType(providerType)) {
+    delete record.providerType;
+    delete record.provider_type;
+  }
+
+  if (state.enabled) {
+    record.providerType = isAzureProviderType(providerType) ? providerType : 'azure';
+    const deployments = Object.fromEntries(
+      state.deployments
+        .map(({ model, deployment }) => [model.trim(), deployment.trim()])
+        .filter(([model, deployment]) => model !== '' && deployment !== ''),
+    );
+    if (Object.keys(deployments).length > 0) {
+      record.azureDeployments = deployments;
+    }
+    const apiVersion = state.apiVersion.trim();
+    if (apiVersion) {
+      record.azureApiVersion = apiVersion;
+    }
+  }
+
+  return Object.values(nextMeta).some((value) => value !== undefined && value !== null && value !== '')
+    ? nextMeta
+    : undefined;
+}
@@ -0,0 +1,250 @@
+import React from 'react';
+import { Alert, DatePicker, Input, Modal, Radio, Select, Space, Table, Typography, message } from 'antd';
+import type { ColumnsType } from 'antd/es/table';
+import { useTranslation } from 'react-i18next';
+
+import { getToolSessionAnalytics } from './sessionManagerApi';
+import type {
+  SessionAnalyticsBucket,
+  SessionAnalyticsGroupBy,
+  SessionAnalyticsResult,
+  SessionTool,
+} from './types';
+import {
+  INDEXED_SESSION_TOOLS,
+  resolveSessionDateRange,
+  type SessionDateRange,
+} from './utils';
+import {
+  formatCompactInteger,
+  formatDuration,
+  formatInteger,
+  formatUsd,
+} from '../../gateway/utils/gatewayFormatters';
+
+const { RangePicker } = DatePicker;
+const { Text } = Typography;
+
+const GROUP_BY_OPTIONS: SessionAnalyticsGroupBy[] = ['tool', 'project', 'day', 'session'];
+
+const formatPercent = (ratio: number) => `${(ratio * 100).toFixed(1)}%`;
+
+interface SessionAnalyticsModalProps {
+  open: boolean;
+  tool: SessionTool;
+  onClose: () => void;
+}
+
+const SessionAnalyticsModal: React.FC<SessionAnalyticsModalProps> = ({ open, tool, onClose }) => {
+  const { t } = useTranslation();
+  const [groupBy, setGroupBy] = React.useState<SessionAnalyticsGroupBy>('tool');
+  const [tools, setTools] = React.useState<SessionTool[]>(
+    () => (INDEXED_SESSION_TOOLS.includes(tool) ? [tool] : []),
+  );
+  const [projectDir, setProjectDir] = React.useState('');
+  const [appliedProjectDir, setAppliedProjectDir] = React.useState('');
+  const [dateRange, setDateRange] = React.useState<SessionDateRange>(null);
+  const [result, setResult] = React.useState<SessionAnalyticsResult | null>(null);
+  const [loading, setLoading] = React.useState(false);
+
+  const loadAnalytics = React.useCallback(async () => {
+    setLoading(true);
+    try {
+      setResult(await getToolSessionAnalytics({
+        tools,
+        groupBy,
+        projectDir: appliedProjectDir,
+        ...resolveSessionDateRange(dateRange),
+      }));
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error);
+      message.error(errorMessage || t('common.error'));
+    } finally {
+      setLoading(false);
+    }
+  }, [appliedProjectDir, dateRange, groupBy, t, tools]);
+
+  React.useEffect(() => {
+    if (open) {
+      void loadAnalytics();
+    }
+  }, [loadAnalytics, open]);
+
+  const renderBucketLabel = (bucket: SessionAnalyticsBucket) => {
+    if (groupBy === 'tool' && bucket.tool) {
+      return <Text strong>{t(`subModules.${bucket.tool}`)}</Text>;
+    }
+    if (groupBy === 'session') {
+      return (
+        <Space direction="vertical" size={0}>
+          <Text strong ellipsis={{ tooltip: bucket.sourcePath }}>{bucket.title || bucket.sourcePath || bucket.key}</Text>
+          {bucket.tool ? (
+            <Text type="secondary" style={{ fontSize: 12 }}>{t(`subModules.${bucket.tool}`)}</Text>
+          ) : null}
+        </Space>
+      );
+    }
+    return (
+      <Text strong ellipsis={{ tooltip: bucket.key }}>
+        {bucket.key || t('sessionManager.analytics.unknownBucket')}
+      </Text>
+    );
+  };
+
+  const statColumns: ColumnsType<SessionAnalyticsBucket> = [
+    {
+      title: t('sessionManager.analytics.columnSessions'),
+      key: 'sessions',
+      width: 90,
+      align: 'right',
+      render: (_, bucket) => formatInteger(bucket.stats.sessionCount),
+    },
+    {
+      title: t('sessionManager.analytics.columnTokens'),
+      key: 'tokens',
+      width: 100,
+      align: 'right',
+      render: (_, bucket) => formatCompactInteger(bucket.stats.totalTokens),
+    },
+    {
+      title: t('sessionManager.analytics.columnCacheHitRate'),
+      key: 'cacheHitRate',
+      width: 100,
+      align: 'right',
+      render: (_, bucket) => formatPercent(bucket.stats.cacheHitRate),
+    },
+    {
+      title: t('sessionManager.analytics.columnCost'),
+      key: 'cost',
+      width: 100,
+      align: 'right',
+      render: (_, bucket) => formatUsd(bucket.stats.costUsd, 4),
+    },
+    {
+      title: t('sessionManager.analytics.columnToolCalls'),
+      key: 'toolCalls',
+      width: 100,
+      align: 'right',
+      render: (_, bucket) => formatInteger(bucket.stats.toolCallCount),
+    },
+    {
+      title: t('sessionManager.analytics.columnErrorRate'),
+      key: 'errorRate',
+      width: 90,
+      align: 'right',
+      render: (_, bucket) => formatPercent(bucket.stats.toolErrorRate),
+    },
+    {
+      title: t('sessionManager.analytics.columnWallTime'),
+      key: 'wallTime',
+      width: 100,
+      align: 'right',
+      render: (_, bucket) => formatDuration(bucket.stats.wallTimeMs),
+    },
+  ];
+
+  const columns: ColumnsType<SessionAnalyticsBucket> = [
+    {
+      title: t(`sessionManager.analytics.groupBy.${groupBy}`),
+      key: 'bucket',
+      render: (_, bucket) => renderBucketLabel(bucket),
+    },
+    ...statColumns,
+  ];
+
+  const totals = result?.totals;
+
+  return (
+    <Modal
+      open={open}
+      title={t('sessionManager.analytics.title')}
+      width={960}
+      onCancel={onClose}
+      footer={null}
+    >
+      <Space direction="vertical" size={12} style={{ width: '100%' }}>
+        <Space wrap>
+          <Radio.Group
+            size="small"
+            optionType="button"
+            value={groupBy}
+            options={GROUP_BY_OPTIONS.map((option) => ({
+              value: option,
+              label: t(`sessionManager.analytics.groupBy.${option}`),
+            }))}
+            onChange={(event) => setGroupBy(event.target.value as SessionAnalyticsGroupBy)}
+          />
+          <Select<SessionTool[]>
+            mode="multiple"
+            allowClear
+            maxTagCount="responsive"
+            style={{ minWidth: 200 }}
+            value={tools}
+            placeholder={t('sessionManager.globalSearch.allTools')}
+            options={INDEXED_SESSION_TOOLS.map((option) => ({
+              value: option,
+              label: t(`subModules.${option}`),
+            }))}
+            onChange={setTools}
+          />
+          <Input
+            allowClear
+            style={{ width: 180 }}
+            value={projectDir}
+            placeholder={t('sessionManager.globalSearch.projectDirPlaceholder')}
+            onChange={(event) => {
+              setProjectDir(event.target.value);
+              if (!event.target.value) {
+                setAppliedProjectDir('');
+              }
+            }}
+            onPressEnter={() => setAppliedProjectDir(projectDir.trim())}
+          />
+          <RangePicker
+            value={dateRange as never}
+            onChange={(dates) => setDateRange(dates as never)}
+          />
+        </Space>
+
+        {totals ? (
+          <Text type="secondary">
+            {t('sessionManager.analytics.totals', {
+              sessions: formatInteger(totals.sessionCount),
+              tokens: formatCompactInteger(totals.totalTokens),
+              cost: formatUsd(totals.costUsd, 4),
+              toolCalls: formatInteger(totals.toolCallCount),
+            })}
+          </Text>
+        ) : null}
+
+        {totals?.unpricedModels.length ? (
+          <Alert
+            type="warning"
+            showIcon
+            message={t('sessionManager.analytics.unpricedModels', {
+              models: totals.unpricedModels.join(', '),
+            })}
+          />
+        ) : null}
+
+        {result && result.failedCount > 0 ? (
+          <Text type="warning">
+            {t('sessionManager.analytics.failed', { count: result.failedCount })}
+          </Text>
+        ) : null}
+
+        <Table
+          rowKey="key"
+          size="small"
+          columns={columns}
+          dataSource={result?.buckets ?? []}
+          loading={loading}
+          pagination={groupBy === 'session' ? { pageSize: 20, size: 'small' } : false}
+          scroll={{ x: 880, y: 420 }}
+        />
+      </Space>
+    </Modal>
+  );
+};
+
+export default SessionAnalyticsModal;
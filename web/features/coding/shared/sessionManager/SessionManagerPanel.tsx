@@ -1,16 +1,19 @@
 import React from 'react';
 import {
+  BarChartOutlined,
   CheckOutlined,
   CloseOutlined,
   ClockCircleOutlined,
   CopyOutlined,
   DeleteOutlined,
   ExclamationCircleOutlined,
+  FileSearchOutlined,
   ExportOutlined,
   ImportOutlined,
   FolderOpenOutlined,
   MessageOutlined,
   ReloadOutlined,
+  RestOutlined,
   SearchOutlined,
 } from '@ant-design/icons';
 import {
@@ -63,7 +66,11 @@ import {
   resolveEffectiveSessionSourceMode,
   shortSessionId,
   shouldShowVisibleFeedback as shouldShowVisibleFeedbackForContext,
+  supportsSessionTrash,
 } from './utils';
+import SessionAnalyticsModal from './SessionAnalyticsModal';
+import SessionSearchModal from './SessionSearchModal';
+import SessionTrashModal from './SessionTrashModal';
 import { useKeepAlive } from '@/components/layout/KeepAliveOutlet';
 import styles from './SessionManagerPanel.module.less';
 
@@ -219,6 +226,9 @@ const SessionManagerContent: React.FC<SessionManagerContentProps> = ({
   const [messageSearchRunning, setMessageSearchRunning] = React.useState(false);
   const [metadataRefreshReason, setMetadataRefreshReason] = React.useState<MetadataRefreshReason>(null);
   const [importing, setImporting] = React.useState(false);
+  const [trashOpen, setTrashOpen] = React.useState(false);
+  const [globalSearchOpen, setGlobalSearchOpen] = React.useState(false);
+  const [analyticsOpen, setAnalyticsOpen] = React.useState(false);
   const [selectionMode, setSelectionMode] = React.useState(false);
   const [selectedSourcePaths, setSelectedSourcePaths] = React.useState<string[]>([]);
   const [bulkExporting, setBulkExporting] = React.useState(false);
@@ -776,9 +786,9 @@ const SessionManagerContent: React.FC<SessionManagerContentProps> = ({
     }
   };
 
-  const handleOpenDetail = (session: SessionMeta) => {
+  const handleOpenDetail = (session: SessionMeta, sessionTool: SessionTool = tool) => {
     const fromScrollTop = rememberScrollPosition();
-    navigate(buildSessionDetailPath(tool, session.sourcePath), {
+    navigate(buildSessionDetailPath(sessionTool, session.sourcePath), {
       state: {
         from: location.pathname + location.search,
         fromScrollTop,
@@ -796,8 +806,12 @@ const SessionManagerContent: React.FC<SessionManagerContentProps> = ({
     }
   };
 
-  const performDeleteSession = async (session: SessionMeta, visibleContextId: number) => {
-    await deleteToolSession(tool, session.sourcePath);
+  const performDeleteSession = async (
+    session: SessionMeta,
+    visibleContextId: number,
+    permanent: boolean,
+  ) => {
+    await deleteToolSession(tool, session.sourcePath, permanent);
 
     await loadSessions({
       forceRefresh: true,
@@ -807,7 +821,7 @@ const SessionManagerContent: React.FC<SessionManagerContentProps> = ({
       trigger: 'delete-refresh',
     });
     if (shouldShowVisibleFeedback(visibleContextId)) {
-      message.success(t('sessionManager.deleteSuccess'));
+      message.success(t(permanent ? 'sessionManager.deletePermanentSuccess' : 'sessionManager.deleteSuccess'));
     }
   };
 
@@ -850,7 +864,16 @@ const SessionManagerContent: React.FC<SessionManagerContentProps> = ({
   const performBulkDeleteSessions = async (
     visibleContextId: number,
   ): Promise<DeleteToolSessionsResult> => {
-    const result = await deleteToolSessions(tool, selectedSourcePaths);
+    const trashablePaths = selectedSourcePaths.filter((sourcePath) => supportsSessionTrash(tool, sourcePath));
+    const permanentPaths = selectedSourcePaths.filter((sourcePath) => !supportsSessionTrash(tool, sourcePath));
+    const results = await Promise.all([
+      trashablePaths.length > 0 ? deleteToolSessions(tool, trashablePaths) : null,
+      permanentPaths.length > 0 ? deleteToolSessions(tool, permanentPaths, true) : null,
+    ]);
+    const result: DeleteToolSessionsResult = {
+      deletedCount: results.reduce((count, item) => count + (item?.deletedCount ?? 0), 0),
+      failedItems: results.flatMap((item) => item?.failedItems ?? []),
+    };
     const failedSourcePathSet = new Set(result.failedItems.map((item) => item.sourcePath));
 
     await loadSessions({
@@ -916,34 +939,36 @@ const SessionManagerContent: React.FC<SessionManagerContentProps> = ({
       return;
     }
 
-    let exportFormat: SessionExportFormat = 'ai_toolbox';
-    if (tool === 'grok') {
-      const selectedFormat = await new Promise<SessionExportFormat | null>((resolve) => {
-        let currentFormat: SessionExportFormat = 'ai_toolbox';
-        Modal.confirm({
-          title: t('sessionManager.grokExportFormatTitle'),
-          content: (
-            <Radio.Group
-              defaultValue={currentFormat}
-              onChange={(event) => {
-                currentFormat = event.target.value as SessionExportFormat;
-              }}
-            >
-              <Radio value="ai_toolbox">{t('sessionManager.grokExportAiToolbox')}</Radio>
-              <Radio value="grok_markdown">{t('sessionManager.grokExportMarkdown')}</Radio>
-              <Radio value="grok_native">{t('sessionManager.grokExportNative')}</Radio>
-            </Radio.Group>
-          ),
-          okText: t('common.confirm'),
-          cancelText: t('common.cancel'),
-          onOk: () => resolve(currentFormat),
-          onCancel: () => resolve(null),
-        });
+    const exportFormat = await new Promise<SessionExportFormat | null>((resolve) => {
+      let currentFormat: SessionExportFormat = 'ai_toolbox';
+      Modal.confirm({
+        title: t('sessionManager.exportFormatTitle'),
+        content: (
+          <Radio.Group
+            defaultValue={currentFormat}
+            onChange={(event) => {
+              currentFormat = event.target.value as SessionExportFormat;
+            }}
+          >
+            <Radio value="ai_toolbox">{t('sessionManager.exportFormatAiToolbox')}</Radio>
+            <Radio value="markdown">{t('sessionManager.exportFormatMarkdown')}</Radio>
+            <Radio value="html">{t('sessionManager.exportFormatHtml')}</Radio>
+            {tool === 'grok' && (
+              <>
+                <Radio value="grok_markdown">{t('sessionManager.grokExportMarkdown')}</Radio>
+                <Radio value="grok_native">{t('sessionManager.grokExportNative')}</Radio>
+              </>
+            )}
+          </Radio.Group>
+        ),
+        okText: t('common.confirm'),
+        cancelText: t('common.cancel'),
+        onOk: () => resolve(currentFormat),
+        onCancel: () => resolve(null),
       });
-      if (!selectedFormat) {
-        return;
-      }
-      exportFormat = selectedFormat;
+    });
+    if (!exportFormat) {
+      return;
     }
 
     let selectedExportDir: string | null = null;
@@ -995,15 +1020,21 @@ const SessionManagerContent: React.FC<SessionManagerContentProps> = ({
       .slice(0, 5)
       .map((session) => formatSessionTitle(session))
       .join('、');
+    const permanentCount = selectedSourcePaths.filter((sourcePath) => !supportsSessionTrash(tool, sourcePath)).length;
+
+    let content = t('sessionManager.bulkDeleteConfirmContent', { count: selectedSourcePaths.length });
+    if (permanentCount > 0) {
+      content = t('sessionManager.bulkDeletePermanentConfirmContent', { permanentCount });
+    } else if (previewTitles) {
+      content = t('sessionManager.bulkDeleteConfirmContentWithPreview', {
+        count: selectedSourcePaths.length,
+        titles: previewTitles,
+      });
+    }
 
     Modal.confirm({
       title: t('sessionManager.bulkDeleteConfirmTitle', { count: selectedSourcePaths.length }),
-      content: previewTitles
-        ? t('sessionManager.bulkDeleteConfirmContentWithPreview', {
-          count: selectedSourcePaths.length,
-          titles: previewTitles,
-        })
-        : t('sessionManager.bulkDeleteConfirmContent', { count: selectedSourcePaths.length }),
+      content,
       icon: <ExclamationCircleOutlined />,
       okText: t('common.delete'),
       okButtonProps: { danger: true },
@@ -1027,9 +1058,10 @@ const SessionManagerContent: React.FC<SessionManagerContentProps> = ({
   };
 
   const handleDeleteSession = (session: SessionMeta) => {
+    const permanent = !supportsSessionTrash(tool, session.sourcePath);
     Modal.confirm({
       title: t('sessionManager.deleteConfirmTitle', { title: formatSessionTitle(session) }),
-      content: t('sessionManager.deleteConfirmContent'),
+      content: t(permanent ? 'sessionManager.deletePermanentConfirmContent' : 'sessionManager.deleteConfirmContent'),
       icon: <ExclamationCircleOutlined />,
       okText: t('common.delete'),
       okButtonProps: { danger: true },
@@ -1037,7 +1069,7 @@ const SessionManagerContent: React.FC<SessionManagerContentProps> = ({
       onOk: async () => {
         const visibleContextId = captureVisibleContextId();
         try {
-          await performDeleteSession(session, visibleContextId);
+          await performDeleteSession(session, visibleContextId, permanent);
         } catch (error) {
           if (!shouldShowVisibleFeedback(visibleContextId)) {
             return;
@@ -1171,6 +1203,33 @@ const SessionManagerContent: React.FC<SessionManagerContentProps> = ({
               >
                 {t('sessionManager.import')}
               </Button>
+              <Button
+                type="link"
+                size="small"
+                className={styles.actionButton}
+                icon={<FileSearchOutlined />}
+                onClick={() => setGlobalSearchOpen(true)}
+              >
+                {t('sessionManager.globalSearch.open')}
+              </Button>
+              <Button
+                type="link"
+                size="small"
+                className={styles.actionButton}
+                icon={<BarChartOutlined />}
+                onClick={() => setAnalyticsOpen(true)}
+              >
+                {t('sessionManager.analytics.open')}
+              </Button>
+              <Button
+                type="link"
+                size="small"
+                className={styles.actionButton}
+                icon={<RestOutlined />}
+                onClick={() => setTrashOpen(true)}
+              >
+                {t('sessionManager.trash')}
+              </Button>
             </>
           ) : null}
         </div>
@@ -1281,6 +1340,35 @@ const SessionManagerContent: React.FC<SessionManagerContentProps> = ({
         ) : null}
       </div>
 
+      <SessionSearchModal
+        open={globalSearchOpen}
+        onClose={() => setGlobalSearchOpen(false)}
+        onOpenSession={(hit) => {
+          setGlobalSearchOpen(false);
+          handleOpenDetail(hit.session, hit.tool);
+        }}
+      />
+
+      <SessionAnalyticsModal
+        open={analyticsOpen}
+        tool={tool}
+        onClose={() => setAnalyticsOpen(false)}
+      />
+
+      <SessionTrashModal
+        open={trashOpen}
+        tool={tool}
+        onClose={() => setTrashOpen(false)}
+        onRestored={async () => {
+          await loadSessions({
+            forceRefresh: true,
+            loadMode: 'refresh',
+            background: true,
+            refreshReason: 'manual-refresh',
+            trigger: 'trash-restore-refresh',
+          });
+        }}
+      />
     </>
   );
 };
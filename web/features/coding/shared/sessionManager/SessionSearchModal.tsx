@@ -0,0 +1,197 @@
+import React from 'react';
+import { ClockCircleOutlined, FolderOpenOutlined } from '@ant-design/icons';
+import { DatePicker, Empty, Input, Modal, Select, Space, Table, Tag, Typography, message } from 'antd';
+import type { ColumnsType } from 'antd/es/table';
+import { useTranslation } from 'react-i18next';
+
+import { searchToolSessions } from './sessionManagerApi';
+import type { SessionSearchHit, SessionSearchResult, SessionTool } from './types';
+import {
+  INDEXED_SESSION_TOOLS,
+  formatRelativeTime,
+  formatSessionTitle,
+  resolveSessionDateRange,
+  type SessionDateRange,
+} from './utils';
+import SessionSearchHighlight from './detail/SessionSearchHighlight';
+
+const { RangePicker } = DatePicker;
+const { Text } = Typography;
+
+interface SessionSearchModalProps {
+  open: boolean;
+  onClose: () => void;
+  onOpenSession: (hit: SessionSearchHit) => void;
+}
+
+const SessionSearchModal: React.FC<SessionSearchModalProps> = ({ open, onClose, onOpenSession }) => {
+  const { t } = useTranslation();
+  const [query, setQuery] = React.useState('');
+  const [tools, setTools] = React.useState<SessionTool[]>([]);
+  const [projectDir, setProjectDir] = React.useState('');
+  const [model, setModel] = React.useState('');
+  const [dateRange, setDateRange] = React.useState<SessionDateRange>(null);
+  const [result, setResult] = React.useState<SessionSearchResult | null>(null);
+  const [searchedQuery, setSearchedQuery] = React.useState('');
+  const [loading, setLoading] = React.useState(false);
+
+  const runSearch = async (forceRefresh = false) => {
+    const trimmedQuery = query.trim();
+    if (!trimmedQuery) {
+      return;
+    }
+    setLoading(true);
+    try {
+      const nextResult = await searchToolSessions({
+        query: trimmedQuery,
+        tools,
+        projectDir,
+        model,
+        ...resolveSessionDateRange(dateRange),
+        forceRefresh,
+      });
+      setResult(nextResult);
+      setSearchedQuery(trimmedQuery);
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error);
+      message.error(errorMessage || t('common.error'));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const columns: ColumnsType<SessionSearchHit> = [
+    {
+      title: t('sessionManager.globalSearch.columnSession'),
+      key: 'session',
+      render: (_, hit) => (
+        <Space direction="vertical" size={2} style={{ width: '100%' }}>
+          <Space size={6} wrap>
+            <Tag>{t(`subModules.${hit.tool}`)}</Tag>
+            <Text strong>{formatSessionTitle(hit.session)}</Text>
+          </Space>
+          <Text type="secondary" style={{ fontSize: 12 }}>
+            <SessionSearchHighlight text={hit.snippet} query={searchedQuery} />
+          </Text>
+          <Space size={12} wrap>
+            <Text type="secondary" style={{ fontSize: 12 }}>
+              <ClockCircleOutlined style={{ marginRight: 4 }} />
+              {formatRelativeTime(hit.session.lastActiveAt || hit.session.createdAt, t)}
+            </Text>
+            {hit.session.projectDir ? (
+              <Text type="secondary" style={{ fontSize: 12 }} ellipsis={{ tooltip: hit.session.projectDir }}>
+                <FolderOpenOutlined style={{ marginRight: 4 }} />
+                {hit.session.projectDir}
+              </Text>
+            ) : null}
+            {hit.models?.length ? (
+              <Text type="secondary" style={{ fontSize: 12 }}>
+                {hit.models.join(', ')}
+              </Text>
+            ) : null}
+          </Space>
+        </Space>
+      ),
+    },
+  ];
+
+  return (
+    <Modal
+      open={open}
+      title={t('sessionManager.globalSearch.title')}
+      width={860}
+      onCancel={onClose}
+      footer={null}
+    >
+      <Space direction="vertical" size={12} style={{ width: '100%' }}>
+        <Input.Search
+          allowClear
+          value={query}
+          placeholder={t('sessionManager.globalSearch.placeholder')}
+          enterButton
+          loading={loading}
+          onChange={(event) => setQuery(event.target.value)}
+          onSearch={() => void runSearch()}
+        />
+        <Space wrap>
+          <Select<SessionTool[]>
+            mode="multiple"
+            allowClear
+            maxTagCount="responsive"
+            style={{ minWidth: 200 }}
+            value={tools}
+            placeholder={t('sessionManager.globalSearch.allTools')}
+            options={INDEXED_SESSION_TOOLS.map((tool) => ({
+              value: tool,
+              label: t(`subModules.${tool}`),
+            }))}
+            onChange={setTools}
+          />
+          <Input
+            allowClear
+            style={{ width: 180 }}
+            value={projectDir}
+            placeholder={t('sessionManager.globalSearch.projectDirPlaceholder')}
+            onChange={(event) => setProjectDir(event.target.value)}
+            onPressEnter={() => void runSearch()}
+          />
+          <Input
+            allowClear
+            style={{ width: 150 }}
+            value={model}
+            placeholder={t('sessionManager.globalSearch.modelPlaceholder')}
+            onChange={(event) => setModel(event.target.value)}
+            onPressEnter={() => void runSearch()}
+          />
+          <RangePicker
+            value={dateRange as never}
+            onChange={(dates) => setDateRange(dates as never)}
+          />
+        </Space>
+        {result ? (
+          <Space size={12} wrap>
+            <Text type="secondary">
+              {t('sessionManager.globalSearch.summary', {
+                total: result.total,
+                indexed: result.indexedCount,
+                reindexed: result.reindexedCount,
+              })}
+            </Text>
+            {result.failedCount > 0 ? (
+              <Text type="warning">
+                {t('sessionManager.globalSearch.failed', { count: result.failedCount })}
+              </Text>
+            ) : null}
+            <Typography.Link onClick={() => void runSearch(true)} disabled={loading}>
+              {t('sessionManager.globalSearch.rebuild')}
+            </Typography.Link>
+          </Space>
+        ) : null}
+        <Table
+          rowKey={(hit) => `${hit.tool}:${hit.session.sourcePath}`}
+          size="small"
+          showHeader={false}
+          columns={columns}
+          dataSource={result?.items ?? []}
+          loading={loading}
+          pagination={false}
+          scroll={{ y: 420 }}
+          onRow={(hit) => ({
+            style: { cursor: 'pointer' },
+            onClick: () => onOpenSession(hit),
+          })}
+          locale={{
+            emptyText: (
+              <Empty
+                image={Empty.PRESENTED_IMAGE_SIMPLE}
+                description={t(result ? 'sessionManager.globalSearch.noResults' : 'sessionManager.globalSearch.hint')}
+              />
+            ),
+          }}
+        />
+      </Space>
+    </Modal>
+  );
+};
+
+export default SessionSearchModal;
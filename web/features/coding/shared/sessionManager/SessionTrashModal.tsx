@@ -0,0 +1,175 @@
+import React from 'react';
+import { DeleteOutlined, UndoOutlined } from '@ant-design/icons';
+import { Button, Empty, Modal, Space, Table, Typography, message } from 'antd';
+import type { ColumnsType } from 'antd/es/table';
+import { useTranslation } from 'react-i18next';
+
+import { listSessionTrash, purgeSessionTrash, restoreSessionTrash } from './sessionManagerApi';
+import type { SessionTool, SessionTrashEntry } from './types';
+import { formatRelativeTime, shortSessionId } from './utils';
+
+const { Text } = Typography;
+
+interface SessionTrashModalProps {
+  open: boolean;
+  tool: SessionTool;
+  onClose: () => void;
+  onRestored: () => void | Promise<void>;
+}
+
+const SessionTrashModal: React.FC<SessionTrashModalProps> = ({
+  open,
+  tool,
+  onClose,
+  onRestored,
+}) => {
+  const { t } = useTranslation();
+  const [entries, setEntries] = React.useState<SessionTrashEntry[]>([]);
+  const [loading, setLoading] = React.useState(false);
+  const [busyId, setBusyId] = React.useState<string | null>(null);
+
+  const loadEntries = React.useCallback(async () => {
+    setLoading(true);
+    try {
+      const allEntries = await listSessionTrash();
+      setEntries(allEntries.filter((entry) => entry.tool === tool));
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error);
+      message.error(errorMessage || t('common.error'));
+    } finally {
+      setLoading(false);
+    }
+  }, [t, tool]);
+
+  React.useEffect(() => {
+    if (open) {
+      void loadEntries();
+    }
+  }, [loadEntries, open]);
+
+  const handleRestore = async (entry: SessionTrashEntry) => {
+    setBusyId(entry.id);
+    try {
+      const result = await restoreSessionTrash([entry.id]);
+      if (result.failedItems.length > 0) {
+        message.error(result.failedItems[0].error || t('common.error'));
+      } else {
+        message.success(t('sessionManager.trashRestoreSuccess'));
+        await onRestored();
+      }
+      await loadEntries();
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error);
+      message.error(errorMessage || t('common.error'));
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  const purge = async (ids: string[]) => {
+    try {
+      await purgeSessionTrash({ ids });
+      message.success(t('sessionManager.trashPurgeSuccess', { count: ids.length }));
+      await loadEntries();
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error);
+      message.error(errorMessage || t('common.error'));
+    }
+  };
+
+  const handlePurge = (targets: SessionTrashEntry[]) => {
+    Modal.confirm({
+      title: t('sessionManager.trashPurgeConfirmTitle', { count: targets.length }),
+      content: t('sessionManager.trashPurgeConfirmContent'),
+      okButtonProps: { danger: true },
+      onOk: () => purge(targets.map((entry) => entry.id)),
+    });
+  };
+
+  const columns: ColumnsType<SessionTrashEntry> = [
+    {
+      title: t('sessionManager.trashColumnSession'),
+      key: 'session',
+      render: (_, entry) => (
+        <Space direction="vertical" size={0}>
+          <Text strong>{entry.title || shortSessionId(entry.sessionId)}</Text>
+          <Text type="secondary" ellipsis={{ tooltip: entry.originalPath }}>
+            {entry.projectDir || entry.originalPath}
+          </Text>
+        </Space>
+      ),
+    },
+    {
+      title: t('sessionManager.trashColumnDeletedAt'),
+      key: 'deletedAt',
+      width: 140,
+      render: (_, entry) => formatRelativeTime(entry.deletedAt, t),
+    },
+    {
+      title: t('sessionManager.trashColumnActions'),
+      key: 'actions',
+      width: 180,
+      render: (_, entry) => (
+        <Space size={4}>
+          <Button
+            type="link"
+            size="small"
+            icon={<UndoOutlined />}
+            loading={busyId === entry.id}
+            onClick={() => void handleRestore(entry)}
+          >
+            {t('sessionManager.trashRestore')}
+          </Button>
+          <Button
+            type="link"
+            size="small"
+            danger
+            icon={<DeleteOutlined />}
+            disabled={busyId === entry.id}
+            onClick={() => handlePurge([entry])}
+          >
+            {t('sessionManager.trashPurge')}
+          </Button>
+        </Space>
+      ),
+    },
+  ];
+
+  return (
+    <Modal
+      open={open}
+      title={t('sessionManager.trashTitle')}
+      width={720}
+      onCancel={onClose}
+      footer={[
+        <Button
+          key="empty"
+          danger
+          disabled={entries.length === 0}
+          onClick={() => handlePurge(entries)}
+        >
+          {t('sessionManager.trashEmpty')}
+        </Button>,
+        <Button key="close" onClick={onClose}>
+          {t('common.close')}
+        </Button>,
+      ]}
+    >
+      <Text type="secondary">{t('sessionManager.trashHint')}</Text>
+      <Table
+        rowKey="id"
+        size="small"
+        columns={columns}
+        dataSource={entries}
+        loading={loading}
+        pagination={false}
+        scroll={{ y: 420 }}
+        locale={{
+          emptyText: <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={t('sessionManager.trashEmptyState')} />,
+        }}
+      />
+    </Modal>
+  );
+};
+
+export default SessionTrashModal;
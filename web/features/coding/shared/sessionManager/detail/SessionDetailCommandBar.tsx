@@ -2,6 +2,7 @@ import React from 'react';
 import { Button, Input } from 'antd';
 import {
   ArrowLeft,
+  ArrowRightLeft,
   Bot,
   Brain,
   ChevronRight,
@@ -39,6 +40,7 @@ interface SessionDetailCommandBarProps {
   activeMatchPosition: number;
   canRename: boolean;
   canExport: boolean;
+  canConvert: boolean;
   canDelete: boolean;
   exporting: boolean;
   hasResumeCommand: boolean;
@@ -53,6 +55,7 @@ interface SessionDetailCommandBarProps {
   onNextMatch: () => void;
   onRename: () => void;
   onExport: () => void;
+  onConvert: () => void;
   onCopyResume: () => void;
   onDelete: () => void;
   onBackToParent: () => void;
@@ -82,6 +85,7 @@ const SessionDetailCommandBar: React.FC<SessionDetailCommandBarProps> = ({
   activeMatchPosition,
   canRename,
   canExport,
+  canConvert,
   canDelete,
   exporting,
   hasResumeCommand,
@@ -96,6 +100,7 @@ const SessionDetailCommandBar: React.FC<SessionDetailCommandBarProps> = ({
   onNextMatch,
   onRename,
   onExport,
+  onConvert,
   onCopyResume,
   onDelete,
   onBackToParent,
@@ -158,6 +163,11 @@ const SessionDetailCommandBar: React.FC<SessionDetailCommandBarProps> = ({
             {t(exporting ? 'sessionManager.exporting' : 'sessionManager.export')}
           </Button>
         ) : null}
+        {canConvert ? (
+          <Button icon={<ArrowRightLeft size={15} />} onClick={onConvert}>
+            {t('sessionManager.convert')}
+          </Button>
+        ) : null}
         {!isSubagentDetail ? (
           <Button icon={<Copy size={15} />} disabled={!hasResumeCommand} onClick={onCopyResume}>
             {t('sessionManager.copyResume')}
@@ -15,6 +15,7 @@ import { save } from '@tauri-apps/plugin-dialog';
 import { useKeepAlive } from '@/components/layout/KeepAliveOutlet';
 import SecondaryPageShell from '@/components/layout/SecondaryPageShell';
 import {
+  convertToolSession,
   deleteToolSession,
   exportToolSession,
   getToolSessionDetail,
@@ -39,6 +40,7 @@ import {
   advanceVisibleContextId,
   formatSessionTitle,
   shouldShowVisibleFeedback as shouldShowVisibleFeedbackForContext,
+  supportsSessionTrash,
 } from '../utils';
 import SessionDetailWorkbench from './SessionDetailWorkbench';
 import styles from './SessionDetailPage.module.less';
@@ -56,11 +58,22 @@ const canRenameSession = (tool: SessionTool) => (
   tool === 'opencode' || tool === 'codex' || tool === 'pi'
 );
 
+// Tools a session can be rewritten into as a native, resumable session.
+const CONVERT_TARGET_TOOLS: SessionTool[] = ['codex', 'claudecode'];
+
+const SESSION_EXPORT_FILE_TYPES: Record<SessionExportFormat, { name: string; extension: string }> = {
+  ai_toolbox: { name: 'JSON', extension: 'json' },
+  markdown: { name: 'Markdown', extension: 'md' },
+  html: { name: 'HTML', extension: 'html' },
+  grok_markdown: { name: 'Markdown', extension: 'md' },
+  grok_native: { name: 'JSON', extension: 'json' },
+};
+
 const buildSessionExportFileName = (
   tool: SessionTool,
   session: SessionMeta,
   exportFormat: SessionExportFormat,
-) => `${tool}-session-${session.sessionId}.${exportFormat === 'grok_markdown' ? 'md' : 'json'}`;
+) => `${tool}-session-${session.sessionId}.${SESSION_EXPORT_FILE_TYPES[exportFormat].extension}`;
 
 const SessionDetailPage: React.FC<SessionDetailPageProps> = ({ tool }) => {
   const { t } = useTranslation();
@@ -75,6 +88,9 @@ const SessionDetailPage: React.FC<SessionDetailPageProps> = ({ tool }) => {
   const [exporting, setExporting] = React.useState(false);
   const [renameModalOpen, setRenameModalOpen] = React.useState(false);
   const [renaming, setRenaming] = React.useState(false);
+  const [convertModalOpen, setConvertModalOpen] = React.useState(false);
+  const [convertTarget, setConvertTarget] = React.useState<SessionTool | null>(null);
+  const [converting, setConverting] = React.useState(false);
   const [reloadNonce, setReloadNonce] = React.useState(0);
   const requestIdRef = React.useRef(0);
   const activePageRef = React.useRef(isActive);
@@ -100,6 +116,7 @@ const SessionDetailPage: React.FC<SessionDetailPageProps> = ({ tool }) => {
   const basePath = getSessionToolBasePath(tool);
   const sourcePath = routeParams?.sourcePath;
   const pageTitle = detail ? formatSessionTitle(detail.meta) : t('sessionManager.detailTitle');
+  const convertTargets = CONVERT_TARGET_TOOLS.filter((target) => target !== tool);
 
   const shouldShowVisibleFeedback = React.useCallback((visibleContextId?: number) => {
     return shouldShowVisibleFeedbackForContext(
@@ -231,42 +248,44 @@ const SessionDetailPage: React.FC<SessionDetailPageProps> = ({ tool }) => {
     const exportMessageKey = `session-export-${tool}`;
     const visibleContextId = captureVisibleContextId();
     try {
-      let exportFormat: SessionExportFormat = 'ai_toolbox';
-      if (tool === 'grok') {
-        const selectedFormat = await new Promise<SessionExportFormat | null>((resolve) => {
-          let currentFormat: SessionExportFormat = 'ai_toolbox';
-          Modal.confirm({
-            title: t('sessionManager.grokExportFormatTitle'),
-            content: (
-              <Radio.Group
-                defaultValue={currentFormat}
-                onChange={(event) => {
-                  currentFormat = event.target.value as SessionExportFormat;
-                }}
-              >
-                <Radio value="ai_toolbox">{t('sessionManager.grokExportAiToolbox')}</Radio>
-                <Radio value="grok_markdown">{t('sessionManager.grokExportMarkdown')}</Radio>
-                <Radio value="grok_native">{t('sessionManager.grokExportNative')}</Radio>
-              </Radio.Group>
-            ),
-            okText: t('common.confirm'),
-            cancelText: t('common.cancel'),
-            onOk: () => resolve(currentFormat),
-            onCancel: () => resolve(null),
-          });
+      const exportFormat = await new Promise<SessionExportFormat | null>((resolve) => {
+        let currentFormat: SessionExportFormat = 'ai_toolbox';
+        Modal.confirm({
+          title: t('sessionManager.exportFormatTitle'),
+          content: (
+            <Radio.Group
+              defaultValue={currentFormat}
+              onChange={(event) => {
+                currentFormat = event.target.value as SessionExportFormat;
+              }}
+            >
+              <Radio value="ai_toolbox">{t('sessionManager.exportFormatAiToolbox')}</Radio>
+              <Radio value="markdown">{t('sessionManager.exportFormatMarkdown')}</Radio>
+              <Radio value="html">{t('sessionManager.exportFormatHtml')}</Radio>
+              {tool === 'grok' && (
+                <>
+                  <Radio value="grok_markdown">{t('sessionManager.grokExportMarkdown')}</Radio>
+                  <Radio value="grok_native">{t('sessionManager.grokExportNative')}</Radio>
+                </>
+              )}
+            </Radio.Group>
+          ),
+          okText: t('common.confirm'),
+          cancelText: t('common.cancel'),
+          onOk: () => resolve(currentFormat),
+          onCancel: () => resolve(null),
         });
-        if (!selectedFormat) {
-          return;
-        }
-        exportFormat = selectedFormat;
+      });
+      if (!exportFormat) {
+        return;
       }
       const exportPath = await save({
         title: t('sessionManager.exportDialogTitle'),
         defaultPath: buildSessionExportFileName(tool, sessionDetail.meta, exportFormat),
         filters: [
           {
-            name: exportFormat === 'grok_markdown' ? 'Markdown' : 'JSON',
-            extensions: [exportFormat === 'grok_markdown' ? 'md' : 'json'],
+            name: SESSION_EXPORT_FILE_TYPES[exportFormat].name,
+            extensions: [SESSION_EXPORT_FILE_TYPES[exportFormat].extension],
           },
         ],
       });
@@ -348,20 +367,58 @@ const SessionDetailPage: React.FC<SessionDetailPageProps> = ({ tool }) => {
     }
   };
 
+  const openConvertModal = () => {
+    if (!detail || isSubagentDetail) {
+      return;
+    }
+    setConvertTarget(convertTargets[0] ?? null);
+    setConvertModalOpen(true);
+  };
+
+  const handleConvertSession = async () => {
+    if (!detail || !convertTarget || isSubagentDetail) {
+      return;
+    }
+
+    const visibleContextId = captureVisibleContextId();
+    try {
+      setConverting(true);
+      const converted = await convertToolSession(tool, detail.meta.sourcePath, convertTarget);
+      dispatchSessionManagerRefresh(convertTarget);
+      setConvertModalOpen(false);
+      if (!shouldShowVisibleFeedback(visibleContextId)) {
+        return;
+      }
+      message.success(t('sessionManager.convertSuccess', {
+        tool: t(`subModules.${convertTarget}`),
+      }));
+      navigate(buildSessionDetailPath(convertTarget, converted.sourcePath));
+    } catch (error) {
+      if (!shouldShowVisibleFeedback(visibleContextId)) {
+        return;
+      }
+      const errorMessage = error instanceof Error ? error.message : String(error);
+      message.error(errorMessage || t('common.error'));
+    } finally {
+      setConverting(false);
+    }
+  };
+
   const handleDeleteSession = (session: SessionMeta) => {
+    const permanent = !supportsSessionTrash(tool, session.sourcePath);
     Modal.confirm({
       title: t('sessionManager.deleteConfirmTitle', { title: formatSessionTitle(session) }),
-      content: t('sessionManager.deleteConfirmContent'),
+      content: t(permanent ? 'sessionManager.deletePermanentConfirmContent' : 'sessionManager.deleteConfirmContent'),
       okText: t('common.delete'),
       okButtonProps: { danger: true },
       cancelText: t('common.cancel'),
       onOk: async () => {
         const visibleContextId = captureVisibleContextId();
         try {
-          await deleteToolSession(tool, session.sourcePath);
+          await deleteToolSession(tool, session.sourcePath, permanent);
           dispatchSessionManagerRefresh(tool);
           if (shouldShowVisibleFeedback(visibleContextId)) {
-            message.success(t('sessionManager.deleteSuccess'));
+            message.success(t(permanent ? 'sessionManager.deletePermanentSuccess' : 'sessionManager.deleteSuccess'));
           }
           handleBackToList();
         } catch (error) {
@@ -402,10 +459,12 @@ const SessionDetailPage: React.FC<SessionDetailPageProps> = ({ tool }) => {
                 exporting={exporting}
                 canRename={canRenameSession(tool) && !isSubagentDetail}
                 canExport={!isSubagentDetail}
+                canConvert={!isSubagentDetail && convertTargets.length > 0}
                 canDelete={!isSubagentDetail}
                 t={t}
                 onRename={openRenameModal}
                 onExport={() => void handleExportSession(detail)}
+                onConvert={openConvertModal}
                 onDelete={() => handleDeleteSession(detail.meta)}
                 onOpenSubagent={handleOpenSubagentDetail}
                 onBackToParent={handleBackToParentDetail}
@@ -454,6 +513,28 @@ const SessionDetailPage: React.FC<SessionDetailPageProps> = ({ tool }) => {
           </Form.Item>
         </Form>
       </Modal>
+
+      <Modal
+        open={convertModalOpen}
+        title={t('sessionManager.convertTitle')}
+        okText={t('sessionManager.convert')}
+        cancelText={t('common.cancel')}
+        onOk={() => void handleConvertSession()}
+        okButtonProps={{ disabled: !convertTarget }}
+        confirmLoading={converting}
+        onCancel={() => setConvertModalOpen(false)}
+        destroyOnHidden
+      >
+        <Radio.Group
+          value={convertTarget}
+          options={convertTargets.map((target) => ({
+            value: target,
+            label: t(`subModules.${target}`),
+          }))}
+          onChange={(event) => setConvertTarget(event.target.value as SessionTool)}
+        />
+        <div className={styles.convertHint}>{t('sessionManager.convertHint')}</div>
+      </Modal>
     </>
   );
 };
@@ -42,10 +42,12 @@ interface SessionDetailWorkbenchProps {
   exporting: boolean;
   canRename: boolean;
   canExport: boolean;
+  canConvert: boolean;
   canDelete: boolean;
   t: TFunction;
   onRename: () => void;
   onExport: () => void;
+  onConvert: () => void;
   onDelete: () => void;
   onOpenSubagent: (subagent: SessionSubagentMeta) => void;
   onBackToParent: () => void;
@@ -64,10 +66,12 @@ const SessionDetailWorkbench: React.FC<SessionDetailWorkbenchProps> = ({
   exporting,
   canRename,
   canExport,
+  canConvert,
   canDelete,
   t,
   onRename,
   onExport,
+  onConvert,
   onDelete,
   onOpenSubagent,
   onBackToParent,
@@ -339,6 +343,7 @@ const SessionDetailWorkbench: React.FC<SessionDetailWorkbenchProps> = ({
         activeMatchPosition={getActiveMatchPosition(activeMatchOffset, matchedMessageIndexes.length)}
         canRename={canRename}
         canExport={canExport}
+        canConvert={canConvert}
         canDelete={canDelete}
         exporting={exporting}
         hasResumeCommand={Boolean(detail.meta.resumeCommand)}
@@ -353,6 +358,7 @@ const SessionDetailWorkbench: React.FC<SessionDetailWorkbenchProps> = ({
         onNextMatch={handleNextMatch}
         onRename={onRename}
         onExport={onExport}
+        onConvert={onConvert}
         onCopyResume={() => {
           if (detail.meta.resumeCommand) {
             void onCopyText(detail.meta.resumeCommand, t('sessionManager.copyResumeSuccess'));
@@ -3,13 +3,20 @@ import { invoke } from '@tauri-apps/api/core';
 import type {
   DeleteToolSessionsResult,
   ExportToolSessionsResult,
+  SessionAnalyticsGroupBy,
+  SessionAnalyticsResult,
   SessionDetail,
   SessionListPage,
   SessionListLoadMode,
+  SessionMeta,
+  SessionSearchResult,
   SessionSourceMode,
   SessionSubagentMeta,
   SessionTool,
+  SessionUsageStats,
   SessionExportFormat,
+  SessionTrashEntry,
+  RestoreSessionTrashResult,
 } from './types';
 
 interface ListToolSessionsInput {
@@ -69,6 +76,73 @@ export const listToolSessions = async ({
   return await request;
 };
 
+interface SearchToolSessionsInput {
+  query: string;
+  tools?: SessionTool[];
+  projectDir?: string;
+  model?: string;
+  dateFrom?: number;
+  dateTo?: number;
+  limit?: number;
+  forceRefresh?: boolean;
+}
+
+export const searchToolSessions = async ({
+  query,
+  tools,
+  projectDir,
+  model,
+  dateFrom,
+  dateTo,
+  limit = 50,
+  forceRefresh = false,
+}: SearchToolSessionsInput): Promise<SessionSearchResult> => {
+  return await invoke<SessionSearchResult>('search_tool_sessions', {
+    query,
+    tools: tools?.length ? tools : undefined,
+    projectDir: projectDir || undefined,
+    model: model || undefined,
+    dateFrom,
+    dateTo,
+    limit,
+    forceRefresh,
+  });
+};
+
+interface GetToolSessionAnalyticsInput {
+  tools?: SessionTool[];
+  groupBy?: SessionAnalyticsGroupBy;
+  projectDir?: string;
+  dateFrom?: number;
+  dateTo?: number;
+}
+
+export const getToolSessionAnalytics = async ({
+  tools,
+  groupBy = 'tool',
+  projectDir,
+  dateFrom,
+  dateTo,
+}: GetToolSessionAnalyticsInput = {}): Promise<SessionAnalyticsResult> => {
+  return await invoke<SessionAnalyticsResult>('get_tool_session_analytics', {
+    tools: tools?.length ? tools : undefined,
+    groupBy,
+    projectDir: projectDir || undefined,
+    dateFrom,
+    dateTo,
+  });
+};
+
+export const getToolSessionUsage = async (
+  tool: SessionTool,
+  sourcePath: string,
+): Promise<SessionUsageStats> => {
+  return await invoke<SessionUsageStats>('get_tool_session_usage', {
+    tool,
+    sourcePath,
+  });
+};
+
 export const listToolSessionPaths = async (
   tool: SessionTool,
   limit = 200,
@@ -116,20 +190,44 @@ export const getToolSubagentSessionDetail = async (
 export const deleteToolSession = async (
   tool: SessionTool,
   sourcePath: string,
+  permanent = false,
 ): Promise<void> => {
   await invoke('delete_tool_session', {
     tool,
     sourcePath,
+    permanent,
   });
 };
 
 export const deleteToolSessions = async (
   tool: SessionTool,
   sourcePaths: string[],
+  permanent = false,
 ): Promise<DeleteToolSessionsResult> => {
   return await invoke<DeleteToolSessionsResult>('delete_tool_sessions', {
     tool,
     sourcePaths,
+    permanent,
+  });
+};
+
+export const listSessionTrash = async (): Promise<SessionTrashEntry[]> => {
+  return await invoke<SessionTrashEntry[]>('list_session_trash');
+};
+
+export const restoreSessionTrash = async (
+  ids: string[],
+): Promise<RestoreSessionTrashResult> => {
+  return await invoke<RestoreSessionTrashResult>('restore_session_trash', { ids });
+};
+
+export const purgeSessionTrash = async (input: {
+  ids?: string[];
+  olderThanDays?: number;
+} = {}): Promise<number> => {
+  return await invoke<number>('purge_session_trash', {
+    ids: input.ids ?? null,
+    olderThanDays: input.olderThanDays ?? null,
   });
 };
 
@@ -182,3 +280,15 @@ export const renameToolSession = async (
     title,
   });
 };
+
+export const convertToolSession = async (
+  tool: SessionTool,
+  sourcePath: string,
+  targetTool: SessionTool,
+): Promise<SessionMeta> => {
+  return await invoke<SessionMeta>('convert_tool_session', {
+    tool,
+    sourcePath,
+    targetTool,
+  });
+};
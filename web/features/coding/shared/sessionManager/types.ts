@@ -14,7 +14,12 @@ export type SessionTool =
 export type SessionSourceMode = 'all' | 'local' | 'wsl';
 export type SessionListLoadMode = 'auto' | 'cache-first' | 'full' | 'refresh';
 export type SessionListCacheState = 'none' | 'quick' | 'stale' | 'fresh';
-export type SessionExportFormat = 'ai_toolbox' | 'grok_markdown' | 'grok_native';
+export type SessionExportFormat =
+  | 'ai_toolbox'
+  | 'markdown'
+  | 'html'
+  | 'grok_markdown'
+  | 'grok_native';
 
 export interface SessionMeta {
   providerId: SessionTool;
@@ -83,6 +88,76 @@ export interface SessionListPage {
   availableSources?: SessionSourceOption[];
 }
 
+export interface SessionSearchHit {
+  tool: SessionTool;
+  session: SessionMeta;
+  snippet: string;
+  models?: string[];
+  rank: number;
+}
+
+export interface SessionSearchResult {
+  items: SessionSearchHit[];
+  total: number;
+  indexedCount: number;
+  reindexedCount: number;
+  failedCount: number;
+}
+
+export type SessionAnalyticsGroupBy = 'session' | 'project' | 'tool' | 'day';
+
+export interface SessionModelUsageStat {
+  model: string;
+  messageCount: number;
+  inputTokens: number;
+  outputTokens: number;
+  cacheReadTokens: number;
+  cacheCreationTokens: number;
+  costUsd: number;
+}
+
+export interface SessionToolCallStat {
+  toolName: string;
+  callCount: number;
+  errorCount: number;
+}
+
+export interface SessionUsageStats {
+  sessionCount: number;
+  messageCount: number;
+  inputTokens: number;
+  outputTokens: number;
+  cacheReadTokens: number;
+  cacheCreationTokens: number;
+  totalTokens: number;
+  cacheHitRate: number;
+  recordedCostUsd: number;
+  costUsd: number;
+  unpricedModels: string[];
+  toolCallCount: number;
+  toolErrorCount: number;
+  toolErrorRate: number;
+  wallTimeMs: number;
+  durationMs: number;
+  models: SessionModelUsageStat[];
+  toolCalls: SessionToolCallStat[];
+}
+
+export interface SessionAnalyticsBucket {
+  key: string;
+  tool?: SessionTool;
+  title?: string;
+  sourcePath?: string;
+  stats: SessionUsageStats;
+}
+
+export interface SessionAnalyticsResult {
+  groupBy: SessionAnalyticsGroupBy;
+  buckets: SessionAnalyticsBucket[];
+  totals: SessionUsageStats;
+  failedCount: number;
+}
+
 export interface SessionDetail {
   meta: SessionMeta;
   messages: SessionMessage[];
@@ -109,6 +184,33 @@ export interface DeleteToolSessionsResult {
   failedItems: DeleteSessionFailure[];
 }
 
+export type SessionTrashStorage = 'snapshot' | 'files';
+
+export interface SessionTrashEntry {
+  id: string;
+  tool: SessionTool;
+  sessionId: string;
+  title?: string;
+  projectDir?: string;
+  sourcePath: string;
+  originalPath: string;
+  runtimeSource?: string;
+  runtimeDistro?: string;
+  deletedAt: number;
+  storage: SessionTrashStorage;
+  contextKey: string;
+}
+
+export interface SessionTrashFailure {
+  id: string;
+  error: string;
+}
+
+export interface RestoreSessionTrashResult {
+  restoredCount: number;
+  failedItems: SessionTrashFailure[];
+}
+
 export interface ExportSessionItem {
   sourcePath: string;
   exportPath: string;
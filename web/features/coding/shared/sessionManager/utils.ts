@@ -6,6 +6,7 @@ import type {
   SessionSourceMode,
   SessionSourceOption,
   SessionTocItem,
+  SessionTool,
 } from './types';
 
 export function advanceVisibleContextId(
@@ -32,6 +33,17 @@ export function shouldShowVisibleFeedback(
   return requestVisibleContextId === currentVisibleContextId;
 }
 
+/**
+ * Whether a deleted session can go to the trash. Claude Desktop sessions and Hermes
+ * SQLite rows can only be deleted permanently, which needs its own confirmation.
+ */
+export function supportsSessionTrash(tool: SessionTool, sourcePath: string): boolean {
+  if (tool === 'claudedesktop') {
+    return false;
+  }
+  return !(tool === 'hermes' && sourcePath.startsWith('sqlite:'));
+}
+
 export function resolveEffectiveSessionSourceMode(
   sourceMode: SessionSourceMode,
   availableSources: SessionSourceOption[],
@@ -178,3 +190,34 @@ export function usesCompactMessageCollapse(role: string): boolean {
     || normalizedRole === 'developer'
   );
 }
+
+/** Tools covered by the cross-tool session search index and analytics. */
+export const INDEXED_SESSION_TOOLS: SessionTool[] = [
+  'codex',
+  'claudecode',
+  'geminicli',
+  'opencode',
+  'openclaw',
+  'pi',
+  'oh_my_pi',
+  'grok',
+  'hermes',
+  'dsh',
+];
+
+interface SessionDateLike {
+  toDate: () => Date;
+}
+
+export type SessionDateRange = [SessionDateLike | null, SessionDateLike | null] | null;
+
+export function resolveSessionDateRange(range: SessionDateRange): {
+  dateFrom?: number;
+  dateTo?: number;
+} {
+  const [start, end] = range ?? [];
+  return {
+    dateFrom: start ? start.toDate().getTime() : undefined,
+    dateTo: end ? end.toDate().getTime() : undefined,
+  };
+}
@@ -10,6 +10,7 @@ import {
   Plus,
   Power,
   RefreshCw,
+  ShieldAlert,
   Tags,
   Trash2,
   TriangleAlert,
@@ -101,6 +102,10 @@ const SkillCardContent = React.memo(function SkillCardContent({
   const sourceWarningMessage = skill.source_health === 'warning'
     ? (skill.source_error || t('skills.sourceWarningFallback'))
     : undefined;
+  const safetyLevel = skill.safety_report?.level ?? 'clean';
+  const safetyLabel = safetyLevel === 'clean'
+    ? undefined
+    : t(`skills.safety.level.${safetyLevel}`, { count: skill.safety_report?.findings.length ?? 0 });
   const cardClassName = [
     styles.card,
     selectable && selected ? styles.cardSelected : undefined,
@@ -363,6 +368,15 @@ const SkillCardContent = React.memo(function SkillCardContent({
               <TriangleAlert size={11} aria-hidden="true" />
             </span>
           )}
+          {safetyLabel && (
+            <span
+              className={`${styles.safetyMeta}${safetyLevel === 'danger' ? ` ${styles.safetyMetaDanger}` : ''}`}
+              title={safetyLabel}
+              aria-label={safetyLabel}
+            >
+              <ShieldAlert size={11} aria-hidden="true" />
+            </span>
+          )}
           <span className={styles.hoverActions}>
             <button
               type="button"
@@ -12,13 +12,14 @@ import {
   Power,
   PowerOff,
   RefreshCw,
+  ShieldCheck,
   Trash2,
   X,
 } from 'lucide-react';
 import { useTranslation } from 'react-i18next';
 import MarkdownPreview from '@/components/common/MarkdownPreview';
 import * as api from '../services/skillsApi';
-import type { ManagedSkill, SkillDocument, ToolOption } from '../types';
+import type { ManagedSkill, SkillDocument, SkillLintReport, SkillSafetyReport, ToolOption } from '../types';
 import { hashTagColorIndex, normalizeTagList } from '../utils/skillTags';
 import { getSkillManifestPath } from '../utils/skillPath';
 import { GitHubSourceIcon, ToolIcon } from './ToolIcon';
@@ -121,6 +122,44 @@ export const SkillDetailPanel: React.FC<SkillDetailPanelProps> = ({
     setGroupDraft('');
   }, [skill.id]);
 
+  // A rescan result shown until the skill list reloads with the stored report.
+  const [rescannedReport, setRescannedReport] = React.useState<SkillSafetyReport | null>(null);
+  const [rescanning, setRescanning] = React.useState(false);
+  React.useEffect(() => {
+    setRescannedReport(null);
+  }, [skill.id, skill.safety_report]);
+  const safetyReport = rescannedReport ?? skill.safety_report;
+
+  const handleRescan = async () => {
+    setRescanning(true);
+    try {
+      setRescannedReport(await api.scanSkillSafety(skill.id));
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setRescanning(false);
+    }
+  };
+
+  // SKILL.md validation for the installed tools; re-run when the content changes.
+  const [lintReport, setLintReport] = React.useState<SkillLintReport | null>(null);
+  const installedToolKey = installedTools.map((tool) => tool.id).join(',');
+  React.useEffect(() => {
+    let cancelled = false;
+    setLintReport(null);
+    api
+      .lintSkill(skill.id, installedToolKey ? installedToolKey.split(',') : [])
+      .then((report) => {
+        if (!cancelled) setLintReport(report);
+      })
+      .catch(() => {
+        if (!cancelled) setLintReport(null);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [skill.id, skill.content_hash, installedToolKey]);
+
   const isUpdating = updatingSkillIds.includes(skill.id);
   // Shared disable state for the inline tag and group editors.
   const metaEditDisabled = loading || isUpdating || !skill.management_enabled;
@@ -477,6 +516,95 @@ export const SkillDetailPanel: React.FC<SkillDetailPanelProps> = ({
           )}
         </div>
 
+        {lintReport && (
+          <div className={styles.section}>
+            <p className={styles.sectionTitle}>
+              {t('skills.section.validation')}
+              <span className={styles.sectionCount}>{t(`skills.lint.status.${lintReport.status}`)}</span>
+            </p>
+            {lintReport.tools.length > 0 && (
+              <div className={styles.lintToolRow}>
+                {lintReport.tools.map((item) => {
+                  const tool = installedTools.find((candidate) => candidate.id === item.tool);
+                  const label = tool?.label ?? item.tool;
+                  const statusText = t(`skills.lint.status.${item.status}`);
+                  return (
+                    <span
+                      key={item.tool}
+                      className={`${styles.lintToolChip} ${styles[`lintTool_${item.status}`] ?? ''}`}
+                      title={`${label} — ${statusText}`}
+                    >
+                      <ToolIcon toolKey={item.tool} label={label} size={12} iconUrl={tool?.iconUrl ?? undefined} />
+                      {label}
+                    </span>
+                  );
+                })}
+              </div>
+            )}
+            {lintReport.issues.length > 0 && (
+              <ul className={styles.findingList}>
+                {lintReport.issues.map((issue, index) => {
+                  const tool = issue.tool
+                    ? (installedTools.find((candidate) => candidate.id === issue.tool)?.label ?? issue.tool)
+                    : null;
+                  return (
+                    <li
+                      key={`${issue.code}:${issue.tool ?? ''}:${index}`}
+                      className={`${styles.findingItem}${issue.severity === 'error' ? ` ${styles.findingItemError}` : ''}`}
+                    >
+                      <span className={styles.findingTitle}>
+                        {t(`skills.lint.code.${issue.code}`, { defaultValue: issue.message })}
+                      </span>
+                      <span className={styles.findingPath}>
+                        {[issue.line ? `SKILL.md:${issue.line}` : 'SKILL.md', tool].filter(Boolean).join(' · ')}
+                      </span>
+                      <span className={styles.findingExcerpt}>{issue.message}</span>
+                    </li>
+                  );
+                })}
+              </ul>
+            )}
+          </div>
+        )}
+
+        <div className={styles.section}>
+          <p className={styles.sectionTitle}>
+            {t('skills.section.safety')}
+            <span className={styles.sectionCount}>
+              {safetyReport
+                ? t(`skills.safety.level.${safetyReport.level}`, { count: safetyReport.findings.length })
+                : t('skills.safety.notScanned')}
+            </span>
+            <button
+              type="button"
+              className={styles.sectionAction}
+              disabled={rescanning}
+              onClick={handleRescan}
+            >
+              <ShieldCheck size={11} aria-hidden="true" />
+              {t('skills.safety.rescan')}
+            </button>
+          </p>
+          {safetyReport && safetyReport.findings.length > 0 && (
+            <ul className={styles.findingList}>
+              {safetyReport.findings.map((finding, index) => (
+                <li
+                  key={`${finding.path}:${finding.line ?? 0}:${finding.rule}:${index}`}
+                  className={`${styles.findingItem}${finding.severity === 'danger' ? ` ${styles.findingItemError}` : ''}`}
+                >
+                  <span className={styles.findingTitle}>
+                    {t(`skills.safety.rule.${finding.rule}`, { defaultValue: finding.message })}
+                  </span>
+                  <span className={styles.findingPath}>
+                    {finding.line ? `${finding.path}:${finding.line}` : finding.path}
+                  </span>
+                  {finding.excerpt && <code className={styles.findingExcerpt}>{finding.excerpt}</code>}
+                </li>
+              ))}
+            </ul>
+          )}
+        </div>
+
         <div className={styles.section}>
           <p className={styles.sectionTitle}>{t('skills.section.document')}</p>
           {(docLoading && !documents) ? (
@@ -14,6 +14,7 @@ import {
   confirmBatchOverwrite,
 } from '../../utils/errorHandlers';
 import { syncSkillToTools } from '../../utils/syncHelpers';
+import { isArchiveUrl, parseGitRepo } from '../../utils/gitUrl';
 import { refreshTrayMenu } from '@/services/appApi';
 import styles from './AddSkillModal.module.less';
 
@@ -136,7 +137,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
   const handleRepoSelect = (value: string) => {
     const repo = repos.find((r) => `${r.owner}/${r.name}` === value);
     if (repo) {
-      setGitUrl(`https://github.com/${repo.owner}/${repo.name}`);
+      setGitUrl(repo.url || `https://github.com/${repo.owner}/${repo.name}`);
       setGitBranch(repo.branch);
     }
   };
@@ -151,12 +152,16 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
     }
   };
 
-  const parseGitUrl = (url: string): { owner: string; name: string } | null => {
+  const parseGitUrl = (url: string): { owner: string; name: string; url?: string } | null => {
     const match = url.match(/github\.com[/:]([^/]+)\/([^/.]+)/);
     if (match) {
       return { owner: match[1], name: match[2] };
     }
-    return null;
+    // Other hosts keep the full source URL; archive downloads are one-off and not saved
+    const location = url.split('#')[0].trim();
+    if (isArchiveUrl(location)) return null;
+    const repo = parseGitRepo(location);
+    return repo ? { owner: `${repo.host}/${repo.owner}`, name: repo.repo, url: location } : null;
   };
 
   const doLocalInstall = async (overwrite: boolean) => {
@@ -289,7 +294,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
       // Save repo on success
       const parsed = parseGitUrl(gitUrl);
       if (parsed) {
-        await api.addSkillRepo(parsed.owner, parsed.name, gitBranch || 'main');
+        await api.addSkillRepo(parsed.owner, parsed.name, gitBranch || 'main', parsed.url);
         await loadRepos();
       }
 
@@ -341,7 +346,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
 
       const parsed = parseGitUrl(gitUrl);
       if (parsed) {
-        await api.addSkillRepo(parsed.owner, parsed.name, gitBranch || 'main');
+        await api.addSkillRepo(parsed.owner, parsed.name, gitBranch || 'main', parsed.url);
         await loadRepos();
       }
 
@@ -459,7 +464,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
       // Save repo on success
       const parsed = parseGitUrl(gitUrl);
       if (parsed) {
-        await api.addSkillRepo(parsed.owner, parsed.name, gitBranch || 'main');
+        await api.addSkillRepo(parsed.owner, parsed.name, gitBranch || 'main', parsed.url);
         await loadRepos();
       }
 
@@ -703,6 +708,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
                         <li>{t('skills.addGit.hintAutoSave')}</li>
                         <li>{t('skills.addGit.hintMultiSkill')}</li>
                         <li>{t('skills.addGit.hintBranch')}</li>
+                        <li>{t('skills.addGit.hintSources')}</li>
                       </ul>
                     </div>
                   </div>
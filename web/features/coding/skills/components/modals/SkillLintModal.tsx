@@ -0,0 +1,136 @@
+import React from 'react';
+import { Button, Checkbox, Empty, message, Modal, Spin, Tag, Tooltip } from 'antd';
+import { SyncOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import * as api from '../../services/skillsApi';
+import type { SkillLintReport, SkillLintStatus, ToolOption } from '../../types';
+import styles from './SkillLintModal.module.less';
+
+interface SkillLintModalProps {
+  open: boolean;
+  allTools: ToolOption[];
+  onClose: () => void;
+}
+
+const STATUS_COLORS: Record<SkillLintStatus, string> = {
+  ok: 'success',
+  warning: 'warning',
+  error: 'error',
+};
+
+export const SkillLintModal: React.FC<SkillLintModalProps> = ({ open, allTools, onClose }) => {
+  const { t } = useTranslation();
+  const [reports, setReports] = React.useState<SkillLintReport[]>([]);
+  const [loading, setLoading] = React.useState(false);
+  const [onlyProblems, setOnlyProblems] = React.useState(true);
+
+  const installedTools = React.useMemo(() => allTools.filter((tool) => tool.installed), [allTools]);
+  const installedToolKey = installedTools.map((tool) => tool.id).join(',');
+
+  const toolLabel = React.useCallback(
+    (toolId: string) => installedTools.find((tool) => tool.id === toolId)?.label ?? toolId,
+    [installedTools],
+  );
+
+  const runLint = React.useCallback(async () => {
+    setLoading(true);
+    try {
+      const toolIds = installedToolKey ? installedToolKey.split(',') : undefined;
+      setReports(await api.lintAllSkills(toolIds));
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setLoading(false);
+    }
+  }, [installedToolKey]);
+
+  React.useEffect(() => {
+    if (open) {
+      void runLint();
+    } else {
+      setReports([]);
+    }
+  }, [open, runLint]);
+
+  const counts = React.useMemo(
+    () => ({
+      errors: reports.filter((report) => report.status === 'error').length,
+      warnings: reports.filter((report) => report.status === 'warning').length,
+      ok: reports.filter((report) => report.status === 'ok').length,
+    }),
+    [reports],
+  );
+
+  const visibleReports = onlyProblems ? reports.filter((report) => report.status !== 'ok') : reports;
+
+  return (
+    <Modal
+      open={open}
+      title={t('skills.lint.title')}
+      width={820}
+      onCancel={onClose}
+      destroyOnHidden
+      className={styles.modal}
+      footer={
+        <div className={styles.footer}>
+          <Checkbox checked={onlyProblems} onChange={(event) => setOnlyProblems(event.target.checked)}>
+            {t('skills.lint.onlyProblems')}
+          </Checkbox>
+          <div className={styles.footerActions}>
+            <Button onClick={onClose}>{t('common.close')}</Button>
+            <Button type="primary" icon={<SyncOutlined />} loading={loading} onClick={() => void runLint()}>
+              {t('skills.lint.rerun')}
+            </Button>
+          </div>
+        </div>
+      }
+    >
+      <p className={styles.hint}>{t('skills.lint.hint')}</p>
+      <Spin spinning={loading}>
+        {reports.length > 0 && <div className={styles.summary}>{t('skills.lint.summary', counts)}</div>}
+        {visibleReports.length === 0 ? (
+          <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={loading ? null : t('skills.lint.empty')} />
+        ) : (
+          <ul className={styles.reportList}>
+            {visibleReports.map((report) => (
+              <li key={report.skill_id} className={styles.report}>
+                <div className={styles.reportHeader}>
+                  <span className={styles.reportName}>{report.name}</span>
+                  <Tag color={STATUS_COLORS[report.status]}>{t(`skills.lint.status.${report.status}`)}</Tag>
+                  <span className={styles.toolTags}>
+                    {report.tools.map((item) => (
+                      <Tooltip key={item.tool} title={t(`skills.lint.status.${item.status}`)}>
+                        <Tag color={STATUS_COLORS[item.status]} bordered={false}>
+                          {toolLabel(item.tool)}
+                        </Tag>
+                      </Tooltip>
+                    ))}
+                  </span>
+                </div>
+                {report.issues.length > 0 && (
+                  <ul className={styles.issueList}>
+                    {report.issues.map((issue, index) => (
+                      <li
+                        key={`${issue.code}:${issue.tool ?? ''}:${index}`}
+                        className={issue.severity === 'error' ? styles.issueError : styles.issueWarning}
+                      >
+                        <span className={styles.issueTitle}>
+                          {t(`skills.lint.code.${issue.code}`, { defaultValue: issue.message })}
+                          {issue.tool ? ` · ${toolLabel(issue.tool)}` : ''}
+                        </span>
+                        <span className={styles.issueMessage}>
+                          {issue.line ? `SKILL.md:${issue.line} — ` : ''}
+                          {issue.message}
+                        </span>
+                      </li>
+                    ))}
+                  </ul>
+                )}
+              </li>
+            ))}
+          </ul>
+        )}
+      </Spin>
+    </Modal>
+  );
+};
@@ -0,0 +1,396 @@
+import React from 'react';
+import { Button, Checkbox, Collapse, Empty, Input, message, Modal, Space, Table, Tag, Tooltip } from 'antd';
+import { CloudDownloadOutlined, DownloadOutlined, LockOutlined, SyncOutlined, UploadOutlined } from '@ant-design/icons';
+import { open as openDialog, save as saveDialog } from '@tauri-apps/plugin-dialog';
+import { useTranslation } from 'react-i18next';
+import * as api from '../../services/skillsApi';
+import type { ManagedSkill, SkillFileDiffStatus, SkillUpdateCheck, SkillUpdatePreview } from '../../types';
+import styles from './SkillVersionsModal.module.less';
+
+interface SkillVersionsModalProps {
+  open: boolean;
+  skills: ManagedSkill[];
+  onClose: () => void;
+  onSuccess: () => void;
+}
+
+interface PinDraft {
+  skill: ManagedSkill;
+  pinnedRef: string;
+  semverUpdates: boolean;
+}
+
+const FILE_STATUS_COLORS: Record<SkillFileDiffStatus, string> = {
+  added: 'success',
+  removed: 'error',
+  modified: 'warning',
+};
+
+const shortRevision = (revision: string | null | undefined) =>
+  revision ? (/^[0-9a-f]{40}$/i.test(revision) ? revision.slice(0, 7) : revision) : '-';
+
+const diffLineClass = (line: string) => {
+  if (line.startsWith('@@')) return styles.diffHunk;
+  if (line.startsWith('+')) return styles.diffAdded;
+  if (line.startsWith('-')) return styles.diffRemoved;
+  return undefined;
+};
+
+export const SkillVersionsModal: React.FC<SkillVersionsModalProps> = ({ open, skills, onClose, onSuccess }) => {
+  const { t } = useTranslation();
+  const [checks, setChecks] = React.useState<Record<string, SkillUpdateCheck>>({});
+  const [checking, setChecking] = React.useState(false);
+  const [busy, setBusy] = React.useState(false);
+  const [pinDraft, setPinDraft] = React.useState<PinDraft | null>(null);
+  const [review, setReview] = React.useState<SkillUpdatePreview | null>(null);
+  const [reviewLoadingId, setReviewLoadingId] = React.useState<string | null>(null);
+
+  const gitSkills = React.useMemo(() => skills.filter((skill) => skill.source_type === 'git'), [skills]);
+
+  React.useEffect(() => {
+    if (!open) {
+      setChecks({});
+      setPinDraft(null);
+      setReview(null);
+    }
+  }, [open]);
+
+  const runAction = async (action: () => Promise<void>) => {
+    setBusy(true);
+    try {
+      await action();
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleCheckUpdates = async () => {
+    setChecking(true);
+    try {
+      const results = await api.checkSkillUpdates();
+      setChecks(Object.fromEntries(results.map((result) => [result.skill_id, result])));
+      const available = results.filter((result) => result.update_available).length;
+      message.info(t('skills.versions.checkResult', { count: available }));
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setChecking(false);
+    }
+  };
+
+  const handleExportLockfile = () =>
+    runAction(async () => {
+      const filePath = await saveDialog({
+        title: t('skills.versions.exportLockfile'),
+        defaultPath: 'skills.lock.json',
+        filters: [{ name: 'JSON', extensions: ['json'] }],
+      });
+      if (!filePath) return;
+      const path = await api.exportSkillLockfileFile(filePath);
+      message.success(t('skills.versions.exportSuccess', { path }));
+    });
+
+  const handleApplyLockfile = async () => {
+    const selected = await openDialog({
+      title: t('skills.versions.applyLockfile'),
+      multiple: false,
+      directory: false,
+      filters: [{ name: 'JSON', extensions: ['json'] }],
+    });
+    if (typeof selected !== 'string') return;
+    Modal.confirm({
+      title: t('skills.versions.applyConfirmTitle'),
+      content: t('skills.versions.applyConfirmContent', { path: selected }),
+      okText: t('skills.versions.applyLockfile'),
+      cancelText: t('common.cancel'),
+      onOk: () =>
+        runAction(async () => {
+          const result = await api.applySkillLockfileFile(selected);
+          onSuccess();
+          setChecks({});
+          const summary = t('skills.versions.applyResult', {
+            pinned: result.pinned.length,
+            updated: result.updated.length,
+          });
+          const problems = [
+            ...result.missing.map((name) => t('skills.versions.applyMissing', { name })),
+            ...result.errors.map((error) => `${error.name}: ${error.error}`),
+          ];
+          if (problems.length > 0) {
+            Modal.warning({
+              title: summary,
+              content: (
+                <ul className={styles.problemList}>
+                  {problems.map((problem) => (
+                    <li key={problem}>{problem}</li>
+                  ))}
+                </ul>
+              ),
+            });
+          } else {
+            message.success(summary);
+          }
+        }),
+    });
+  };
+
+  const handleSavePin = () =>
+    runAction(async () => {
+      if (!pinDraft) return;
+      const pinnedRef = pinDraft.pinnedRef.trim();
+      await api.setSkillPin(pinDraft.skill.id, pinnedRef || null, pinnedRef ? pinDraft.semverUpdates : false);
+      setPinDraft(null);
+      setChecks((previous) => {
+        const next = { ...previous };
+        delete next[pinDraft.skill.id];
+        return next;
+      });
+      onSuccess();
+      message.success(t('skills.versions.pinSaved'));
+    });
+
+  const handleReview = async (check: SkillUpdateCheck) => {
+    if (!check.update_ref) return;
+    setReviewLoadingId(check.skill_id);
+    try {
+      setReview(await api.previewSkillUpdate(check.skill_id, check.update_ref));
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setReviewLoadingId(null);
+    }
+  };
+
+  const handleAccept = () =>
+    runAction(async () => {
+      if (!review) return;
+      await api.acceptSkillUpdate(review.skill_id, review.to_ref);
+      message.success(t('skills.versions.accepted', { name: review.name }));
+      setChecks((previous) => {
+        const next = { ...previous };
+        delete next[review.skill_id];
+        return next;
+      });
+      setReview(null);
+      onSuccess();
+    });
+
+  const renderUpdate = (skill: ManagedSkill) => {
+    const check = checks[skill.id];
+    if (!check) {
+      return <span className={styles.muted}>-</span>;
+    }
+    if (check.error) {
+      return (
+        <Tooltip title={check.error}>
+          <Tag color="error">{t('skills.versions.checkFailed')}</Tag>
+        </Tooltip>
+      );
+    }
+    if (!check.update_available) {
+      return <Tag color="success">{t('skills.versions.upToDate')}</Tag>;
+    }
+    return (
+      <Space size={6}>
+        <Tag color="processing">
+          {check.latest_ref} @ {shortRevision(check.latest_revision)}
+        </Tag>
+        <Button
+          size="small"
+          loading={reviewLoadingId === skill.id}
+          onClick={() => void handleReview(check)}
+        >
+          {t('skills.versions.review')}
+        </Button>
+      </Space>
+    );
+  };
+
+  return (
+    <>
+      <Modal
+        open={open}
+        title={t('skills.versions.title')}
+        width={900}
+        onCancel={onClose}
+        destroyOnHidden
+        className={styles.modal}
+        footer={
+          <div className={styles.footer}>
+            <Space>
+              <Button icon={<DownloadOutlined />} disabled={busy} onClick={() => void handleExportLockfile()}>
+                {t('skills.versions.exportLockfile')}
+              </Button>
+              <Button icon={<UploadOutlined />} disabled={busy} onClick={() => void handleApplyLockfile()}>
+                {t('skills.versions.applyLockfile')}
+              </Button>
+            </Space>
+            <Space>
+              <Button onClick={onClose}>{t('common.close')}</Button>
+              <Button
+                type="primary"
+                icon={<SyncOutlined />}
+                loading={checking}
+                disabled={gitSkills.length === 0}
+                onClick={() => void handleCheckUpdates()}
+              >
+                {t('skills.versions.checkUpdates')}
+              </Button>
+            </Space>
+          </div>
+        }
+      >
+        <p className={styles.hint}>{t('skills.versions.hint')}</p>
+        {gitSkills.length === 0 ? (
+          <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={t('skills.versions.noGitSkills')} />
+        ) : (
+          <Table<ManagedSkill>
+            size="small"
+            pagination={false}
+            rowKey="id"
+            dataSource={gitSkills}
+            scroll={{ y: 420 }}
+            columns={[
+              {
+                title: t('skills.versions.columns.skill'),
+                dataIndex: 'name',
+                ellipsis: true,
+              },
+              {
+                title: t('skills.versions.columns.installed'),
+                dataIndex: 'source_revision',
+                width: 110,
+                render: (value: string | null) => <code>{shortRevision(value)}</code>,
+              },
+              {
+                title: t('skills.versions.columns.pin'),
+                key: 'pin',
+                width: 200,
+                render: (_, skill) =>
+                  skill.pinned_ref ? (
+                    <Space size={4}>
+                      <Tag icon={<LockOutlined />}>{shortRevision(skill.pinned_ref)}</Tag>
+                      {skill.semver_updates && <Tag color="blue">{t('skills.versions.semver')}</Tag>}
+                    </Space>
+                  ) : (
+                    <span className={styles.muted}>{t('skills.versions.tracking')}</span>
+                  ),
+              },
+              {
+                title: t('skills.versions.columns.update'),
+                key: 'update',
+                render: (_, skill) => renderUpdate(skill),
+              },
+              {
+                key: 'actions',
+                width: 90,
+                render: (_, skill) => (
+                  <Button
+                    size="small"
+                    type="link"
+                    onClick={() =>
+                      setPinDraft({
+                        skill,
+                        pinnedRef: skill.pinned_ref ?? skill.source_revision ?? '',
+                        semverUpdates: skill.semver_updates,
+                      })
+                    }
+                  >
+                    {t('skills.versions.editPin')}
+                  </Button>
+                ),
+              },
+            ]}
+          />
+        )}
+      </Modal>
+
+      <Modal
+        open={!!pinDraft}
+        title={t('skills.versions.pinTitle', { name: pinDraft?.skill.name ?? '' })}
+        okText={t('common.save')}
+        cancelText={t('common.cancel')}
+        confirmLoading={busy}
+        onOk={() => void handleSavePin()}
+        onCancel={() => setPinDraft(null)}
+        destroyOnHidden
+      >
+        {pinDraft && (
+          <div className={styles.pinForm}>
+            <p className={styles.hint}>{t('skills.versions.pinHint')}</p>
+            <Input
+              value={pinDraft.pinnedRef}
+              placeholder={t('skills.versions.pinPlaceholder')}
+              onChange={(event) => setPinDraft({ ...pinDraft, pinnedRef: event.target.value })}
+            />
+            <Checkbox
+              checked={pinDraft.semverUpdates}
+              onChange={(event) => setPinDraft({ ...pinDraft, semverUpdates: event.target.checked })}
+            >
+              {t('skills.versions.semverUpdates')}
+            </Checkbox>
+          </div>
+        )}
+      </Modal>
+
+      <Modal
+        open={!!review}
+        title={t('skills.versions.reviewTitle', { name: review?.name ?? '' })}
+        width={960}
+        onCancel={() => setReview(null)}
+        destroyOnHidden
+        footer={
+          <Space>
+            <Button onClick={() => setReview(null)}>{t('common.cancel')}</Button>
+            <Button type="primary" icon={<CloudDownloadOutlined />} loading={busy} onClick={() => void handleAccept()}>
+              {t('skills.versions.accept')}
+            </Button>
+          </Space>
+        }
+      >
+        {review && (
+          <div className={styles.review}>
+            <p className={styles.hint}>
+              {t('skills.versions.reviewSummary', {
+                from: shortRevision(review.from_revision),
+                to: `${review.to_ref === review.to_revision ? '' : `${review.to_ref} @ `}${shortRevision(review.to_revision)}`,
+                count: review.files.length,
+              })}
+            </p>
+            {review.files.length === 0 ? (
+              <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={t('skills.versions.noFileChanges')} />
+            ) : (
+              <Collapse
+                size="small"
+                items={review.files.map((file) => ({
+                  key: file.path,
+                  label: (
+                    <Space size={8}>
+                      <Tag color={FILE_STATUS_COLORS[file.status]}>{t(`skills.versions.fileStatus.${file.status}`)}</Tag>
+                      <code>{file.path}</code>
+                    </Space>
+                  ),
+                  children: file.diff ? (
+                    <pre className={styles.diff}>
+                      {file.diff.split('\n').map((line, index) => (
+                        <div key={index} className={diffLineClass(line)}>
+                          {line || ' '}
+                        </div>
+                      ))}
+                    </pre>
+                  ) : (
+                    <span className={styles.muted}>
+                      {file.binary ? t('skills.versions.binaryFile') : t('skills.versions.diffTooLarge')}
+                    </span>
+                  ),
+                }))}
+              />
+            )}
+          </div>
+        )}
+      </Modal>
+    </>
+  );
+};
@@ -4,7 +4,7 @@ import { FolderOpenOutlined, DeleteOutlined, PlusOutlined, ClearOutlined, Reload
 import { open } from '@tauri-apps/plugin-dialog';
 import { revealItemInDir } from '@tauri-apps/plugin-opener';
 import { useTranslation } from 'react-i18next';
-import type { ToolInfo, CustomTool, SkillViewMode, CentralRepoPathPreview, CentralRepoScan } from '../../types';
+import type { ToolInfo, CustomTool, SkillViewMode, CentralRepoPathPreview, CentralRepoScan, SkillSafetyPolicy } from '../../types';
 import * as api from '../../services/skillsApi';
 import { useSkillsStore } from '../../stores/skillsStore';
 import { refreshTrayMenu } from '@/services/appApi';
@@ -66,6 +66,7 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
   const [dailyHour, setDailyHour] = React.useState('09');
   const [dailyMinute, setDailyMinute] = React.useState('30');
   const [cronExpr, setCronExpr] = React.useState('0 3 * * *');
+  const [safetyPolicy, setSafetyPolicy] = React.useState<SkillSafetyPolicy>('warn');
   const [previewTimes, setPreviewTimes] = React.useState<string[]>([]);
   const [previewError, setPreviewError] = React.useState<string | null>(null);
   const [previewLoading, setPreviewLoading] = React.useState(false);
@@ -81,6 +82,7 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
     api.getGitCacheTtlSecs().then(setTtlSecs).catch(console.error);
     api.getShowSkillsInTray().then(setShowInTray).catch(console.error);
     api.getDefaultViewMode().then(setDefaultViewMode).catch(console.error);
+    api.getSafetyPolicy().then(setSafetyPolicy).catch(console.error);
     api.getAutoUpdate().then((config) => {
       setAutoUpdateEnabled(config.enabled);
       const match = /^(\d{1,2}) (\d{1,2}) \* \* \*$/.exec(config.schedule.trim());
@@ -324,6 +326,7 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
         enabled: autoUpdateEnabled,
         schedule: effectiveSchedule,
       });
+      await api.setSafetyPolicy(safetyPolicy);
       await loadToolStatus(); // Refresh global store
       onDefaultViewModeApply?.(defaultViewMode);
       message.success(t('common.success'));
@@ -648,6 +651,22 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
         </div>
       </div>
 
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('skills.safety.policy')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <Radio.Group
+            value={safetyPolicy}
+            onChange={(event) => setSafetyPolicy(event.target.value as SkillSafetyPolicy)}
+          >
+            <Radio value="warn">{t('skills.safety.policyWarn')}</Radio>
+            <Radio value="block">{t('skills.safety.policyBlock')}</Radio>
+          </Radio.Group>
+          <p className={styles.hint}>{t('skills.safety.policyHint')}</p>
+        </div>
+      </div>
+
       {cardColumnSetting !== undefined && cardColumnOptions && onCardColumnSettingChange && (
         <div className={styles.section}>
           <div className={styles.labelArea}>
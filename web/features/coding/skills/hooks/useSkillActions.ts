@@ -6,7 +6,7 @@ import { useTranslation } from 'react-i18next';
 import * as api from '../services/skillsApi';
 import { useSkills } from './useSkills';
 import type { ManagedSkill, ToolOption } from '../types';
-import { showGitError, confirmTargetOverwrite } from '../utils/errorHandlers';
+import { showGitError, confirmTargetOverwrite, confirmLintIssues } from '../utils/errorHandlers';
 import { shouldOverwriteExistingTarget, type BatchToolOptions } from '../utils/batchToolOptions';
 import { refreshTrayMenu } from '@/services/appApi';
 
@@ -71,6 +71,22 @@ export function useSkillActions({ allTools }: UseSkillActionsOptions): UseSkillA
     const target = skill.targets.find((t) => t.tool === toolId);
     const synced = Boolean(target);
 
+    // Validate SKILL.md against the tool's limits before linking it in. A
+    // failed lint call must not block the sync itself.
+    if (!synced) {
+      const report = await api.lintSkill(skill.id, [toolId]).catch(() => null);
+      const toolStatus = report?.tools.find((item) => item.tool === toolId)?.status;
+      if (report && toolStatus === 'error') {
+        const toolLabel = allTools.find((t) => t.id === toolId)?.label || toolId;
+        const issues = report.issues.filter(
+          (issue) => issue.severity === 'error' && (issue.tool === null || issue.tool === toolId),
+        );
+        if (!(await confirmLintIssues(skill.name, toolLabel, issues, t))) {
+          return;
+        }
+      }
+    }
+
     setActionLoading(true);
     try {
       if (synced) {
@@ -29,9 +29,11 @@ export {
   isSkillExistsError,
   extractSkillName,
   parseTargetExistsError,
+  parseSkillUnsafeError,
   showGitError,
   confirmSkillOverwrite,
   confirmTargetOverwrite,
+  confirmLintIssues,
   confirmBatchOverwrite,
 } from './utils/errorHandlers';
 export { syncSkillToTools } from './utils/syncHelpers';
@@ -18,7 +18,9 @@ import {
   Import,
   FileJson,
   LayoutGrid,
+  ListChecks,
   ListTree,
+  Lock,
   MinusCircle,
   MoreHorizontal,
   Plus,
@@ -60,6 +62,8 @@ import { NewToolsModal } from '../components/modals/NewToolsModal';
 import { SkillMetadataModal } from '../components/modals/SkillMetadataModal';
 import { SkillGroupsModal } from '../components/modals/SkillGroupsModal';
 import { SkillInventoryModal } from '../components/modals/SkillInventoryModal';
+import { SkillVersionsModal } from '../components/modals/SkillVersionsModal';
+import { SkillLintModal } from '../components/modals/SkillLintModal';
 import * as api from '../services/skillsApi';
 import {
   buildSkillGroups,
@@ -304,6 +308,8 @@ const SkillsPage: React.FC = () => {
   const [batchGroupValue, setBatchGroupValue] = React.useState('');
   const [groupsModalOpen, setGroupsModalOpen] = React.useState(false);
   const [inventoryModalOpen, setInventoryModalOpen] = React.useState(false);
+  const [versionsModalOpen, setVersionsModalOpen] = React.useState(false);
+  const [lintModalOpen, setLintModalOpen] = React.useState(false);
   const [enabledFilter, setEnabledFilter] = React.useState<SkillEnabledFilter>('all');
   const [tagFilter, setTagFilter] = React.useState<string[]>([]);
   const [batchTagModalOpen, setBatchTagModalOpen] = React.useState(false);
@@ -1201,6 +1207,24 @@ const SkillsPage: React.FC = () => {
                         setInventoryModalOpen(true);
                       }}
                     />
+                    <ToolbarActionItem
+                      icon={<Lock size={14} aria-hidden="true" />}
+                      title={t('skills.toolbar.versions')}
+                      description={t('skills.toolbar.versionsDescription')}
+                      onClick={() => {
+                        close();
+                        setVersionsModalOpen(true);
+                      }}
+                    />
+                    <ToolbarActionItem
+                      icon={<ListChecks size={14} aria-hidden="true" />}
+                      title={t('skills.toolbar.lintAll')}
+                      description={t('skills.toolbar.lintAllDescription')}
+                      onClick={() => {
+                        close();
+                        setLintModalOpen(true);
+                      }}
+                    />
                   </div>
                 </section>
               </>
@@ -1498,6 +1522,19 @@ const SkillsPage: React.FC = () => {
         onSuccess={refresh}
       />
 
+      <SkillVersionsModal
+        open={versionsModalOpen}
+        skills={skills}
+        onClose={() => setVersionsModalOpen(false)}
+        onSuccess={refresh}
+      />
+
+      <SkillLintModal
+        open={lintModalOpen}
+        allTools={allTools}
+        onClose={() => setLintModalOpen(false)}
+      />
+
       <NewToolsModal
         open={isNewToolsModalOpen}
       />
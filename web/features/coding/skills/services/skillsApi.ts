@@ -22,6 +22,12 @@ import type {
   AdoptCentralSkillsResult,
   DeleteManagedSkillOptions,
   SkillDocument,
+  SkillUpdateCheck,
+  SkillUpdatePreview,
+  SkillLockfileApplyResult,
+  SkillSafetyPolicy,
+  SkillSafetyReport,
+  SkillLintReport,
 } from '../types';
 
 // Tool Status
@@ -159,6 +165,60 @@ export const updateAllSkills = async (): Promise<UpdateAllResult> => {
   return invoke<UpdateAllResult>('skills_update_all');
 };
 
+// Version Pins & Lockfile
+export const setSkillPin = async (
+  skillId: string,
+  pinnedRef: string | null,
+  semverUpdates: boolean,
+): Promise<void> => {
+  await invoke('skills_set_pin', { skillId, pinnedRef, semverUpdates });
+};
+
+export const checkSkillUpdates = async (skillIds?: string[]): Promise<SkillUpdateCheck[]> => {
+  return invoke<SkillUpdateCheck[]>('skills_check_updates', { skillIds: skillIds ?? null });
+};
+
+export const previewSkillUpdate = async (
+  skillId: string,
+  reference: string,
+): Promise<SkillUpdatePreview> => {
+  return invoke<SkillUpdatePreview>('skills_preview_update', { skillId, reference });
+};
+
+export const acceptSkillUpdate = async (skillId: string, reference: string): Promise<UpdateResult> => {
+  return invoke<UpdateResult>('skills_accept_update', { skillId, reference });
+};
+
+export const exportSkillLockfileFile = async (filePath: string): Promise<string> => {
+  return invoke<string>('skills_export_lockfile_file', { filePath });
+};
+
+export const applySkillLockfileFile = async (filePath: string): Promise<SkillLockfileApplyResult> => {
+  return invoke<SkillLockfileApplyResult>('skills_apply_lockfile_file', { filePath });
+};
+
+// Safety Scan
+export const scanSkillSafety = async (skillId: string): Promise<SkillSafetyReport> => {
+  return invoke<SkillSafetyReport>('skills_scan_safety', { skillId });
+};
+
+export const getSafetyPolicy = async (): Promise<SkillSafetyPolicy> => {
+  return invoke<SkillSafetyPolicy>('skills_get_safety_policy');
+};
+
+export const setSafetyPolicy = async (policy: SkillSafetyPolicy): Promise<void> => {
+  await invoke('skills_set_safety_policy', { policy });
+};
+
+// Lint
+export const lintSkill = async (skillId: string, tools?: string[]): Promise<SkillLintReport> => {
+  return invoke<SkillLintReport>('skills_lint', { skillId, tools: tools ?? null });
+};
+
+export const lintAllSkills = async (tools?: string[]): Promise<SkillLintReport[]> => {
+  return invoke<SkillLintReport[]>('skills_lint_all', { tools: tools ?? null });
+};
+
 export const getAutoUpdate = async (): Promise<AutoUpdateConfig> => {
   return invoke<AutoUpdateConfig>('skills_get_auto_update');
 };
@@ -323,8 +383,13 @@ export const getSkillRepos = async (): Promise<SkillRepo[]> => {
   return invoke<SkillRepo[]>('skills_get_repos');
 };
 
-export const addSkillRepo = async (owner: string, name: string, branch?: string): Promise<void> => {
-  return invoke('skills_add_repo', { owner, name, branch });
+export const addSkillRepo = async (
+  owner: string,
+  name: string,
+  branch?: string,
+  url?: string,
+): Promise<void> => {
+  return invoke('skills_add_repo', { owner, name, branch, url: url ?? null });
 };
 
 export const removeSkillRepo = async (owner: string, name: string): Promise<void> => {
@@ -5,6 +5,13 @@ export interface ManagedSkill {
   name: string;
   source_type: 'local' | 'git' | 'import' | 'central';
   source_ref: string | null;
+  source_revision: string | null;
+  /** Commit or tag a git skill is pinned to; null tracks the branch head */
+  pinned_ref: string | null;
+  /** Let updates move a tag pin to newer tags of the same major version */
+  semver_updates: boolean;
+  /** Static safety scan from the last install, update or rescan */
+  safety_report: SkillSafetyReport | null;
   central_path: string;
   created_at: number;
   updated_at: number;
@@ -46,6 +53,8 @@ export interface SkillRepo {
   branch: string;
   enabled: boolean;
   created_at: number;
+  /** Full source URL for repos outside GitHub; null means github.com/{owner}/{name} */
+  url?: string | null;
 }
 
 export type SkillViewMode = 'flat' | 'grouped';
@@ -105,6 +114,96 @@ export interface UpdateAllResult {
   errors: UpdateAllError[];
 }
 
+export interface SkillUpdateCheck {
+  skill_id: string;
+  name: string;
+  source_revision: string | null;
+  pinned_ref: string | null;
+  latest_ref: string | null;
+  latest_revision: string | null;
+  update_available: boolean;
+  /** Ref to pass to preview/accept */
+  update_ref: string | null;
+  error: string | null;
+}
+
+export type SkillFileDiffStatus = 'added' | 'removed' | 'modified';
+
+export interface SkillFileDiff {
+  path: string;
+  status: SkillFileDiffStatus;
+  diff: string | null;
+  binary: boolean;
+}
+
+export interface SkillUpdatePreview {
+  skill_id: string;
+  name: string;
+  from_revision: string | null;
+  to_ref: string;
+  to_revision: string;
+  files: SkillFileDiff[];
+}
+
+export type SkillSafetyLevel = 'clean' | 'warning' | 'danger';
+
+export type SkillSafetyPolicy = 'warn' | 'block';
+
+export interface SkillSafetyFinding {
+  rule:
+    | 'executable_file'
+    | 'remote_pipe_exec'
+    | 'encoded_payload'
+    | 'credential_exfiltration'
+    | 'oversized_binary'
+    | 'symlink_escape';
+  severity: 'warning' | 'danger';
+  /** Relative to the skill directory */
+  path: string;
+  line: number | null;
+  message: string;
+  excerpt: string | null;
+}
+
+export interface SkillSafetyReport {
+  scanned_at: number;
+  level: SkillSafetyLevel;
+  findings: SkillSafetyFinding[];
+}
+
+export type SkillLintStatus = 'ok' | 'warning' | 'error';
+
+export interface SkillLintIssue {
+  code: string;
+  severity: 'error' | 'warning';
+  message: string;
+  /** 1-based line in SKILL.md */
+  line: number | null;
+  /** Tool whose limits the issue breaks; null affects every tool */
+  tool: string | null;
+}
+
+export interface SkillLintToolStatus {
+  tool: string;
+  status: SkillLintStatus;
+}
+
+export interface SkillLintReport {
+  skill_id: string;
+  name: string;
+  frontmatter_name: string | null;
+  status: SkillLintStatus;
+  issues: SkillLintIssue[];
+  tools: SkillLintToolStatus[];
+}
+
+export interface SkillLockfileApplyResult {
+  pinned: string[];
+  updated: string[];
+  missing: string[];
+  errors: UpdateAllError[];
+}
+
 /** Progress event payload emitted on the `skills-update-progress` channel. */
 export interface SkillsUpdateProgress {
   current: number;
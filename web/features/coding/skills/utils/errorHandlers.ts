@@ -2,7 +2,7 @@ import React from 'react';
 import { Modal, Button, message } from 'antd';
 import type { TFunction } from 'i18next';
 import { formatGitError, isGitError } from './gitErrorParser';
-import type { ToolOption } from '../types';
+import type { SkillLintIssue, ToolOption } from '../types';
 
 /**
  * Check if error is a SKILL_EXISTS error
@@ -28,6 +28,14 @@ export function parseTargetExistsError(errMsg: string): { targetPath: string } |
   return match ? { targetPath: match[1] } : null;
 }
 
+/**
+ * Parse SKILL_UNSAFE error (install/update refused by the safety scan policy)
+ */
+export function parseSkillUnsafeError(errMsg: string): { name: string; findings: string } | null {
+  const match = errMsg.match(/SKILL_UNSAFE\|([^|]*)\|([\s\S]*)/);
+  return match ? { name: match[1], findings: match[2] } : null;
+}
+
 /**
  * Show git error or general error message
  */
@@ -55,6 +63,21 @@ export function showGitError(
     return;
   }
 
+  // Handle SKILL_UNSAFE|name|findings error (block policy refused the install)
+  const unsafe = parseSkillUnsafeError(errMsg);
+  if (unsafe) {
+    Modal.error({
+      title: t('skills.safety.blockedTitle'),
+      content: React.createElement('div', null, [
+        React.createElement('p', { key: 'msg' }, t('skills.safety.blockedMessage', { name: unsafe.name })),
+        React.createElement('p', { key: 'findings', style: { fontSize: 12, color: 'var(--color-text-tertiary)' } },
+          unsafe.findings
+        ),
+      ]),
+    });
+    return;
+  }
+
   if (isGitError(errMsg)) {
     Modal.error({
       title: t('common.error'),
@@ -108,6 +131,37 @@ export function confirmTargetOverwrite(
   });
 }
 
+/**
+ * Confirm syncing a skill whose SKILL.md breaks the tool's limits
+ */
+export function confirmLintIssues(
+  skillName: string,
+  toolLabel: string,
+  issues: SkillLintIssue[],
+  t: TFunction
+): Promise<boolean> {
+  return new Promise((resolve) => {
+    Modal.confirm({
+      title: t('skills.lint.syncTitle'),
+      content: React.createElement('div', null, [
+        React.createElement('p', { key: 'msg' }, t('skills.lint.syncMessage', { skill: skillName, tool: toolLabel })),
+        React.createElement('ul', { key: 'issues', style: { paddingLeft: 18, fontSize: 12 } },
+          issues.map((issue, index) =>
+            React.createElement('li', { key: `${issue.code}-${index}` },
+              t(`skills.lint.code.${issue.code}`, { defaultValue: issue.message })
+            )
+          )
+        ),
+      ]),
+      okText: t('skills.lint.syncAnyway'),
+      okType: 'danger',
+      cancelText: t('common.cancel'),
+      onOk: () => resolve(true),
+      onCancel: () => resolve(false),
+    });
+  });
+}
+
 /**
  * Confirm batch overwrite with three options: overwrite, overwriteAll, skip
  */
@@ -12,6 +12,10 @@ const GIT_ERROR_CODES = {
   GIT_CHECKOUT_FAILED: 'skills.errors.gitCheckoutFailed',
   GIT_RESET_FAILED: 'skills.errors.gitResetFailed',
   GIT_REVPARSE_FAILED: 'skills.errors.gitRevParseFailed',
+  ARCHIVE_DOWNLOAD_FAILED: 'skills.errors.archiveDownloadFailed',
+  ARCHIVE_TOO_LARGE: 'skills.errors.archiveTooLarge',
+  ARCHIVE_UNSUPPORTED: 'skills.errors.archiveUnsupported',
+  ARCHIVE_INVALID: 'skills.errors.archiveInvalid',
 } as const;
 
 type GitErrorCode = keyof typeof GIT_ERROR_CODES;
@@ -44,7 +48,8 @@ export function parseGitError(errorMsg: string): ParsedGitError {
       details = parts.slice(2).join('|');
       break;
     case 'GIT_CLONE_FAILED':
-      // GIT_CLONE_FAILED|url|stderr
+    case 'ARCHIVE_DOWNLOAD_FAILED':
+      // ERROR_CODE|url|details
       params.url = parts[1] || '';
       details = parts.slice(2).join('|');
       break;
@@ -57,7 +62,10 @@ export function parseGitError(errorMsg: string): ParsedGitError {
     case 'GIT_FETCH_FAILED':
     case 'GIT_RESET_FAILED':
     case 'GIT_REVPARSE_FAILED':
-      // ERROR_CODE|stderr
+    case 'ARCHIVE_TOO_LARGE':
+    case 'ARCHIVE_UNSUPPORTED':
+    case 'ARCHIVE_INVALID':
+      // ERROR_CODE|details
       details = parts.slice(1).join('|');
       break;
     case 'GIT_NOT_FOUND':
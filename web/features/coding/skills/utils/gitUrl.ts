@@ -67,6 +67,14 @@ export function parseGitRepo(url: string | null | undefined): ParsedGitRepo | nu
   return null;
 }
 
+/**
+ * Whether a skill source URL points at a tarball or zip download.
+ */
+export function isArchiveUrl(url: string | null | undefined): boolean {
+  const path = (url ?? '').trim().split(/[?#]/)[0].toLowerCase();
+  return /^https?:\/\//.test(path) && /\.(tar\.gz|tgz|tar|zip)$/.test(path);
+}
+
 /**
  * Normalize any Git remote URL into a plain HTTPS web URL.
  * Returns null when the input cannot be parsed as a Git remote.
@@ -25,10 +25,17 @@ import {
 } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
 import { open } from '@tauri-apps/plugin-dialog';
-import { useSettingsStore, type WebDAVConfigFE } from '@/stores';
+import {
+  useSettingsStore,
+  toBackendS3,
+  type BackupType,
+  type S3ConfigFE,
+  type WebDAVConfigFE,
+} from '@/stores';
 import {
   listBackupFileFilterPathOptions,
   normalizeBackupCustomEntryPath,
+  testS3Connection,
   testWebDAVConnection,
   type BackupCustomEntry,
   type BackupCustomEntryType,
@@ -68,27 +75,39 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
     backupType,
     localBackupPath,
     webdav,
+    s3,
     backupImageAssetsEnabled,
+    backupSessionTrashEnabled,
     backupCliConfigFilesEnabled,
     backupCustomEntries,
     backupFileFilterRules,
+    backupEncryptionEnabled,
+    backupEncryptionPassphrase,
     setBackupSettings,
     autoBackupEnabled,
     autoBackupIntervalDays,
     autoBackupMaxKeep,
+    autoBackupMaxAgeDays,
     setAutoBackupSettings,
   } = useSettingsStore();
 
-  const [currentBackupType, setCurrentBackupType] = React.useState<'local' | 'webdav'>(backupType);
+  const [currentBackupType, setCurrentBackupType] = React.useState<BackupType>(backupType);
   const [currentLocalPath, setCurrentLocalPath] = React.useState(localBackupPath);
   const [testingConnection, setTestingConnection] = React.useState(false);
   const [currentBackupImageAssetsEnabled, setCurrentBackupImageAssetsEnabled] =
     React.useState(backupImageAssetsEnabled);
+  const [currentBackupSessionTrashEnabled, setCurrentBackupSessionTrashEnabled] =
+    React.useState(backupSessionTrashEnabled);
   const [currentBackupCliConfigFilesEnabled, setCurrentBackupCliConfigFilesEnabled] =
     React.useState(backupCliConfigFilesEnabled);
+  const [currentEncryptionEnabled, setCurrentEncryptionEnabled] =
+    React.useState(backupEncryptionEnabled);
+  const [currentEncryptionPassphrase, setCurrentEncryptionPassphrase] =
+    React.useState(backupEncryptionPassphrase);
   const [currentAutoBackupEnabled, setCurrentAutoBackupEnabled] = React.useState(autoBackupEnabled);
   const [currentIntervalDays, setCurrentIntervalDays] = React.useState(autoBackupIntervalDays);
   const [currentMaxKeep, setCurrentMaxKeep] = React.useState(autoBackupMaxKeep);
+  const [currentMaxAgeDays, setCurrentMaxAgeDays] = React.useState(autoBackupMaxAgeDays);
   const [currentBackupCustomEntries, setCurrentBackupCustomEntries] =
     React.useState<BackupCustomEntry[]>(backupCustomEntries);
   const [customEntryModalOpen, setCustomEntryModalOpen] = React.useState(false);
@@ -155,15 +174,20 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
       setCurrentBackupType(backupType);
       setCurrentLocalPath(localBackupPath);
       setCurrentBackupImageAssetsEnabled(backupImageAssetsEnabled);
+      setCurrentBackupSessionTrashEnabled(backupSessionTrashEnabled);
       setCurrentBackupCliConfigFilesEnabled(backupCliConfigFilesEnabled);
+      setCurrentEncryptionEnabled(backupEncryptionEnabled);
+      setCurrentEncryptionPassphrase(backupEncryptionPassphrase);
       setCurrentAutoBackupEnabled(autoBackupEnabled);
       setCurrentIntervalDays(autoBackupIntervalDays);
       setCurrentMaxKeep(autoBackupMaxKeep);
+      setCurrentMaxAgeDays(autoBackupMaxAgeDays);
       setCurrentBackupCustomEntries(backupCustomEntries);
       setCurrentFileFilterRules(backupFileFilterRules);
       form.setFieldsValue({
         backupType,
         webdav,
+        s3,
       });
       void loadFilterPathOptions();
     }
@@ -172,13 +196,18 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
     backupType,
     localBackupPath,
     webdav,
+    s3,
     backupImageAssetsEnabled,
+    backupSessionTrashEnabled,
     backupCliConfigFilesEnabled,
     backupCustomEntries,
     backupFileFilterRules,
+    backupEncryptionEnabled,
+    backupEncryptionPassphrase,
     autoBackupEnabled,
     autoBackupIntervalDays,
     autoBackupMaxKeep,
+    autoBackupMaxAgeDays,
     form,
     loadFilterPathOptions,
   ]);
@@ -201,19 +230,28 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
   const handleSave = async () => {
     try {
       const values = await form.validateFields();
+      if (currentEncryptionEnabled && !currentEncryptionPassphrase) {
+        message.warning(t('settings.backupSettings.encryption.passphraseRequired'));
+        return;
+      }
       await setBackupSettings({
         backupType: currentBackupType,
         localBackupPath: currentLocalPath,
         webdav: values.webdav as Partial<WebDAVConfigFE>,
+        s3: values.s3 as Partial<S3ConfigFE>,
         backupImageAssetsEnabled: currentBackupImageAssetsEnabled,
+        backupSessionTrashEnabled: currentBackupSessionTrashEnabled,
         backupCliConfigFilesEnabled: currentBackupCliConfigFilesEnabled,
         backupCustomEntries: currentBackupCustomEntries,
         backupFileFilterRules: currentFileFilterRules,
+        backupEncryptionEnabled: currentEncryptionEnabled,
+        backupEncryptionPassphrase: currentEncryptionPassphrase,
       });
       await setAutoBackupSettings({
         enabled: currentAutoBackupEnabled,
         intervalDays: currentIntervalDays,
         maxKeep: currentMaxKeep,
+        maxAgeDays: currentMaxAgeDays,
       });
       onClose();
     } catch {
@@ -221,7 +259,7 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
     }
   };
 
-  const handleBackupTypeChange = (value: 'local' | 'webdav') => {
+  const handleBackupTypeChange = (value: BackupType) => {
     setCurrentBackupType(value);
   };
 
@@ -377,6 +415,27 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
     }
   };
 
+  const handleTestS3Connection = async () => {
+    try {
+      const values = await form.validateFields(['s3']);
+      const s3Config = { ...s3, ...(values.s3 as Partial<S3ConfigFE>) };
+
+      if (!s3Config.bucket) {
+        message.warning(t('settings.s3.errors.checkBucket'));
+        return;
+      }
+
+      setTestingConnection(true);
+      await testS3Connection(toBackendS3(s3Config));
+      message.success(t('settings.webdav.testSuccess'));
+    } catch (error) {
+      console.error('S3 connection test failed:', error);
+      message.error(`${t('settings.webdav.testFailed')}: ${String(error)}`);
+    } finally {
+      setTestingConnection(false);
+    }
+  };
+
   return (
     <>
     <Modal
@@ -405,13 +464,14 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
               <h3 className={styles.sectionTitle} style={{ marginBottom: 0 }}>
                 {t('settings.backupSettings.storageType')}
               </h3>
-              <ManagementSegmented<'local' | 'webdav'>
+              <ManagementSegmented<BackupType>
                 value={currentBackupType}
                 onChange={handleBackupTypeChange}
                 ariaLabel={t('settings.backupSettings.storageType')}
                 options={[
                   { value: 'local', label: t('settings.backupSettings.local') },
                   { value: 'webdav', label: t('settings.backupSettings.webdav') },
+                  { value: 's3', label: t('settings.backupSettings.s3') },
                 ]}
               />
             </div>
@@ -459,6 +519,48 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
                 </div>
               </div>
             )}
+
+            {currentBackupType === 's3' && (
+              <div style={{ marginTop: 10 }}>
+                <Form.Item label={t('settings.s3.bucket')} name={['s3', 'bucket']}>
+                  <Input />
+                </Form.Item>
+                <Form.Item label={t('settings.s3.region')} name={['s3', 'region']}>
+                  <Input placeholder="us-east-1" />
+                </Form.Item>
+                <Form.Item label={t('settings.s3.endpointUrl')} name={['s3', 'endpointUrl']}>
+                  <Input placeholder={t('settings.s3.endpointUrlPlaceholder')} />
+                </Form.Item>
+                <Form.Item label={t('settings.s3.accessKey')} name={['s3', 'accessKey']}>
+                  <Input.Password visibilityToggle />
+                </Form.Item>
+                <Form.Item label={t('settings.s3.secretKey')} name={['s3', 'secretKey']}>
+                  <Input.Password visibilityToggle />
+                </Form.Item>
+                <Form.Item label={t('settings.s3.prefix')} name={['s3', 'prefix']}>
+                  <Input placeholder="ai-toolbox/backups" />
+                </Form.Item>
+                <Form.Item
+                  label={t('settings.s3.forcePathStyle')}
+                  name={['s3', 'forcePathStyle']}
+                  valuePropName="checked"
+                  extra={t('settings.s3.forcePathStyleHint')}
+                >
+                  <Switch />
+                </Form.Item>
+                <Form.Item label={t('settings.webdav.hostLabel')} name={['webdav', 'hostLabel']}>
+                  <Input placeholder={t('settings.webdav.hostLabelPlaceholder')} />
+                </Form.Item>
+                <div className={styles.testConnectionRow}>
+                  <Button
+                    onClick={handleTestS3Connection}
+                    loading={testingConnection}
+                  >
+                    {testingConnection ? t('settings.webdav.testing') : t('settings.webdav.testConnection')}
+                  </Button>
+                </div>
+              </div>
+            )}
           </section>
 
           <section className={styles.sectionCard}>
@@ -475,6 +577,20 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
               </div>
             </div>
 
+            <div className={styles.switchBlock}>
+              <div className={styles.switchRow}>
+                <span className={styles.switchLabel}>{t('settings.backupSettings.sessionTrash')}</span>
+                <Switch
+                  checked={currentBackupSessionTrashEnabled}
+                  onChange={setCurrentBackupSessionTrashEnabled}
+                  aria-label={t('settings.backupSettings.sessionTrash')}
+                />
+              </div>
+              <Typography.Text className={styles.helperText}>
+                {t('settings.backupSettings.sessionTrashDesc')}
+              </Typography.Text>
+            </div>
+
             <div className={styles.switchBlock}>
               <div className={styles.switchRow}>
                 <span className={styles.switchLabel}>{t('settings.backupSettings.cliConfigFiles')}</span>
@@ -669,6 +785,34 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
             </div>
           </section>
 
+          <section className={styles.sectionCard}>
+            <div className={styles.switchRow}>
+              <span className={styles.switchLabel} style={{ fontWeight: 600 }}>
+                {t('settings.backupSettings.encryption.title')}
+              </span>
+              <Switch
+                checked={currentEncryptionEnabled}
+                onChange={setCurrentEncryptionEnabled}
+                aria-label={t('settings.backupSettings.encryption.title')}
+              />
+            </div>
+            <Typography.Text className={styles.helperText}>
+              {t('settings.backupSettings.encryption.description')}
+            </Typography.Text>
+            {currentEncryptionEnabled && (
+              <Form.Item
+                label={t('settings.backupSettings.encryption.passphrase')}
+                style={{ marginTop: 10, marginBottom: 0 }}
+              >
+                <Input.Password
+                  value={currentEncryptionPassphrase}
+                  onChange={(e) => setCurrentEncryptionPassphrase(e.target.value)}
+                  autoComplete="new-password"
+                />
+              </Form.Item>
+            )}
+          </section>
+
           <section className={styles.sectionCard}>
             <div className={styles.switchRow}>
               <span className={styles.switchLabel} style={{ fontWeight: 600 }}>
@@ -707,6 +851,21 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
                     </Typography.Text>
                   )}
                 </Form.Item>
+                <Form.Item label={t('settings.autoBackup.maxAgeDays')} style={{ marginBottom: 0 }}>
+                  <InputNumber
+                    value={currentMaxAgeDays}
+                    onChange={(v) => setCurrentMaxAgeDays(v != null && v >= 0 ? Math.floor(v) : 0)}
+                    min={0}
+                    precision={0}
+                    style={{ width: 120 }}
+                    addonAfter={t('settings.autoBackup.days')}
+                  />
+                  {currentMaxAgeDays === 0 && (
+                    <Typography.Text className={styles.warningHint}>
+                      {t('settings.autoBackup.unlimitedAgeHint')}
+                    </Typography.Text>
+                  )}
+                </Form.Item>
               </div>
             )}
           </section>
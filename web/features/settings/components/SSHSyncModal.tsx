@@ -25,7 +25,7 @@ import {
 } from '@/services/sshSyncApi';
 import { useSettingsStore } from '@/stores';
 import { DEFAULT_SSH_DIRECTORY_EXCLUDES } from '@/types/sshsync';
-import type { SSHConnection, SSHFileMapping, SSHConnectionResult } from '@/types/sshsync';
+import type { SSHConnection, SSHFileMapping, SSHConnectionResult, HostKeyCheck } from '@/types/sshsync';
 import type { WslDirectModuleStatus } from '@/types/wslsync';
 import { SSHConnectionModal } from './SSHConnectionModal';
 import { SSHFileMappingModal } from './SSHFileMappingModal';
@@ -295,6 +295,43 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
     }
   };
 
+  // Trust-on-first-use: the backend stops the handshake at an unknown host key,
+  // before any credentials are sent, and pins it only once the user confirms.
+  const confirmHostKey = useCallback((hostKey: HostKeyCheck) => new Promise<boolean>((resolve) => {
+    AntdModal.confirm({
+      title: t('settings.ssh.hostKey.confirmTitle'),
+      width: 520,
+      content: (
+        <Space direction="vertical" size={4}>
+          <Text>{t('settings.ssh.hostKey.confirmMessage', { host: `${hostKey.host}:${hostKey.port}` })}</Text>
+          <Text type="secondary" style={{ fontSize: 12 }}>{hostKey.algorithm}</Text>
+          <Text code copyable style={{ wordBreak: 'break-all' }}>{hostKey.fingerprint}</Text>
+          <Text type="secondary" style={{ fontSize: 12 }}>{t('settings.ssh.hostKey.confirmHint')}</Text>
+        </Space>
+      ),
+      okText: t('settings.ssh.hostKey.trust'),
+      cancelText: t('settings.ssh.hostKey.reject'),
+      onOk: () => resolve(true),
+      onCancel: () => resolve(false),
+    });
+  }), [t]);
+
+  // Test a connection, asking the user to trust a first-seen host key and
+  // retrying with the confirmed fingerprint so the backend pins exactly that key.
+  const testWithHostKeyConfirmation = useCallback(async (conn: SSHConnection): Promise<SSHConnectionResult> => {
+    const result = await sshTestConnection(conn);
+    if (result.hostKey?.status !== 'unconfirmed') {
+      return result;
+    }
+    if (!(await confirmHostKey(result.hostKey))) {
+      return {
+        connected: false,
+        error: t('settings.ssh.hostKey.rejected'),
+      };
+    }
+    return sshTestConnection(conn, result.hostKey.fingerprint);
+  }, [confirmHostKey, t]);
+
   // Test connection
   const handleTestConnection = useCallback(async (connId?: string) => {
     const targetId = connId || activeConnectionId;
@@ -304,8 +341,7 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
     setTesting(true);
     setTestResult(null);
     try {
-      const result = await sshTestConnection(conn);
-      setTestResult(result);
+      setTestResult(await testWithHostKeyConfirmation(conn));
     } catch (error) {
       setTestResult({
         connected: false,
@@ -314,7 +350,7 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
     } finally {
       setTesting(false);
     }
-  }, [activeConnectionId, config?.connections]);
+  }, [activeConnectionId, config?.connections, testWithHostKeyConfirmation]);
 
   // Auto test connection when modal opens or active connection changes
   useEffect(() => {
@@ -450,6 +486,14 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
 
   const handleSyncNow = async () => {
     try {
+      // The sync session never accepts an unconfirmed host key, so route a
+      // first-seen host through the same confirmation before syncing.
+      const conn = config?.connections.find(c => c.id === activeConnectionId);
+      if (conn && !testResult?.connected) {
+        const result = await testWithHostKeyConfirmation(conn);
+        setTestResult(result);
+        if (!result.connected) return;
+      }
       await sync();
     } catch (error) {
       console.error('Failed to sync:', error);
@@ -695,6 +739,13 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
                   <Text type="secondary" style={{ fontSize: 12 }}>{testResult.serverInfo}</Text>
                 </div>
               )}
+              {testResult?.connected && testResult.hostKey && (
+                <div style={{ marginTop: 4 }}>
+                  <Text type="secondary" style={{ fontSize: 12 }}>
+                    {t('settings.ssh.hostKey.label')}: {testResult.hostKey.algorithm} {testResult.hostKey.fingerprint}
+                  </Text>
+                </div>
+              )}
               {testResult?.error && (
                 <div style={{ marginTop: 4 }}>
                   <Text type="danger" style={{ fontSize: 12 }}>{translateSyncMessage(testResult.error, 'ssh', t)}</Text>
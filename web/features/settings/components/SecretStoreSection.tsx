@@ -0,0 +1,173 @@
+import React from 'react';
+import { Alert, Button, Input, Modal, Select, Space, Tag, Typography, message } from 'antd';
+import { useTranslation } from 'react-i18next';
+import {
+  disableSecretStore,
+  enableSecretStore,
+  getSecretStoreStatus,
+  lockSecretStore,
+  unlockSecretStore,
+  type SecretStoreKeySource,
+  type SecretStoreStatus,
+} from '@/services';
+
+const { Text } = Typography;
+
+const KEY_SOURCES: SecretStoreKeySource[] = ['keyring', 'file', 'passphrase'];
+
+/** Status and lifecycle controls for the encrypted credential store. */
+const SecretStoreSection: React.FC = () => {
+  const { t } = useTranslation();
+  const [status, setStatus] = React.useState<SecretStoreStatus | null>(null);
+  const [keySource, setKeySource] = React.useState<SecretStoreKeySource>('keyring');
+  const [passphrase, setPassphrase] = React.useState('');
+  const [busy, setBusy] = React.useState(false);
+
+  const refreshStatus = React.useCallback(async () => {
+    try {
+      setStatus(await getSecretStoreStatus());
+    } catch (error) {
+      message.error(String(error));
+    }
+  }, []);
+
+  React.useEffect(() => {
+    void refreshStatus();
+  }, [refreshStatus]);
+
+  const run = async (action: () => Promise<number | void>, successKey: string) => {
+    setBusy(true);
+    try {
+      const count = await action();
+      setPassphrase('');
+      message.success(t(successKey, { count: count ?? 0 }));
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setBusy(false);
+      await refreshStatus();
+    }
+  };
+
+  const handleEnable = () => {
+    if (keySource === 'passphrase' && !passphrase) {
+      message.warning(t('settings.secretStore.passphraseRequired'));
+      return;
+    }
+    void run(
+      () => enableSecretStore(keySource, keySource === 'passphrase' ? passphrase : undefined),
+      'settings.secretStore.enableSuccess',
+    );
+  };
+
+  const handleUnlock = () => {
+    if (!passphrase) {
+      message.warning(t('settings.secretStore.passphraseRequired'));
+      return;
+    }
+    void run(() => unlockSecretStore(passphrase), 'settings.secretStore.unlockSuccess');
+  };
+
+  const handleDisable = () => {
+    Modal.confirm({
+      title: t('settings.secretStore.disableConfirmTitle'),
+      content: t('settings.secretStore.disableConfirmContent'),
+      okText: t('settings.secretStore.disable'),
+      okButtonProps: { danger: true },
+      cancelText: t('common.cancel'),
+      onOk: () => run(disableSecretStore, 'settings.secretStore.disableSuccess'),
+    });
+  };
+
+  if (!status) {
+    return null;
+  }
+
+  const locked = status.enabled && !status.unlocked;
+
+  return (
+    <div style={{ display: 'flex', flexDirection: 'column', gap: 12 }}>
+      <Space size={8} wrap>
+        {!status.enabled && <Tag>{t('settings.secretStore.statusDisabled')}</Tag>}
+        {status.enabled && status.keySource && (
+          <Tag color="blue">{t(`settings.secretStore.keySource.${status.keySource}`)}</Tag>
+        )}
+        {status.enabled && (
+          <Tag color={locked ? 'warning' : 'success'}>
+            {t(locked ? 'settings.secretStore.statusLocked' : 'settings.secretStore.statusUnlocked')}
+          </Tag>
+        )}
+        {status.enabled && (
+          <Text type="secondary" style={{ fontSize: 12 }}>
+            {t('settings.secretStore.secretCount', { count: status.secretCount })}
+          </Text>
+        )}
+      </Space>
+
+      {locked && <Alert type="warning" showIcon message={t('settings.secretStore.lockedHint')} />}
+
+      {!status.enabled && (
+        <>
+          <Text type="secondary" style={{ fontSize: 12 }}>
+            {t('settings.secretStore.hint')}
+          </Text>
+          <Space wrap>
+            <Select
+              value={keySource}
+              onChange={setKeySource}
+              style={{ width: 200 }}
+              options={KEY_SOURCES.map((source) => ({
+                value: source,
+                label: t(`settings.secretStore.keySource.${source}`),
+              }))}
+            />
+            {keySource === 'passphrase' && (
+              <Input.Password
+                value={passphrase}
+                onChange={(event) => setPassphrase(event.target.value)}
+                placeholder={t('settings.secretStore.passphrasePlaceholder')}
+                style={{ width: 220 }}
+              />
+            )}
+            <Button type="primary" loading={busy} onClick={handleEnable}>
+              {t('settings.secretStore.enable')}
+            </Button>
+          </Space>
+        </>
+      )}
+
+      {locked && (
+        <Space wrap>
+          <Input.Password
+            value={passphrase}
+            onChange={(event) => setPassphrase(event.target.value)}
+            onPressEnter={handleUnlock}
+            placeholder={t('settings.secretStore.passphrasePlaceholder')}
+            style={{ width: 220 }}
+          />
+          <Button type="primary" loading={busy} onClick={handleUnlock}>
+            {t('settings.secretStore.unlock')}
+          </Button>
+        </Space>
+      )}
+
+      {status.enabled && !locked && (
+        <Space wrap>
+          {status.keySource === 'passphrase' && (
+            <Button
+              loading={busy}
+              onClick={() => void run(lockSecretStore, 'settings.secretStore.lockSuccess')}
+            >
+              {t('settings.secretStore.lock')}
+            </Button>
+          )}
+          <Button danger loading={busy} onClick={handleDisable}>
+            {t('settings.secretStore.disable')}
+          </Button>
+        </Space>
+      )}
+    </div>
+  );
+};
+
+export default SecretStoreSection;
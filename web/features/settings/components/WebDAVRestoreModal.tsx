@@ -27,6 +27,9 @@ interface WebDAVRestoreModalProps {
   password: string;
   remotePath: string;
   currentHostLabel: string;
+  /** Override the WebDAV listing, e.g. to browse S3 backups in the same list */
+  listBackups?: () => Promise<BackupFileInfo[]>;
+  deleteBackup?: (filename: string) => Promise<void>;
 }
 
 const WebDAVRestoreModal: React.FC<WebDAVRestoreModalProps> = ({
@@ -38,6 +41,8 @@ const WebDAVRestoreModal: React.FC<WebDAVRestoreModalProps> = ({
   password,
   remotePath,
   currentHostLabel,
+  listBackups,
+  deleteBackup,
 }) => {
   const { t } = useTranslation();
   const [loading, setLoading] = React.useState(false);
@@ -63,14 +68,16 @@ const WebDAVRestoreModal: React.FC<WebDAVRestoreModalProps> = ({
   }, [open, showHostTabs]);
 
   const loadBackups = async () => {
-    if (!url) {
+    if (!listBackups && !url) {
       message.warning(t('settings.backupSettings.noWebDAVConfigured'));
       return;
     }
 
     setLoading(true);
     try {
-      const files = await listWebDAVBackups(url, username, password, remotePath);
+      const files = listBackups
+        ? await listBackups()
+        : await listWebDAVBackups(url, username, password, remotePath);
       files.sort((a, b) => b.filename.localeCompare(a.filename));
       setBackups(files);
     } catch (error) {
@@ -105,7 +112,11 @@ const WebDAVRestoreModal: React.FC<WebDAVRestoreModalProps> = ({
   const handleDelete = async (filename: string, e: React.MouseEvent) => {
     e.stopPropagation(); // 阻止触发选择
     try {
-      await deleteWebDAVBackup(url, username, password, remotePath, filename);
+      if (deleteBackup) {
+        await deleteBackup(filename);
+      } else {
+        await deleteWebDAVBackup(url, username, password, remotePath, filename);
+      }
       message.success(t('common.success'));
       // 刷新列表
       setBackups((currentBackups) =>
@@ -1,3 +1,4 @@
 export { default as BackupSettingsModal } from './BackupSettingsModal';
 export { default as S3SettingsModal } from './S3SettingsModal';
+export { default as SecretStoreSection } from './SecretStoreSection';
 export { default as WebDAVRestoreModal } from './WebDAVRestoreModal';
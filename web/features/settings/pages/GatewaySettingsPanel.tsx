@@ -5,22 +5,49 @@ import {
   AlertCircle,
   ArrowRightLeft,
   CircleHelp,
+  Database,
+  FlaskConical,
   FileText,
   Gauge,
+  KeyRound,
   Loader2,
   Network,
+  PiggyBank,
   Terminal,
 } from 'lucide-react';
 import { useTranslation } from 'react-i18next';
 import {
   checkProxyGatewayPortAvailable,
+  clearProxyGatewayResponseCache,
+  createProxyGatewayAccessToken,
   DEFAULT_RETRYABLE_STATUS_CODES,
+  deleteProxyGatewayAccessToken,
+  deleteProxyGatewayBudgetRule,
+  getMockUpstreamStatus,
+  getProxyGatewayBudgetStatuses,
   getProxyGatewayCliStatuses,
+  getProxyGatewayResponseCacheStats,
   getProxyGatewaySettings,
   getProxyGatewayStatus,
+  listProxyGatewayAccessTokens,
+  listProxyGatewayFixtureCaptures,
+  replayProxyGatewayFixtureCapture,
+  saveProxyGatewayBudgetRule,
+  startMockUpstream,
+  stopMockUpstream,
   updateProxyGatewaySettings,
+  type GatewayAccessToken,
+  type GatewayBudgetAction,
+  type GatewayBudgetPeriod,
+  type GatewayBudgetRule,
+  type GatewayBudgetRuleInput,
+  type GatewayBudgetStatus,
   type GatewayCliTakeoverStatus,
   type GatewayCliKey,
+  type GatewayLoadBalanceStrategy,
+  type GatewayResponseCacheStats,
+  type MockUpstreamBehavior,
+  type MockUpstreamStatus,
   type AppProxyConfig,
   type ProxyGatewaySettings,
   type ProxyGatewayStatus,
@@ -67,6 +94,69 @@ const CLI_OPTIONS: CliOption[] = [
   },
 ];
 
+const LOAD_BALANCE_STRATEGIES: GatewayLoadBalanceStrategy[] = [
+  'weighted',
+  'least_latency',
+  'least_error_rate',
+];
+
+const BUDGET_PERIODS: GatewayBudgetPeriod[] = ['daily', 'weekly', 'monthly'];
+const BUDGET_ACTIONS: GatewayBudgetAction[] = ['warn', 'fallback', 'reject'];
+
+type MockUpstreamPreset =
+  | 'success'
+  | 'slow'
+  | 'rateLimit'
+  | 'serverError'
+  | 'firstByteTimeout'
+  | 'midStreamError'
+  | 'invalidThinkingSignature'
+  | 'emptyStream';
+
+const MOCK_UPSTREAM_PRESETS: Record<MockUpstreamPreset, MockUpstreamBehavior> = {
+  success: { kind: 'success' },
+  slow: { kind: 'delay', delay_ms: 5000 },
+  rateLimit: { kind: 'status', status_code: 429 },
+  serverError: { kind: 'status', status_code: 503 },
+  firstByteTimeout: { kind: 'first_byte_timeout', hold_ms: 120000 },
+  midStreamError: { kind: 'mid_stream_error' },
+  invalidThinkingSignature: { kind: 'invalid_thinking_signature' },
+  emptyStream: { kind: 'empty_stream' },
+};
+
+interface BudgetDraft {
+  name: string;
+  cli_key: SupportedGatewayCliKey | '';
+  model: string;
+  period: GatewayBudgetPeriod;
+  limit_usd: string;
+  limit_tokens: string;
+  action: GatewayBudgetAction;
+}
+
+const EMPTY_BUDGET_DRAFT: BudgetDraft = {
+  name: '',
+  cli_key: '',
+  model: '',
+  period: 'daily',
+  limit_usd: '',
+  limit_tokens: '',
+  action: 'warn',
+};
+
+const budgetRuleToInput = (rule: GatewayBudgetRule): GatewayBudgetRuleInput => ({
+  id: rule.id,
+  name: rule.name,
+  enabled: rule.enabled,
+  cli_key: rule.cli_key,
+  provider_id: rule.provider_id,
+  model: rule.model,
+  period: rule.period,
+  limit_usd: rule.limit_usd,
+  limit_tokens: rule.limit_tokens,
+  action: rule.action,
+});
+
 const joinClassNames = (...classNames: Array<string | false | null | undefined>) =>
   classNames.filter(Boolean).join(' ');
 
@@ -128,6 +218,7 @@ const appProxyConfigKeys: Array<keyof AppProxyConfig> = [
   'retry_interval_secs',
   'cost_multiplier',
   'pricing_model_source',
+  'response_cache_enabled',
 ];
 
 interface SwitchControlProps {
@@ -215,6 +306,20 @@ const GatewaySettingsPanel: React.FC<GatewaySettingsPanelProps> = ({
   const [cliStatuses, setCliStatuses] = React.useState<GatewayCliTakeoverStatus[]>([]);
   const [busyAction, setBusyAction] = React.useState<BusyAction | null>('load');
   const [checkingPort, setCheckingPort] = React.useState(false);
+  const [accessTokens, setAccessTokens] = React.useState<GatewayAccessToken[]>([]);
+  const [tokenLabel, setTokenLabel] = React.useState('');
+  const [tokenCliKeys, setTokenCliKeys] = React.useState<SupportedGatewayCliKey[]>([]);
+  const [tokenBusy, setTokenBusy] = React.useState(false);
+  const [createdTokenSecret, setCreatedTokenSecret] = React.useState<string | null>(null);
+  const [budgetStatuses, setBudgetStatuses] = React.useState<GatewayBudgetStatus[]>([]);
+  const [budgetDraft, setBudgetDraft] = React.useState<BudgetDraft>(EMPTY_BUDGET_DRAFT);
+  const [budgetBusy, setBudgetBusy] = React.useState(false);
+  const [replayBusy, setReplayBusy] = React.useState(false);
+  const [mockUpstream, setMockUpstream] = React.useState<MockUpstreamStatus | null>(null);
+  const [mockPreset, setMockPreset] = React.useState<MockUpstreamPreset>('success');
+  const [mockBusy, setMockBusy] = React.useState(false);
+  const [responseCacheStats, setResponseCacheStats] = React.useState<GatewayResponseCacheStats | null>(null);
+  const [responseCacheBusy, setResponseCacheBusy] = React.useState(false);
   const [notice, setNotice] = React.useState<NoticeState | null>(null);
   const saveTimerRef = React.useRef<ReturnType<typeof setTimeout> | null>(null);
   const saveSequenceRef = React.useRef(0);
@@ -245,11 +350,14 @@ const GatewaySettingsPanel: React.FC<GatewaySettingsPanelProps> = ({
     const loadGateway = async () => {
       setBusyAction('load');
       try {
-        const [nextSettings, nextStatus, nextCliStatuses] = await Promise.all([
-          getProxyGatewaySettings(),
-          getProxyGatewayStatus(),
-          getProxyGatewayCliStatuses(),
-        ]);
+        const [nextSettings, nextStatus, nextCliStatuses, nextAccessTokens, nextBudgetStatuses] =
+          await Promise.all([
+            getProxyGatewaySettings(),
+            getProxyGatewayStatus(),
+            getProxyGatewayCliStatuses(),
+            listProxyGatewayAccessTokens(),
+            getProxyGatewayBudgetStatuses(),
+          ]);
         if (disposed) {
           return;
         }
@@ -258,6 +366,8 @@ const GatewaySettingsPanel: React.FC<GatewaySettingsPanelProps> = ({
         setStatus(nextStatus);
         onStatusChange?.(nextStatus);
         setCliStatuses(nextCliStatuses);
+        setAccessTokens(nextAccessTokens);
+        setBudgetStatuses(nextBudgetStatuses);
       } catch (error) {
         if (!disposed) {
           setNotice({
@@ -392,6 +502,188 @@ const GatewaySettingsPanel: React.FC<GatewaySettingsPanelProps> = ({
     }
   };
 
+  const toggleTokenCliKey = (cliKey: SupportedGatewayCliKey, checked: boolean) => {
+    setTokenCliKeys((previousKeys) =>
+      checked
+        ? [...previousKeys.filter((key) => key !== cliKey), cliKey]
+        : previousKeys.filter((key) => key !== cliKey),
+    );
+  };
+
+  const handleCreateAccessToken = async () => {
+    if (!tokenLabel.trim() || tokenCliKeys.length === 0) {
+      return;
+    }
+    setTokenBusy(true);
+    try {
+      const created = await createProxyGatewayAccessToken({
+        label: tokenLabel.trim(),
+        cli_keys: tokenCliKeys,
+      });
+      setAccessTokens((previousTokens) => [...previousTokens, created.token]);
+      setCreatedTokenSecret(created.secret);
+      setTokenLabel('');
+      setTokenCliKeys([]);
+      setNotice({ kind: 'success', text: t('settings.gateway.notice.tokenCreated', { label: created.token.label }) });
+    } catch (error) {
+      setNotice({
+        kind: 'error',
+        text: t('settings.gateway.notice.tokenCreateFailed', { error: formatGatewayError(error) }),
+      });
+    } finally {
+      setTokenBusy(false);
+    }
+  };
+
+  const handleDeleteAccessToken = async (token: GatewayAccessToken) => {
+    setTokenBusy(true);
+    try {
+      await deleteProxyGatewayAccessToken(token.id);
+      setAccessTokens((previousTokens) => previousTokens.filter((item) => item.id !== token.id));
+      setCreatedTokenSecret(null);
+    } catch (error) {
+      setNotice({
+        kind: 'error',
+        text: t('settings.gateway.notice.tokenDeleteFailed', { error: formatGatewayError(error) }),
+      });
+    } finally {
+      setTokenBusy(false);
+    }
+  };
+
+  const handleReplayFixtureCaptures = async () => {
+    setReplayBusy(true);
+    try {
+      const captures = await listProxyGatewayFixtureCaptures();
+      if (captures.length === 0) {
+        setNotice({ kind: 'info', text: t('settings.gateway.notice.fixtureReplayEmpty') });
+        return;
+      }
+      const reports = await Promise.all(
+        captures.map((capture) => replayProxyGatewayFixtureCapture(capture.manifest_path)),
+      );
+      const failed = reports.filter((report) => !report.passed);
+      setNotice({
+        kind: failed.length === 0 ? 'success' : 'error',
+        text: t('settings.gateway.notice.fixtureReplayResult', {
+          passed: reports.length - failed.length,
+          total: reports.length,
+          failed: failed.map((report) => report.name).join(', ') || '-',
+        }),
+      });
+    } catch (error) {
+      setNotice({
+        kind: 'error',
+        text: t('settings.gateway.notice.fixtureReplayFailed', { error: formatGatewayError(error) }),
+      });
+    } finally {
+      setReplayBusy(false);
+    }
+  };
+
+  React.useEffect(() => {
+    getMockUpstreamStatus()
+      .then(setMockUpstream)
+      .catch(() => setMockUpstream(null));
+  }, []);
+
+  const handleToggleMockUpstream = async () => {
+    setMockBusy(true);
+    try {
+      const nextStatus = mockUpstream?.running
+        ? await stopMockUpstream()
+        : await startMockUpstream({ steps: [], fallback: MOCK_UPSTREAM_PRESETS[mockPreset] });
+      setMockUpstream(nextStatus);
+    } catch (error) {
+      setNotice({
+        kind: 'error',
+        text: t('settings.gateway.notice.mockUpstreamFailed', { error: formatGatewayError(error) }),
+      });
+    } finally {
+      setMockBusy(false);
+    }
+  };
+
+  React.useEffect(() => {
+    getProxyGatewayResponseCacheStats()
+      .then(setResponseCacheStats)
+      .catch(() => setResponseCacheStats(null));
+  }, []);
+
+  const handleClearResponseCache = async () => {
+    setResponseCacheBusy(true);
+    try {
+      const cleared = await clearProxyGatewayResponseCache();
+      setResponseCacheStats({ entry_count: 0, total_bytes: 0 });
+      setNotice({
+        kind: 'success',
+        text: t('settings.gateway.notice.responseCacheCleared', { count: cleared.entry_count }),
+      });
+    } catch (error) {
+      setNotice({
+        kind: 'error',
+        text: t('settings.gateway.notice.responseCacheClearFailed', { error: formatGatewayError(error) }),
+      });
+    } finally {
+      setResponseCacheBusy(false);
+    }
+  };
+
+  const updateBudgetDraft = <K extends keyof BudgetDraft>(key: K, value: BudgetDraft[K]) => {
+    setBudgetDraft((previousDraft) => ({ ...previousDraft, [key]: value }));
+  };
+
+  const saveBudgetRule = async (input: GatewayBudgetRuleInput) => {
+    setBudgetBusy(true);
+    try {
+      await saveProxyGatewayBudgetRule(input);
+      setBudgetStatuses(await getProxyGatewayBudgetStatuses());
+      return true;
+    } catch (error) {
+      setNotice({
+        kind: 'error',
+        text: t('settings.gateway.notice.budgetSaveFailed', { error: formatGatewayError(error) }),
+      });
+      return false;
+    } finally {
+      setBudgetBusy(false);
+    }
+  };
+
+  const handleCreateBudget = async () => {
+    const limitTokens = budgetDraft.limit_tokens.trim();
+    const saved = await saveBudgetRule({
+      name: budgetDraft.name.trim(),
+      enabled: true,
+      cli_key: budgetDraft.cli_key || null,
+      model: budgetDraft.model.trim() || null,
+      period: budgetDraft.period,
+      limit_usd: budgetDraft.limit_usd.trim() || null,
+      limit_tokens: limitTokens ? toInteger(limitTokens, 0, 0) : null,
+      action: budgetDraft.action,
+    });
+    if (saved) {
+      setBudgetDraft(EMPTY_BUDGET_DRAFT);
+    }
+  };
+
+  const handleDeleteBudget = async (rule: GatewayBudgetRule) => {
+    setBudgetBusy(true);
+    try {
+      await deleteProxyGatewayBudgetRule(rule.id);
+      setBudgetStatuses((previousStatuses) =>
+        previousStatuses.filter((item) => item.rule.id !== rule.id),
+      );
+    } catch (error) {
+      setNotice({
+        kind: 'error',
+        text: t('settings.gateway.notice.budgetDeleteFailed', { error: formatGatewayError(error) }),
+      });
+    } finally {
+      setBudgetBusy(false);
+    }
+  };
+
   const handleLogPartToggle = (
     key: 'store_request_body' | 'store_headers' | 'store_response_body',
     checked: boolean,
@@ -443,6 +735,24 @@ const GatewaySettingsPanel: React.FC<GatewaySettingsPanelProps> = ({
     });
   };
 
+  const updateAppResponseCache = (cliKey: SupportedGatewayCliKey, enabled: boolean) => {
+    if (!draftSettings) {
+      return;
+    }
+    const nextConfig = {
+      ...(draftSettings.app_configs?.[cliKey] ?? {}),
+      response_cache_enabled: enabled ? true : null,
+    };
+    const emptyConfig = appProxyConfigKeys.every((configKey) => nextConfig[configKey] == null);
+    setDraftSettings({
+      ...draftSettings,
+      app_configs: {
+        ...(draftSettings.app_configs ?? {}),
+        [cliKey]: emptyConfig ? undefined : nextConfig,
+      },
+    });
+  };
+
   if (!draftSettings) {
     return (
       <div className={styles.loadingState}>
@@ -547,6 +857,78 @@ const GatewaySettingsPanel: React.FC<GatewaySettingsPanelProps> = ({
             </div>
           </Section>
 
+          <Section icon={<KeyRound size={15} aria-hidden="true" />} title={t('settings.gateway.sections.accessTokens')}>
+            <div className={styles.fieldStack}>
+              <span className={styles.fieldDescription}>{t('settings.gateway.hints.accessTokens')}</span>
+              {accessTokens.length > 0 ? (
+                <div className={styles.tokenList}>
+                  {accessTokens.map((token) => (
+                    <div key={token.id} className={styles.tokenItem}>
+                      <span className={styles.tokenLabel}>{token.label}</span>
+                      <span className={styles.tokenMeta}>
+                        {`atb_…${token.token_hint}`}
+                        {' · '}
+                        {token.cli_keys.map((cliKey) => t(`settings.gateway.cli.${cliKey}`)).join(', ')}
+                      </span>
+                      <button
+                        type="button"
+                        className={styles.textButton}
+                        disabled={tokenBusy}
+                        onClick={() => void handleDeleteAccessToken(token)}
+                      >
+                        {t('common.delete')}
+                      </button>
+                    </div>
+                  ))}
+                </div>
+              ) : null}
+              {createdTokenSecret ? (
+                <FieldRow
+                  label={t('settings.gateway.fields.newTokenSecret')}
+                  description={t('settings.gateway.hints.newTokenSecret')}
+                >
+                  <input
+                    className={styles.textInput}
+                    value={createdTokenSecret}
+                    readOnly
+                    onFocus={(event) => event.currentTarget.select()}
+                  />
+                </FieldRow>
+              ) : null}
+              <FieldRow label={t('settings.gateway.fields.tokenLabel')}>
+                <div className={styles.inlineControlGroup}>
+                  <input
+                    className={styles.textInput}
+                    value={tokenLabel}
+                    maxLength={64}
+                    placeholder={t('settings.gateway.placeholders.tokenLabel')}
+                    onChange={(event) => setTokenLabel(event.currentTarget.value)}
+                  />
+                  <button
+                    type="button"
+                    className={styles.textButton}
+                    disabled={tokenBusy || !tokenLabel.trim() || tokenCliKeys.length === 0}
+                    onClick={() => void handleCreateAccessToken()}
+                  >
+                    {t('settings.gateway.actions.createToken')}
+                  </button>
+                </div>
+              </FieldRow>
+              <div className={styles.logParts} aria-label={t('settings.gateway.fields.tokenScope')}>
+                {CLI_OPTIONS.map((option) => (
+                  <label key={option.key} className={styles.checkItem}>
+                    <input
+                      type="checkbox"
+                      checked={tokenCliKeys.includes(option.key)}
+                      onChange={(event) => toggleTokenCliKey(option.key, event.currentTarget.checked)}
+                    />
+                    <span>{t(option.labelKey)}</span>
+                  </label>
+                ))}
+              </div>
+            </div>
+          </Section>
+
           <Section icon={<ArrowRightLeft size={15} aria-hidden="true" />} title={t('settings.gateway.sections.resilience')}>
             <div className={styles.fieldStack}>
               <div className={styles.subGroup}>
@@ -874,6 +1256,46 @@ const GatewaySettingsPanel: React.FC<GatewaySettingsPanelProps> = ({
                     />
                   </FieldRow>
                 </div>
+                <div className={styles.fieldPairGrid}>
+                  <FieldRow
+                    label={t('settings.gateway.fields.loadBalanceStrategy')}
+                    help={t('settings.gateway.fieldHelp.loadBalanceStrategy')}
+                  >
+                    <select
+                      className={styles.textInput}
+                      value={draftSettings.load_balance_strategy}
+                      onChange={(event) =>
+                        updateDraftSetting(
+                          'load_balance_strategy',
+                          event.currentTarget.value as GatewayLoadBalanceStrategy,
+                        )
+                      }
+                    >
+                      {LOAD_BALANCE_STRATEGIES.map((strategy) => (
+                        <option key={strategy} value={strategy}>
+                          {t(`settings.gateway.loadBalanceStrategy.${strategy}`)}
+                        </option>
+                      ))}
+                    </select>
+                  </FieldRow>
+                  <FieldRow
+                    label={t('settings.gateway.fields.stickyTtl')}
+                    help={t('settings.gateway.fieldHelp.stickyTtl')}
+                  >
+                    <input
+                      className={styles.numberInput}
+                      type="number"
+                      min={0}
+                      value={draftSettings.load_balance_sticky_ttl_secs}
+                      onChange={(event) =>
+                        updateDraftSetting(
+                          'load_balance_sticky_ttl_secs',
+                          toInteger(event.currentTarget.value, draftSettings.load_balance_sticky_ttl_secs, 0),
+                        )
+                      }
+                    />
+                  </FieldRow>
+                </div>
               </div>
             </div>
           </Section>
@@ -981,12 +1403,167 @@ const GatewaySettingsPanel: React.FC<GatewaySettingsPanelProps> = ({
                         />
                       </label>
                     </div>
+                    <SwitchControl
+                      checked={cliConfig.response_cache_enabled === true}
+                      label={t('settings.gateway.perCli.responseCache')}
+                      onChange={(checked) => updateAppResponseCache(option.key, checked)}
+                    />
                   </div>
                 );
               })}
             </div>
           </Section>
 
+          <Section icon={<PiggyBank size={15} aria-hidden="true" />} title={t('settings.gateway.sections.budgets')}>
+            <div className={styles.fieldStack}>
+              <span className={styles.fieldDescription}>{t('settings.gateway.hints.budgets')}</span>
+              {budgetStatuses.length > 0 ? (
+                <div className={styles.tokenList}>
+                  {budgetStatuses.map(({ rule, spent_usd, spent_tokens, exceeded }) => (
+                    <div key={rule.id} className={styles.tokenItem}>
+                      <span className={styles.tokenLabel}>{rule.name}</span>
+                      <span
+                        className={joinClassNames(styles.tokenMeta, exceeded && styles.budgetExceeded)}
+                        title={t(`settings.gateway.budgets.actions.${rule.action}`)}
+                      >
+                        {[
+                          rule.cli_key ? t(`settings.gateway.cli.${rule.cli_key}`) : t('settings.gateway.budgets.allClis'),
+                          rule.model,
+                          t(`settings.gateway.budgets.periods.${rule.period}`),
+                          rule.limit_usd ? `$${spent_usd} / $${rule.limit_usd}` : null,
+                          rule.limit_tokens != null ? `${spent_tokens} / ${rule.limit_tokens}` : null,
+                          t(`settings.gateway.budgets.actions.${rule.action}`),
+                        ]
+                          .filter(Boolean)
+                          .join(' · ')}
+                      </span>
+                      <div className={styles.inlineControlGroup}>
+                        <Switch
+                          size="small"
+                          checked={rule.enabled}
+                          disabled={budgetBusy}
+                          onChange={(checked) => void saveBudgetRule({ ...budgetRuleToInput(rule), enabled: checked })}
+                        />
+                        <button
+                          type="button"
+                          className={styles.textButton}
+                          disabled={budgetBusy}
+                          onClick={() => void handleDeleteBudget(rule)}
+                        >
+                          {t('common.delete')}
+                        </button>
+                      </div>
+                    </div>
+                  ))}
+                </div>
+              ) : null}
+              <div className={styles.fieldPairGrid}>
+                <FieldRow label={t('settings.gateway.fields.budgetName')}>
+                  <input
+                    className={styles.textInput}
+                    value={budgetDraft.name}
+                    maxLength={64}
+                    onChange={(event) => updateBudgetDraft('name', event.currentTarget.value)}
+                  />
+                </FieldRow>
+                <FieldRow label={t('settings.gateway.fields.budgetCli')}>
+                  <select
+                    className={styles.textInput}
+                    value={budgetDraft.cli_key}
+                    onChange={(event) =>
+                      updateBudgetDraft('cli_key', event.currentTarget.value as BudgetDraft['cli_key'])
+                    }
+                  >
+                    <option value="">{t('settings.gateway.budgets.allClis')}</option>
+                    {CLI_OPTIONS.map((option) => (
+                      <option key={option.key} value={option.key}>
+                        {t(option.labelKey)}
+                      </option>
+                    ))}
+                  </select>
+                </FieldRow>
+              </div>
+              <div className={styles.fieldPairGrid}>
+                <FieldRow label={t('settings.gateway.fields.budgetModel')}>
+                  <input
+                    className={styles.textInput}
+                    value={budgetDraft.model}
+                    placeholder={t('settings.gateway.placeholders.budgetModel')}
+                    onChange={(event) => updateBudgetDraft('model', event.currentTarget.value)}
+                  />
+                </FieldRow>
+                <FieldRow label={t('settings.gateway.fields.budgetPeriod')}>
+                  <select
+                    className={styles.textInput}
+                    value={budgetDraft.period}
+                    onChange={(event) =>
+                      updateBudgetDraft('period', event.currentTarget.value as GatewayBudgetPeriod)
+                    }
+                  >
+                    {BUDGET_PERIODS.map((period) => (
+                      <option key={period} value={period}>
+                        {t(`settings.gateway.budgets.periods.${period}`)}
+                      </option>
+                    ))}
+                  </select>
+                </FieldRow>
+              </div>
+              <div className={styles.fieldPairGrid}>
+                <FieldRow label={t('settings.gateway.fields.budgetLimitUsd')}>
+                  <input
+                    className={styles.numberInput}
+                    type="number"
+                    min={0}
+                    step="0.01"
+                    value={budgetDraft.limit_usd}
+                    onChange={(event) => updateBudgetDraft('limit_usd', event.currentTarget.value)}
+                  />
+                </FieldRow>
+                <FieldRow label={t('settings.gateway.fields.budgetLimitTokens')}>
+                  <input
+                    className={styles.numberInput}
+                    type="number"
+                    min={0}
+                    value={budgetDraft.limit_tokens}
+                    onChange={(event) => updateBudgetDraft('limit_tokens', event.currentTarget.value)}
+                  />
+                </FieldRow>
+              </div>
+              <FieldRow
+                label={t('settings.gateway.fields.budgetAction')}
+                help={t('settings.gateway.fieldHelp.budgetAction')}
+              >
+                <div className={styles.inlineControlGroup}>
+                  <select
+                    className={styles.textInput}
+                    value={budgetDraft.action}
+                    onChange={(event) =>
+                      updateBudgetDraft('action', event.currentTarget.value as GatewayBudgetAction)
+                    }
+                  >
+                    {BUDGET_ACTIONS.map((action) => (
+                      <option key={action} value={action}>
+                        {t(`settings.gateway.budgets.actions.${action}`)}
+                      </option>
+                    ))}
+                  </select>
+                  <button
+                    type="button"
+                    className={styles.textButton}
+                    disabled={
+                      budgetBusy
+                      || !budgetDraft.name.trim()
+                      || (!budgetDraft.limit_usd.trim() && !budgetDraft.limit_tokens.trim())
+                    }
+                    onClick={() => void handleCreateBudget()}
+                  >
+                    {t('settings.gateway.actions.createBudget')}
+                  </button>
+                </div>
+              </FieldRow>
+            </div>
+          </Section>
+
           <Section icon={<FileText size={15} aria-hidden="true" />} title={t('settings.gateway.sections.logs')}>
             <div className={styles.fieldStack}>
               <FieldRow label={t('settings.gateway.fields.requestLog')} wide>
@@ -1003,6 +1580,50 @@ const GatewaySettingsPanel: React.FC<GatewaySettingsPanelProps> = ({
                   onChange={(checked) => updateDraftAndSave('metrics_enabled', checked)}
                 />
               </FieldRow>
+              <FieldRow
+                label={t('settings.gateway.fields.metricsEndpoint')}
+                help={t('settings.gateway.fieldHelp.metricsEndpoint')}
+                wide
+              >
+                <SwitchControl
+                  checked={draftSettings.metrics_endpoint_enabled}
+                  label={draftSettings.metrics_endpoint_enabled ? t('common.enabled') : t('common.disabled')}
+                  onChange={(checked) => updateDraftAndSave('metrics_endpoint_enabled', checked)}
+                />
+              </FieldRow>
+              <FieldRow
+                label={t('settings.gateway.fields.fixtureCapture')}
+                help={t('settings.gateway.fieldHelp.fixtureCapture')}
+                wide
+              >
+                <SwitchControl
+                  checked={draftSettings.fixture_capture_enabled}
+                  label={draftSettings.fixture_capture_enabled ? t('common.enabled') : t('common.disabled')}
+                  onChange={(checked) => updateDraftAndSave('fixture_capture_enabled', checked)}
+                />
+              </FieldRow>
+              <FieldRow
+                label={t('settings.gateway.fields.fixtureCaptureFilter')}
+                description={t('settings.gateway.hints.fixtureCaptureFilter')}
+              >
+                <div className={styles.inlineControlGroup}>
+                  <input
+                    className={styles.textInput}
+                    value={draftSettings.fixture_capture_filter}
+                    disabled={!draftSettings.fixture_capture_enabled}
+                    placeholder="deepseek, /v1/messages"
+                    onChange={(event) => updateDraftSetting('fixture_capture_filter', event.currentTarget.value)}
+                  />
+                  <button
+                    type="button"
+                    className={styles.textButton}
+                    disabled={replayBusy}
+                    onClick={() => void handleReplayFixtureCaptures()}
+                  >
+                    {t('settings.gateway.actions.replayFixtureCaptures')}
+                  </button>
+                </div>
+              </FieldRow>
               <div className={styles.logParts} aria-label={t('settings.gateway.fields.detailStorage')}>
                 <label className={styles.checkItem}>
                   <input
@@ -1080,6 +1701,107 @@ const GatewaySettingsPanel: React.FC<GatewaySettingsPanelProps> = ({
               </div>
             </div>
           </Section>
+
+          <Section icon={<Database size={15} aria-hidden="true" />} title={t('settings.gateway.sections.responseCache')}>
+            <div className={styles.fieldStack}>
+              <span className={styles.fieldDescription}>{t('settings.gateway.hints.responseCache')}</span>
+              <div className={styles.fieldPairGrid}>
+                <FieldRow label={t('settings.gateway.fields.responseCacheTtl')}>
+                  <input
+                    className={styles.numberInput}
+                    type="number"
+                    min={1}
+                    value={draftSettings.response_cache_ttl_secs}
+                    onChange={(event) =>
+                      updateDraftSetting(
+                        'response_cache_ttl_secs',
+                        toInteger(event.currentTarget.value, draftSettings.response_cache_ttl_secs, 1),
+                      )
+                    }
+                  />
+                </FieldRow>
+                <FieldRow label={t('settings.gateway.fields.responseCacheMaxSize')}>
+                  <input
+                    className={styles.numberInput}
+                    type="number"
+                    min={1}
+                    value={draftSettings.response_cache_max_size_mb}
+                    onChange={(event) =>
+                      updateDraftSetting(
+                        'response_cache_max_size_mb',
+                        toInteger(event.currentTarget.value, draftSettings.response_cache_max_size_mb, 1),
+                      )
+                    }
+                  />
+                </FieldRow>
+              </div>
+              <FieldRow
+                label={t('settings.gateway.fields.responseCacheUsage')}
+                description={t('settings.gateway.hints.responseCacheUsage', {
+                  count: responseCacheStats?.entry_count ?? 0,
+                  size: ((responseCacheStats?.total_bytes ?? 0) / (1024 * 1024)).toFixed(1),
+                })}
+              >
+                <button
+                  type="button"
+                  className={styles.textButton}
+                  disabled={responseCacheBusy || !responseCacheStats?.entry_count}
+                  onClick={() => void handleClearResponseCache()}
+                >
+                  {t('settings.gateway.actions.clearResponseCache')}
+                </button>
+              </FieldRow>
+            </div>
+          </Section>
+
+          <Section icon={<FlaskConical size={15} aria-hidden="true" />} title={t('settings.gateway.sections.mockUpstream')}>
+            <div className={styles.fieldStack}>
+              <FieldRow
+                label={t('settings.gateway.fields.mockUpstreamScenario')}
+                help={t('settings.gateway.fieldHelp.mockUpstream')}
+              >
+                <div className={styles.inlineControlGroup}>
+                  <select
+                    className={styles.textInput}
+                    value={mockPreset}
+                    disabled={mockUpstream?.running}
+                    onChange={(event) => setMockPreset(event.currentTarget.value as MockUpstreamPreset)}
+                  >
+                    {(Object.keys(MOCK_UPSTREAM_PRESETS) as MockUpstreamPreset[]).map((preset) => (
+                      <option key={preset} value={preset}>
+                        {t(`settings.gateway.mockUpstream.presets.${preset}`)}
+                      </option>
+                    ))}
+                  </select>
+                  <button
+                    type="button"
+                    className={styles.textButton}
+                    disabled={mockBusy}
+                    onClick={() => void handleToggleMockUpstream()}
+                  >
+                    {mockUpstream?.running
+                      ? t('settings.gateway.actions.stopMockUpstream')
+                      : t('settings.gateway.actions.startMockUpstream')}
+                  </button>
+                </div>
+              </FieldRow>
+              {mockUpstream?.running && mockUpstream.base_url ? (
+                <FieldRow
+                  label={t('settings.gateway.fields.mockUpstreamBaseUrl')}
+                  description={t('settings.gateway.hints.mockUpstreamBaseUrl', {
+                    count: mockUpstream.request_count,
+                  })}
+                >
+                  <input
+                    className={styles.textInput}
+                    value={mockUpstream.base_url}
+                    readOnly
+                    onFocus={(event) => event.currentTarget.select()}
+                  />
+                </FieldRow>
+              ) : null}
+            </div>
+          </Section>
         </div>
       </div>
 
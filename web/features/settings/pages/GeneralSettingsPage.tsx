@@ -16,7 +16,8 @@ import {
   BulbOutlined,
   EyeOutlined,
   HolderOutlined,
-  DragOutlined
+  DragOutlined,
+  LockOutlined
 } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
 import {
@@ -34,11 +35,11 @@ import {
   arrayMove,
 } from '@dnd-kit/sortable';
 import { CSS } from '@dnd-kit/utilities';
-import { useAppStore, useSettingsStore } from '@/stores';
+import { useAppStore, useSettingsStore, toBackendS3 } from '@/stores';
 import { useThemeStore, type ThemeMode } from '@/stores/themeStore';
 import { languages, type Language } from '@/i18n';
 import i18n from '@/i18n';
-import { BackupSettingsModal, WebDAVRestoreModal } from '../components';
+import { BackupSettingsModal, SecretStoreSection, WebDAVRestoreModal } from '../components';
 import { platform } from '@tauri-apps/plugin-os';
 import {
   backupDatabase,
@@ -46,6 +47,12 @@ import {
   selectBackupFile,
   backupToWebDAV,
   restoreFromWebDAV,
+  backupToS3,
+  listS3Backups,
+  deleteS3Backup,
+  restoreFromS3,
+  isBackupPassphraseError,
+  BACKUP_PASSPHRASE_INVALID,
   type ProxyMode,
   type RestoreResult,
   openAppDataDir,
@@ -166,6 +173,7 @@ const GeneralSettingsPage: React.FC = () => {
     backupType,
     localBackupPath,
     webdav,
+    s3,
     lastBackupTime,
     setLastBackupTime,
     launchOnStartup,
@@ -405,6 +413,16 @@ const GeneralSettingsPage: React.FC = () => {
         await setLastBackupTime(now);
         message.success(t('settings.backupSettings.backupSuccess'));
         console.log('Backup uploaded to:', uploadUrl);
+      } else if (backupType === 's3') {
+        if (!s3.bucket) {
+          message.warning(t('settings.backupSettings.noS3Configured'));
+          return;
+        }
+        const location = await backupToS3(toBackendS3(s3), webdav.hostLabel);
+        const now = new Date().toISOString();
+        await setLastBackupTime(now);
+        message.success(t('settings.backupSettings.backupSuccess'));
+        console.log('Backup uploaded to:', location);
       } else {
         // Local backup
         if (!localBackupPath) {
@@ -437,6 +455,56 @@ const GeneralSettingsPage: React.FC = () => {
     }
   };
 
+  const promptBackupPassphrase = (wrongPassphrase: boolean) =>
+    new Promise<string | null>((resolve) => {
+      let passphrase = '';
+      Modal.confirm({
+        title: t('settings.backupSettings.encryption.restoreTitle'),
+        content: (
+          <div>
+            <p>
+              {wrongPassphrase
+                ? t('settings.backupSettings.encryption.wrongPassphrase')
+                : t('settings.backupSettings.encryption.restorePrompt')}
+            </p>
+            <Input.Password
+              autoFocus
+              onChange={(e) => {
+                passphrase = e.target.value;
+              }}
+            />
+          </div>
+        ),
+        okText: t('common.confirm'),
+        cancelText: t('common.cancel'),
+        onOk: () => resolve(passphrase || null),
+        onCancel: () => resolve(null),
+      });
+    });
+
+  /** Run a restore, prompting for the passphrase while the backup reports one is needed. */
+  const restoreWithPassphrasePrompt = async (
+    restore: (passphrase?: string) => Promise<RestoreResult>
+  ): Promise<RestoreResult | null> => {
+    let passphrase: string | undefined;
+    for (;;) {
+      try {
+        return await restore(passphrase);
+      } catch (error) {
+        if (!isBackupPassphraseError(error)) {
+          throw error;
+        }
+        const entered = await promptBackupPassphrase(
+          String(error).includes(BACKUP_PASSPHRASE_INVALID)
+        );
+        if (!entered) {
+          return null;
+        }
+        passphrase = entered;
+      }
+    }
+  };
+
   const handleRestore = async () => {
     if (backupType === 'webdav') {
       // Show WebDAV file selection modal
@@ -445,6 +513,12 @@ const GeneralSettingsPage: React.FC = () => {
         return;
       }
       setWebdavRestoreModalOpen(true);
+    } else if (backupType === 's3') {
+      if (!s3.bucket) {
+        message.warning(t('settings.backupSettings.noS3Configured'));
+        return;
+      }
+      setWebdavRestoreModalOpen(true);
     } else {
       // Local file selection
       setRestoreLoading(true);
@@ -467,9 +541,15 @@ const GeneralSettingsPage: React.FC = () => {
           cancelText: t('common.cancel'),
           onOk: async () => {
             try {
-              const restoreResult = await restoreDatabase(zipFilePath, {
-                skipCliCustomRoots: skipCliCustomRootsHolder.value,
-              });
+              const restoreResult = await restoreWithPassphrasePrompt((passphrase) =>
+                restoreDatabase(zipFilePath, {
+                  skipCliCustomRoots: skipCliCustomRootsHolder.value,
+                  passphrase,
+                })
+              );
+              if (!restoreResult) {
+                return;
+              }
               // 恢复成功后弹出重启对话框
               Modal.info({
                 title: t('settings.backupSettings.restoreSuccess'),
@@ -547,14 +627,22 @@ const GeneralSettingsPage: React.FC = () => {
       onOk: async () => {
         setRestoreLoading(true);
         try {
-          const restoreResult = await restoreFromWebDAV(
-            webdav.url,
-            webdav.username,
-            webdav.password,
-            webdav.remotePath,
-            selection.filename,
-            { skipCliCustomRoots: skipCliCustomRootsHolder.value }
+          const restoreOptions = { skipCliCustomRoots: skipCliCustomRootsHolder.value };
+          const restoreResult = await restoreWithPassphrasePrompt((passphrase) =>
+            backupType === 's3'
+              ? restoreFromS3(toBackendS3(s3), selection.filename, { ...restoreOptions, passphrase })
+              : restoreFromWebDAV(
+                  webdav.url,
+                  webdav.username,
+                  webdav.password,
+                  webdav.remotePath,
+                  selection.filename,
+                  { ...restoreOptions, passphrase }
+                )
           );
+          if (!restoreResult) {
+            return;
+          }
           // 恢复成功后弹出重启对话框
           Modal.info({
             title: t('settings.backupSettings.restoreSuccess'),
@@ -641,9 +729,20 @@ const GeneralSettingsPage: React.FC = () => {
   };
 
   // Backup settings table data
+  const backupPathTitle = {
+    local: t('settings.backupSettings.localPath'),
+    webdav: t('settings.webdav.url'),
+    s3: t('settings.s3.bucket'),
+  }[backupType];
+  const backupPath = {
+    local: localBackupPath,
+    webdav: webdav.url,
+    s3: s3.bucket,
+  }[backupType];
+
   const backupColumns = [
     { title: t('settings.backupSettings.storageType'), dataIndex: 'storageType', key: 'storageType' },
-    { title: backupType === 'local' ? t('settings.backupSettings.localPath') : t('settings.webdav.url'), dataIndex: 'path', key: 'path' },
+    { title: backupPathTitle, dataIndex: 'path', key: 'path' },
     ...(backupType === 'webdav' ? [{ title: t('settings.webdav.username'), dataIndex: 'username', key: 'username' }] : []),
     { title: t('settings.lastBackup'), dataIndex: 'lastBackup', key: 'lastBackup' },
   ];
@@ -651,8 +750,8 @@ const GeneralSettingsPage: React.FC = () => {
   const backupData = [
     {
       key: '1',
-      storageType: backupType === 'local' ? t('settings.backupSettings.local') : t('settings.backupSettings.webdav'),
-      path: backupType === 'local' ? (localBackupPath || t('common.notSet')) : (webdav.url || t('common.notSet')),
+      storageType: t(`settings.backupSettings.${backupType}`),
+      path: backupPath || t('common.notSet'),
       username: webdav.username || t('common.notSet'),
       lastBackup: formatBackupTime(lastBackupTime),
     },
@@ -1006,6 +1105,12 @@ const GeneralSettingsPage: React.FC = () => {
                 </Text>
               </div>
             )}
+
+            <Divider />
+
+            {/* Secret Store */}
+            <SectionTitle icon={<LockOutlined style={{ color: '#eb2f96' }} />} title={t('settings.cards.secretStore')} />
+            <SecretStoreSection />
           </Card>
           </div>
         </Col>
@@ -1022,6 +1127,10 @@ const GeneralSettingsPage: React.FC = () => {
         password={webdav.password}
         remotePath={webdav.remotePath}
         currentHostLabel={webdav.hostLabel}
+        listBackups={backupType === 's3' ? () => listS3Backups(toBackendS3(s3)) : undefined}
+        deleteBackup={
+          backupType === 's3' ? (filename) => deleteS3Backup(toBackendS3(s3), filename) : undefined
+        }
       />
 
       {/* Update Progress Modal */}
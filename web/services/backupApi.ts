@@ -7,6 +7,8 @@
 import { invoke } from '@tauri-apps/api/core';
 import { open } from '@tauri-apps/plugin-dialog';
 
+import type { S3Config } from './settingsApi';
+
 /**
  * Backup file info from WebDAV server
  */
@@ -28,19 +30,34 @@ export interface RestoreResult {
 
 export interface RestoreOptions {
   skipCliCustomRoots?: boolean;
+  /** Passphrase for encrypted backups; the stored one is tried when omitted */
+  passphrase?: string;
 }
 
+/** Error prefixes returned when an encrypted backup needs a (different) passphrase */
+export const BACKUP_PASSPHRASE_REQUIRED = 'BACKUP_PASSPHRASE_REQUIRED';
+export const BACKUP_PASSPHRASE_INVALID = 'BACKUP_PASSPHRASE_INVALID';
+
+/**
+ * Whether a restore error means the user should be prompted for a passphrase
+ */
+export const isBackupPassphraseError = (error: unknown): boolean => {
+  const message = String(error);
+  return message.includes(BACKUP_PASSPHRASE_REQUIRED) || message.includes(BACKUP_PASSPHRASE_INVALID);
+};
+
 /**
  * Backup database to a local zip file
  * @param backupPath - The directory to save the backup file
+ * @param passphrase - Encrypt the archive with this passphrase instead of the stored one
  * @returns The full path of the created backup file
  */
-export const backupDatabase = async (backupPath: string): Promise<string> => {
+export const backupDatabase = async (backupPath: string, passphrase?: string): Promise<string> => {
   if (!backupPath) {
     throw new Error('Backup path is not configured');
   }
 
-  const result = await invoke<string>('backup_database', { backupPath });
+  const result = await invoke<string>('backup_database', { backupPath, passphrase });
   return result;
 };
 
@@ -55,6 +72,7 @@ export const restoreDatabase = async (
   return await invoke<RestoreResult>('restore_database', {
     zipFilePath,
     skipCliCustomRoots: options?.skipCliCustomRoots ?? false,
+    passphrase: options?.passphrase,
   });
 };
 
@@ -76,7 +94,7 @@ export const selectBackupFile = async (): Promise<string | null> => {
     filters: [
       {
         name: 'Backup Files',
-        extensions: ['zip'],
+        extensions: ['zip', 'enc'],
       },
     ],
     title: 'Select Backup File',
@@ -93,7 +111,8 @@ export const backupToWebDAV = async (
   username: string,
   password: string,
   remotePath: string,
-  hostLabel: string
+  hostLabel: string,
+  passphrase?: string
 ): Promise<string> => {
   const result = await invoke<string>('backup_to_webdav', {
     url,
@@ -101,6 +120,7 @@ export const backupToWebDAV = async (
     password,
     remotePath,
     hostLabel,
+    passphrase,
   });
   return result;
 };
@@ -141,6 +161,7 @@ export const restoreFromWebDAV = async (
     remotePath,
     filename,
     skipCliCustomRoots: options?.skipCliCustomRoots ?? false,
+    passphrase: options?.passphrase,
   });
 };
 
@@ -179,3 +200,52 @@ export const deleteWebDAVBackup = async (
     filename,
   });
 };
+
+/**
+ * Backup database to S3-compatible object storage
+ * @returns The `s3://bucket/key` location of the uploaded backup
+ */
+export const backupToS3 = async (
+  config: S3Config,
+  hostLabel: string,
+  passphrase?: string
+): Promise<string> => {
+  return await invoke<string>('backup_to_s3', { config, hostLabel, passphrase });
+};
+
+/**
+ * List backup files under the configured S3 prefix, newest first
+ */
+export const listS3Backups = async (config: S3Config): Promise<BackupFileInfo[]> => {
+  return await invoke<BackupFileInfo[]>('list_s3_backups', { config });
+};
+
+/**
+ * Restore database from S3-compatible object storage
+ */
+export const restoreFromS3 = async (
+  config: S3Config,
+  filename: string,
+  options?: RestoreOptions
+): Promise<RestoreResult> => {
+  return await invoke<RestoreResult>('restore_from_s3', {
+    config,
+    filename,
+    skipCliCustomRoots: options?.skipCliCustomRoots ?? false,
+    passphrase: options?.passphrase,
+  });
+};
+
+/**
+ * Test S3 connection and credentials
+ */
+export const testS3Connection = async (config: S3Config): Promise<void> => {
+  await invoke('test_s3_connection', { config });
+};
+
+/**
+ * Delete a backup file from S3-compatible object storage
+ */
+export const deleteS3Backup = async (config: S3Config, filename: string): Promise<void> => {
+  await invoke('delete_s3_backup', { config, filename });
+};
@@ -8,6 +8,7 @@
 export * from './settingsApi';
 export * from './proxyGatewayApi';
 export * from './backupApi';
+export * from './secretStoreApi';
 export * from './opencodeApi';
 export * from '../features/coding/image/services/imageApi';
 export * from './globalPromptApi';
@@ -12,7 +12,8 @@ export type GatewayCliKey =
   | 'opencode'
   | 'claude_desktop';
 export type GatewayPricingModelSource = 'upstream' | 'requested';
-export type GatewayProxyMode = 'single' | 'failover';
+export type GatewayProxyMode = 'single' | 'failover' | 'load_balance';
+export type GatewayLoadBalanceStrategy = 'weighted' | 'least_latency' | 'least_error_rate';
 
 export interface AppProxyConfig {
   streaming_first_byte_timeout_secs?: number | null;
@@ -23,6 +24,7 @@ export interface AppProxyConfig {
   retry_interval_secs?: number | null;
   cost_multiplier?: string | null;
   pricing_model_source?: GatewayPricingModelSource | string | null;
+  response_cache_enabled?: boolean | null;
 }
 
 export interface GatewayPricingConfig {
@@ -37,6 +39,19 @@ export interface ModelPricing {
   output_cost_per_million: string;
   cache_read_cost_per_million: string;
   cache_creation_cost_per_million: string;
+  tiers: ModelPricingTier[];
+  reasoning_cost_per_million: string | null;
+  image_cost: string | null;
+  request_cost: string | null;
+  batch_discount_percent: string | null;
+}
+
+export interface ModelPricingTier {
+  above_input_tokens: number;
+  input_cost_per_million: string;
+  output_cost_per_million: string;
+  cache_read_cost_per_million?: string | null;
+  cache_creation_cost_per_million?: string | null;
 }
 
 export interface ModelPricingSyncResult {
@@ -53,6 +68,7 @@ export interface ProxyGatewaySettings {
   request_log_enabled: boolean;
   request_log_level: string;
   metrics_enabled: boolean;
+  metrics_endpoint_enabled: boolean;
   store_request_body: boolean;
   store_headers: boolean;
   store_response_body: boolean;
@@ -78,6 +94,13 @@ export interface ProxyGatewaySettings {
   model_base_cooldown_seconds: number;
   model_max_cooldown_seconds: number;
   half_open_success_required: number;
+  load_balance_strategy: GatewayLoadBalanceStrategy;
+  load_balance_sticky_ttl_secs: number;
+  fixture_capture_enabled: boolean;
+  /** Comma-separated substrings matched against requested model and path. */
+  fixture_capture_filter: string;
+  response_cache_ttl_secs: number;
+  response_cache_max_size_mb: number;
 }
 
 /** Compact default matching historical gateway retry status behavior. */
@@ -191,6 +214,8 @@ export interface GatewayRequestLogFilters {
    * terminal event to the client is no longer hidden as a clean success.
    */
   only_failed?: boolean | null;
+  /** Label of the gateway access token that issued the request. */
+  client_label?: string | null;
 }
 
 export interface GatewayPaginatedRequestLogs {
@@ -225,6 +250,8 @@ export interface GatewayRequestLogItem {
   first_token_ms: number | null;
   detail_file?: string | null;
   detail_offset?: number | null;
+  client_label?: string | null;
+  cache_hit?: boolean;
 }
 
 export interface GatewayUsageSummary {
@@ -236,6 +263,7 @@ export interface GatewayUsageSummary {
   total_cache_creation_tokens: number;
   success_rate: number;
   total_tokens: number;
+  cache_hit_requests?: number;
 }
 
 export interface GatewayUsageSummaryByCli {
@@ -265,6 +293,15 @@ export interface GatewayProviderStats {
   avg_latency_ms: number;
 }
 
+export interface GatewayClientStats {
+  client_label: string;
+  request_count: number;
+  total_tokens: number;
+  total_cost_usd: string;
+  success_rate: number;
+  avg_latency_ms: number;
+}
+
 export interface GatewayModelStats {
   cli_key: GatewayCliKey;
   model: string;
@@ -305,6 +342,8 @@ export interface GatewayRequestLogSummary {
   response_body_bytes: number;
   is_streaming: boolean;
   first_token_ms: number | null;
+  client_label?: string | null;
+  cache_hit?: boolean;
 }
 
 export interface GatewayProviderAttempt {
@@ -477,6 +516,109 @@ export const checkProxyGatewayPortAvailable = async (
   return invoke<ProxyGatewayPortCheckResult>('proxy_gateway_check_port_available', { input });
 };
 
+export interface GatewayAccessToken {
+  id: string;
+  label: string;
+  /** Last characters of the token, for recognising it in lists. */
+  token_hint: string;
+  cli_keys: GatewayCliKey[];
+  created_at: string;
+}
+
+export interface GatewayAccessTokenInput {
+  label: string;
+  cli_keys: GatewayCliKey[];
+}
+
+export interface GatewayAccessTokenCreated {
+  token: GatewayAccessToken;
+  /** Plaintext token; only returned once at creation. */
+  secret: string;
+}
+
+export type GatewayBudgetPeriod = 'daily' | 'weekly' | 'monthly';
+
+/** `warn` only notifies, `fallback` prefers cheaper providers, `reject` answers with a quota error. */
+export type GatewayBudgetAction = 'warn' | 'fallback' | 'reject';
+
+export interface GatewayBudgetRule {
+  id: string;
+  name: string;
+  enabled: boolean;
+  /** Empty scope fields match every CLI / provider / model. */
+  cli_key: GatewayCliKey | null;
+  provider_id: string | null;
+  model: string | null;
+  period: GatewayBudgetPeriod;
+  limit_usd: string | null;
+  limit_tokens: number | null;
+  action: GatewayBudgetAction;
+  created_at: string;
+}
+
+export interface GatewayBudgetRuleInput {
+  id?: string | null;
+  name: string;
+  enabled: boolean;
+  cli_key?: GatewayCliKey | null;
+  provider_id?: string | null;
+  model?: string | null;
+  period: GatewayBudgetPeriod;
+  limit_usd?: string | null;
+  limit_tokens?: number | null;
+  action: GatewayBudgetAction;
+}
+
+export interface GatewayBudgetStatus {
+  rule: GatewayBudgetRule;
+  /** Unix seconds of the local midnight the current period started at. */
+  period_start: number;
+  spent_usd: string;
+  spent_tokens: number;
+  exceeded: boolean;
+}
+
+/** Payload of the `gateway-budget-exceeded` event. */
+export interface GatewayBudgetExceededEvent {
+  cli_key: GatewayCliKey;
+  rule_id: string;
+  rule_name: string;
+  action: GatewayBudgetAction;
+  period: GatewayBudgetPeriod;
+  spent_usd: string;
+  limit_usd: string | null;
+  spent_tokens: number;
+  limit_tokens: number | null;
+}
+
+export const getProxyGatewayBudgetStatuses = async (): Promise<GatewayBudgetStatus[]> => {
+  return invoke<GatewayBudgetStatus[]>('proxy_gateway_budget_statuses');
+};
+
+export const saveProxyGatewayBudgetRule = async (
+  input: GatewayBudgetRuleInput
+): Promise<GatewayBudgetRule> => {
+  return invoke<GatewayBudgetRule>('proxy_gateway_save_budget_rule', { input });
+};
+
+export const deleteProxyGatewayBudgetRule = async (id: string): Promise<void> => {
+  await invoke('proxy_gateway_delete_budget_rule', { id });
+};
+
+export const listProxyGatewayAccessTokens = async (): Promise<GatewayAccessToken[]> => {
+  return invoke<GatewayAccessToken[]>('proxy_gateway_list_access_tokens');
+};
+
+export const createProxyGatewayAccessToken = async (
+  input: GatewayAccessTokenInput
+): Promise<GatewayAccessTokenCreated> => {
+  return invoke<GatewayAccessTokenCreated>('proxy_gateway_create_access_token', { input });
+};
+
+export const deleteProxyGatewayAccessToken = async (id: string): Promise<void> => {
+  await invoke('proxy_gateway_delete_access_token', { id });
+};
+
 export const getProxyGatewayCliStatuses = async (): Promise<GatewayCliTakeoverStatus[]> => {
   return invoke<GatewayCliTakeoverStatus[]>('proxy_gateway_cli_statuses');
 };
@@ -500,6 +642,12 @@ export const engageProxyGatewayFailover = async (
   return invoke<GatewayCliTakeoverStatus>('proxy_gateway_engage_failover', { cliKey });
 };
 
+export const engageProxyGatewayLoadBalance = async (
+  cliKey: GatewayCliKey
+): Promise<GatewayCliTakeoverStatus> => {
+  return invoke<GatewayCliTakeoverStatus>('proxy_gateway_engage_load_balance', { cliKey });
+};
+
 export const disengageProxyGatewayFailover = async (
   cliKey: GatewayCliKey
 ): Promise<GatewayCliTakeoverStatus> => {
@@ -550,6 +698,109 @@ export const exportProxyGatewayRequestLogDetail = async (
   return invoke<void>('proxy_gateway_export_request_log_detail', { traceId, exportPath });
 };
 
+export interface GatewayConversionRoute {
+  source: string;
+  target: string;
+}
+
+export interface GatewayFixtureCaptureSummary {
+  name: string;
+  manifest_path: string;
+  captured_at: string;
+  cli_key: GatewayCliKey | null;
+  path: string;
+  requested_model: string | null;
+  route: GatewayConversionRoute;
+  status_code: number;
+}
+
+export interface GatewayFixtureJsonDifference {
+  path: string;
+  expected: unknown;
+  actual: unknown;
+}
+
+export interface GatewayFixtureReplayReport {
+  name: string;
+  route: GatewayConversionRoute;
+  passed: boolean;
+  differences: GatewayFixtureJsonDifference[];
+  output: Record<string, unknown> | null;
+  error: string | null;
+}
+
+export const listProxyGatewayFixtureCaptures = async (): Promise<GatewayFixtureCaptureSummary[]> => {
+  return invoke<GatewayFixtureCaptureSummary[]>('proxy_gateway_list_fixture_captures');
+};
+
+export const replayProxyGatewayFixtureCapture = async (
+  manifestPath: string
+): Promise<GatewayFixtureReplayReport> => {
+  return invoke<GatewayFixtureReplayReport>('proxy_gateway_replay_fixture_capture', { manifestPath });
+};
+
+export type MockUpstreamBehavior =
+  | { kind: 'success' }
+  | { kind: 'delay'; delay_ms: number }
+  | { kind: 'first_byte_timeout'; hold_ms: number }
+  | { kind: 'mid_stream_error' }
+  | { kind: 'status'; status_code: number }
+  | { kind: 'invalid_thinking_signature' }
+  | { kind: 'empty_stream' };
+
+export interface MockUpstreamScript {
+  steps: MockUpstreamBehavior[];
+  fallback: MockUpstreamBehavior;
+  chunk_interval_ms?: number;
+  response_text?: string;
+}
+
+export interface MockUpstreamRequestRecord {
+  received_at: string;
+  method: string;
+  path: string;
+  protocol: string | null;
+  model: string | null;
+  stream: boolean;
+  behavior: MockUpstreamBehavior;
+}
+
+export interface MockUpstreamStatus {
+  running: boolean;
+  base_url: string | null;
+  request_count: number;
+  script: MockUpstreamScript | null;
+  recent_requests: MockUpstreamRequestRecord[];
+}
+
+export const getMockUpstreamStatus = async (): Promise<MockUpstreamStatus> => {
+  return invoke<MockUpstreamStatus>('proxy_gateway_mock_upstream_status');
+};
+
+export const startMockUpstream = async (
+  script?: MockUpstreamScript,
+  port?: number
+): Promise<MockUpstreamStatus> => {
+  return invoke<MockUpstreamStatus>('proxy_gateway_mock_upstream_start', { script, port });
+};
+
+export const stopMockUpstream = async (): Promise<MockUpstreamStatus> => {
+  return invoke<MockUpstreamStatus>('proxy_gateway_mock_upstream_stop');
+};
+
+export interface GatewayResponseCacheStats {
+  entry_count: number;
+  total_bytes: number;
+}
+
+export const getProxyGatewayResponseCacheStats = async (): Promise<GatewayResponseCacheStats> => {
+  return invoke<GatewayResponseCacheStats>('proxy_gateway_response_cache_stats');
+};
+
+export const clearProxyGatewayResponseCache = async (): Promise<GatewayResponseCacheStats> => {
+  return invoke<GatewayResponseCacheStats>('proxy_gateway_clear_response_cache');
+};
+
 export const getProxyGatewayUsageSummary = async (
   startDate?: number,
   endDate?: number,
@@ -608,6 +859,18 @@ export const getProxyGatewayModelStats = async (
   });
 };
 
+export const getProxyGatewayClientStats = async (
+  startDate?: number,
+  endDate?: number,
+  cliKey?: GatewayCliKey
+): Promise<GatewayClientStats[]> => {
+  return invoke<GatewayClientStats[]>('proxy_gateway_client_stats', {
+    startDate: startDate ?? null,
+    endDate: endDate ?? null,
+    cliKey: cliKey ?? null,
+  });
+};
+
 export const importProxyGatewaySessionUsage = async (
   input: GatewaySessionUsageImportInput
 ): Promise<GatewaySessionUsageImportResult> => {
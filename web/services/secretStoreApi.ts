@@ -0,0 +1,50 @@
+/**
+ * Secret Store API Service
+ *
+ * Manages the encrypted credential store (OS keyring, key file or passphrase).
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type SecretStoreKeySource = 'keyring' | 'file' | 'passphrase';
+
+export interface SecretStoreStatus {
+  enabled: boolean;
+  keySource?: SecretStoreKeySource | null;
+  unlocked: boolean;
+  secretCount: number;
+}
+
+export const getSecretStoreStatus = async (): Promise<SecretStoreStatus> => {
+  return await invoke<SecretStoreStatus>('secret_store_status');
+};
+
+/**
+ * Enable the store and encrypt existing credentials
+ * @returns Number of records that were sealed
+ */
+export const enableSecretStore = async (
+  keySource: SecretStoreKeySource,
+  passphrase?: string,
+): Promise<number> => {
+  return await invoke<number>('secret_store_enable', { keySource, passphrase });
+};
+
+/**
+ * Unlock a passphrase-protected store
+ * @returns Number of plaintext records sealed while unlocking (e.g. after a restore)
+ */
+export const unlockSecretStore = async (passphrase: string): Promise<number> => {
+  return await invoke<number>('secret_store_unlock', { passphrase });
+};
+
+export const lockSecretStore = async (): Promise<void> => {
+  await invoke('secret_store_lock');
+};
+
+/**
+ * Decrypt all credentials back into the database and remove the key material
+ */
+export const disableSecretStore = async (): Promise<number> => {
+  return await invoke<number>('secret_store_disable');
+};
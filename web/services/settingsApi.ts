@@ -103,8 +103,11 @@ export interface AppSettings {
   s3: S3Config;
   last_backup_time: string | null;
   backup_image_assets_enabled: boolean;
+  backup_session_trash_enabled: boolean;
   backup_cli_config_files_enabled: boolean;
   backup_custom_entries: BackupCustomEntry[];
+  backup_encryption_enabled: boolean;
+  backup_encryption_passphrase: string;
   backup_file_filter_rules: BackupFileFilterRule[];
   launch_on_startup: boolean;
   minimize_to_tray_on_close: boolean;
@@ -115,6 +118,7 @@ export interface AppSettings {
   auto_backup_enabled: boolean;
   auto_backup_interval_days: number;
   auto_backup_max_keep: number;
+  auto_backup_max_age_days: number;
   last_auto_backup_time: string | null;
   auto_check_update: boolean;
   visible_tabs: string[];
@@ -154,8 +158,11 @@ export const defaultSettings: AppSettings = {
   },
   last_backup_time: null,
   backup_image_assets_enabled: true,
+  backup_session_trash_enabled: false,
   backup_cli_config_files_enabled: true,
   backup_custom_entries: [],
+  backup_encryption_enabled: false,
+  backup_encryption_passphrase: '',
   backup_file_filter_rules: [],
   launch_on_startup: true,
   minimize_to_tray_on_close: true,
@@ -166,6 +173,7 @@ export const defaultSettings: AppSettings = {
   auto_backup_enabled: false,
   auto_backup_interval_days: 7,
   auto_backup_max_keep: 10,
+  auto_backup_max_age_days: 0,
   last_auto_backup_time: null,
   auto_check_update: true,
   visible_tabs: ['opencode', 'claudecode', 'claudedesktop', 'codex', 'grok', 'geminicli', 'openclaw', 'pi', 'oh_my_pi', 'hermes', 'dsh', 'gateway', 'image', 'ssh', 'wsl'],
@@ -192,6 +200,8 @@ export const getSettings = async (): Promise<AppSettings> => {
       backup_custom_entries: settings.backup_custom_entries ?? [],
       backup_file_filter_rules: settings.backup_file_filter_rules ?? [],
       backup_cli_config_files_enabled: settings.backup_cli_config_files_enabled ?? true,
+      backup_encryption_enabled: settings.backup_encryption_enabled ?? false,
+      backup_encryption_passphrase: settings.backup_encryption_passphrase ?? '',
       codex_preserve_official_auth_on_switch: settings.codex_preserve_official_auth_on_switch ?? false,
       codex_unified_session_history_enabled: settings.codex_unified_session_history_enabled ?? false,
       sidebar_hidden_by_page: normalizeSidebarHiddenByPage(
@@ -66,8 +66,18 @@ export const sshSetActiveConnection = async (connectionId: string): Promise<void
 /**
  * Test an SSH connection
  */
-export const sshTestConnection = async (connection: SSHConnection): Promise<SSHConnectionResult> => {
-  return await invoke<SSHConnectionResult>('ssh_test_connection', { connection });
+export const sshTestConnection = async (
+  connection: SSHConnection,
+  trustedFingerprint?: string,
+): Promise<SSHConnectionResult> => {
+  return await invoke<SSHConnectionResult>('ssh_test_connection', { connection, trustedFingerprint });
+};
+
+/**
+ * Remove pinned host keys for a host from the app's known_hosts store
+ */
+export const sshForgetHostKey = async (host: string, port: number): Promise<number> => {
+  return await invoke<number>('ssh_forget_host_key', { host, port });
 };
 
 /**
@@ -1,6 +1,12 @@
 export { useThemeStore, type ThemeMode } from './themeStore';
 export { useAppStore } from './appStore';
-export { useSettingsStore, type WebDAVConfigFE, type S3ConfigFE } from './settingsStore';
+export {
+  useSettingsStore,
+  toBackendS3,
+  type BackupType,
+  type WebDAVConfigFE,
+  type S3ConfigFE,
+} from './settingsStore';
 export type {
   SidebarPageKey,
   SidebarHiddenByPage,
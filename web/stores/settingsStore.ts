@@ -26,6 +26,8 @@ export interface WebDAVConfigFE {
   hostLabel: string;
 }
 
+export type BackupType = 'local' | 'webdav' | 's3';
+
 export interface S3ConfigFE {
   accessKey: string;
   secretKey: string;
@@ -43,14 +45,17 @@ interface SettingsState {
   isInitialized: boolean;
 
   // Backup settings
-  backupType: 'local' | 'webdav';
+  backupType: BackupType;
   localBackupPath: string;
   webdav: WebDAVConfigFE;
   lastBackupTime: string | null;
   backupImageAssetsEnabled: boolean;
+  backupSessionTrashEnabled: boolean;
   backupCliConfigFilesEnabled: boolean;
   backupCustomEntries: BackupCustomEntry[];
   backupFileFilterRules: BackupFileFilterRule[];
+  backupEncryptionEnabled: boolean;
+  backupEncryptionPassphrase: string;
 
   // S3 storage settings
   s3: S3ConfigFE;
@@ -68,6 +73,7 @@ interface SettingsState {
   autoBackupEnabled: boolean;
   autoBackupIntervalDays: number;
   autoBackupMaxKeep: number;
+  autoBackupMaxAgeDays: number;
   lastAutoBackupTime: string | null;
 
   // Update settings
@@ -97,13 +103,17 @@ interface SettingsState {
   // Actions
   initSettings: () => Promise<void>;
   setBackupSettings: (config: {
-    backupType: 'local' | 'webdav';
+    backupType: BackupType;
     localBackupPath?: string;
     webdav?: Partial<WebDAVConfigFE>;
+    s3?: Partial<S3ConfigFE>;
     backupImageAssetsEnabled?: boolean;
+    backupSessionTrashEnabled?: boolean;
     backupCliConfigFilesEnabled?: boolean;
     backupCustomEntries?: BackupCustomEntry[];
     backupFileFilterRules?: BackupFileFilterRule[];
+    backupEncryptionEnabled?: boolean;
+    backupEncryptionPassphrase?: string;
   }) => Promise<void>;
   setS3: (config: Partial<S3ConfigFE>) => Promise<void>;
   setLastBackupTime: (time: string | null) => Promise<void>;
@@ -116,6 +126,7 @@ interface SettingsState {
     enabled: boolean;
     intervalDays: number;
     maxKeep: number;
+    maxAgeDays: number;
   }) => Promise<void>;
   setLastAutoBackupTime: (time: string) => void;
   setAutoCheckUpdate: (enabled: boolean) => Promise<void>;
@@ -159,7 +170,7 @@ const toBackendWebDAV = (webdav: WebDAVConfigFE): WebDAVConfig => ({
   host_label: webdav.hostLabel,
 });
 
-const toBackendS3 = (s3: S3ConfigFE): S3Config => ({
+export const toBackendS3 = (s3: S3ConfigFE): S3Config => ({
   access_key: s3.accessKey,
   secret_key: s3.secretKey,
   bucket: s3.bucket,
@@ -198,9 +209,12 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
   s3: defaultS3,
   lastBackupTime: null,
   backupImageAssetsEnabled: true,
+  backupSessionTrashEnabled: false,
   backupCliConfigFilesEnabled: true,
   backupCustomEntries: [],
   backupFileFilterRules: [],
+  backupEncryptionEnabled: false,
+  backupEncryptionPassphrase: '',
   launchOnStartup: true,
   minimizeToTrayOnClose: true,
   startMinimized: false,
@@ -209,6 +223,7 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
   autoBackupEnabled: false,
   autoBackupIntervalDays: 7,
   autoBackupMaxKeep: 10,
+  autoBackupMaxAgeDays: 0,
   lastAutoBackupTime: null,
   autoCheckUpdate: true,
   visibleTabs: ['opencode', 'claudecode', 'claudedesktop', 'codex', 'grok', 'geminicli', 'openclaw', 'pi', 'oh_my_pi', 'hermes', 'dsh', 'gateway', 'image', 'ssh', 'wsl'],
@@ -228,15 +243,18 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
     try {
       const settings = await getSettings();
       set({
-        backupType: (settings.backup_type as 'local' | 'webdav') || 'local',
+        backupType: (settings.backup_type as BackupType) || 'local',
         localBackupPath: settings.local_backup_path,
         webdav: toFrontendWebDAV(settings.webdav),
         s3: toFrontendS3(settings.s3),
         lastBackupTime: settings.last_backup_time,
         backupImageAssetsEnabled: settings.backup_image_assets_enabled ?? true,
+        backupSessionTrashEnabled: settings.backup_session_trash_enabled ?? false,
         backupCliConfigFilesEnabled: settings.backup_cli_config_files_enabled ?? true,
         backupCustomEntries: settings.backup_custom_entries ?? [],
         backupFileFilterRules: settings.backup_file_filter_rules ?? [],
+        backupEncryptionEnabled: settings.backup_encryption_enabled ?? false,
+        backupEncryptionPassphrase: settings.backup_encryption_passphrase ?? '',
         launchOnStartup: settings.launch_on_startup,
         minimizeToTrayOnClose: settings.minimize_to_tray_on_close,
         startMinimized: settings.start_minimized ?? false,
@@ -245,6 +263,7 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
         autoBackupEnabled: settings.auto_backup_enabled ?? false,
         autoBackupIntervalDays: settings.auto_backup_interval_days ?? 7,
         autoBackupMaxKeep: settings.auto_backup_max_keep ?? 10,
+        autoBackupMaxAgeDays: settings.auto_backup_max_age_days ?? 0,
         lastAutoBackupTime: settings.last_auto_backup_time ?? null,
         autoCheckUpdate: settings.auto_check_update ?? true,
         visibleTabs: settings.visible_tabs ?? ['opencode', 'claudecode', 'claudedesktop', 'codex', 'grok', 'geminicli', 'openclaw', 'pi', 'oh_my_pi', 'hermes', 'dsh', 'gateway', 'image', 'ssh', 'wsl'],
@@ -271,22 +290,33 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
     const newWebdav = config.webdav
       ? { ...state.webdav, ...config.webdav }
       : state.webdav;
+    const newS3 = config.s3 ? { ...state.s3, ...config.s3 } : state.s3;
     const newLocalPath = config.localBackupPath ?? state.localBackupPath;
     const newBackupImageAssetsEnabled =
       config.backupImageAssetsEnabled ?? state.backupImageAssetsEnabled;
+    const newBackupSessionTrashEnabled =
+      config.backupSessionTrashEnabled ?? state.backupSessionTrashEnabled;
     const newBackupCliConfigFilesEnabled =
       config.backupCliConfigFilesEnabled ?? state.backupCliConfigFilesEnabled;
     const newBackupCustomEntries = config.backupCustomEntries ?? state.backupCustomEntries;
     const newBackupFileFilterRules = config.backupFileFilterRules ?? state.backupFileFilterRules;
+    const newBackupEncryptionEnabled =
+      config.backupEncryptionEnabled ?? state.backupEncryptionEnabled;
+    const newBackupEncryptionPassphrase =
+      config.backupEncryptionPassphrase ?? state.backupEncryptionPassphrase;
 
     set({
       backupType: newBackupType,
       localBackupPath: newLocalPath,
       webdav: newWebdav,
+      s3: newS3,
       backupImageAssetsEnabled: newBackupImageAssetsEnabled,
+      backupSessionTrashEnabled: newBackupSessionTrashEnabled,
       backupCliConfigFilesEnabled: newBackupCliConfigFilesEnabled,
       backupCustomEntries: newBackupCustomEntries,
       backupFileFilterRules: newBackupFileFilterRules,
+      backupEncryptionEnabled: newBackupEncryptionEnabled,
+      backupEncryptionPassphrase: newBackupEncryptionPassphrase,
     });
 
     // Get current settings and update
@@ -296,21 +326,29 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
       backup_type: newBackupType,
       local_backup_path: newLocalPath,
       webdav: toBackendWebDAV(newWebdav),
+      s3: toBackendS3(newS3),
       backup_image_assets_enabled: newBackupImageAssetsEnabled,
+      backup_session_trash_enabled: newBackupSessionTrashEnabled,
       backup_cli_config_files_enabled: newBackupCliConfigFilesEnabled,
       backup_custom_entries: newBackupCustomEntries,
       backup_file_filter_rules: newBackupFileFilterRules,
+      backup_encryption_enabled: newBackupEncryptionEnabled,
+      backup_encryption_passphrase: newBackupEncryptionPassphrase,
     };
     await saveSettings(newSettings);
     const savedSettings = await getSettings();
     set({
-      backupType: (savedSettings.backup_type as 'local' | 'webdav') || newBackupType,
+      backupType: (savedSettings.backup_type as BackupType) || newBackupType,
       localBackupPath: savedSettings.local_backup_path,
       webdav: toFrontendWebDAV(savedSettings.webdav),
+      s3: toFrontendS3(savedSettings.s3),
       backupImageAssetsEnabled: savedSettings.backup_image_assets_enabled ?? true,
+      backupSessionTrashEnabled: savedSettings.backup_session_trash_enabled ?? false,
       backupCliConfigFilesEnabled: savedSettings.backup_cli_config_files_enabled ?? true,
       backupCustomEntries: savedSettings.backup_custom_entries ?? [],
       backupFileFilterRules: savedSettings.backup_file_filter_rules ?? [],
+      backupEncryptionEnabled: savedSettings.backup_encryption_enabled ?? false,
+      backupEncryptionPassphrase: savedSettings.backup_encryption_passphrase ?? '',
     });
   },
 
@@ -409,6 +447,7 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
       autoBackupEnabled: config.enabled,
       autoBackupIntervalDays: config.intervalDays,
       autoBackupMaxKeep: config.maxKeep,
+      autoBackupMaxAgeDays: config.maxAgeDays,
     });
 
     const currentSettings = await getSettings();
@@ -417,6 +456,7 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
       auto_backup_enabled: config.enabled,
       auto_backup_interval_days: config.intervalDays,
       auto_backup_max_keep: config.maxKeep,
+      auto_backup_max_age_days: config.maxAgeDays,
     };
     await saveSettings(newSettings);
   },
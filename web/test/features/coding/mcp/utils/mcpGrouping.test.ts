@@ -24,6 +24,7 @@ function makeServer(overrides: Partial<McpServer>): McpServer {
     server_config: { command: 'node', args: [] },
     enabled_tools: [],
     sync_details: [],
+    probe_result: null,
     description: null,
     user_group: null,
     user_note: null,
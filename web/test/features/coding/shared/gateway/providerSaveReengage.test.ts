@@ -131,3 +131,44 @@ test('save provider reengage helper does not reengage when save fails after rest
 
   assert.deepEqual(calls, ['restore', 'status:direct', 'save']);
 });
+
+test('save provider reengage helper reengages load balance mode after single', async () => {
+  const calls: string[] = [];
+
+  const result = await saveProviderWithGatewayReengage({
+    gatewayMode: 'load_balance',
+    saveProvider: async () => {
+      calls.push('save');
+      return 'saved';
+    },
+    restoreDirect: async () => {
+      calls.push('restore');
+      return 'direct';
+    },
+    engageSingle: async () => {
+      calls.push('single');
+      return 'single';
+    },
+    engageFailover: async () => {
+      calls.push('failover');
+      return 'failover';
+    },
+    engageLoadBalance: async () => {
+      calls.push('load_balance');
+      return 'load_balance';
+    },
+    onGatewayStatusChange: (status) => {
+      calls.push(`status:${status}`);
+    },
+  });
+
+  assert.equal(result, 'saved');
+  assert.deepEqual(calls, [
+    'restore',
+    'status:direct',
+    'save',
+    'single',
+    'load_balance',
+    'status:load_balance',
+  ]);
+});
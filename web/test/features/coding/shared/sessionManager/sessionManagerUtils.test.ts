@@ -5,6 +5,7 @@ import {
   advanceVisibleContextId,
   resolveEffectiveSessionSourceMode,
   shouldShowVisibleFeedback,
+  supportsSessionTrash,
 } from '../../../../../features/coding/shared/sessionManager/utils.ts';
 
 test('advanceVisibleContextId only increments when page becomes hidden', () => {
@@ -43,3 +44,10 @@ test('resolveEffectiveSessionSourceMode keeps explicit source only when both sou
   assert.equal(resolveEffectiveSessionSourceMode('local', [{ source: 'wsl', distro: 'Ubuntu' }]), 'all');
   assert.equal(resolveEffectiveSessionSourceMode('wsl', []), 'all');
 });
+
+test('supportsSessionTrash excludes Claude Desktop and Hermes SQLite sessions', () => {
+  assert.equal(supportsSessionTrash('codex', '/home/me/.codex/sessions/a.jsonl'), true);
+  assert.equal(supportsSessionTrash('hermes', '/home/me/.hermes/sessions/a.json'), true);
+  assert.equal(supportsSessionTrash('hermes', 'sqlite:/home/me/.hermes/state.db#abc'), false);
+  assert.equal(supportsSessionTrash('claudedesktop', '/tmp/session.json'), false);
+});
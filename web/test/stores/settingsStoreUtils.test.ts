@@ -30,8 +30,11 @@ function createSettings(overrides: Partial<AppSettings> = {}): AppSettings {
     },
     last_backup_time: null,
     backup_image_assets_enabled: true,
+    backup_session_trash_enabled: false,
     backup_cli_config_files_enabled: true,
     backup_custom_entries: [],
+    backup_encryption_enabled: false,
+    backup_encryption_passphrase: '',
     backup_file_filter_rules: [],
     launch_on_startup: true,
     minimize_to_tray_on_close: true,
@@ -42,6 +45,7 @@ function createSettings(overrides: Partial<AppSettings> = {}): AppSettings {
     auto_backup_enabled: false,
     auto_backup_interval_days: 7,
     auto_backup_max_keep: 10,
+    auto_backup_max_age_days: 0,
     last_auto_backup_time: null,
     auto_check_update: true,
     visible_tabs: ['opencode', 'claudecode', 'codex', 'geminicli', 'openclaw', 'pi', 'oh_my_pi', 'gateway', 'image', 'ssh', 'wsl'],
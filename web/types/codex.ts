@@ -37,6 +37,10 @@ export interface GatewayProviderMeta {
   pricingModelSource?: 'upstream' | 'requested' | string;
   /** Provider-level custom request-header overrides applied by the gateway on upstream requests. */
   customHeaders?: CustomHeaderEntry[];
+  /** Azure OpenAI model-to-deployment names, used when `providerType` is Azure. */
+  azureDeployments?: Record<string, string>;
+  /** Azure OpenAI `api-version`; overrides the one in the base URL. */
+  azureApiVersion?: string;
 }
 
 export interface CodexAuthConfig extends Record<string, unknown> {
@@ -73,6 +73,22 @@ export interface SSHConnectionResult {
   connected: boolean;
   error?: string;
   serverInfo?: string;
+  hostKey?: HostKeyCheck | null;
+}
+
+/**
+ * Host key verification outcome
+ */
+export type HostKeyStatus = 'known' | 'learned' | 'unconfirmed' | 'changed' | 'revoked';
+
+export interface HostKeyCheck {
+  host: string;
+  port: number;
+  status: HostKeyStatus;
+  algorithm: string;
+  fingerprint: string;
+  source?: string | null;
+  knownFingerprint?: string | null;
 }
 
 /**